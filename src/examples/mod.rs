@@ -1,3 +1,7 @@
 //! This module contains an example implementation of the [TSP][tsp] and several
-//! [solvers][tsp::solvers].
+//! [solvers][tsp::solvers], as well as a [shift scheduling][shift_scheduling] example that uses
+//! durations and points in time inside the objective. The [toy] example is the step-by-step
+//! example of the [crate documentation][crate] with builders for every solver.
+pub mod shift_scheduling;
+pub mod toy;
 pub mod tsp;
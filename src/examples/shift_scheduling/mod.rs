@@ -0,0 +1,274 @@
+//! A small shift scheduling problem that demonstrates the time types of
+//! [`rapid_time`](https://docs.rs/rapid_time/) inside the [objective machinery][crate::objective]:
+//! Each [`Shift`] (given by its start and end [`DateTime`]) must be assigned to exactly one
+//! [`Employee`].
+//! * Between two shifts of the same employee, there must be a minimal rest time. Missing rest
+//!   time is measured as [`BaseValue::Duration`][crate::objective::BaseValue::Duration] and
+//!   minimized first (see [`objective`]).
+//! * Each employee has a maximal working time. The total overtime (also a
+//!   [`Duration`]) is minimized second.
+//! * The [`Roster`] assigns an employee to each shift. It is modified by the
+//!   [`MoveNeighborhood`][neighborhood::MoveNeighborhood] (reassign a single shift) and the
+//!   [`SwapNeighborhood`][neighborhood::SwapNeighborhood] (exchange the employees of two shifts).
+//! * The [`tabu_search`] module builds a
+//!   [`TabuSearchSolver`][crate::heuristics::tabu_search::TabuSearchSolver] for the problem.
+//! * Shifts and employees can be loaded from CSV files with the [`io`][crate::io] module, as
+//!   [`Shift`] and [`Employee`] implement [`FromCsvRecord`].
+pub mod neighborhood;
+pub mod objective;
+pub mod tabu_search;
+
+use std::sync::Arc;
+
+use rapid_time::{DateTime, Duration};
+
+use crate::io::{CsvError, CsvRecord, FromCsvRecord};
+
+/// A shift index (with respect to the shifts of the [`ShiftSchedulingInstance`], which are sorted
+/// by start time).
+pub type ShiftIdx = usize;
+
+/// An employee index.
+pub type EmployeeIdx = usize;
+
+/// A shift that has to be covered by one employee.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shift {
+    start: DateTime,
+    end: DateTime,
+}
+
+impl Shift {
+    /// Creates a new [`Shift`] from `start` to `end` (`end` must not be before `start`).
+    pub fn new(start: DateTime, end: DateTime) -> Shift {
+        assert!(start <= end, "The shift must not end before it starts.");
+        Shift { start, end }
+    }
+
+    /// Returns the start of the shift.
+    pub fn start(&self) -> DateTime {
+        self.start
+    }
+
+    /// Returns the end of the shift.
+    pub fn end(&self) -> DateTime {
+        self.end
+    }
+
+    /// Returns the length of the shift.
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Reads a shift from the columns `start` and `end`.
+impl FromCsvRecord for Shift {
+    fn from_csv_record(record: &CsvRecord) -> Result<Self, CsvError> {
+        Ok(Shift::new(
+            record.date_time("start")?,
+            record.date_time("end")?,
+        ))
+    }
+}
+
+/// An employee that can work up to `max_working_time` (without overtime).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Employee {
+    name: String,
+    max_working_time: Duration,
+}
+
+impl Employee {
+    /// Creates a new [`Employee`] with the given `name` and `max_working_time`.
+    pub fn new(name: &str, max_working_time: Duration) -> Employee {
+        Employee {
+            name: String::from(name),
+            max_working_time,
+        }
+    }
+
+    /// Returns the name of the employee.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the maximal working time of the employee.
+    pub fn max_working_time(&self) -> Duration {
+        self.max_working_time
+    }
+}
+
+/// Reads an employee from the columns `name` and `max_working_time` (in the format "hh:mm").
+impl FromCsvRecord for Employee {
+    fn from_csv_record(record: &CsvRecord) -> Result<Self, CsvError> {
+        Ok(Employee {
+            name: String::from(record.get("name")?),
+            max_working_time: record.duration("max_working_time")?,
+        })
+    }
+}
+
+/// A [`ShiftSchedulingInstance`] consists of the shifts, the employees and the minimal rest time
+/// between two shifts of the same employee.
+pub struct ShiftSchedulingInstance {
+    shifts: Vec<Shift>,
+    employees: Vec<Employee>,
+    minimal_rest_time: Duration,
+}
+
+impl ShiftSchedulingInstance {
+    /// Creates a new [`ShiftSchedulingInstance`]. The shifts are sorted by their start time.
+    pub fn new(
+        mut shifts: Vec<Shift>,
+        employees: Vec<Employee>,
+        minimal_rest_time: Duration,
+    ) -> ShiftSchedulingInstance {
+        assert!(
+            !employees.is_empty(),
+            "There must be at least one employee."
+        );
+        shifts.sort_by_key(|shift| (shift.start, shift.end));
+        ShiftSchedulingInstance {
+            shifts,
+            employees,
+            minimal_rest_time,
+        }
+    }
+
+    /// Returns the shift with the given index.
+    pub fn get_shift(&self, shift: ShiftIdx) -> &Shift {
+        &self.shifts[shift]
+    }
+
+    /// Returns the employee with the given index.
+    pub fn get_employee(&self, employee: EmployeeIdx) -> &Employee {
+        &self.employees[employee]
+    }
+
+    /// Returns the number of shifts.
+    pub fn get_number_of_shifts(&self) -> usize {
+        self.shifts.len()
+    }
+
+    /// Returns the number of employees.
+    pub fn get_number_of_employees(&self) -> usize {
+        self.employees.len()
+    }
+
+    /// Returns the minimal rest time between two shifts of the same employee.
+    pub fn get_minimal_rest_time(&self) -> Duration {
+        self.minimal_rest_time
+    }
+}
+
+/// Assigns an employee to each shift of a [`ShiftSchedulingInstance`].
+#[derive(Clone)]
+pub struct Roster {
+    assignment: Vec<EmployeeIdx>,
+    instance: Arc<ShiftSchedulingInstance>,
+}
+
+impl Roster {
+    /// Creates a new [`Roster`], where `assignment[shift]` is the employee of the shift.
+    pub fn new(assignment: Vec<EmployeeIdx>, instance: Arc<ShiftSchedulingInstance>) -> Roster {
+        assert_eq!(assignment.len(), instance.get_number_of_shifts());
+        assert!(assignment
+            .iter()
+            .all(|&employee| employee < instance.get_number_of_employees()));
+        Roster {
+            assignment,
+            instance,
+        }
+    }
+
+    /// Creates a [`Roster`] that assigns the shifts (sorted by start time) to the employees in
+    /// turn.
+    pub fn round_robin(instance: Arc<ShiftSchedulingInstance>) -> Roster {
+        let number_of_employees = instance.get_number_of_employees();
+        Roster::new(
+            (0..instance.get_number_of_shifts())
+                .map(|shift| shift % number_of_employees)
+                .collect(),
+            instance,
+        )
+    }
+
+    /// Returns the instance of the roster.
+    pub fn get_instance(&self) -> &ShiftSchedulingInstance {
+        &self.instance
+    }
+
+    /// Returns the employee of the given shift.
+    pub fn employee_of(&self, shift: ShiftIdx) -> EmployeeIdx {
+        self.assignment[shift]
+    }
+
+    /// Returns the shifts of the given employee sorted by start time.
+    pub fn shifts_of(&self, employee: EmployeeIdx) -> impl Iterator<Item = ShiftIdx> + '_ {
+        (0..self.assignment.len()).filter(move |&shift| self.assignment[shift] == employee)
+    }
+
+    /// Returns the total length of all shifts of the given employee.
+    pub fn working_time_of(&self, employee: EmployeeIdx) -> Duration {
+        self.shifts_of(employee)
+            .map(|shift| self.instance.get_shift(shift).duration())
+            .sum()
+    }
+
+    /// Returns the rest time that is missing between the consecutive shifts of the given employee.
+    /// If two shifts overlap, the overlap is missing in addition to the minimal rest time.
+    pub fn missing_rest_time_of(&self, employee: EmployeeIdx) -> Duration {
+        let minimal_rest_time = self.instance.get_minimal_rest_time();
+        let shifts: Vec<&Shift> = self
+            .shifts_of(employee)
+            .map(|shift| self.instance.get_shift(shift))
+            .collect();
+        shifts
+            .windows(2)
+            .map(|pair| {
+                let (previous, next) = (pair[0], pair[1]);
+                if next.start() < previous.end() {
+                    minimal_rest_time + (previous.end() - next.start())
+                } else {
+                    let rest_time = next.start() - previous.end();
+                    if rest_time < minimal_rest_time {
+                        minimal_rest_time - rest_time
+                    } else {
+                        Duration::ZERO
+                    }
+                }
+            })
+            .sum()
+    }
+
+    /// Returns the working time of the given employee that exceeds the maximal working time.
+    pub fn overtime_of(&self, employee: EmployeeIdx) -> Duration {
+        let working_time = self.working_time_of(employee);
+        let max_working_time = self.instance.get_employee(employee).max_working_time();
+        if working_time > max_working_time {
+            working_time - max_working_time
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Assigns the given shift to the given employee.
+    pub fn move_shift(&self, shift: ShiftIdx, employee: EmployeeIdx) -> Roster {
+        let mut assignment = self.assignment.clone();
+        assignment[shift] = employee;
+        Roster {
+            assignment,
+            instance: self.instance.clone(),
+        }
+    }
+
+    /// Exchanges the employees of the two given shifts.
+    pub fn swap_shifts(&self, shift: ShiftIdx, other_shift: ShiftIdx) -> Roster {
+        let mut assignment = self.assignment.clone();
+        assignment.swap(shift, other_shift);
+        Roster {
+            assignment,
+            instance: self.instance.clone(),
+        }
+    }
+}
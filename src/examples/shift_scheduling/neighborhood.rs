@@ -0,0 +1,61 @@
+//! This module contains the [`MoveNeighborhood`] and the [`SwapNeighborhood`] for the [`Roster`].
+//! For the local search, both can be chained by the [`MoveSwapNeighborhood`].
+use crate::heuristics::common::Neighborhood;
+
+use super::Roster;
+
+/// Given a [`Roster`], this [`Neighborhood`] generates all rosters that can be obtained by
+/// assigning a single shift to another employee.
+pub struct MoveNeighborhood;
+
+impl Neighborhood<Roster> for MoveNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        roster: &'a Roster,
+    ) -> Box<dyn Iterator<Item = Roster> + Send + Sync + 'a> {
+        let number_of_employees = roster.get_instance().get_number_of_employees();
+        Box::new(
+            (0..roster.get_instance().get_number_of_shifts()).flat_map(move |shift| {
+                (0..number_of_employees)
+                    .filter(move |&employee| employee != roster.employee_of(shift))
+                    .map(move |employee| roster.move_shift(shift, employee))
+            }),
+        )
+    }
+}
+
+/// Given a [`Roster`], this [`Neighborhood`] generates all rosters that can be obtained by
+/// exchanging the employees of two shifts (only shifts of different employees are swapped).
+pub struct SwapNeighborhood;
+
+impl Neighborhood<Roster> for SwapNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        roster: &'a Roster,
+    ) -> Box<dyn Iterator<Item = Roster> + Send + Sync + 'a> {
+        let number_of_shifts = roster.get_instance().get_number_of_shifts();
+        Box::new((0..number_of_shifts).flat_map(move |shift| {
+            (shift + 1..number_of_shifts)
+                .filter(move |&other_shift| {
+                    roster.employee_of(shift) != roster.employee_of(other_shift)
+                })
+                .map(move |other_shift| roster.swap_shifts(shift, other_shift))
+        }))
+    }
+}
+
+/// The [`MoveNeighborhood`] followed by the [`SwapNeighborhood`].
+pub struct MoveSwapNeighborhood;
+
+impl Neighborhood<Roster> for MoveSwapNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        roster: &'a Roster,
+    ) -> Box<dyn Iterator<Item = Roster> + Send + Sync + 'a> {
+        Box::new(
+            MoveNeighborhood
+                .neighbors_of(roster)
+                .chain(SwapNeighborhood.neighbors_of(roster)),
+        )
+    }
+}
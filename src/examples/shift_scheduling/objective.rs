@@ -0,0 +1,58 @@
+//! This module contains the [`Objective`] for the shift scheduling problem.
+//! Both levels are [`BaseValue::Duration`] values:
+//! 1. the total missing rest time between consecutive shifts of the same employee (hard
+//!    constraint),
+//! 2. the total overtime of all employees.
+use crate::objective::{BaseValue, Constraint, Indicator, Objective, Unit};
+
+use super::Roster;
+
+struct MissingRestTimeIndicator;
+
+impl Indicator<Roster> for MissingRestTimeIndicator {
+    fn evaluate(&self, roster: &Roster) -> BaseValue {
+        BaseValue::Duration(
+            (0..roster.get_instance().get_number_of_employees())
+                .map(|employee| roster.missing_rest_time_of(employee))
+                .sum(),
+        )
+    }
+
+    fn name(&self) -> String {
+        String::from("MissingRestTime")
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        Some(Unit::Seconds)
+    }
+}
+
+struct OvertimeIndicator;
+
+impl Indicator<Roster> for OvertimeIndicator {
+    fn evaluate(&self, roster: &Roster) -> BaseValue {
+        BaseValue::Duration(
+            (0..roster.get_instance().get_number_of_employees())
+                .map(|employee| roster.overtime_of(employee))
+                .sum(),
+        )
+    }
+
+    fn name(&self) -> String {
+        String::from("Overtime")
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        Some(Unit::Seconds)
+    }
+}
+
+/// Builds the [`Objective`] for the shift scheduling problem, which first minimizes the missing
+/// rest time (as a hard [`Constraint`]) and then the overtime.
+pub fn build_shift_scheduling_objective() -> Objective<Roster> {
+    Objective::builder()
+        .add_hard_constraint_level(vec![Constraint::new(Box::new(MissingRestTimeIndicator))])
+        .level()
+        .indicator(OvertimeIndicator)
+        .build()
+}
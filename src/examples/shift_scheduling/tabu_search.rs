@@ -0,0 +1,219 @@
+//! This module contains the implementation of the [`TabuSearchSolver`] for the shift scheduling
+//! problem, see the [build] function for details.
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    heuristics::tabu_search::{TabuNeighborhood, TabuSearchSolver},
+    objective::Objective,
+};
+
+use super::{objective::build_shift_scheduling_objective, EmployeeIdx, Roster, ShiftIdx};
+
+/// A tabu consists of a shift and an employee. Neighbors that would assign the shift to this
+/// employee are tabu.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ShiftTabu {
+    shift: ShiftIdx,
+    employee: EmployeeIdx,
+}
+
+impl ShiftTabu {
+    /// Checks if assigning the `shift` to the `employee` is tabu.
+    pub fn is_tabu(&self, shift: ShiftIdx, employee: EmployeeIdx) -> bool {
+        self.shift == shift && self.employee == employee
+    }
+
+    /// Creates the tabu that forbids to assign the `shift` back to its current employee in the
+    /// `roster`.
+    pub fn create_tabu(shift: ShiftIdx, roster: &Roster) -> ShiftTabu {
+        ShiftTabu {
+            shift,
+            employee: roster.employee_of(shift),
+        }
+    }
+}
+
+/// A [`TabuNeighborhood`] for the shift scheduling problem that consists of all moves (assign a
+/// shift to another employee) and all swaps (exchange the employees of two shifts).
+/// * A move or swap is tabu if one of its new assignments is tabu.
+/// * Each move is equipped with one tabu and each swap with two tabus, which forbid to assign
+///   the shifts back to their previous employees.
+pub struct MoveSwapTabuNeighborhood;
+
+impl TabuNeighborhood<Roster, ShiftTabu> for MoveSwapTabuNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        roster: &'a Roster,
+        tabu_list: &'a VecDeque<ShiftTabu>,
+    ) -> Box<dyn Iterator<Item = (Roster, Vec<ShiftTabu>)> + Send + Sync + 'a> {
+        let number_of_shifts = roster.get_instance().get_number_of_shifts();
+        let number_of_employees = roster.get_instance().get_number_of_employees();
+        let is_tabu = move |shift: ShiftIdx, employee: EmployeeIdx| {
+            tabu_list.iter().any(|tabu| tabu.is_tabu(shift, employee))
+        };
+
+        let moves = (0..number_of_shifts).flat_map(move |shift| {
+            (0..number_of_employees)
+                .filter(move |&employee| {
+                    employee != roster.employee_of(shift) && !is_tabu(shift, employee)
+                })
+                .map(move |employee| {
+                    (
+                        roster.move_shift(shift, employee),
+                        vec![ShiftTabu::create_tabu(shift, roster)],
+                    )
+                })
+        });
+        let swaps = (0..number_of_shifts).flat_map(move |shift| {
+            (shift + 1..number_of_shifts)
+                .filter(move |&other_shift| {
+                    roster.employee_of(shift) != roster.employee_of(other_shift)
+                        && !is_tabu(shift, roster.employee_of(other_shift))
+                        && !is_tabu(other_shift, roster.employee_of(shift))
+                })
+                .map(move |other_shift| {
+                    (
+                        roster.swap_shifts(shift, other_shift),
+                        vec![
+                            ShiftTabu::create_tabu(shift, roster),
+                            ShiftTabu::create_tabu(other_shift, roster),
+                        ],
+                    )
+                })
+        });
+        Box::new(moves.chain(swaps))
+    }
+
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        roster: &'a Roster,
+        tabu_list: &'a VecDeque<ShiftTabu>,
+    ) -> Box<dyn Iterator<Item = (Roster, Vec<ShiftTabu>, bool)> + Send + Sync + 'a> {
+        let number_of_shifts = roster.get_instance().get_number_of_shifts();
+        let number_of_employees = roster.get_instance().get_number_of_employees();
+        let is_tabu = move |shift: ShiftIdx, employee: EmployeeIdx| {
+            tabu_list.iter().any(|tabu| tabu.is_tabu(shift, employee))
+        };
+
+        let moves = (0..number_of_shifts).flat_map(move |shift| {
+            (0..number_of_employees)
+                .filter(move |&employee| employee != roster.employee_of(shift))
+                .map(move |employee| {
+                    (
+                        roster.move_shift(shift, employee),
+                        vec![ShiftTabu::create_tabu(shift, roster)],
+                        is_tabu(shift, employee),
+                    )
+                })
+        });
+        let swaps = (0..number_of_shifts).flat_map(move |shift| {
+            (shift + 1..number_of_shifts)
+                .filter(move |&other_shift| {
+                    roster.employee_of(shift) != roster.employee_of(other_shift)
+                })
+                .map(move |other_shift| {
+                    (
+                        roster.swap_shifts(shift, other_shift),
+                        vec![
+                            ShiftTabu::create_tabu(shift, roster),
+                            ShiftTabu::create_tabu(other_shift, roster),
+                        ],
+                        is_tabu(shift, roster.employee_of(other_shift))
+                            || is_tabu(other_shift, roster.employee_of(shift)),
+                    )
+                })
+        });
+        Box::new(moves.chain(swaps))
+    }
+}
+
+/// Builds a [`TabuSearchSolver`] for the shift scheduling problem.
+/// * The neighborhood is the [`MoveSwapTabuNeighborhood`].
+/// * The tabu list size is set to 10.
+/// * The iteration without global improvement limit is set to 50, i.e., the search stops if no
+///   global improvement is found for 50 iterations.
+/// * Takes the default [`TabuImprover`][crate::heuristics::tabu_search::tabu_improver::TabuImprover]
+///   [`TabuMinimizer`][crate::heuristics::tabu_search::tabu_improver::TabuMinimizer], which
+///   returns the best non-tabu neighbor without using parallelism.
+/// * No instance is needed, as each [`Roster`] refers to its
+///   [`ShiftSchedulingInstance`][super::ShiftSchedulingInstance].
+pub fn build() -> TabuSearchSolver<Roster, ShiftTabu> {
+    let objective: Arc<Objective<Roster>> = Arc::new(build_shift_scheduling_objective());
+    let neighborhood = Arc::new(MoveSwapTabuNeighborhood);
+    let tabu_list_size = 10;
+    let iteration_without_global_improvement_limit = 50;
+
+    TabuSearchSolver::initialize(
+        neighborhood,
+        objective,
+        tabu_list_size,
+        iteration_without_global_improvement_limit,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::{
+        examples::shift_scheduling::{
+            objective::build_shift_scheduling_objective, Employee, Roster, Shift,
+            ShiftSchedulingInstance,
+        },
+        heuristics::Solver,
+        io::parse_csv,
+        objective::BaseValue,
+    };
+    use rapid_time::Duration;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_tabu_search() {
+        let shifts: Vec<Shift> = parse_csv(
+            "start,end\n\
+             2024-03-04T06:00,2024-03-04T14:00\n\
+             2024-03-04T14:00,2024-03-04T22:00\n\
+             2024-03-04T22:00,2024-03-05T06:00\n\
+             2024-03-05T06:00,2024-03-05T14:00\n\
+             2024-03-05T14:00,2024-03-05T22:00\n\
+             2024-03-05T22:00,2024-03-06T06:00",
+        )
+        .unwrap();
+        let employees: Vec<Employee> = parse_csv(
+            "name,max_working_time\n\
+             Alice,16:00\n\
+             Bob,16:00\n\
+             Carol,16:00",
+        )
+        .unwrap();
+        let instance = Arc::new(ShiftSchedulingInstance::new(
+            shifts,
+            employees,
+            Duration::new("11:00"),
+        ));
+        let roster = Roster::new(vec![0; 6], instance.clone());
+        assert_eq!(
+            roster.missing_rest_time_of(0),
+            Duration::new("55:00") // 5 times 11 hours
+        );
+        assert_eq!(roster.overtime_of(0), Duration::new("32:00"));
+
+        let solver = build();
+
+        let final_roster = solver.solve(roster);
+
+        assert_eq!(
+            *final_roster.objective_value().as_vec(),
+            vec![
+                BaseValue::Duration(Duration::ZERO),
+                BaseValue::Duration(Duration::ZERO)
+            ]
+        );
+        assert!(build_shift_scheduling_objective().is_feasible(&final_roster));
+        for employee in 0..instance.get_number_of_employees() {
+            assert_eq!(
+                final_roster.solution().working_time_of(employee),
+                Duration::new("16:00")
+            );
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! The step-by-step example of the [crate documentation][crate] as real code, such that it can be
+//! run and modified directly: A [`Solution`] is a fixed-size vector of integers and we search for
+//! a permutation (each of the numbers 0 to n-1 appears exactly once) whose sum of squared
+//! differences between consecutive elements (cyclic) is minimal.
+//! * The [`objective`] minimizes the
+//!   [`PermutationViolation`][objective::PermutationViolation] first and the
+//!   [`SquaredDifference`][objective::SquaredDifference] second.
+//! * The [`neighborhood`] module contains the neighborhood of the tutorial (change an entry, then
+//!   swap two entries) for the sequential and the parallel solvers as well as tabu versions.
+//! * The [`operators`] module contains random operators (perturbation, destroy and repair,
+//!   crossover, mutation and random initial solutions) for the stochastic solvers.
+//! * The [`solvers`] module builds every solver of the [heuristics][crate::heuristics] module for
+//!   the problem.
+//!
+//! The problem is tiny and totally artificial, so the example is a playground to try out
+//! solvers and their parameters (e.g., in a test), not a benchmark.
+//! ```rust
+//! use rapid_solve::examples::toy::{solvers, Solution};
+//! use rapid_solve::heuristics::Solver;
+//!
+//! let solver = solvers::build_local_search();
+//! let local_minimum = solver.solve(Solution(vec![0; 10]));
+//! assert_eq!(local_minimum.solution().0, vec![1, 0, 2, 4, 5, 7, 9, 8, 6, 3]);
+//! ```
+pub mod neighborhood;
+pub mod objective;
+pub mod operators;
+pub mod solvers;
+
+/// The solution of the toy problem, a fixed-size vector of integers (a permutation of 0 to n-1
+/// if feasible).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Solution(pub Vec<i64>);
+
+impl Solution {
+    /// Returns a modified clone, in which the entry at `index` is replaced by `new_value`.
+    pub fn change_entry(&self, index: usize, new_value: i64) -> Self {
+        let mut new_values = self.0.clone();
+        new_values[index] = new_value;
+        Solution(new_values)
+    }
+
+    /// Returns a modified clone, in which the entries at `index1` and `index2` are swapped.
+    pub fn swap(&self, index1: usize, index2: usize) -> Self {
+        let mut new_values = self.0.clone();
+        new_values.swap(index1, index2);
+        Solution(new_values)
+    }
+
+    /// Returns `true` if each of the numbers 0 to n-1 appears exactly once.
+    pub fn is_permutation(&self) -> bool {
+        let mut values = self.0.clone();
+        values.sort_unstable();
+        values.into_iter().eq(0..self.0.len() as i64)
+    }
+}
@@ -0,0 +1,177 @@
+//! The neighborhoods of the toy problem: First, all changes of a single entry (to a number
+//! between 0 and n-1) and then all swaps of two entries.
+//! * In contrast to the step-by-step example of the [crate documentation][crate], the unchanged
+//!   solution is skipped (changing an entry to its value, swapping an entry with itself) and
+//!   each pair of entries is swapped only once. Otherwise, solvers that accept neighbors that are
+//!   not worse (e.g., threshold accepting) would stall on the unchanged solution. The local
+//!   minima of the local search are the same.
+//! * [`ChangeEntryThenSwapNeighborhood`] is the [`Neighborhood`] of the step-by-step example of
+//!   the [crate documentation][crate].
+//! * [`ParallelChangeEntryThenSwapNeighborhood`] is the same neighborhood as
+//!   [`ParallelNeighborhood`].
+//! * [`TabuChangeEntryThenSwapNeighborhood`] is the same neighborhood for the (parallel) tabu
+//!   search, where the tabus are the modified indices.
+use std::collections::VecDeque;
+
+use crate::heuristics::common::{Neighborhood, ParallelNeighborhood};
+use crate::heuristics::parallel_tabu_search::ParallelTabuNeighborhood;
+use crate::heuristics::tabu_search::TabuNeighborhood;
+use crate::parallel::{IntoParallelIterator, ParallelIterator};
+
+use super::Solution;
+
+/// All [`Solutions`][Solution] that can be obtained by changing one entry (to a number between 0
+/// and n-1), followed by all [`Solutions`][Solution] that can be obtained by swapping two
+/// entries.
+pub struct ChangeEntryThenSwapNeighborhood;
+
+impl Neighborhood<Solution> for ChangeEntryThenSwapNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        solution: &'a Solution,
+    ) -> Box<dyn Iterator<Item = Solution> + Send + Sync + 'a> {
+        let size = solution.0.len();
+        let change_entry = (0..size).flat_map(move |i| {
+            (0..size as i64)
+                .filter(move |&new_value| new_value != solution.0[i])
+                .map(move |new_value| solution.change_entry(i, new_value))
+        });
+        let swap = (0..size).flat_map(move |i| (i + 1..size).map(move |j| solution.swap(i, j)));
+        Box::new(change_entry.chain(swap))
+    }
+}
+
+/// The [`ChangeEntryThenSwapNeighborhood`] as [`ParallelNeighborhood`] (each index is a parallel
+/// task).
+pub struct ParallelChangeEntryThenSwapNeighborhood;
+
+impl ParallelNeighborhood<Solution> for ParallelChangeEntryThenSwapNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        solution: &'a Solution,
+    ) -> impl ParallelIterator<Item = Solution> + 'a {
+        let size = solution.0.len();
+        let change_entry = (0..size).into_par_iter().flat_map_iter(move |i| {
+            (0..size as i64)
+                .filter(move |&new_value| new_value != solution.0[i])
+                .map(move |new_value| solution.change_entry(i, new_value))
+        });
+        let swap = (0..size)
+            .into_par_iter()
+            .flat_map_iter(move |i| (i + 1..size).map(move |j| solution.swap(i, j)));
+        change_entry.chain(swap)
+    }
+}
+
+/// The [`ChangeEntryThenSwapNeighborhood`] for the tabu search: The tabus are the indices that
+/// were modified, and neighbors that modify a tabu index are skipped (or flagged as tabu for the
+/// aspiration criterion).
+pub struct TabuChangeEntryThenSwapNeighborhood;
+
+impl TabuNeighborhood<Solution, usize> for TabuChangeEntryThenSwapNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        solution: &'a Solution,
+        tabu_list: &'a VecDeque<usize>,
+    ) -> Box<dyn Iterator<Item = (Solution, Vec<usize>)> + Send + Sync + 'a> {
+        let size = solution.0.len();
+        let allowed = move |i: &usize| !tabu_list.contains(i);
+        let change_entry = (0..size).filter(allowed).flat_map(move |i| {
+            (0..size as i64)
+                .filter(move |&new_value| new_value != solution.0[i])
+                .map(move |new_value| (solution.change_entry(i, new_value), vec![i]))
+        });
+        let swap = (0..size).filter(allowed).flat_map(move |i| {
+            (i + 1..size)
+                .filter(allowed)
+                .map(move |j| (solution.swap(i, j), vec![i, j]))
+        });
+        Box::new(change_entry.chain(swap))
+    }
+
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        solution: &'a Solution,
+        tabu_list: &'a VecDeque<usize>,
+    ) -> Box<dyn Iterator<Item = (Solution, Vec<usize>, bool)> + Send + Sync + 'a> {
+        let size = solution.0.len();
+        let change_entry = (0..size).flat_map(move |i| {
+            (0..size as i64)
+                .filter(move |&new_value| new_value != solution.0[i])
+                .map(move |new_value| {
+                    (
+                        solution.change_entry(i, new_value),
+                        vec![i],
+                        tabu_list.contains(&i),
+                    )
+                })
+        });
+        let swap = (0..size).flat_map(move |i| {
+            (i + 1..size).map(move |j| {
+                (
+                    solution.swap(i, j),
+                    vec![i, j],
+                    tabu_list.contains(&i) || tabu_list.contains(&j),
+                )
+            })
+        });
+        Box::new(change_entry.chain(swap))
+    }
+}
+
+impl ParallelTabuNeighborhood<Solution, usize> for TabuChangeEntryThenSwapNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        solution: &'a Solution,
+        tabu_list: &'a VecDeque<usize>,
+    ) -> impl ParallelIterator<Item = (Solution, Vec<usize>)> + 'a {
+        let size = solution.0.len();
+        let allowed = move |i: &usize| !tabu_list.contains(i);
+        let change_entry = (0..size)
+            .into_par_iter()
+            .filter(allowed)
+            .flat_map_iter(move |i| {
+                (0..size as i64)
+                    .filter(move |&new_value| new_value != solution.0[i])
+                    .map(move |new_value| (solution.change_entry(i, new_value), vec![i]))
+            });
+        let swap = (0..size)
+            .into_par_iter()
+            .filter(allowed)
+            .flat_map_iter(move |i| {
+                (i + 1..size)
+                    .filter(allowed)
+                    .map(move |j| (solution.swap(i, j), vec![i, j]))
+            });
+        change_entry.chain(swap)
+    }
+
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        solution: &'a Solution,
+        tabu_list: &'a VecDeque<usize>,
+    ) -> impl ParallelIterator<Item = (Solution, Vec<usize>, bool)> + 'a {
+        let size = solution.0.len();
+        let change_entry = (0..size).into_par_iter().flat_map_iter(move |i| {
+            (0..size as i64)
+                .filter(move |&new_value| new_value != solution.0[i])
+                .map(move |new_value| {
+                    (
+                        solution.change_entry(i, new_value),
+                        vec![i],
+                        tabu_list.contains(&i),
+                    )
+                })
+        });
+        let swap = (0..size).into_par_iter().flat_map_iter(move |i| {
+            (i + 1..size).map(move |j| {
+                (
+                    solution.swap(i, j),
+                    vec![i, j],
+                    tabu_list.contains(&i) || tabu_list.contains(&j),
+                )
+            })
+        });
+        change_entry.chain(swap)
+    }
+}
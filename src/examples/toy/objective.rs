@@ -0,0 +1,68 @@
+//! The [`Objective`] of the toy problem: First, the [`PermutationViolation`] is minimized and
+//! only for tie-breaks the [`SquaredDifference`] is considered.
+use crate::objective::{BaseValue, Indicator, Objective};
+
+use super::Solution;
+
+/// The sum over the numbers 0 to n-1 of the absolute difference between the number of its
+/// occurrences and 1, i.e., zero if and only if the [`Solution`] is a permutation.
+pub struct PermutationViolation;
+
+impl Indicator<Solution> for PermutationViolation {
+    fn evaluate(&self, solution: &Solution) -> BaseValue {
+        let violation: i64 = (0..solution.0.len())
+            .map(|i| (solution.0.iter().filter(|&n| *n == i as i64).count() as i64 - 1).abs())
+            .sum();
+        BaseValue::Integer(violation)
+    }
+
+    fn name(&self) -> String {
+        String::from("PermutationViolation")
+    }
+}
+
+/// The sum of the squared differences between consecutive entries (including the last and the
+/// first entry).
+pub struct SquaredDifference;
+
+impl Indicator<Solution> for SquaredDifference {
+    fn evaluate(&self, solution: &Solution) -> BaseValue {
+        let squared_diff: i64 = (0..solution.0.len())
+            .map(|i| (solution.0[i] - solution.0[(i + 1) % solution.0.len()]).pow(2))
+            .sum();
+        BaseValue::Integer(squared_diff)
+    }
+
+    fn name(&self) -> String {
+        String::from("SquaredDifference")
+    }
+}
+
+/// Builds the hierarchical [`Objective`] with the [`PermutationViolation`] on the first and the
+/// [`SquaredDifference`] on the second level.
+pub fn build_objective() -> Objective<Solution> {
+    Objective::new_single_indicator_per_level(vec![
+        Box::new(PermutationViolation),
+        Box::new(SquaredDifference),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_objective() {
+        let objective = build_objective();
+        let evaluated = objective.evaluate(Solution(vec![0, 2, 4, 6, 8, 9, 7, 5, 3, 1]));
+        assert_eq!(
+            *evaluated.objective_value().as_vec(),
+            vec![BaseValue::Integer(0), BaseValue::Integer(34)]
+        );
+        let evaluated = objective.evaluate(Solution(vec![0; 4]));
+        assert_eq!(
+            *evaluated.objective_value().as_vec(),
+            vec![BaseValue::Integer(6), BaseValue::Integer(0)]
+        );
+    }
+}
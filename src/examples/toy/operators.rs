@@ -0,0 +1,147 @@
+//! Random operators of the toy problem for the stochastic solvers.
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+
+use crate::heuristics::common::{Crossover, InitialSolutionGenerator, Mutation};
+use crate::heuristics::iterated_local_search::Perturbation;
+use crate::heuristics::large_neighborhood_search::{DestroyOperator, RepairOperator};
+
+use super::Solution;
+
+/// The value of an entry that was removed by the [`RemoveSegment`] destroy operator.
+const REMOVED: i64 = -1;
+
+/// Swaps `number_of_swaps` random pairs of entries. Used as [`Perturbation`] of the iterated
+/// local search and as [`Mutation`] of the memetic algorithm and NSGA-II.
+pub struct RandomSwaps {
+    number_of_swaps: usize,
+}
+
+impl RandomSwaps {
+    /// Creates a new [`RandomSwaps`] operator that applies `number_of_swaps` random swaps.
+    pub fn new(number_of_swaps: usize) -> Self {
+        Self { number_of_swaps }
+    }
+}
+
+impl Perturbation<Solution> for RandomSwaps {
+    fn perturb(&self, solution: &Solution, rng: &mut dyn RngCore) -> Solution {
+        let size = solution.0.len();
+        (0..self.number_of_swaps).fold(solution.clone(), |solution, _| {
+            solution.swap(rng.gen_range(0..size), rng.gen_range(0..size))
+        })
+    }
+}
+
+impl Mutation<Solution> for RandomSwaps {
+    fn mutate(&self, solution: Solution, rng: &mut dyn RngCore) -> Solution {
+        self.perturb(&solution, rng)
+    }
+}
+
+/// Removes the entries of a random segment of `segment_length` consecutive (cyclic) positions
+/// (see [`DestroyOperator`]).
+pub struct RemoveSegment {
+    segment_length: usize,
+}
+
+impl RemoveSegment {
+    /// Creates a new [`RemoveSegment`] operator that removes `segment_length` entries.
+    pub fn new(segment_length: usize) -> Self {
+        Self { segment_length }
+    }
+}
+
+impl DestroyOperator<Solution> for RemoveSegment {
+    fn destroy(&self, solution: &Solution, rng: &mut dyn RngCore) -> Solution {
+        let size = solution.0.len();
+        let start = rng.gen_range(0..size);
+        (start..start + self.segment_length.min(size)).fold(solution.clone(), |solution, i| {
+            solution.change_entry(i % size, REMOVED)
+        })
+    }
+}
+
+/// Fills the removed entries from left to right, each with the missing number that is closest to
+/// its predecessor (see [`RepairOperator`]).
+pub struct GreedyRepair;
+
+impl RepairOperator<Solution> for GreedyRepair {
+    fn repair(&self, destroyed_solution: Solution, _rng: &mut dyn RngCore) -> Solution {
+        let size = destroyed_solution.0.len();
+        let mut missing: Vec<i64> = (0..size as i64)
+            .filter(|value| !destroyed_solution.0.contains(value))
+            .collect();
+        let mut values = destroyed_solution.0;
+        for i in 0..size {
+            if values[i] != REMOVED {
+                continue;
+            }
+            let predecessor = values[(i + size - 1) % size].max(0);
+            let closest = (0..missing.len())
+                .min_by_key(|&m| (missing[m] - predecessor).abs())
+                .map(|m| missing.swap_remove(m));
+            values[i] = closest.unwrap_or(0);
+        }
+        Solution(values)
+    }
+}
+
+/// Takes each entry from a random parent (see [`Crossover`]). The offspring is not necessarily a
+/// permutation, which is repaired by the local search of the memetic algorithm.
+pub struct UniformCrossover;
+
+impl Crossover<Solution> for UniformCrossover {
+    fn crossover(&self, parent1: &Solution, parent2: &Solution, rng: &mut dyn RngCore) -> Solution {
+        Solution(
+            parent1
+                .0
+                .iter()
+                .zip(parent2.0.iter())
+                .map(|(&value1, &value2)| if rng.gen_bool(0.5) { value1 } else { value2 })
+                .collect(),
+        )
+    }
+}
+
+/// Generates random permutations of the given size, seeded by the `start_index` (see
+/// [`InitialSolutionGenerator`]).
+pub struct RandomPermutations {
+    size: usize,
+}
+
+impl RandomPermutations {
+    /// Creates a new [`RandomPermutations`] generator for solutions with `size` entries.
+    pub fn new(size: usize) -> Self {
+        Self { size }
+    }
+}
+
+impl InitialSolutionGenerator<Solution> for RandomPermutations {
+    fn generate(&self, start_index: usize) -> Solution {
+        let mut values: Vec<i64> = (0..self.size as i64).collect();
+        values.shuffle(&mut StdRng::seed_from_u64(start_index as u64));
+        Solution(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destroy_and_repair() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let solution = RandomPermutations::new(10).generate(3);
+        assert!(solution.is_permutation());
+
+        let destroyed = RemoveSegment::new(4).destroy(&solution, &mut rng);
+        assert_eq!(destroyed.0.iter().filter(|&&v| v == REMOVED).count(), 4);
+        assert!(GreedyRepair.repair(destroyed, &mut rng).is_permutation());
+
+        assert!(RandomSwaps::new(3)
+            .perturb(&solution, &mut rng)
+            .is_permutation());
+    }
+}
@@ -0,0 +1,318 @@
+//! Builders for every solver of the [heuristics][crate::heuristics] module for the toy problem.
+//! All solvers use the [`Objective`][crate::objective::Objective] of the [objective][super::objective] module and (if they
+//! explore a neighborhood) the neighborhoods of the [neighborhood][super::neighborhood] module.
+//! The stochastic solvers use the random seed 42 to have reproducible results.
+//! ```ignore
+//! pub fn build_local_search() -> LocalSearchSolver<Solution> {
+//!     let objective = Arc::new(build_objective());
+//!     let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+//!     LocalSearchSolver::initialize(neighborhood, objective)
+//! }
+//! ```
+use std::sync::Arc;
+
+use crate::heuristics::common::{InitialSolutionGenerator, TerminationCriteria};
+use crate::heuristics::great_deluge::GreatDelugeSolver;
+use crate::heuristics::iterated_local_search::IteratedLocalSearchSolver;
+use crate::heuristics::large_neighborhood_search::LnsSolver;
+use crate::heuristics::local_search::LocalSearchSolver;
+use crate::heuristics::memetic::MemeticSolver;
+use crate::heuristics::nsga2::Nsga2Solver;
+use crate::heuristics::parallel_local_search::ParallelLocalSearchSolver;
+use crate::heuristics::parallel_simulated_annealing::ParallelSimulatedAnnealingSolver;
+use crate::heuristics::parallel_tabu_search::ParallelTabuSearchSolver;
+use crate::heuristics::pareto_local_search::ParetoLocalSearchSolver;
+use crate::heuristics::simulated_annealing::{
+    AcceptanceProbabilityFunction, NeighborSampling, SimulatedAnnealingSolver, Temperature,
+};
+use crate::heuristics::tabu_search::TabuSearchSolver;
+use crate::heuristics::threshold_accepting::ThresholdAcceptingSolver;
+use crate::objective::{BaseValue, Indicator, ObjectiveValue};
+
+use super::neighborhood::{
+    ChangeEntryThenSwapNeighborhood, ParallelChangeEntryThenSwapNeighborhood,
+    TabuChangeEntryThenSwapNeighborhood,
+};
+use super::objective::{build_objective, PermutationViolation, SquaredDifference};
+use super::operators::{
+    GreedyRepair, RandomPermutations, RandomSwaps, RemoveSegment, UniformCrossover,
+};
+use super::Solution;
+
+/// The number of entries of the solutions generated by the population-based solvers.
+const SIZE: usize = 10;
+
+/// Returns the [`AcceptanceProbabilityFunction`] of the simulated annealing solvers: A better
+/// neighbor is always accepted and a neighbor with a larger [`PermutationViolation`] is never
+/// accepted. Otherwise, the neighbor is accepted with probability e<sup>-∆f/T</sup>, where ∆f is
+/// the increase of the [`SquaredDifference`] (but at least 1, such that the search does not cycle
+/// between equally good solutions) and T is the current temperature.
+pub fn squared_difference_acceptance_probability_function() -> AcceptanceProbabilityFunction {
+    Box::new(
+        |current_objective_value: &ObjectiveValue,
+         new_objective_value: &ObjectiveValue,
+         temperature: Temperature| {
+            let current = current_objective_value.as_vec();
+            let new = new_objective_value.as_vec();
+            if new_objective_value < current_objective_value {
+                1.0
+            } else if new[0] > current[0] {
+                0.0
+            } else {
+                (-(new[1] - current[1]).to_f64().max(1.0) / temperature).exp()
+            }
+        },
+    )
+}
+
+/// Builds the [`LocalSearchSolver`] of the step-by-step example of the [crate
+/// documentation][crate].
+pub fn build_local_search() -> LocalSearchSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    LocalSearchSolver::initialize(neighborhood, objective)
+}
+
+/// Builds a [`ParallelLocalSearchSolver`] on the [`ParallelChangeEntryThenSwapNeighborhood`].
+pub fn build_parallel_local_search() -> ParallelLocalSearchSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ParallelChangeEntryThenSwapNeighborhood);
+    ParallelLocalSearchSolver::initialize(neighborhood, objective)
+}
+
+/// Builds a [`ThresholdAcceptingSolver`], which accepts neighbors without additional
+/// [`PermutationViolation`] whose [`SquaredDifference`] is larger by at most 10 (reduced by 0.9
+/// whenever a worse neighbor is accepted). As the unchanged solution is a neighbor that is always
+/// accepted, the search is limited to 200 iterations.
+pub fn build_threshold_accepting() -> ThresholdAcceptingSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    let initial_threshold =
+        ObjectiveValue::new(vec![BaseValue::Integer(0), BaseValue::Integer(10)]);
+    ThresholdAcceptingSolver::with_options(
+        neighborhood,
+        objective,
+        initial_threshold,
+        0.9,
+        None,
+        Some(TerminationCriteria::iteration_limit(200)),
+    )
+}
+
+/// Builds a [`GreatDelugeSolver`] whose water level starts at a [`SquaredDifference`] of 100
+/// (without [`PermutationViolation`]) and sinks by 1 per iteration (at most 200 iterations).
+/// The water level is below the objective value of any solution that is not a permutation, such
+/// that the search cannot stall on the unchanged neighbors of the neighborhood.
+pub fn build_great_deluge() -> GreatDelugeSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    let rain_speed = ObjectiveValue::new(vec![BaseValue::Integer(0), BaseValue::Integer(1)]);
+    let initial_water_level =
+        ObjectiveValue::new(vec![BaseValue::Integer(0), BaseValue::Integer(100)]);
+    GreatDelugeSolver::with_options(
+        neighborhood,
+        objective,
+        rain_speed,
+        Some(initial_water_level),
+        None,
+        Some(TerminationCriteria::iteration_limit(200)),
+    )
+}
+
+/// Builds a [`SimulatedAnnealingSolver`] with initial temperature 10 and cooling factor 0.9 (see
+/// [`squared_difference_acceptance_probability_function`]), which considers the neighbors in a
+/// random order and is limited to 500 iterations.
+pub fn build_simulated_annealing() -> SimulatedAnnealingSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    SimulatedAnnealingSolver::with_options(
+        neighborhood,
+        objective,
+        10.0,
+        0.9,
+        squared_difference_acceptance_probability_function(),
+        Some(42), // random_seed
+        None,
+        Some(TerminationCriteria::iteration_limit(500)),
+    )
+    .with_neighbor_sampling(NeighborSampling::Shuffled)
+}
+
+/// Builds a [`ParallelSimulatedAnnealingSolver`] with three chains (initial temperatures 5, 10
+/// and 20), which continue from the best solution every 20 iterations (at most 500 iterations
+/// per chain).
+pub fn build_parallel_simulated_annealing() -> ParallelSimulatedAnnealingSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    ParallelSimulatedAnnealingSolver::with_options(
+        neighborhood,
+        objective,
+        vec![5.0, 10.0, 20.0],
+        0.9,
+        squared_difference_acceptance_probability_function(),
+        20,
+        Some(42), // random_seed
+        None,
+        Some(TerminationCriteria::iteration_limit(500)),
+    )
+}
+
+/// Builds a [`TabuSearchSolver`] whose tabus are the last 3 modified indices (stops after 20
+/// iterations without global improvement).
+pub fn build_tabu_search() -> TabuSearchSolver<Solution, usize> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(TabuChangeEntryThenSwapNeighborhood);
+    TabuSearchSolver::initialize(neighborhood, objective, 3, 20)
+}
+
+/// Builds a [`ParallelTabuSearchSolver`] with the same parameters as [`build_tabu_search`].
+pub fn build_parallel_tabu_search() -> ParallelTabuSearchSolver<Solution, usize> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(TabuChangeEntryThenSwapNeighborhood);
+    ParallelTabuSearchSolver::initialize(neighborhood, objective, 3, 20)
+}
+
+/// Builds an [`IteratedLocalSearchSolver`] that perturbs the local minimum by two
+/// [`RandomSwaps`] (20 iterations).
+pub fn build_iterated_local_search() -> IteratedLocalSearchSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    IteratedLocalSearchSolver::initialize(
+        neighborhood,
+        Box::new(RandomSwaps::new(2)),
+        objective,
+        20,
+        Some(42), // random_seed
+    )
+}
+
+/// Builds an [`LnsSolver`] that removes a segment of 4 entries and refills it by the
+/// [`GreedyRepair`] (100 iterations).
+pub fn build_large_neighborhood_search() -> LnsSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    LnsSolver::initialize(
+        vec![Box::new(RemoveSegment::new(4))],
+        vec![Box::new(GreedyRepair)],
+        objective,
+        100,
+        Some(42), // random_seed
+    )
+}
+
+/// Builds a [`MemeticSolver`] with a population of 6 random permutations (of size 10), the
+/// [`UniformCrossover`] and two [`RandomSwaps`] as mutation (10 iterations).
+pub fn build_memetic() -> MemeticSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    MemeticSolver::initialize(
+        neighborhood,
+        Box::new(UniformCrossover),
+        Box::new(RandomSwaps::new(2)),
+        Box::new(RandomPermutations::new(SIZE)),
+        objective,
+        6,
+        10,
+        Some(42), // random_seed
+    )
+}
+
+/// Builds a [`ParetoLocalSearchSolver`], which approximates the Pareto front of both levels of
+/// the [`Objective`][crate::objective::Objective].
+pub fn build_pareto_local_search() -> ParetoLocalSearchSolver<Solution> {
+    let objective = Arc::new(build_objective());
+    let neighborhood = Arc::new(ChangeEntryThenSwapNeighborhood);
+    ParetoLocalSearchSolver::initialize(neighborhood, objective)
+}
+
+/// Builds an [`Nsga2Solver`] on the [`PermutationViolation`] and the [`SquaredDifference`] with
+/// a population of 20 solutions (of size 10) and 20 generations.
+pub fn build_nsga2() -> Nsga2Solver<Solution> {
+    let indicators: Vec<Arc<dyn Indicator<Solution>>> =
+        vec![Arc::new(PermutationViolation), Arc::new(SquaredDifference)];
+    Nsga2Solver::initialize(
+        indicators,
+        Box::new(UniformCrossover),
+        Box::new(RandomSwaps::new(2)),
+        Box::new(RandomPermutations::new(SIZE)),
+        20,
+        20,
+        Some(42), // random_seed
+    )
+}
+
+/// Returns a random permutation of size 10 as initial solution for the solvers (the same for
+/// each `start_index`).
+pub fn initial_solution(start_index: usize) -> Solution {
+    RandomPermutations::new(SIZE).generate(start_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::Solver;
+    use crate::objective::EvaluatedSolution;
+
+    fn assert_feasible(name: &str, solution: EvaluatedSolution<Solution>) {
+        assert!(
+            solution.solution().is_permutation(),
+            "{} returned {:?}",
+            name,
+            solution.solution()
+        );
+    }
+
+    #[test]
+    fn test_local_search_as_in_the_tutorial() {
+        let local_minimum = build_local_search().solve(Solution(vec![0; 10]));
+        assert_eq!(
+            *local_minimum.objective_value().as_vec(),
+            vec![BaseValue::Integer(0), BaseValue::Integer(36)]
+        );
+        assert_eq!(
+            local_minimum.solution().0,
+            vec![1, 0, 2, 4, 5, 7, 9, 8, 6, 3]
+        );
+    }
+
+    #[test]
+    fn test_all_solvers() {
+        let solvers: Vec<(&str, Box<dyn Solver<Solution>>)> = vec![
+            ("local search", Box::new(build_local_search())),
+            (
+                "parallel local search",
+                Box::new(build_parallel_local_search()),
+            ),
+            ("threshold accepting", Box::new(build_threshold_accepting())),
+            ("great deluge", Box::new(build_great_deluge())),
+            ("simulated annealing", Box::new(build_simulated_annealing())),
+            (
+                "parallel simulated annealing",
+                Box::new(build_parallel_simulated_annealing()),
+            ),
+            ("tabu search", Box::new(build_tabu_search())),
+            (
+                "parallel tabu search",
+                Box::new(build_parallel_tabu_search()),
+            ),
+            (
+                "iterated local search",
+                Box::new(build_iterated_local_search()),
+            ),
+            (
+                "large neighborhood search",
+                Box::new(build_large_neighborhood_search()),
+            ),
+            ("memetic", Box::new(build_memetic())),
+            ("pareto local search", Box::new(build_pareto_local_search())),
+        ];
+        for (name, solver) in solvers {
+            assert_feasible(name, solver.solve(Solution(vec![0; 10])));
+        }
+
+        let pareto_front = build_nsga2().solve(initial_solution(0));
+        assert!(pareto_front
+            .points()
+            .iter()
+            .any(|point| point.solution.is_permutation()));
+    }
+}
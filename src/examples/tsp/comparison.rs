@@ -0,0 +1,367 @@
+//! This module runs all TSP [solvers][super::solvers] on the same instance with the same time
+//! budget and ranks them by the length of the best tour (see [`compare_solvers`]), e.g., by
+//! `rapid_solve_tsp_example compare <tsplib_file> [<seconds>]`.
+//! * The solvers are configured as by the `build` functions of their modules, but with the
+//!   common time limit. Solvers with further limits (e.g., the iteration limit of the
+//!   [`IteratedLocalSearchSolver`] or the stopping criterion of a local search) may stop earlier.
+//! * Record-to-record travel and the random-key genetic algorithm are not compared, as they are
+//!   variants of the threshold accepting and the memetic solver.
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::neighborhood::{ParallelThreeOptNeighborhood, ThreeOptNeighborhood};
+use super::objective::{build_tsp_delta_objective, build_tsp_objective};
+use super::solvers::iterated_local_search::RandomThreeOptPerturbation;
+use super::solvers::memetic::{GreedyRandomizedTours, OrderCrossover};
+use super::solvers::parallel_tabu_search::ParallelThreeOptTabuNeighborhood;
+use super::solvers::simulated_annealing::distance_acceptance_probability_function;
+use super::solvers::tabu_search::ThreeOptTabuNeighborhood;
+use super::tsp_instance::TspInstance;
+use super::tsp_tour::TspTour;
+use super::tsp_tour_with_info::neighborhood::RotatedThreeOptNeighborhood;
+use super::tsp_tour_with_info::objective::build_objective_for_tsp_tour_with_info;
+use super::tsp_tour_with_info::TspTourWithInfo;
+use super::Distance;
+use crate::heuristics::common::{FunctionBetweenSteps, TerminationCriteria};
+use crate::heuristics::iterated_local_search::IteratedLocalSearchSolver;
+use crate::heuristics::local_search::local_improver::{
+    BatchParallelMinimizer, TakeFirst, TakeFirstRecursion,
+};
+use crate::heuristics::local_search::LocalSearchSolver;
+use crate::heuristics::memetic::{MemeticSolver, OffspringImprover};
+use crate::heuristics::parallel_local_search::ParallelLocalSearchSolver;
+use crate::heuristics::parallel_simulated_annealing::ParallelSimulatedAnnealingSolver;
+use crate::heuristics::parallel_tabu_search::ParallelTabuSearchSolver;
+use crate::heuristics::simulated_annealing::SimulatedAnnealingSolver;
+use crate::heuristics::tabu_search::tabu_improver::{DeltaTabuMinimizer, MemoizedTabuMinimizer};
+use crate::heuristics::tabu_search::TabuSearchSolver;
+use crate::heuristics::threshold_accepting::ThresholdAcceptingSolver;
+use crate::heuristics::Solver;
+use crate::objective::{BaseValue, ObjectiveValue};
+
+/// The result of one solver in a comparison (see [`compare_solvers`]).
+pub struct ComparisonEntry {
+    /// The name of the solver (as accepted by the `rapid_solve_tsp_example` binary).
+    pub solver_name: &'static str,
+    /// The total distance of the best tour.
+    pub best_distance: Distance,
+    /// The time from the start of the solver until the best tour was found.
+    pub time_to_best: Duration,
+    /// The number of iterations. For solvers that only report new best solutions (e.g., the
+    /// [`MemeticSolver`]), this is the iteration of the last improvement.
+    pub iterations: u32,
+    /// The wall-clock time of the run.
+    pub elapsed: Duration,
+}
+
+/// The progress of a run as observed by the function between steps.
+struct Progress {
+    best_objective_value: Option<ObjectiveValue>,
+    time_to_best: Duration,
+    iterations: u32,
+}
+
+/// Runs all solvers (one after another) starting from the nearest-neighbor tour of the
+/// `tsp_instance`, each with the `time_budget` as time limit, and returns their
+/// [`ComparisonEntry`]s ranked by the best distance (ties are broken by the time to best).
+pub fn compare_solvers(
+    tsp_instance: Arc<TspInstance>,
+    time_budget: Duration,
+) -> Vec<ComparisonEntry> {
+    let initial_tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+    let tour_objective = Arc::new(build_tsp_objective());
+    let tour_with_info_objective = Arc::new(build_objective_for_tsp_tour_with_info());
+    let time_limit = Some(time_budget);
+    let average_distance = tsp_instance.get_average_distance();
+    let tsp = || tsp_instance.clone();
+    let three_opt = || Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+
+    let mut entries = vec![
+        run("basic_local_search", initial_tour.clone(), |steps| {
+            LocalSearchSolver::with_options(
+                three_opt(),
+                tour_objective.clone(),
+                None,
+                Some(steps),
+                time_limit.map(TerminationCriteria::time_limit),
+            )
+        }),
+        run("take_first_local_search", initial_tour.clone(), |steps| {
+            LocalSearchSolver::with_options(
+                three_opt(),
+                tour_objective.clone(),
+                Some(Box::new(TakeFirstRecursion::new(
+                    2,
+                    5,
+                    three_opt(),
+                    tour_objective.clone(),
+                ))),
+                Some(steps),
+                time_limit.map(TerminationCriteria::time_limit),
+            )
+        }),
+        run(
+            "batch_parallel_local_search",
+            initial_tour.clone(),
+            |steps| {
+                LocalSearchSolver::with_options(
+                    three_opt(),
+                    tour_objective.clone(),
+                    Some(Box::new(BatchParallelMinimizer::new(
+                        three_opt(),
+                        tour_objective.clone(),
+                        1000,
+                    ))),
+                    Some(steps),
+                    time_limit.map(TerminationCriteria::time_limit),
+                )
+            },
+        ),
+        run("parallel_local_search", initial_tour.clone(), |steps| {
+            ParallelLocalSearchSolver::with_options(
+                Arc::new(ParallelThreeOptNeighborhood::new(tsp())),
+                tour_objective.clone(),
+                None,
+                Some(steps),
+                time_limit.map(TerminationCriteria::time_limit),
+            )
+        }),
+        run("iterated_local_search", initial_tour.clone(), |steps| {
+            IteratedLocalSearchSolver::with_options(
+                three_opt(),
+                Box::new(RandomThreeOptPerturbation::new(3)),
+                tour_objective.clone(),
+                Some(Box::new(TakeFirst::new(
+                    three_opt(),
+                    tour_objective.clone(),
+                ))),
+                None,
+                Some(42),
+                Some(steps),
+                Some(
+                    TerminationCriteria::time_limit(time_budget)
+                        .or(TerminationCriteria::iteration_limit(5)),
+                ),
+            )
+        }),
+        run("memetic", initial_tour.clone(), |steps| {
+            MemeticSolver::with_options(
+                Box::new(OrderCrossover::new(tsp())),
+                Box::new(GreedyRandomizedTours::new(tsp(), 0.3)),
+                tour_objective.clone(),
+                6,
+                Some(Box::new(RandomThreeOptPerturbation::new(1))),
+                Some(OffspringImprover::Sequential(Box::new(TakeFirst::new(
+                    three_opt(),
+                    tour_objective.clone(),
+                )))),
+                None,
+                Some(0.5),
+                Some(42),
+                Some(steps),
+                Some(
+                    TerminationCriteria::time_limit(time_budget)
+                        .or(TerminationCriteria::iteration_limit(10)),
+                ),
+            )
+        }),
+        run(
+            "threshold_accepting",
+            TspTourWithInfo::new(initial_tour.clone(), 0),
+            |steps| {
+                ThresholdAcceptingSolver::with_options(
+                    Arc::new(RotatedThreeOptNeighborhood::new(tsp())),
+                    tour_with_info_objective.clone(),
+                    ObjectiveValue::new(vec![BaseValue::Float(average_distance)]),
+                    0.9,
+                    Some(steps),
+                    time_limit.map(TerminationCriteria::time_limit),
+                )
+            },
+        ),
+        run(
+            "simulated_annealing",
+            TspTourWithInfo::new(initial_tour.clone(), 0),
+            |steps| {
+                SimulatedAnnealingSolver::with_options(
+                    Arc::new(RotatedThreeOptNeighborhood::new(tsp())),
+                    tour_with_info_objective.clone(),
+                    average_distance,
+                    0.9,
+                    distance_acceptance_probability_function(),
+                    Some(13),
+                    Some(steps),
+                    time_limit.map(TerminationCriteria::time_limit),
+                )
+            },
+        ),
+        run(
+            "parallel_simulated_annealing",
+            TspTourWithInfo::new(initial_tour.clone(), 0),
+            |steps| {
+                ParallelSimulatedAnnealingSolver::with_options(
+                    Arc::new(RotatedThreeOptNeighborhood::new(tsp())),
+                    tour_with_info_objective.clone(),
+                    vec![
+                        average_distance / 8.0,
+                        average_distance / 4.0,
+                        average_distance / 2.0,
+                        average_distance,
+                    ],
+                    0.9,
+                    distance_acceptance_probability_function(),
+                    50,
+                    Some(13),
+                    Some(steps),
+                    Some(
+                        TerminationCriteria::time_limit(time_budget)
+                            .or(TerminationCriteria::iteration_limit(1000)),
+                    ),
+                )
+            },
+        ),
+        run("tabu_search", initial_tour.clone(), |steps| {
+            TabuSearchSolver::with_options(
+                Arc::new(ThreeOptTabuNeighborhood::new(tsp())),
+                tour_objective.clone(),
+                30,
+                None,
+                Some(steps),
+                Some(
+                    TerminationCriteria::iterations_without_improvement(100)
+                        .or(TerminationCriteria::time_limit(time_budget)),
+                ),
+            )
+        }),
+        run("memoized_tabu_search", initial_tour.clone(), |steps| {
+            let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp()));
+            TabuSearchSolver::with_options(
+                neighborhood.clone(),
+                tour_objective.clone(),
+                30,
+                Some(Box::new(MemoizedTabuMinimizer::new(
+                    neighborhood,
+                    tour_objective.clone(),
+                ))),
+                Some(steps),
+                Some(
+                    TerminationCriteria::iterations_without_improvement(100)
+                        .or(TerminationCriteria::time_limit(time_budget)),
+                ),
+            )
+        }),
+        run("delta_tabu_search", initial_tour.clone(), |steps| {
+            let delta_objective = Arc::new(build_tsp_delta_objective());
+            let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp()));
+            TabuSearchSolver::with_options(
+                neighborhood.clone(),
+                delta_objective.objective(),
+                30,
+                Some(Box::new(DeltaTabuMinimizer::new(
+                    neighborhood,
+                    delta_objective.clone(),
+                ))),
+                Some(steps),
+                Some(
+                    TerminationCriteria::iterations_without_improvement(100)
+                        .or(TerminationCriteria::time_limit(time_budget)),
+                ),
+            )
+        }),
+        run("parallel_tabu_search", initial_tour, |steps| {
+            ParallelTabuSearchSolver::with_options(
+                Arc::new(ParallelThreeOptTabuNeighborhood::new(tsp())),
+                tour_objective.clone(),
+                30,
+                None,
+                Some(steps),
+                Some(
+                    TerminationCriteria::iterations_without_improvement(100)
+                        .or(TerminationCriteria::time_limit(time_budget)),
+                ),
+            )
+        }),
+    ];
+
+    entries.sort_by(|a, b| {
+        a.best_distance
+            .total_cmp(&b.best_distance)
+            .then(a.time_to_best.cmp(&b.time_to_best))
+    });
+    entries
+}
+
+/// Builds the solver with a function between steps that records the [`Progress`], solves the
+/// problem starting from the `initial_solution` and summarizes the run.
+fn run<S: 'static, T: Solver<S>>(
+    solver_name: &'static str,
+    initial_solution: S,
+    build: impl FnOnce(FunctionBetweenSteps<S>) -> T,
+) -> ComparisonEntry {
+    let progress = Arc::new(Mutex::new(Progress {
+        best_objective_value: None,
+        time_to_best: Duration::ZERO,
+        iterations: 0,
+    }));
+    let recorded_progress = progress.clone();
+    let creation_time = Instant::now();
+    let solver = build(Box::new(
+        move |iteration, current_solution, _, _, start_time, _, _, _| {
+            let mut progress = recorded_progress.lock().unwrap();
+            progress.iterations = progress.iterations.max(iteration);
+            if progress
+                .best_objective_value
+                .as_ref()
+                .is_none_or(|best| current_solution.objective_value() < best)
+            {
+                progress.best_objective_value = Some(current_solution.objective_value().clone());
+                progress.time_to_best = start_time.unwrap_or(creation_time).elapsed();
+            }
+        },
+    ));
+
+    let result = solver.solve_with_report(initial_solution);
+    let progress = progress.lock().unwrap();
+    let best_objective_value = result.best_solution.objective_value();
+    ComparisonEntry {
+        solver_name,
+        best_distance: best_objective_value.iter().next().unwrap().to_f64(),
+        // the best solution might not have been passed to the function between steps (e.g.,
+        // the final descent of a soft time limit)
+        time_to_best: match &progress.best_objective_value {
+            Some(best) if best <= best_objective_value => progress.time_to_best,
+            _ => result.elapsed,
+        },
+        iterations: result.iterations.unwrap_or(progress.iterations),
+        elapsed: result.elapsed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_solvers() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/br17.atsp").unwrap(),
+        );
+        let initial_distance =
+            TspTour::from_instance_nearest_neighbor(tsp_instance.clone()).get_total_distance();
+
+        let entries = compare_solvers(tsp_instance, Duration::from_millis(20));
+
+        assert_eq!(entries.len(), 13);
+        assert!(entries
+            .windows(2)
+            .all(|pair| pair[0].best_distance <= pair[1].best_distance));
+        for entry in entries.iter() {
+            assert!(
+                entry.best_distance <= initial_distance + 1e-6,
+                "{} got worse than the initial tour",
+                entry.solver_name
+            );
+            assert!(entry.time_to_best <= entry.elapsed);
+        }
+    }
+}
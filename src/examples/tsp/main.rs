@@ -1,8 +1,11 @@
 use std::env;
 use std::sync::Arc;
 
+use rapid_solve::examples::tsp::comparison::compare_solvers;
 use rapid_solve::examples::tsp::solvers;
 use rapid_solve::examples::tsp::tsp_tour_with_info::TspTourWithInfo;
+#[cfg(feature = "tsplib_download")]
+use rapid_solve::examples::tsp::tsplib_download::TsplibCache;
 use rapid_solve::examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour};
 use rapid_solve::heuristics::Solver;
 
@@ -11,53 +14,88 @@ fn main() {
     let args: Vec<String> = env::args().collect();
     let start_time = std::time::Instant::now();
 
+    if args.len() == 4 && args[1] == "compare" {
+        let Ok(seconds) = args[3].parse::<f64>() else {
+            print_usage(args[0].as_str());
+            std::process::exit(1);
+        };
+        compare(&args[2], std::time::Duration::from_secs_f64(seconds));
+        return;
+    }
     if args.len() != 3 {
         print_usage(args[0].as_str());
         std::process::exit(1);
     }
+    if args[1] == "compare" {
+        compare(&args[2], std::time::Duration::from_secs(10));
+        return;
+    }
 
-    let tsp_instance = Arc::new(TspInstance::from_tsplib_file(&args[2]).unwrap());
+    let tsp_instance = Arc::new(load_instance(&args[2]));
     let initial_tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
 
     let final_tour = match args[1].as_str() {
-        "basic_local_search" => {
-            let basic_local_search_solver =
-                Box::new(solvers::basic_local_search::build(tsp_instance));
-            basic_local_search_solver.solve(initial_tour).unwrap()
-        }
-        "take_first_local_search" => {
-            let take_first_local_search_solver =
-                Box::new(solvers::take_first_local_search::build(tsp_instance));
-            take_first_local_search_solver.solve(initial_tour).unwrap()
-        }
-        "parallel_local_search" => {
-            let parallel_local_search_solver =
-                Box::new(solvers::parallel_local_search::build(tsp_instance));
-            parallel_local_search_solver.solve(initial_tour).unwrap()
-        }
-        "threshold_accepting" => {
-            let threshold_accepting_solver =
-                Box::new(solvers::threshold_accepting::build(tsp_instance));
-            let tsp_tour_with_info =
-                threshold_accepting_solver.solve(TspTourWithInfo::new(initial_tour, 0));
-            tsp_tour_with_info.unwrap().unwrap()
-        }
-        "simulated_annealing" => {
-            let simulated_annealing_solver =
-                Box::new(solvers::simulated_annealing::build(tsp_instance));
-            let tsp_tour_with_info =
-                simulated_annealing_solver.solve(TspTourWithInfo::new(initial_tour, 0));
-            tsp_tour_with_info.unwrap().unwrap()
-        }
-        "tabu_search" => {
-            let tabu_search_solver = Box::new(solvers::tabu_search::build(tsp_instance));
-            tabu_search_solver.solve(initial_tour).unwrap()
-        }
-        "parallel_tabu_search" => {
-            let parallel_tabu_search_solver =
-                Box::new(solvers::parallel_tabu_search::build(tsp_instance));
-            parallel_tabu_search_solver.solve(initial_tour).unwrap()
+        "basic_local_search" => run(
+            solvers::basic_local_search::build(tsp_instance),
+            initial_tour,
+        ),
+        "take_first_local_search" => run(
+            solvers::take_first_local_search::build(tsp_instance),
+            initial_tour,
+        ),
+        "batch_parallel_local_search" => run(
+            solvers::batch_parallel_local_search::build(tsp_instance),
+            initial_tour,
+        ),
+        "iterated_local_search" => run(
+            solvers::iterated_local_search::build(tsp_instance),
+            initial_tour,
+        ),
+        "memetic" => run(solvers::memetic::build(tsp_instance), initial_tour),
+        "parallel_local_search" => run(
+            solvers::parallel_local_search::build(tsp_instance),
+            initial_tour,
+        ),
+        "threshold_accepting" => run(
+            solvers::threshold_accepting::build(tsp_instance),
+            TspTourWithInfo::new(initial_tour, 0),
+        )
+        .unwrap(),
+        "record_to_record_travel" => {
+            let initial_tour_with_info = TspTourWithInfo::new(initial_tour, 0);
+            run(
+                solvers::threshold_accepting::build_record_to_record_travel(
+                    tsp_instance,
+                    &initial_tour_with_info,
+                    10000,
+                ),
+                initial_tour_with_info,
+            )
+            .unwrap()
         }
+        "simulated_annealing" => run(
+            solvers::simulated_annealing::build(tsp_instance),
+            TspTourWithInfo::new(initial_tour, 0),
+        )
+        .unwrap(),
+        "parallel_simulated_annealing" => run(
+            solvers::parallel_simulated_annealing::build(tsp_instance),
+            TspTourWithInfo::new(initial_tour, 0),
+        )
+        .unwrap(),
+        "tabu_search" => run(solvers::tabu_search::build(tsp_instance), initial_tour),
+        "memoized_tabu_search" => run(
+            solvers::memoized_tabu_search::build(tsp_instance),
+            initial_tour,
+        ),
+        "delta_tabu_search" => run(
+            solvers::delta_tabu_search::build(tsp_instance),
+            initial_tour,
+        ),
+        "parallel_tabu_search" => run(
+            solvers::parallel_tabu_search::build(tsp_instance),
+            initial_tour,
+        ),
         _ => {
             eprintln!("Unknown solver: {}", args[1]);
             print_usage(args[0].as_str());
@@ -73,16 +111,73 @@ fn main() {
     );
 }
 
+/// Prints the configuration of the `solver` and solves the problem starting from the
+/// `initial_solution`.
+fn run<S>(solver: impl Solver<S>, initial_solution: S) -> S {
+    println!("{}\n", solver.describe());
+    solver.solve(initial_solution).unwrap()
+}
+
+/// Runs all solvers on the TSPLIB file `instance` with the same `time_budget` and prints them
+/// ranked by the best distance.
+fn compare(instance: &str, time_budget: std::time::Duration) {
+    let entries = compare_solvers(Arc::new(load_instance(instance)), time_budget);
+
+    println!(
+        "\nRanking with a time budget of {:0.2}sec:",
+        time_budget.as_secs_f64()
+    );
+    println!(
+        "  {:>4} {:<30} {:>14} {:>14} {:>10}",
+        "rank", "solver", "best distance", "time-to-best", "iterations"
+    );
+    for (rank, entry) in entries.iter().enumerate() {
+        println!(
+            "  {:>4} {:<30} {:>14.2} {:>13.2}s {:>10}",
+            rank + 1,
+            entry.solver_name,
+            entry.best_distance,
+            entry.time_to_best.as_secs_f64(),
+            entry.iterations
+        );
+    }
+}
+
+/// Loads the TSPLIB file `instance`. With the `tsplib_download` feature, `instance` can also be
+/// the name of a TSPLIB instance (e.g., `berlin52`), which is downloaded into the cache.
+fn load_instance(instance: &str) -> TspInstance {
+    #[cfg(feature = "tsplib_download")]
+    if !std::path::Path::new(instance).exists() {
+        return TsplibCache::default_location().load(instance).unwrap();
+    }
+    TspInstance::from_tsplib_file(instance).unwrap()
+}
+
 fn print_usage(program_name: &str) {
     eprintln!("Usage: {} <solver> <tsplib_file>", program_name);
+    eprintln!(
+        "   or: {} compare <tsplib_file> [<seconds>] (runs all solvers with the same time budget, default 10)",
+        program_name
+    );
     eprintln!(
         "  <solver>: \n\
         - basic_local_search\n\
         - take_first_local_search\n\
+        - batch_parallel_local_search\n\
+        - iterated_local_search\n\
+        - memetic\n\
         - parallel_local_search\n\
         - threshold_accepting\n\
+        - record_to_record_travel\n\
         - simulated_annealing\n\
+        - parallel_simulated_annealing\n\
         - tabu_search\n\
+        - memoized_tabu_search\n\
+        - delta_tabu_search\n\
         - parallel_tabu_search\n"
     );
+    #[cfg(feature = "tsplib_download")]
+    eprintln!(
+        "  <tsplib_file> can also be a TSPLIB instance name (e.g., berlin52), which is downloaded."
+    );
 }
@@ -1,11 +1,18 @@
 //! A simple implementation of the [Travelling Salesman Problem (TSP)](https://en.wikipedia.org/wiki/Travelling_salesman_problem) and [several metaheuristic solvers][solvers].
+//! With the `tsplib_download` feature, TSPLIB instances can be downloaded by name (see
+//! `tsplib_download::TsplibCache`). The solvers can be [compared][comparison] on an instance with
+//! the same time budget.
 
+pub mod comparison;
 pub mod neighborhood;
 pub mod objective;
+pub mod prize_collecting;
 pub mod solvers;
 pub mod tsp_instance;
 pub mod tsp_tour;
 pub mod tsp_tour_with_info;
+#[cfg(feature = "tsplib_download")]
+pub mod tsplib_download;
 
 /// A node index.
 pub type NodeIdx = usize;
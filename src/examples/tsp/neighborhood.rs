@@ -1,65 +1,275 @@
 //! The [`Neighborhood`] defines for every solution (in this case a tour) an iterator over all neighbors.
 //! The [`ThreeOptNeighborhood`] generates all tours that can be obtained by applying a 3-opt move.
+//! As [`ModificationNeighborhood`], it also yields the indices of the 3-opt moves and as
+//! [`MoveNeighborhood`], it only yields the [`ThreeOptMoves`][ThreeOptMove].
+//! The neighborhoods work for every tour type that implements the [`TourLike`] trait (e.g., the
+//! [`TspTour`] or the route of another routing problem).
 use std::sync::Arc;
 
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
 use crate::{
-    examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour},
-    heuristics::common::{Neighborhood, ParallelNeighborhood},
+    examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour, Distance, NodeIdx},
+    heuristics::common::{
+        ModificationNeighborhood, Move, MoveNeighborhood, Neighborhood, ParallelNeighborhood,
+    },
+    objective::{BaseValue, ObjectiveValue},
 };
 
-/// Given a [`TspTour`], this [`Neighborhood`] generates all tours that can be obtained by applying a
-/// 3-opt move (deleting three arcs and reconnecting the tour by adding three new arcs).
-pub struct ThreeOptNeighborhood {
-    tsp_instance: Arc<TspInstance>,
+/// A tour (a cyclic sequence of nodes) on which 3-opt moves can be applied, such that the
+/// [`ThreeOptNeighborhood`], the [`ParallelThreeOptNeighborhood`] and the
+/// [`RotatedThreeOptNeighborhood`][super::tsp_tour_with_info::neighborhood::RotatedThreeOptNeighborhood]
+/// can be used for other solution types than the [`TspTour`].
+/// * The 3-opt move `(i, j, k)` with `0 <= i < j < k < n` (where `n` is the number of nodes)
+///   exchanges the segments of the nodes with the indices `i+1` to `j` and `j+1` to `k` (see
+///   [`TspTour::three_opt_swap`]).
+pub trait TourLike: Send + Sync {
+    /// Returns the nodes of the tour in the order they are visited.
+    fn nodes(&self) -> &[NodeIdx];
+
+    /// Returns a new tour in which the segments of the 3-opt move `(i, j, k)` are exchanged.
+    fn splice_segments(&self, i: usize, j: usize, k: usize) -> Self;
+
+    /// Returns the change of the total distance caused by the 3-opt move `(i, j, k)` without
+    /// performing it.
+    fn distance_delta(&self, i: usize, j: usize, k: usize) -> Distance;
 }
 
+impl TourLike for TspTour {
+    fn nodes(&self) -> &[NodeIdx] {
+        self.get_nodes()
+    }
+
+    fn splice_segments(&self, i: usize, j: usize, k: usize) -> Self {
+        self.three_opt_swap(i, j, k)
+    }
+
+    fn distance_delta(&self, i: usize, j: usize, k: usize) -> Distance {
+        self.three_opt_delta(i, j, k)
+    }
+}
+
+/// Given a [`TourLike`] tour (e.g., a [`TspTour`]), this [`Neighborhood`] generates all tours
+/// that can be obtained by applying a 3-opt move (deleting three arcs and reconnecting the tour by
+/// adding three new arcs).
+/// * The number of nodes is taken from each tour, so tours of other problems can use the
+///   [`Default`] neighborhood.
+#[derive(Default)]
+pub struct ThreeOptNeighborhood;
+
 impl ThreeOptNeighborhood {
-    /// Creates a new [`ThreeOptNeighborhood`] for the given [`TspInstance`].
-    pub fn new(tsp_instance: Arc<TspInstance>) -> Self {
-        Self { tsp_instance }
+    /// Creates a new [`ThreeOptNeighborhood`] for the tours of the given [`TspInstance`].
+    pub fn new(_tsp_instance: Arc<TspInstance>) -> Self {
+        Self
     }
 }
 
-impl Neighborhood<TspTour> for ThreeOptNeighborhood {
-    fn neighbors_of<'a>(
+impl<T: TourLike> Neighborhood<T> for ThreeOptNeighborhood {
+    fn neighbors_of<'a>(&'a self, tour: &'a T) -> Box<dyn Iterator<Item = T> + Send + Sync + 'a> {
+        let num_nodes = tour.nodes().len();
+        Box::new((0..num_nodes - 2).flat_map(move |i| {
+            (i + 1..num_nodes - 1)
+                .flat_map(move |j| (j + 1..num_nodes).map(move |k| tour.splice_segments(i, j, k)))
+        }))
+    }
+}
+
+/// Each neighbor is paired with the indices `(i, j, k)` of its 3-opt move, such that it can be
+/// evaluated by the [`build_tsp_delta_objective`][super::objective::build_tsp_delta_objective].
+impl<T: TourLike> ModificationNeighborhood<T, (usize, usize, usize)> for ThreeOptNeighborhood {
+    fn neighbors_with_modifications_of<'a>(
         &'a self,
-        tour: &'a TspTour,
-    ) -> Box<dyn Iterator<Item = TspTour> + Send + Sync + 'a> {
-        let num_nodes = self.tsp_instance.get_number_of_nodes();
+        tour: &'a T,
+    ) -> Box<dyn Iterator<Item = (T, (usize, usize, usize))> + Send + Sync + 'a> {
+        let num_nodes = tour.nodes().len();
+        Box::new((0..num_nodes - 2).flat_map(move |i| {
+            (i + 1..num_nodes - 1).flat_map(move |j| {
+                (j + 1..num_nodes).map(move |k| (tour.splice_segments(i, j, k), (i, j, k)))
+            })
+        }))
+    }
+}
+
+/// The 3-opt move `(i, j, k)` (see [`TourLike::splice_segments`]), whose delta is the change of
+/// the total distance (see [`TourLike::distance_delta`]).
+pub struct ThreeOptMove {
+    i: usize,
+    j: usize,
+    k: usize,
+}
+
+impl<T: TourLike> Move<T> for ThreeOptMove {
+    fn apply(&self, tour: &T) -> T {
+        tour.splice_segments(self.i, self.j, self.k)
+    }
+
+    fn delta(&self, tour: &T) -> Option<ObjectiveValue> {
+        Some(ObjectiveValue::new(vec![BaseValue::Float(
+            tour.distance_delta(self.i, self.j, self.k),
+        )]))
+    }
+}
+
+/// Yields the [`ThreeOptMoves`][ThreeOptMove] without performing the swaps, such that only the
+/// best move needs to be applied (see
+/// [`MoveMinimizer`][crate::heuristics::local_search::local_improver::MoveMinimizer]).
+impl<T: TourLike> MoveNeighborhood<T> for ThreeOptNeighborhood {
+    type Move = ThreeOptMove;
+
+    fn moves_of<'a>(
+        &'a self,
+        tour: &'a T,
+    ) -> Box<dyn Iterator<Item = ThreeOptMove> + Send + Sync + 'a> {
+        let num_nodes = tour.nodes().len();
         Box::new((0..num_nodes - 2).flat_map(move |i| {
             (i + 1..num_nodes - 1)
-                .flat_map(move |j| (j + 1..num_nodes).map(move |k| tour.three_opt_swap(i, j, k)))
+                .flat_map(move |j| (j + 1..num_nodes).map(move |k| ThreeOptMove { i, j, k }))
         }))
     }
 }
 
-/// Given a [`TspTour`], this [`ParallelNeighborhood`] generates all tours that can be obtained by
-/// applying a
+/// Given a [`TourLike`] tour (e.g., a [`TspTour`]), this [`ParallelNeighborhood`] generates all
+/// tours that can be obtained by applying a
 /// 3-opt move (deleting three arcs and reconnecting the tour by adding three new arcs).
 /// The parallel version of the [`ThreeOptNeighborhood`] as it uses the parallel iterator.
 pub struct ParallelThreeOptNeighborhood {
-    tsp_instance: Arc<TspInstance>,
+    min_len: usize,
 }
 
 impl ParallelThreeOptNeighborhood {
-    /// Creates a new [`ParallelThreeOptNeighborhood`] for the given [`TspInstance`].
-    pub fn new(tsp_instance: Arc<TspInstance>) -> Self {
-        Self { tsp_instance }
+    /// Creates a new [`ParallelThreeOptNeighborhood`] for the tours of the given [`TspInstance`].
+    pub fn new(_tsp_instance: Arc<TspInstance>) -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimal number of indices of each of the three nested ranges of a 3-opt move
+    /// that are processed by one task (see
+    /// [`with_min_len`][IndexedParallelIterator::with_min_len]). As a single 3-opt move is cheap,
+    /// larger values (e.g., 16) reduce the task overhead on many-core machines. The default is 1.
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len.max(1);
+        self
+    }
+}
+
+impl Default for ParallelThreeOptNeighborhood {
+    /// Creates a new [`ParallelThreeOptNeighborhood`] for tours of any [`TourLike`] type.
+    fn default() -> Self {
+        Self { min_len: 1 }
     }
 }
 
-impl ParallelNeighborhood<TspTour> for ParallelThreeOptNeighborhood {
-    fn neighbors_of<'a>(&'a self, tour: &'a TspTour) -> impl ParallelIterator<Item = TspTour> + 'a {
-        let num_nodes = self.tsp_instance.get_number_of_nodes();
+impl<T: TourLike> ParallelNeighborhood<T> for ParallelThreeOptNeighborhood {
+    fn neighbors_of<'a>(&'a self, tour: &'a T) -> impl ParallelIterator<Item = T> + 'a {
+        let num_nodes = tour.nodes().len();
+        let min_len = self.min_len;
         (0..num_nodes - 2)
             .into_par_iter()
-            .flat_map(move |i| (i + 1..num_nodes - 1).into_par_iter().map(move |j| (i, j)))
+            .with_min_len(min_len)
+            .flat_map(move |i| {
+                (i + 1..num_nodes - 1)
+                    .into_par_iter()
+                    .with_min_len(min_len)
+                    .map(move |j| (i, j))
+            })
             .flat_map(move |(i, j)| {
                 (j + 1..num_nodes)
                     .into_par_iter()
-                    .map(move |k| tour.three_opt_swap(i, j, k))
+                    .with_min_len(min_len)
+                    .map(move |k| tour.splice_segments(i, j, k))
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::tsp::tsp_tour_with_info::neighborhood::RotatedThreeOptNeighborhood;
+    use crate::examples::tsp::tsp_tour_with_info::TspTourWithInfo;
+    use crate::heuristics::common::silent_function_between_steps;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::heuristics::Solver;
+    use crate::objective::{Indicator, Objective};
+
+    /// A route of another routing problem: the nodes are points on a line.
+    #[derive(Clone)]
+    struct LineRoute {
+        nodes: Vec<NodeIdx>,
+    }
+
+    impl LineRoute {
+        fn length(&self) -> Distance {
+            self.nodes
+                .iter()
+                .zip(self.nodes.iter().cycle().skip(1))
+                .map(|(&a, &b)| (a as Distance - b as Distance).abs())
+                .sum()
+        }
+    }
+
+    impl TourLike for LineRoute {
+        fn nodes(&self) -> &[NodeIdx] {
+            &self.nodes
+        }
+
+        fn splice_segments(&self, i: usize, j: usize, k: usize) -> Self {
+            let mut nodes = self.nodes[0..i + 1].to_vec();
+            nodes.extend_from_slice(&self.nodes[j + 1..k + 1]);
+            nodes.extend_from_slice(&self.nodes[i + 1..j + 1]);
+            nodes.extend_from_slice(&self.nodes[k + 1..]);
+            LineRoute { nodes }
+        }
+
+        fn distance_delta(&self, i: usize, j: usize, k: usize) -> Distance {
+            self.splice_segments(i, j, k).length() - self.length()
+        }
+    }
+
+    struct Length;
+
+    impl Indicator<LineRoute> for Length {
+        fn evaluate(&self, route: &LineRoute) -> BaseValue {
+            BaseValue::Float(route.length())
+        }
+
+        fn name(&self) -> String {
+            String::from("Length")
+        }
+    }
+
+    #[test]
+    fn test_three_opt_neighborhood_for_other_tour_types() {
+        let route = LineRoute {
+            nodes: vec![0, 4, 1, 3, 2, 5],
+        };
+        let neighbors: Vec<LineRoute> = ThreeOptNeighborhood.neighbors_of(&route).collect();
+        assert_eq!(neighbors.len(), 20);
+        let moves: Vec<ThreeOptMove> = ThreeOptNeighborhood.moves_of(&route).collect();
+        for (neighbor, three_opt_move) in neighbors.iter().zip(moves.iter()) {
+            assert_eq!(
+                three_opt_move.delta(&route),
+                Some(ObjectiveValue::new(vec![BaseValue::Float(
+                    neighbor.length() - route.length()
+                )]))
+            );
+        }
+
+        let with_info = TspTourWithInfo::new(route.clone(), 2);
+        let rotated: Vec<TspTourWithInfo<LineRoute>> = RotatedThreeOptNeighborhood
+            .neighbors_of(&with_info)
+            .collect();
+        assert_eq!(rotated.len(), 20);
+        assert_eq!(rotated[0].get_last_i(), 3);
+
+        let solver = LocalSearchSolver::with_options(
+            Arc::new(ThreeOptNeighborhood),
+            Arc::new(Objective::new_single_indicator(Box::new(Length))),
+            None,
+            Some(silent_function_between_steps()),
+            None,
+        );
+        // the optimal route on a line has twice the length of the line
+        let optimum = solver.solve(route);
+        assert_eq!(optimum.solution().length(), 10.0);
+    }
+}
@@ -1,5 +1,8 @@
 //! This module contains the [`Objective`] for the TSP.
-use crate::objective::{BaseValue, Indicator, Objective};
+use std::sync::Arc;
+
+use crate::heuristics::common::{DecodedIndicator, RandomKeys};
+use crate::objective::{BaseValue, DeltaObjective, IncrementalIndicator, Indicator, Objective};
 
 use super::tsp_tour::TspTour;
 
@@ -15,8 +18,29 @@ impl Indicator<TspTour> for DistanceIndicator {
     }
 }
 
+/// The modifications are the indices `(i, j, k)` of a 3-opt move.
+impl IncrementalIndicator<TspTour, (usize, usize, usize)> for DistanceIndicator {
+    fn evaluate_delta(&self, tsp_tour: &TspTour, &(i, j, k): &(usize, usize, usize)) -> BaseValue {
+        BaseValue::Float(tsp_tour.three_opt_delta(i, j, k))
+    }
+}
+
 /// Builds the [`Objective`] for the TSP, which consists of a single [`Indicator`] for the total
 /// distance of the tour.
 pub fn build_tsp_objective() -> Objective<TspTour> {
     Objective::new_single_indicator(Box::new(DistanceIndicator))
 }
+
+/// Builds the [`DeltaObjective`] for the TSP, which evaluates 3-opt moves `(i, j, k)` by the
+/// change of the total distance (see [`TspTour::three_opt_delta`]). Its
+/// [`objective`][DeltaObjective::objective] is the same as [`build_tsp_objective`].
+pub fn build_tsp_delta_objective() -> DeltaObjective<TspTour, (usize, usize, usize)> {
+    DeltaObjective::new_single_indicator(Arc::new(DistanceIndicator))
+}
+
+/// Builds the [`Objective`] for [`RandomKeys`] that are decoded into TSP tours (see
+/// [`random_key_genetic_algorithm`][super::solvers::random_key_genetic_algorithm]), which
+/// evaluates the total distance of the decoded tour.
+pub fn build_tsp_random_keys_objective() -> Objective<RandomKeys<TspTour>> {
+    Objective::new_single_indicator(Box::new(DecodedIndicator::new(Box::new(DistanceIndicator))))
+}
@@ -0,0 +1,154 @@
+//! A prize-collecting variant of the TSP: Each node has a prize that is collected if the node is
+//! visited, but not all nodes have to be visited. The goal is to maximize the collected prizes
+//! minus the travel cost.
+//! * Maximization is modeled by minimizing the negated objective, i.e., the
+//!   [`Objective`][crate::objective::Objective] is the travel cost plus the collected prizes
+//!   multiplied with the [`Coefficient`][crate::objective::Coefficient] -1 (see
+//!   [`objective`]).
+//! * The [`PrizeCollectingTour`] always starts at the depot (node 0) and visits a subset of the
+//!   other nodes.
+//! * The [`AddDropNeighborhood`][neighborhood::AddDropNeighborhood] inserts unvisited nodes,
+//!   removes visited nodes and replaces visited nodes by unvisited nodes.
+//! * The [`RandomRemoval`][operators::RandomRemoval] and
+//!   [`GreedyInsertion`][operators::GreedyInsertion] operators are used by the
+//!   [large neighborhood search][crate::heuristics::large_neighborhood_search].
+pub mod neighborhood;
+pub mod objective;
+pub mod operators;
+
+use std::sync::Arc;
+
+use super::{tsp_instance::TspInstance, Distance, NodeIdx};
+
+/// The prize of a node.
+pub type Prize = f64;
+
+/// A [`PrizeCollectingTspInstance`] consists of a [`TspInstance`] and a prize for each node.
+/// Node 0 is the depot, which is always visited.
+pub struct PrizeCollectingTspInstance {
+    tsp_instance: Arc<TspInstance>,
+    prizes: Vec<Prize>,
+}
+
+impl PrizeCollectingTspInstance {
+    /// Creates a new [`PrizeCollectingTspInstance`] from a [`TspInstance`] and a prize for each
+    /// node.
+    pub fn new(tsp_instance: Arc<TspInstance>, prizes: Vec<Prize>) -> PrizeCollectingTspInstance {
+        assert_eq!(prizes.len(), tsp_instance.get_number_of_nodes());
+        PrizeCollectingTspInstance {
+            tsp_instance,
+            prizes,
+        }
+    }
+
+    /// Returns the distance between two nodes.
+    pub fn get_distance(&self, from: NodeIdx, to: NodeIdx) -> Distance {
+        self.tsp_instance.get_distance(from, to)
+    }
+
+    /// Returns the prize of a node.
+    pub fn get_prize(&self, node: NodeIdx) -> Prize {
+        self.prizes[node]
+    }
+
+    /// Returns the number of nodes in the instance.
+    pub fn get_number_of_nodes(&self) -> usize {
+        self.tsp_instance.get_number_of_nodes()
+    }
+}
+
+/// A tour of a [`PrizeCollectingTspInstance`] that starts at the depot (node 0) and visits a
+/// subset of the other nodes.
+#[derive(Clone)]
+pub struct PrizeCollectingTour {
+    nodes: Vec<NodeIdx>,
+    total_distance: Distance,
+    total_prize: Prize,
+    instance: Arc<PrizeCollectingTspInstance>,
+}
+
+impl PrizeCollectingTour {
+    /// Creates a new [`PrizeCollectingTour`] visiting the given nodes (in this order). The first
+    /// node must be the depot (node 0).
+    pub fn new(nodes: Vec<NodeIdx>, instance: Arc<PrizeCollectingTspInstance>) -> Self {
+        assert_eq!(nodes.first(), Some(&0), "The tour must start at the depot.");
+        let total_distance = nodes
+            .iter()
+            .zip(nodes.iter().cycle().skip(1))
+            .map(|(&i, &j)| instance.get_distance(i, j))
+            .sum();
+        let total_prize = nodes.iter().map(|&node| instance.get_prize(node)).sum();
+        PrizeCollectingTour {
+            nodes,
+            total_distance,
+            total_prize,
+            instance,
+        }
+    }
+
+    /// Creates a [`PrizeCollectingTour`] that only visits the depot.
+    pub fn depot_only(instance: Arc<PrizeCollectingTspInstance>) -> Self {
+        PrizeCollectingTour::new(vec![0], instance)
+    }
+
+    /// Returns the visited `nodes` of the tour.
+    pub fn get_nodes(&self) -> &Vec<NodeIdx> {
+        &self.nodes
+    }
+
+    /// Returns the `total_distance` of the tour.
+    pub fn get_total_distance(&self) -> Distance {
+        self.total_distance
+    }
+
+    /// Returns the sum of the prizes of all visited nodes.
+    pub fn get_total_prize(&self) -> Prize {
+        self.total_prize
+    }
+
+    /// Returns `true` if the given node is visited by the tour.
+    pub fn visits(&self, node: NodeIdx) -> bool {
+        self.nodes.contains(&node)
+    }
+
+    /// Inserts the (unvisited) `node` directly after the node at index `position`.
+    pub fn add_node(&self, position: usize, node: NodeIdx) -> PrizeCollectingTour {
+        let n = self.nodes.len();
+        let before = self.nodes[position];
+        let after = self.nodes[(position + 1) % n];
+        let mut new_nodes = self.nodes.clone();
+        new_nodes.insert(position + 1, node);
+        PrizeCollectingTour {
+            nodes: new_nodes,
+            total_distance: self.total_distance - self.instance.get_distance(before, after)
+                + self.instance.get_distance(before, node)
+                + self.instance.get_distance(node, after),
+            total_prize: self.total_prize + self.instance.get_prize(node),
+            instance: self.instance.clone(),
+        }
+    }
+
+    /// Removes the node at index `position` (must not be 0, as the depot is always visited).
+    pub fn drop_node(&self, position: usize) -> PrizeCollectingTour {
+        assert!(position > 0, "The depot cannot be dropped.");
+        let n = self.nodes.len();
+        let before = self.nodes[position - 1];
+        let node = self.nodes[position];
+        let after = self.nodes[(position + 1) % n];
+        let mut new_nodes = self.nodes.clone();
+        new_nodes.remove(position);
+        PrizeCollectingTour {
+            nodes: new_nodes,
+            total_distance: self.total_distance + self.instance.get_distance(before, after)
+                - self.instance.get_distance(before, node)
+                - self.instance.get_distance(node, after),
+            total_prize: self.total_prize - self.instance.get_prize(node),
+            instance: self.instance.clone(),
+        }
+    }
+
+    /// Replaces the node at index `position` (must not be 0) by the (unvisited) `node`.
+    pub fn replace_node(&self, position: usize, node: NodeIdx) -> PrizeCollectingTour {
+        self.drop_node(position).add_node(position - 1, node)
+    }
+}
@@ -0,0 +1,35 @@
+//! This module contains the [`AddDropNeighborhood`] for the [`PrizeCollectingTour`].
+use crate::heuristics::common::Neighborhood;
+
+use super::PrizeCollectingTour;
+
+/// Given a [`PrizeCollectingTour`], this [`Neighborhood`] generates all tours that can be
+/// obtained by one of the following selective-visit moves:
+/// * dropping a visited node (except the depot),
+/// * adding an unvisited node at any position of the tour,
+/// * replacing a visited node (except the depot) by an unvisited node.
+pub struct AddDropNeighborhood;
+
+impl Neighborhood<PrizeCollectingTour> for AddDropNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        tour: &'a PrizeCollectingTour,
+    ) -> Box<dyn Iterator<Item = PrizeCollectingTour> + Send + Sync + 'a> {
+        let num_nodes = tour.instance.get_number_of_nodes();
+        let tour_length = tour.get_nodes().len();
+        let unvisited: Vec<usize> = (0..num_nodes).filter(|&n| !tour.visits(n)).collect();
+        let unvisited_for_replace = unvisited.clone();
+
+        let drops = (1..tour_length).map(move |position| tour.drop_node(position));
+        let adds = unvisited.into_iter().flat_map(move |node| {
+            (0..tour_length).map(move |position| tour.add_node(position, node))
+        });
+        let replaces = (1..tour_length).flat_map(move |position| {
+            unvisited_for_replace
+                .clone()
+                .into_iter()
+                .map(move |node| tour.replace_node(position, node))
+        });
+        Box::new(drops.chain(adds).chain(replaces))
+    }
+}
@@ -0,0 +1,40 @@
+//! This module contains the [`Objective`] for the prize-collecting TSP.
+//! As the solvers always minimize, the collected prizes are maximized by giving them the
+//! [`Coefficient`][crate::objective::Coefficient] -1, i.e., the objective is the travel cost minus the collected prizes.
+use crate::objective::{BaseValue, Indicator, Objective};
+
+use super::PrizeCollectingTour;
+
+struct TravelCostIndicator;
+
+impl Indicator<PrizeCollectingTour> for TravelCostIndicator {
+    fn evaluate(&self, tour: &PrizeCollectingTour) -> BaseValue {
+        BaseValue::Float(tour.get_total_distance())
+    }
+
+    fn name(&self) -> String {
+        String::from("TravelCost")
+    }
+}
+
+struct CollectedPrizeIndicator;
+
+impl Indicator<PrizeCollectingTour> for CollectedPrizeIndicator {
+    fn evaluate(&self, tour: &PrizeCollectingTour) -> BaseValue {
+        BaseValue::Float(tour.get_total_prize())
+    }
+
+    fn name(&self) -> String {
+        String::from("CollectedPrize")
+    }
+}
+
+/// Builds the [`Objective`] for the prize-collecting TSP, which consists of a single level:
+/// the travel cost minus the collected prizes (i.e., the collected prizes are maximized).
+pub fn build_prize_collecting_objective() -> Objective<PrizeCollectingTour> {
+    Objective::builder()
+        .level()
+        .indicator(TravelCostIndicator)
+        .weighted_indicator(-1, CollectedPrizeIndicator)
+        .build()
+}
@@ -0,0 +1,73 @@
+//! This module contains the [`RandomRemoval`] destroy operator and the [`GreedyInsertion`] repair
+//! operator for the [`PrizeCollectingTour`].
+use rand::Rng;
+use rand::RngCore;
+
+use crate::heuristics::large_neighborhood_search::{DestroyOperator, RepairOperator};
+
+use super::PrizeCollectingTour;
+
+/// Removes between 1 and `max_removed` randomly chosen visited nodes (except the depot) from the
+/// tour.
+pub struct RandomRemoval {
+    max_removed: usize,
+}
+
+impl RandomRemoval {
+    /// Creates a new [`RandomRemoval`] that removes at most `max_removed` nodes.
+    pub fn new(max_removed: usize) -> RandomRemoval {
+        RandomRemoval { max_removed }
+    }
+}
+
+impl DestroyOperator<PrizeCollectingTour> for RandomRemoval {
+    fn destroy(&self, tour: &PrizeCollectingTour, rng: &mut dyn RngCore) -> PrizeCollectingTour {
+        let mut destroyed_tour = tour.clone();
+        let num_removed = rng.gen_range(1..=self.max_removed.max(1));
+        for _ in 0..num_removed {
+            if destroyed_tour.get_nodes().len() <= 1 {
+                break;
+            }
+            let position = rng.gen_range(1..destroyed_tour.get_nodes().len());
+            destroyed_tour = destroyed_tour.drop_node(position);
+        }
+        destroyed_tour
+    }
+}
+
+/// Repeatedly inserts the unvisited node with the largest gain (prize minus insertion cost) at
+/// its cheapest position, as long as the gain is positive.
+pub struct GreedyInsertion;
+
+impl RepairOperator<PrizeCollectingTour> for GreedyInsertion {
+    fn repair(
+        &self,
+        destroyed_tour: PrizeCollectingTour,
+        _: &mut dyn RngCore,
+    ) -> PrizeCollectingTour {
+        let instance = destroyed_tour.instance.clone();
+        let mut tour = destroyed_tour;
+        loop {
+            let nodes = tour.get_nodes();
+            let n = nodes.len();
+            let mut best_insertion: Option<(f64, usize, usize)> = None; // (gain, position, node)
+            for node in (0..instance.get_number_of_nodes()).filter(|&node| !tour.visits(node)) {
+                for position in 0..n {
+                    let before = nodes[position];
+                    let after = nodes[(position + 1) % n];
+                    let gain = instance.get_prize(node)
+                        - (instance.get_distance(before, node)
+                            + instance.get_distance(node, after)
+                            - instance.get_distance(before, after));
+                    if gain > best_insertion.map_or(0.0, |(best_gain, _, _)| best_gain) {
+                        best_insertion = Some((gain, position, node));
+                    }
+                }
+            }
+            match best_insertion {
+                Some((_, position, node)) => tour = tour.add_node(position, node),
+                None => return tour,
+            }
+        }
+    }
+}
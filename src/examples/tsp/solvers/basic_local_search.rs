@@ -31,8 +31,18 @@ pub fn build(tsp_instance: Arc<TspInstance>) -> LocalSearchSolver<TspTour> {
 mod tests {
     use super::build;
     use crate::{
-        examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour},
-        heuristics::Solver,
+        examples::tsp::{
+            neighborhood::ThreeOptNeighborhood, objective::build_tsp_delta_objective,
+            objective::build_tsp_objective, tsp_instance::TspInstance, tsp_tour::TspTour,
+        },
+        heuristics::{
+            common::{ModificationJournal, MoveNeighborhoodAdapter},
+            local_search::{
+                local_improver::{DeltaMinimizer, MoveMinimizer},
+                LocalSearchSolver,
+            },
+            Solver,
+        },
     };
     use std::sync::Arc;
 
@@ -73,4 +83,138 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_delta_local_search_large_instance() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let delta_objective = Arc::new(build_tsp_delta_objective());
+        let neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+        let solver = LocalSearchSolver::with_options(
+            neighborhood.clone(),
+            delta_objective.objective(),
+            Some(Box::new(DeltaMinimizer::new(
+                neighborhood,
+                delta_objective.clone(),
+            ))),
+            None,
+            None,
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+
+        let local_opt_tour = solver.solve(tour);
+
+        // same result as the minimizer that evaluates the neighbors from scratch
+        assert_eq!(
+            local_opt_tour.solution().get_nodes(),
+            &vec![
+                0, 35, 38, 39, 36, 37, 47, 23, 4, 14, 5, 3, 24, 45, 43, 33, 34, 48, 31, 44, 18, 40,
+                7, 8, 9, 42, 32, 50, 11, 27, 26, 12, 13, 51, 10, 25, 46, 28, 15, 49, 19, 22, 29, 1,
+                6, 41, 20, 16, 2, 17, 30, 21
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delta_local_search_with_journal() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let delta_objective = Arc::new(build_tsp_delta_objective());
+        let neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+        let journal = ModificationJournal::new();
+        let solver = LocalSearchSolver::with_options(
+            neighborhood.clone(),
+            delta_objective.objective(),
+            Some(Box::new(
+                DeltaMinimizer::new(neighborhood, delta_objective.clone())
+                    .with_journal(journal.clone()),
+            )),
+            None,
+            None,
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let initial_objective_value = delta_objective.objective().evaluate(tour.clone());
+
+        let result = solver.solve_with_report(tour);
+
+        // one entry per applied modification, forming a chain from the initial to the final value
+        let entries = journal.entries();
+        assert!(!entries.is_empty());
+        assert_eq!(
+            &entries[0].objective_value_before,
+            initial_objective_value.objective_value()
+        );
+        for (entry, next_entry) in entries.iter().zip(entries.iter().skip(1)) {
+            assert!(entry.objective_value_after < entry.objective_value_before);
+            assert_eq!(
+                entry.objective_value_after,
+                next_entry.objective_value_before
+            );
+        }
+        assert_eq!(
+            &entries.last().unwrap().objective_value_after,
+            result.best_solution.objective_value()
+        );
+    }
+
+    #[test]
+    fn test_move_local_search_large_instance() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let objective = Arc::new(build_tsp_objective());
+        let move_neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+        let move_solver = LocalSearchSolver::with_options(
+            Arc::new(MoveNeighborhoodAdapter::new(move_neighborhood.clone())),
+            objective.clone(),
+            Some(Box::new(MoveMinimizer::new(
+                move_neighborhood,
+                objective.clone(),
+            ))),
+            None,
+            None,
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+
+        let local_opt_tour = move_solver.solve(tour.clone());
+
+        // same result as the minimizer that materializes all neighbors
+        assert_eq!(
+            local_opt_tour.solution().get_nodes(),
+            build(tsp_instance).solve(tour).solution().get_nodes()
+        );
+    }
+
+    #[test]
+    fn test_basic_local_search_batch() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/br17.atsp").unwrap(),
+        );
+        let solver = build(tsp_instance.clone());
+        let instances = vec![
+            TspTour::from_instance_nearest_neighbor(tsp_instance.clone()),
+            TspTour::new((0..17).collect(), tsp_instance.clone()),
+        ];
+
+        let summary = solver.solve_batch(instances.clone());
+        let parallel_summary = solver.solve_batch_parallel(instances.clone());
+
+        assert_eq!(summary.number_of_runs(), 2);
+        for ((index, result), (parallel_index, parallel_result)) in
+            summary.runs().iter().zip(parallel_summary.runs())
+        {
+            assert_eq!(index, parallel_index);
+            assert_eq!(
+                result.solution().get_nodes(),
+                solver
+                    .solve(instances[*index].clone())
+                    .solution()
+                    .get_nodes()
+            );
+            assert_eq!(result.objective_value(), parallel_result.objective_value());
+        }
+        assert_eq!(summary.mean(), parallel_summary.mean());
+    }
 }
@@ -0,0 +1,88 @@
+//! This module contains a [`TabuSearchSolver`] for the TSP that uses the [`DeltaTabuMinimizer`]
+//! to evaluate the 3-opt moves by the change of the tour length, see the [build] function for
+//! details.
+//! ```ignore
+//! pub fn build(tsp_instance: Arc<TspInstance>) -> TabuSearchSolver<TspTour, Tabu> {
+//!     let delta_objective = Arc::new(build_tsp_delta_objective());
+//!     let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone()));
+//!     let tabu_improver = Box::new(DeltaTabuMinimizer::new(
+//!         neighborhood.clone(),
+//!         delta_objective.clone(),
+//!     ));
+//!     TabuSearchSolver::with_options(
+//!         neighborhood,
+//!         delta_objective.objective(),
+//!         30,
+//!         Some(tabu_improver),
+//!         None,
+//!         Some(TerminationCriteria::iterations_without_improvement(100)),
+//!     )
+//! }
+//! ```
+use std::sync::Arc;
+
+use super::tabu_search::{Tabu, ThreeOptTabuNeighborhood};
+use crate::{
+    examples::tsp::{
+        objective::build_tsp_delta_objective, tsp_instance::TspInstance, tsp_tour::TspTour,
+    },
+    heuristics::{
+        common::TerminationCriteria,
+        tabu_search::{tabu_improver::DeltaTabuMinimizer, TabuSearchSolver},
+    },
+};
+
+/// Builds a [`TabuSearchSolver`] for the TSP with the [`DeltaTabuMinimizer`].
+/// * The neighborhood is the 3-opt neighborhood, where each move is identified by its indices.
+/// * Each move is evaluated by the change of the tour length (three removed and three added
+///   arcs), only the best non-tabu move is applied.
+/// * The tabu list size is set to 30.
+/// * The iteration without global improvement limit is set to 100.
+/// * Gives the same result as the [tabu search][super::tabu_search] with the
+///   [`TabuMinimizer`][crate::heuristics::tabu_search::tabu_improver::TabuMinimizer].
+pub fn build(tsp_instance: Arc<TspInstance>) -> TabuSearchSolver<TspTour, Tabu> {
+    let delta_objective = Arc::new(build_tsp_delta_objective());
+    let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone()));
+    let tabu_improver = Box::new(DeltaTabuMinimizer::new(
+        neighborhood.clone(),
+        delta_objective.clone(),
+    ));
+    TabuSearchSolver::with_options(
+        neighborhood,
+        delta_objective.objective(),
+        30,
+        Some(tabu_improver),
+        None,
+        Some(TerminationCriteria::iterations_without_improvement(100)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tabu_search, build};
+    use crate::examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_delta_tabu_search_large_instance() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let solver = build(tsp_instance.clone());
+        let tabu_search_solver = tabu_search::build(tsp_instance);
+
+        // same iterations as the tabu search without deltas
+        for (delta_step, step) in solver
+            .steps(tour.clone())
+            .zip(tabu_search_solver.steps(tour))
+            .take(10)
+        {
+            assert_eq!(
+                delta_step.solution().get_nodes(),
+                step.solution().get_nodes()
+            );
+            assert_eq!(delta_step.objective_value(), step.objective_value());
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! For the [`IteratedLocalSearchSolver`], we need in addition to the [`Objective`] ([length of
+//! the TSP tour][build_tsp_objective]) and the 3-opt [`ThreeOptNeighborhood`] a
+//! [`Perturbation`], which is given by a few random 3-opt moves
+//! ([`RandomThreeOptPerturbation`]).
+//! ```ignore
+//! pub fn build(tsp_instance: Arc<TspInstance>) -> IteratedLocalSearchSolver<TspTour> {
+//!     let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
+//!     let neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+//!     let local_improver = Box::new(TakeFirst::new(neighborhood.clone(), objective.clone()));
+//!     let perturbation = Box::new(RandomThreeOptPerturbation::new(3));
+//!     IteratedLocalSearchSolver::with_options(
+//!         neighborhood,
+//!         perturbation,
+//!         objective,
+//!         Some(local_improver),
+//!         None,
+//!         Some(42),
+//!         None,
+//!         Some(TerminationCriteria::iteration_limit(5)),
+//!     )
+//! }
+//! ```
+use super::super::objective::build_tsp_objective;
+use super::super::tsp_instance::TspInstance;
+use super::super::tsp_tour::TspTour;
+use crate::examples::tsp::neighborhood::ThreeOptNeighborhood;
+use crate::heuristics::common::TerminationCriteria;
+use crate::heuristics::iterated_local_search::{IteratedLocalSearchSolver, Perturbation};
+use crate::heuristics::local_search::local_improver::TakeFirst;
+use crate::objective::Objective;
+use rand::seq::index::sample;
+use rand::RngCore;
+use std::sync::Arc;
+
+/// Perturbs a [`TspTour`] by applying `strength` many random 3-opt moves.
+pub struct RandomThreeOptPerturbation {
+    strength: usize,
+}
+
+impl RandomThreeOptPerturbation {
+    /// Creates a new [`RandomThreeOptPerturbation`] that applies `strength` random 3-opt moves.
+    pub fn new(strength: usize) -> Self {
+        Self { strength }
+    }
+}
+
+impl Perturbation<TspTour> for RandomThreeOptPerturbation {
+    fn perturb(&self, tour: &TspTour, rng: &mut dyn RngCore) -> TspTour {
+        let num_nodes = tour.get_nodes().len();
+        let mut perturbed_tour = tour.clone();
+        if num_nodes < 3 {
+            return perturbed_tour;
+        }
+        for _ in 0..self.strength {
+            let mut indices = sample(rng, num_nodes, 3).into_vec();
+            indices.sort_unstable();
+            perturbed_tour = perturbed_tour.three_opt_swap(indices[0], indices[1], indices[2]);
+        }
+        perturbed_tour
+    }
+}
+
+/// Builds an [`IteratedLocalSearchSolver`] for the TSP.
+/// * The neighborhood is the 3-opt neighborhood, which is explored by the
+///   [`TakeFirst`] improver.
+/// * The perturbation applies 3 random 3-opt moves.
+/// * The default acceptance criterion is used, i.e., only better local optima are accepted.
+/// * The random seed is set to 42 and the iteration limit is set to 5.
+pub fn build(tsp_instance: Arc<TspInstance>) -> IteratedLocalSearchSolver<TspTour> {
+    let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
+    let neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+    let local_improver = Box::new(TakeFirst::new(neighborhood.clone(), objective.clone()));
+    let perturbation = Box::new(RandomThreeOptPerturbation::new(3));
+    IteratedLocalSearchSolver::with_options(
+        neighborhood,
+        perturbation,
+        objective,
+        Some(local_improver),
+        None,
+        Some(42),
+        None,
+        Some(TerminationCriteria::iteration_limit(5)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::{
+        examples::tsp::{
+            neighborhood::ThreeOptNeighborhood, objective::build_tsp_objective,
+            tsp_instance::TspInstance, tsp_tour::TspTour,
+        },
+        heuristics::{
+            local_search::{local_improver::TakeFirst, LocalSearchSolver},
+            Solver,
+        },
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_iterated_local_search() {
+        let tsp_instance = Arc::new(TspInstance::new(vec![
+            vec![0.0, 10.0, 15.0, 20.0],
+            vec![10.0, 0.0, 35.0, 25.0],
+            vec![15.0, 35.0, 0.0, 30.0],
+            vec![20.0, 25.0, 30.0, 0.0],
+        ]));
+
+        let tour = TspTour::new(vec![0, 1, 2, 3], tsp_instance.clone());
+
+        let solver = build(tsp_instance.clone());
+
+        let final_tour = solver.solve(tour);
+
+        assert_eq!(final_tour.solution().get_total_distance(), 80.0);
+    }
+
+    #[test]
+    fn test_iterated_local_search_at_least_as_good_as_local_search() {
+        // the first 15 nodes of berlin52
+        let berlin52 =
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap();
+        let tsp_instance = Arc::new(TspInstance::new(
+            (0..15)
+                .map(|from| (0..15).map(|to| berlin52.get_distance(from, to)).collect())
+                .collect(),
+        ));
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+
+        let objective = Arc::new(build_tsp_objective());
+        let neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+        let local_search_solver = LocalSearchSolver::with_options(
+            neighborhood.clone(),
+            objective.clone(),
+            Some(Box::new(TakeFirst::new(neighborhood, objective))),
+            None,
+            None,
+        );
+        let local_opt_tour = local_search_solver.solve(tour.clone());
+
+        let solver = build(tsp_instance.clone());
+        let final_tour = solver.solve(tour);
+
+        // the first local optimum of the iterated local search is the local optimum of the local
+        // search, so the result is at least as good
+        assert!(
+            final_tour.solution().get_total_distance()
+                <= local_opt_tour.solution().get_total_distance()
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! For the [`MemeticSolver`], we need in addition to the [`Objective`] ([length of the TSP
+//! tour][build_tsp_objective]) and the 3-opt [`ThreeOptNeighborhood`] a [`Crossover`]
+//! ([`OrderCrossover`]), a [`Mutation`] (a few random 3-opt moves, see
+//! [`RandomThreeOptPerturbation`]) and an [`InitialSolutionGenerator`] for the initial population
+//! ([`GreedyRandomizedTours`]).
+//! ```ignore
+//! pub fn build(tsp_instance: Arc<TspInstance>) -> MemeticSolver<TspTour> {
+//!     let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
+//!     let neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+//!     let local_improver = Box::new(TakeFirst::new(neighborhood, objective.clone()));
+//!     MemeticSolver::with_options(
+//!         Box::new(OrderCrossover::new(tsp_instance.clone())),
+//!         Box::new(GreedyRandomizedTours::new(tsp_instance, 0.3)),
+//!         objective,
+//!         6,
+//!         Some(Box::new(RandomThreeOptPerturbation::new(1))),
+//!         Some(OffspringImprover::Sequential(local_improver)),
+//!         None,
+//!         Some(0.5),
+//!         Some(42),
+//!         None,
+//!         Some(TerminationCriteria::iteration_limit(10)),
+//!     )
+//! }
+//! ```
+use super::super::objective::build_tsp_objective;
+use super::super::tsp_instance::TspInstance;
+use super::super::tsp_tour::TspTour;
+use super::iterated_local_search::RandomThreeOptPerturbation;
+use crate::examples::tsp::neighborhood::ThreeOptNeighborhood;
+use crate::heuristics::common::{
+    Crossover, InitialSolutionGenerator, Mutation, TerminationCriteria,
+};
+use crate::heuristics::iterated_local_search::Perturbation;
+use crate::heuristics::local_search::local_improver::TakeFirst;
+use crate::heuristics::memetic::{MemeticSolver, OffspringImprover};
+use crate::objective::Objective;
+use rand::Rng;
+use rand::RngCore;
+use std::sync::Arc;
+
+/// The order crossover (OX) for [`TspTours`][TspTour]: A random segment of the first parent is
+/// copied to the offspring (at the same positions) and the remaining positions are filled with
+/// the missing nodes in the order in which they appear in the second parent.
+pub struct OrderCrossover {
+    tsp_instance: Arc<TspInstance>,
+}
+
+impl OrderCrossover {
+    /// Creates a new [`OrderCrossover`] for tours of the given [`TspInstance`].
+    pub fn new(tsp_instance: Arc<TspInstance>) -> Self {
+        Self { tsp_instance }
+    }
+}
+
+impl Crossover<TspTour> for OrderCrossover {
+    fn crossover(&self, parent1: &TspTour, parent2: &TspTour, rng: &mut dyn RngCore) -> TspTour {
+        let num_nodes = parent1.get_nodes().len();
+        if num_nodes < 2 {
+            return parent1.clone();
+        }
+        let start = rng.gen_range(0..num_nodes);
+        let end = rng.gen_range(start..num_nodes);
+        let segment = &parent1.get_nodes()[start..=end];
+
+        let mut in_segment = vec![false; num_nodes];
+        for &node in segment {
+            in_segment[node] = true;
+        }
+        let mut remaining_nodes = parent2
+            .get_nodes()
+            .iter()
+            .copied()
+            .filter(|&node| !in_segment[node]);
+
+        let nodes = (0..num_nodes)
+            .map(|position| {
+                if (start..=end).contains(&position) {
+                    segment[position - start]
+                } else {
+                    remaining_nodes.next().unwrap()
+                }
+            })
+            .collect();
+        TspTour::new(nodes, self.tsp_instance.clone())
+    }
+}
+
+impl Mutation<TspTour> for RandomThreeOptPerturbation {
+    fn mutate(&self, tour: TspTour, rng: &mut dyn RngCore) -> TspTour {
+        self.perturb(&tour, rng)
+    }
+}
+
+/// Generates the initial population of the [`MemeticSolver`] by the [greedy randomized
+/// construction][TspTour::from_instance_greedy_randomized] seeded by the `start_index`.
+pub struct GreedyRandomizedTours {
+    tsp_instance: Arc<TspInstance>,
+    alpha: f64,
+}
+
+impl GreedyRandomizedTours {
+    /// Creates a new [`GreedyRandomizedTours`] generator with the given `alpha` (between 0 and
+    /// 1) of the restricted candidate list.
+    pub fn new(tsp_instance: Arc<TspInstance>, alpha: f64) -> Self {
+        Self {
+            tsp_instance,
+            alpha,
+        }
+    }
+}
+
+impl InitialSolutionGenerator<TspTour> for GreedyRandomizedTours {
+    fn generate(&self, start_index: usize) -> TspTour {
+        TspTour::from_instance_greedy_randomized(
+            self.tsp_instance.clone(),
+            self.alpha,
+            start_index as u64,
+        )
+    }
+}
+
+/// Builds a [`MemeticSolver`] for the TSP.
+/// * The initial population consists of 6 tours (the initial tour and 5 greedy randomized
+///   tours).
+/// * The offspring are created by the [`OrderCrossover`], mutated with probability 0.5 by a
+///   random 3-opt move, and improved by the [`TakeFirst`] improver on the 3-opt neighborhood.
+/// * The random seed is set to 42 and the iteration limit is set to 10.
+pub fn build(tsp_instance: Arc<TspInstance>) -> MemeticSolver<TspTour> {
+    let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
+    let neighborhood = Arc::new(ThreeOptNeighborhood::new(tsp_instance.clone()));
+    let local_improver = Box::new(TakeFirst::new(neighborhood, objective.clone()));
+    MemeticSolver::with_options(
+        Box::new(OrderCrossover::new(tsp_instance.clone())),
+        Box::new(GreedyRandomizedTours::new(tsp_instance, 0.3)),
+        objective,
+        6,
+        Some(Box::new(RandomThreeOptPerturbation::new(1))),
+        Some(OffspringImprover::Sequential(local_improver)),
+        None,
+        Some(0.5),
+        Some(42),
+        None,
+        Some(TerminationCriteria::iteration_limit(10)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::Solver;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_order_crossover() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let parent1 = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let parent2 = TspTour::from_instance_greedy_randomized(tsp_instance.clone(), 1.0, 7);
+        let crossover = OrderCrossover::new(tsp_instance);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..10 {
+            let offspring = crossover.crossover(&parent1, &parent2, &mut rng);
+            let mut nodes = offspring.get_nodes().clone();
+            nodes.sort();
+            assert_eq!(nodes, (0..52).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_memetic_improves_nearest_neighbor_tour() {
+        // the first 15 nodes of berlin52
+        let berlin52 =
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap();
+        let tsp_instance = Arc::new(TspInstance::new(
+            (0..15)
+                .map(|from| (0..15).map(|to| berlin52.get_distance(from, to)).collect())
+                .collect(),
+        ));
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let initial_distance = tour.get_total_distance();
+
+        let solver = build(tsp_instance);
+        let final_tour = solver.solve(tour);
+
+        assert!(final_tour.solution().get_total_distance() < initial_distance);
+    }
+}
@@ -0,0 +1,107 @@
+//! This module contains a [`TabuSearchSolver`] for the TSP that uses the
+//! [`MemoizedTabuMinimizer`] to reuse the evaluations of 3-opt moves between consecutive
+//! iterations, see the [build] function for details.
+//! ```ignore
+//! pub fn build(tsp_instance: Arc<TspInstance>) -> TabuSearchSolver<TspTour, Tabu> {
+//!     let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
+//!     let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone()));
+//!     let tabu_improver = Box::new(MemoizedTabuMinimizer::new(
+//!         neighborhood.clone(),
+//!         objective.clone(),
+//!     ));
+//!     TabuSearchSolver::with_options(
+//!         neighborhood,
+//!         objective,
+//!         30,
+//!         Some(tabu_improver),
+//!         None,
+//!         Some(TerminationCriteria::iterations_without_improvement(100)),
+//!     )
+//! }
+//! ```
+use std::sync::Arc;
+
+use super::tabu_search::{Tabu, ThreeOptTabuNeighborhood};
+use crate::{
+    examples::tsp::{objective::build_tsp_objective, tsp_instance::TspInstance, tsp_tour::TspTour},
+    heuristics::{
+        common::TerminationCriteria,
+        tabu_search::{tabu_improver::MemoizedTabuMinimizer, TabuSearchSolver},
+    },
+    objective::Objective,
+};
+
+/// Builds a [`TabuSearchSolver`] for the TSP with the [`MemoizedTabuMinimizer`].
+/// * The neighborhood is the 3-opt neighborhood, where each move is identified by its indices.
+/// * A 3-opt move only changes a part of the tour, hence all moves outside of this part are not
+///   evaluated again in the next iteration.
+/// * The tabu list size is set to 30.
+/// * The iteration without global improvement limit is set to 100.
+/// * Gives the same result as the [tabu search][super::tabu_search] with the
+///   [`TabuMinimizer`][crate::heuristics::tabu_search::tabu_improver::TabuMinimizer].
+pub fn build(tsp_instance: Arc<TspInstance>) -> TabuSearchSolver<TspTour, Tabu> {
+    let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
+    let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone()));
+    let tabu_improver = Box::new(MemoizedTabuMinimizer::new(
+        neighborhood.clone(),
+        objective.clone(),
+    ));
+    TabuSearchSolver::with_options(
+        neighborhood,
+        objective,
+        30,
+        Some(tabu_improver),
+        None,
+        Some(TerminationCriteria::iterations_without_improvement(100)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tabu_search, build};
+    use crate::{
+        examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour},
+        heuristics::Solver,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_memoized_tabu_search() {
+        let tsp_instance = Arc::new(TspInstance::new(vec![
+            vec![0.0, 10.0, 15.0, 20.0],
+            vec![10.0, 0.0, 35.0, 25.0],
+            vec![15.0, 35.0, 0.0, 30.0],
+            vec![20.0, 25.0, 30.0, 0.0],
+        ]));
+
+        let tour = TspTour::new(vec![0, 1, 2, 3], tsp_instance.clone());
+
+        let solver = build(tsp_instance.clone());
+
+        let local_opt_tour = solver.solve(tour);
+
+        assert_eq!(local_opt_tour.solution().get_nodes(), &vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_memoized_tabu_search_large_instance() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let solver = build(tsp_instance.clone());
+        let tabu_search_solver = tabu_search::build(tsp_instance);
+
+        // same iterations as the tabu search without memo
+        for (memoized_step, step) in solver
+            .steps(tour.clone())
+            .zip(tabu_search_solver.steps(tour))
+            .take(10)
+        {
+            assert_eq!(
+                memoized_step.solution().get_nodes(),
+                step.solution().get_nodes()
+            );
+        }
+    }
+}
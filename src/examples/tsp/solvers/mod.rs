@@ -1,7 +1,16 @@
 //! This module contains the implementation of several 3-opt local search metaheuristics.
 pub mod basic_local_search;
+pub mod batch_parallel_local_search;
+pub mod delta_tabu_search;
+pub mod iterated_local_search;
+pub mod memetic;
+pub mod memoized_tabu_search;
 pub mod parallel_local_search;
+pub mod parallel_simulated_annealing;
 pub mod parallel_tabu_search;
+pub mod prize_collecting_lns;
+pub mod prize_collecting_local_search;
+pub mod random_key_genetic_algorithm;
 pub mod simulated_annealing;
 pub mod tabu_search;
 pub mod take_first_local_search;
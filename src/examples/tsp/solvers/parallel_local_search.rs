@@ -30,10 +30,17 @@ pub fn build(tsp_instance: Arc<TspInstance>) -> ParallelLocalSearchSolver<TspTou
 
 #[cfg(test)]
 mod tests {
-    use super::build;
+    use super::{
+        build, build_tsp_objective, ParallelLocalSearchSolver, ParallelThreeOptNeighborhood,
+    };
     use crate::{
-        examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour},
-        heuristics::Solver,
+        examples::tsp::{
+            neighborhood::ThreeOptNeighborhood, tsp_instance::TspInstance, tsp_tour::TspTour,
+        },
+        heuristics::{
+            common::ChunkedNeighborhood,
+            parallel_local_search::parallel_local_improver::ParallelMinimizer, Solver,
+        },
     };
     use std::sync::Arc;
 
@@ -74,4 +81,81 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_parallel_local_search_with_task_granularity() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let objective = Arc::new(build_tsp_objective());
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let default_tour = build(tsp_instance.clone()).solve(tour.clone());
+
+        let min_len_tour = ParallelLocalSearchSolver::initialize(
+            Arc::new(ParallelThreeOptNeighborhood::new(tsp_instance.clone()).with_min_len(16)),
+            objective.clone(),
+        )
+        .solve(tour.clone());
+        let chunked_tour = ParallelLocalSearchSolver::initialize(
+            Arc::new(ChunkedNeighborhood::new(
+                Arc::new(ThreeOptNeighborhood::new(tsp_instance)),
+                256,
+            )),
+            objective,
+        )
+        .solve(tour);
+
+        assert_eq!(
+            min_len_tour.objective_value(),
+            default_tour.objective_value()
+        );
+        assert_eq!(
+            chunked_tour.objective_value(),
+            default_tour.objective_value()
+        );
+    }
+
+    #[test]
+    fn test_parallel_local_search_with_dedicated_thread_pool() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let default_tour = build(tsp_instance.clone()).solve(tour.clone());
+
+        let two_threads_tour = build(tsp_instance).with_num_threads(2).solve(tour);
+
+        assert_eq!(
+            two_threads_tour.objective_value(),
+            default_tour.objective_value()
+        );
+    }
+
+    #[test]
+    fn test_parallel_local_search_with_batch_objective() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let objective = Arc::new(build_tsp_objective());
+        let neighborhood = Arc::new(ParallelThreeOptNeighborhood::new(tsp_instance.clone()));
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let default_tour = build(tsp_instance).solve(tour.clone());
+
+        let batched_tour = ParallelLocalSearchSolver::with_options(
+            neighborhood.clone(),
+            objective.clone(),
+            Some(Box::new(
+                ParallelMinimizer::new(neighborhood, objective.clone())
+                    .with_batch_objective(objective, 64),
+            )),
+            None,
+            None,
+        )
+        .solve(tour);
+
+        assert_eq!(
+            batched_tour.objective_value(),
+            default_tour.objective_value()
+        );
+    }
 }
@@ -0,0 +1,110 @@
+//! This module contains the implementation of the [`ParallelSimulatedAnnealingSolver`] for the
+//! TSP, see the [build] function for details.
+//! ```ignore
+//! pub fn build(tsp_instance: Arc<TspInstance>) -> ParallelSimulatedAnnealingSolver<TspTourWithInfo> {
+//!     let average_distance = tsp_instance.get_average_distance();
+//!     let temperatures = vec![
+//!         average_distance / 8.0,
+//!         average_distance / 4.0,
+//!         average_distance / 2.0,
+//!         average_distance,
+//!     ];
+//!
+//!     let neighborhood = Arc::new(RotatedThreeOptNeighborhood::new(tsp_instance));
+//!
+//!     let objective: Arc<Objective<TspTourWithInfo>> =
+//!         Arc::new(build_objective_for_tsp_tour_with_info());
+//!
+//!     ParallelSimulatedAnnealingSolver::with_options(
+//!         neighborhood,
+//!         objective,
+//!         temperatures,
+//!         0.9,
+//!         distance_acceptance_probability_function(),
+//!         50,
+//!         Some(13), // random_seed
+//!         None,
+//!         Some(TerminationCriteria::iteration_limit(1000)),
+//!     )
+//! }
+//! ```
+use super::simulated_annealing::distance_acceptance_probability_function;
+use crate::examples::tsp::tsp_instance::TspInstance;
+use crate::examples::tsp::tsp_tour_with_info::neighborhood::RotatedThreeOptNeighborhood;
+use crate::examples::tsp::tsp_tour_with_info::objective::build_objective_for_tsp_tour_with_info;
+use crate::examples::tsp::tsp_tour_with_info::TspTourWithInfo;
+use crate::heuristics::common::TerminationCriteria;
+use crate::heuristics::parallel_simulated_annealing::ParallelSimulatedAnnealingSolver;
+use crate::objective::Objective;
+use std::sync::Arc;
+
+/// Builds a [`ParallelSimulatedAnnealingSolver`] for the TSP.
+/// * The neighborhood and the acceptance probability function are the same as for the
+///   [simulated annealing solver][super::simulated_annealing::build].
+/// * There are four chains, whose initial temperatures are 1/8, 1/4, 1/2 and 1 times the
+///   average distance between two nodes. Each temperature is reduced by 0.9 whenever its chain
+///   accepts a worse neighbor.
+/// * Every 50 iterations, all chains continue from the best tour found so far.
+/// * The iteration limit (per chain) is set to 1000 and we set a random seed to have reproducible
+///   results.
+pub fn build(tsp_instance: Arc<TspInstance>) -> ParallelSimulatedAnnealingSolver<TspTourWithInfo> {
+    let average_distance = tsp_instance.get_average_distance();
+    let temperatures = vec![
+        average_distance / 8.0,
+        average_distance / 4.0,
+        average_distance / 2.0,
+        average_distance,
+    ];
+
+    let neighborhood = Arc::new(RotatedThreeOptNeighborhood::new(tsp_instance));
+
+    let objective: Arc<Objective<TspTourWithInfo>> =
+        Arc::new(build_objective_for_tsp_tour_with_info());
+
+    ParallelSimulatedAnnealingSolver::with_options(
+        neighborhood,
+        objective,
+        temperatures,
+        0.9,
+        distance_acceptance_probability_function(),
+        50,
+        Some(13), // random_seed
+        None,
+        Some(TerminationCriteria::iteration_limit(1000)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::{
+        examples::tsp::{
+            tsp_instance::TspInstance, tsp_tour::TspTour, tsp_tour_with_info::TspTourWithInfo,
+        },
+        heuristics::Solver,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parallel_simulated_annealing_improves_nearest_neighbor_tour() {
+        // the first 15 nodes of berlin52
+        let berlin52 =
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap();
+        let tsp_instance = Arc::new(TspInstance::new(
+            (0..15)
+                .map(|from| (0..15).map(|to| berlin52.get_distance(from, to)).collect())
+                .collect(),
+        ));
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let initial_distance = tour.get_total_distance();
+        let solver = build(tsp_instance.clone());
+
+        let final_tour = solver.solve(TspTourWithInfo::new(tour, 0));
+
+        let final_tour = final_tour.unwrap().unwrap();
+        let mut nodes = final_tour.get_nodes().clone();
+        nodes.sort();
+        assert_eq!(nodes, (0..15).collect::<Vec<_>>());
+        assert!(final_tour.get_total_distance() < initial_distance);
+    }
+}
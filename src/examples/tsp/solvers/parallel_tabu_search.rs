@@ -1,9 +1,14 @@
 //! This module contains the implementation of the [`ParallelTabuSearchSolver`] for the TSP, see
 //! the [build] function for details.
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::Range,
+    sync::Arc,
+};
 
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+use super::tabu_search::{create_tabus, Tabu};
 use crate::{
     examples::tsp::{
         objective::build_tsp_objective, tsp_instance::TspInstance, tsp_tour::TspTour, NodeIdx,
@@ -12,59 +17,78 @@ use crate::{
     objective::Objective,
 };
 
-/// A tabu consisits of a directed arc between two nodes. Neighbors that would insert this arc are
-/// tabu.
-#[derive(Debug)]
-pub struct Tabu {
-    start: NodeIdx,
-    end: NodeIdx,
-}
-
-impl Tabu {
-    /// Checks if the given 3-opt move is tabu.
-    pub fn is_tabu(&self, i: usize, j: usize, k: usize, tour: &TspTour) -> bool {
-        let n = tour.get_nodes().len();
-        (self.start == *tour.get_nodes().get(i).unwrap()
-            && self.end == *tour.get_nodes().get(j + 1).unwrap())
-            || (self.start == *tour.get_nodes().get(j).unwrap()
-                && self.end == *tour.get_nodes().get((k + 1) % n).unwrap())
-            || (self.start == *tour.get_nodes().get(k).unwrap()
-                && self.end == *tour.get_nodes().get(i + 1).unwrap())
-    }
-
-    /// Creates for a given 3-opt move the three tabus corresponding to the arcs that are removed
-    /// by the move.
-    pub fn create_tabus(i: usize, j: usize, k: usize, tour: &TspTour) -> Vec<Tabu> {
-        let n = tour.get_nodes().len();
-        vec![
-            Tabu {
-                start: *tour.get_nodes().get(i).unwrap(),
-                end: *tour.get_nodes().get(i + 1).unwrap(),
-            },
-            Tabu {
-                start: *tour.get_nodes().get(j).unwrap(),
-                end: *tour.get_nodes().get(j + 1).unwrap(),
-            },
-            Tabu {
-                start: *tour.get_nodes().get(k).unwrap(),
-                end: *tour.get_nodes().get((k + 1) % n).unwrap(),
-            },
-        ]
-    }
-}
-
 /// A 3-opt [`ParallelTabuNeighborhood`] for the TSP.
 /// For a given tour and a tabu list, all 3-opt moves are generated as a [`ParallelIterator`],
-/// all moves that are tabu (i.e., that would insert a tabu arc) are filtered out.
+/// all moves that are tabu (i.e., that would insert a tabu arc) are filtered out (or flagged as
+/// tabu for the aspiration criterion).
 /// Each 3-opt move is equipped with three tabus, one for each arc that is removed by the move.
+/// * With [candidate lists][ParallelThreeOptTabuNeighborhood::with_candidate_lists], only moves
+///   whose first new arc connects a node with one of its nearest nodes are generated. This
+///   reduces the neighborhood from O(n³) to O(n² · number of candidates) moves.
+/// * The moves are partitioned into chunks of at most [`MOVES_PER_CHUNK`] moves, such that the
+///   threads get balanced work (instead of one task per first index, which are very uneven).
+/// * Moves are filtered as early as possible: If the first new arc (which only depends on the
+///   first two indices) is tabu, the whole chunk is not created.
 pub struct ParallelThreeOptTabuNeighborhood {
     tsp_instance: Arc<TspInstance>,
+    candidates: Option<Vec<Vec<NodeIdx>>>,
 }
 
+/// The maximal number of 3-opt moves of a chunk that is processed by a single rayon task.
+pub const MOVES_PER_CHUNK: usize = 64;
+
 impl ParallelThreeOptTabuNeighborhood {
-    /// Creates a new [`ParallelThreeOptTabuNeighborhood`] for the given [`TspInstance`].
+    /// Creates a new [`ParallelThreeOptTabuNeighborhood`] for the given [`TspInstance`], which
+    /// generates all 3-opt moves.
     pub fn new(tsp_instance: Arc<TspInstance>) -> Self {
-        Self { tsp_instance }
+        Self {
+            tsp_instance,
+            candidates: None,
+        }
+    }
+
+    /// Creates a new [`ParallelThreeOptTabuNeighborhood`] for the given [`TspInstance`], which
+    /// only generates 3-opt moves `(i, j, k)` where the node at index `j+1` is one of the
+    /// `number_of_candidates` nearest nodes of the node at index `i` (i.e., the new arc between
+    /// them is short).
+    pub fn with_candidate_lists(
+        tsp_instance: Arc<TspInstance>,
+        number_of_candidates: usize,
+    ) -> Self {
+        let candidates = (0..tsp_instance.get_number_of_nodes())
+            .map(|node| tsp_instance.get_nearest_nodes(node, number_of_candidates))
+            .collect();
+        Self {
+            tsp_instance,
+            candidates: Some(candidates),
+        }
+    }
+
+    /// Returns all pairs `(i, j)` of the first two indices of the 3-opt moves of the `tour`
+    /// (restricted by the candidate lists if present).
+    fn index_pairs(&self, tour: &TspTour) -> Vec<(usize, usize)> {
+        let num_nodes = self.tsp_instance.get_number_of_nodes();
+        match &self.candidates {
+            None => (0..num_nodes - 2)
+                .flat_map(|i| (i + 1..num_nodes - 1).map(move |j| (i, j)))
+                .collect(),
+            Some(candidates) => {
+                let mut position = vec![0; num_nodes];
+                for (idx, &node) in tour.get_nodes().iter().enumerate() {
+                    position[node] = idx;
+                }
+                (0..num_nodes - 2)
+                    .flat_map(|i| {
+                        let position = &position;
+                        candidates[tour.get_nodes()[i]]
+                            .iter()
+                            .map(move |&candidate| position[candidate])
+                            .filter(move |&candidate_position| candidate_position >= i + 2)
+                            .map(move |candidate_position| (i, candidate_position - 1))
+                    })
+                    .collect()
+            }
+        }
     }
 }
 
@@ -75,30 +99,80 @@ impl ParallelTabuNeighborhood<TspTour, Tabu> for ParallelThreeOptTabuNeighborhoo
         tabu_list: &'a VecDeque<Tabu>,
     ) -> impl ParallelIterator<Item = (TspTour, Vec<Tabu>)> + 'a {
         let num_nodes = self.tsp_instance.get_number_of_nodes();
-        (0..num_nodes - 2)
-            .into_par_iter()
-            .flat_map(move |i| (i + 1..num_nodes - 1).into_par_iter().map(move |j| (i, j)))
-            .flat_map(move |(i, j)| (j + 1..num_nodes).into_par_iter().map(move |k| (i, j, k)))
-            .filter_map(move |(i, j, k)| {
-                if tabu_list.iter().any(|tabu| tabu.is_tabu(i, j, k, tour)) {
-                    return None;
-                }
-                Some((
+        let nodes = tour.get_nodes();
+        let tabu_arcs: Arc<HashSet<(NodeIdx, NodeIdx)>> = Arc::new(
+            tabu_list
+                .iter()
+                .map(|tabu| (tabu.start(), tabu.end()))
+                .collect(),
+        );
+        let chunks: Vec<(usize, usize, Range<usize>)> = self
+            .index_pairs(tour)
+            .into_iter()
+            .filter(|&(i, j)| !tabu_arcs.contains(&(nodes[i], nodes[j + 1])))
+            .flat_map(|(i, j)| {
+                (j + 1..num_nodes)
+                    .step_by(MOVES_PER_CHUNK)
+                    .map(move |start| (i, j, start..(start + MOVES_PER_CHUNK).min(num_nodes)))
+            })
+            .collect();
+        chunks.into_par_iter().flat_map_iter(move |(i, j, ks)| {
+            let tabu_arcs = tabu_arcs.clone();
+            ks.filter(move |&k| {
+                !tabu_arcs.contains(&(nodes[j], nodes[(k + 1) % num_nodes]))
+                    && !tabu_arcs.contains(&(nodes[k], nodes[i + 1]))
+            })
+            .map(move |k| (tour.three_opt_swap(i, j, k), create_tabus(i, j, k, tour)))
+        })
+    }
+
+    /// All chunks are created, a move is tabu if one of its three new arcs is tabu.
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        tour: &'a TspTour,
+        tabu_list: &'a VecDeque<Tabu>,
+    ) -> impl ParallelIterator<Item = (TspTour, Vec<Tabu>, bool)> + 'a {
+        let num_nodes = self.tsp_instance.get_number_of_nodes();
+        let nodes = tour.get_nodes();
+        let tabu_arcs: Arc<HashSet<(NodeIdx, NodeIdx)>> = Arc::new(
+            tabu_list
+                .iter()
+                .map(|tabu| (tabu.start(), tabu.end()))
+                .collect(),
+        );
+        let chunks: Vec<(usize, usize, Range<usize>)> = self
+            .index_pairs(tour)
+            .into_iter()
+            .flat_map(|(i, j)| {
+                (j + 1..num_nodes)
+                    .step_by(MOVES_PER_CHUNK)
+                    .map(move |start| (i, j, start..(start + MOVES_PER_CHUNK).min(num_nodes)))
+            })
+            .collect();
+        chunks.into_par_iter().flat_map_iter(move |(i, j, ks)| {
+            let tabu_arcs = tabu_arcs.clone();
+            ks.map(move |k| {
+                let is_tabu = tabu_arcs.contains(&(nodes[i], nodes[j + 1]))
+                    || tabu_arcs.contains(&(nodes[j], nodes[(k + 1) % num_nodes]))
+                    || tabu_arcs.contains(&(nodes[k], nodes[i + 1]));
+                (
                     tour.three_opt_swap(i, j, k),
-                    Tabu::create_tabus(i, j, k, tour),
-                ))
+                    create_tabus(i, j, k, tour),
+                    is_tabu,
+                )
             })
+        })
     }
 }
 
 /// Builds a [`ParallelTabuSearchSolver`] for the TSP.
 /// * The neighborhood is the 3-opt neighborhood, i.e., the neighborhood that consists of
-/// all tours that can be obtained by applying the 3-opt operation.
+///   all tours that can be obtained by applying the 3-opt operation.
 /// * The tabu list size is set to 30.
 /// * The iteration without global improvement limit is set to 100, i.e., the search stops if no
-/// global improvement is found for 100 iterations.
+///   global improvement is found for 100 iterations.
 /// * Takes the default ['ParallelTabuImprover`] [`ParallelTabuMinimizer`] which returns the best non-tabu neighbor
-/// while using parallelism.
+///   while using parallelism.
 pub fn build(tsp_instance: Arc<TspInstance>) -> ParallelTabuSearchSolver<TspTour, Tabu> {
     let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
     let neighborhood = Arc::new(ParallelThreeOptTabuNeighborhood::new(tsp_instance.clone()));
@@ -113,14 +187,41 @@ pub fn build(tsp_instance: Arc<TspInstance>) -> ParallelTabuSearchSolver<TspTour
     )
 }
 
+/// Builds a [`ParallelTabuSearchSolver`] for the TSP as [build], but the neighborhood only
+/// contains the 3-opt moves allowed by the candidate lists of the `number_of_candidates` nearest
+/// nodes (see [`ParallelThreeOptTabuNeighborhood::with_candidate_lists`]).
+pub fn build_with_candidate_lists(
+    tsp_instance: Arc<TspInstance>,
+    number_of_candidates: usize,
+) -> ParallelTabuSearchSolver<TspTour, Tabu> {
+    let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
+    let neighborhood = Arc::new(ParallelThreeOptTabuNeighborhood::with_candidate_lists(
+        tsp_instance.clone(),
+        number_of_candidates,
+    ));
+    let tabu_list_size = 30;
+    let iteration_without_global_improvement_limit = 100;
+
+    ParallelTabuSearchSolver::initialize(
+        neighborhood,
+        objective,
+        tabu_list_size,
+        iteration_without_global_improvement_limit,
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::build;
+    use super::{build, build_with_candidate_lists, ParallelThreeOptTabuNeighborhood};
+    use crate::examples::tsp::solvers::tabu_search::{create_tabus, is_tabu};
     use crate::{
-        examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour},
-        heuristics::Solver,
+        examples::tsp::{
+            objective::build_tsp_objective, tsp_instance::TspInstance, tsp_tour::TspTour,
+        },
+        heuristics::{parallel_tabu_search::ParallelTabuNeighborhood, Solver},
     };
-    use std::sync::Arc;
+    use rayon::iter::ParallelIterator;
+    use std::{collections::VecDeque, sync::Arc};
 
     #[test]
     fn test_parallel_tabu_search() {
@@ -153,10 +254,55 @@ mod tests {
         assert_eq!(
             local_opt_tour.solution().get_nodes(),
             &vec![
-                0, 35, 38, 39, 36, 37, 23, 47, 4, 14, 5, 3, 24, 45, 43, 33, 34, 48, 31, 44, 18, 40,
+                0, 35, 38, 39, 36, 37, 47, 23, 4, 14, 5, 3, 24, 45, 43, 33, 34, 48, 31, 44, 18, 40,
                 7, 8, 9, 42, 32, 50, 11, 27, 26, 12, 13, 51, 10, 25, 46, 28, 15, 49, 19, 22, 29, 1,
                 6, 41, 20, 16, 2, 17, 30, 21
             ]
         );
     }
+
+    #[test]
+    fn test_candidate_lists_restrict_neighborhood() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let tabu_list = VecDeque::from(create_tabus(0, 1, 2, &tour));
+        let number_of_neighbors = |neighborhood: ParallelThreeOptTabuNeighborhood| {
+            neighborhood.neighbors_of(&tour, &tabu_list).count()
+        };
+
+        let all = number_of_neighbors(ParallelThreeOptTabuNeighborhood::new(tsp_instance.clone()));
+        let all_candidates = number_of_neighbors(
+            ParallelThreeOptTabuNeighborhood::with_candidate_lists(tsp_instance.clone(), 51),
+        );
+        let five_candidates = number_of_neighbors(
+            ParallelThreeOptTabuNeighborhood::with_candidate_lists(tsp_instance.clone(), 5),
+        );
+
+        let non_tabu = (0..50)
+            .flat_map(|i| (i + 1..51).flat_map(move |j| (j + 1..52).map(move |k| (i, j, k))))
+            .filter(|&(i, j, k)| !is_tabu(&tabu_list, i, j, k, &tour))
+            .count();
+        assert_eq!(all, non_tabu);
+        assert_eq!(all_candidates, non_tabu);
+        assert!(five_candidates < non_tabu / 5);
+    }
+
+    #[test]
+    fn test_parallel_tabu_search_with_candidate_lists() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let initial_objective_value = build_tsp_objective()
+            .evaluate(tour.clone())
+            .objective_value()
+            .clone();
+        let solver = build_with_candidate_lists(tsp_instance.clone(), 8);
+
+        let local_opt_tour = solver.solve(tour);
+
+        assert!(local_opt_tour.objective_value() < &initial_objective_value);
+    }
 }
@@ -0,0 +1,76 @@
+//! For the [prize-collecting variant][crate::examples::tsp::prize_collecting] of the TSP, a
+//! [`LnsSolver`] maximizes the collected prizes minus the travel cost by repeatedly removing
+//! random nodes ([`RandomRemoval`]) and greedily inserting profitable nodes
+//! ([`GreedyInsertion`]).
+//! ```ignore
+//! pub fn build() -> LnsSolver<PrizeCollectingTour> {
+//!     let objective = Arc::new(build_prize_collecting_objective());
+//!     let iteration_limit = 100;
+//!     let random_seed = Some(42);
+//!     LnsSolver::initialize(
+//!         vec![Box::new(RandomRemoval::new(3))],
+//!         vec![Box::new(GreedyInsertion)],
+//!         objective,
+//!         iteration_limit,
+//!         random_seed,
+//!     )
+//! }
+//! ```
+use crate::examples::tsp::prize_collecting::objective::build_prize_collecting_objective;
+use crate::examples::tsp::prize_collecting::operators::{GreedyInsertion, RandomRemoval};
+use crate::examples::tsp::prize_collecting::PrizeCollectingTour;
+use crate::heuristics::large_neighborhood_search::LnsSolver;
+use std::sync::Arc;
+
+/// Builds a [`LnsSolver`] for the prize-collecting TSP.
+/// * In each iteration, up to 3 random nodes are removed and the tour is repaired by greedy
+///   insertion.
+/// * The iteration limit is set to 100.
+/// * The random seed is set to 42 to make the search reproducible.
+pub fn build() -> LnsSolver<PrizeCollectingTour> {
+    let objective = Arc::new(build_prize_collecting_objective());
+    let iteration_limit = 100;
+    let random_seed = Some(42);
+    LnsSolver::initialize(
+        vec![Box::new(RandomRemoval::new(3))],
+        vec![Box::new(GreedyInsertion)],
+        objective,
+        iteration_limit,
+        random_seed,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::{
+        examples::tsp::{
+            prize_collecting::{PrizeCollectingTour, PrizeCollectingTspInstance},
+            tsp_instance::TspInstance,
+        },
+        heuristics::Solver,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_prize_collecting_lns() {
+        let tsp_instance = Arc::new(TspInstance::new(vec![
+            vec![0.0, 10.0, 15.0, 20.0],
+            vec![10.0, 0.0, 35.0, 25.0],
+            vec![15.0, 35.0, 0.0, 30.0],
+            vec![20.0, 25.0, 30.0, 0.0],
+        ]));
+        let instance = Arc::new(PrizeCollectingTspInstance::new(
+            tsp_instance,
+            vec![0.0, 30.0, 5.0, 40.0],
+        ));
+
+        let solver = build();
+
+        let final_tour = solver.solve(PrizeCollectingTour::depot_only(instance));
+
+        assert_eq!(final_tour.solution().get_nodes(), &vec![0, 3, 1]);
+        assert_eq!(final_tour.solution().get_total_prize(), 70.0);
+        assert_eq!(final_tour.solution().get_total_distance(), 55.0);
+    }
+}
@@ -0,0 +1,60 @@
+//! For the [prize-collecting variant][crate::examples::tsp::prize_collecting] of the TSP, a
+//! [`LocalSearchSolver`] maximizes the collected prizes minus the travel cost by minimizing the
+//! [negated objective][build_prize_collecting_objective] over the
+//! [add/drop neighborhood][AddDropNeighborhood].
+//! ```ignore
+//! pub fn build() -> LocalSearchSolver<PrizeCollectingTour> {
+//!     let objective = Arc::new(build_prize_collecting_objective());
+//!     let neighborhood = Arc::new(AddDropNeighborhood);
+//!     LocalSearchSolver::initialize(neighborhood, objective)
+//! }
+//! ```
+use crate::examples::tsp::prize_collecting::neighborhood::AddDropNeighborhood;
+use crate::examples::tsp::prize_collecting::objective::build_prize_collecting_objective;
+use crate::examples::tsp::prize_collecting::PrizeCollectingTour;
+use crate::heuristics::local_search::LocalSearchSolver;
+use std::sync::Arc;
+
+/// Builds a [`LocalSearchSolver`] for the prize-collecting TSP with the default
+/// [`LocalImprover`][`crate::heuristics::local_search::local_improver::LocalImprover`]
+/// [`Minimizer`][`crate::heuristics::local_search::local_improver::Minimizer`].
+pub fn build() -> LocalSearchSolver<PrizeCollectingTour> {
+    let objective = Arc::new(build_prize_collecting_objective());
+    let neighborhood = Arc::new(AddDropNeighborhood);
+    LocalSearchSolver::initialize(neighborhood, objective)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::{
+        examples::tsp::{
+            prize_collecting::{PrizeCollectingTour, PrizeCollectingTspInstance},
+            tsp_instance::TspInstance,
+        },
+        heuristics::Solver,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_prize_collecting_local_search() {
+        let tsp_instance = Arc::new(TspInstance::new(vec![
+            vec![0.0, 10.0, 15.0, 20.0],
+            vec![10.0, 0.0, 35.0, 25.0],
+            vec![15.0, 35.0, 0.0, 30.0],
+            vec![20.0, 25.0, 30.0, 0.0],
+        ]));
+        let instance = Arc::new(PrizeCollectingTspInstance::new(
+            tsp_instance,
+            vec![0.0, 30.0, 5.0, 40.0],
+        ));
+
+        let solver = build();
+
+        let final_tour = solver.solve(PrizeCollectingTour::depot_only(instance));
+
+        assert_eq!(final_tour.solution().get_nodes(), &vec![0, 3, 1]);
+        assert_eq!(final_tour.solution().get_total_prize(), 70.0);
+        assert_eq!(final_tour.solution().get_total_distance(), 55.0);
+    }
+}
@@ -0,0 +1,94 @@
+//! For a genetic algorithm on [`RandomKeys`], we only need a [`KeyDecoder`] that decodes the
+//! keys into a [`TspTour`] ([`TspKeyDecoder`], visiting the nodes in the order of their keys).
+//! The [`Crossover`][crate::heuristics::common::Crossover] ([`UniformKeyCrossover`]), the
+//! [`Mutation`][crate::heuristics::common::Mutation] ([`KeyResampling`]) and the initial
+//! population ([`RandomKeysGenerator`]) are generic.
+//! ```ignore
+//! pub fn build(tsp_instance: Arc<TspInstance>) -> MemeticSolver<RandomKeys<TspTour>> {
+//!     let number_of_nodes = tsp_instance.get_number_of_nodes();
+//!     let decoder: Arc<dyn KeyDecoder<TspTour>> = Arc::new(TspKeyDecoder::new(tsp_instance));
+//!     MemeticSolver::genetic_algorithm(
+//!         Box::new(UniformKeyCrossover::new(decoder.clone(), 0.7)),
+//!         Box::new(KeyResampling::new(decoder.clone(), 0.02)),
+//!         Box::new(RandomKeysGenerator::new(decoder, number_of_nodes)),
+//!         Arc::new(build_tsp_random_keys_objective()),
+//!         20,
+//!         500,
+//!         Some(42),
+//!     )
+//! }
+//! ```
+use super::super::objective::build_tsp_random_keys_objective;
+use super::super::tsp_instance::TspInstance;
+use super::super::tsp_tour::TspTour;
+use crate::heuristics::common::{
+    permutation_from_keys, KeyDecoder, KeyResampling, RandomKeys, RandomKeysGenerator,
+    UniformKeyCrossover,
+};
+use crate::heuristics::memetic::MemeticSolver;
+use std::sync::Arc;
+
+/// Decodes random keys (one per node) into the [`TspTour`] that visits the nodes in ascending
+/// order of their keys (see [`permutation_from_keys`]).
+pub struct TspKeyDecoder {
+    tsp_instance: Arc<TspInstance>,
+}
+
+impl TspKeyDecoder {
+    /// Creates a new [`TspKeyDecoder`] for tours of the given [`TspInstance`].
+    pub fn new(tsp_instance: Arc<TspInstance>) -> Self {
+        Self { tsp_instance }
+    }
+}
+
+impl KeyDecoder<TspTour> for TspKeyDecoder {
+    fn decode(&self, keys: &[f64]) -> TspTour {
+        TspTour::new(permutation_from_keys(keys), self.tsp_instance.clone())
+    }
+}
+
+/// Builds a genetic algorithm on [`RandomKeys`] for the TSP.
+/// * The initial population consists of 20 uniformly drawn key vectors (the keys of the initial
+///   solution and 19 generated ones).
+/// * The offspring are created by the [`UniformKeyCrossover`] with bias 0.7 and each key is
+///   redrawn with probability 0.02 by the [`KeyResampling`].
+/// * The random seed is set to 42 and the iteration limit is set to 500.
+pub fn build(tsp_instance: Arc<TspInstance>) -> MemeticSolver<RandomKeys<TspTour>> {
+    let number_of_nodes = tsp_instance.get_number_of_nodes();
+    let decoder: Arc<dyn KeyDecoder<TspTour>> = Arc::new(TspKeyDecoder::new(tsp_instance));
+    MemeticSolver::genetic_algorithm(
+        Box::new(UniformKeyCrossover::new(decoder.clone(), 0.7)),
+        Box::new(KeyResampling::new(decoder.clone(), 0.02)),
+        Box::new(RandomKeysGenerator::new(decoder, number_of_nodes)),
+        Arc::new(build_tsp_random_keys_objective()),
+        20,
+        500,
+        Some(42),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::InitialSolutionGenerator;
+    use crate::heuristics::Solver;
+
+    #[test]
+    fn test_random_key_genetic_algorithm() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let decoder: Arc<dyn KeyDecoder<TspTour>> =
+            Arc::new(TspKeyDecoder::new(tsp_instance.clone()));
+        let initial_keys = RandomKeysGenerator::new(decoder, 52).generate(0);
+        let initial_distance = initial_keys.decoded().get_total_distance();
+
+        let solver = build(tsp_instance);
+        let final_keys = solver.solve(initial_keys).unwrap();
+
+        let mut nodes = final_keys.decoded().get_nodes().clone();
+        nodes.sort();
+        assert_eq!(nodes, (0..52).collect::<Vec<_>>());
+        assert!(final_keys.decoded().get_total_distance() < initial_distance);
+    }
+}
@@ -2,30 +2,9 @@
 //! the [build] function for details.
 //! ```ignore
 //! pub fn build(tsp_instance: Arc<TspInstance>) -> SimulatedAnnealingSolver<TspTourWithInfo> {
-//!     let node_count = tsp_instance.get_number_of_nodes();
-//!     let average_distance: Distance = (0..node_count)
-//!         .flat_map(|i| (0..node_count).filter_map(move |j| if i != j { Some((i, j)) } else { None }))
-//!         .map(|(i, j)| tsp_instance.get_distance(i, j))
-//!         .sum::<Distance>()
-//!         / (node_count * (node_count - 1)) as Distance;
+//!     let average_distance = tsp_instance.get_average_distance();
 //!
-//!     let acceptance_probability_function = Box::new(
-//!         |current_objective_value: &ObjectiveValue,
-//!          new_objective_value: &ObjectiveValue,
-//!          temperature: Temperature| {
-//!             if new_objective_value < current_objective_value {
-//!                 1.0
-//!             } else {
-//!                 let current_total_distance = current_objective_value
-//!                     .iter()
-//!                     .next()
-//!                     .unwrap()
-//!                     .unwrap_float();
-//!                 let new_total_distance = new_objective_value.iter().next().unwrap().unwrap_float();
-//!                 ((current_total_distance - new_total_distance) / temperature).exp()
-//!             }
-//!         },
-//!     );
+//!     let acceptance_probability_function = distance_acceptance_probability_function();
 //!
 //!     let initial_temperature = average_distance;
 //!
@@ -48,34 +27,17 @@ use crate::examples::tsp::tsp_instance::TspInstance;
 use crate::examples::tsp::tsp_tour_with_info::neighborhood::RotatedThreeOptNeighborhood;
 use crate::examples::tsp::tsp_tour_with_info::objective::build_objective_for_tsp_tour_with_info;
 use crate::examples::tsp::tsp_tour_with_info::TspTourWithInfo;
-use crate::examples::tsp::Distance;
-use crate::heuristics::simulated_annealing::{SimulatedAnnealingSolver, Temperature};
+use crate::heuristics::simulated_annealing::{
+    AcceptanceProbabilityFunction, SimulatedAnnealingSolver, Temperature,
+};
 use crate::objective::{Objective, ObjectiveValue};
 use std::sync::Arc;
 
-/// Builds a [`SimulatedAnnealingSolver`] for the TSP.
-/// * The neighborhood is the [3-opt neighborhood][RotatedThreeOptNeighborhood], i.e., the neighborhood that consists of
-/// all tours that can be obtained by applying the 3-opt operation.
-/// * Since starting each neighborhood with the index (0, 1, 2) leads to back and forth moves,
-/// the [`TspTour`][`super::super::tsp_tour::TspTour`] is wrapped in a [`TspTourWithInfo`] to store
-/// the first index of the last move. The next move then starts with the first index one after the
-/// first index of the previous move, which means that the backwards move appears very late in the
-/// neighborhood iterator.
-/// * The initial temperature is set to the average distance between two nodes.
-/// * The acceptance probability function is an exponential function that accepts worse solutions
-/// with a probability given by the formula e<sup>-∆f/T</sup>, where ∆f is the difference in
-/// objective value and T is the current temperature.
-/// * The cooling factor is set to 0.9.
-/// * We set a random seed to have reproducible results.
-pub fn build(tsp_instance: Arc<TspInstance>) -> SimulatedAnnealingSolver<TspTourWithInfo> {
-    let node_count = tsp_instance.get_number_of_nodes();
-    let average_distance: Distance = (0..node_count)
-        .flat_map(|i| (0..node_count).filter_map(move |j| if i != j { Some((i, j)) } else { None }))
-        .map(|(i, j)| tsp_instance.get_distance(i, j))
-        .sum::<Distance>()
-        / (node_count * (node_count - 1)) as Distance;
-
-    let acceptance_probability_function = Box::new(
+/// Returns the [`AcceptanceProbabilityFunction`] that accepts a worse tour with probability
+/// e<sup>-∆f/T</sup>, where ∆f is the increase of the total distance and T is the current
+/// temperature. Better tours are always accepted.
+pub fn distance_acceptance_probability_function() -> AcceptanceProbabilityFunction {
+    Box::new(
         |current_objective_value: &ObjectiveValue,
          new_objective_value: &ObjectiveValue,
          temperature: Temperature| {
@@ -91,7 +53,28 @@ pub fn build(tsp_instance: Arc<TspInstance>) -> SimulatedAnnealingSolver<TspTour
                 ((current_total_distance - new_total_distance) / temperature).exp()
             }
         },
-    );
+    )
+}
+
+/// Builds a [`SimulatedAnnealingSolver`] for the TSP.
+/// * The neighborhood is the [3-opt neighborhood][RotatedThreeOptNeighborhood], i.e., the neighborhood that consists of
+///   all tours that can be obtained by applying the 3-opt operation.
+/// * Since starting each neighborhood with the index (0, 1, 2) leads to back and forth moves,
+///   the [`TspTour`][`super::super::tsp_tour::TspTour`] is wrapped in a [`TspTourWithInfo`] to store
+///   the first index of the last move. The next move then starts with the first index one after the
+///   first index of the previous move, which means that the backwards move appears very late in the
+///   neighborhood iterator.
+/// * The initial temperature is set to the average distance between two nodes.
+/// * The acceptance probability function is an exponential function that accepts worse solutions
+///   with a probability given by the formula e<sup>-∆f/T</sup>, where ∆f is the difference in
+///   objective value and T is the current temperature (see
+///   [`distance_acceptance_probability_function`]).
+/// * The cooling factor is set to 0.9.
+/// * We set a random seed to have reproducible results.
+pub fn build(tsp_instance: Arc<TspInstance>) -> SimulatedAnnealingSolver<TspTourWithInfo> {
+    let average_distance = tsp_instance.get_average_distance();
+
+    let acceptance_probability_function = distance_acceptance_probability_function();
 
     let initial_temperature = average_distance;
 
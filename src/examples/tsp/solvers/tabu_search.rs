@@ -6,54 +6,36 @@ use crate::{
     examples::tsp::{
         objective::build_tsp_objective, tsp_instance::TspInstance, tsp_tour::TspTour, NodeIdx,
     },
-    heuristics::tabu_search::{TabuNeighborhood, TabuSearchSolver},
+    heuristics::tabu_search::{
+        permutation_tabu::ArcTabu, TabuMoveNeighborhood, TabuNeighborhood, TabuSearchSolver,
+    },
     objective::Objective,
 };
 
-/// A tabu consisits of a directed arc between two nodes. Neighbors that would insert this arc are
-/// tabu.
-#[derive(Debug)]
-pub struct Tabu {
-    start: NodeIdx,
-    end: NodeIdx,
-}
+/// A tabu consists of a directed arc between two nodes. Neighbors that would insert this arc are
+/// tabu (see [`ArcTabu`]).
+pub type Tabu = ArcTabu<NodeIdx>;
 
-impl Tabu {
-    /// Checks if the given 3-opt move is tabu.
-    pub fn is_tabu(&self, i: usize, j: usize, k: usize, tour: &TspTour) -> bool {
-        let n = tour.get_nodes().len();
-        (self.start == *tour.get_nodes().get(i).unwrap()
-            && self.end == *tour.get_nodes().get(j + 1).unwrap())
-            || (self.start == *tour.get_nodes().get(j).unwrap()
-                && self.end == *tour.get_nodes().get((k + 1) % n).unwrap())
-            || (self.start == *tour.get_nodes().get(k).unwrap()
-                && self.end == *tour.get_nodes().get(i + 1).unwrap())
-    }
+/// Checks if the given 3-opt move is tabu, i.e., if it would insert one of the tabu arcs
+/// `(i, j+1)`, `(j, k+1)` or `(k, i+1)`.
+pub fn is_tabu(tabu_list: &VecDeque<Tabu>, i: usize, j: usize, k: usize, tour: &TspTour) -> bool {
+    ArcTabu::any_forbids(
+        tabu_list,
+        tour.get_nodes(),
+        &[(i, j + 1), (j, k + 1), (k, i + 1)],
+    )
+}
 
-    /// Creates for a given 3-opt move the three tabus corresponding to the arcs that are removed
-    /// by the move.
-    pub fn create_tabus(i: usize, j: usize, k: usize, tour: &TspTour) -> Vec<Tabu> {
-        let n = tour.get_nodes().len();
-        vec![
-            Tabu {
-                start: *tour.get_nodes().get(i).unwrap(),
-                end: *tour.get_nodes().get(i + 1).unwrap(),
-            },
-            Tabu {
-                start: *tour.get_nodes().get(j).unwrap(),
-                end: *tour.get_nodes().get(j + 1).unwrap(),
-            },
-            Tabu {
-                start: *tour.get_nodes().get(k).unwrap(),
-                end: *tour.get_nodes().get((k + 1) % n).unwrap(),
-            },
-        ]
-    }
+/// Creates for a given 3-opt move the three tabus corresponding to the arcs that are removed by
+/// the move.
+pub fn create_tabus(i: usize, j: usize, k: usize, tour: &TspTour) -> Vec<Tabu> {
+    ArcTabu::for_removed_arcs(tour.get_nodes(), &[i, j, k])
 }
 
 /// A 3-opt [`TabuNeighborhood`] for the TSP.
 /// For a given tour and a tabu list, all 3-opt moves are generated, all moves that are tabu (i.e.,
-/// that would insert a tabu arc) are filtered out.
+/// that would insert a tabu arc) are filtered out (or flagged as tabu for the aspiration
+/// criterion).
 /// Each 3-opt move is equipped with three tabus, one for each arc that is removed by the move.
 pub struct ThreeOptTabuNeighborhood {
     tsp_instance: Arc<TspInstance>,
@@ -80,26 +62,96 @@ impl TabuNeighborhood<TspTour, Tabu> for ThreeOptTabuNeighborhood {
                         .flat_map(move |j| (j + 1..num_nodes).map(move |k| (i, j, k)))
                 })
                 .filter_map(move |(i, j, k)| {
-                    if tabu_list.iter().any(|tabu| tabu.is_tabu(i, j, k, tour)) {
+                    if is_tabu(tabu_list, i, j, k, tour) {
                         return None;
                     }
-                    Some((
-                        tour.three_opt_swap(i, j, k),
-                        Tabu::create_tabus(i, j, k, tour),
-                    ))
+                    Some((tour.three_opt_swap(i, j, k), create_tabus(i, j, k, tour)))
                 }),
         )
     }
+
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        tour: &'a TspTour,
+        tabu_list: &'a VecDeque<Tabu>,
+    ) -> Box<dyn Iterator<Item = (TspTour, Vec<Tabu>, bool)> + Send + Sync + 'a> {
+        Box::new(self.moves_with_tabu_status_of(tour, tabu_list).map(
+            move |((i, j, k), is_tabu)| {
+                (
+                    tour.three_opt_swap(i, j, k),
+                    create_tabus(i, j, k, tour),
+                    is_tabu,
+                )
+            },
+        ))
+    }
+}
+
+/// The moves are identified by the indices `(i, j, k)` of the 3-opt move.
+impl TabuMoveNeighborhood<TspTour, Tabu, (usize, usize, usize)> for ThreeOptTabuNeighborhood {
+    fn moves_of<'a>(
+        &'a self,
+        tour: &'a TspTour,
+        tabu_list: &'a VecDeque<Tabu>,
+    ) -> Box<dyn Iterator<Item = (usize, usize, usize)> + Send + Sync + 'a> {
+        let num_nodes = self.tsp_instance.get_number_of_nodes();
+        Box::new(
+            (0..num_nodes - 2)
+                .flat_map(move |i| {
+                    (i + 1..num_nodes - 1)
+                        .flat_map(move |j| (j + 1..num_nodes).map(move |k| (i, j, k)))
+                })
+                .filter(move |&(i, j, k)| !is_tabu(tabu_list, i, j, k, tour)),
+        )
+    }
+
+    fn moves_with_tabu_status_of<'a>(
+        &'a self,
+        tour: &'a TspTour,
+        tabu_list: &'a VecDeque<Tabu>,
+    ) -> Box<dyn Iterator<Item = ((usize, usize, usize), bool)> + Send + Sync + 'a>
+    where
+        (usize, usize, usize): 'a,
+    {
+        let num_nodes = self.tsp_instance.get_number_of_nodes();
+        Box::new(
+            (0..num_nodes - 2)
+                .flat_map(move |i| {
+                    (i + 1..num_nodes - 1)
+                        .flat_map(move |j| (j + 1..num_nodes).map(move |k| (i, j, k)))
+                })
+                .map(move |(i, j, k)| ((i, j, k), is_tabu(tabu_list, i, j, k, tour))),
+        )
+    }
+
+    fn apply(&self, tour: &TspTour, &(i, j, k): &(usize, usize, usize)) -> (TspTour, Vec<Tabu>) {
+        (tour.three_opt_swap(i, j, k), create_tabus(i, j, k, tour))
+    }
+
+    /// A 3-opt move `(i, j, k)` only changes the nodes at the indices `i+1` to `k`. Another move
+    /// is independent if the nodes of its removed arcs (indices `i'`, `i'+1`, `j'`, `j'+1`, `k'`
+    /// and `k'+1`) are not in this range.
+    fn is_independent(
+        &self,
+        &(i, _, k): &(usize, usize, usize),
+        &(other_i, other_j, other_k): &(usize, usize, usize),
+    ) -> bool {
+        let n = self.tsp_instance.get_number_of_nodes();
+        [other_i, other_j, other_k]
+            .iter()
+            .flat_map(|&idx| [idx, (idx + 1) % n])
+            .all(|idx| idx <= i || idx > k)
+    }
 }
 
 /// Builds a [`TabuSearchSolver`] for the TSP.
 /// * The neighborhood is the 3-opt neighborhood, i.e., the neighborhood that consists of
-/// all tours that can be obtained by applying the 3-opt operation.
+///   all tours that can be obtained by applying the 3-opt operation.
 /// * The tabu list size is set to 30.
 /// * The iteration without global improvement limit is set to 100, i.e., the search stops if no
-/// global improvement is found for 100 iterations.
+///   global improvement is found for 100 iterations.
 /// * Takes the default ['TabuImprover`] [`TabuMinimizer`] which returns the best non-tabu neighbor
-/// without using parallelism.
+///   without using parallelism.
 pub fn build(tsp_instance: Arc<TspInstance>) -> TabuSearchSolver<TspTour, Tabu> {
     let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
     let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone()));
@@ -116,12 +168,26 @@ pub fn build(tsp_instance: Arc<TspInstance>) -> TabuSearchSolver<TspTour, Tabu>
 
 #[cfg(test)]
 mod tests {
-    use super::build;
+    use super::{build, ThreeOptTabuNeighborhood};
     use crate::{
-        examples::tsp::{tsp_instance::TspInstance, tsp_tour::TspTour},
-        heuristics::Solver,
+        examples::tsp::{
+            objective::build_tsp_objective, tsp_instance::TspInstance, tsp_tour::TspTour,
+        },
+        heuristics::{
+            common::{silent_function_between_steps, ReactiveTenure, TerminationCriteria},
+            tabu_search::{
+                tabu_improver::{FrequencyPenalizedTabuMinimizer, ObjectivePhase, PhaseChange},
+                TabuSearchSolver,
+            },
+            Solver,
+        },
+        objective::BaseValue,
+    };
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+        sync::{Arc, Mutex},
     };
-    use std::sync::Arc;
 
     #[test]
     fn test_tabu_search() {
@@ -154,10 +220,133 @@ mod tests {
         assert_eq!(
             local_opt_tour.solution().get_nodes(),
             &vec![
-                0, 35, 38, 39, 36, 37, 23, 47, 4, 14, 5, 3, 24, 45, 43, 33, 34, 48, 31, 44, 18, 40,
+                0, 35, 38, 39, 36, 37, 47, 23, 4, 14, 5, 3, 24, 45, 43, 33, 34, 48, 31, 44, 18, 40,
                 7, 8, 9, 42, 32, 50, 11, 27, 26, 12, 13, 51, 10, 25, 46, 28, 15, 49, 19, 22, 29, 1,
                 6, 41, 20, 16, 2, 17, 30, 21
             ]
         );
     }
+
+    #[test]
+    fn test_tabu_search_with_long_term_memory() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let objective = Arc::new(build_tsp_objective());
+        let neighborhood = Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone()));
+        let solver = TabuSearchSolver::with_options(
+            neighborhood.clone(),
+            objective.clone(),
+            10,
+            Some(Box::new(FrequencyPenalizedTabuMinimizer::new(
+                neighborhood,
+                objective.clone(),
+                0,
+                BaseValue::Float(5.0),
+            ))),
+            Some(silent_function_between_steps()),
+            Some(TerminationCriteria::iteration_limit(15)),
+        )
+        .with_reactive_tenure(ReactiveTenure::new(
+            5,
+            50,
+            Box::new(|tour: &TspTour| {
+                let mut hasher = DefaultHasher::new();
+                tour.get_nodes().hash(&mut hasher);
+                hasher.finish()
+            }),
+        ));
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let initial_objective_value = objective.evaluate(tour.clone()).objective_value().clone();
+
+        let final_tour = solver.solve(tour);
+
+        assert!(final_tour.objective_value() < &initial_objective_value);
+    }
+
+    #[test]
+    fn test_tabu_search_with_objective_phases() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let objective = Arc::new(build_tsp_objective());
+        let phase_changes: Arc<Mutex<Vec<PhaseChange>>> = Arc::new(Mutex::new(Vec::new()));
+        let recorded_phase_changes = phase_changes.clone();
+        // the diversification phase takes the longest non-tabu tour
+        let solver = TabuSearchSolver::with_options(
+            Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone())),
+            objective.clone(),
+            10,
+            None,
+            Some(silent_function_between_steps()),
+            Some(TerminationCriteria::iteration_limit(30)),
+        )
+        .with_objective_phases(
+            vec![
+                ObjectivePhase::new("intensification", 10, vec![1.0]),
+                ObjectivePhase::new("diversification", 2, vec![-1.0]),
+            ],
+            Some(Box::new(move |phase_change| {
+                recorded_phase_changes
+                    .lock()
+                    .unwrap()
+                    .push(phase_change.clone())
+            })),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let initial_objective_value = objective.evaluate(tour.clone()).objective_value().clone();
+
+        let final_tour = solver.solve(tour);
+
+        assert!(final_tour.objective_value() < &initial_objective_value);
+        assert_eq!(
+            phase_changes
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|phase_change| (phase_change.iteration, phase_change.phase_name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, "intensification"),
+                (11, "diversification"),
+                (13, "intensification"),
+                (23, "diversification"),
+                (25, "intensification")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tabu_search_continued_from_state() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let solver = |iteration_limit| {
+            TabuSearchSolver::with_options(
+                Arc::new(ThreeOptTabuNeighborhood::new(tsp_instance.clone())),
+                Arc::new(build_tsp_objective()),
+                10,
+                None,
+                Some(silent_function_between_steps()),
+                Some(TerminationCriteria::iteration_limit(iteration_limit)),
+            )
+        };
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+
+        let uninterrupted = solver(10).solve_with_state(tour.clone());
+        let first_session = solver(5).solve_with_state(tour);
+        assert_eq!(first_session.tabu_list.len(), 10);
+        let second_session = solver(5).continue_from_state(first_session);
+
+        // with the carried-over tabu list, two sessions behave like one uninterrupted run
+        assert_eq!(
+            second_session.current_solution.solution().get_nodes(),
+            uninterrupted.current_solution.solution().get_nodes()
+        );
+        assert_eq!(
+            second_session.best_solution.solution().get_nodes(),
+            uninterrupted.best_solution.solution().get_nodes()
+        );
+        assert_eq!(second_session.tabu_list, uninterrupted.tabu_list);
+    }
 }
@@ -15,8 +15,9 @@
 //!         objective,
 //!         Some(local_improver),
 //!         None,
-//!         Some(std::time::Duration::from_secs(600)),
-//!         None,
+//!         Some(TerminationCriteria::time_limit(
+//!             std::time::Duration::from_secs(600),
+//!         )),
 //!     )
 //! }
 //! ```
@@ -24,6 +25,7 @@ use super::super::objective::build_tsp_objective;
 use super::super::tsp_instance::TspInstance;
 use super::super::tsp_tour::TspTour;
 use crate::examples::tsp::neighborhood::ThreeOptNeighborhood;
+use crate::heuristics::common::TerminationCriteria;
 use crate::heuristics::local_search::local_improver::TakeFirstRecursion;
 use crate::heuristics::local_search::LocalSearchSolver;
 use crate::objective::Objective;
@@ -32,12 +34,12 @@ use std::sync::Arc;
 /// Builds a [`LocalSearchSolver`] with [`TakeFirstRecursion`] as
 /// [`LocalImprover`][`crate::heuristics::local_search::local_improver::LocalImprover`].
 /// * The neighborhood is the 3-opt neighborhood, i.e., the neighborhood that consists of
-/// all tours that can be obtained by applying the 3-opt operation.
+///   all tours that can be obtained by applying the 3-opt operation.
 /// * The local improver is set to [`TakeFirstRecursion`], which takes the first improving
-/// neighbor.
+///   neighbor.
 /// * If no improving neighbor is found, the best 5 neighbors are considered in recursion.
 /// * The recursion depth is set to 2. The time limit is set to 10 minutes. There is no iteration
-/// limit.
+///   limit.
 /// * The time limit is set to 10 minutes. There is no iteration limit.
 pub fn build(tsp_instance: Arc<TspInstance>) -> LocalSearchSolver<TspTour> {
     let objective: Arc<Objective<TspTour>> = Arc::new(build_tsp_objective());
@@ -53,8 +55,9 @@ pub fn build(tsp_instance: Arc<TspInstance>) -> LocalSearchSolver<TspTour> {
         objective,
         Some(local_improver),
         None,
-        Some(std::time::Duration::from_secs(600)),
-        None,
+        Some(TerminationCriteria::time_limit(
+            std::time::Duration::from_secs(600),
+        )),
     )
 }
 
@@ -2,12 +2,7 @@
 //! the [build] function for details.
 //! ```ignore
 //! pub fn build(tsp_instance: Arc<TspInstance>) -> ThresholdAcceptingSolver<TspTourWithInfo> {
-//!     let node_count = tsp_instance.get_number_of_nodes();
-//!     let average_distance: Distance = (0..node_count)
-//!         .flat_map(|i| (0..node_count).filter_map(move |j| if i != j { Some((i, j)) } else { None }))
-//!         .map(|(i, j)| tsp_instance.get_distance(i, j))
-//!         .sum::<Distance>()
-//!         / (node_count * (node_count - 1)) as Distance;
+//!     let average_distance = tsp_instance.get_average_distance();
 //!     let initial_threshold = ObjectiveValue::new(vec![BaseValue::Float(average_distance)]);
 //!
 //!     let neighborhood = Arc::new(RotatedThreeOptNeighborhood::new(tsp_instance));
@@ -22,27 +17,21 @@ use crate::examples::tsp::tsp_instance::TspInstance;
 use crate::examples::tsp::tsp_tour_with_info::neighborhood::RotatedThreeOptNeighborhood;
 use crate::examples::tsp::tsp_tour_with_info::objective::build_objective_for_tsp_tour_with_info;
 use crate::examples::tsp::tsp_tour_with_info::TspTourWithInfo;
-use crate::examples::tsp::Distance;
 use crate::heuristics::threshold_accepting::ThresholdAcceptingSolver;
 use crate::objective::{BaseValue, Objective, ObjectiveValue};
 use std::sync::Arc;
 
 /// Builds a [`ThresholdAcceptingSolver`] for the TSP.
 /// * The neighborhood is the [3-opt neighborhood][RotatedThreeOptNeighborhood], i.e., the neighborhood that consists of
-/// all tours that can be obtained by applying the 3-opt operation.
+///   all tours that can be obtained by applying the 3-opt operation.
 /// * Since starting each neighborhood with the index (0, 1, 2) leads to back and forth moves,
-/// the [`TspTour`][`super::super::tsp_tour::TspTour`] is wrapped in a [`TspTourWithInfo`] to store the first index of the last move.
-/// The next move then starts with the first index one after the first index of the previous move, which
-/// means that the backwards move appears very late in the neighborhood iterator.
+///   the [`TspTour`][`super::super::tsp_tour::TspTour`] is wrapped in a [`TspTourWithInfo`] to store the first index of the last move.
+///   The next move then starts with the first index one after the first index of the previous move, which
+///   means that the backwards move appears very late in the neighborhood iterator.
 /// * The initial threshold is set to the average distance between two nodes.
 /// * The threshold is reduced by 0.9 whenever a worse neighbor is accepted.
 pub fn build(tsp_instance: Arc<TspInstance>) -> ThresholdAcceptingSolver<TspTourWithInfo> {
-    let node_count = tsp_instance.get_number_of_nodes();
-    let average_distance: Distance = (0..node_count)
-        .flat_map(|i| (0..node_count).filter_map(move |j| if i != j { Some((i, j)) } else { None }))
-        .map(|(i, j)| tsp_instance.get_distance(i, j))
-        .sum::<Distance>()
-        / (node_count * (node_count - 1)) as Distance;
+    let average_distance = tsp_instance.get_average_distance();
     let initial_threshold = ObjectiveValue::new(vec![BaseValue::Float(average_distance)]);
 
     let neighborhood = Arc::new(RotatedThreeOptNeighborhood::new(tsp_instance));
@@ -53,17 +42,45 @@ pub fn build(tsp_instance: Arc<TspInstance>) -> ThresholdAcceptingSolver<TspTour
     ThresholdAcceptingSolver::initialize(neighborhood, objective, initial_threshold, 0.9)
 }
 
+/// Builds a [record-to-record travel][ThresholdAcceptingSolver::record_to_record_travel] variant
+/// of the [`ThresholdAcceptingSolver`] for the TSP.
+/// * The neighborhood is the same as in [build].
+/// * A neighbor is accepted if it is at most 2% longer than the best tour seen.
+/// * The solver stops after `iteration_limit` iterations (or if no neighbor is accepted).
+pub fn build_record_to_record_travel(
+    tsp_instance: Arc<TspInstance>,
+    initial_tour: &TspTourWithInfo,
+    iteration_limit: u32,
+) -> ThresholdAcceptingSolver<TspTourWithInfo> {
+    let deviation = ObjectiveValue::new(vec![BaseValue::Float(
+        0.02 * initial_tour.get_tour().get_total_distance(),
+    )]);
+
+    let neighborhood = Arc::new(RotatedThreeOptNeighborhood::new(tsp_instance));
+
+    let objective: Arc<Objective<TspTourWithInfo>> =
+        Arc::new(build_objective_for_tsp_tour_with_info());
+
+    ThresholdAcceptingSolver::record_to_record_travel(
+        neighborhood,
+        objective,
+        deviation,
+        iteration_limit,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         examples::tsp::{
             tsp_instance::TspInstance, tsp_tour::TspTour, tsp_tour_with_info::TspTourWithInfo,
         },
+        heuristics::local_search::local_improver::{LocalImprover, TakeFirst},
         heuristics::Solver,
     };
     use std::sync::Arc;
 
-    use super::build;
+    use super::*;
 
     #[test]
     fn test_threshold_accepting() {
@@ -102,4 +119,44 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_record_to_record_travel() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let initial_distance = tour.get_total_distance();
+        let tour_with_infos = TspTourWithInfo::new(tour, 0);
+        let solver = build_record_to_record_travel(tsp_instance.clone(), &tour_with_infos, 500);
+
+        let final_tour = solver.solve(tour_with_infos).unwrap().unwrap();
+
+        assert!(final_tour.get_total_distance() < initial_distance);
+    }
+
+    #[test]
+    fn test_soft_time_limit_returns_local_optimum() {
+        let tsp_instance = Arc::new(
+            TspInstance::from_tsplib_file("resources/tsp_test_instances/berlin52.tsp").unwrap(),
+        );
+        let tour = TspTour::from_instance_nearest_neighbor(tsp_instance.clone());
+        let neighborhood = Arc::new(RotatedThreeOptNeighborhood::new(tsp_instance.clone()));
+        let objective = Arc::new(build_objective_for_tsp_tour_with_info());
+        let solver = ThresholdAcceptingSolver::with_options(
+            neighborhood.clone(),
+            objective.clone(),
+            ObjectiveValue::new(vec![BaseValue::Float(500.0)]),
+            0.9,
+            Some(Box::new(|_, _, _, _, _, _, _, _| {})),
+            None,
+        )
+        .with_soft_time_limit(std::time::Duration::ZERO);
+
+        let final_tour = solver.solve(TspTourWithInfo::new(tour, 0));
+
+        // after the soft time limit, the best tour is descended to a local optimum
+        let take_first = TakeFirst::new(neighborhood, objective);
+        assert!(take_first.improve(&final_tour).is_none());
+    }
 }
@@ -28,6 +28,31 @@ impl TspInstance {
     pub fn get_number_of_nodes(&self) -> NodeCount {
         self.number_of_nodes
     }
+
+    /// Returns the `count` nearest other nodes of `node` (with respect to the distance from
+    /// `node`) sorted by increasing distance.
+    pub fn get_nearest_nodes(&self, node: NodeIdx, count: usize) -> Vec<NodeIdx> {
+        let mut others: Vec<NodeIdx> = (0..self.number_of_nodes).filter(|&n| n != node).collect();
+        others.sort_by(|&a, &b| {
+            self.get_distance(node, a)
+                .partial_cmp(&self.get_distance(node, b))
+                .unwrap()
+        });
+        others.truncate(count);
+        others
+    }
+
+    /// Returns the average distance between two distinct nodes.
+    pub fn get_average_distance(&self) -> Distance {
+        let node_count = self.number_of_nodes;
+        (0..node_count)
+            .flat_map(|i| {
+                (0..node_count).filter_map(move |j| if i != j { Some((i, j)) } else { None })
+            })
+            .map(|(i, j)| self.get_distance(i, j))
+            .sum::<Distance>()
+            / (node_count * (node_count - 1)) as Distance
+    }
 }
 
 // static
@@ -139,7 +164,6 @@ impl TspInstance {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
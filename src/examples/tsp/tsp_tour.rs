@@ -1,6 +1,10 @@
 //! This module contains the [`TspTour`], i.e., a permutation of all nodes of the [`TspInstance`].
 use std::sync::Arc;
 
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::heuristics::construction::greedy_randomized;
+
 use super::{tsp_instance::TspInstance, Distance, NodeIdx};
 
 /// Represents a tour of a [`TspInstance`]. Contain all indices between 0 and n-1.
@@ -73,6 +77,29 @@ impl TspTour {
         TspTour::new_pre_computed(nodes, total_distance, tsp_instance)
     }
 
+    /// Creates a new [`TspTour`] using the [greedy randomized
+    /// construction][crate::heuristics::construction::greedy_randomized], i.e., a randomized
+    /// nearest neighbor heuristic: In each step, the next node is chosen uniformly at random among
+    /// all unvisited nodes whose distance to the current node is at most `min + alpha * (max -
+    /// min)`. The tour is deterministic for a fixed `seed`.
+    pub fn from_instance_greedy_randomized(
+        tsp_instance: Arc<TspInstance>,
+        alpha: f64,
+        seed: u64,
+    ) -> TspTour {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut nodes = vec![0];
+        nodes.extend(greedy_randomized(
+            (1..tsp_instance.get_number_of_nodes()).collect(),
+            |partial_tour, &node| {
+                tsp_instance.get_distance(*partial_tour.last().unwrap_or(&0), node)
+            },
+            alpha,
+            &mut rng,
+        ));
+        TspTour::new(nodes, tsp_instance)
+    }
+
     /// Returns the `nodes` of the tour.
     pub fn get_nodes(&self) -> &Vec<NodeIdx> {
         &self.nodes
@@ -83,6 +110,23 @@ impl TspTour {
         self.total_distance
     }
 
+    /// Returns the change of the total distance caused by the 3-opt swap `(i, j, k)` (see
+    /// [`three_opt_swap`][TspTour::three_opt_swap]) without performing the swap.
+    pub fn three_opt_delta(&self, i: usize, j: usize, k: usize) -> Distance {
+        let n = self.nodes.len();
+        let distance = |a: usize, b: usize| {
+            self.tsp_instance
+                .get_distance(self.nodes[a % n], self.nodes[b % n])
+        };
+
+        // Remove distance of arcs (i, i+1), (j, j+1), and (k, k+1) and add distance of arcs
+        // (i, j+1), (j, k+1), and (k, i+1)
+        -distance(i, i + 1) - distance(j, j + 1) - distance(k, k + 1)
+            + distance(i, j + 1)
+            + distance(j, k + 1)
+            + distance(k, i + 1)
+    }
+
     /// Performs a single [3-opt swap](https://en.wikipedia.org/wiki/3-opt) on the tour.
     /// * Assumes that 0 <= i < j < k < n.
     /// * New [`TspTour`] consists of the nodes with the following index in the current tour
@@ -91,30 +135,7 @@ impl TspTour {
     ///     - then the nodes with index from i+1 to j
     ///     - finally the nodes with index from k+1 to n-1.
     pub fn three_opt_swap(&self, i: usize, j: usize, k: usize) -> TspTour {
-        let mut new_distance = self.total_distance;
-        let n = self.nodes.len();
-
-        // Remove distance of arcs (i, i+1), (j, j+1), and (k, k+1)
-        new_distance -= self
-            .tsp_instance
-            .get_distance(self.nodes[i], self.nodes[(i + 1) % n]);
-        new_distance -= self
-            .tsp_instance
-            .get_distance(self.nodes[j], self.nodes[(j + 1) % n]);
-        new_distance -= self
-            .tsp_instance
-            .get_distance(self.nodes[k], self.nodes[(k + 1) % n]);
-
-        // Add distance of arcs (i, j+1), (j, k+1), and (k, i+1)
-        new_distance += self
-            .tsp_instance
-            .get_distance(self.nodes[i], self.nodes[(j + 1) % n]);
-        new_distance += self
-            .tsp_instance
-            .get_distance(self.nodes[j], self.nodes[(k + 1) % n]);
-        new_distance += self
-            .tsp_instance
-            .get_distance(self.nodes[k], self.nodes[(i + 1) % n]);
+        let new_distance = self.total_distance + self.three_opt_delta(i, j, k);
 
         // Perform the swap
         let mut new_nodes = Vec::with_capacity(self.nodes.len());
@@ -131,6 +152,13 @@ impl TspTour {
     }
 }
 
+/// Two tours are equal if they visit the nodes of the same [`TspInstance`] in the same order.
+impl PartialEq for TspTour {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.tsp_instance, &other.tsp_instance) && self.nodes == other.nodes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +191,27 @@ mod tests {
         assert_eq!(tour.get_total_distance(), 10.0 + 25.0 + 30.0 + 15.0);
     }
 
+    #[test]
+    fn test_new_tsp_tour_greedy_randomized() {
+        let tsp_instance = Arc::new(TspInstance::new(vec![
+            vec![0.0, 10.0, 15.0, 20.0],
+            vec![10.0, 0.0, 35.0, 25.0],
+            vec![15.0, 35.0, 0.0, 30.0],
+            vec![20.0, 25.0, 30.0, 0.0],
+        ]));
+
+        // alpha = 0 is the nearest neighbor heuristic
+        let greedy_tour = TspTour::from_instance_greedy_randomized(tsp_instance.clone(), 0.0, 42);
+        assert_eq!(greedy_tour.get_nodes(), &vec![0, 1, 3, 2]);
+
+        // same seed, same tour
+        let tour = TspTour::from_instance_greedy_randomized(tsp_instance.clone(), 1.0, 7);
+        let same_tour = TspTour::from_instance_greedy_randomized(tsp_instance, 1.0, 7);
+        assert_eq!(tour.get_nodes(), same_tour.get_nodes());
+        assert_eq!(tour.get_nodes()[0], 0);
+        assert_eq!(tour.get_nodes().len(), 4);
+    }
+
     #[test]
     fn test_three_opt_swap() {
         let tsp_instance = TspInstance::new(vec![
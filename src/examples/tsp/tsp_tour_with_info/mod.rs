@@ -2,27 +2,31 @@
 //! [`TspTourWithInfo`] struct equips a [`TspTour`] with the first index of the last accpeted
 //! 3-opt move. The [`RotatedThreeOptNeighborhood`][`neighborhood::RotatedThreeOptNeighborhood`] generates the same neighbors as the
 //! [`ThreeOptNeighborhood`][`super::neighborhood::ThreeOptNeighborhood`], but starts with the first index at `last_i + 1`.
+//! Both work for any [`TourLike`][super::neighborhood::TourLike] tour instead of the [`TspTour`]
+//! as well.
 
 use super::tsp_tour::TspTour;
 pub mod neighborhood;
 pub mod objective;
 
-/// This struct labels are [`TspTour`] with the last index of the first node of the 3-opt move.
+/// This struct labels are [`TspTour`] (or another
+/// [`TourLike`][super::neighborhood::TourLike] tour `T`) with the last index of the first node
+/// of the 3-opt move.
 #[derive(Clone)]
-pub struct TspTourWithInfo {
-    tour: TspTour,
+pub struct TspTourWithInfo<T = TspTour> {
+    tour: T,
     last_i: usize,
 }
 
-impl TspTourWithInfo {
+impl<T> TspTourWithInfo<T> {
     /// Creates a new [`TspTourWithInfo`] from a [`TspTour`] and the first index of the last
     /// 3-opt move.
-    pub fn new(tour: TspTour, last_i: usize) -> Self {
+    pub fn new(tour: T, last_i: usize) -> Self {
         Self { tour, last_i }
     }
 
     /// Returns the [`TspTour`] of this [`TspTourWithInfo`].
-    pub fn get_tour(&self) -> &TspTour {
+    pub fn get_tour(&self) -> &T {
         &self.tour
     }
 
@@ -32,7 +36,7 @@ impl TspTourWithInfo {
     }
 
     /// Unwraps the [`TspTour`] from this [`TspTourWithInfo`].
-    pub fn unwrap(self) -> TspTour {
+    pub fn unwrap(self) -> T {
         self.tour
     }
 }
@@ -2,30 +2,35 @@
 
 use std::sync::Arc;
 
-use crate::{examples::tsp::tsp_instance::TspInstance, heuristics::common::Neighborhood};
+use crate::{
+    examples::tsp::{neighborhood::TourLike, tsp_instance::TspInstance},
+    heuristics::common::Neighborhood,
+};
 
 use super::TspTourWithInfo;
 
-/// Given a [`TspTourWithInfo`], this [`Neighborhood`] generates all tours that can be obtained by
-/// applying a 3-opt move. It starts with the first index at `last_i + 1` in order to avoid back and forth
-/// moves in threshold accepting or simulated annealing.
-pub struct RotatedThreeOptNeighborhood {
-    tsp_instance: Arc<TspInstance>,
-}
+/// Given a [`TspTourWithInfo`] (of any [`TourLike`] tour), this [`Neighborhood`] generates all
+/// tours that can be obtained by applying a 3-opt move. It starts with the first index at
+/// `last_i + 1` in order to avoid back and forth moves in threshold accepting or simulated
+/// annealing.
+/// * The number of nodes is taken from each tour, so tours of other problems can use the
+///   [`Default`] neighborhood.
+#[derive(Default)]
+pub struct RotatedThreeOptNeighborhood;
 
 impl RotatedThreeOptNeighborhood {
-    /// Creates a new [`RotatedThreeOptNeighborhood`] for the given [`TspInstance`].
-    pub fn new(tsp_instance: Arc<TspInstance>) -> Self {
-        Self { tsp_instance }
+    /// Creates a new [`RotatedThreeOptNeighborhood`] for the tours of the given [`TspInstance`].
+    pub fn new(_tsp_instance: Arc<TspInstance>) -> Self {
+        Self
     }
 }
 
-impl Neighborhood<TspTourWithInfo> for RotatedThreeOptNeighborhood {
+impl<T: TourLike> Neighborhood<TspTourWithInfo<T>> for RotatedThreeOptNeighborhood {
     fn neighbors_of<'a>(
         &'a self,
-        tour_with_info: &'a TspTourWithInfo,
-    ) -> Box<dyn Iterator<Item = TspTourWithInfo> + Send + Sync + 'a> {
-        let num_nodes = self.tsp_instance.get_number_of_nodes();
+        tour_with_info: &'a TspTourWithInfo<T>,
+    ) -> Box<dyn Iterator<Item = TspTourWithInfo<T>> + Send + Sync + 'a> {
+        let num_nodes = tour_with_info.tour.nodes().len();
         let start_i = tour_with_info.last_i + 1 % num_nodes;
 
         Box::new(
@@ -34,7 +39,7 @@ impl Neighborhood<TspTourWithInfo> for RotatedThreeOptNeighborhood {
                 .flat_map(move |i| {
                     (i + 1..num_nodes - 1).flat_map(move |j| {
                         (j + 1..num_nodes).map(move |k| TspTourWithInfo {
-                            tour: tour_with_info.tour.three_opt_swap(i, j, k),
+                            tour: tour_with_info.tour.splice_segments(i, j, k),
                             last_i: i,
                         })
                     })
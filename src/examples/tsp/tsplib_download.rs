@@ -0,0 +1,230 @@
+//! This module contains the [`TsplibCache`], which downloads named
+//! [TSPLIB](http://comopt.ifi.uni-heidelberg.de/software/TSPLIB95/) instances (e.g.,
+//! `"berlin52"`) into a local cache directory and loads them as [`TspInstance`].
+//! * Instances are only downloaded once. Afterwards, the cached file is used (also offline).
+//! * The TSPLIB server provides gzip-compressed files, which are decompressed before caching.
+//! * Requires the `tsplib_download` feature (using [`ureq`](https://docs.rs/ureq/) and
+//!   [`flate2`](https://docs.rs/flate2/)).
+//!
+//! ```no_run
+//! use rapid_solve::examples::tsp::tsplib_download::TsplibCache;
+//!
+//! let tsp_instance = TsplibCache::default_location().load("berlin52").unwrap();
+//! assert_eq!(tsp_instance.get_number_of_nodes(), 52);
+//! ```
+use std::{
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+
+use super::tsp_instance::TspInstance;
+
+/// The default URL of the directory of the (gzip-compressed) symmetric TSPLIB instances.
+pub const DEFAULT_TSPLIB_URL: &str = "http://comopt.ifi.uni-heidelberg.de/software/TSPLIB95/tsp/";
+
+/// The environment variable that overrides the cache directory of
+/// [`TsplibCache::default_location`].
+pub const TSPLIB_CACHE_ENV_VARIABLE: &str = "RAPID_SOLVE_TSPLIB_CACHE";
+
+/// Error that is returned if a TSPLIB instance cannot be downloaded or loaded.
+#[derive(Debug)]
+pub enum TsplibDownloadError {
+    /// The name is not a valid instance name (only ASCII letters, digits and `_` are allowed).
+    InvalidName(String),
+    /// The instance cannot be downloaded.
+    Http(Box<ureq::Error>),
+    /// The cache directory or the cached file cannot be accessed, or the download cannot be
+    /// decompressed.
+    Io(io::Error),
+    /// The cached file is not a valid TSPLIB file.
+    Parse(String),
+}
+
+impl fmt::Display for TsplibDownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TsplibDownloadError::InvalidName(name) => {
+                write!(f, "Invalid TSPLIB instance name: {}", name)
+            }
+            TsplibDownloadError::Http(error) => {
+                write!(f, "Cannot download TSPLIB instance: {}", error)
+            }
+            TsplibDownloadError::Io(error) => write!(f, "Cannot access TSPLIB cache: {}", error),
+            TsplibDownloadError::Parse(message) => {
+                write!(f, "Invalid TSPLIB file: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for TsplibDownloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TsplibDownloadError::Http(error) => Some(error.as_ref()),
+            TsplibDownloadError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TsplibDownloadError {
+    fn from(error: io::Error) -> Self {
+        TsplibDownloadError::Io(error)
+    }
+}
+
+impl From<ureq::Error> for TsplibDownloadError {
+    fn from(error: ureq::Error) -> Self {
+        TsplibDownloadError::Http(Box::new(error))
+    }
+}
+
+/// A local cache directory for TSPLIB instances, which are downloaded on first use.
+/// * The instance `name` is cached as `<cache_dir>/<name>.tsp`.
+/// * A download is first written to a temporary file (`<name>.tsp.tmp`), which then is renamed,
+///   such that an interrupted download does not leave a corrupt cached file.
+pub struct TsplibCache {
+    cache_dir: PathBuf,
+    base_url: String,
+}
+
+impl TsplibCache {
+    /// Creates a new [`TsplibCache`] in `cache_dir` that downloads from the
+    /// [`DEFAULT_TSPLIB_URL`].
+    pub fn new(cache_dir: impl Into<PathBuf>) -> TsplibCache {
+        TsplibCache::with_base_url(cache_dir, DEFAULT_TSPLIB_URL)
+    }
+
+    /// Creates a new [`TsplibCache`] in `cache_dir` that downloads `<base_url><name>.tsp.gz`
+    /// (e.g., from a mirror).
+    pub fn with_base_url(cache_dir: impl Into<PathBuf>, base_url: &str) -> TsplibCache {
+        TsplibCache {
+            cache_dir: cache_dir.into(),
+            base_url: String::from(base_url),
+        }
+    }
+
+    /// Creates a new [`TsplibCache`] in the directory given by the environment variable
+    /// [`TSPLIB_CACHE_ENV_VARIABLE`] or, if it is not set, in `rapid_solve_tsplib` inside the
+    /// temporary directory of the system.
+    pub fn default_location() -> TsplibCache {
+        match env::var_os(TSPLIB_CACHE_ENV_VARIABLE) {
+            Some(cache_dir) => TsplibCache::new(cache_dir),
+            None => TsplibCache::new(env::temp_dir().join("rapid_solve_tsplib")),
+        }
+    }
+
+    /// Returns the cache directory.
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Returns `true` if the instance `name` is already in the cache.
+    pub fn contains(&self, name: &str) -> bool {
+        self.cached_path(name).is_file()
+    }
+
+    /// Returns the path of the cached instance `name`, which is downloaded first if it is not in
+    /// the cache.
+    pub fn path_of(&self, name: &str) -> Result<PathBuf, TsplibDownloadError> {
+        if !is_valid_name(name) {
+            return Err(TsplibDownloadError::InvalidName(String::from(name)));
+        }
+        let path = self.cached_path(name);
+        if !path.is_file() {
+            let url = format!("{}{}.tsp.gz", self.base_url, name);
+            report!(Info, "Downloading {} to {}", url, path.display());
+            let response = ureq::get(&url).call()?;
+            self.store(name, response.into_reader())?;
+        }
+        Ok(path)
+    }
+
+    /// Returns the instance `name` as [`TspInstance`], which is downloaded first if it is not in
+    /// the cache.
+    pub fn load(&self, name: &str) -> Result<TspInstance, TsplibDownloadError> {
+        let path = self.path_of(name)?;
+        TspInstance::from_tsplib_file(&path.to_string_lossy())
+            .map_err(|error| TsplibDownloadError::Parse(error.to_string()))
+    }
+
+    /// Decompresses the gzip-compressed `compressed` TSPLIB file and stores it as instance `name`
+    /// in the cache.
+    fn store(&self, name: &str, compressed: impl Read) -> Result<(), TsplibDownloadError> {
+        fs::create_dir_all(&self.cache_dir)?;
+        let path = self.cached_path(name);
+        let mut temporary_path = path.clone().into_os_string();
+        temporary_path.push(".tmp");
+        let mut content = Vec::new();
+        GzDecoder::new(compressed).read_to_end(&mut content)?;
+        fs::write(&temporary_path, content)?;
+        fs::rename(&temporary_path, &path)?;
+        Ok(())
+    }
+
+    fn cached_path(&self, name: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}.tsp", name))
+    }
+}
+
+/// Only ASCII letters, digits and `_` are allowed, such that the name cannot escape the cache
+/// directory.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    fn temporary_cache(test_name: &str) -> TsplibCache {
+        let cache_dir = env::temp_dir().join(format!(
+            "rapid_solve_tsplib_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&cache_dir);
+        // unreachable base url, such that the tests never access the network
+        TsplibCache::with_base_url(cache_dir, "http://127.0.0.1:9/")
+    }
+
+    #[test]
+    fn test_store_and_load_from_cache() {
+        let cache = temporary_cache("store");
+        let content = fs::read("resources/tsp_test_instances/berlin52.tsp").unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert!(!cache.contains("berlin52"));
+        cache.store("berlin52", compressed.as_slice()).unwrap();
+        assert!(cache.contains("berlin52"));
+
+        let tsp_instance = cache.load("berlin52").unwrap();
+        assert_eq!(tsp_instance.get_number_of_nodes(), 52);
+        fs::remove_dir_all(cache.cache_dir()).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_name_and_failed_download() {
+        let cache = temporary_cache("invalid");
+        assert!(matches!(
+            cache.load("../berlin52"),
+            Err(TsplibDownloadError::InvalidName(_))
+        ));
+        assert!(matches!(
+            cache.load("berlin52"),
+            Err(TsplibDownloadError::Http(_))
+        ));
+        assert!(!cache.contains("berlin52"));
+    }
+}
@@ -0,0 +1,173 @@
+//! This module contains the [`AcceptanceLogger`], which logs the acceptance and rejection
+//! decisions of the [`ThresholdAcceptingSolver`][crate::heuristics::threshold_accepting::ThresholdAcceptingSolver],
+//! the [`SimulatedAnnealingSolver`][crate::heuristics::simulated_annealing::SimulatedAnnealingSolver],
+//! the [`TabuSearchSolver`][crate::heuristics::tabu_search::TabuSearchSolver] and the
+//! [`ParallelTabuSearchSolver`][crate::heuristics::parallel_tabu_search::ParallelTabuSearchSolver]
+//! for debugging.
+
+use std::sync::{Arc, Mutex};
+use std::time as stdtime;
+
+use crate::objective::ObjectiveValue;
+
+/// The parameters of the acceptance criterion at the time of the decision.
+#[derive(Clone, Debug)]
+pub enum AcceptanceCriterion {
+    /// Threshold accepting: The neighbor is accepted if its objective value is smaller than the
+    /// current objective value plus the `threshold`.
+    Threshold {
+        /// The current threshold.
+        threshold: ObjectiveValue,
+    },
+    /// Simulated annealing: The neighbor is accepted if the `acceptance_probability` is larger
+    /// than the `random_number`.
+    Temperature {
+        /// The current temperature.
+        temperature: f64,
+        /// The acceptance probability computed for the neighbor.
+        acceptance_probability: f64,
+        /// The random number the acceptance probability is compared with.
+        random_number: f64,
+    },
+    /// Tabu search: The best non-tabu neighbor is accepted, unless the best tabu neighbor that
+    /// satisfies the aspiration criterion (found in the same exploration) is better.
+    Tabu {
+        /// `true` if the neighbor is tabu.
+        tabu: bool,
+        /// `true` if the neighbor satisfies the aspiration criterion (only checked for tabu
+        /// neighbors).
+        aspiration: bool,
+    },
+}
+
+/// A single acceptance or rejection decision of a solver.
+pub struct AcceptanceDecision<'a> {
+    /// `true` if the neighbor was accepted.
+    pub accepted: bool,
+    /// The objective value of the current solution.
+    pub current_objective_value: &'a ObjectiveValue,
+    /// The objective value of the considered neighbor.
+    pub neighbor_objective_value: &'a ObjectiveValue,
+    /// The parameters of the acceptance criterion.
+    pub criterion: AcceptanceCriterion,
+}
+
+struct RateLimit {
+    window_start: stdtime::Instant,
+    logged_in_window: u32,
+    suppressed: u64,
+}
+
+/// Logs every acceptance and rejection decision (with the compared
+/// [`ObjectiveValues`][ObjectiveValue] and the criterion parameters) to debug why a solver
+/// accepted or rejected a neighbor.
+/// * The logging is rate-limited to `max_logs_per_second` decisions per second. Suppressed
+///   decisions are counted and reported when the next second starts.
+/// * Can be passed to the solvers as `acceptance_logger` in `with_options` (or by
+///   `with_acceptance_logger`). As the logger is shared via [`Arc`], the same logger can be used
+///   by several solvers.
+pub struct AcceptanceLogger {
+    max_logs_per_second: u32,
+    rate_limit: Mutex<RateLimit>,
+}
+
+impl AcceptanceLogger {
+    /// Creates a new [`AcceptanceLogger`] that logs at most `max_logs_per_second` decisions per
+    /// second.
+    pub fn new(max_logs_per_second: u32) -> Arc<AcceptanceLogger> {
+        Arc::new(AcceptanceLogger {
+            max_logs_per_second,
+            rate_limit: Mutex::new(RateLimit {
+                window_start: stdtime::Instant::now(),
+                logged_in_window: 0,
+                suppressed: 0,
+            }),
+        })
+    }
+
+    /// Logs the given decision (if the rate limit is not exceeded). Returns `true` if the
+    /// decision was printed.
+    pub fn log(&self, decision: &AcceptanceDecision) -> bool {
+        let mut rate_limit = self.rate_limit.lock().unwrap();
+        if rate_limit.window_start.elapsed() >= stdtime::Duration::from_secs(1) {
+            if rate_limit.suppressed > 0 {
+                report!(
+                    Info,
+                    "[acceptance] ... {} decisions suppressed (rate limit)",
+                    rate_limit.suppressed
+                );
+            }
+            rate_limit.window_start = stdtime::Instant::now();
+            rate_limit.logged_in_window = 0;
+            rate_limit.suppressed = 0;
+        }
+        if rate_limit.logged_in_window >= self.max_logs_per_second {
+            rate_limit.suppressed += 1;
+            return false;
+        }
+        rate_limit.logged_in_window += 1;
+
+        report!(
+            Info,
+            "[acceptance] {}: neighbor {} vs current {} ({})",
+            if decision.accepted {
+                "accepted"
+            } else {
+                "rejected"
+            },
+            format_objective_value(decision.neighbor_objective_value),
+            format_objective_value(decision.current_objective_value),
+            match &decision.criterion {
+                AcceptanceCriterion::Threshold { threshold } =>
+                    format!("threshold: {}", format_objective_value(threshold)),
+                AcceptanceCriterion::Temperature {
+                    temperature,
+                    acceptance_probability,
+                    random_number,
+                } => format!(
+                    "temperature: {:0.4}, acceptance probability: {:0.4}, random number: {:0.4}",
+                    temperature, acceptance_probability, random_number
+                ),
+                AcceptanceCriterion::Tabu { tabu, aspiration } =>
+                    format!("tabu: {}, aspiration: {}", tabu, aspiration),
+            }
+        );
+        true
+    }
+}
+
+fn format_objective_value(objective_value: &ObjectiveValue) -> String {
+    format!(
+        "[{}]",
+        objective_value
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>()
+            .join(" | ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::BaseValue;
+
+    #[test]
+    fn test_acceptance_logger_rate_limit() {
+        let logger = AcceptanceLogger::new(2);
+        let current = ObjectiveValue::new(vec![BaseValue::Integer(10)]);
+        let neighbor = ObjectiveValue::new(vec![BaseValue::Integer(12)]);
+        let decision = AcceptanceDecision {
+            accepted: false,
+            current_objective_value: &current,
+            neighbor_objective_value: &neighbor,
+            criterion: AcceptanceCriterion::Threshold {
+                threshold: ObjectiveValue::new(vec![BaseValue::Integer(1)]),
+            },
+        };
+
+        assert!(logger.log(&decision));
+        assert!(logger.log(&decision));
+        assert!(!logger.log(&decision)); // rate limit exceeded
+    }
+}
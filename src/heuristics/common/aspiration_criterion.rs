@@ -0,0 +1,437 @@
+//! This module contains the [`AspirationCriterion`] trait, which decides whether a tabu neighbor
+//! is accepted anyway by the [`TabuSearchSolver`][crate::heuristics::tabu_search::TabuSearchSolver]
+//! and the
+//! [`ParallelTabuSearchSolver`][crate::heuristics::parallel_tabu_search::ParallelTabuSearchSolver],
+//! and the [`Aspiration`], which checks the criterion during the neighborhood exploration of an
+//! iteration.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use super::{AcceptanceCriterion, AcceptanceDecision, AcceptanceLogger, NeighborCounts};
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// Decides whether a tabu neighbor is accepted although it is tabu.
+/// * In each iteration (with a non-empty tabu list), the tabu improver explores the tabu
+///   neighbors together with the non-tabu neighbors and offers them to the [`Aspiration`] of the
+///   iteration. The best tabu neighbor that satisfies the criterion replaces the best non-tabu
+///   neighbor if it is better.
+/// * The tabu neighbors are only explored if the neighborhood reports them (see
+///   [`neighbors_with_tabu_status_of`][crate::heuristics::tabu_search::TabuNeighborhood::neighbors_with_tabu_status_of]),
+///   otherwise the tabu list is strict.
+/// * Each tabu neighbor is evaluated. If the criterion cannot be satisfied in the current
+///   iteration, [`might_be_satisfied`][AspirationCriterion::might_be_satisfied] should return
+///   `false`, such that the tabu neighbors are not generated at all.
+/// * The default of the tabu solvers is the [`NewGlobalBestAspiration`].
+pub trait AspirationCriterion<S, T>: Send + Sync {
+    /// Returns `true` if the `neighbor` (reached by a move with the tabus `new_tabus`) of the
+    /// `current_solution` should be accepted even if the move is tabu.
+    fn is_satisfied(
+        &self,
+        neighbor: &EvaluatedSolution<S>,
+        new_tabus: &[T],
+        current_solution: &EvaluatedSolution<S>,
+        best_solution_seen: &EvaluatedSolution<S>,
+        objective: &Objective<S>,
+    ) -> bool;
+
+    /// Returns `false` if no neighbor of the `current_solution` can satisfy the criterion, such
+    /// that the tabu neighbors are not generated. The default is `true`.
+    fn might_be_satisfied(
+        &self,
+        _current_solution: &EvaluatedSolution<S>,
+        _best_solution_seen: &EvaluatedSolution<S>,
+    ) -> bool {
+        true
+    }
+}
+
+/// Accepts a tabu neighbor if it is better than the best solution seen so far (aspiration by
+/// objective).
+pub struct NewGlobalBestAspiration;
+
+impl<S, T> AspirationCriterion<S, T> for NewGlobalBestAspiration {
+    fn is_satisfied(
+        &self,
+        neighbor: &EvaluatedSolution<S>,
+        _new_tabus: &[T],
+        _current_solution: &EvaluatedSolution<S>,
+        best_solution_seen: &EvaluatedSolution<S>,
+        objective: &Objective<S>,
+    ) -> bool {
+        objective.is_better(neighbor, best_solution_seen)
+    }
+}
+
+/// Never accepts a tabu neighbor, i.e., the tabu list is strict.
+pub struct NoAspiration;
+
+impl<S, T> AspirationCriterion<S, T> for NoAspiration {
+    fn is_satisfied(
+        &self,
+        _neighbor: &EvaluatedSolution<S>,
+        _new_tabus: &[T],
+        _current_solution: &EvaluatedSolution<S>,
+        _best_solution_seen: &EvaluatedSolution<S>,
+        _objective: &Objective<S>,
+    ) -> bool {
+        false
+    }
+
+    fn might_be_satisfied(
+        &self,
+        _current_solution: &EvaluatedSolution<S>,
+        _best_solution_seen: &EvaluatedSolution<S>,
+    ) -> bool {
+        false
+    }
+}
+
+/// The aspiration check of a single iteration of a tabu search, which the solver passes to its
+/// tabu improver (see
+/// [`TabuImprover::improve_with_aspiration`][crate::heuristics::tabu_search::tabu_improver::TabuImprover::improve_with_aspiration]).
+/// * During its exploration, the improver hands each tabu neighbor to the [`Aspiration`], which
+///   evaluates it and keeps the best tabu neighbor that satisfies the [`AspirationCriterion`].
+/// * It can be shared between threads, such that parallel improvers can offer the tabu neighbors
+///   concurrently.
+/// * If the aspiration is not [active][Aspiration::is_active] (e.g., with [`NoAspiration`] or an
+///   empty tabu list), the improver should not generate the tabu neighbors at all.
+pub struct Aspiration<'a, S, T> {
+    criterion: &'a dyn AspirationCriterion<S, T>,
+    current_solution: &'a EvaluatedSolution<S>,
+    best_solution_seen: &'a EvaluatedSolution<S>,
+    objective: &'a Objective<S>,
+    active: bool,
+    best_tabu_neighbor: Mutex<Option<(EvaluatedSolution<S>, Vec<T>)>>,
+    offered: AtomicU64,
+}
+
+impl<'a, S, T> Aspiration<'a, S, T> {
+    /// Creates the [`Aspiration`] of an iteration starting in `current_solution`. It is only
+    /// active if `has_tabus` (i.e., the tabu list is not empty) and the `criterion` might be
+    /// satisfied.
+    pub(crate) fn new(
+        criterion: &'a dyn AspirationCriterion<S, T>,
+        current_solution: &'a EvaluatedSolution<S>,
+        best_solution_seen: &'a EvaluatedSolution<S>,
+        objective: &'a Objective<S>,
+        has_tabus: bool,
+    ) -> Self {
+        Self {
+            criterion,
+            current_solution,
+            best_solution_seen,
+            objective,
+            active: has_tabus && criterion.might_be_satisfied(current_solution, best_solution_seen),
+            best_tabu_neighbor: Mutex::new(None),
+            offered: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns `true` if a tabu neighbor might satisfy the [`AspirationCriterion`] in this
+    /// iteration, i.e., if the tabu neighbors should be explored.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Passes a neighbor that is not tabu through. A tabu neighbor is evaluated and offered
+    /// (see [`offer`][Aspiration::offer]) and `None` is returned.
+    pub fn filter_tabu(
+        &self,
+        neighbor: S,
+        new_tabus: Vec<T>,
+        is_tabu: bool,
+    ) -> Option<(S, Vec<T>)> {
+        if !is_tabu {
+            return Some((neighbor, new_tabus));
+        }
+        self.offer(self.objective.evaluate(neighbor), new_tabus);
+        None
+    }
+
+    /// Offers an evaluated tabu neighbor (reached by a move with the tabus `new_tabus`). It is
+    /// kept if it satisfies the [`AspirationCriterion`] and is better than all tabu neighbors
+    /// kept before.
+    pub fn offer(&self, neighbor: EvaluatedSolution<S>, new_tabus: Vec<T>) {
+        self.offered.fetch_add(1, Ordering::Relaxed);
+        if !self.criterion.is_satisfied(
+            &neighbor,
+            &new_tabus,
+            self.current_solution,
+            self.best_solution_seen,
+            self.objective,
+        ) {
+            return;
+        }
+        let mut best_tabu_neighbor = self.best_tabu_neighbor.lock().unwrap();
+        let is_better = match best_tabu_neighbor.as_ref() {
+            Some((best, _)) => self.objective.is_better(&neighbor, best),
+            None => true,
+        };
+        if is_better {
+            *best_tabu_neighbor = Some((neighbor, new_tabus));
+        }
+    }
+
+    /// Forgets the tabu neighbors kept so far (e.g., if the exploration was aborted by a panic).
+    pub(crate) fn discard(&self) {
+        *self
+            .best_tabu_neighbor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+        self.best_tabu_neighbor.clear_poison();
+    }
+
+    /// Returns the best tabu neighbor that satisfies the [`AspirationCriterion`] (if any) and the
+    /// number of offered tabu neighbors.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn finish(self) -> (Option<(EvaluatedSolution<S>, Vec<T>)>, u64) {
+        (
+            self.best_tabu_neighbor.into_inner().unwrap(),
+            self.offered.into_inner(),
+        )
+    }
+}
+
+/// Returns the `aspirating_neighbor` if it is better than the `non_tabu_neighbor` (or if there
+/// is no non-tabu neighbor), otherwise the `non_tabu_neighbor`.
+pub(crate) fn prefer_aspirating_neighbor<S, T>(
+    objective: &Objective<S>,
+    non_tabu_neighbor: Option<(EvaluatedSolution<S>, Vec<T>)>,
+    aspirating_neighbor: Option<(EvaluatedSolution<S>, Vec<T>)>,
+) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+    match (non_tabu_neighbor, aspirating_neighbor) {
+        (Some(non_tabu), Some(aspirating)) => {
+            if objective.is_better(&aspirating.0, &non_tabu.0) {
+                report!(Debug, "Aspiration criterion overrides the tabu list.");
+                Some(aspirating)
+            } else {
+                Some(non_tabu)
+            }
+        }
+        (None, Some(aspirating)) => {
+            report!(Debug, "Aspiration criterion overrides the tabu list.");
+            Some(aspirating)
+        }
+        (non_tabu, None) => non_tabu,
+    }
+}
+
+/// Extends the [`NeighborCounts`] of the non-tabu neighbors of the tabu improver by the
+/// `tabu_offered` tabu neighbors that it offered to the [`Aspiration`] (which evaluated them).
+/// * If [`prefer_aspirating_neighbor`] takes the `aspirating_neighbor`, this tabu neighbor is
+///   accepted, all other tabu neighbors are rejected as tabu.
+pub(crate) fn counts_with_aspiration<S, T>(
+    objective: &Objective<S>,
+    non_tabu_neighbor: &Option<(EvaluatedSolution<S>, Vec<T>)>,
+    aspirating_neighbor: &Option<(EvaluatedSolution<S>, Vec<T>)>,
+    non_tabu_counts: NeighborCounts,
+    tabu_offered: u64,
+) -> NeighborCounts {
+    let aspiration_overrides = match (non_tabu_neighbor, aspirating_neighbor) {
+        (Some(non_tabu), Some(aspirating)) => objective.is_better(&aspirating.0, &non_tabu.0),
+        (None, Some(_)) => true,
+        (_, None) => false,
+    };
+    NeighborCounts {
+        generated: non_tabu_counts.generated + tabu_offered,
+        evaluated: non_tabu_counts.evaluated + tabu_offered,
+        rejected_as_tabu: tabu_offered.saturating_sub(u64::from(aspiration_overrides)),
+        rejected_by_threshold: non_tabu_counts.rejected_by_threshold,
+        accepted: non_tabu_counts
+            .accepted
+            .max(u64::from(aspiration_overrides)),
+    }
+}
+
+/// Logs the decision of [`prefer_aspirating_neighbor`] to the `acceptance_logger`: The neighbor
+/// that is taken is accepted, the other one (if any) is rejected.
+/// * The `non_tabu_neighbor` is not tabu, the `aspirating_neighbor` is tabu and satisfies the
+///   aspiration criterion.
+pub(crate) fn log_aspiration_decisions<S, T>(
+    acceptance_logger: &AcceptanceLogger,
+    objective: &Objective<S>,
+    current_solution: &EvaluatedSolution<S>,
+    non_tabu_neighbor: &Option<(EvaluatedSolution<S>, Vec<T>)>,
+    aspirating_neighbor: &Option<(EvaluatedSolution<S>, Vec<T>)>,
+) {
+    let aspiration_overrides = match (non_tabu_neighbor, aspirating_neighbor) {
+        (Some(non_tabu), Some(aspirating)) => objective.is_better(&aspirating.0, &non_tabu.0),
+        (None, Some(_)) => true,
+        (_, None) => false,
+    };
+    if let Some((neighbor, _)) = non_tabu_neighbor {
+        acceptance_logger.log(&AcceptanceDecision {
+            accepted: !aspiration_overrides,
+            current_objective_value: current_solution.objective_value(),
+            neighbor_objective_value: neighbor.objective_value(),
+            criterion: AcceptanceCriterion::Tabu {
+                tabu: false,
+                aspiration: false,
+            },
+        });
+    }
+    if let Some((neighbor, _)) = aspirating_neighbor {
+        acceptance_logger.log(&AcceptanceDecision {
+            accepted: aspiration_overrides,
+            current_objective_value: current_solution.objective_value(),
+            neighbor_objective_value: neighbor.objective_value(),
+            criterion: AcceptanceCriterion::Tabu {
+                tabu: true,
+                aspiration: true,
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Value;
+
+    #[test]
+    fn test_new_global_best_aspiration() {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        let current = objective.evaluate(5);
+        let best = objective.evaluate(3);
+        let criterion = NewGlobalBestAspiration;
+        assert!(AspirationCriterion::<i64, ()>::is_satisfied(
+            &criterion,
+            &objective.evaluate(2),
+            &[],
+            &current,
+            &best,
+            &objective
+        ));
+        assert!(!AspirationCriterion::<i64, ()>::is_satisfied(
+            &criterion,
+            &objective.evaluate(3),
+            &[],
+            &current,
+            &best,
+            &objective
+        ));
+    }
+
+    #[test]
+    fn test_aspiration() {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        let current = objective.evaluate(5);
+        let best = objective.evaluate(3);
+        let criterion = NewGlobalBestAspiration;
+        let aspiration = Aspiration::new(&criterion, &current, &best, &objective, true);
+        assert!(aspiration.is_active());
+        assert_eq!(
+            aspiration.filter_tabu(4, vec![1], false),
+            Some((4, vec![1]))
+        );
+        assert_eq!(aspiration.filter_tabu(2, vec![2], true), None);
+        assert_eq!(aspiration.filter_tabu(1, vec![3], true), None);
+        assert_eq!(aspiration.filter_tabu(6, vec![4], true), None);
+        let (aspirating_neighbor, offered) = aspiration.finish();
+        assert_eq!(aspirating_neighbor.unwrap().1, vec![3]);
+        assert_eq!(offered, 3);
+
+        // inactive with an empty tabu list or a criterion that cannot be satisfied
+        assert!(
+            !Aspiration::<i64, i64>::new(&criterion, &current, &best, &objective, false)
+                .is_active()
+        );
+        assert!(
+            !Aspiration::<i64, i64>::new(&NoAspiration, &current, &best, &objective, true)
+                .is_active()
+        );
+    }
+
+    #[test]
+    fn test_prefer_aspirating_neighbor() {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        let chosen = prefer_aspirating_neighbor(
+            &objective,
+            Some((objective.evaluate(4), vec![1])),
+            Some((objective.evaluate(2), vec![2])),
+        );
+        assert_eq!(chosen.unwrap().1, vec![2]);
+        let chosen = prefer_aspirating_neighbor(
+            &objective,
+            Some((objective.evaluate(4), vec![1])),
+            Some((objective.evaluate(4), vec![2])),
+        );
+        assert_eq!(chosen.unwrap().1, vec![1]);
+        let chosen =
+            prefer_aspirating_neighbor(&objective, None, Some((objective.evaluate(7), vec![2])));
+        assert_eq!(chosen.unwrap().1, vec![2]);
+    }
+
+    #[test]
+    fn test_counts_with_aspiration() {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        let non_tabu_counts = NeighborCounts {
+            generated: 3,
+            evaluated: 3,
+            accepted: 1,
+            ..NeighborCounts::default()
+        };
+        let non_tabu_neighbor = Some((objective.evaluate(4), vec![1]));
+        let counts = counts_with_aspiration(
+            &objective,
+            &non_tabu_neighbor,
+            &Some((objective.evaluate(2), vec![2])),
+            non_tabu_counts,
+            2,
+        );
+        assert_eq!(
+            counts,
+            NeighborCounts {
+                generated: 5,
+                evaluated: 5,
+                rejected_as_tabu: 1,
+                rejected_by_threshold: 0,
+                accepted: 1,
+            }
+        );
+        let counts =
+            counts_with_aspiration(&objective, &non_tabu_neighbor, &None, non_tabu_counts, 2);
+        assert_eq!(counts.rejected_as_tabu, 2);
+    }
+
+    #[test]
+    fn test_log_aspiration_decisions() {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        let current = objective.evaluate(5);
+        let non_tabu_neighbor = Some((objective.evaluate(4), vec![1]));
+        let aspirating_neighbor = Some((objective.evaluate(2), vec![2]));
+        let decision = AcceptanceDecision {
+            accepted: true,
+            current_objective_value: current.objective_value(),
+            neighbor_objective_value: current.objective_value(),
+            criterion: AcceptanceCriterion::Tabu {
+                tabu: false,
+                aspiration: false,
+            },
+        };
+
+        // the non-tabu and the aspirating neighbor are logged, which exhausts the rate limit
+        let logger = AcceptanceLogger::new(2);
+        log_aspiration_decisions(
+            &logger,
+            &objective,
+            &current,
+            &non_tabu_neighbor,
+            &aspirating_neighbor,
+        );
+        assert!(!logger.log(&decision));
+
+        // without aspirating neighbor, only the non-tabu neighbor is logged
+        let logger = AcceptanceLogger::new(2);
+        log_aspiration_decisions::<i64, i64>(
+            &logger,
+            &objective,
+            &current,
+            &non_tabu_neighbor,
+            &None,
+        );
+        assert!(logger.log(&decision));
+        assert!(!logger.log(&decision));
+    }
+}
@@ -0,0 +1,157 @@
+//! This module contains the [`CachedNeighborhood`], which remembers the neighbors of the most
+//! recent solution, such that repeated passes over the neighborhood of the same solution do not
+//! regenerate them.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::{Neighborhood, SolutionFingerprint};
+
+/// Wraps a [`Neighborhood`] and remembers the neighbors of the most recent solution, such that
+/// repeated passes over the neighborhood of the same solution (e.g., a recursion improver that
+/// visits the same solution twice or several improvers exploring the same solution) do not
+/// regenerate the neighbors but clone them from the cache.
+/// * The solutions are identified by a [`SolutionFingerprint`] (e.g., a hash of the solution),
+///   so solutions with the same fingerprint must have the same neighbors.
+/// * The neighbors are still generated lazily. They are only stored in the cache if the
+///   neighborhood is exhausted, i.e., passes that stop early (e.g.,
+///   [`TakeFirst`][crate::heuristics::local_search::local_improver::TakeFirst]) are not cached.
+/// * Only the neighbors of a single solution are cached, so the memory is bounded by the size
+///   of one neighborhood.
+pub struct CachedNeighborhood<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    fingerprint: SolutionFingerprint<S>,
+    cache: Mutex<Option<(u64, Arc<Vec<S>>)>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<S> CachedNeighborhood<S> {
+    /// Creates a new [`CachedNeighborhood`] that caches the neighbors of the `neighborhood` for
+    /// the most recent solution (identified by the `fingerprint`).
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        fingerprint: SolutionFingerprint<S>,
+    ) -> CachedNeighborhood<S> {
+        CachedNeighborhood {
+            neighborhood,
+            fingerprint,
+            cache: Mutex::new(None),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of passes that were answered by the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of passes that generated the neighbors by the wrapped neighborhood.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Forgets the cached neighbors.
+    pub fn clear(&self) {
+        *self.cache.lock().unwrap() = None;
+    }
+}
+
+impl<S: Clone + Send + Sync> Neighborhood<S> for CachedNeighborhood<S> {
+    fn neighbors_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a> {
+        let fingerprint = (self.fingerprint)(current_solution);
+        let cached_neighbors = match self.cache.lock().unwrap().as_ref() {
+            Some((cached_fingerprint, neighbors)) if *cached_fingerprint == fingerprint => {
+                Some(neighbors.clone())
+            }
+            _ => None,
+        };
+        if let Some(neighbors) = cached_neighbors {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Box::new((0..neighbors.len()).map(move |index| neighbors[index].clone()));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let mut neighbors = self.neighborhood.neighbors_of(current_solution);
+        let mut recorded_neighbors = Vec::new();
+        Box::new(std::iter::from_fn(move || match neighbors.next() {
+            Some(neighbor) => {
+                recorded_neighbors.push(neighbor.clone());
+                Some(neighbor)
+            }
+            None => {
+                if !recorded_neighbors.is_empty() {
+                    *self.cache.lock().unwrap() = Some((
+                        fingerprint,
+                        Arc::new(std::mem::take(&mut recorded_neighbors)),
+                    ));
+                }
+                None
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Neighborhood that counts how often it is asked for the neighbors.
+    struct CountingPlusMinusOne {
+        calls: Arc<AtomicU64>,
+    }
+
+    impl Neighborhood<i64> for CountingPlusMinusOne {
+        fn neighbors_of<'a>(
+            &'a self,
+            current_solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Box::new(
+                [-1, 1]
+                    .into_iter()
+                    .map(move |delta| current_solution + delta),
+            )
+        }
+    }
+
+    #[test]
+    fn test_cached_neighborhood() {
+        let calls = Arc::new(AtomicU64::new(0));
+        let neighborhood = CachedNeighborhood::new(
+            Arc::new(CountingPlusMinusOne {
+                calls: calls.clone(),
+            }),
+            Box::new(|solution: &i64| *solution as u64),
+        );
+
+        // a pass that stops early is not cached
+        assert_eq!(neighborhood.neighbors_of(&5).next(), Some(4));
+        assert_eq!(
+            neighborhood.neighbors_of(&5).collect::<Vec<_>>(),
+            vec![4, 6]
+        );
+        assert_eq!(
+            neighborhood.neighbors_of(&5).collect::<Vec<_>>(),
+            vec![4, 6]
+        );
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        assert_eq!((neighborhood.hits(), neighborhood.misses()), (1, 2));
+
+        // another solution replaces the cached neighbors
+        assert_eq!(
+            neighborhood.neighbors_of(&7).collect::<Vec<_>>(),
+            vec![6, 8]
+        );
+        assert_eq!(neighborhood.neighbors_of(&5).count(), 2);
+        assert_eq!(calls.load(Ordering::Relaxed), 4);
+
+        neighborhood.clear();
+        assert_eq!(neighborhood.neighbors_of(&5).count(), 2);
+        assert_eq!((neighborhood.hits(), neighborhood.misses()), (1, 5));
+    }
+}
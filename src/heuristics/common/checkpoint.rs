@@ -0,0 +1,254 @@
+//! This module contains the checkpoint subsystem: the [`CheckpointWriter`] periodically writes
+//! the state of a long-running search as JSON (via [`serde`](https://docs.rs/serde/)), such that
+//! the run can be resumed after a crash (see
+//! [`TabuSearchSolver::resume_from_checkpoint`][crate::heuristics::tabu_search::TabuSearchSolver::resume_from_checkpoint]).
+//! Currently, only the tabu search writes checkpoints.
+//!
+//! Requires the `checkpoint` feature.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time as stdtime;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::{ReactiveTenureState, TerminationProgress};
+
+/// The state of a [`TabuSearchSolver`][crate::heuristics::tabu_search::TabuSearchSolver] run.
+/// * The objective values of the solutions are not stored, they are recomputed on resume.
+/// * The state of the [`ReactiveTenure`][super::ReactiveTenure] and the progress of the
+///   improvement-based [`TerminationCriteria`][super::TerminationCriteria] (e.g., the iterations
+///   without improvement) are restored on resume, such that the resumed run behaves as if it had
+///   not been interrupted.
+/// * `elapsed_seconds` is the running time before the checkpoint was written, such that the time
+///   limits of the solver include the time before the crash.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TabuSearchCheckpoint<S, T> {
+    /// The current solution.
+    pub current_solution: S,
+    /// The best solution seen so far.
+    pub best_solution: S,
+    /// The tabu list (oldest tabu first).
+    pub tabu_list: VecDeque<T>,
+    /// The last finished iteration.
+    pub iteration: u32,
+    /// The running time (in seconds) before the checkpoint was written.
+    pub elapsed_seconds: f64,
+    /// The state of the [`ReactiveTenure`][super::ReactiveTenure] (`None` if not configured).
+    #[serde(default)]
+    pub reactive_tenure_state: Option<ReactiveTenureState>,
+    /// The progress of the [`TerminationCriteria`][super::TerminationCriteria] (`None` if not
+    /// configured).
+    #[serde(default)]
+    pub termination_progress: Option<TerminationProgress>,
+}
+
+impl<S: DeserializeOwned, T: DeserializeOwned> TabuSearchCheckpoint<S, T> {
+    /// Loads a checkpoint that was written by a [`CheckpointWriter`].
+    pub fn load(path: impl AsRef<Path>) -> Result<TabuSearchCheckpoint<S, T>, CheckpointError> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// Error that is returned if a checkpoint cannot be written or loaded.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// The checkpoint file cannot be read or written.
+    Io(std::io::Error),
+    /// The checkpoint cannot be (de)serialized.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(error) => write!(f, "Cannot access checkpoint file: {}", error),
+            CheckpointError::Json(error) => write!(f, "Invalid checkpoint: {}", error),
+        }
+    }
+}
+
+impl Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckpointError::Io(error) => Some(error),
+            CheckpointError::Json(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for CheckpointError {
+    fn from(error: std::io::Error) -> Self {
+        CheckpointError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(error: serde_json::Error) -> Self {
+        CheckpointError::Json(error)
+    }
+}
+
+/// Writes a checkpoint to a file at most once per `interval`.
+/// * The checkpoint is first written to a temporary file (`<path>.tmp`), which then replaces the
+///   previous checkpoint, such that a crash during writing does not corrupt the last checkpoint.
+/// * If a checkpoint cannot be written, a warning is reported and the search continues.
+pub struct CheckpointWriter {
+    path: PathBuf,
+    interval: stdtime::Duration,
+    last_written: Mutex<Option<stdtime::Instant>>,
+}
+
+impl CheckpointWriter {
+    /// Creates a new [`CheckpointWriter`] that writes to `path` at most once per `interval`. The
+    /// first checkpoint is written after the first iteration.
+    pub fn new(path: impl Into<PathBuf>, interval: stdtime::Duration) -> CheckpointWriter {
+        CheckpointWriter {
+            path: path.into(),
+            interval,
+            last_written: Mutex::new(None),
+        }
+    }
+
+    /// Returns the path of the checkpoint file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if the interval since the last checkpoint has passed. In this case, the
+    /// caller is expected to [`write`][CheckpointWriter::write] a checkpoint.
+    pub fn is_due(&self) -> bool {
+        let mut last_written = self.last_written.lock().unwrap();
+        match *last_written {
+            Some(instant) if instant.elapsed() < self.interval => false,
+            _ => {
+                *last_written = Some(stdtime::Instant::now());
+                true
+            }
+        }
+    }
+
+    /// Writes the `checkpoint` (regardless of the interval).
+    pub fn write<C: Serialize>(&self, checkpoint: &C) -> Result<(), CheckpointError> {
+        let mut temporary_path = self.path.clone().into_os_string();
+        temporary_path.push(".tmp");
+        fs::write(&temporary_path, serde_json::to_string(checkpoint)?)?;
+        fs::rename(&temporary_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::heuristics::common::{FunctionBetweenSteps, ReactiveTenure, TerminationCriteria};
+    use crate::heuristics::tabu_search::TabuSearchSolver;
+    use crate::heuristics::Solver;
+    use crate::objective::Objective;
+    use crate::test_fixtures::{DistanceTo, PlusMinusOne};
+
+    fn tabu_search(iteration_limit: u32) -> TabuSearchSolver<i64, i64> {
+        tabu_search_with(
+            TerminationCriteria::iteration_limit(iteration_limit),
+            Box::new(|_, _, _, _, _, _, _, _| {}),
+        )
+    }
+
+    fn tabu_search_with(
+        termination_criteria: TerminationCriteria,
+        function_between_steps: FunctionBetweenSteps<i64>,
+    ) -> TabuSearchSolver<i64, i64> {
+        TabuSearchSolver::with_options(
+            Arc::new(PlusMinusOne),
+            Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+                DistanceTo(100),
+            )])),
+            3,
+            None,
+            Some(function_between_steps),
+            Some(termination_criteria),
+        )
+    }
+
+    fn checkpoint_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "rapid_solve_checkpoint_test_{}_{}.json",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_checkpoint_and_resume() {
+        let path = checkpoint_path("resume");
+        let solver = tabu_search(5)
+            .with_checkpoints(CheckpointWriter::new(path.clone(), stdtime::Duration::ZERO));
+        assert_eq!(*solver.solve(0).solution(), 5);
+
+        let checkpoint: TabuSearchCheckpoint<i64, i64> = TabuSearchCheckpoint::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(checkpoint.iteration, 5);
+        assert_eq!(checkpoint.best_solution, 5);
+        assert_eq!(checkpoint.tabu_list, VecDeque::from(vec![2, 3, 4]));
+
+        // the resumed run continues with iteration 6 until the iteration limit of 8
+        assert_eq!(
+            *tabu_search(8).resume_from_checkpoint(checkpoint).solution(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_resume_restores_reactive_tenure_and_termination_progress() {
+        let path = checkpoint_path("progress");
+        let last_iteration = Arc::new(AtomicU32::new(0));
+        // starting in the optimum 100, no iteration improves the best solution
+        let solver = |iteration_limit| {
+            let last_iteration = last_iteration.clone();
+            tabu_search_with(
+                TerminationCriteria::iteration_limit(iteration_limit)
+                    .or(TerminationCriteria::iterations_without_improvement(5)),
+                Box::new(move |iteration, _, _, _, _, _, _, _| {
+                    last_iteration.store(iteration, Ordering::SeqCst);
+                }),
+            )
+            .with_reactive_tenure(ReactiveTenure::new(
+                2,
+                10,
+                Box::new(|s: &i64| *s as u64),
+            ))
+        };
+        solver(4)
+            .with_checkpoints(CheckpointWriter::new(path.clone(), stdtime::Duration::ZERO))
+            .solve(100);
+
+        let checkpoint: TabuSearchCheckpoint<i64, i64> = TabuSearchCheckpoint::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(checkpoint.iteration, 4);
+        let reactive_tenure_state =
+            serde_json::to_value(checkpoint.reactive_tenure_state.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            reactive_tenure_state["visited"].as_object().unwrap().len(),
+            4
+        );
+        assert!(checkpoint.termination_progress.is_some());
+
+        // 3 iterations without improvement before the checkpoint, so the limit of 5 is reached
+        // in iteration 6 (instead of 10 if the counting started anew)
+        assert_eq!(
+            *solver(100).resume_from_checkpoint(checkpoint).solution(),
+            100
+        );
+        assert_eq!(last_iteration.load(Ordering::SeqCst), 6);
+    }
+}
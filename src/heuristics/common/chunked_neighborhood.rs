@@ -0,0 +1,79 @@
+//! This module contains the [`ChunkedNeighborhood`], which explores a sequential [`Neighborhood`]
+//! in parallel with a configurable task granularity.
+
+use std::sync::Arc;
+
+use super::{Neighborhood, ParallelNeighborhood};
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::{ParallelBridge, ParallelIterator};
+
+/// Wraps a [`Neighborhood`] into a [`ParallelNeighborhood`] whose tasks consist of chunks of
+/// `chunk_size` consecutive neighbors.
+/// * The neighbors are generated sequentially, but each chunk is processed (e.g., evaluated by a
+///   [`ParallelMinimizer`][crate::heuristics::parallel_local_search::parallel_local_improver::ParallelMinimizer])
+///   as one task. For cheap neighbors (e.g., 3-opt moves), a chunk size of a few hundred avoids
+///   that the scheduling overhead of single-neighbor tasks dominates on many-core machines.
+/// * For neighborhoods that are parallel themselves, the granularity of indexed iterators can be
+///   controlled by [`with_min_len`][crate::parallel::IndexedParallelIterator::with_min_len]
+///   instead.
+/// * Without the `rayon` feature, the chunks are processed one after another.
+pub struct ChunkedNeighborhood<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    chunk_size: usize,
+}
+
+impl<S> ChunkedNeighborhood<S> {
+    /// Creates a new [`ChunkedNeighborhood`] that hands out the neighbors of the `neighborhood`
+    /// in chunks of `chunk_size` (at least 1) neighbors.
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        chunk_size: usize,
+    ) -> ChunkedNeighborhood<S> {
+        ChunkedNeighborhood {
+            neighborhood,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+}
+
+impl<S: Send + Sync> ParallelNeighborhood<S> for ChunkedNeighborhood<S> {
+    fn neighbors_of<'a>(&'a self, current_solution: &'a S) -> impl ParallelIterator<Item = S> + 'a {
+        let mut neighbors = self.neighborhood.neighbors_of(current_solution);
+        std::iter::from_fn(move || {
+            let chunk: Vec<S> = neighbors.by_ref().take(self.chunk_size).collect();
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        })
+        .par_bridge()
+        .flat_map_iter(|chunk| chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Range(u32);
+
+    impl Neighborhood<u32> for Range {
+        fn neighbors_of<'a>(
+            &'a self,
+            _: &'a u32,
+        ) -> Box<dyn Iterator<Item = u32> + Send + Sync + 'a> {
+            Box::new(0..self.0)
+        }
+    }
+
+    #[test]
+    fn test_chunked_neighborhood() {
+        for chunk_size in [0, 1, 7, 100, 1000] {
+            let neighborhood = ChunkedNeighborhood::new(Arc::new(Range(100)), chunk_size);
+            let mut neighbors: Vec<u32> = neighborhood.neighbors_of(&0).collect();
+            neighbors.sort();
+            assert_eq!(neighbors, (0..100).collect::<Vec<u32>>());
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! This module contains selection operators for population-based solvers that handle constraints
+//! modelled as a violation level of the hierarchical objective: [`stochastic_ranking`] and the
+//! [`feasibility_rules`] (with a [`tournament_selection`] to apply them).
+
+use std::cmp::Ordering;
+
+use rand::Rng;
+use rand::RngCore;
+
+use crate::objective::EvaluatedSolution;
+
+/// Compares two solutions by Deb's feasibility rules, where a solution is feasible if the
+/// `violation_level` of its objective value is zero:
+/// * A feasible solution is better than an infeasible one.
+/// * Two feasible solutions are compared by the `cost_level`.
+/// * Two infeasible solutions are compared by the `violation_level`.
+///
+/// Returns [`Ordering::Less`] if `a` is better than `b`.
+pub fn feasibility_rules<S>(
+    a: &EvaluatedSolution<S>,
+    b: &EvaluatedSolution<S>,
+    violation_level: usize,
+    cost_level: usize,
+) -> Ordering {
+    let a_value = a.objective_value();
+    let b_value = b.objective_value();
+    match (
+        a_value.level_is_zero(violation_level),
+        b_value.level_is_zero(violation_level),
+    ) {
+        (true, true) => a_value.as_vec()[cost_level].cmp(&b_value.as_vec()[cost_level]),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a_value.as_vec()[violation_level].cmp(&b_value.as_vec()[violation_level]),
+    }
+}
+
+/// Sorts the `population` (best first) by stochastic ranking (Runarsson and Yao, 2000).
+/// * The population is sorted by a bubble-sort-like procedure, where two adjacent solutions are
+///   compared by the `cost_level` if both are feasible (i.e., their `violation_level` is zero) or
+///   with probability `comparison_probability` (usually 0.4 to 0.45). Otherwise, they are compared
+///   by the `violation_level`.
+/// * Hence, infeasible solutions with low costs have a chance to survive, which balances the
+///   violation and the cost level instead of strictly minimizing the violation first (as the
+///   hierarchical objective does).
+/// * At most `population.len()` sweeps are performed; the sorting stops early if a sweep does not
+///   swap any solutions.
+pub fn stochastic_ranking<S>(
+    population: &mut [EvaluatedSolution<S>],
+    violation_level: usize,
+    cost_level: usize,
+    comparison_probability: f64,
+    rng: &mut dyn RngCore,
+) {
+    for _ in 0..population.len() {
+        let mut swapped = false;
+        for j in 0..population.len().saturating_sub(1) {
+            let a_value = population[j].objective_value();
+            let b_value = population[j + 1].objective_value();
+            let both_feasible =
+                a_value.level_is_zero(violation_level) && b_value.level_is_zero(violation_level);
+            let level = if both_feasible || rng.gen::<f64>() < comparison_probability {
+                cost_level
+            } else {
+                violation_level
+            };
+            if a_value.as_vec()[level] > b_value.as_vec()[level] {
+                population.swap(j, j + 1);
+                swapped = true;
+            }
+        }
+        if !swapped {
+            break;
+        }
+    }
+}
+
+/// Selects the best of `tournament_size` uniformly drawn solutions (with replacement) of the
+/// `population` w.r.t. `compare` (e.g., the [`feasibility_rules`]).
+/// Panics if the population is empty.
+pub fn tournament_selection<'a, S>(
+    population: &'a [EvaluatedSolution<S>],
+    tournament_size: usize,
+    compare: impl Fn(&EvaluatedSolution<S>, &EvaluatedSolution<S>) -> Ordering,
+    rng: &mut dyn RngCore,
+) -> &'a EvaluatedSolution<S> {
+    if population.is_empty() {
+        panic!("Cannot select from an empty population.");
+    }
+    (0..tournament_size.max(1))
+        .map(|_| &population[rng.gen_range(0..population.len())])
+        .min_by(|a, b| compare(a, b))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::objective::{BaseValue, ObjectiveValue};
+
+    fn solution(id: u32, violation: i64, cost: i64) -> EvaluatedSolution<u32> {
+        EvaluatedSolution::new(
+            id,
+            ObjectiveValue::new(vec![
+                BaseValue::Integer(violation),
+                BaseValue::Integer(cost),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_constrained_selection() {
+        let population = vec![
+            solution(0, 2, 1),
+            solution(1, 0, 10),
+            solution(2, 1, 5),
+            solution(3, 0, 7),
+        ];
+        let mut sorted: Vec<u32> = {
+            let mut population = population.clone();
+            population.sort_by(|a, b| feasibility_rules(a, b, 0, 1));
+            population.iter().map(|s| *s.solution()).collect()
+        };
+        assert_eq!(sorted, vec![3, 1, 2, 0]);
+
+        // with comparison probability 0, stochastic ranking equals the feasibility rules
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut ranked = population.clone();
+        stochastic_ranking(&mut ranked, 0, 1, 0.0, &mut rng);
+        sorted = ranked.iter().map(|s| *s.solution()).collect();
+        assert_eq!(sorted, vec![3, 1, 2, 0]);
+
+        // with comparison probability 1, only the costs are considered
+        stochastic_ranking(&mut ranked, 0, 1, 1.0, &mut rng);
+        sorted = ranked.iter().map(|s| *s.solution()).collect();
+        assert_eq!(sorted, vec![0, 2, 3, 1]);
+
+        // a tournament over the whole population (with high probability) finds the best
+        let winner = tournament_selection(
+            &population,
+            20,
+            |a, b| feasibility_rules(a, b, 0, 1),
+            &mut rng,
+        );
+        assert_eq!(*winner.solution(), 3);
+    }
+}
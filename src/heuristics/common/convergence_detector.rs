@@ -0,0 +1,105 @@
+//! This module contains the [`ConvergenceDetector`], a termination criterion based on the
+//! coefficient of variation of the best objective value over the last iterations.
+
+use std::collections::VecDeque;
+
+use crate::objective::ObjectiveValue;
+
+/// Detects convergence by the relative standard deviation (coefficient of variation) of the best
+/// objective value over the last `window` iterations.
+/// * The search is considered converged as soon as `window` values have been recorded and their
+///   standard deviation divided by the absolute value of their mean falls below `threshold`
+///   (e.g., 0.001). If the mean is zero, the search is converged if all values are zero.
+/// * Only the first (most important) level of the [`ObjectiveValue`] is considered.
+///   [`BaseValue::Maximum`][crate::objective::BaseValue::Maximum] is never considered converged.
+/// * This is useful for stochastic solvers (e.g., simulated annealing), for which absolute
+///   stagnation is hard to define.
+/// * The solvers clone the detector at the start of each run, so the same detector can be used
+///   for several runs.
+#[derive(Clone, Debug)]
+pub struct ConvergenceDetector {
+    window: usize,
+    threshold: f64,
+    recent_values: VecDeque<f64>,
+}
+
+impl ConvergenceDetector {
+    /// Creates a new [`ConvergenceDetector`] that considers the last `window` iterations and
+    /// stops if the coefficient of variation is below `threshold`.
+    pub fn new(window: usize, threshold: f64) -> ConvergenceDetector {
+        ConvergenceDetector {
+            window: window.max(1),
+            threshold,
+            recent_values: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Records the best objective value of the current iteration. Returns `true` if the search
+    /// has converged.
+    pub fn record(&mut self, best_objective_value: &ObjectiveValue) -> bool {
+        let value = best_objective_value
+            .iter()
+            .next()
+            .map_or(f64::INFINITY, |value| value.to_f64());
+        if self.recent_values.len() == self.window {
+            self.recent_values.pop_front();
+        }
+        self.recent_values.push_back(value);
+        self.has_converged()
+    }
+
+    /// Returns the coefficient of variation of the recorded values, or `None` if fewer than
+    /// `window` values have been recorded (or a value is infinite).
+    pub fn coefficient_of_variation(&self) -> Option<f64> {
+        if self.recent_values.len() < self.window
+            || self.recent_values.iter().any(|value| value.is_infinite())
+        {
+            return None;
+        }
+        let count = self.recent_values.len() as f64;
+        let mean = self.recent_values.iter().sum::<f64>() / count;
+        let variance = self
+            .recent_values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let standard_deviation = variance.sqrt();
+        if mean == 0.0 {
+            return Some(if standard_deviation == 0.0 {
+                0.0
+            } else {
+                f64::INFINITY
+            });
+        }
+        Some(standard_deviation / mean.abs())
+    }
+
+    /// Returns `true` if the coefficient of variation is below the threshold.
+    pub fn has_converged(&self) -> bool {
+        self.coefficient_of_variation()
+            .is_some_and(|coefficient_of_variation| coefficient_of_variation < self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::BaseValue;
+
+    #[test]
+    fn test_convergence_detector() {
+        let mut detector = ConvergenceDetector::new(3, 0.01);
+        let value = |v: f64| ObjectiveValue::new(vec![BaseValue::Float(v)]);
+
+        assert!(!detector.record(&value(100.0)));
+        assert!(!detector.record(&value(90.0))); // window not full yet
+        assert!(!detector.record(&value(80.0))); // cv ~ 0.09
+        assert!(!detector.record(&value(80.0)));
+        assert!(detector.record(&value(80.0))); // cv = 0
+
+        let mut zero_detector = ConvergenceDetector::new(2, 0.01);
+        assert!(!zero_detector.record(&ObjectiveValue::new(vec![BaseValue::Zero])));
+        assert!(zero_detector.record(&ObjectiveValue::new(vec![BaseValue::Integer(0)])));
+    }
+}
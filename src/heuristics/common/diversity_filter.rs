@@ -0,0 +1,197 @@
+//! This module contains the [`SolutionDistance`] trait and the [`DiversityFilter`], which rejects
+//! solutions that are too similar to recently visited solutions to force diversification.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use super::{default_function_between_steps, FunctionBetweenSteps, Neighborhood};
+
+/// Measures how different two solutions are (e.g., the number of differing arcs of two tours or
+/// the Hamming distance of two assignments).
+/// * The distance should be non-negative and `0.0` for identical solutions.
+pub trait SolutionDistance<S>: Send + Sync {
+    /// Returns the distance between the solutions `a` and `b`.
+    fn distance(&self, a: &S, b: &S) -> f64;
+}
+
+/// Acceptance filter that only accepts solutions whose [`SolutionDistance`] to each of the
+/// recently visited solutions is at least `minimum_distance`.
+/// * The recent solutions are kept in a ring buffer of size `memory_size`, i.e., if the buffer is
+///   full, the oldest solution is forgotten.
+/// * Can be used with any solver: Wrap the neighborhood into a [`DiversityFilteredNeighborhood`]
+///   (which skips all neighbors that are too close to the recent solutions) and pass the
+///   [`recording_function_between_steps`][DiversityFilter::recording_function_between_steps]
+///   as `function_between_steps` (which remembers each new solution of the solver).
+/// * The filter is shared via [`Arc`] between the neighborhood and the `function_between_steps`.
+pub struct DiversityFilter<S> {
+    distance: Arc<dyn SolutionDistance<S>>,
+    minimum_distance: f64,
+    memory_size: usize,
+    recent_solutions: Mutex<VecDeque<S>>,
+}
+
+impl<S> DiversityFilter<S> {
+    /// Creates a new [`DiversityFilter`] with the given [`SolutionDistance`], that remembers the
+    /// `memory_size` most recent solutions and rejects all solutions whose distance to one of them
+    /// is smaller than `minimum_distance`.
+    pub fn new(
+        distance: Arc<dyn SolutionDistance<S>>,
+        minimum_distance: f64,
+        memory_size: usize,
+    ) -> Arc<DiversityFilter<S>> {
+        Arc::new(DiversityFilter {
+            distance,
+            minimum_distance,
+            memory_size,
+            recent_solutions: Mutex::new(VecDeque::with_capacity(memory_size)),
+        })
+    }
+
+    /// Returns `true` if the `solution` has at least the `minimum_distance` to all recent
+    /// solutions.
+    pub fn is_diverse(&self, solution: &S) -> bool {
+        self.recent_solutions
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|recent_solution| {
+                self.distance.distance(solution, recent_solution) >= self.minimum_distance
+            })
+    }
+
+    /// Adds the `solution` to the recent solutions (and forgets the oldest one if the memory is
+    /// full).
+    pub fn remember(&self, solution: S) {
+        if self.memory_size == 0 {
+            return;
+        }
+        let mut recent_solutions = self.recent_solutions.lock().unwrap();
+        if recent_solutions.len() == self.memory_size {
+            recent_solutions.pop_front();
+        }
+        recent_solutions.push_back(solution);
+    }
+
+    /// Forgets all recent solutions, e.g., before the filter is used for another solver run.
+    pub fn clear(&self) {
+        self.recent_solutions.lock().unwrap().clear();
+    }
+}
+
+impl<S: Clone + Send + 'static> DiversityFilter<S> {
+    /// Returns a [`FunctionBetweenSteps`] that remembers each new solution of the solver and then
+    /// executes the given `function_between_steps` (if `None`, the default function between steps
+    /// is executed).
+    pub fn recording_function_between_steps(
+        filter: Arc<DiversityFilter<S>>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+    ) -> FunctionBetweenSteps<S> {
+        let function_between_steps =
+            function_between_steps.unwrap_or(default_function_between_steps());
+        Box::new(
+            move |iteration,
+                  current_solution,
+                  previous_solution,
+                  objective,
+                  start_time,
+                  time_limit,
+                  iteration_limit,
+                  neighbor_counts| {
+                filter.remember(current_solution.solution().clone());
+                function_between_steps(
+                    iteration,
+                    current_solution,
+                    previous_solution,
+                    objective,
+                    start_time,
+                    time_limit,
+                    iteration_limit,
+                    neighbor_counts,
+                );
+            },
+        )
+    }
+}
+
+/// Wraps a [`Neighborhood`] and skips all neighbors that are rejected by the [`DiversityFilter`].
+pub struct DiversityFilteredNeighborhood<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    filter: Arc<DiversityFilter<S>>,
+}
+
+impl<S> DiversityFilteredNeighborhood<S> {
+    /// Creates a new [`DiversityFilteredNeighborhood`] for the given [`Neighborhood`] and
+    /// [`DiversityFilter`].
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        filter: Arc<DiversityFilter<S>>,
+    ) -> DiversityFilteredNeighborhood<S> {
+        DiversityFilteredNeighborhood {
+            neighborhood,
+            filter,
+        }
+    }
+}
+
+impl<S: Send> Neighborhood<S> for DiversityFilteredNeighborhood<S> {
+    fn neighbors_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a> {
+        Box::new(
+            self.neighborhood
+                .neighbors_of(current_solution)
+                .filter(|neighbor| self.filter.is_diverse(neighbor)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AbsoluteDifference;
+
+    impl SolutionDistance<i64> for AbsoluteDifference {
+        fn distance(&self, a: &i64, b: &i64) -> f64 {
+            (a - b).abs() as f64
+        }
+    }
+
+    struct PlusMinusOneTwo;
+
+    impl Neighborhood<i64> for PlusMinusOneTwo {
+        fn neighbors_of<'a>(
+            &'a self,
+            current_solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new(
+                [-2, -1, 1, 2]
+                    .into_iter()
+                    .map(move |delta| current_solution + delta),
+            )
+        }
+    }
+
+    #[test]
+    fn test_diversity_filter() {
+        let filter = DiversityFilter::new(Arc::new(AbsoluteDifference), 2.0, 2);
+        filter.remember(0);
+        filter.remember(10);
+
+        let neighborhood =
+            DiversityFilteredNeighborhood::new(Arc::new(PlusMinusOneTwo), filter.clone());
+        // neighbors of 1 that are at least 2 away from 0 and 10
+        assert_eq!(
+            neighborhood.neighbors_of(&1).collect::<Vec<i64>>(),
+            vec![2, 3]
+        );
+
+        filter.remember(20); // 0 is forgotten
+        assert!(filter.is_diverse(&1));
+        assert!(!filter.is_diverse(&19));
+
+        filter.clear();
+        assert!(filter.is_diverse(&20));
+    }
+}
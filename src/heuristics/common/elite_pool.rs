@@ -0,0 +1,238 @@
+//! This module contains the [`ElitePool`], which stores the best diverse solutions found by one or
+//! several solvers and provides them for restarts and diversification.
+
+use std::sync::{Arc, Mutex};
+
+use super::SolutionDistance;
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// A thread-safe pool of the `capacity` best solutions found, which have pairwise a
+/// [`SolutionDistance`] of at least `minimum_distance`.
+/// * Solutions are added by [`offer`][ElitePool::offer]: A solution that is too close to a member
+///   replaces this member if it is better, otherwise it is rejected. A solution that is far enough
+///   from all members is added as long as the pool is not full, and afterwards replaces the worst
+///   member if it is better than it.
+/// * The pool can be plugged into the [`TabuSearchSolver`][crate::heuristics::tabu_search::TabuSearchSolver],
+///   the [`SimulatedAnnealingSolver`][crate::heuristics::simulated_annealing::SimulatedAnnealingSolver]
+///   and the [`LnsSolver`][crate::heuristics::large_neighborhood_search::LnsSolver] (see their
+///   `with_elite_pool` methods). The solvers offer each new current solution to the pool and, if
+///   there is no new best solution for a number of iterations, restart from the elite solution
+///   that is [most distant][ElitePool::most_distant_from] from the current solution.
+/// * As the pool is shared via [`Arc`], several solvers (e.g., in a
+///   [`PortfolioSolver`][super::PortfolioSolver]) can fill and use the same pool.
+pub struct ElitePool<S> {
+    capacity: usize,
+    distance: Arc<dyn SolutionDistance<S>>,
+    minimum_distance: f64,
+    objective: Arc<Objective<S>>,
+    members: Mutex<Vec<EvaluatedSolution<S>>>,
+}
+
+impl<S> ElitePool<S> {
+    /// Creates a new empty [`ElitePool`] with at most `capacity` members, whose pairwise distance
+    /// (w.r.t. the [`SolutionDistance`]) is at least `minimum_distance`.
+    pub fn new(
+        capacity: usize,
+        distance: Arc<dyn SolutionDistance<S>>,
+        minimum_distance: f64,
+        objective: Arc<Objective<S>>,
+    ) -> Arc<ElitePool<S>> {
+        Arc::new(ElitePool {
+            capacity,
+            distance,
+            minimum_distance,
+            objective,
+            members: Mutex::new(Vec::with_capacity(capacity)),
+        })
+    }
+
+    /// Returns the maximal number of members.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.members.lock().unwrap().len()
+    }
+
+    /// Returns `true` if the pool has no members.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all members, e.g., before the pool is used for another instance.
+    pub fn clear(&self) {
+        self.members.lock().unwrap().clear();
+    }
+}
+
+impl<S: Clone> ElitePool<S> {
+    /// Offers the `solution` to the pool (see [`ElitePool`]). Returns `true` if the solution
+    /// became a member.
+    pub fn offer(&self, solution: &EvaluatedSolution<S>) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+        let mut members = self.members.lock().unwrap();
+        let closest_member = members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| {
+                (
+                    index,
+                    self.distance
+                        .distance(solution.solution(), member.solution()),
+                )
+            })
+            .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2));
+
+        let replaced_index = match closest_member {
+            Some((index, distance)) if distance < self.minimum_distance => index,
+            _ if members.len() < self.capacity => {
+                members.push(solution.clone());
+                return true;
+            }
+            _ => (0..members.len())
+                .max_by(|&i, &j| self.objective.compare(&members[i], &members[j]))
+                .unwrap(),
+        };
+        if self.objective.is_better(solution, &members[replaced_index]) {
+            members[replaced_index] = solution.clone();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the members ordered by their objective value (best first).
+    pub fn members(&self) -> Vec<EvaluatedSolution<S>> {
+        let mut members = self.members.lock().unwrap().clone();
+        members.sort_by(|a, b| self.objective.compare(a, b));
+        members
+    }
+
+    /// Returns the best member (or `None` if the pool is empty).
+    pub fn best(&self) -> Option<EvaluatedSolution<S>> {
+        self.members
+            .lock()
+            .unwrap()
+            .iter()
+            .min_by(|a, b| self.objective.compare(a, b))
+            .cloned()
+    }
+
+    /// Returns the member with the largest distance to the `solution` (e.g., to restart a
+    /// stagnating search in a different region). Returns `None` if there is no member with a
+    /// positive distance.
+    pub fn most_distant_from(&self, solution: &S) -> Option<EvaluatedSolution<S>> {
+        self.members
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|member| (self.distance.distance(solution, member.solution()), member))
+            .filter(|(distance, _)| *distance > 0.0)
+            .max_by(|(d1, _), (d2, _)| d1.total_cmp(d2))
+            .map(|(_, member)| member.clone())
+    }
+
+    /// Offers the `current_solution` of a solver to the pool and replaces it by the most distant
+    /// member if the solver did not find a new best solution for `restart_after` iterations
+    /// (counted by `iterations_without_improvement`, which is reset on a restart). Returns `true`
+    /// if the solver restarted.
+    pub(crate) fn offer_or_restart(
+        &self,
+        current_solution: &mut EvaluatedSolution<S>,
+        iterations_without_improvement: &mut u32,
+        restart_after: Option<u32>,
+    ) -> bool {
+        self.offer(current_solution);
+        match restart_after {
+            Some(restart_after) if *iterations_without_improvement >= restart_after => {
+                *iterations_without_improvement = 0;
+                match self.most_distant_from(current_solution.solution()) {
+                    Some(elite_solution) => {
+                        report!(Info, "Restart from an elite solution.");
+                        *current_solution = elite_solution;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::{silent_function_between_steps, TerminationCriteria};
+    use crate::heuristics::tabu_search::TabuSearchSolver;
+    use crate::heuristics::Solver;
+    use crate::test_fixtures::{DistanceTo, PlusMinusOne};
+
+    struct AbsoluteDifference;
+
+    impl SolutionDistance<i64> for AbsoluteDifference {
+        fn distance(&self, a: &i64, b: &i64) -> f64 {
+            (a - b).abs() as f64
+        }
+    }
+
+    fn objective() -> Arc<Objective<i64>> {
+        Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            DistanceTo(50),
+        )]))
+    }
+
+    #[test]
+    fn test_elite_pool() {
+        let objective = objective();
+        let pool = ElitePool::new(2, Arc::new(AbsoluteDifference), 5.0, objective.clone());
+
+        assert!(pool.offer(&objective.evaluate(20)));
+        // too close to 20 but better, so it replaces 20
+        assert!(pool.offer(&objective.evaluate(22)));
+        // too close to 22 and worse
+        assert!(!pool.offer(&objective.evaluate(18)));
+        assert!(pool.offer(&objective.evaluate(40)));
+        // far from all members and better than the worst member (22)
+        assert!(pool.offer(&objective.evaluate(60)));
+        assert!(!pool.offer(&objective.evaluate(30)));
+
+        let mut members: Vec<i64> = pool
+            .members()
+            .iter()
+            .map(|member| *member.solution())
+            .collect();
+        // both members have the same objective value
+        members.sort();
+        assert_eq!(members, vec![40, 60]);
+        assert_eq!(*pool.most_distant_from(&45).unwrap().solution(), 60);
+        assert!(
+            ElitePool::new(2, Arc::new(AbsoluteDifference), 5.0, objective)
+                .most_distant_from(&0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_tabu_search_with_elite_pool() {
+        let objective = objective();
+        let pool = ElitePool::new(3, Arc::new(AbsoluteDifference), 5.0, objective.clone());
+        let solver = TabuSearchSolver::with_options(
+            Arc::new(PlusMinusOne),
+            objective,
+            4,
+            None,
+            Some(silent_function_between_steps()),
+            Some(TerminationCriteria::iteration_limit(200)),
+        )
+        .with_elite_pool(pool.clone(), Some(5));
+
+        assert_eq!(*solver.solve(0).solution(), 50);
+        assert_eq!(pool.len(), 3);
+        assert_eq!(*pool.best().unwrap().solution(), 50);
+    }
+}
@@ -1,11 +1,13 @@
 //! This module contains the FunctionBetweenSteps type which is used to define a function that is
 //! called between steps of a solver.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time as stdtime;
 use std::time::Instant;
 
+use super::NeighborCounts;
 use crate::objective::{EvaluatedSolution, Objective};
+use crate::progress::{self, Verbosity};
 /// Type for a function that is executed between steps.
 /// * iteration counter
 /// * current solution
@@ -14,6 +16,7 @@ use crate::objective::{EvaluatedSolution, Objective};
 /// * time that local search started (Option)
 /// * time limit (Option)
 /// * iteration limit (Option)
+/// * neighbor counts of the iteration (Option), see [`NeighborCounts`]
 pub type FunctionBetweenSteps<S> = Box<
     dyn Fn(
             u32,
@@ -23,14 +26,19 @@ pub type FunctionBetweenSteps<S> = Box<
             Option<Instant>,
             Option<stdtime::Duration>,
             Option<u32>,
+            Option<NeighborCounts>,
         ) + Send
         + Sync,
 >;
 
-/// Default function between steps that prints the iteration number, the objective value of the
+/// Default function between steps that reports the iteration number, the objective value of the
 /// current solution, the comparison of the objective value of the current solution with the
-/// previous solution (if it exists), the elapsed time for the local search, and the time and
-/// iteration limits (if they exist).
+/// previous solution (if it exists), the elapsed time for the local search, the time and
+/// iteration limits (if they exist) and the number of evaluated neighbors (if counted).
+/// * The output is reported via the [`progress`][crate::progress] module with
+///   [`Verbosity::Info`], so it can be redirected by a [`ProgressSink`][crate::progress::ProgressSink]
+///   or silenced by [`set_verbosity`][crate::progress::set_verbosity] (see also
+///   [`silent_function_between_steps`]).
 pub fn default_function_between_steps<S>() -> FunctionBetweenSteps<S> {
     Box::new(
         |iteration,
@@ -39,30 +47,42 @@ pub fn default_function_between_steps<S>() -> FunctionBetweenSteps<S> {
          objective,
          start_time,
          time_limit,
-         iteration_limit| {
-            println!("\nIteration {}:", iteration,);
+         iteration_limit,
+         neighbor_counts| {
+            if !progress::is_reported(Verbosity::Info) {
+                return;
+            }
+            let mut message = format!("\nIteration {}:\n", iteration);
             match previous_solution {
                 Some(prev_solution) => {
-                    objective.print_objective_value_with_comparison(
+                    message.push_str(&objective.format_objective_value_with_comparison(
                         current_solution.objective_value(),
                         prev_solution.objective_value(),
-                    );
+                    ));
                 }
                 None => {
-                    objective.print_objective_value(current_solution.objective_value());
+                    message.push_str(
+                        &objective.format_objective_value(current_solution.objective_value()),
+                    );
                 }
             }
             if let Some(start_time) = start_time {
-                println!(
-                    "elapsed time for local search: {:0.2}sec",
+                message.push_str(&format!(
+                    "\nelapsed time for local search: {:0.2}sec",
                     stdtime::Instant::now()
                         .duration_since(start_time)
                         .as_secs_f32(),
-                );
+                ));
+            }
+            if let Some(neighbor_counts) = neighbor_counts {
+                message.push_str(&format!(
+                    "\nevaluated neighbors: {}",
+                    neighbor_counts.evaluated
+                ));
             }
             if time_limit.is_some() || iteration_limit.is_some() {
-                println!(
-                    "({}{}{})",
+                message.push_str(&format!(
+                    "\n({}{}{})",
                     match iteration_limit {
                         Some(iteration_limit) => format!("iteration limit: {}", iteration_limit),
                         None => "".to_string(),
@@ -77,8 +97,104 @@ pub fn default_function_between_steps<S>() -> FunctionBetweenSteps<S> {
                             format!("time limit: {:0.2}sec", time_limit.as_secs_f32()),
                         None => "".to_string(),
                     },
-                );
+                ));
+            }
+            report!(Info, "{}", message);
+        },
+    )
+}
+
+/// Function between steps that does nothing (e.g., for benchmarks or if the solver runs inside a
+/// service).
+pub fn silent_function_between_steps<S>() -> FunctionBetweenSteps<S> {
+    Box::new(|_, _, _, _, _, _, _, _| {})
+}
+
+/// Wraps the given `function_between_steps` such that it is only executed every
+/// `every_n_iterations` iterations and at most once per `min_interval` (e.g., for expensive
+/// functions that write files or send the progress over the network).
+/// * If both are set, the function is executed if the iteration is a multiple of
+///   `every_n_iterations` and at least `min_interval` has passed since the last execution.
+/// * If both are `None`, every step is forwarded.
+/// * The previous solution passed to the function is the solution of the previous step (not of
+///   the previous forwarded step).
+pub fn throttled_function_between_steps<S: 'static>(
+    function_between_steps: FunctionBetweenSteps<S>,
+    every_n_iterations: Option<u32>,
+    min_interval: Option<stdtime::Duration>,
+) -> FunctionBetweenSteps<S> {
+    let last_forwarded: Mutex<Option<Instant>> = Mutex::new(None);
+    Box::new(
+        move |iteration,
+              current_solution,
+              previous_solution,
+              objective,
+              start_time,
+              time_limit,
+              iteration_limit,
+              neighbor_counts| {
+            if let Some(every_n_iterations) = every_n_iterations {
+                if iteration % every_n_iterations.max(1) != 0 {
+                    return;
+                }
+            }
+            if let Some(min_interval) = min_interval {
+                let mut last_forwarded = last_forwarded.lock().unwrap();
+                if matches!(*last_forwarded, Some(instant) if instant.elapsed() < min_interval) {
+                    return;
+                }
+                *last_forwarded = Some(Instant::now());
             }
+            function_between_steps(
+                iteration,
+                current_solution,
+                previous_solution,
+                objective,
+                start_time,
+                time_limit,
+                iteration_limit,
+                neighbor_counts,
+            );
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::test_fixtures::Value;
+
+    #[test]
+    fn test_throttled_function_between_steps() {
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            Value,
+        )]));
+        let solution = objective.evaluate(0);
+        let forwarded = Arc::new(AtomicU32::new(0));
+        let counter = forwarded.clone();
+
+        let every_third = throttled_function_between_steps(
+            Box::new(move |_, _, _, _, _, _, _, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+            Some(3),
+            Some(stdtime::Duration::from_secs(3600)),
+        );
+        for iteration in 1..=10 {
+            every_third(
+                iteration,
+                &solution,
+                None,
+                objective.clone(),
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        // iteration 3 is forwarded, 6 and 9 are within the minimal interval
+        assert_eq!(forwarded.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,391 @@
+//! This module contains the [`ImprovementGraph`], which records the sequence of accepted moves
+//! of a solver run (with operator labels and objective deltas) and exports which operators
+//! follow each other as DOT or JSON graph.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use super::{default_function_between_steps, FunctionBetweenSteps};
+use crate::objective::{EvaluatedSolution, ObjectiveValue};
+
+/// Type for the function that labels an accepted move by its operator (e.g., `"2-opt"` or
+/// `"swap"`), given the previous and the new solution.
+pub type OperatorLabelFunction<S> =
+    Box<dyn Fn(&EvaluatedSolution<S>, &EvaluatedSolution<S>) -> String + Send + Sync>;
+
+/// A single accepted move of a solver run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcceptedMove {
+    /// The iteration in which the move was accepted.
+    pub iteration: u32,
+    /// The label of the operator of the move.
+    pub operator: String,
+    /// The difference of the objective values (new minus previous) for each level (see
+    /// [`BaseValue::to_f64`][crate::objective::BaseValue::to_f64]). Negative values are
+    /// improvements.
+    pub objective_delta: Vec<f64>,
+}
+
+/// The statistics of an edge of the [`ImprovementGraph`] (the operator `to` was applied directly
+/// after the operator `from`) or of a single operator (see
+/// [`operators`][ImprovementGraph::operators]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OperatorTransition {
+    /// The number of times `to` followed `from` (or the number of moves of the operator).
+    pub count: u32,
+    /// The number of these moves that improved the objective value.
+    pub improving_count: u32,
+    /// The summed objective deltas of these moves for each level.
+    pub total_objective_delta: Vec<f64>,
+}
+
+/// Records the sequence of accepted moves of one or several solver runs, such that one can
+/// analyze which operators chain together productively.
+/// * The moves are recorded by the [`FunctionBetweenSteps`] returned by
+///   [`recording_function_between_steps`][ImprovementGraph::recording_function_between_steps]
+///   (the operator is determined by an [`OperatorLabelFunction`]) or manually by
+///   [`record`][ImprovementGraph::record].
+/// * The nodes of the graph are the operators and there is an edge from operator `a` to operator
+///   `b` if `b` was accepted directly after `a` (see
+///   [`transitions`][ImprovementGraph::transitions]).
+/// * The graph can be exported with [`to_dot`][ImprovementGraph::to_dot] (Graphviz) and, with
+///   the `serde_json` feature, with [`to_json`][ImprovementGraph::to_json].
+/// * [`new_run`][ImprovementGraph::new_run] separates runs, such that the last move of a run
+///   is not connected to the first move of the next run.
+pub struct ImprovementGraph {
+    runs: Mutex<Vec<Vec<AcceptedMove>>>,
+}
+
+impl ImprovementGraph {
+    /// Creates a new empty [`ImprovementGraph`].
+    pub fn new() -> Arc<ImprovementGraph> {
+        Arc::new(ImprovementGraph {
+            runs: Mutex::new(vec![Vec::new()]),
+        })
+    }
+
+    /// Returns a [`FunctionBetweenSteps`] that records each accepted move labeled by the
+    /// `operator_label` function and then executes the given `function_between_steps` (if
+    /// `None`, the default function between steps is executed).
+    /// * Steps without previous solution (e.g., the final report of some solvers) are not
+    ///   recorded.
+    pub fn recording_function_between_steps<S: 'static>(
+        graph: Arc<ImprovementGraph>,
+        operator_label: OperatorLabelFunction<S>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+    ) -> FunctionBetweenSteps<S> {
+        let function_between_steps =
+            function_between_steps.unwrap_or(default_function_between_steps());
+        Box::new(
+            move |iteration,
+                  current_solution,
+                  previous_solution,
+                  objective,
+                  start_time,
+                  time_limit,
+                  iteration_limit,
+                  neighbor_counts| {
+                if let Some(previous_solution) = previous_solution {
+                    graph.record(
+                        iteration,
+                        &operator_label(previous_solution, current_solution),
+                        previous_solution.objective_value(),
+                        current_solution.objective_value(),
+                    );
+                }
+                function_between_steps(
+                    iteration,
+                    current_solution,
+                    previous_solution,
+                    objective,
+                    start_time,
+                    time_limit,
+                    iteration_limit,
+                    neighbor_counts,
+                );
+            },
+        )
+    }
+
+    /// Records an accepted move of the `operator` in the given `iteration` from a solution with
+    /// the `previous` objective value to a solution with the `current` objective value.
+    pub fn record(
+        &self,
+        iteration: u32,
+        operator: &str,
+        previous: &ObjectiveValue,
+        current: &ObjectiveValue,
+    ) {
+        let objective_delta = current
+            .iter()
+            .zip(previous.iter())
+            .map(|(current, previous)| current.to_f64() - previous.to_f64())
+            .collect();
+        self.runs
+            .lock()
+            .unwrap()
+            .last_mut()
+            .unwrap()
+            .push(AcceptedMove {
+                iteration,
+                operator: operator.to_string(),
+                objective_delta,
+            });
+    }
+
+    /// Starts a new run, i.e., the next recorded move is not connected to the previous one.
+    pub fn new_run(&self) {
+        let mut runs = self.runs.lock().unwrap();
+        if !runs.last().unwrap().is_empty() {
+            runs.push(Vec::new());
+        }
+    }
+
+    /// Returns all recorded moves (of all runs) in the order of their acceptance.
+    pub fn moves(&self) -> Vec<AcceptedMove> {
+        self.runs.lock().unwrap().concat()
+    }
+
+    /// Returns for each operator the number of accepted moves and the summed objective deltas.
+    pub fn operators(&self) -> BTreeMap<String, OperatorTransition> {
+        let mut operators: BTreeMap<String, OperatorTransition> = BTreeMap::new();
+        for accepted_move in self.moves() {
+            add_move(
+                operators.entry(accepted_move.operator.clone()).or_default(),
+                &accepted_move,
+            );
+        }
+        operators
+    }
+
+    /// Returns the edges of the graph: For each pair `(from, to)` of operators, where `to` was
+    /// accepted directly after `from` (in the same run), the number of these transitions and
+    /// the objective deltas of the moves of `to`.
+    pub fn transitions(&self) -> BTreeMap<(String, String), OperatorTransition> {
+        let mut transitions: BTreeMap<(String, String), OperatorTransition> = BTreeMap::new();
+        for run in self.runs.lock().unwrap().iter() {
+            for pair in run.windows(2) {
+                add_move(
+                    transitions
+                        .entry((pair[0].operator.clone(), pair[1].operator.clone()))
+                        .or_default(),
+                    &pair[1],
+                );
+            }
+        }
+        transitions
+    }
+
+    /// Exports the graph in the DOT format of Graphviz. The nodes are labeled by the operator,
+    /// the number of accepted moves and their mean objective delta, the edges by the number of
+    /// transitions and the mean objective delta of the second move.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph improvement_graph {\n");
+        for (operator, statistics) in self.operators() {
+            writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\\n{} moves\\nmean delta: {}\"];",
+                escape(&operator),
+                escape(&operator),
+                statistics.count,
+                format_mean_delta(&statistics)
+            )
+            .unwrap();
+        }
+        for ((from, to), transition) in self.transitions() {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [label=\"{}x\\nmean delta: {}\"];",
+                escape(&from),
+                escape(&to),
+                transition.count,
+                format_mean_delta(&transition)
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports the recorded moves and the graph as JSON object with the keys `"moves"` (per run
+    /// a list of moves with `iteration`, `operator` and `objective_delta`), `"operators"` and
+    /// `"transitions"`.
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        let runs: Vec<serde_json::Value> = self
+            .runs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|run| !run.is_empty())
+            .map(|run| {
+                run.iter()
+                    .map(|accepted_move| {
+                        json!({
+                            "iteration": accepted_move.iteration,
+                            "operator": accepted_move.operator,
+                            "objective_delta": accepted_move.objective_delta,
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        let operators: Vec<serde_json::Value> = self
+            .operators()
+            .into_iter()
+            .map(|(operator, statistics)| {
+                json!({
+                    "operator": operator,
+                    "count": statistics.count,
+                    "improving_count": statistics.improving_count,
+                    "total_objective_delta": statistics.total_objective_delta,
+                })
+            })
+            .collect();
+        let transitions: Vec<serde_json::Value> = self
+            .transitions()
+            .into_iter()
+            .map(|((from, to), transition)| {
+                json!({
+                    "from": from,
+                    "to": to,
+                    "count": transition.count,
+                    "improving_count": transition.improving_count,
+                    "total_objective_delta": transition.total_objective_delta,
+                })
+            })
+            .collect();
+        json!({
+            "moves": runs,
+            "operators": operators,
+            "transitions": transitions,
+        })
+    }
+}
+
+fn add_move(statistics: &mut OperatorTransition, accepted_move: &AcceptedMove) {
+    statistics.count += 1;
+    // hierarchical comparison: the first level that changed decides
+    if accepted_move
+        .objective_delta
+        .iter()
+        .find(|delta| **delta != 0.0)
+        .is_some_and(|delta| *delta < 0.0)
+    {
+        statistics.improving_count += 1;
+    }
+    statistics
+        .total_objective_delta
+        .resize(accepted_move.objective_delta.len(), 0.0);
+    for (total, delta) in statistics
+        .total_objective_delta
+        .iter_mut()
+        .zip(accepted_move.objective_delta.iter())
+    {
+        *total += delta;
+    }
+}
+
+fn format_mean_delta(statistics: &OperatorTransition) -> String {
+    statistics
+        .total_objective_delta
+        .iter()
+        .map(|total| format!("{:0.2}", total / statistics.count as f64))
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{BaseValue, Objective};
+    use crate::test_fixtures::Value;
+
+    #[test]
+    fn test_improvement_graph() {
+        let graph = ImprovementGraph::new();
+        let function_between_steps = ImprovementGraph::recording_function_between_steps(
+            graph.clone(),
+            Box::new(|previous: &EvaluatedSolution<i64>, current| {
+                if current.solution() < previous.solution() {
+                    String::from("decrease")
+                } else {
+                    String::from("increase")
+                }
+            }),
+            Some(Box::new(|_, _, _, _, _, _, _, _| {})),
+        );
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            Value,
+        )]));
+
+        let solutions: Vec<EvaluatedSolution<i64>> = [10, 7, 8, 5]
+            .into_iter()
+            .map(|solution| objective.evaluate(solution))
+            .collect();
+        function_between_steps(
+            0,
+            &solutions[0],
+            None,
+            objective.clone(),
+            None,
+            None,
+            None,
+            None,
+        );
+        for (iteration, pair) in solutions.windows(2).enumerate() {
+            function_between_steps(
+                iteration as u32 + 1,
+                &pair[1],
+                Some(&pair[0]),
+                objective.clone(),
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        graph.new_run();
+        graph.record(
+            1,
+            "decrease",
+            &ObjectiveValue::new(vec![BaseValue::Integer(5)]),
+            &ObjectiveValue::new(vec![BaseValue::Integer(4)]),
+        );
+
+        assert_eq!(graph.moves().len(), 4);
+        assert_eq!(graph.moves()[1].operator, "increase");
+        assert_eq!(graph.moves()[1].objective_delta, vec![1.0]);
+
+        let operators = graph.operators();
+        assert_eq!(operators["decrease"].count, 3);
+        assert_eq!(operators["decrease"].improving_count, 3);
+        assert_eq!(operators["decrease"].total_objective_delta, vec![-7.0]);
+
+        // the move of the second run is not connected to the first run
+        let transitions = graph.transitions();
+        assert_eq!(transitions.len(), 2);
+        let decrease_increase = &transitions[&("decrease".to_string(), "increase".to_string())];
+        assert_eq!(decrease_increase.count, 1);
+        assert_eq!(decrease_increase.improving_count, 0);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph improvement_graph {"));
+        assert!(dot.contains("\"increase\" -> \"decrease\" [label=\"1x\\nmean delta: -3.00\"];"));
+
+        #[cfg(feature = "serde_json")]
+        {
+            let json = graph.to_json();
+            assert_eq!(json["moves"].as_array().unwrap().len(), 2);
+            assert_eq!(json["transitions"][0]["from"], "decrease");
+            assert_eq!(json["operators"][0]["count"], 3);
+        }
+    }
+}
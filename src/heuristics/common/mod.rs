@@ -1,11 +1,360 @@
 //! This module contains types, traits and algorithms that are used by multiple solvers.
 //! In particular, it contains the [`Neighborhood`] trait, which is used to define the neighborhood
 //! of a solution, and the [`FunctionBetweenSteps`] type, which is used to define the function
-//! that is executed between steps of the solver.
+//! that is executed between steps of the solver. Expensive functions between steps can be
+//! wrapped by [`throttled_function_between_steps`] to run only every N iterations or T
+//! milliseconds.
+//! Moreover, the [`SolveScheduler`] can be used to interleave several solver runs on a bounded
+//! number of threads and the [`PanicSafeImprover`] catches panics inside improvers such that the
+//! best solution found so far is still returned.
+//! For set-based solutions implementing [`SelectionSet`], the [`AddDropSwapNeighborhood`] provides
+//! add, drop and swap moves.
+//! Before long runs, the [`NeighborhoodDiagnostics`] detect operators that never change the
+//! objective or always produce invalid solutions.
+//! The [`ShrinkingNeighborhood`] decreases the step size of a [`StepSizeNeighborhood`] over time.
+//! The [`PrefetchingNeighborhood`] evaluates batches of neighbors ahead of time (e.g.,
+//! concurrently by an [`ExpensiveIndicator`][crate::objective::ExpensiveIndicator]). The
+//! [`CachedNeighborhood`] remembers the neighbors of the most recent solution, such that repeated
+//! passes over the same neighborhood do not regenerate them.
+//! For debugging, the [`AcceptanceLogger`] logs the acceptance decisions of threshold accepting,
+//! simulated annealing and the tabu solvers.
+//! The [`TabuPolicy`] defines separate tenures for different classes of tabus in the tabu solvers
+//! and the [`ReactiveTenure`] adapts the tabu list size whenever the search cycles. An
+//! [`AspirationCriterion`] accepts tabu neighbors anyway (e.g., if they are a new global best),
+//! which the tabu improvers check by the [`Aspiration`] of each iteration.
+//! To force diversification, the [`DiversityFilter`] rejects solutions whose
+//! [`SolutionDistance`] to recently visited solutions is too small. The [`ElitePool`] keeps the
+//! best diverse solutions found by one or several solvers for restarts.
+//! The [`ConvergenceDetector`] stops stochastic solvers as soon as the best objective value
+//! stagnates relative to its magnitude. The [`TerminationCriteria`] combine time limits, iteration
+//! limits, target objective values and stagnation by any-of and all-of, and are accepted by the
+//! `with_options` of the single-objective solvers.
+//! The [`MultiStartSolver`] runs any solver from several initial solutions of an
+//! [`InitialSolutionGenerator`] (optionally in parallel) and returns the best result.
+//! The [`RunSummary`] aggregates the final results of several runs (mean and standard deviation
+//! of the objective values, index of the best run).
+//! The [`PortfolioSolver`] races several solvers in parallel, which share their best solutions
+//! via a [`PortfolioChannel`]. The underlying [`SharedBest`] is a thread-safe slot for the best
+//! solution of cooperating solvers, which only accepts better solutions.
+//! The [`SolverRegistry`] constructs solvers by name (e.g., from a config file or the command
+//! line) and the [`SolverDescription`] lists the configured parameters of a solver.
+//! For basic multi-objective studies, the [`WeightedSumSweep`] scalarizes several indicators by
+//! weighted sums, runs a solver for each weight vector and collects the non-dominated solutions
+//! in a [`ParetoFront`].
+//! Population-based solvers share the [`Population`] (with tournament selection and steady-state
+//! replacement) and the [`Crossover`] and [`Mutation`] operators. With the random-key encoding,
+//! a solution is a vector of [`RandomKeys`] that a [`KeyDecoder`] decodes (e.g., by
+//! [`permutation_from_keys`]), such that the [`UniformKeyCrossover`] and the [`KeyResampling`]
+//! mutation apply to any combinatorial problem.
+//! For population-based solvers with constraints, [`stochastic_ranking`] and the
+//! [`feasibility_rules`] balance the violation level and the cost level of the objective.
+//! The stochastic solvers create their random number generator by an [`RngFactory`] (by default
+//! for [`StdRng`][rand::rngs::StdRng]), such that other generators can be plugged in.
+//! The [`SearchResult`] returned by
+//! [`solve_with_report`][crate::heuristics::Solver::solve_with_report] contains the iteration
+//! count, the [`TerminationReason`], the objective trajectory (with the [`NeighborCounts`] of
+//! each iteration) and the [`LevelMilestones`][LevelMilestone] (e.g., when the first feasible
+//! solution was found), which are detected by the [`MilestoneTracker`].
+//! For parameter tuning, the [`SearchStatistics`] summarize a run (evaluations per second,
+//! acceptance rate, time per iteration, improving steps per level). They are computed from the
+//! [`SearchResult`] or streamed during the run by a [`SearchStatisticsCollector`].
+//! The [`RunTraceWriter`] records CSV traces of solver runs, which can be compared with the
+//! `rapid_solve_compare_runs` binary. The [`ImprovementGraph`] records the accepted moves with
+//! their operators and exports which operators follow each other as DOT or JSON graph.
+//! To debug nondeterministic runs, the [`MoveRecorder`] records the accepted moves as indices into
+//! the neighborhood and the [`Replay`] re-applies such a [`MoveTrace`] step by step.
+//! With the `checkpoint` feature, the `CheckpointWriter` periodically writes the state of a tabu
+//! search, such that it can be resumed from the `TabuSearchCheckpoint` after a crash.
+//! A [`ModificationNeighborhood`] yields the neighbors together with their modifications, such
+//! that they can be evaluated incrementally. A [`MoveNeighborhood`] only yields lightweight
+//! [`Moves`][Move], which are applied on demand (see [`MoveNeighborhoodAdapter`]). The
+//! [`ChunkedNeighborhood`] explores a [`Neighborhood`] in parallel with tasks of a given number of
+//! neighbors. For auditing,
+//! the [`ModificationJournal`] records the applied modifications with timestamps and objective
+//! values.
+//! Without the `std` feature, only the [`Neighborhood`], [`ModificationNeighborhood`] and
+//! [`MoveNeighborhood`] traits and the [`NeighborCounts`] are available.
 
+#[cfg(feature = "std")]
+mod acceptance_logger;
+#[cfg(feature = "std")]
+mod aspiration_criterion;
+#[cfg(feature = "std")]
+mod cached_neighborhood;
+#[cfg(feature = "checkpoint")]
+mod checkpoint;
+#[cfg(feature = "std")]
+mod chunked_neighborhood;
+#[cfg(feature = "rand")]
+mod constrained_selection;
+#[cfg(feature = "std")]
+mod convergence_detector;
+#[cfg(feature = "std")]
+mod diversity_filter;
+#[cfg(feature = "std")]
+mod elite_pool;
+#[cfg(feature = "std")]
 mod function_between_steps;
+#[cfg(feature = "std")]
+mod improvement_graph;
+#[cfg(feature = "std")]
+mod modification_journal;
+#[cfg(feature = "std")]
+mod multi_start;
+mod neighbor_counts;
 mod neighborhood;
+#[cfg(feature = "std")]
+mod neighborhood_diagnostics;
+#[cfg(feature = "std")]
+mod panic_guard;
+#[cfg(feature = "rand")]
+mod population;
+#[cfg(feature = "std")]
+mod portfolio;
+#[cfg(feature = "std")]
+mod prefetching_neighborhood;
+#[cfg(feature = "rand")]
+mod random_keys;
+#[cfg(feature = "std")]
+mod replay;
+#[cfg(feature = "rand")]
+mod rng;
+#[cfg(feature = "std")]
+mod run_summary;
+#[cfg(feature = "std")]
+mod run_trace;
+#[cfg(feature = "std")]
+mod search_result;
+#[cfg(feature = "std")]
+mod search_statistics;
+#[cfg(feature = "std")]
+mod selection_set;
+#[cfg(feature = "std")]
+mod shared_best;
+#[cfg(feature = "std")]
+mod shrinking_neighborhood;
+#[cfg(feature = "std")]
+mod solve_scheduler;
+#[cfg(feature = "std")]
+mod solver_description;
+#[cfg(feature = "std")]
+mod solver_registry;
+#[cfg(feature = "std")]
+mod tabu_policy;
+#[cfg(feature = "std")]
+mod termination;
+#[cfg(feature = "std")]
+mod weighted_sum_sweep;
+#[cfg(feature = "std")]
+mod wind_down;
+#[cfg(feature = "std")]
+pub use acceptance_logger::AcceptanceCriterion;
+#[cfg(feature = "std")]
+pub use acceptance_logger::AcceptanceDecision;
+#[cfg(feature = "std")]
+pub use acceptance_logger::AcceptanceLogger;
+#[cfg(feature = "std")]
+pub use aspiration_criterion::Aspiration;
+#[cfg(feature = "std")]
+pub use aspiration_criterion::AspirationCriterion;
+#[cfg(feature = "std")]
+pub use aspiration_criterion::NewGlobalBestAspiration;
+#[cfg(feature = "std")]
+pub use aspiration_criterion::NoAspiration;
+#[cfg(feature = "std")]
+pub(crate) use aspiration_criterion::{
+    counts_with_aspiration, log_aspiration_decisions, prefer_aspirating_neighbor,
+};
+#[cfg(feature = "std")]
+pub use cached_neighborhood::CachedNeighborhood;
+#[cfg(feature = "checkpoint")]
+pub use checkpoint::CheckpointError;
+#[cfg(feature = "checkpoint")]
+pub use checkpoint::CheckpointWriter;
+#[cfg(feature = "checkpoint")]
+pub use checkpoint::TabuSearchCheckpoint;
+#[cfg(feature = "std")]
+pub use chunked_neighborhood::ChunkedNeighborhood;
+#[cfg(feature = "rand")]
+pub use constrained_selection::feasibility_rules;
+#[cfg(feature = "rand")]
+pub use constrained_selection::stochastic_ranking;
+#[cfg(feature = "rand")]
+pub use constrained_selection::tournament_selection;
+#[cfg(feature = "std")]
+pub use convergence_detector::ConvergenceDetector;
+#[cfg(feature = "std")]
+pub use diversity_filter::DiversityFilter;
+#[cfg(feature = "std")]
+pub use diversity_filter::DiversityFilteredNeighborhood;
+#[cfg(feature = "std")]
+pub use diversity_filter::SolutionDistance;
+#[cfg(feature = "std")]
+pub use elite_pool::ElitePool;
+#[cfg(feature = "std")]
 pub use function_between_steps::default_function_between_steps;
+#[cfg(feature = "std")]
+pub use function_between_steps::silent_function_between_steps;
+#[cfg(feature = "std")]
+pub use function_between_steps::throttled_function_between_steps;
+#[cfg(feature = "std")]
 pub use function_between_steps::FunctionBetweenSteps;
+#[cfg(feature = "std")]
+pub use improvement_graph::AcceptedMove;
+#[cfg(feature = "std")]
+pub use improvement_graph::ImprovementGraph;
+#[cfg(feature = "std")]
+pub use improvement_graph::OperatorLabelFunction;
+#[cfg(feature = "std")]
+pub use improvement_graph::OperatorTransition;
+#[cfg(feature = "std")]
+pub use modification_journal::JournalEntry;
+#[cfg(feature = "std")]
+pub use modification_journal::ModificationJournal;
+#[cfg(feature = "std")]
+pub use multi_start::InitialSolutionGenerator;
+#[cfg(feature = "std")]
+pub use multi_start::MultiStartSolver;
+pub use neighbor_counts::NeighborCounts;
+pub use neighborhood::ModificationNeighborhood;
+pub use neighborhood::Move;
+pub use neighborhood::MoveNeighborhood;
+pub use neighborhood::MoveNeighborhoodAdapter;
 pub use neighborhood::Neighborhood;
 pub use neighborhood::ParallelNeighborhood;
+#[cfg(feature = "std")]
+pub use neighborhood_diagnostics::NeighborhoodDiagnostics;
+#[cfg(feature = "std")]
+pub use neighborhood_diagnostics::NeighborhoodReport;
+#[cfg(feature = "std")]
+pub use neighborhood_diagnostics::OperatorStatistics;
+#[cfg(feature = "std")]
+pub use neighborhood_diagnostics::ValidityCheck;
+#[cfg(feature = "std")]
+pub use panic_guard::PanicGuard;
+#[cfg(feature = "std")]
+pub use panic_guard::PanicSafeImprover;
+#[cfg(feature = "rand")]
+pub use population::Crossover;
+#[cfg(feature = "rand")]
+pub use population::Mutation;
+#[cfg(feature = "rand")]
+pub use population::Population;
+#[cfg(feature = "std")]
+pub use portfolio::PortfolioChannel;
+#[cfg(feature = "std")]
+pub use portfolio::PortfolioSolver;
+#[cfg(feature = "std")]
+pub use prefetching_neighborhood::PrefetchingNeighborhood;
+#[cfg(feature = "rand")]
+pub use random_keys::assignment_from_keys;
+#[cfg(feature = "rand")]
+pub use random_keys::permutation_from_keys;
+#[cfg(feature = "rand")]
+pub use random_keys::DecodedIndicator;
+#[cfg(feature = "rand")]
+pub use random_keys::KeyDecoder;
+#[cfg(feature = "rand")]
+pub use random_keys::KeyResampling;
+#[cfg(feature = "rand")]
+pub use random_keys::RandomKeys;
+#[cfg(feature = "rand")]
+pub use random_keys::RandomKeysGenerator;
+#[cfg(feature = "rand")]
+pub use random_keys::UniformKeyCrossover;
+#[cfg(feature = "std")]
+pub use replay::MoveRecorder;
+#[cfg(feature = "std")]
+pub use replay::MoveTrace;
+#[cfg(feature = "std")]
+pub use replay::RecordedStep;
+#[cfg(feature = "std")]
+pub use replay::Replay;
+#[cfg(feature = "std")]
+pub use replay::ReplayError;
+#[cfg(feature = "rand")]
+pub use rng::default_rng_factory;
+#[cfg(feature = "rand")]
+pub use rng::seeded_rng_factory;
+#[cfg(feature = "rand")]
+pub use rng::RngFactory;
+#[cfg(feature = "std")]
+pub use run_summary::RunSummary;
+#[cfg(feature = "std")]
+pub use run_trace::RunTraceWriter;
+#[cfg(feature = "std")]
+pub use run_trace::RUN_TRACE_HEADER;
+#[cfg(feature = "std")]
+pub use search_result::LevelMilestone;
+#[cfg(feature = "std")]
+pub use search_result::MilestoneTracker;
+#[cfg(feature = "std")]
+pub(crate) use search_result::SearchRecorder;
+#[cfg(feature = "std")]
+pub use search_result::SearchResult;
+#[cfg(feature = "std")]
+pub use search_result::TerminationReason;
+#[cfg(feature = "std")]
+pub use search_result::TrajectoryPoint;
+#[cfg(feature = "std")]
+pub use search_statistics::SearchStatistics;
+#[cfg(feature = "std")]
+pub use search_statistics::SearchStatisticsCollector;
+#[cfg(feature = "std")]
+pub use search_statistics::StatisticsObserver;
+#[cfg(feature = "std")]
+pub use selection_set::AddDropSwapNeighborhood;
+#[cfg(feature = "std")]
+pub use selection_set::SelectionSet;
+#[cfg(feature = "std")]
+pub use shared_best::SharedBest;
+#[cfg(feature = "std")]
+pub use shrinking_neighborhood::ShrinkingNeighborhood;
+#[cfg(feature = "std")]
+pub use shrinking_neighborhood::StepSizeNeighborhood;
+#[cfg(feature = "std")]
+pub use shrinking_neighborhood::StepSizeSchedule;
+#[cfg(feature = "std")]
+pub use solve_scheduler::RunProgress;
+#[cfg(feature = "std")]
+pub use solve_scheduler::SliceFunction;
+#[cfg(feature = "std")]
+pub use solve_scheduler::SolveScheduler;
+#[cfg(feature = "std")]
+pub use solver_description::short_type_name;
+#[cfg(feature = "std")]
+pub use solver_description::SolverDescription;
+#[cfg(feature = "std")]
+pub use solver_registry::ParameterSchema;
+#[cfg(feature = "std")]
+pub use solver_registry::RegistryError;
+#[cfg(feature = "std")]
+pub use solver_registry::SolverBuilder;
+#[cfg(feature = "std")]
+pub use solver_registry::SolverParameters;
+#[cfg(feature = "std")]
+pub use solver_registry::SolverRegistry;
+#[cfg(feature = "std")]
+pub use tabu_policy::ReactiveTenure;
+#[cfg(feature = "std")]
+pub use tabu_policy::ReactiveTenureState;
+#[cfg(feature = "std")]
+pub use tabu_policy::SolutionFingerprint;
+#[cfg(feature = "std")]
+pub use tabu_policy::TabuClassFunction;
+#[cfg(feature = "std")]
+pub use tabu_policy::TabuPolicy;
+#[cfg(feature = "std")]
+pub(crate) use termination::report_termination;
+#[cfg(feature = "std")]
+pub use termination::TerminationCriteria;
+#[cfg(feature = "checkpoint")]
+pub use termination::TerminationProgress;
+#[cfg(feature = "std")]
+pub use weighted_sum_sweep::ParetoFront;
+#[cfg(feature = "std")]
+pub use weighted_sum_sweep::ParetoPoint;
+#[cfg(feature = "std")]
+pub use weighted_sum_sweep::WeightedSumSweep;
+#[cfg(feature = "std")]
+pub(crate) use wind_down::wind_down;
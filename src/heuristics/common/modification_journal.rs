@@ -0,0 +1,142 @@
+//! This module contains the [`ModificationJournal`], which records the sequence of applied
+//! modifications of a move-based search (with timestamps and objective values) for auditing.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::objective::ObjectiveValue;
+
+/// A single applied modification recorded by a [`ModificationJournal`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct JournalEntry<M> {
+    /// The position of the modification in the journal (starting at 1).
+    pub step: usize,
+    /// The time since the creation (or the last [`clear`][ModificationJournal::clear]) of the
+    /// journal.
+    pub elapsed: Duration,
+    /// The applied modification.
+    pub modification: M,
+    /// The [`ObjectiveValue`] of the solution before the modification.
+    pub objective_value_before: ObjectiveValue,
+    /// The [`ObjectiveValue`] of the solution after the modification.
+    pub objective_value_after: ObjectiveValue,
+}
+
+/// Records the sequence of modifications applied by a move-based search, such that one can audit
+/// how the final solution differs from the initial one and why each modification was applied.
+/// * The modifications are recorded by a
+///   [`DeltaMinimizer`][crate::heuristics::local_search::local_improver::DeltaMinimizer] with
+///   [`with_journal`][crate::heuristics::local_search::local_improver::DeltaMinimizer::with_journal]
+///   or manually by [`record`][ModificationJournal::record].
+/// * The journal can be shared by several runs (e.g., by cloning the [`Arc`]) and is printed as
+///   one line per modification by its [`Display`][fmt::Display] implementation.
+pub struct ModificationJournal<M> {
+    start_time: Mutex<Instant>,
+    entries: Mutex<Vec<JournalEntry<M>>>,
+}
+
+impl<M> ModificationJournal<M> {
+    /// Creates a new empty [`ModificationJournal`]. The timestamps of the entries are relative to
+    /// the creation time.
+    pub fn new() -> Arc<ModificationJournal<M>> {
+        Arc::new(ModificationJournal {
+            start_time: Mutex::new(Instant::now()),
+            entries: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Records that the `modification` was applied to a solution with the objective value
+    /// `before`, resulting in a solution with the objective value `after`.
+    pub fn record(&self, modification: M, before: &ObjectiveValue, after: &ObjectiveValue) {
+        let elapsed = self.start_time.lock().unwrap().elapsed();
+        let mut entries = self.entries.lock().unwrap();
+        let step = entries.len() + 1;
+        entries.push(JournalEntry {
+            step,
+            elapsed,
+            modification,
+            objective_value_before: before.clone(),
+            objective_value_after: after.clone(),
+        });
+    }
+
+    /// Returns the number of recorded modifications.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no modification was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all entries and restarts the clock of the timestamps.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+        *self.start_time.lock().unwrap() = Instant::now();
+    }
+}
+
+impl<M: Clone> ModificationJournal<M> {
+    /// Returns the recorded modifications in the order of their application.
+    pub fn entries(&self) -> Vec<JournalEntry<M>> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+impl<M: fmt::Debug> fmt::Display for ModificationJournal<M> {
+    /// Prints one line per modification, e.g.,
+    /// `#1 [0.001s] Swap(2, 5): [120] -> [110]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in self.entries.lock().unwrap().iter() {
+            writeln!(
+                f,
+                "#{} [{:.3}s] {:?}: {} -> {}",
+                entry.step,
+                entry.elapsed.as_secs_f64(),
+                entry.modification,
+                format_objective_value(&entry.objective_value_before),
+                format_objective_value(&entry.objective_value_after),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn format_objective_value(objective_value: &ObjectiveValue) -> String {
+    let levels: Vec<String> = objective_value
+        .iter()
+        .map(|value| value.to_string())
+        .collect();
+    format!("[{}]", levels.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::BaseValue;
+
+    #[test]
+    fn test_modification_journal() {
+        let journal = ModificationJournal::new();
+        let value = |v| ObjectiveValue::new(vec![BaseValue::Integer(0), BaseValue::Integer(v)]);
+        journal.record((0, 1), &value(10), &value(7));
+        journal.record((2, 3), &value(7), &value(5));
+
+        let entries = journal.entries();
+        assert_eq!(journal.len(), 2);
+        assert_eq!(entries[1].step, 2);
+        assert_eq!(entries[1].modification, (2, 3));
+        assert_eq!(entries[1].objective_value_after, value(5));
+        assert!(entries[0].elapsed <= entries[1].elapsed);
+
+        let lines: Vec<String> = journal.to_string().lines().map(String::from).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("#1 ["));
+        assert!(lines[0].ends_with("(0, 1): [0, 10] -> [0, 7]"));
+
+        journal.clear();
+        assert!(journal.is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+//! This module contains the [`MultiStartSolver`], which runs a [`Solver`] from several initial
+//! solutions (random restarts) and returns the best result.
+
+use std::sync::Arc;
+
+use super::{RunSummary, SharedBest, SolverDescription};
+use crate::heuristics::Solver;
+use crate::objective::EvaluatedSolution;
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::{IntoParallelIterator, ParallelIterator};
+
+/// Generates the initial solutions for the starts of the [`MultiStartSolver`].
+/// * The `start_index` can be used to seed a random generator, such that each start gets a
+///   different but reproducible initial solution.
+pub trait InitialSolutionGenerator<S>: Send + Sync {
+    /// Returns the initial solution for the start with the given `start_index`.
+    fn generate(&self, start_index: usize) -> S;
+}
+
+/// Runs the wrapped [`Solver`] from `number_of_starts` independent initial solutions and returns
+/// the best [`EvaluatedSolution`] (w.r.t. the objective value; on ties, the earliest start wins).
+/// * The initial solutions are produced by an [`InitialSolutionGenerator`].
+/// * If [`solve`][Solver::solve] is called, the given initial solution is used for the first start
+///   and the generator is only called for the remaining starts (with `start_index` 1, 2, ...).
+///   [`solve_all_starts`][MultiStartSolver::solve_all_starts] uses the generator for all starts.
+/// * [`solve_with_summary`][MultiStartSolver::solve_with_summary] returns a [`RunSummary`] (best
+///   result per start, mean and standard deviation of the final objective values, start index of
+///   the best run) to assess the robustness of the configuration.
+/// * If `parallel` is `true`, the starts are executed in parallel (sequentially without the
+///   `rayon` feature). Note that the wrapped solver should then be sequential to avoid oversubscription.
+/// * With a [`SharedBest`] (see [`with_shared_best`][MultiStartSolver::with_shared_best]), the
+///   result of each start is offered as soon as the start is finished, such that other threads
+///   can observe the best result while the remaining starts are running.
+pub struct MultiStartSolver<S> {
+    solver: Box<dyn Solver<S> + Send + Sync>,
+    initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+    number_of_starts: usize,
+    parallel: bool,
+    shared_best: Option<Arc<SharedBest<S>>>,
+}
+
+impl<S> MultiStartSolver<S> {
+    /// Creates a new [`MultiStartSolver`] that executes the `solver` sequentially from
+    /// `number_of_starts` initial solutions of the `initial_solution_generator`.
+    pub fn initialize(
+        solver: Box<dyn Solver<S> + Send + Sync>,
+        initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+        number_of_starts: usize,
+    ) -> Self {
+        Self::with_options(solver, initial_solution_generator, number_of_starts, None)
+    }
+
+    /// Creates a new [`MultiStartSolver`] with the given options.
+    /// * `parallel`: if `true`, the starts are executed in parallel (without the `rayon`
+    ///   feature, they are executed sequentially anyway). Default: `false`.
+    pub fn with_options(
+        solver: Box<dyn Solver<S> + Send + Sync>,
+        initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+        number_of_starts: usize,
+        parallel: Option<bool>,
+    ) -> Self {
+        if number_of_starts == 0 {
+            panic!("The number of starts must be at least 1.");
+        }
+        Self {
+            solver,
+            initial_solution_generator,
+            number_of_starts,
+            parallel: parallel.unwrap_or(false),
+            shared_best: None,
+        }
+    }
+
+    /// Offers the result of each start to the `shared_best` (see [`SharedBest`]).
+    pub fn with_shared_best(mut self, shared_best: Arc<SharedBest<S>>) -> Self {
+        self.shared_best = Some(shared_best);
+        self
+    }
+}
+
+impl<S: Clone + Send + Sync> MultiStartSolver<S> {
+    /// Runs all starts with initial solutions of the generator and returns the results of all
+    /// starts (in the order of the starts).
+    pub fn solve_all_starts(&self) -> Vec<EvaluatedSolution<S>> {
+        self.run_starts(None)
+    }
+
+    fn run_starts(&self, first_initial_solution: Option<S>) -> Vec<EvaluatedSolution<S>> {
+        let generated_from = usize::from(first_initial_solution.is_some());
+        let run_start = |start_index: usize, initial_solution: S| {
+            let result = self.solver.solve(initial_solution);
+            report!(Info, "Start {} finished.", start_index);
+            if let Some(shared_best) = &self.shared_best {
+                shared_best.offer(&result);
+            }
+            result
+        };
+
+        let mut results = Vec::with_capacity(self.number_of_starts);
+        if let Some(initial_solution) = first_initial_solution {
+            results.push(run_start(0, initial_solution));
+        }
+
+        if self.parallel {
+            results.extend(
+                (generated_from..self.number_of_starts)
+                    .into_par_iter()
+                    .map(|start_index| {
+                        run_start(
+                            start_index,
+                            self.initial_solution_generator.generate(start_index),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            return results;
+        }
+
+        results.extend((generated_from..self.number_of_starts).map(|start_index| {
+            run_start(
+                start_index,
+                self.initial_solution_generator.generate(start_index),
+            )
+        }));
+        results
+    }
+
+    fn best_of(results: Vec<EvaluatedSolution<S>>) -> EvaluatedSolution<S> {
+        // min_by returns the first minimum, so ties are won by the earliest start.
+        results
+            .into_iter()
+            .min_by(|a, b| a.objective_value().cmp(b.objective_value()))
+            .unwrap()
+    }
+
+    /// Runs all starts with initial solutions of the generator and returns the best result.
+    pub fn solve_from_generator(&self) -> EvaluatedSolution<S> {
+        Self::best_of(self.solve_all_starts())
+    }
+
+    /// Runs all starts with initial solutions of the generator and returns the [`RunSummary`],
+    /// where each run is identified by its `start_index`.
+    pub fn solve_with_summary(&self) -> RunSummary<S> {
+        RunSummary::new(self.solve_all_starts().into_iter().enumerate().collect())
+    }
+}
+
+impl<S: Clone + Send + Sync> Solver<S> for MultiStartSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        Self::best_of(self.run_starts(Some(initial_solution)))
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("MultiStartSolver")
+            .with_inner_solver("solver", self.solver.describe())
+            .with_parameter("number_of_starts", self.number_of_starts)
+            .with_parameter("parallel", self.parallel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::objective::{BaseValue, Indicator, Objective};
+    use crate::test_fixtures::PlusMinusOne;
+
+    /// Two local minima: 2 (value 1) and 10 (value 0).
+    struct TwoValleys;
+
+    impl Indicator<i64> for TwoValleys {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer(((solution - 2).abs() + 1).min((solution - 10).abs()))
+        }
+        fn name(&self) -> String {
+            String::from("TwoValleys")
+        }
+    }
+
+    struct EveryFourth;
+
+    impl InitialSolutionGenerator<i64> for EveryFourth {
+        fn generate(&self, start_index: usize) -> i64 {
+            4 * start_index as i64
+        }
+    }
+
+    #[test]
+    fn test_multi_start_solver() {
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            TwoValleys,
+        )]));
+
+        for parallel in [false, true] {
+            let local_search =
+                LocalSearchSolver::initialize(Arc::new(PlusMinusOne), objective.clone());
+            let solver = MultiStartSolver::with_options(
+                Box::new(local_search),
+                Box::new(EveryFourth),
+                4,
+                Some(parallel),
+            );
+            // starts 0, 4, 8, 12 (only the last two reach the global minimum)
+            let results = solver.solve_all_starts();
+            assert_eq!(
+                results.iter().map(|r| *r.solution()).collect::<Vec<i64>>(),
+                vec![2, 2, 10, 10]
+            );
+            assert_eq!(*solver.solve_from_generator().solution(), 10);
+            // the given initial solution replaces start 0
+            assert_eq!(*solver.solve(11).solution(), 10);
+
+            let summary = solver.solve_with_summary();
+            assert_eq!(summary.best_run_index(), Some(2));
+            assert_eq!(summary.mean(), &[0.5]);
+            assert_eq!(summary.standard_deviation(), &[0.5]);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! This module contains the [`NeighborCounts`], which count the neighbors that are generated,
+//! evaluated, rejected and accepted in an iteration of a solver.
+
+use core::ops::Add;
+
+/// The numbers of neighbors of an iteration, such that bottlenecks in the move generation and the
+/// evaluation are visible.
+/// * The solvers pass the counts of each iteration to the `function_between_steps` and record
+///   them in the trajectory of the `SearchResult`.
+/// * The counts are collected by the improvers (see
+///   [`improve_counted`][crate::heuristics::local_search::local_improver::LocalImprover::improve_counted])
+///   and by the solvers that explore the neighborhood themselves.
+/// * Counts that a solver cannot observe (e.g., the tabu rejections of a solver without tabu
+///   list) are zero.
+/// * The counts of several iterations are summed by `+`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NeighborCounts {
+    /// The number of neighbors that were generated by the neighborhood.
+    pub generated: u64,
+    /// The number of neighbors that were evaluated by the objective.
+    pub evaluated: u64,
+    /// The number of neighbors that were rejected because they are tabu.
+    pub rejected_as_tabu: u64,
+    /// The number of neighbors that were rejected by the acceptance threshold (e.g., the water
+    /// level of the great deluge).
+    pub rejected_by_threshold: u64,
+    /// The number of neighbors that were accepted (e.g., as new current solution or as member of
+    /// a Pareto archive).
+    pub accepted: u64,
+}
+
+impl Add for NeighborCounts {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        NeighborCounts {
+            generated: self.generated + rhs.generated,
+            evaluated: self.evaluated + rhs.evaluated,
+            rejected_as_tabu: self.rejected_as_tabu + rhs.rejected_as_tabu,
+            rejected_by_threshold: self.rejected_by_threshold + rhs.rejected_by_threshold,
+            accepted: self.accepted + rhs.accepted,
+        }
+    }
+}
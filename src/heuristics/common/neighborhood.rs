@@ -1,7 +1,12 @@
 //! This module provides the [`Neighborhood`] trait which is used to define a local search
 //! neighborhood.
+//! The [`MoveNeighborhood`] trait yields lightweight [`Moves`][Move] instead of full solutions,
+//! which can be consumed by the existing solvers via the [`MoveNeighborhoodAdapter`].
 
-use rayon::iter::ParallelIterator;
+use alloc::{boxed::Box, sync::Arc};
+
+use crate::objective::ObjectiveValue;
+use crate::parallel::ParallelIterator;
 
 /// A local search neighborhood that provides for each solution an iterator over all neighbors.
 /// The provided `current_solution`, as well as the [`Neighborhood`] instance must live as long as the iterator.
@@ -15,11 +20,100 @@ pub trait Neighborhood<S>: Send + Sync {
     ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a>;
 }
 
+/// A local search neighborhood that yields each neighbor together with the modification `M` that
+/// turns `current_solution` into the neighbor (e.g., the indices of a 3-opt move), such that the
+/// neighbor can be evaluated incrementally by a [`DeltaObjective`][crate::objective::DeltaObjective]
+/// (see [`DeltaMinimizer`][crate::heuristics::local_search::local_improver::DeltaMinimizer]).
+pub trait ModificationNeighborhood<S, M>: Send + Sync {
+    /// Returns an iterator over all neighbors of `current_solution`, each paired with its
+    /// modification.
+    fn neighbors_with_modifications_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = (S, M)> + Send + Sync + 'a>;
+}
+
+/// A lightweight modification of a solution (e.g., the indices of a swap), which is only applied
+/// to the solution if needed (see [`MoveNeighborhood`]).
+pub trait Move<S>: Send + Sync {
+    /// Returns the neighbor obtained by applying this move to `solution`.
+    fn apply(&self, solution: &S) -> S;
+
+    /// Returns the change of the [`ObjectiveValue`] (new minus old value on each level) caused
+    /// by applying this move to `solution`, if it can be computed without applying the move.
+    /// The default is `None`, i.e., the move must be applied and the neighbor evaluated.
+    fn delta(&self, _solution: &S) -> Option<ObjectiveValue> {
+        None
+    }
+}
+
+/// A local search neighborhood that provides for each solution an iterator over the
+/// [`Moves`][Move] to its neighbors instead of the neighbors themselves.
+/// * For large solutions, cloning a full solution for each neighbor (most of which are rejected)
+///   dominates the runtime. A [`MoveMinimizer`][crate::heuristics::local_search::local_improver::MoveMinimizer]
+///   ranks the moves by their [`delta`][Move::delta] and applies only the best move.
+/// * To use a [`MoveNeighborhood`] with any solver that takes a [`Neighborhood`] (or a
+///   [`ModificationNeighborhood`]), wrap it into a [`MoveNeighborhoodAdapter`].
+pub trait MoveNeighborhood<S>: Send + Sync {
+    /// The type of the moves.
+    type Move: Move<S>;
+
+    /// Returns an iterator over the moves from `current_solution` to all its neighbors.
+    fn moves_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = Self::Move> + Send + Sync + 'a>;
+}
+
+/// Adapts a [`MoveNeighborhood`] to a [`Neighborhood`] (each move is applied to obtain the
+/// neighbor) and to a [`ModificationNeighborhood`] (each neighbor is paired with its move).
+pub struct MoveNeighborhoodAdapter<N> {
+    move_neighborhood: Arc<N>,
+}
+
+impl<N> MoveNeighborhoodAdapter<N> {
+    /// Creates a new [`MoveNeighborhoodAdapter`] for the given [`MoveNeighborhood`].
+    pub fn new(move_neighborhood: Arc<N>) -> MoveNeighborhoodAdapter<N> {
+        MoveNeighborhoodAdapter { move_neighborhood }
+    }
+}
+
+impl<S: Sync, N: MoveNeighborhood<S>> Neighborhood<S> for MoveNeighborhoodAdapter<N> {
+    fn neighbors_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a> {
+        Box::new(
+            self.move_neighborhood
+                .moves_of(current_solution)
+                .map(move |m| m.apply(current_solution)),
+        )
+    }
+}
+
+impl<S: Sync, N: MoveNeighborhood<S>> ModificationNeighborhood<S, N::Move>
+    for MoveNeighborhoodAdapter<N>
+{
+    fn neighbors_with_modifications_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = (S, N::Move)> + Send + Sync + 'a> {
+        Box::new(
+            self.move_neighborhood
+                .moves_of(current_solution)
+                .map(move |m| (m.apply(current_solution), m)),
+        )
+    }
+}
+
 /// A parallel local search neighborhood that provides for each solution an [`ParallelIterator`] over all
 /// neighbors.
 /// The provided `current_solution`, as well as the [`Neighborhood`] instance must live as long as the iterator.
 /// (Note that the iterator highly depends on the `current_solution` and that the [`Neighborhood`] may
 /// have some attributes which goes into the iterator.)
+/// * The [`ParallelIterator`] is taken from the [`parallel`][crate::parallel] module, such that
+///   the neighborhood compiles with and without the `rayon` feature (without it, the neighbors are
+///   generated sequentially).
 pub trait ParallelNeighborhood<S: Send>: Send + Sync {
     /// Returns an [`ParallelIterator`] over all neighbors of `current_solution`.
     fn neighbors_of<'a>(&'a self, current_solution: &'a S) -> impl ParallelIterator<Item = S> + 'a;
@@ -0,0 +1,252 @@
+//! This module contains the [`NeighborhoodDiagnostics`], which enumerates the neighborhoods of
+//! sample solutions to detect dead or broken operators before long solver runs.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::{InitialSolutionGenerator, Neighborhood, OperatorLabelFunction};
+use crate::objective::Objective;
+
+/// Type for the function that returns `true` if a solution is valid (e.g., a tour visits every
+/// node exactly once).
+pub type ValidityCheck<S> = Box<dyn Fn(&S) -> bool + Send + Sync>;
+
+/// The statistics of a single operator in a [`NeighborhoodReport`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperatorStatistics {
+    /// The label of the operator.
+    pub operator: String,
+    /// The number of neighbors produced by the operator.
+    pub neighbors: usize,
+    /// The number of neighbors with a better objective value than the sample solution.
+    pub improving: usize,
+    /// The number of neighbors with the same objective value as the sample solution.
+    pub unchanged: usize,
+    /// The number of neighbors rejected by the [`ValidityCheck`].
+    pub invalid: usize,
+}
+
+impl OperatorStatistics {
+    /// Returns `true` if none of the neighbors changed the objective value (dead or ineffective
+    /// operator).
+    pub fn is_dead(&self) -> bool {
+        self.neighbors > 0 && self.unchanged == self.neighbors
+    }
+
+    /// Returns `true` if all neighbors are invalid.
+    pub fn is_always_invalid(&self) -> bool {
+        self.neighbors > 0 && self.invalid == self.neighbors
+    }
+}
+
+/// The result of [`NeighborhoodDiagnostics::run`]. The [`Display`][fmt::Display] lists the
+/// statistics of each operator and marks the dead and the always invalid operators.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NeighborhoodReport {
+    /// The number of sampled solutions.
+    pub sampled_solutions: usize,
+    /// The number of sampled solutions without any neighbor.
+    pub empty_neighborhoods: usize,
+    /// The statistics of each operator (ordered by the label).
+    pub operators: Vec<OperatorStatistics>,
+}
+
+impl NeighborhoodReport {
+    /// Returns the labels of the operators that never changed the objective value.
+    pub fn dead_operators(&self) -> Vec<&str> {
+        self.operators
+            .iter()
+            .filter(|statistics| statistics.is_dead())
+            .map(|statistics| statistics.operator.as_str())
+            .collect()
+    }
+
+    /// Returns the labels of the operators that only produced invalid neighbors.
+    pub fn invalid_operators(&self) -> Vec<&str> {
+        self.operators
+            .iter()
+            .filter(|statistics| statistics.is_always_invalid())
+            .map(|statistics| statistics.operator.as_str())
+            .collect()
+    }
+
+    /// Returns `true` if there is no dead or always invalid operator and every sampled solution
+    /// has a neighbor.
+    pub fn is_healthy(&self) -> bool {
+        self.empty_neighborhoods == 0
+            && self
+                .operators
+                .iter()
+                .all(|statistics| !statistics.is_dead() && !statistics.is_always_invalid())
+    }
+}
+
+impl fmt::Display for NeighborhoodReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Neighborhood diagnostics ({} sampled solutions, {} without neighbors):",
+            self.sampled_solutions, self.empty_neighborhoods
+        )?;
+        for statistics in self.operators.iter() {
+            write!(
+                f,
+                "\n    {}: {} neighbors, {} improving, {} unchanged, {} invalid",
+                statistics.operator,
+                statistics.neighbors,
+                statistics.improving,
+                statistics.unchanged,
+                statistics.invalid
+            )?;
+            if statistics.is_dead() {
+                write!(f, " (dead: never changes the objective)")?;
+            }
+            if statistics.is_always_invalid() {
+                write!(f, " (always invalid)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Diagnoses a [`Neighborhood`] before long solver runs: The neighborhoods of several sample
+/// solutions are enumerated completely and each neighbor is attributed to its operator by an
+/// [`OperatorLabelFunction`] (called with the sample solution and the neighbor).
+/// * An operator is reported as dead if none of its neighbors changes the objective value (e.g.,
+///   a swap of two identical elements or a move that is undone by a normalization).
+/// * With a [`ValidityCheck`], an operator is reported if all of its neighbors are invalid.
+/// * The sample solutions are taken from an [`InitialSolutionGenerator`] (e.g., random
+///   solutions seeded by the index), such that the diagnosis is reproducible.
+pub struct NeighborhoodDiagnostics<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    objective: Arc<Objective<S>>,
+    operator_label: OperatorLabelFunction<S>,
+    validity_check: Option<ValidityCheck<S>>,
+}
+
+impl<S> NeighborhoodDiagnostics<S> {
+    /// Creates a new [`NeighborhoodDiagnostics`] for the given [`Neighborhood`] and
+    /// [`Objective`].
+    /// * `operator_label` determines the operator of a neighbor given the sample solution and the
+    ///   neighbor.
+    /// * `validity_check` determines whether a neighbor is valid. If `None`, all neighbors are
+    ///   considered valid.
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        operator_label: OperatorLabelFunction<S>,
+        validity_check: Option<ValidityCheck<S>>,
+    ) -> NeighborhoodDiagnostics<S> {
+        NeighborhoodDiagnostics {
+            neighborhood,
+            objective,
+            operator_label,
+            validity_check,
+        }
+    }
+
+    /// Enumerates the neighborhoods of the first `sample_size` solutions of the `generator` and
+    /// returns the [`NeighborhoodReport`].
+    pub fn run(
+        &self,
+        generator: &dyn InitialSolutionGenerator<S>,
+        sample_size: usize,
+    ) -> NeighborhoodReport {
+        let mut operators: BTreeMap<String, OperatorStatistics> = BTreeMap::new();
+        let mut empty_neighborhoods = 0;
+        for sample_index in 0..sample_size {
+            let sample = self.objective.evaluate(generator.generate(sample_index));
+            let mut has_neighbors = false;
+            for neighbor in self.neighborhood.neighbors_of(sample.solution()) {
+                has_neighbors = true;
+                let invalid = self
+                    .validity_check
+                    .as_ref()
+                    .is_some_and(|validity_check| !validity_check(&neighbor));
+                let neighbor = self.objective.evaluate(neighbor);
+                let operator = (self.operator_label)(&sample, &neighbor);
+                let statistics =
+                    operators
+                        .entry(operator.clone())
+                        .or_insert_with(|| OperatorStatistics {
+                            operator,
+                            ..Default::default()
+                        });
+                statistics.neighbors += 1;
+                if neighbor.objective_value() == sample.objective_value() {
+                    statistics.unchanged += 1;
+                } else if self.objective.is_better(&neighbor, &sample) {
+                    statistics.improving += 1;
+                }
+                if invalid {
+                    statistics.invalid += 1;
+                }
+            }
+            if !has_neighbors {
+                empty_neighborhoods += 1;
+            }
+        }
+        NeighborhoodReport {
+            sampled_solutions: sample_size,
+            empty_neighborhoods,
+            operators: operators.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::DistanceTo;
+
+    // the `identity` operator is dead and the `negate` operator always yields negative solutions
+    struct Operators;
+
+    impl Neighborhood<i64> for Operators {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new([solution + 1, solution - 1, *solution, -solution].into_iter())
+        }
+    }
+
+    struct MultiplesOfSeven;
+
+    impl InitialSolutionGenerator<i64> for MultiplesOfSeven {
+        fn generate(&self, start_index: usize) -> i64 {
+            7 * (start_index as i64 + 1)
+        }
+    }
+
+    #[test]
+    fn test_neighborhood_diagnostics() {
+        let diagnostics = NeighborhoodDiagnostics::new(
+            Arc::new(Operators),
+            Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(100)))),
+            Box::new(
+                |sample, neighbor| match neighbor.solution() - sample.solution() {
+                    1 => String::from("increment"),
+                    -1 => String::from("decrement"),
+                    0 => String::from("identity"),
+                    _ => String::from("negate"),
+                },
+            ),
+            Some(Box::new(|solution: &i64| *solution >= 0)),
+        );
+        let report = diagnostics.run(&MultiplesOfSeven, 5);
+
+        assert_eq!(report.sampled_solutions, 5);
+        assert_eq!(report.empty_neighborhoods, 0);
+        assert_eq!(report.dead_operators(), vec!["identity"]);
+        assert_eq!(report.invalid_operators(), vec!["negate"]);
+        assert!(!report.is_healthy());
+        let increment = &report.operators[2];
+        assert_eq!(increment.operator, "increment");
+        assert_eq!((increment.neighbors, increment.improving), (5, 5));
+        assert!(report
+            .to_string()
+            .contains("identity: 5 neighbors, 0 improving, 5 unchanged, 0 invalid (dead"));
+    }
+}
@@ -0,0 +1,400 @@
+//! This module contains the [`PanicSafeImprover`] wrapper, which catches panics inside an
+//! improver (e.g., caused by a user-defined neighborhood or indicator) and records them in a
+//! [`PanicGuard`].
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::time as stdtime;
+
+use crate::heuristics::common::{Aspiration, NeighborCounts};
+use crate::heuristics::local_search::local_improver::LocalImprover;
+use crate::heuristics::parallel_local_search::parallel_local_improver::ParallelLocalImprover;
+use crate::heuristics::parallel_tabu_search::parallel_tabu_improver::ParallelTabuImprover;
+use crate::heuristics::tabu_search::tabu_improver::TabuImprover;
+use crate::objective::EvaluatedSolution;
+
+/// Records whether a panic was caught by a [`PanicSafeImprover`] and contains the panic payload.
+/// * Shared (via [`Arc`]) between the [`PanicSafeImprover`] and the caller of the solver.
+/// * After [`solve`][super::super::Solver::solve] returned, [`panicked`][PanicGuard::panicked]
+///   tells whether the returned solution is only the best solution found before the panic.
+/// * The solvers [`reset`][PanicGuard::reset] the guard at the start of each run, so it only
+///   reports a panic of the last run.
+#[derive(Default)]
+pub struct PanicGuard {
+    panic_message: Mutex<Option<String>>,
+}
+
+impl PanicGuard {
+    /// Creates a new [`PanicGuard`] without any recorded panic.
+    pub fn new() -> Arc<PanicGuard> {
+        Arc::new(PanicGuard::default())
+    }
+
+    /// Returns `true` if a panic was caught.
+    pub fn panicked(&self) -> bool {
+        self.panic_message.lock().unwrap().is_some()
+    }
+
+    /// Returns the payload of the first caught panic (if the payload is a string).
+    pub fn panic_message(&self) -> Option<String> {
+        self.panic_message.lock().unwrap().clone()
+    }
+
+    /// Forgets the recorded panic.
+    pub fn reset(&self) {
+        *self.panic_message.lock().unwrap() = None;
+    }
+
+    /// Executes `f`. If `f` panics, the panic payload is recorded and `None` is returned.
+    fn catch<R>(&self, f: impl FnOnce() -> Option<R>) -> Option<R> {
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => result,
+            Err(payload) => {
+                let message = panic_payload_message(payload.as_ref());
+                report!(
+                    Warning,
+                    "improver panicked ({}), returning best solution so far.",
+                    message
+                );
+                let mut panic_message = self.panic_message.lock().unwrap();
+                if panic_message.is_none() {
+                    *panic_message = Some(message);
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Returns the message of a panic `payload` (if the payload is a string).
+pub(crate) fn panic_payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Wraps an improver ([`LocalImprover`], [`ParallelLocalImprover`], [`TabuImprover`] or
+/// [`ParallelTabuImprover`]) such that a panic inside the improver does not abort the whole
+/// search.
+/// * If the wrapped improver panics, the panic is caught, the payload is recorded in the
+///   [`PanicGuard`] and `None` is returned. Hence, the solver terminates regularly and returns
+///   the best solution found so far, and its [`SearchResult`][super::SearchResult] reports
+///   [`TerminationReason::Panicked`][super::TerminationReason::Panicked].
+/// * The [`PanicGuard`] can be inspected after the search to check whether a panic occurred. It
+///   is reset at the start of each run, and the message of the panic is also part of the
+///   [`SearchResult`][super::SearchResult].
+pub struct PanicSafeImprover<I> {
+    improver: I,
+    panic_guard: Arc<PanicGuard>,
+}
+
+impl<I> PanicSafeImprover<I> {
+    /// Wraps the given `improver`. Caught panics are recorded in `panic_guard`.
+    pub fn new(improver: I, panic_guard: Arc<PanicGuard>) -> PanicSafeImprover<I> {
+        PanicSafeImprover {
+            improver,
+            panic_guard,
+        }
+    }
+}
+
+impl<S, I: LocalImprover<S>> LocalImprover<S> for PanicSafeImprover<I> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.panic_guard.catch(|| self.improver.improve(solution))
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: stdtime::Instant,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.panic_guard
+            .catch(|| self.improver.improve_until(solution, deadline))
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<stdtime::Instant>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        self.panic_guard
+            .catch(|| Some(self.improver.improve_counted(solution, deadline)))
+            .unwrap_or((None, None))
+    }
+
+    fn panic_message(&self) -> Option<String> {
+        self.panic_guard
+            .panic_message()
+            .or_else(|| self.improver.panic_message())
+    }
+
+    fn reset_panic(&self) {
+        self.panic_guard.reset();
+        self.improver.reset_panic();
+    }
+}
+
+impl<S, I: ParallelLocalImprover<S>> ParallelLocalImprover<S> for PanicSafeImprover<I> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.panic_guard.catch(|| self.improver.improve(solution))
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        self.panic_guard
+            .catch(|| Some(self.improver.improve_counted(solution)))
+            .unwrap_or((None, None))
+    }
+
+    fn panic_message(&self) -> Option<String> {
+        self.panic_guard
+            .panic_message()
+            .or_else(|| self.improver.panic_message())
+    }
+
+    fn reset_panic(&self) {
+        self.panic_guard.reset();
+        self.improver.reset_panic();
+    }
+}
+
+impl<S, T, I: TabuImprover<S, T>> TabuImprover<S, T> for PanicSafeImprover<I> {
+    fn improve(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+        self.panic_guard
+            .catch(|| self.improver.improve(solution, tabu_list))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.panic_guard
+            .catch(|| Some(self.improver.improve_counted(solution, tabu_list)))
+            .unwrap_or((None, None))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.panic_guard
+            .catch(|| {
+                Some(
+                    self.improver
+                        .improve_with_aspiration(solution, tabu_list, aspiration),
+                )
+            })
+            .unwrap_or_else(|| {
+                // the search must stop, so no tabu neighbor offered before the panic is taken
+                aspiration.discard();
+                (None, None)
+            })
+    }
+
+    fn panic_message(&self) -> Option<String> {
+        self.panic_guard
+            .panic_message()
+            .or_else(|| self.improver.panic_message())
+    }
+
+    fn reset_panic(&self) {
+        self.panic_guard.reset();
+        self.improver.reset_panic();
+    }
+}
+
+impl<S, T, I: ParallelTabuImprover<S, T>> ParallelTabuImprover<S, T> for PanicSafeImprover<I> {
+    fn improve(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+        self.panic_guard
+            .catch(|| self.improver.improve(solution, tabu_list))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.panic_guard
+            .catch(|| Some(self.improver.improve_counted(solution, tabu_list)))
+            .unwrap_or((None, None))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.panic_guard
+            .catch(|| {
+                Some(
+                    self.improver
+                        .improve_with_aspiration(solution, tabu_list, aspiration),
+                )
+            })
+            .unwrap_or_else(|| {
+                // the search must stop, so no tabu neighbor offered before the panic is taken
+                aspiration.discard();
+                (None, None)
+            })
+    }
+
+    fn panic_message(&self) -> Option<String> {
+        self.panic_guard
+            .panic_message()
+            .or_else(|| self.improver.panic_message())
+    }
+
+    fn reset_panic(&self) {
+        self.panic_guard.reset();
+        self.improver.reset_panic();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::{Neighborhood, TerminationReason};
+    use crate::heuristics::local_search::local_improver::Minimizer;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::heuristics::Solver;
+    use crate::objective::Objective;
+    use crate::test_fixtures::Value;
+
+    /// Decrements the solution by one (down to 0), but panics as soon as the solution reaches 5.
+    struct PanickingNeighborhood;
+
+    impl Neighborhood<i64> for PanickingNeighborhood {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            if *solution == 5 {
+                panic!("neighborhood exploded");
+            }
+            Box::new((*solution > 0).then_some(solution - 1).into_iter())
+        }
+    }
+
+    #[test]
+    fn test_panic_safe_improver() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let neighborhood = Arc::new(PanickingNeighborhood);
+        let panic_guard = PanicGuard::new();
+        let local_improver = Box::new(PanicSafeImprover::new(
+            Minimizer::new(neighborhood.clone(), objective.clone()),
+            panic_guard.clone(),
+        ));
+        let solver = LocalSearchSolver::with_options(
+            neighborhood,
+            objective,
+            Some(local_improver),
+            None,
+            None,
+        );
+
+        let result = solver.solve(10);
+
+        assert_eq!(*result.solution(), 5);
+        assert!(panic_guard.panicked());
+        assert_eq!(
+            panic_guard.panic_message(),
+            Some("neighborhood exploded".to_string())
+        );
+    }
+
+    #[test]
+    fn test_panic_is_reported_as_termination_reason() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let neighborhood = Arc::new(PanickingNeighborhood);
+        let panic_guard = PanicGuard::new();
+        let local_improver = Box::new(PanicSafeImprover::new(
+            Minimizer::new(neighborhood.clone(), objective.clone()),
+            panic_guard.clone(),
+        ));
+        let solver = LocalSearchSolver::with_options(
+            neighborhood,
+            objective,
+            Some(local_improver),
+            None,
+            None,
+        );
+
+        let result = solver.solve_with_report(10);
+
+        assert_eq!(*result.best_solution.solution(), 5);
+        assert_eq!(result.termination_reason, TerminationReason::Panicked);
+        assert_eq!(result.iterations, Some(5));
+        assert_eq!(
+            result.panic_message,
+            Some("neighborhood exploded".to_string())
+        );
+        assert!(panic_guard.panicked());
+    }
+
+    #[test]
+    fn test_panic_is_not_reported_in_next_run() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let neighborhood = Arc::new(PanickingNeighborhood);
+        let panic_guard = PanicGuard::new();
+        let local_improver = Box::new(PanicSafeImprover::new(
+            Minimizer::new(neighborhood.clone(), objective.clone()),
+            panic_guard.clone(),
+        ));
+        let solver = LocalSearchSolver::with_options(
+            neighborhood,
+            objective,
+            Some(local_improver),
+            None,
+            None,
+        );
+
+        let first_result = solver.solve_with_report(10);
+        // starting below 5, the neighborhood does not panic
+        let second_result = solver.solve_with_report(4);
+
+        assert_eq!(first_result.termination_reason, TerminationReason::Panicked);
+        assert_eq!(*second_result.best_solution.solution(), 0);
+        assert_eq!(
+            second_result.termination_reason,
+            TerminationReason::LocalOptimum
+        );
+        assert_eq!(second_result.panic_message, None);
+        assert!(!panic_guard.panicked());
+    }
+}
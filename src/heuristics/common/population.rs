@@ -0,0 +1,163 @@
+//! This module contains the shared infrastructure of population-based solvers (e.g., the
+//! [`MemeticSolver`][crate::heuristics::memetic::MemeticSolver]): the [`Population`] of
+//! evaluated solutions with tournament selection and steady-state replacement, and the
+//! [`Crossover`] and [`Mutation`] operators.
+
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use super::tournament_selection;
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// Combines two parent solutions into an offspring.
+/// * The random number generator of the solver is provided to make the search reproducible.
+pub trait Crossover<S>: Send + Sync {
+    /// Returns an offspring of the two parents.
+    fn crossover(&self, parent1: &S, parent2: &S, rng: &mut dyn RngCore) -> S;
+}
+
+/// Randomly modifies an offspring (e.g., by applying a random move) to keep the population
+/// diverse.
+/// * The random number generator of the solver is provided to make the search reproducible.
+pub trait Mutation<S>: Send + Sync {
+    /// Returns the mutated `solution`.
+    fn mutate(&self, solution: S, rng: &mut dyn RngCore) -> S;
+}
+
+/// A population of [`EvaluatedSolutions`][EvaluatedSolution] of bounded size for
+/// population-based solvers.
+/// * Parents are chosen by [`select_parent`][Population::select_parent] (tournament selection
+///   w.r.t. the [`Objective`]).
+/// * Offspring are added by [`insert`][Population::insert] (steady-state replacement): As long as
+///   the population is not full, every offspring is added. Afterwards, an offspring replaces the
+///   worst member if it is better than it.
+/// * An offspring with the same [`ObjectiveValue`][crate::objective::ObjectiveValue] as a member
+///   is rejected, which keeps the population diverse (and avoids that it is taken over by copies
+///   of the same solution).
+pub struct Population<S> {
+    members: Vec<EvaluatedSolution<S>>,
+    capacity: usize,
+    objective: Arc<Objective<S>>,
+}
+
+impl<S> Population<S> {
+    /// Creates an empty [`Population`] with at most `capacity` (at least 2) members.
+    pub fn new(capacity: usize, objective: Arc<Objective<S>>) -> Population<S> {
+        Population {
+            members: Vec::with_capacity(capacity),
+            capacity: capacity.max(2),
+            objective,
+        }
+    }
+
+    /// Returns the members of the population (in no particular order).
+    pub fn members(&self) -> &[EvaluatedSolution<S>] {
+        &self.members
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the population has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns `true` if the population has `capacity` members.
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= self.capacity
+    }
+
+    /// Returns the best member (or `None` if the population is empty).
+    pub fn best(&self) -> Option<&EvaluatedSolution<S>> {
+        self.members
+            .iter()
+            .min_by(|a, b| self.objective.compare(a, b))
+    }
+
+    /// Selects a parent by a tournament of `tournament_size` uniformly drawn members.
+    /// Panics if the population is empty.
+    pub fn select_parent(
+        &self,
+        tournament_size: usize,
+        rng: &mut dyn RngCore,
+    ) -> &EvaluatedSolution<S> {
+        tournament_selection(
+            &self.members,
+            tournament_size,
+            |a, b| self.objective.compare(a, b),
+            rng,
+        )
+    }
+
+    /// Inserts the `offspring` (steady-state replacement, see [`Population`]). Returns `true` if
+    /// the offspring became a member.
+    pub fn insert(&mut self, offspring: EvaluatedSolution<S>) -> bool {
+        if self
+            .members
+            .iter()
+            .any(|member| member.objective_value() == offspring.objective_value())
+        {
+            return false;
+        }
+        if !self.is_full() {
+            self.members.push(offspring);
+            return true;
+        }
+        let worst_index = (0..self.members.len())
+            .max_by(|&i, &j| self.objective.compare(&self.members[i], &self.members[j]))
+            .unwrap();
+        if self
+            .objective
+            .is_better(&offspring, &self.members[worst_index])
+        {
+            self.members[worst_index] = offspring;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::test_fixtures::Value;
+
+    #[test]
+    fn test_population() {
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            Value,
+        )]));
+        let mut population = Population::new(3, objective.clone());
+        assert!(population.best().is_none());
+
+        for solution in [5, 8, 3] {
+            assert!(population.insert(objective.evaluate(solution)));
+        }
+        assert!(population.is_full());
+        // duplicates and offspring worse than the worst member are rejected
+        assert!(!population.insert(objective.evaluate(5)));
+        assert!(!population.insert(objective.evaluate(9)));
+        // a better offspring replaces the worst member
+        assert!(population.insert(objective.evaluate(4)));
+        let mut members: Vec<i64> = population
+            .members()
+            .iter()
+            .map(|member| *member.solution())
+            .collect();
+        members.sort();
+        assert_eq!(members, vec![3, 4, 5]);
+        assert_eq!(*population.best().unwrap().solution(), 3);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        // a tournament over many draws almost surely contains the best member
+        assert_eq!(*population.select_parent(50, &mut rng).solution(), 3);
+    }
+}
@@ -0,0 +1,340 @@
+//! This module contains the [`PortfolioSolver`], which races several solvers in parallel, and the
+//! [`PortfolioChannel`], through which the solvers share their best solutions.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time as stdtime;
+
+use super::{
+    default_function_between_steps, FunctionBetweenSteps, RunSummary, SharedBest, SolverDescription,
+};
+use crate::heuristics::Solver;
+use crate::objective::{EvaluatedSolution, Objective, ObjectiveValue};
+
+/// How often the [`PortfolioSolver`] checks whether the target objective value is reached.
+const POLL_INTERVAL: stdtime::Duration = stdtime::Duration::from_millis(10);
+
+/// The shared best solution of the solvers of a [`PortfolioSolver`].
+/// * Each solver reports its final solution to the channel. In order to report intermediate
+///   solutions (such that the target objective value is detected as early as possible), use the
+///   [`reporting_function_between_steps`][PortfolioChannel::reporting_function_between_steps]
+///   as `function_between_steps` of the solvers.
+/// * The target objective value is reached as soon as a reported solution has an objective value
+///   of at most `target_objective_value`.
+/// * The best solution is stored in a [`SharedBest`] (see
+///   [`shared_best`][PortfolioChannel::shared_best]), which the solvers can also poll, e.g., to
+///   restart from the best solution of another solver.
+pub struct PortfolioChannel<S> {
+    objective: Arc<Objective<S>>,
+    target_objective_value: Option<ObjectiveValue>,
+    shared_best: Arc<SharedBest<S>>,
+    target_reached: AtomicBool,
+}
+
+impl<S: Clone> PortfolioChannel<S> {
+    /// Creates a new [`PortfolioChannel`] that compares solutions w.r.t. the [`Objective`] and
+    /// signals when the `target_objective_value` is reached (if `None`, never).
+    pub fn new(
+        objective: Arc<Objective<S>>,
+        target_objective_value: Option<ObjectiveValue>,
+    ) -> Arc<PortfolioChannel<S>> {
+        Arc::new(PortfolioChannel {
+            shared_best: SharedBest::new(objective.clone()),
+            objective,
+            target_objective_value,
+            target_reached: AtomicBool::new(false),
+        })
+    }
+
+    /// Reports a solution. It replaces the best solution if it is better.
+    pub fn report(&self, solution: &EvaluatedSolution<S>) {
+        if !self.shared_best.offer(solution) {
+            return;
+        }
+        if let Some(target_objective_value) = &self.target_objective_value {
+            if solution.objective_value() <= target_objective_value {
+                self.target_reached.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Returns the best solution reported so far.
+    pub fn best_solution(&self) -> Option<EvaluatedSolution<S>> {
+        self.shared_best.best_solution()
+    }
+
+    /// Returns the [`SharedBest`] that stores the best solution reported so far.
+    pub fn shared_best(&self) -> Arc<SharedBest<S>> {
+        self.shared_best.clone()
+    }
+
+    /// Returns `true` if a reported solution reached the target objective value.
+    pub fn target_reached(&self) -> bool {
+        self.target_reached.load(Ordering::Relaxed)
+    }
+
+    /// Forgets the best solution, e.g., before the channel is used for another portfolio run.
+    pub fn reset(&self) {
+        self.shared_best.reset();
+        self.target_reached.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static> PortfolioChannel<S> {
+    /// Returns a [`FunctionBetweenSteps`] that reports each new solution of the solver to the
+    /// `channel` and then executes the given `function_between_steps` (if `None`, the default
+    /// function between steps is executed).
+    pub fn reporting_function_between_steps(
+        channel: Arc<PortfolioChannel<S>>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+    ) -> FunctionBetweenSteps<S> {
+        let function_between_steps =
+            function_between_steps.unwrap_or(default_function_between_steps());
+        Box::new(
+            move |iteration,
+                  current_solution,
+                  previous_solution,
+                  objective,
+                  start_time,
+                  time_limit,
+                  iteration_limit,
+                  neighbor_counts| {
+                channel.report(current_solution);
+                function_between_steps(
+                    iteration,
+                    current_solution,
+                    previous_solution,
+                    objective,
+                    start_time,
+                    time_limit,
+                    iteration_limit,
+                    neighbor_counts,
+                );
+            },
+        )
+    }
+}
+
+/// Races several solvers (e.g., a tabu search and a simulated annealing) on clones of the initial
+/// solution, each in its own thread, and returns the best solution reported to the
+/// [`PortfolioChannel`].
+/// * The portfolio returns as soon as all solvers are finished, the target objective value of the
+///   channel is reached, or the `time_limit` is exceeded.
+/// * Solvers cannot be interrupted, i.e., solvers that are still running when the portfolio
+///   returns continue in the background until their own termination criterion is met. Hence,
+///   the solvers should be configured with the same time limit (and target objective value).
+/// * The initial solution is reported to the channel first, so the result is never worse than the
+///   initial solution.
+/// * [`solve_with_summary`][PortfolioSolver::solve_with_summary] additionally returns a
+///   [`RunSummary`] over the final solutions of the finished solvers.
+pub struct PortfolioSolver<S> {
+    solvers: Vec<Arc<dyn Solver<S> + Send + Sync>>,
+    channel: Arc<PortfolioChannel<S>>,
+    time_limit: Option<stdtime::Duration>,
+}
+
+impl<S: Clone> PortfolioSolver<S> {
+    /// Creates a new [`PortfolioSolver`] with the given solvers and [`Objective`], without target
+    /// objective value and time limit.
+    pub fn initialize(
+        solvers: Vec<Arc<dyn Solver<S> + Send + Sync>>,
+        objective: Arc<Objective<S>>,
+    ) -> Self {
+        Self::with_options(solvers, PortfolioChannel::new(objective, None), None)
+    }
+
+    /// Creates a new [`PortfolioSolver`] with the given solvers and [`PortfolioChannel`] (which
+    /// contains the [`Objective`] and the target objective value).
+    /// * `time_limit` is the maximum time the portfolio waits for the solvers. If `None`, it
+    ///   waits until all solvers are finished (or the target objective value is reached).
+    pub fn with_options(
+        solvers: Vec<Arc<dyn Solver<S> + Send + Sync>>,
+        channel: Arc<PortfolioChannel<S>>,
+        time_limit: Option<stdtime::Duration>,
+    ) -> Self {
+        if solvers.is_empty() {
+            panic!("A portfolio must contain at least one solver.");
+        }
+        Self {
+            solvers,
+            channel,
+            time_limit,
+        }
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static> PortfolioSolver<S> {
+    /// Races the solvers like [`solve`][Solver::solve] and returns the best solution together
+    /// with a [`RunSummary`] over the final solutions of the solvers (identified by their index).
+    /// * Solvers that are still running when the portfolio returns are not part of the summary.
+    /// * The best solution might be better than the best run of the summary (if it is the initial
+    ///   solution or an intermediate solution reported to the channel).
+    pub fn solve_with_summary(&self, initial_solution: S) -> (EvaluatedSolution<S>, RunSummary<S>) {
+        let runs = self.race(initial_solution);
+        (self.channel.best_solution().unwrap(), RunSummary::new(runs))
+    }
+
+    /// Races the solvers and returns the final solutions of the finished solvers. The best
+    /// solution is reported to the channel.
+    fn race(&self, initial_solution: S) -> Vec<(usize, EvaluatedSolution<S>)> {
+        let start_time = stdtime::Instant::now();
+        self.channel.reset();
+        self.channel
+            .report(&self.channel.objective.evaluate(initial_solution.clone()));
+
+        let (sender, receiver) = mpsc::channel();
+        for (index, solver) in self.solvers.iter().enumerate() {
+            let solver = solver.clone();
+            let sender = sender.clone();
+            let initial_solution = initial_solution.clone();
+            thread::spawn(move || {
+                let result = solver.solve(initial_solution);
+                // the portfolio might already have returned
+                let _ = sender.send((index, result));
+            });
+        }
+        drop(sender);
+
+        let mut finished_runs = Vec::with_capacity(self.solvers.len());
+        while finished_runs.len() < self.solvers.len() {
+            if self.channel.target_reached() {
+                report!(Info, "Target objective value reached.");
+                break;
+            }
+            let timeout = match self.time_limit {
+                Some(time_limit) => {
+                    let elapsed = stdtime::Instant::now().duration_since(start_time);
+                    if elapsed > time_limit {
+                        report!(Info, "Time limit reached.");
+                        break;
+                    }
+                    POLL_INTERVAL.min(time_limit - elapsed)
+                }
+                None => POLL_INTERVAL,
+            };
+            match receiver.recv_timeout(timeout) {
+                Ok((index, result)) => {
+                    report!(Info, "Solver {} of the portfolio finished.", index);
+                    self.channel.report(&result);
+                    finished_runs.push((index, result));
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    report!(
+                        Warning,
+                        "{} solver(s) of the portfolio panicked.",
+                        self.solvers.len() - finished_runs.len()
+                    );
+                    break;
+                }
+            }
+        }
+        finished_runs
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static> Solver<S> for PortfolioSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.race(initial_solution);
+        self.channel.best_solution().unwrap()
+    }
+
+    fn describe(&self) -> SolverDescription {
+        self.solvers.iter().enumerate().fold(
+            SolverDescription::new("PortfolioSolver").with_duration("time_limit", self.time_limit),
+            |description, (index, solver)| {
+                description.with_inner_solver(&format!("solver_{}", index), solver.describe())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::BaseValue;
+    use crate::test_fixtures::Value;
+    use std::sync::Mutex;
+
+    /// Returns `result`, if blocked only after `release` is dropped (or after ten seconds).
+    struct BlockableSolver {
+        objective: Arc<Objective<i64>>,
+        result: i64,
+        release: Option<Mutex<mpsc::Receiver<()>>>,
+    }
+
+    impl Solver<i64> for BlockableSolver {
+        fn solve(&self, _initial_solution: i64) -> EvaluatedSolution<i64> {
+            if let Some(release) = &self.release {
+                let _ = release
+                    .lock()
+                    .unwrap()
+                    .recv_timeout(stdtime::Duration::from_secs(10));
+            }
+            self.objective.evaluate(self.result)
+        }
+    }
+
+    #[test]
+    fn test_portfolio_solver() {
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            Value,
+        )]));
+        let solver = |result: i64| {
+            Arc::new(BlockableSolver {
+                objective: objective.clone(),
+                result,
+                release: None,
+            }) as Arc<dyn Solver<i64> + Send + Sync>
+        };
+        let blocked_solver = |result: i64| {
+            let (release, receiver) = mpsc::channel();
+            let solver = Arc::new(BlockableSolver {
+                objective: objective.clone(),
+                result,
+                release: Some(Mutex::new(receiver)),
+            }) as Arc<dyn Solver<i64> + Send + Sync>;
+            (solver, release)
+        };
+
+        // without target: wait for all solvers
+        let portfolio = PortfolioSolver::initialize(vec![solver(5), solver(3)], objective.clone());
+        assert_eq!(*portfolio.solve(10).solution(), 3);
+
+        // the initial solution is kept if no solver improves it
+        assert_eq!(*portfolio.solve(1).solution(), 1);
+
+        let (best_solution, summary) = portfolio.solve_with_summary(10);
+        assert_eq!(*best_solution.solution(), 3);
+        assert_eq!(summary.number_of_runs(), 2);
+        assert_eq!(summary.best_run_index(), Some(1));
+        assert_eq!(summary.mean(), &[4.0]);
+
+        // with target: return as soon as the target is reached
+        let start_time = stdtime::Instant::now();
+        let (blocked, release) = blocked_solver(0);
+        let portfolio = PortfolioSolver::with_options(
+            vec![solver(2), blocked],
+            PortfolioChannel::new(
+                objective.clone(),
+                Some(ObjectiveValue::new(vec![BaseValue::Integer(2)])),
+            ),
+            None,
+        );
+        assert_eq!(*portfolio.solve(10).solution(), 2);
+        assert!(start_time.elapsed() < stdtime::Duration::from_secs(5));
+        drop(release);
+
+        // with time limit: return the best solution so far
+        let (blocked, release) = blocked_solver(0);
+        let portfolio = PortfolioSolver::with_options(
+            vec![solver(4), blocked],
+            PortfolioChannel::new(objective, None),
+            Some(stdtime::Duration::from_millis(100)),
+        );
+        assert_eq!(*portfolio.solve(10).solution(), 4);
+        drop(release);
+    }
+}
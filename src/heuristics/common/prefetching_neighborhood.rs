@@ -0,0 +1,98 @@
+//! This module contains the [`PrefetchingNeighborhood`], which evaluates batches of neighbors
+//! ahead of time (e.g., concurrently by an
+//! [`ExpensiveIndicator`][crate::objective::ExpensiveIndicator]) before they are passed on to the
+//! solver.
+
+use std::sync::Arc;
+
+use super::Neighborhood;
+use crate::objective::Prefetch;
+
+/// Wraps a [`Neighborhood`] such that the neighbors are collected in batches of `batch_size` and
+/// each batch is [prefetched][Prefetch::prefetch] before its neighbors are yielded (e.g.,
+/// evaluated concurrently and cached by an
+/// [`ExpensiveIndicator`][crate::objective::ExpensiveIndicator], or sent as a single request to
+/// an external process, see the `external_evaluator` feature).
+/// * The subsequent evaluation of the neighbors by the [`Objective`][crate::objective::Objective]
+///   (containing a clone of the prefetching indicator) is then answered by the cache.
+/// * Works with every solver that uses a [`Neighborhood`]. Improvers that stop early (e.g.,
+///   [`TakeFirst`][crate::heuristics::local_search::local_improver::TakeFirst]) evaluate at most
+///   one batch more than necessary.
+pub struct PrefetchingNeighborhood<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    indicator: Box<dyn Prefetch<S>>,
+    batch_size: usize,
+}
+
+impl<S> PrefetchingNeighborhood<S> {
+    /// Creates a new [`PrefetchingNeighborhood`] that prefetches batches of `batch_size` (at least
+    /// 1) neighbors of the `neighborhood` by the `indicator`.
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        indicator: Box<dyn Prefetch<S>>,
+        batch_size: usize,
+    ) -> PrefetchingNeighborhood<S> {
+        PrefetchingNeighborhood {
+            neighborhood,
+            indicator,
+            batch_size: batch_size.max(1),
+        }
+    }
+}
+
+impl<S: Send + Sync> Neighborhood<S> for PrefetchingNeighborhood<S> {
+    fn neighbors_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a> {
+        let mut neighbors = self.neighborhood.neighbors_of(current_solution);
+        Box::new(
+            std::iter::from_fn(move || {
+                let batch: Vec<S> = neighbors.by_ref().take(self.batch_size).collect();
+                if batch.is_empty() {
+                    return None;
+                }
+                self.indicator.prefetch(&batch);
+                Some(batch)
+            })
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::heuristics::Solver;
+    use crate::objective::{ExpensiveIndicator, Objective};
+    use crate::test_fixtures::{DistanceTo, PlusMinusOne};
+
+    #[test]
+    fn test_prefetching_neighborhood() {
+        let indicator = ExpensiveIndicator::new(
+            Box::new(DistanceTo(10)),
+            Box::new(|solution: &i64| *solution as u64),
+            100,
+        );
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(indicator.clone())));
+        let neighborhood = Arc::new(PrefetchingNeighborhood::new(
+            Arc::new(PlusMinusOne),
+            Box::new(indicator.clone()),
+            8,
+        ));
+        let solver = LocalSearchSolver::with_options(
+            neighborhood,
+            objective,
+            None,
+            Some(Box::new(|_, _, _, _, _, _, _, _| {})),
+            None,
+        );
+
+        assert_eq!(*solver.solve(5).solution(), 10);
+        // each of the solutions 4 to 11 is evaluated only once by the wrapped indicator and the
+        // evaluations of the neighbors by the objective are answered by the cache
+        assert_eq!(indicator.misses(), 8);
+        assert_eq!(indicator.hits(), 12);
+    }
+}
@@ -0,0 +1,262 @@
+//! This module contains the random-key encoding for population-based solvers: A solution is
+//! encoded as a vector of keys in `[0, 1)`, which a [`KeyDecoder`] decodes into the actual
+//! solution (e.g., a permutation or an assignment), such that generic [`Crossover`] and
+//! [`Mutation`] operators on the keys can be used for any combinatorial problem.
+
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use super::{Crossover, InitialSolutionGenerator, Mutation};
+use crate::objective::{BaseValue, Indicator, Unit};
+
+/// Decodes a vector of random keys (each in `[0, 1)`) into a solution.
+/// * Every key vector of the right length must be decoded into a feasible solution, e.g., by
+///   [`permutation_from_keys`] or [`assignment_from_keys`].
+/// * Decoders can be hierarchical, e.g., the first keys decide the assignment of jobs to machines
+///   and the remaining keys the order of the jobs on each machine.
+pub trait KeyDecoder<S>: Send + Sync {
+    /// Decodes the `keys` into a solution.
+    fn decode(&self, keys: &[f64]) -> S;
+}
+
+/// Returns the permutation that sorts the `keys` ascendingly, i.e., the index of the smallest key
+/// comes first. Equal keys keep their order.
+pub fn permutation_from_keys(keys: &[f64]) -> Vec<usize> {
+    let mut permutation: Vec<usize> = (0..keys.len()).collect();
+    permutation.sort_by(|&i, &j| keys[i].total_cmp(&keys[j]));
+    permutation
+}
+
+/// Returns the assignment of each key to one of `number_of_options` options, i.e., the key `k`
+/// is assigned to option `floor(k * number_of_options)`.
+pub fn assignment_from_keys(keys: &[f64], number_of_options: usize) -> Vec<usize> {
+    keys.iter()
+        .map(|key| ((key * number_of_options as f64) as usize).min(number_of_options - 1))
+        .collect()
+}
+
+/// A solution encoded by random keys together with its decoded solution (see [`KeyDecoder`]).
+/// * The solution is decoded once when the [`RandomKeys`] are created, so the
+///   [`Objective`][crate::objective::Objective] (e.g., built from
+///   [`DecodedIndicators`][DecodedIndicator]) evaluates the decoded solution without decoding it
+///   again.
+#[derive(Clone, Debug)]
+pub struct RandomKeys<S> {
+    keys: Vec<f64>,
+    decoded: S,
+}
+
+impl<S> RandomKeys<S> {
+    /// Creates the [`RandomKeys`] for the given `keys` (each in `[0, 1)`) and decodes them by the
+    /// `decoder`.
+    pub fn new(keys: Vec<f64>, decoder: &dyn KeyDecoder<S>) -> RandomKeys<S> {
+        let decoded = decoder.decode(&keys);
+        RandomKeys { keys, decoded }
+    }
+
+    /// Creates `number_of_keys` uniformly drawn keys and decodes them by the `decoder`.
+    pub fn random(
+        number_of_keys: usize,
+        decoder: &dyn KeyDecoder<S>,
+        rng: &mut dyn RngCore,
+    ) -> RandomKeys<S> {
+        RandomKeys::new(
+            (0..number_of_keys).map(|_| rng.gen::<f64>()).collect(),
+            decoder,
+        )
+    }
+
+    /// Returns the keys.
+    pub fn keys(&self) -> &[f64] {
+        &self.keys
+    }
+
+    /// Returns the decoded solution.
+    pub fn decoded(&self) -> &S {
+        &self.decoded
+    }
+
+    /// Consumes the [`RandomKeys`] and returns the decoded solution.
+    pub fn into_decoded(self) -> S {
+        self.decoded
+    }
+}
+
+/// Evaluates [`RandomKeys`] by applying an [`Indicator`] to the decoded solution.
+pub struct DecodedIndicator<S> {
+    indicator: Box<dyn Indicator<S>>,
+}
+
+impl<S> DecodedIndicator<S> {
+    /// Creates a new [`DecodedIndicator`] that evaluates the decoded solutions by the
+    /// `indicator`.
+    pub fn new(indicator: Box<dyn Indicator<S>>) -> DecodedIndicator<S> {
+        DecodedIndicator { indicator }
+    }
+}
+
+impl<S> Indicator<RandomKeys<S>> for DecodedIndicator<S> {
+    fn evaluate(&self, solution: &RandomKeys<S>) -> BaseValue {
+        self.indicator.evaluate(solution.decoded())
+    }
+
+    fn name(&self) -> String {
+        self.indicator.name()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.indicator.unit()
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.indicator.bounds()
+    }
+}
+
+/// The parameterized uniform crossover of random keys (as in biased random-key genetic
+/// algorithms): Each key of the offspring is taken from the first parent with probability
+/// `bias` and from the second parent otherwise.
+pub struct UniformKeyCrossover<S> {
+    decoder: Arc<dyn KeyDecoder<S>>,
+    bias: f64,
+}
+
+impl<S> UniformKeyCrossover<S> {
+    /// Creates a new [`UniformKeyCrossover`] that takes each key from the first parent with
+    /// probability `bias` (e.g., 0.7 if the first parent is the better one, 0.5 for an unbiased
+    /// crossover) and decodes the offspring by the `decoder`.
+    pub fn new(decoder: Arc<dyn KeyDecoder<S>>, bias: f64) -> UniformKeyCrossover<S> {
+        UniformKeyCrossover { decoder, bias }
+    }
+}
+
+impl<S> Crossover<RandomKeys<S>> for UniformKeyCrossover<S> {
+    fn crossover(
+        &self,
+        parent1: &RandomKeys<S>,
+        parent2: &RandomKeys<S>,
+        rng: &mut dyn RngCore,
+    ) -> RandomKeys<S> {
+        let keys = parent1
+            .keys
+            .iter()
+            .zip(parent2.keys.iter())
+            .map(
+                |(&key1, &key2)| {
+                    if rng.gen_bool(self.bias) {
+                        key1
+                    } else {
+                        key2
+                    }
+                },
+            )
+            .collect();
+        RandomKeys::new(keys, self.decoder.as_ref())
+    }
+}
+
+/// Mutates random keys by redrawing each key uniformly with the `key_probability`.
+pub struct KeyResampling<S> {
+    decoder: Arc<dyn KeyDecoder<S>>,
+    key_probability: f64,
+}
+
+impl<S> KeyResampling<S> {
+    /// Creates a new [`KeyResampling`] that redraws each key with the `key_probability` and
+    /// decodes the mutated keys by the `decoder`.
+    pub fn new(decoder: Arc<dyn KeyDecoder<S>>, key_probability: f64) -> KeyResampling<S> {
+        KeyResampling {
+            decoder,
+            key_probability,
+        }
+    }
+}
+
+impl<S> Mutation<RandomKeys<S>> for KeyResampling<S> {
+    fn mutate(&self, solution: RandomKeys<S>, rng: &mut dyn RngCore) -> RandomKeys<S> {
+        let keys = solution
+            .keys
+            .into_iter()
+            .map(|key| {
+                if rng.gen_bool(self.key_probability) {
+                    rng.gen::<f64>()
+                } else {
+                    key
+                }
+            })
+            .collect();
+        RandomKeys::new(keys, self.decoder.as_ref())
+    }
+}
+
+/// Generates uniformly drawn [`RandomKeys`] (seeded by the `start_index`), e.g., for the initial
+/// population of a [`MemeticSolver`][crate::heuristics::memetic::MemeticSolver].
+pub struct RandomKeysGenerator<S> {
+    decoder: Arc<dyn KeyDecoder<S>>,
+    number_of_keys: usize,
+}
+
+impl<S> RandomKeysGenerator<S> {
+    /// Creates a new [`RandomKeysGenerator`] for `number_of_keys` keys, which are decoded by the
+    /// `decoder`.
+    pub fn new(decoder: Arc<dyn KeyDecoder<S>>, number_of_keys: usize) -> RandomKeysGenerator<S> {
+        RandomKeysGenerator {
+            decoder,
+            number_of_keys,
+        }
+    }
+}
+
+impl<S> InitialSolutionGenerator<RandomKeys<S>> for RandomKeysGenerator<S> {
+    fn generate(&self, start_index: usize) -> RandomKeys<S> {
+        RandomKeys::random(
+            self.number_of_keys,
+            self.decoder.as_ref(),
+            &mut StdRng::seed_from_u64(start_index as u64),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assigns the first half of the keys to one of three machines and orders the jobs by the
+    /// second half of the keys.
+    struct MachineSchedule;
+
+    impl KeyDecoder<(Vec<usize>, Vec<usize>)> for MachineSchedule {
+        fn decode(&self, keys: &[f64]) -> (Vec<usize>, Vec<usize>) {
+            let (assignment_keys, order_keys) = keys.split_at(keys.len() / 2);
+            (
+                assignment_from_keys(assignment_keys, 3),
+                permutation_from_keys(order_keys),
+            )
+        }
+    }
+
+    #[test]
+    fn test_random_keys() {
+        let decoder: Arc<dyn KeyDecoder<(Vec<usize>, Vec<usize>)>> = Arc::new(MachineSchedule);
+        let keys = RandomKeys::new(vec![0.1, 0.5, 0.99, 0.7, 0.2, 0.4], decoder.as_ref());
+        assert_eq!(keys.decoded(), &(vec![0, 1, 2], vec![1, 2, 0]));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let other = RandomKeysGenerator::new(decoder.clone(), 6).generate(1);
+        let offspring =
+            UniformKeyCrossover::new(decoder.clone(), 0.7).crossover(&keys, &other, &mut rng);
+        assert!(offspring
+            .keys()
+            .iter()
+            .enumerate()
+            .all(|(i, key)| *key == keys.keys()[i] || *key == other.keys()[i]));
+
+        let mutated = KeyResampling::new(decoder, 1.0).mutate(offspring, &mut rng);
+        assert!(mutated.keys().iter().all(|key| (0.0..1.0).contains(key)));
+        let mut order = mutated.decoded().1.clone();
+        order.sort();
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+}
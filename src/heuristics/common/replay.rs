@@ -0,0 +1,377 @@
+//! This module contains the [`MoveRecorder`], which records the accepted moves of a solver run as
+//! indices into the [`Neighborhood`], and the [`Replay`], which re-applies a recorded
+//! [`MoveTrace`] step by step to inspect the intermediate solutions.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::{default_function_between_steps, FunctionBetweenSteps, Neighborhood};
+use crate::objective::{EvaluatedSolution, Objective, ObjectiveValue};
+
+/// A single accepted move of a recorded solver run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedStep {
+    /// The iteration in which the move was accepted.
+    pub iteration: u32,
+    /// The index of the accepted neighbor in the [`Neighborhood`] iterator of the previous
+    /// solution.
+    pub neighbor_index: usize,
+    /// The [`ObjectiveValue`] of the accepted neighbor during the recording.
+    pub objective_value: ObjectiveValue,
+}
+
+/// The accepted moves of a solver run (see [`MoveRecorder`]), which can be replayed by a
+/// [`Replay`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoveTrace {
+    /// The random seed of the run (e.g., of the solver or a randomized neighborhood), which is
+    /// needed to rebuild the neighborhood for the replay.
+    pub random_seed: Option<u64>,
+    /// The accepted moves in the order of their acceptance.
+    pub steps: Vec<RecordedStep>,
+}
+
+/// Records the accepted moves of a solver run as indices into the [`Neighborhood`], such that the
+/// run can be replayed by a [`Replay`] (e.g., to debug a nondeterministic run).
+/// * The moves are recorded by the [`FunctionBetweenSteps`] returned by
+///   [`recording_function_between_steps`][MoveRecorder::recording_function_between_steps] or
+///   manually by [`record`][MoveRecorder::record].
+/// * The index of a move is found by enumerating the [`Neighborhood`] of the previous solution
+///   until the new solution is found, which is slow for large neighborhoods, so the recorder is
+///   meant for debugging only.
+/// * If the new solution is not a neighbor of the previous solution (e.g., after a restart or a
+///   perturbation by another operator), a warning is reported and the step is not recorded. The
+///   replay then diverges at this step.
+pub struct MoveRecorder<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    trace: Mutex<MoveTrace>,
+}
+
+impl<S: PartialEq> MoveRecorder<S> {
+    /// Creates a new [`MoveRecorder`] for the given [`Neighborhood`]. The `random_seed` is only
+    /// stored in the [`MoveTrace`].
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        random_seed: Option<u64>,
+    ) -> Arc<MoveRecorder<S>> {
+        Arc::new(MoveRecorder {
+            neighborhood,
+            trace: Mutex::new(MoveTrace {
+                random_seed,
+                steps: Vec::new(),
+            }),
+        })
+    }
+
+    /// Records the move from the `previous` solution to the `current` solution that was accepted
+    /// in the given `iteration`. Returns `false` (and records nothing) if `current` is not a
+    /// neighbor of `previous`.
+    pub fn record(&self, iteration: u32, previous: &S, current: &EvaluatedSolution<S>) -> bool {
+        match self
+            .neighborhood
+            .neighbors_of(previous)
+            .position(|neighbor| neighbor == *current.solution())
+        {
+            Some(neighbor_index) => {
+                self.trace.lock().unwrap().steps.push(RecordedStep {
+                    iteration,
+                    neighbor_index,
+                    objective_value: current.objective_value().clone(),
+                });
+                true
+            }
+            None => {
+                report!(
+                    Warning,
+                    "The solution of iteration {} is not a neighbor of the previous solution, the \
+                     step is not recorded.",
+                    iteration
+                );
+                false
+            }
+        }
+    }
+
+    /// Returns the recorded [`MoveTrace`].
+    pub fn trace(&self) -> MoveTrace {
+        self.trace.lock().unwrap().clone()
+    }
+}
+
+impl<S: PartialEq + 'static> MoveRecorder<S> {
+    /// Returns a [`FunctionBetweenSteps`] that records each accepted move and then executes the
+    /// given `function_between_steps` (if `None`, the default function between steps is
+    /// executed).
+    /// * Steps without previous solution (e.g., the final report of some solvers) and steps in
+    ///   which the solution did not change are not recorded.
+    pub fn recording_function_between_steps(
+        recorder: Arc<MoveRecorder<S>>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+    ) -> FunctionBetweenSteps<S> {
+        let function_between_steps =
+            function_between_steps.unwrap_or(default_function_between_steps());
+        Box::new(
+            move |iteration,
+                  current_solution,
+                  previous_solution,
+                  objective,
+                  start_time,
+                  time_limit,
+                  iteration_limit,
+                  neighbor_counts| {
+                if let Some(previous_solution) = previous_solution {
+                    if previous_solution.solution() != current_solution.solution() {
+                        recorder.record(iteration, previous_solution.solution(), current_solution);
+                    }
+                }
+                function_between_steps(
+                    iteration,
+                    current_solution,
+                    previous_solution,
+                    objective,
+                    start_time,
+                    time_limit,
+                    iteration_limit,
+                    neighbor_counts,
+                );
+            },
+        )
+    }
+}
+
+/// The error returned by a [`Replay`] if a recorded step cannot be replayed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayError {
+    /// All recorded steps are already replayed.
+    EndOfTrace,
+    /// The neighborhood of the current solution has less than `neighbor_index + 1` neighbors,
+    /// e.g., because the neighborhood is not the same as during the recording.
+    MissingNeighbor {
+        /// The index of the step (0 for the first move).
+        step: usize,
+        /// The recorded index of the neighbor.
+        neighbor_index: usize,
+    },
+    /// The replayed neighbor has another [`ObjectiveValue`] than during the recording, i.e., the
+    /// run is not reproduced (e.g., because the neighborhood enumerates the neighbors in another
+    /// order).
+    Divergence {
+        /// The index of the step (0 for the first move).
+        step: usize,
+        /// The [`ObjectiveValue`] during the recording.
+        recorded: ObjectiveValue,
+        /// The [`ObjectiveValue`] of the replayed neighbor.
+        replayed: ObjectiveValue,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::EndOfTrace => write!(f, "all recorded steps are replayed"),
+            ReplayError::MissingNeighbor {
+                step,
+                neighbor_index,
+            } => write!(
+                f,
+                "step {}: the neighborhood has no neighbor with index {}",
+                step, neighbor_index
+            ),
+            ReplayError::Divergence {
+                step,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "step {}: the replayed objective value {:?} differs from the recorded {:?}",
+                step, replayed, recorded
+            ),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+/// Re-applies the moves of a recorded [`MoveTrace`] step by step, starting from the initial
+/// solution of the recorded run, such that the intermediate solutions and objective values can
+/// be inspected.
+/// * The [`Neighborhood`] must enumerate the neighbors in the same order as during the recording
+///   (e.g., a randomized neighborhood must be seeded with the
+///   [`random_seed`][MoveTrace::random_seed] of the trace).
+/// * Each replayed neighbor is evaluated by the [`Objective`] and compared to the recorded
+///   objective value, such that the first step in which a run is not reproduced is detected (see
+///   [`ReplayError::Divergence`]).
+/// * All replayed solutions are kept, such that one can go back with
+///   [`step_back`][Replay::step_back] or jump to any step with [`run_to`][Replay::run_to].
+pub struct Replay<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    objective: Arc<Objective<S>>,
+    trace: MoveTrace,
+    solutions: Vec<EvaluatedSolution<S>>,
+}
+
+impl<S> Replay<S> {
+    /// Creates a new [`Replay`] of the `trace` starting at the `initial_solution` of the recorded
+    /// run.
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        initial_solution: S,
+        trace: MoveTrace,
+    ) -> Replay<S> {
+        let initial_solution = objective.evaluate(initial_solution);
+        Replay {
+            neighborhood,
+            objective,
+            trace,
+            solutions: vec![initial_solution],
+        }
+    }
+
+    /// Replays the next recorded step and returns the new current solution.
+    pub fn step(&mut self) -> Result<&EvaluatedSolution<S>, ReplayError> {
+        let step = self.position();
+        let recorded_step = self.trace.steps.get(step).ok_or(ReplayError::EndOfTrace)?;
+        let neighbor = self
+            .neighborhood
+            .neighbors_of(self.current().solution())
+            .nth(recorded_step.neighbor_index)
+            .ok_or(ReplayError::MissingNeighbor {
+                step,
+                neighbor_index: recorded_step.neighbor_index,
+            })?;
+        let neighbor = self.objective.evaluate(neighbor);
+        if *neighbor.objective_value() != recorded_step.objective_value {
+            return Err(ReplayError::Divergence {
+                step,
+                recorded: recorded_step.objective_value.clone(),
+                replayed: neighbor.objective_value().clone(),
+            });
+        }
+        self.solutions.push(neighbor);
+        Ok(self.current())
+    }
+
+    /// Goes back to the previous solution. Returns `false` if the current solution is the initial
+    /// solution.
+    pub fn step_back(&mut self) -> bool {
+        if self.solutions.len() > 1 {
+            self.solutions.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Goes forward or back until `position` steps are replayed and returns the current
+    /// solution.
+    pub fn run_to(&mut self, position: usize) -> Result<&EvaluatedSolution<S>, ReplayError> {
+        self.solutions.truncate(position + 1);
+        while self.position() < position {
+            self.step()?;
+        }
+        Ok(self.current())
+    }
+
+    /// Replays all remaining steps and returns the final solution.
+    pub fn run_to_end(&mut self) -> Result<&EvaluatedSolution<S>, ReplayError> {
+        self.run_to(self.trace.steps.len())
+    }
+
+    /// Returns the number of replayed steps, i.e., 0 at the initial solution.
+    pub fn position(&self) -> usize {
+        self.solutions.len() - 1
+    }
+
+    /// Returns the current solution.
+    pub fn current(&self) -> &EvaluatedSolution<S> {
+        self.solutions.last().unwrap()
+    }
+
+    /// Returns the solution after the given number of replayed steps (0 is the initial
+    /// solution), if it is already replayed.
+    pub fn solution_at(&self, position: usize) -> Option<&EvaluatedSolution<S>> {
+        self.solutions.get(position)
+    }
+
+    /// Returns the [`RecordedStep`] that is replayed next (`None` at the end of the trace).
+    pub fn next_step(&self) -> Option<&RecordedStep> {
+        self.trace.steps.get(self.position())
+    }
+
+    /// Returns the replayed [`MoveTrace`].
+    pub fn trace(&self) -> &MoveTrace {
+        &self.trace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::silent_function_between_steps;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::heuristics::Solver;
+    use crate::test_fixtures::DistanceTo;
+
+    struct Steps;
+
+    impl Neighborhood<i64> for Steps {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new([solution - 1, solution + 1, solution + 7].into_iter())
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let neighborhood: Arc<dyn Neighborhood<i64>> = Arc::new(Steps);
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(100))));
+        let recorder = MoveRecorder::new(neighborhood.clone(), Some(42));
+        let solver = LocalSearchSolver::with_options(
+            neighborhood.clone(),
+            objective.clone(),
+            None,
+            Some(MoveRecorder::recording_function_between_steps(
+                recorder.clone(),
+                Some(silent_function_between_steps()),
+            )),
+            None,
+        );
+        let result = solver.solve(80);
+        let trace = recorder.trace();
+        assert_eq!(trace.random_seed, Some(42));
+        assert!(!trace.steps.is_empty());
+
+        let mut replay = Replay::new(neighborhood.clone(), objective.clone(), 80, trace.clone());
+        assert_eq!(replay.next_step().unwrap().neighbor_index, 2); // +7 is the best neighbor
+        assert_eq!(*replay.step().unwrap().solution(), 87);
+        assert_eq!(replay.run_to_end().unwrap().solution(), result.solution());
+        assert_eq!(replay.step().err(), Some(ReplayError::EndOfTrace));
+        assert!(replay.step_back());
+        assert_eq!(replay.position(), trace.steps.len() - 1);
+        assert_eq!(*replay.run_to(0).unwrap().solution(), 80);
+        assert_eq!(*replay.solution_at(0).unwrap().solution(), 80);
+
+        let mut diverging_trace = trace.clone();
+        diverging_trace.steps[0].neighbor_index = 1;
+        let mut replay = Replay::new(neighborhood.clone(), objective.clone(), 80, diverging_trace);
+        assert!(matches!(
+            replay.step(),
+            Err(ReplayError::Divergence { step: 0, .. })
+        ));
+
+        let mut missing_trace = trace;
+        missing_trace.steps[0].neighbor_index = 3;
+        let mut replay = Replay::new(neighborhood, objective, 80, missing_trace);
+        assert_eq!(
+            replay.step().err(),
+            Some(ReplayError::MissingNeighbor {
+                step: 0,
+                neighbor_index: 3
+            })
+        );
+    }
+}
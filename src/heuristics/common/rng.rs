@@ -0,0 +1,54 @@
+//! This module contains the [`RngFactory`], which creates the random number generator of each run
+//! of a stochastic solver.
+
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+/// Creates the random number generator of a run of a stochastic solver (simulated annealing,
+/// iterated local search, large neighborhood search, memetic algorithm, NSGA-II) from the
+/// `random_seed` of the solver (`None` if no seed is given).
+/// * The generator is created anew for each run and passed as `&mut dyn RngCore` to the
+///   stochastic components of the solver (e.g., the
+///   [`Perturbation`][crate::heuristics::iterated_local_search::Perturbation], the
+///   [`Crossover`][super::Crossover] or the [`Mutation`][super::Mutation]).
+/// * The default of all solvers is [`StdRng`] (see [`default_rng_factory`]). Faster generators
+///   (e.g., `SmallRng` with the `small_rng` feature of `rand`) can be plugged in by
+///   [`seeded_rng_factory`], and a recorded or mocked generator can be injected for debugging by
+///   a custom factory.
+pub type RngFactory = Arc<dyn Fn(Option<u64>) -> Box<dyn RngCore + Send> + Send + Sync>;
+
+/// Returns an [`RngFactory`] for the [`SeedableRng`] `R`, which seeds the generator by
+/// [`seed_from_u64`][SeedableRng::seed_from_u64] if a random seed is given and by
+/// [`from_entropy`][SeedableRng::from_entropy] otherwise.
+pub fn seeded_rng_factory<R: RngCore + SeedableRng + Send + 'static>() -> RngFactory {
+    Arc::new(|random_seed| -> Box<dyn RngCore + Send> {
+        match random_seed {
+            Some(seed) => Box::new(R::seed_from_u64(seed)),
+            None => Box::new(R::from_entropy()),
+        }
+    })
+}
+
+/// Returns the [`RngFactory`] of [`StdRng`], which is the default of all stochastic solvers.
+pub fn default_rng_factory() -> RngFactory {
+    seeded_rng_factory::<StdRng>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+    use rand::Rng;
+
+    #[test]
+    fn test_rng_factory() {
+        let factory = default_rng_factory();
+        let draw = |seed| (factory)(seed).gen::<u64>();
+        assert_eq!(draw(Some(3)), draw(Some(3)));
+
+        let mocked: RngFactory = Arc::new(|_| Box::new(StepRng::new(7, 0)));
+        assert_eq!((mocked)(None).next_u64(), 7);
+    }
+}
@@ -0,0 +1,162 @@
+//! This module contains the [`RunSummary`], a statistical summary of the final results of several
+//! solver runs (e.g., the starts of a [`MultiStartSolver`][super::MultiStartSolver], the solvers
+//! of a [`PortfolioSolver`][super::PortfolioSolver] or the instances of a
+//! [`solve_batch`][crate::heuristics::Solver::solve_batch]).
+
+use crate::objective::EvaluatedSolution;
+
+/// The final results of several solver runs together with statistics over their objective values,
+/// such that the robustness of a configuration can be assessed in one call.
+/// * Each run is identified by its index (the `start_index` for the
+///   [`MultiStartSolver`][super::MultiStartSolver], which also seeds the
+///   [`InitialSolutionGenerator`][super::InitialSolutionGenerator], the index of the solver for
+///   the [`PortfolioSolver`][super::PortfolioSolver] or the index of the instance for
+///   [`solve_batch`][crate::heuristics::Solver::solve_batch]).
+/// * The mean and the standard deviation are computed per level of the objective value, where
+///   each [`BaseValue`][crate::objective::BaseValue] is converted by
+///   [`to_f64`][crate::objective::BaseValue::to_f64].
+pub struct RunSummary<S> {
+    runs: Vec<(usize, EvaluatedSolution<S>)>,
+    best_position: Option<usize>,
+    mean: Vec<f64>,
+    standard_deviation: Vec<f64>,
+}
+
+impl<S> RunSummary<S> {
+    /// Creates a new [`RunSummary`] from the results of the runs (pairs of run index and final
+    /// solution). The runs are kept in the given order.
+    pub fn new(runs: Vec<(usize, EvaluatedSolution<S>)>) -> RunSummary<S> {
+        // min_by returns the first minimum, so ties are won by the earliest run.
+        let best_position = runs
+            .iter()
+            .enumerate()
+            .min_by(|(_, (_, a)), (_, (_, b))| a.objective_value().cmp(b.objective_value()))
+            .map(|(position, _)| position);
+
+        let number_of_levels = runs
+            .first()
+            .map_or(0, |(_, result)| result.objective_value().as_vec().len());
+        let level_values = |level: usize| {
+            runs.iter()
+                .map(move |(_, result)| result.objective_value().as_vec()[level].to_f64())
+        };
+        let mean: Vec<f64> = (0..number_of_levels)
+            .map(|level| level_values(level).sum::<f64>() / runs.len() as f64)
+            .collect();
+        let standard_deviation = (0..number_of_levels)
+            .map(|level| {
+                let variance = level_values(level)
+                    .map(|value| (value - mean[level]).powi(2))
+                    .sum::<f64>()
+                    / runs.len() as f64;
+                variance.sqrt()
+            })
+            .collect();
+
+        RunSummary {
+            runs,
+            best_position,
+            mean,
+            standard_deviation,
+        }
+    }
+
+    /// Returns the final solution of each run together with the index of the run.
+    pub fn runs(&self) -> &[(usize, EvaluatedSolution<S>)] {
+        &self.runs
+    }
+
+    /// Returns the number of runs.
+    pub fn number_of_runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Returns the best final solution over all runs (on ties, the earliest run).
+    /// Returns `None` if there are no runs.
+    pub fn best(&self) -> Option<&EvaluatedSolution<S>> {
+        self.best_position.map(|position| &self.runs[position].1)
+    }
+
+    /// Returns the index (e.g., the `start_index`) of the run with the best final solution.
+    /// Returns `None` if there are no runs.
+    pub fn best_run_index(&self) -> Option<usize> {
+        self.best_position.map(|position| self.runs[position].0)
+    }
+
+    /// Returns the mean of the final objective values (one entry per level).
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// Returns the (population) standard deviation of the final objective values (one entry per
+    /// level).
+    pub fn standard_deviation(&self) -> &[f64] {
+        &self.standard_deviation
+    }
+
+    /// Consumes the summary and returns the best final solution (see [`best`][RunSummary::best]).
+    pub fn into_best(mut self) -> Option<EvaluatedSolution<S>> {
+        self.best_position
+            .map(|position| self.runs.swap_remove(position).1)
+    }
+
+    /// Prints the number of runs, the index of the best run and the mean and standard deviation
+    /// of each level.
+    pub fn print(&self) {
+        match self.best_run_index() {
+            Some(best_run_index) => println!(
+                "{} runs, best run: {}",
+                self.number_of_runs(),
+                best_run_index
+            ),
+            None => println!("0 runs"),
+        }
+        for (level, (mean, standard_deviation)) in self
+            .mean
+            .iter()
+            .zip(self.standard_deviation.iter())
+            .enumerate()
+        {
+            println!(
+                "  level {}: mean {:.3}, standard deviation {:.3}",
+                level, mean, standard_deviation
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{BaseValue, ObjectiveValue};
+
+    #[test]
+    fn test_run_summary() {
+        let result = |solution: i64, cost: f64| {
+            EvaluatedSolution::new(
+                solution,
+                ObjectiveValue::new(vec![BaseValue::Integer(solution), BaseValue::Float(cost)]),
+            )
+        };
+        let summary = RunSummary::new(vec![
+            (3, result(4, 1.0)),
+            (5, result(2, 3.0)),
+            (7, result(2, 5.0)),
+            (9, result(8, 7.0)),
+        ]);
+
+        assert_eq!(summary.number_of_runs(), 4);
+        assert_eq!(summary.best_run_index(), Some(5));
+        assert_eq!(summary.mean(), &[4.0, 4.0]);
+        assert_eq!(summary.standard_deviation()[0], 6.0f64.sqrt());
+        assert_eq!(summary.standard_deviation()[1], 5.0f64.sqrt());
+        assert_eq!(
+            *summary.into_best().unwrap().objective_value().as_vec(),
+            vec![BaseValue::Integer(2), BaseValue::Float(3.0)]
+        );
+
+        let empty = RunSummary::<i64>::new(vec![]);
+        assert!(empty.best().is_none());
+        assert!(empty.mean().is_empty());
+    }
+}
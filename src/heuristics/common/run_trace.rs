@@ -0,0 +1,140 @@
+//! This module contains the [`RunTraceWriter`], which records the objective values of solver runs
+//! as CSV traces. The traces of different configurations can be compared with the
+//! `rapid_solve_compare_runs` binary (feature `compare_runs`).
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::{default_function_between_steps, FunctionBetweenSteps};
+
+/// The header of a CSV run trace.
+pub const RUN_TRACE_HEADER: &str = "instance,run,iteration,elapsed_seconds,objective";
+
+/// Writes one CSV row per step of a solver run.
+/// * The columns are `instance,run,iteration,elapsed_seconds,objective`, where `objective`
+///   contains the levels of the [`ObjectiveValue`][crate::objective::ObjectiveValue] of the
+///   current solution as floats separated by `;` (see [`BaseValue::to_f64`][crate::objective::BaseValue::to_f64]).
+/// * Several runs (e.g., different instances or seeds) can share the same writer. The instance
+///   name must not contain commas.
+/// * Write errors are printed as warnings and do not abort the search.
+pub struct RunTraceWriter {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl RunTraceWriter {
+    /// Creates a new [`RunTraceWriter`] and writes the [`RUN_TRACE_HEADER`] to the `writer`
+    /// (e.g., a [`File`][std::fs::File]).
+    pub fn new(writer: Box<dyn Write + Send>) -> Arc<RunTraceWriter> {
+        let trace_writer = RunTraceWriter {
+            writer: Mutex::new(writer),
+        };
+        trace_writer.write_line(RUN_TRACE_HEADER);
+        Arc::new(trace_writer)
+    }
+
+    /// Returns a [`FunctionBetweenSteps`] that writes a row for each step of the run `run` on the
+    /// instance `instance` and then executes the given `function_between_steps` (if `None`, the
+    /// default function between steps is executed).
+    /// * The elapsed time is measured from the start of the solver (or, if the solver does not
+    ///   provide a start time, from the creation of the function).
+    pub fn recording_function_between_steps<S: 'static>(
+        trace_writer: Arc<RunTraceWriter>,
+        instance: &str,
+        run: u32,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+    ) -> FunctionBetweenSteps<S> {
+        let function_between_steps =
+            function_between_steps.unwrap_or(default_function_between_steps());
+        let instance = instance.to_string();
+        let creation_time = Instant::now();
+        Box::new(
+            move |iteration,
+                  current_solution,
+                  previous_solution,
+                  objective,
+                  start_time,
+                  time_limit,
+                  iteration_limit,
+                  neighbor_counts| {
+                let elapsed = start_time.unwrap_or(creation_time).elapsed();
+                let objective_levels: Vec<String> = current_solution
+                    .objective_value()
+                    .iter()
+                    .map(|value| value.to_f64().to_string())
+                    .collect();
+                trace_writer.write_line(&format!(
+                    "{},{},{},{},{}",
+                    instance,
+                    run,
+                    iteration,
+                    elapsed.as_secs_f64(),
+                    objective_levels.join(";")
+                ));
+                function_between_steps(
+                    iteration,
+                    current_solution,
+                    previous_solution,
+                    objective,
+                    start_time,
+                    time_limit,
+                    iteration_limit,
+                    neighbor_counts,
+                );
+            },
+        )
+    }
+
+    fn write_line(&self, line: &str) {
+        if let Err(error) = writeln!(self.writer.lock().unwrap(), "{}", line) {
+            report!(Warning, "could not write run trace: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{BaseValue, EvaluatedSolution, Objective, ObjectiveValue};
+    use crate::test_fixtures::Value;
+
+    #[derive(Clone)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_run_trace_writer() {
+        let buffer = SharedBuffer(Arc::new(Mutex::new(Vec::new())));
+        let trace_writer = RunTraceWriter::new(Box::new(buffer.clone()));
+        let function_between_steps = RunTraceWriter::recording_function_between_steps(
+            trace_writer,
+            "instance_a",
+            3,
+            Some(Box::new(|_, _, _, _, _, _, _, _| {})),
+        );
+
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            Value,
+        )]));
+        let solution = EvaluatedSolution::new(
+            7,
+            ObjectiveValue::new(vec![BaseValue::Integer(2), BaseValue::Float(1.5)]),
+        );
+        function_between_steps(5, &solution, None, objective, None, None, None, None);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], RUN_TRACE_HEADER);
+        assert!(lines[1].starts_with("instance_a,3,5,"));
+        assert!(lines[1].ends_with(",2;1.5"));
+    }
+}
@@ -0,0 +1,318 @@
+//! This module contains the [`SearchResult`], which is returned by
+//! [`solve_with_report`][crate::heuristics::Solver::solve_with_report].
+
+use std::sync::Arc;
+use std::time as stdtime;
+
+use super::{FunctionBetweenSteps, NeighborCounts, SearchStatistics};
+use crate::objective::{EvaluatedSolution, Objective, ObjectiveValue};
+
+/// The reason why a solver stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminationReason {
+    /// No improving neighbor was found (local optimum).
+    LocalOptimum,
+    /// The time limit was reached.
+    TimeLimit,
+    /// The iteration limit was reached.
+    IterationLimit,
+    /// The target objective value was reached.
+    TargetObjectiveValue,
+    /// The best objective value stagnated (e.g., detected by a
+    /// [`ConvergenceDetector`][super::ConvergenceDetector]).
+    Converged,
+    /// An iteration was stopped by its iteration timeout before an improving neighbor was found,
+    /// so the solution is not necessarily a local optimum (see `iteration_timeout` of the
+    /// [`LocalSearchSolver`][crate::heuristics::local_search::LocalSearchSolver]).
+    IterationTimeout,
+    /// The improver panicked and the panic was caught by a
+    /// [`PanicSafeImprover`][super::PanicSafeImprover], so the best solution is only the best
+    /// solution found before the panic (see [`PanicGuard`][super::PanicGuard]).
+    Panicked,
+    /// The solver does not report why it stopped (see the default implementation of
+    /// [`solve_with_report`][crate::heuristics::Solver::solve_with_report]).
+    Unknown,
+}
+
+/// A point of the objective trajectory of a [`SearchResult`].
+#[derive(Clone, Debug)]
+pub struct TrajectoryPoint {
+    /// The iteration (0 for the initial solution).
+    pub iteration: u32,
+    /// The time elapsed since the start of the search.
+    pub elapsed: stdtime::Duration,
+    /// The objective value of the current solution after this iteration.
+    pub objective_value: ObjectiveValue,
+    /// The numbers of neighbors that were generated, evaluated, rejected and accepted in this
+    /// iteration. `None` for the initial solution and if the solver (or its improver) does not
+    /// count them.
+    pub neighbor_counts: Option<NeighborCounts>,
+}
+
+/// The first time a hierarchy level of the current solution became zero, e.g., the first
+/// feasible solution if the level measures the violation of hard constraints.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelMilestone {
+    /// The index of the level.
+    pub level: usize,
+    /// The name of the level (see [`Objective::level_names`]).
+    pub level_name: String,
+    /// The iteration in which the level became zero (0 if the initial solution is zero on this
+    /// level).
+    pub iteration: u32,
+    /// The time elapsed since the start of the search.
+    pub elapsed: stdtime::Duration,
+}
+
+/// Detects the [`LevelMilestones`][LevelMilestone] of a run from the objective values of the
+/// current solution after each iteration.
+/// * Each milestone is reported as info when it is reached.
+/// * A level that becomes non-zero again does not produce a second milestone.
+pub struct MilestoneTracker {
+    level_names: Vec<String>,
+    milestones: Vec<LevelMilestone>,
+}
+
+impl MilestoneTracker {
+    /// Creates a new [`MilestoneTracker`] for the levels of the [`Objective`].
+    pub fn new<S>(objective: &Objective<S>) -> MilestoneTracker {
+        MilestoneTracker {
+            level_names: objective.level_names(),
+            milestones: Vec::new(),
+        }
+    }
+
+    /// Checks the `objective_value` of the current solution after the `iteration` and returns
+    /// the milestones that are reached for the first time.
+    pub fn observe(
+        &mut self,
+        iteration: u32,
+        elapsed: stdtime::Duration,
+        objective_value: &ObjectiveValue,
+    ) -> &[LevelMilestone] {
+        let number_of_previous_milestones = self.milestones.len();
+        for (level, level_name) in self.level_names.iter().enumerate() {
+            if level >= objective_value.as_vec().len()
+                || !objective_value.level_is_zero(level)
+                || self
+                    .milestones
+                    .iter()
+                    .any(|milestone| milestone.level == level)
+            {
+                continue;
+            }
+            report!(
+                Info,
+                "Level '{}' reached zero in iteration {} after {:0.2}sec.",
+                level_name,
+                iteration,
+                elapsed.as_secs_f32()
+            );
+            self.milestones.push(LevelMilestone {
+                level,
+                level_name: level_name.clone(),
+                iteration,
+                elapsed,
+            });
+        }
+        &self.milestones[number_of_previous_milestones..]
+    }
+
+    /// Returns the milestones reached so far (in the order they were reached).
+    pub fn milestones(&self) -> &[LevelMilestone] {
+        &self.milestones
+    }
+
+    /// Consumes the tracker and returns the milestones (in the order they were reached).
+    pub fn into_milestones(self) -> Vec<LevelMilestone> {
+        self.milestones
+    }
+}
+
+/// The result of a solver run including the information that is otherwise only printed.
+/// * `iterations` is `None` if the solver does not count them.
+/// * The `trajectory` contains the objective value of the current solution after each iteration
+///   together with the [`NeighborCounts`] that were passed to the `function_between_steps`.
+///   It is empty if the solver does not record it. The final exploration of the neighborhood,
+///   which does not lead to an iteration (e.g., in a local optimum), is not counted.
+/// * The `milestones` contain the first iteration in which each level became zero (see
+///   [`MilestoneTracker`]). They are empty if the solver does not record them.
+pub struct SearchResult<S> {
+    /// The best solution found.
+    pub best_solution: EvaluatedSolution<S>,
+    /// The number of iterations performed.
+    pub iterations: Option<u32>,
+    /// The wall-clock time of the run.
+    pub elapsed: stdtime::Duration,
+    /// The reason why the solver stopped.
+    pub termination_reason: TerminationReason,
+    /// The objective values of the current solution over the iterations.
+    pub trajectory: Vec<TrajectoryPoint>,
+    /// The levels that became zero during the run (in the order they became zero).
+    pub milestones: Vec<LevelMilestone>,
+    /// The message of the panic that ended the run (see [`TerminationReason::Panicked`]).
+    pub panic_message: Option<String>,
+}
+
+impl<S> SearchResult<S> {
+    /// Creates a [`SearchResult`] that only contains the `best_solution` and the `elapsed` time
+    /// (no counts, no trajectory and [`TerminationReason::Unknown`]).
+    pub fn without_details(
+        best_solution: EvaluatedSolution<S>,
+        elapsed: stdtime::Duration,
+    ) -> SearchResult<S> {
+        SearchResult {
+            best_solution,
+            iterations: None,
+            elapsed,
+            termination_reason: TerminationReason::Unknown,
+            trajectory: Vec::new(),
+            milestones: Vec::new(),
+            panic_message: None,
+        }
+    }
+
+    /// Returns the sum of the [`NeighborCounts`] of all points of the trajectory, or `None` if
+    /// no point has neighbor counts.
+    pub fn total_neighbor_counts(&self) -> Option<NeighborCounts> {
+        self.trajectory
+            .iter()
+            .filter_map(|point| point.neighbor_counts)
+            .reduce(|total, counts| total + counts)
+    }
+
+    /// Returns the number of evaluated neighbors (see
+    /// [`total_neighbor_counts`][SearchResult::total_neighbor_counts]), or `None` if the neighbors
+    /// are not counted.
+    pub fn evaluated_neighbors(&self) -> Option<u64> {
+        self.total_neighbor_counts().map(|counts| counts.evaluated)
+    }
+
+    /// Returns the [`SearchStatistics`] of this run (evaluations per second, acceptance rate,
+    /// time per iteration and improvement histogram, see [`SearchStatistics::from_result`]).
+    pub fn statistics(&self) -> SearchStatistics {
+        SearchStatistics::from_result(self)
+    }
+
+    /// Returns the [`LevelMilestone`] of the level with index `level`, i.e., when the level
+    /// became zero for the first time (e.g., the time to the first feasible solution). Returns
+    /// `None` if the level never became zero or the solver does not record milestones.
+    pub fn milestone(&self, level: usize) -> Option<&LevelMilestone> {
+        self.milestones
+            .iter()
+            .find(|milestone| milestone.level == level)
+    }
+}
+
+/// Passes each step of a solver to its `function_between_steps` and records the same step (with
+/// the same [`NeighborCounts`]) in the trajectory and the milestones of the [`SearchResult`].
+/// * Hence, the statistics streamed by a
+///   [`SearchStatisticsCollector`][super::SearchStatisticsCollector] agree with the statistics of
+///   the [`SearchResult`].
+/// * If `record` is `false` (i.e., the solver is called by
+///   [`solve`][crate::heuristics::Solver::solve]), only the `function_between_steps` is executed
+///   and neither trajectory nor milestones are kept.
+pub(crate) struct SearchRecorder<'a, S> {
+    function_between_steps: &'a FunctionBetweenSteps<S>,
+    objective: Arc<Objective<S>>,
+    start_time: stdtime::Instant,
+    time_limit: Option<stdtime::Duration>,
+    iteration_limit: Option<u32>,
+    record: bool,
+    trajectory: Vec<TrajectoryPoint>,
+    milestone_tracker: MilestoneTracker,
+}
+
+impl<'a, S> SearchRecorder<'a, S> {
+    /// Creates a new [`SearchRecorder`] for a run that started at `start_time` with a solution
+    /// of the `initial_objective_value` (iteration 0). The `objective`, `time_limit` and
+    /// `iteration_limit` are passed to the `function_between_steps`.
+    pub(crate) fn new(
+        function_between_steps: &'a FunctionBetweenSteps<S>,
+        objective: Arc<Objective<S>>,
+        start_time: stdtime::Instant,
+        time_limit: Option<stdtime::Duration>,
+        iteration_limit: Option<u32>,
+        initial_objective_value: &ObjectiveValue,
+        record: bool,
+    ) -> Self {
+        let mut recorder = SearchRecorder {
+            function_between_steps,
+            milestone_tracker: MilestoneTracker::new(&objective),
+            objective,
+            start_time,
+            time_limit,
+            iteration_limit,
+            record,
+            trajectory: Vec::new(),
+        };
+        recorder.push(0, stdtime::Duration::ZERO, initial_objective_value, None);
+        recorder
+    }
+
+    /// Executes the `function_between_steps` for the step of the `iteration` from the
+    /// `previous_solution` to the `current_solution` and records the step.
+    pub(crate) fn step(
+        &mut self,
+        iteration: u32,
+        current_solution: &EvaluatedSolution<S>,
+        previous_solution: Option<&EvaluatedSolution<S>>,
+        neighbor_counts: Option<NeighborCounts>,
+    ) {
+        (self.function_between_steps)(
+            iteration,
+            current_solution,
+            previous_solution,
+            self.objective.clone(),
+            Some(self.start_time),
+            self.time_limit,
+            self.iteration_limit,
+            neighbor_counts,
+        );
+        self.push(
+            iteration,
+            self.start_time.elapsed(),
+            current_solution.objective_value(),
+            neighbor_counts,
+        );
+    }
+
+    /// Returns the [`SearchResult`] with the `best_solution`, the number of `iterations` and the
+    /// `termination_reason`.
+    pub(crate) fn finish(
+        self,
+        best_solution: EvaluatedSolution<S>,
+        iterations: u32,
+        termination_reason: TerminationReason,
+    ) -> SearchResult<S> {
+        SearchResult {
+            best_solution,
+            iterations: Some(iterations),
+            elapsed: self.start_time.elapsed(),
+            termination_reason,
+            trajectory: self.trajectory,
+            milestones: self.milestone_tracker.into_milestones(),
+            panic_message: None,
+        }
+    }
+
+    fn push(
+        &mut self,
+        iteration: u32,
+        elapsed: stdtime::Duration,
+        objective_value: &ObjectiveValue,
+        neighbor_counts: Option<NeighborCounts>,
+    ) {
+        if !self.record {
+            return;
+        }
+        self.milestone_tracker
+            .observe(iteration, elapsed, objective_value);
+        self.trajectory.push(TrajectoryPoint {
+            iteration,
+            elapsed,
+            objective_value: objective_value.clone(),
+            neighbor_counts,
+        });
+    }
+}
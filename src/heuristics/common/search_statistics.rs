@@ -0,0 +1,424 @@
+//! This module contains the [`SearchStatistics`] of a solver run (evaluations per second,
+//! acceptance rate, time per iteration and a histogram of the improved levels), which help to
+//! tune the parameters of the solvers. The statistics are computed after a run from the
+//! [`SearchResult`] or collected during the run by the [`SearchStatisticsCollector`] and
+//! streamed to a [`StatisticsObserver`].
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time as stdtime;
+
+use super::{default_function_between_steps, FunctionBetweenSteps, NeighborCounts, SearchResult};
+use crate::objective::ObjectiveValue;
+
+/// Type for a function that receives the current [`SearchStatistics`] after each recorded step
+/// of a [`SearchStatisticsCollector`] (e.g., to print them or to send them to a dashboard).
+pub type StatisticsObserver = Box<dyn Fn(&SearchStatistics) + Send + Sync>;
+
+/// The counters of a solver run.
+/// * `neighbor_counts` is `None` if the solver does not count the neighbors. The counts are the
+///   ones passed to the `function_between_steps`, so the [`SearchStatisticsCollector`] and
+///   [`SearchStatistics::from_result`] agree.
+/// * The `improvement_histogram` contains for each level of the objective the number of steps
+///   whose first changed level was improved (e.g., the first entry counts the steps that reduced
+///   the violation of the hard constraints). Steps that did not improve the objective value
+///   (e.g., accepted worse neighbors of simulated annealing) are counted as
+///   `non_improving_steps`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchStatistics {
+    /// The number of iterations.
+    pub iterations: u32,
+    /// The time elapsed since the start of the run.
+    pub elapsed: stdtime::Duration,
+    /// The summed [`NeighborCounts`] of all iterations.
+    pub neighbor_counts: Option<NeighborCounts>,
+    /// The number of improving steps per level (see above).
+    pub improvement_histogram: Vec<u64>,
+    /// The number of steps that did not improve the objective value.
+    pub non_improving_steps: u64,
+}
+
+impl SearchStatistics {
+    /// Computes the [`SearchStatistics`] of a run from its [`SearchResult`] (see
+    /// [`solve_with_report`][crate::heuristics::Solver::solve_with_report]).
+    /// * The histogram is computed from the trajectory, so it is empty for solvers that do not
+    ///   record a trajectory.
+    /// * If the solver does not report the number of iterations, the number of points of the
+    ///   trajectory (without the initial solution) is used.
+    pub fn from_result<S>(result: &SearchResult<S>) -> SearchStatistics {
+        let mut statistics = SearchStatistics {
+            iterations: result
+                .iterations
+                .unwrap_or(result.trajectory.len().saturating_sub(1) as u32),
+            elapsed: result.elapsed,
+            neighbor_counts: result.total_neighbor_counts(),
+            ..SearchStatistics::default()
+        };
+        for pair in result.trajectory.windows(2) {
+            statistics.count_step(&pair[0].objective_value, &pair[1].objective_value);
+        }
+        statistics
+    }
+
+    /// Returns the number of evaluated neighbors per second, or `None` if the neighbors are not
+    /// counted or no time has elapsed.
+    pub fn evaluations_per_second(&self) -> Option<f64> {
+        let counts = self.neighbor_counts?;
+        let seconds = self.elapsed.as_secs_f64();
+        (seconds > 0.0).then(|| counts.evaluated as f64 / seconds)
+    }
+
+    /// Returns the fraction of the evaluated neighbors that were accepted, or `None` if the
+    /// neighbors are not counted or no neighbor was evaluated.
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        let counts = self.neighbor_counts?;
+        (counts.evaluated > 0).then(|| counts.accepted as f64 / counts.evaluated as f64)
+    }
+
+    /// Returns the mean time per iteration, or `None` if there was no iteration.
+    pub fn time_per_iteration(&self) -> Option<stdtime::Duration> {
+        (self.iterations > 0).then(|| self.elapsed / self.iterations)
+    }
+
+    /// Returns the number of steps that improved the objective value (on any level).
+    pub fn improving_steps(&self) -> u64 {
+        self.improvement_histogram.iter().sum()
+    }
+
+    /// Counts a step from the `previous` to the `current` objective value in the histogram.
+    fn count_step(&mut self, previous: &ObjectiveValue, current: &ObjectiveValue) {
+        let first_changed_level = current
+            .iter()
+            .zip(previous.iter())
+            .position(|(current, previous)| current != previous);
+        match first_changed_level {
+            Some(level) if current.as_vec()[level] < previous.as_vec()[level] => {
+                if self.improvement_histogram.len() <= level {
+                    self.improvement_histogram.resize(level + 1, 0);
+                }
+                self.improvement_histogram[level] += 1;
+            }
+            _ => self.non_improving_steps += 1,
+        }
+    }
+}
+
+impl fmt::Display for SearchStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} iterations in {:0.2}sec",
+            self.iterations,
+            self.elapsed.as_secs_f32()
+        )?;
+        if let Some(time_per_iteration) = self.time_per_iteration() {
+            write!(
+                f,
+                " ({:0.3}ms per iteration)",
+                time_per_iteration.as_secs_f64() * 1000.0
+            )?;
+        }
+        if let Some(evaluations_per_second) = self.evaluations_per_second() {
+            write!(f, ", {:0.0} evaluations/sec", evaluations_per_second)?;
+        }
+        if let Some(acceptance_rate) = self.acceptance_rate() {
+            write!(f, ", acceptance rate: {:0.2}%", acceptance_rate * 100.0)?;
+        }
+        write!(
+            f,
+            ", improving steps per level: {:?}, non-improving steps: {}",
+            self.improvement_histogram, self.non_improving_steps
+        )
+    }
+}
+
+/// Collects the [`SearchStatistics`] during one or several solver runs and streams them to an
+/// optional [`StatisticsObserver`].
+/// * The steps are recorded by the [`FunctionBetweenSteps`] returned by
+///   [`recording_function_between_steps`][SearchStatisticsCollector::recording_function_between_steps],
+///   which can be given to every solver with a `function_between_steps`, or manually by
+///   [`record`][SearchStatisticsCollector::record] (e.g., with the [`NeighborCounts`] of a custom
+///   solver).
+/// * [`statistics`][SearchStatisticsCollector::statistics] returns the statistics of the current
+///   run and [`new_run`][SearchStatisticsCollector::new_run] resets them.
+pub struct SearchStatisticsCollector {
+    statistics: Mutex<SearchStatistics>,
+    observer: Option<StatisticsObserver>,
+}
+
+impl SearchStatisticsCollector {
+    /// Creates a new [`SearchStatisticsCollector`], which passes the statistics to the `observer`
+    /// (if given) after each recorded step.
+    pub fn new(observer: Option<StatisticsObserver>) -> Arc<SearchStatisticsCollector> {
+        Arc::new(SearchStatisticsCollector {
+            statistics: Mutex::new(SearchStatistics::default()),
+            observer,
+        })
+    }
+
+    /// Returns a [`FunctionBetweenSteps`] that records each step and then executes the given
+    /// `function_between_steps` (if `None`, the default function between steps is executed).
+    /// * The elapsed time is measured from the start time of the solver (if the solver does not
+    ///   provide it, the elapsed time remains zero).
+    /// * Steps without previous solution (e.g., the final report of some solvers) are not
+    ///   recorded.
+    /// * The [`NeighborCounts`] passed between the steps are summed up (they remain `None` if the
+    ///   solver does not count the neighbors).
+    pub fn recording_function_between_steps<S: 'static>(
+        collector: Arc<SearchStatisticsCollector>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+    ) -> FunctionBetweenSteps<S> {
+        let function_between_steps =
+            function_between_steps.unwrap_or(default_function_between_steps());
+        Box::new(
+            move |iteration,
+                  current_solution,
+                  previous_solution,
+                  objective,
+                  start_time,
+                  time_limit,
+                  iteration_limit,
+                  neighbor_counts| {
+                if let Some(previous_solution) = previous_solution {
+                    collector.record(
+                        iteration,
+                        start_time.map(|start_time| start_time.elapsed()),
+                        previous_solution.objective_value(),
+                        current_solution.objective_value(),
+                        neighbor_counts,
+                    );
+                }
+                function_between_steps(
+                    iteration,
+                    current_solution,
+                    previous_solution,
+                    objective,
+                    start_time,
+                    time_limit,
+                    iteration_limit,
+                    neighbor_counts,
+                );
+            },
+        )
+    }
+
+    /// Records a step in the given `iteration` from a solution with the `previous` objective
+    /// value to a solution with the `current` objective value, together with the `elapsed` time
+    /// since the start of the run and the [`NeighborCounts`] of the iteration (if known).
+    /// Afterwards, the observer is notified.
+    pub fn record(
+        &self,
+        iteration: u32,
+        elapsed: Option<stdtime::Duration>,
+        previous: &ObjectiveValue,
+        current: &ObjectiveValue,
+        neighbor_counts: Option<NeighborCounts>,
+    ) {
+        let mut statistics = self.statistics.lock().unwrap();
+        statistics.iterations = statistics.iterations.max(iteration);
+        if let Some(elapsed) = elapsed {
+            statistics.elapsed = statistics.elapsed.max(elapsed);
+        }
+        if let Some(neighbor_counts) = neighbor_counts {
+            let total = statistics.neighbor_counts.unwrap_or_default() + neighbor_counts;
+            statistics.neighbor_counts = Some(total);
+        }
+        statistics.count_step(previous, current);
+        if let Some(observer) = &self.observer {
+            observer(&statistics);
+        }
+    }
+
+    /// Returns the statistics of the current run.
+    pub fn statistics(&self) -> SearchStatistics {
+        self.statistics.lock().unwrap().clone()
+    }
+
+    /// Resets the statistics for a new run.
+    pub fn new_run(&self) {
+        *self.statistics.lock().unwrap() = SearchStatistics::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::Neighborhood;
+    use crate::heuristics::common::{silent_function_between_steps, TerminationCriteria};
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::heuristics::threshold_accepting::ThresholdAcceptingSolver;
+    use crate::heuristics::Solver;
+    use crate::objective::{BaseValue, Indicator, Objective};
+
+    struct Distance;
+
+    impl Indicator<(i64, i64)> for Distance {
+        fn evaluate(&self, solution: &(i64, i64)) -> BaseValue {
+            BaseValue::Integer(solution.0.abs())
+        }
+        fn name(&self) -> String {
+            String::from("Distance")
+        }
+    }
+
+    struct Penalty;
+
+    impl Indicator<(i64, i64)> for Penalty {
+        fn evaluate(&self, solution: &(i64, i64)) -> BaseValue {
+            BaseValue::Integer(solution.1.abs())
+        }
+        fn name(&self) -> String {
+            String::from("Penalty")
+        }
+    }
+
+    struct UnitSteps;
+
+    impl Neighborhood<(i64, i64)> for UnitSteps {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a (i64, i64),
+        ) -> Box<dyn Iterator<Item = (i64, i64)> + Send + Sync + 'a> {
+            let (a, b) = *solution;
+            Box::new(vec![(a - 1, b), (a + 1, b), (a, b - 1), (a, b + 1)].into_iter())
+        }
+    }
+
+    fn objective() -> Arc<Objective<(i64, i64)>> {
+        Arc::new(Objective::new_single_indicator_per_level(vec![
+            Box::new(Distance),
+            Box::new(Penalty),
+        ]))
+    }
+
+    #[test]
+    fn test_statistics_from_result() {
+        let solver = LocalSearchSolver::with_options(
+            Arc::new(UnitSteps),
+            objective(),
+            None,
+            Some(silent_function_between_steps()),
+            None,
+        );
+        let statistics = solver.solve_with_report((3, -2)).statistics();
+
+        assert_eq!(statistics.iterations, 5);
+        assert_eq!(statistics.improvement_histogram, vec![3, 2]);
+        assert_eq!(statistics.improving_steps(), 5);
+        assert_eq!(statistics.non_improving_steps, 0);
+        let counts = statistics.neighbor_counts.unwrap();
+        // the final exploration without improvement is not an iteration of the trajectory
+        assert_eq!(counts.evaluated, 20);
+        assert_eq!(counts.accepted, 5);
+        assert!(statistics.acceptance_rate().unwrap() > 0.0);
+        assert!(statistics.time_per_iteration().is_some());
+        assert!(statistics.to_string().starts_with("5 iterations in "));
+    }
+
+    /// Runs the solver built with a recording `function_between_steps` and asserts that the
+    /// collected statistics agree with the statistics of its [`SearchResult`].
+    fn assert_collector_agrees_with_result(
+        build_solver: impl FnOnce(FunctionBetweenSteps<(i64, i64)>) -> Box<dyn Solver<(i64, i64)>>,
+    ) {
+        let collector = SearchStatisticsCollector::new(None);
+        let solver = build_solver(SearchStatisticsCollector::recording_function_between_steps(
+            collector.clone(),
+            Some(silent_function_between_steps()),
+        ));
+        let result_statistics = solver.solve_with_report((3, -2)).statistics();
+        let collected_statistics = collector.statistics();
+
+        assert!(result_statistics.neighbor_counts.is_some());
+        assert_eq!(
+            collected_statistics.neighbor_counts,
+            result_statistics.neighbor_counts
+        );
+        assert_eq!(
+            collected_statistics.iterations,
+            result_statistics.iterations
+        );
+        assert_eq!(
+            collected_statistics.improvement_histogram,
+            result_statistics.improvement_histogram
+        );
+        assert_eq!(
+            collected_statistics.non_improving_steps,
+            result_statistics.non_improving_steps
+        );
+    }
+
+    #[test]
+    fn test_collector_agrees_with_result() {
+        assert_collector_agrees_with_result(|function_between_steps| {
+            Box::new(LocalSearchSolver::with_options(
+                Arc::new(UnitSteps),
+                objective(),
+                None,
+                Some(function_between_steps),
+                None,
+            ))
+        });
+        assert_collector_agrees_with_result(|function_between_steps| {
+            Box::new(ThresholdAcceptingSolver::with_options(
+                Arc::new(UnitSteps),
+                objective(),
+                ObjectiveValue::new(vec![BaseValue::Integer(1), BaseValue::Integer(1)]),
+                0.9,
+                Some(function_between_steps),
+                Some(TerminationCriteria::iteration_limit(50)),
+            ))
+        });
+    }
+
+    #[test]
+    fn test_statistics_collector() {
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let observed_clone = observed.clone();
+        let collector = SearchStatisticsCollector::new(Some(Box::new(move |statistics| {
+            observed_clone.lock().unwrap().push(statistics.iterations)
+        })));
+        let function_between_steps = SearchStatisticsCollector::recording_function_between_steps(
+            collector.clone(),
+            Some(silent_function_between_steps()),
+        );
+        let objective = objective();
+        let solutions: Vec<_> = [(2, 0), (1, 0), (1, 1), (0, 1), (0, 0)]
+            .into_iter()
+            .map(|solution| objective.evaluate(solution))
+            .collect();
+        for (iteration, pair) in solutions.windows(2).enumerate() {
+            function_between_steps(
+                iteration as u32 + 1,
+                &pair[1],
+                Some(&pair[0]),
+                objective.clone(),
+                Some(stdtime::Instant::now()),
+                None,
+                None,
+                None,
+            );
+        }
+        collector.record(
+            5,
+            None,
+            solutions[4].objective_value(),
+            solutions[4].objective_value(),
+            Some(NeighborCounts {
+                generated: 4,
+                evaluated: 4,
+                accepted: 1,
+                ..NeighborCounts::default()
+            }),
+        );
+
+        let statistics = collector.statistics();
+        assert_eq!(*observed.lock().unwrap(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(statistics.iterations, 5);
+        assert_eq!(statistics.improvement_histogram, vec![2, 1]);
+        assert_eq!(statistics.non_improving_steps, 2);
+        assert_eq!(statistics.acceptance_rate(), Some(0.25));
+
+        collector.new_run();
+        assert_eq!(collector.statistics(), SearchStatistics::default());
+    }
+}
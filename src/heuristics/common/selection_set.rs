@@ -0,0 +1,249 @@
+//! This module contains the [`SelectionSet`] trait for set-based solutions (e.g., knapsack,
+//! facility location or feature selection) and the generic [`AddDropSwapNeighborhood`].
+
+use super::Neighborhood;
+use super::ParallelNeighborhood;
+use crate::parallel::{IntoParallelIterator, ParallelIterator};
+
+/// A solution that consists of a selection of elements from a ground set.
+/// * [`chosen`][SelectionSet::chosen] returns the elements that are currently selected.
+/// * [`unchosen`][SelectionSet::unchosen] returns the elements that could be added.
+/// * [`add`][SelectionSet::add] and [`remove`][SelectionSet::remove] return a modified copy of
+///   the solution (the solution itself is not changed).
+/// * [`swap`][SelectionSet::swap] can be overwritten if removing and adding at once is cheaper
+///   than two separate modifications.
+pub trait SelectionSet: Sized {
+    /// The type of the elements of the ground set.
+    type Element: Copy + Send + Sync;
+
+    /// Returns all elements that are currently chosen.
+    fn chosen(&self) -> Vec<Self::Element>;
+
+    /// Returns all elements that are currently not chosen.
+    fn unchosen(&self) -> Vec<Self::Element>;
+
+    /// Returns a copy of the solution with `element` (currently unchosen) added.
+    fn add(&self, element: Self::Element) -> Self;
+
+    /// Returns a copy of the solution with `element` (currently chosen) removed.
+    fn remove(&self, element: Self::Element) -> Self;
+
+    /// Returns a copy of the solution with `removed` (currently chosen) replaced by `added`
+    /// (currently unchosen).
+    fn swap(&self, removed: Self::Element, added: Self::Element) -> Self {
+        self.remove(removed).add(added)
+    }
+}
+
+/// A [`Neighborhood`] (and [`ParallelNeighborhood`]) for all solutions implementing
+/// [`SelectionSet`].
+/// * Add moves: Each unchosen element is added.
+/// * Drop moves: Each chosen element is removed.
+/// * Swap moves: Each chosen element is replaced by each unchosen element.
+/// * The neighbors are generated in this order. Each move type can be disabled via
+///   [`with_moves`][AddDropSwapNeighborhood::with_moves] (e.g., only swaps for a
+///   cardinality-constrained selection).
+pub struct AddDropSwapNeighborhood {
+    add_moves: bool,
+    drop_moves: bool,
+    swap_moves: bool,
+}
+
+impl AddDropSwapNeighborhood {
+    /// Creates a new [`AddDropSwapNeighborhood`] with all move types enabled.
+    pub fn new() -> AddDropSwapNeighborhood {
+        AddDropSwapNeighborhood::with_moves(true, true, true)
+    }
+
+    /// Creates a new [`AddDropSwapNeighborhood`] with only the given move types enabled.
+    pub fn with_moves(
+        add_moves: bool,
+        drop_moves: bool,
+        swap_moves: bool,
+    ) -> AddDropSwapNeighborhood {
+        AddDropSwapNeighborhood {
+            add_moves,
+            drop_moves,
+            swap_moves,
+        }
+    }
+}
+
+impl Default for AddDropSwapNeighborhood {
+    fn default() -> Self {
+        AddDropSwapNeighborhood::new()
+    }
+}
+
+impl<S: SelectionSet + Send + Sync> Neighborhood<S> for AddDropSwapNeighborhood {
+    fn neighbors_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a> {
+        let chosen = current_solution.chosen();
+        let unchosen = current_solution.unchosen();
+
+        let adds = self
+            .add_moves
+            .then(|| unchosen.clone())
+            .into_iter()
+            .flatten()
+            .map(move |element| current_solution.add(element));
+        let drops = self
+            .drop_moves
+            .then(|| chosen.clone())
+            .into_iter()
+            .flatten()
+            .map(move |element| current_solution.remove(element));
+        let swaps = self
+            .swap_moves
+            .then_some(chosen)
+            .into_iter()
+            .flatten()
+            .flat_map(move |removed| {
+                unchosen
+                    .clone()
+                    .into_iter()
+                    .map(move |added| current_solution.swap(removed, added))
+            });
+
+        Box::new(adds.chain(drops).chain(swaps))
+    }
+}
+
+impl<S: SelectionSet + Send + Sync> ParallelNeighborhood<S> for AddDropSwapNeighborhood {
+    fn neighbors_of<'a>(&'a self, current_solution: &'a S) -> impl ParallelIterator<Item = S> + 'a {
+        let chosen = current_solution.chosen();
+        let unchosen = current_solution.unchosen();
+
+        let adds = if self.add_moves {
+            unchosen.clone()
+        } else {
+            Vec::new()
+        };
+        let drops = if self.drop_moves {
+            chosen.clone()
+        } else {
+            Vec::new()
+        };
+        let swaps = if self.swap_moves { chosen } else { Vec::new() };
+
+        adds.into_par_iter()
+            .map(move |element| current_solution.add(element))
+            .chain(
+                drops
+                    .into_par_iter()
+                    .map(move |element| current_solution.remove(element)),
+            )
+            .chain(swaps.into_par_iter().flat_map(move |removed| {
+                unchosen
+                    .clone()
+                    .into_par_iter()
+                    .map(move |added| current_solution.swap(removed, added))
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::heuristics::Solver;
+    use crate::objective::{BaseValue, Coefficient, Indicator, LinearCombination, Objective};
+    use std::sync::Arc;
+
+    const WEIGHTS: [i64; 5] = [4, 3, 2, 5, 1];
+    const VALUES: [i64; 5] = [10, 7, 5, 11, 1];
+    const CAPACITY: i64 = 9;
+
+    /// A small knapsack solution: `selected[i]` tells whether item `i` is packed.
+    #[derive(Clone)]
+    struct Knapsack {
+        selected: [bool; 5],
+    }
+
+    impl SelectionSet for Knapsack {
+        type Element = usize;
+
+        fn chosen(&self) -> Vec<usize> {
+            (0..5).filter(|&i| self.selected[i]).collect()
+        }
+
+        fn unchosen(&self) -> Vec<usize> {
+            (0..5).filter(|&i| !self.selected[i]).collect()
+        }
+
+        fn add(&self, element: usize) -> Self {
+            let mut selected = self.selected;
+            selected[element] = true;
+            Knapsack { selected }
+        }
+
+        fn remove(&self, element: usize) -> Self {
+            let mut selected = self.selected;
+            selected[element] = false;
+            Knapsack { selected }
+        }
+    }
+
+    struct Overweight;
+
+    impl Indicator<Knapsack> for Overweight {
+        fn evaluate(&self, solution: &Knapsack) -> BaseValue {
+            let weight: i64 = solution.chosen().iter().map(|&i| WEIGHTS[i]).sum();
+            BaseValue::Integer((weight - CAPACITY).max(0))
+        }
+
+        fn name(&self) -> String {
+            String::from("Overweight")
+        }
+    }
+
+    struct PackedValue;
+
+    impl Indicator<Knapsack> for PackedValue {
+        fn evaluate(&self, solution: &Knapsack) -> BaseValue {
+            BaseValue::Integer(solution.chosen().iter().map(|&i| VALUES[i]).sum())
+        }
+
+        fn name(&self) -> String {
+            String::from("PackedValue")
+        }
+    }
+
+    #[test]
+    fn test_add_drop_swap_neighborhood() {
+        let solution = Knapsack {
+            selected: [true, false, true, false, false],
+        };
+        let neighborhood = AddDropSwapNeighborhood::new();
+        // 3 adds, 2 drops, 2 * 3 swaps
+        assert_eq!(
+            Neighborhood::neighbors_of(&neighborhood, &solution).count(),
+            11
+        );
+        assert_eq!(
+            ParallelNeighborhood::neighbors_of(&neighborhood, &solution).count(),
+            11
+        );
+        let swaps_only = AddDropSwapNeighborhood::with_moves(false, false, true);
+        assert_eq!(
+            Neighborhood::neighbors_of(&swaps_only, &solution).count(),
+            6
+        );
+
+        let objective = Arc::new(Objective::new(vec![
+            LinearCombination::new(vec![(Coefficient::Integer(1), Box::new(Overweight))]),
+            LinearCombination::new(vec![(Coefficient::Integer(-1), Box::new(PackedValue))]),
+        ]));
+        let solver = LocalSearchSolver::initialize(Arc::new(neighborhood), objective);
+
+        let result = solver.solve(Knapsack {
+            selected: [false; 5],
+        });
+
+        // items 0 and 3 (weight 9, value 21) form a local optimum: the optimal packing 0, 1, 2
+        // (weight 9, value 22) cannot be reached by a single add, drop or swap move.
+        assert_eq!(result.solution().chosen(), vec![0, 3]);
+    }
+}
@@ -0,0 +1,137 @@
+//! This module contains the [`SharedBest`], a thread-safe slot for the best solution found by
+//! several cooperating solvers.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::objective::{EvaluatedSolution, Objective, ObjectiveValue};
+
+/// The best [`EvaluatedSolution`] found by several cooperating solvers (e.g., the solvers of a
+/// [`PortfolioSolver`][super::PortfolioSolver] or the starts of a
+/// [`MultiStartSolver`][super::MultiStartSolver]), which can be shared between threads via an
+/// [`Arc`].
+/// * A solution [`offered`][SharedBest::offer] to the slot only replaces the stored solution if
+///   it is better w.r.t. the [`Objective`] (compare-and-swap by objective), so the slot never
+///   gets worse.
+/// * Reading the best solution only takes a read lock, such that many solvers can poll it
+///   concurrently.
+/// * The [`version`][SharedBest::version] is increased with every new best solution, such that
+///   solvers can cheaply check whether another solver found a new best solution (e.g., to restart
+///   from it) without cloning it.
+pub struct SharedBest<S> {
+    objective: Arc<Objective<S>>,
+    best_solution: RwLock<Option<EvaluatedSolution<S>>>,
+    version: AtomicU64,
+}
+
+impl<S: Clone> SharedBest<S> {
+    /// Creates a new empty [`SharedBest`] that compares solutions w.r.t. the [`Objective`].
+    pub fn new(objective: Arc<Objective<S>>) -> Arc<SharedBest<S>> {
+        Arc::new(SharedBest {
+            objective,
+            best_solution: RwLock::new(None),
+            version: AtomicU64::new(0),
+        })
+    }
+
+    /// Offers a solution. It replaces the best solution if it is better (or if there is no best
+    /// solution yet) and returns `true` in this case.
+    pub fn offer(&self, solution: &EvaluatedSolution<S>) -> bool {
+        // most offers are not better, so these are rejected with a read lock only
+        if !self.is_improvement(solution, &self.best_solution.read().unwrap()) {
+            return false;
+        }
+        let mut best_solution = self.best_solution.write().unwrap();
+        // another thread might have stored a better solution in the meantime
+        if !self.is_improvement(solution, &best_solution) {
+            return false;
+        }
+        *best_solution = Some(solution.clone());
+        self.version.fetch_add(1, Ordering::Release);
+        true
+    }
+
+    /// Returns a clone of the best solution offered so far.
+    pub fn best_solution(&self) -> Option<EvaluatedSolution<S>> {
+        self.best_solution.read().unwrap().clone()
+    }
+
+    /// Returns the [`ObjectiveValue`] of the best solution offered so far (without cloning the
+    /// solution).
+    pub fn best_objective_value(&self) -> Option<ObjectiveValue> {
+        self.best_solution
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|best_solution| best_solution.objective_value().clone())
+    }
+
+    /// Returns the number of times the best solution has been replaced (0 if no solution has
+    /// been offered).
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Returns the [`Objective`] by which the solutions are compared.
+    pub fn objective(&self) -> Arc<Objective<S>> {
+        self.objective.clone()
+    }
+
+    /// Forgets the best solution, e.g., before the slot is used for another run. The version is
+    /// not reset.
+    pub fn reset(&self) {
+        *self.best_solution.write().unwrap() = None;
+    }
+
+    fn is_improvement(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        best_solution: &Option<EvaluatedSolution<S>>,
+    ) -> bool {
+        match best_solution {
+            Some(best_solution) => self.objective.is_better(solution, best_solution),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::objective::BaseValue;
+    use crate::test_fixtures::DistanceTo;
+
+    #[test]
+    fn test_concurrent_offers() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(100))));
+        let shared_best = SharedBest::new(objective.clone());
+        assert!(shared_best.best_solution().is_none());
+
+        let handles: Vec<_> = (0..4)
+            .map(|thread_index| {
+                let shared_best = shared_best.clone();
+                let objective = objective.clone();
+                thread::spawn(move || {
+                    for value in (0..200).skip(thread_index).step_by(4) {
+                        shared_best.offer(&objective.evaluate(value));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*shared_best.best_solution().unwrap().solution(), 100);
+        assert_eq!(
+            shared_best.best_objective_value().unwrap(),
+            ObjectiveValue::new(vec![BaseValue::Integer(0)])
+        );
+        let version = shared_best.version();
+        assert!(version >= 1);
+        assert!(!shared_best.offer(&objective.evaluate(99)));
+        assert_eq!(shared_best.version(), version);
+    }
+}
@@ -0,0 +1,190 @@
+//! This module contains the [`ShrinkingNeighborhood`], which wraps a [`StepSizeNeighborhood`] and
+//! decreases its step size according to a [`StepSizeSchedule`].
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time as stdtime;
+
+use super::Neighborhood;
+
+/// A neighborhood whose move magnitude is controlled by a step size (e.g., the delta by which an
+/// entry of a numeric solution is changed).
+pub trait StepSizeNeighborhood<S>: Send + Sync {
+    /// Returns an iterator over all neighbors of `current_solution` for the given `step_size`.
+    fn neighbors_of_with_step_size<'a>(
+        &'a self,
+        current_solution: &'a S,
+        step_size: f64,
+    ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a>;
+}
+
+/// Defines how the step size of a [`ShrinkingNeighborhood`] decreases.
+#[derive(Clone, Copy, Debug)]
+pub enum StepSizeSchedule {
+    /// The step size is `initial * factor^iteration`, but at least `minimum`.
+    Geometric {
+        /// The step size in the first iteration.
+        initial: f64,
+        /// The factor (between 0 and 1) by which the step size is multiplied in each iteration.
+        factor: f64,
+        /// The lower bound of the step size.
+        minimum: f64,
+    },
+    /// The step size decreases linearly from `initial` to `minimum` within `duration` (measured
+    /// from the first call of the neighborhood) and stays at `minimum` afterwards.
+    LinearInTime {
+        /// The step size at the start.
+        initial: f64,
+        /// The step size after `duration`.
+        minimum: f64,
+        /// The time after which the step size reaches `minimum`.
+        duration: stdtime::Duration,
+    },
+}
+
+impl StepSizeSchedule {
+    /// Returns the step size for the given `iteration` (starting with 0) and the `elapsed_time`.
+    pub fn step_size(&self, iteration: u32, elapsed_time: stdtime::Duration) -> f64 {
+        match *self {
+            StepSizeSchedule::Geometric {
+                initial,
+                factor,
+                minimum,
+            } => (initial * factor.powi(iteration as i32)).max(minimum),
+            StepSizeSchedule::LinearInTime {
+                initial,
+                minimum,
+                duration,
+            } => {
+                let progress = (elapsed_time.as_secs_f64()
+                    / duration.as_secs_f64().max(f64::EPSILON))
+                .min(1.0);
+                initial + (minimum - initial) * progress
+            }
+        }
+    }
+}
+
+/// Wraps a [`StepSizeNeighborhood`] into a [`Neighborhood`] whose step size decreases over time
+/// (diminishing step sizes), e.g., to take large steps at the beginning and fine-tune the
+/// solution near convergence.
+/// * Each call of [`neighbors_of`][Neighborhood::neighbors_of] counts as one iteration for the
+///   [`StepSizeSchedule`] (for improvers with recursion, this is more than one call per
+///   iteration of the solver).
+/// * The time for the [`StepSizeSchedule::LinearInTime`] is measured from the first call.
+/// * If the same neighborhood is used for several solver runs, [`reset`][Self::reset] restarts
+///   the schedule.
+/// * Note that a [`LocalSearchSolver`][crate::heuristics::local_search::LocalSearchSolver] stops
+///   as soon as no neighbor improves the solution, so shrinking step sizes are most useful for
+///   solvers that continue, e.g., [threshold
+///   accepting][crate::heuristics::threshold_accepting] or [simulated
+///   annealing][crate::heuristics::simulated_annealing].
+pub struct ShrinkingNeighborhood<S> {
+    neighborhood: Arc<dyn StepSizeNeighborhood<S>>,
+    schedule: StepSizeSchedule,
+    iteration: AtomicU32,
+    start_time: Mutex<Option<stdtime::Instant>>,
+}
+
+impl<S> ShrinkingNeighborhood<S> {
+    /// Creates a new [`ShrinkingNeighborhood`] for the given [`StepSizeNeighborhood`] and
+    /// [`StepSizeSchedule`].
+    pub fn new(
+        neighborhood: Arc<dyn StepSizeNeighborhood<S>>,
+        schedule: StepSizeSchedule,
+    ) -> ShrinkingNeighborhood<S> {
+        ShrinkingNeighborhood {
+            neighborhood,
+            schedule,
+            iteration: AtomicU32::new(0),
+            start_time: Mutex::new(None),
+        }
+    }
+
+    /// Returns the step size that is used for the next call.
+    pub fn current_step_size(&self) -> f64 {
+        let elapsed_time = match *self.start_time.lock().unwrap() {
+            Some(start_time) => start_time.elapsed(),
+            None => stdtime::Duration::ZERO,
+        };
+        self.schedule
+            .step_size(self.iteration.load(Ordering::Relaxed), elapsed_time)
+    }
+
+    /// Restarts the schedule (iteration counter and time).
+    pub fn reset(&self) {
+        self.iteration.store(0, Ordering::Relaxed);
+        *self.start_time.lock().unwrap() = None;
+    }
+}
+
+impl<S> Neighborhood<S> for ShrinkingNeighborhood<S> {
+    fn neighbors_of<'a>(
+        &'a self,
+        current_solution: &'a S,
+    ) -> Box<dyn Iterator<Item = S> + Send + Sync + 'a> {
+        let elapsed_time = self
+            .start_time
+            .lock()
+            .unwrap()
+            .get_or_insert_with(stdtime::Instant::now)
+            .elapsed();
+        let iteration = self.iteration.fetch_add(1, Ordering::Relaxed);
+        let step_size = self.schedule.step_size(iteration, elapsed_time);
+        self.neighborhood
+            .neighbors_of_with_step_size(current_solution, step_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct PlusMinusStep;
+
+    impl StepSizeNeighborhood<f64> for PlusMinusStep {
+        fn neighbors_of_with_step_size<'a>(
+            &'a self,
+            current_solution: &'a f64,
+            step_size: f64,
+        ) -> Box<dyn Iterator<Item = f64> + Send + Sync + 'a> {
+            Box::new([current_solution + step_size, current_solution - step_size].into_iter())
+        }
+    }
+
+    #[test]
+    fn test_shrinking_neighborhood() {
+        let neighborhood = ShrinkingNeighborhood::new(
+            Arc::new(PlusMinusStep),
+            StepSizeSchedule::Geometric {
+                initial: 1.0,
+                factor: 0.5,
+                minimum: 0.2,
+            },
+        );
+        assert_eq!(neighborhood.current_step_size(), 1.0);
+        let neighbors: Vec<Vec<f64>> = (0..4)
+            .map(|_| neighborhood.neighbors_of(&2.0).collect())
+            .collect();
+        assert_eq!(
+            neighbors,
+            vec![
+                vec![3.0, 1.0],
+                vec![2.5, 1.5],
+                vec![2.25, 1.75],
+                vec![2.2, 1.8] // minimum step size
+            ]
+        );
+
+        neighborhood.reset();
+        assert_eq!(neighborhood.current_step_size(), 1.0);
+
+        let linear = StepSizeSchedule::LinearInTime {
+            initial: 1.0,
+            minimum: 0.0,
+            duration: stdtime::Duration::from_secs(10),
+        };
+        assert_eq!(linear.step_size(0, stdtime::Duration::from_secs(5)), 0.5);
+        assert_eq!(linear.step_size(0, stdtime::Duration::from_secs(20)), 0.0);
+    }
+}
@@ -0,0 +1,475 @@
+//! This module contains the [`SolveScheduler`] which interleaves several solver runs (e.g.,
+//! different instances or configurations) on a bounded number of threads.
+
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time as stdtime;
+
+use super::panic_guard::panic_payload_message;
+use super::{SearchResult, TerminationReason};
+use crate::objective::EvaluatedSolution;
+use crate::progress::{self, Verbosity};
+
+/// Type for a function that continues the search from the given solution for (roughly) the given
+/// time slice and returns the [`SearchResult`]. Usually, this builds a solver with the time
+/// slice as `time_limit` and calls
+/// [`solve_with_report`][super::super::Solver::solve_with_report].
+pub type SliceFunction<S> = Box<dyn Fn(S, stdtime::Duration) -> SearchResult<S> + Send + Sync>;
+
+/// The progress of a single run of the [`SolveScheduler`].
+#[derive(Clone)]
+pub struct RunProgress<S> {
+    /// The name of the run.
+    pub name: String,
+    /// The number of time slices that have been executed so far.
+    pub slices: u32,
+    /// The total time spent in this run so far.
+    pub time_spent: stdtime::Duration,
+    /// The best solution found so far (`None` before the first slice).
+    pub best_solution: Option<EvaluatedSolution<S>>,
+    /// The [`TerminationReason`] of the last slice (`None` before the first slice).
+    pub termination_reason: Option<TerminationReason>,
+    /// `true` if the run is finished (time budget exhausted, the solver terminated on its own or
+    /// the slice function panicked).
+    pub finished: bool,
+}
+
+struct ScheduledRun<S> {
+    progress_index: usize,
+    current_solution: S,
+    slice_function: SliceFunction<S>,
+    time_budget: stdtime::Duration,
+}
+
+/// Interleaves several solver runs on a bounded number of threads, such that every run gets its
+/// fair share of the computation time.
+/// * Each run consists of a name, an initial solution, a [`SliceFunction`] and a time budget.
+/// * The runs are executed in round-robin order, each for one `time_slice` at a time. After a
+///   slice, the run continues from the best solution of the slice and is put at the end of the
+///   queue.
+/// * At most `number_of_threads` slices are executed at the same time, each on its own thread
+///   (not in a rayon pool, such that the slice functions can use the parallel solvers).
+/// * A run is finished if its time budget is exhausted or if the solver of a slice terminated on
+///   its own, i.e., for any [`TerminationReason`] except [`TimeLimit`][TerminationReason::TimeLimit]
+///   (the end of the slice) and [`Unknown`][TerminationReason::Unknown]. A slice without
+///   improvement does not finish the run (e.g., simulated annealing or tabu search may need
+///   several slices to escape a local optimum).
+/// * If a slice function panics, the panic is caught and the run is finished with
+///   [`Panicked`][TerminationReason::Panicked], while the other runs continue.
+/// * After each slice the progress of all runs is printed as a table (unified progress view).
+pub struct SolveScheduler<S> {
+    runs: Vec<(String, S, SliceFunction<S>, stdtime::Duration)>,
+    number_of_threads: usize,
+    time_slice: stdtime::Duration,
+}
+
+impl<S: Clone + Send + Sync> SolveScheduler<S> {
+    /// Creates a new [`SolveScheduler`] that uses at most `number_of_threads` threads and
+    /// executes each run for `time_slice` before switching to the next run.
+    pub fn new(number_of_threads: usize, time_slice: stdtime::Duration) -> Self {
+        Self {
+            runs: Vec::new(),
+            number_of_threads: number_of_threads.max(1),
+            time_slice,
+        }
+    }
+
+    /// Adds a run with the given `name`, `initial_solution`, [`SliceFunction`] and total
+    /// `time_budget`.
+    pub fn add_run(
+        &mut self,
+        name: &str,
+        initial_solution: S,
+        slice_function: SliceFunction<S>,
+        time_budget: stdtime::Duration,
+    ) {
+        self.runs.push((
+            name.to_string(),
+            initial_solution,
+            slice_function,
+            time_budget,
+        ));
+    }
+
+    /// Executes all runs and returns the final [`RunProgress`] of each run (in the order the runs
+    /// were added).
+    pub fn run(self) -> Vec<RunProgress<S>> {
+        let mut progress: Vec<RunProgress<S>> = Vec::with_capacity(self.runs.len());
+        let mut queue: VecDeque<ScheduledRun<S>> = VecDeque::with_capacity(self.runs.len());
+        for (progress_index, (name, initial_solution, slice_function, time_budget)) in
+            self.runs.into_iter().enumerate()
+        {
+            progress.push(RunProgress {
+                name,
+                slices: 0,
+                time_spent: stdtime::Duration::ZERO,
+                best_solution: None,
+                termination_reason: None,
+                finished: false,
+            });
+            queue.push_back(ScheduledRun {
+                progress_index,
+                current_solution: initial_solution,
+                slice_function,
+                time_budget,
+            });
+        }
+
+        let time_slice = self.time_slice;
+        let state = Mutex::new((queue, progress, 0usize)); // (queue, progress, runs in progress)
+        let condvar = Condvar::new();
+
+        // plain threads instead of a rayon pool: a worker waiting on the condvar must not block a
+        // rayon thread that a slice function (e.g., of a parallel solver) relies on.
+        thread::scope(|s| {
+            for _ in 0..self.number_of_threads {
+                s.spawn(|| loop {
+                    let mut run = {
+                        let mut guard = state.lock().unwrap();
+                        while guard.0.is_empty() && guard.2 > 0 {
+                            guard = condvar.wait(guard).unwrap();
+                        }
+                        match guard.0.pop_front() {
+                            Some(run) => {
+                                guard.2 += 1;
+                                run
+                            }
+                            None => {
+                                condvar.notify_all();
+                                break;
+                            }
+                        }
+                    };
+
+                    let slice_start = stdtime::Instant::now();
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                        (run.slice_function)(run.current_solution.clone(), time_slice)
+                    }));
+                    let slice_time = slice_start.elapsed();
+
+                    let mut guard = state.lock().unwrap();
+                    let (queue, progress, in_progress) = &mut *guard;
+                    let run_progress = &mut progress[run.progress_index];
+                    run_progress.slices += 1;
+                    run_progress.time_spent += slice_time;
+                    match result {
+                        Ok(result) => {
+                            run_progress.termination_reason = Some(result.termination_reason);
+                            run.current_solution = result.best_solution.solution().clone();
+                            let improved = match &run_progress.best_solution {
+                                Some(best) => {
+                                    result.best_solution.objective_value() < best.objective_value()
+                                }
+                                None => true,
+                            };
+                            if improved {
+                                run_progress.best_solution = Some(result.best_solution);
+                            }
+                            let terminated = !matches!(
+                                result.termination_reason,
+                                TerminationReason::TimeLimit | TerminationReason::Unknown
+                            );
+                            if terminated || run_progress.time_spent >= run.time_budget {
+                                run_progress.finished = true;
+                            } else {
+                                queue.push_back(run);
+                            }
+                        }
+                        Err(payload) => {
+                            report!(
+                                Warning,
+                                "slice of run '{}' panicked ({}), finishing the run.",
+                                run_progress.name,
+                                panic_payload_message(payload.as_ref())
+                            );
+                            run_progress.termination_reason = Some(TerminationReason::Panicked);
+                            run_progress.finished = true;
+                        }
+                    }
+                    *in_progress -= 1;
+                    print_progress(progress);
+                    condvar.notify_all();
+                });
+            }
+        });
+
+        state.into_inner().unwrap().1
+    }
+}
+
+/// Reports the progress of all runs as a table.
+fn print_progress<S>(progress: &[RunProgress<S>]) {
+    if !progress::is_reported(Verbosity::Info) {
+        return;
+    }
+    let table = progress
+        .iter()
+        .map(|run_progress| {
+            format!(
+                " * {}: {} slices, {:0.2}sec, best: {}{}",
+                run_progress.name,
+                run_progress.slices,
+                run_progress.time_spent.as_secs_f32(),
+                match &run_progress.best_solution {
+                    Some(best) => best
+                        .objective_value()
+                        .iter()
+                        .map(|value| value.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" | "),
+                    None => "-".to_string(),
+                },
+                if run_progress.finished {
+                    " (finished)"
+                } else {
+                    ""
+                }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    report!(Info, "\nProgress of scheduled runs:\n{}", table);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::objective::Objective;
+    use crate::parallel::prelude::*;
+    use crate::test_fixtures::Value;
+
+    /// Returns the [`SearchResult`] of a slice that ended with the `solution` for the given
+    /// `reason`.
+    fn slice_result(
+        objective: &Objective<i64>,
+        solution: i64,
+        reason: TerminationReason,
+    ) -> SearchResult<i64> {
+        let mut result =
+            SearchResult::without_details(objective.evaluate(solution), stdtime::Duration::ZERO);
+        result.termination_reason = reason;
+        result
+    }
+
+    /// Decrements the `solution` by `decrement` (down to zero). The slice ends by its time limit
+    /// unless the solution is already zero (local optimum).
+    fn decrement_slice(
+        objective: &Objective<i64>,
+        solution: i64,
+        decrement: i64,
+    ) -> SearchResult<i64> {
+        if solution == 0 {
+            slice_result(objective, 0, TerminationReason::LocalOptimum)
+        } else {
+            slice_result(
+                objective,
+                (solution - decrement).max(0),
+                TerminationReason::TimeLimit,
+            )
+        }
+    }
+
+    /// A [`SliceFunction`] that decrements the solution by one (see [`decrement_slice`]) and
+    /// records the `name` of the run in the `log`.
+    fn decrement(name: &'static str, log: Arc<Mutex<Vec<&'static str>>>) -> SliceFunction<i64> {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        Box::new(move |solution, _| {
+            log.lock().unwrap().push(name);
+            decrement_slice(&objective, solution, 1)
+        })
+    }
+
+    #[test]
+    fn test_round_robin_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SolveScheduler::new(1, stdtime::Duration::from_millis(1));
+        scheduler.add_run("a", 2, decrement("a", log.clone()), stdtime::Duration::MAX);
+        scheduler.add_run("b", 1, decrement("b", log.clone()), stdtime::Duration::MAX);
+
+        let progress = scheduler.run();
+
+        // a: 2 -> 1 -> 0 -> 0 (local optimum), b: 1 -> 0 -> 0 (local optimum)
+        assert_eq!(*log.lock().unwrap(), vec!["a", "b", "a", "b", "a"]);
+        assert_eq!(progress[0].name, "a");
+        assert_eq!(progress[0].slices, 3);
+        assert_eq!(progress[1].name, "b");
+        assert_eq!(progress[1].slices, 2);
+        for run_progress in progress.iter() {
+            assert!(run_progress.finished);
+            assert_eq!(
+                run_progress.termination_reason,
+                Some(TerminationReason::LocalOptimum)
+            );
+            assert_eq!(*run_progress.best_solution.as_ref().unwrap().solution(), 0);
+        }
+    }
+
+    #[test]
+    fn test_time_budget_finishes_run() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SolveScheduler::new(1, stdtime::Duration::from_millis(1));
+        scheduler.add_run(
+            "a",
+            10,
+            decrement("a", log.clone()),
+            stdtime::Duration::ZERO,
+        );
+
+        let progress = scheduler.run();
+
+        assert_eq!(progress[0].slices, 1);
+        assert!(progress[0].finished);
+        assert_eq!(*progress[0].best_solution.as_ref().unwrap().solution(), 9);
+    }
+
+    #[test]
+    fn test_slice_without_improvement_does_not_finish_run() {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        let mut scheduler = SolveScheduler::new(1, stdtime::Duration::from_millis(1));
+        // the solution only improves in the third slice (e.g., after escaping a local optimum),
+        // the fourth slice reaches a local optimum
+        let slices = Arc::new(AtomicUsize::new(0));
+        let slices_clone = slices.clone();
+        scheduler.add_run(
+            "a",
+            5,
+            Box::new(
+                move |solution: i64, _| match slices_clone.fetch_add(1, Ordering::SeqCst) {
+                    2 => slice_result(&objective, solution - 1, TerminationReason::TimeLimit),
+                    3 => slice_result(&objective, solution, TerminationReason::LocalOptimum),
+                    _ => slice_result(&objective, solution, TerminationReason::TimeLimit),
+                },
+            ),
+            stdtime::Duration::MAX,
+        );
+
+        let progress = scheduler.run();
+
+        assert!(progress[0].finished);
+        assert_eq!(progress[0].slices, 4);
+        assert_eq!(slices.load(Ordering::SeqCst), 4);
+        assert_eq!(*progress[0].best_solution.as_ref().unwrap().solution(), 4);
+    }
+
+    #[test]
+    fn test_panicking_slice_finishes_only_its_run() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SolveScheduler::new(2, stdtime::Duration::from_millis(1));
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        // the slice function panics in the second slice (at solution 2)
+        scheduler.add_run(
+            "panicking",
+            3,
+            Box::new(move |solution: i64, _| {
+                if solution == 2 {
+                    panic!("slice exploded");
+                }
+                decrement_slice(&objective, solution, 1)
+            }),
+            stdtime::Duration::MAX,
+        );
+        scheduler.add_run("a", 3, decrement("a", log.clone()), stdtime::Duration::MAX);
+
+        let progress = scheduler.run();
+
+        assert!(progress[0].finished);
+        assert_eq!(progress[0].slices, 2);
+        assert_eq!(
+            progress[0].termination_reason,
+            Some(TerminationReason::Panicked)
+        );
+        assert_eq!(*progress[0].best_solution.as_ref().unwrap().solution(), 2);
+        assert!(progress[1].finished);
+        assert_eq!(progress[1].slices, 4);
+        assert_eq!(*progress[1].best_solution.as_ref().unwrap().solution(), 0);
+    }
+
+    #[test]
+    fn test_concurrency_limit() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let mut scheduler = SolveScheduler::new(2, stdtime::Duration::from_millis(1));
+        for name in ["a", "b", "c", "d", "e"] {
+            let objective = Objective::new_single_indicator(Box::new(Value));
+            let running = running.clone();
+            let max_running = max_running.clone();
+            scheduler.add_run(
+                name,
+                3,
+                Box::new(move |solution: i64, _| {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    std::thread::sleep(stdtime::Duration::from_millis(2));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    decrement_slice(&objective, solution, 1)
+                }),
+                stdtime::Duration::MAX,
+            );
+        }
+
+        let progress = scheduler.run();
+
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+        assert!(progress
+            .iter()
+            .all(|run_progress| run_progress.finished && run_progress.slices == 4));
+    }
+
+    #[test]
+    fn test_one_thread_with_nested_parallelism() {
+        let mut scheduler = SolveScheduler::new(1, stdtime::Duration::from_millis(1));
+        for name in ["a", "b", "c"] {
+            let objective = Objective::new_single_indicator(Box::new(Value));
+            scheduler.add_run(
+                name,
+                2,
+                Box::new(move |solution: i64, _| {
+                    let decrement: i64 = (0..4).into_par_iter().map(|_| 1).sum::<i64>() / 4;
+                    decrement_slice(&objective, solution, decrement)
+                }),
+                stdtime::Duration::MAX,
+            );
+        }
+
+        let progress = scheduler.run();
+
+        assert!(progress
+            .iter()
+            .all(|run_progress| run_progress.finished && run_progress.slices == 3));
+    }
+
+    #[test]
+    fn test_nested_parallelism_does_not_deadlock() {
+        let mut scheduler = SolveScheduler::new(4, stdtime::Duration::from_millis(1));
+        for run in 0..16 {
+            let objective = Objective::new_single_indicator(Box::new(Value));
+            scheduler.add_run(
+                &format!("run {}", run),
+                5,
+                Box::new(move |solution: i64, _| {
+                    let decrement: i64 = (0..64)
+                        .into_par_iter()
+                        .map(|_| {
+                            std::thread::yield_now();
+                            1
+                        })
+                        .sum::<i64>()
+                        / 64;
+                    decrement_slice(&objective, solution, decrement)
+                }),
+                stdtime::Duration::MAX,
+            );
+        }
+
+        let progress = scheduler.run();
+
+        assert!(progress
+            .iter()
+            .all(|run_progress| run_progress.finished && run_progress.slices == 6));
+    }
+}
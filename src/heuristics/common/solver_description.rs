@@ -0,0 +1,169 @@
+//! This module contains the [`SolverDescription`], which lists the configured parameters of a
+//! solver (see [`Solver::describe`][crate::heuristics::Solver::describe]).
+
+use std::fmt;
+use std::time as stdtime;
+
+use crate::objective::ObjectiveValue;
+
+/// The configured parameters of a solver and their values (as strings) in the order in which
+/// they were added.
+/// * Returned by [`Solver::describe`][crate::heuristics::Solver::describe], e.g., for printing
+///   the configuration at the start of a run or for storing it next to the results.
+/// * Unset optional parameters (e.g., no time limit) have the value `"none"`.
+/// * The [`Display`][fmt::Display] implementation prints the solver name followed by one
+///   parameter per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolverDescription {
+    solver: String,
+    parameters: Vec<(String, String)>,
+}
+
+impl SolverDescription {
+    /// Creates a new [`SolverDescription`] of the solver with the given name without
+    /// parameters.
+    pub fn new(solver: &str) -> SolverDescription {
+        SolverDescription {
+            solver: solver.to_string(),
+            parameters: Vec::new(),
+        }
+    }
+
+    /// Adds the parameter `name` with the given `value`.
+    pub fn with_parameter(mut self, name: &str, value: impl ToString) -> SolverDescription {
+        self.parameters.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds the optional parameter `name` with the given `value` (`"none"` if `None`).
+    pub fn with_optional_parameter(
+        self,
+        name: &str,
+        value: Option<impl ToString>,
+    ) -> SolverDescription {
+        let value = value.map_or(String::from("none"), |value| value.to_string());
+        self.with_parameter(name, value)
+    }
+
+    /// Adds the optional [`Duration`][stdtime::Duration] parameter `name` (e.g., a time limit).
+    pub fn with_duration(self, name: &str, value: Option<stdtime::Duration>) -> SolverDescription {
+        self.with_optional_parameter(name, value.map(|duration| format!("{:?}", duration)))
+    }
+
+    /// Adds the optional [`ObjectiveValue`] parameter `name` (e.g., a target objective value).
+    pub fn with_objective_value(
+        self,
+        name: &str,
+        value: Option<&ObjectiveValue>,
+    ) -> SolverDescription {
+        self.with_optional_parameter(
+            name,
+            value.map(|objective_value| {
+                let levels: Vec<String> = objective_value
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect();
+                format!("[{}]", levels.join(", "))
+            }),
+        )
+    }
+
+    /// Adds the `description` of an inner solver (e.g., of a multi-start solver): The name of the
+    /// inner solver becomes the parameter `prefix` and its parameters are added as
+    /// `prefix.name`.
+    pub fn with_inner_solver(
+        mut self,
+        prefix: &str,
+        description: SolverDescription,
+    ) -> SolverDescription {
+        self = self.with_parameter(prefix, description.solver);
+        for (name, value) in description.parameters {
+            self = self.with_parameter(&format!("{}.{}", prefix, name), value);
+        }
+        self
+    }
+
+    /// Returns the name of the solver.
+    pub fn solver(&self) -> &str {
+        &self.solver
+    }
+
+    /// Returns all parameters as name-value pairs.
+    pub fn parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+
+    /// Returns the value of the parameter `name` (or `None` if there is no such parameter).
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(parameter, _)| parameter == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for SolverDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.solver)?;
+        for (name, value) in self.parameters.iter() {
+            write!(f, "\n    {}: {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name of the type `T` without module path and generic parameters (e.g.,
+/// `"TabuMinimizer"`). Used as default name of solvers, improvers and schedules.
+pub fn short_type_name<T: ?Sized>() -> String {
+    let type_name = std::any::type_name::<T>();
+    let without_generics = type_name.split('<').next().unwrap_or(type_name);
+    without_generics
+        .rsplit("::")
+        .next()
+        .unwrap_or(without_generics)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::BaseValue;
+
+    #[test]
+    fn test_solver_description() {
+        let description = SolverDescription::new("TabuSearchSolver")
+            .with_parameter("tabu_list_size", 10)
+            .with_optional_parameter("iteration_limit", None::<u32>)
+            .with_duration("time_limit", Some(stdtime::Duration::from_secs(2)))
+            .with_objective_value(
+                "target_objective_value",
+                Some(&ObjectiveValue::new(vec![
+                    BaseValue::Integer(0),
+                    BaseValue::Float(7.5),
+                ])),
+            );
+
+        assert_eq!(description.solver(), "TabuSearchSolver");
+        assert_eq!(description.parameters().len(), 4);
+        assert_eq!(description.parameter("tabu_list_size"), Some("10"));
+        assert_eq!(description.parameter("iteration_limit"), Some("none"));
+        assert_eq!(description.parameter("tenure"), None);
+        assert_eq!(
+            description.to_string(),
+            "TabuSearchSolver\n    tabu_list_size: 10\n    iteration_limit: none\n    \
+             time_limit: 2s\n    target_objective_value: [0, 7.50]"
+        );
+
+        let multi_start = SolverDescription::new("MultiStartSolver")
+            .with_inner_solver("solver", description)
+            .with_parameter("number_of_starts", 4);
+        assert_eq!(multi_start.parameter("solver"), Some("TabuSearchSolver"));
+        assert_eq!(multi_start.parameter("solver.tabu_list_size"), Some("10"));
+        assert_eq!(multi_start.parameters().len(), 6);
+
+        assert_eq!(
+            short_type_name::<crate::heuristics::local_search::LocalSearchSolver<i64>>(),
+            "LocalSearchSolver"
+        );
+    }
+}
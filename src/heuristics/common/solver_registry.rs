@@ -0,0 +1,367 @@
+//! This module contains the [`SolverRegistry`], which constructs solvers by name at runtime (e.g.,
+//! from a config file or command line arguments).
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::SolverDescription;
+use crate::heuristics::Solver;
+
+/// Type for a function that builds a solver from the given [`SolverParameters`].
+pub type SolverBuilder<S> =
+    Box<dyn Fn(&SolverParameters) -> Result<Box<dyn Solver<S>>, RegistryError> + Send + Sync>;
+
+/// Describes a parameter of a registered solver.
+#[derive(Clone, Debug)]
+pub struct ParameterSchema {
+    /// The name of the parameter (e.g., `"tabu_list_size"`).
+    pub name: String,
+    /// A short description of the parameter.
+    pub description: String,
+    /// The default value (as string). If `None`, the parameter is required.
+    pub default_value: Option<String>,
+}
+
+impl ParameterSchema {
+    /// Creates a new required parameter.
+    pub fn required(name: &str, description: &str) -> ParameterSchema {
+        ParameterSchema {
+            name: name.to_string(),
+            description: description.to_string(),
+            default_value: None,
+        }
+    }
+
+    /// Creates a new optional parameter with the given default value.
+    pub fn optional(name: &str, description: &str, default_value: &str) -> ParameterSchema {
+        ParameterSchema {
+            name: name.to_string(),
+            description: description.to_string(),
+            default_value: Some(default_value.to_string()),
+        }
+    }
+}
+
+/// The parameters (as strings) that are passed to a [`SolverBuilder`]. Defaults of the
+/// [`ParameterSchema`] are already filled in.
+pub struct SolverParameters {
+    solver_name: String,
+    values: BTreeMap<String, String>,
+}
+
+impl SolverParameters {
+    /// Parses the parameter `name` into `T`.
+    /// Returns an error if the parameter is missing or cannot be parsed.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, RegistryError> {
+        let value = self
+            .values
+            .get(name)
+            .ok_or_else(|| RegistryError::MissingParameter {
+                solver: self.solver_name.clone(),
+                parameter: name.to_string(),
+            })?;
+        value.parse().map_err(|_| RegistryError::InvalidParameter {
+            solver: self.solver_name.clone(),
+            parameter: name.to_string(),
+            value: value.clone(),
+        })
+    }
+}
+
+/// Error that is returned by the [`SolverRegistry`] if a solver cannot be built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// There is no solver registered under this name.
+    UnknownSolver {
+        /// The requested name.
+        solver: String,
+        /// The names of all registered solvers.
+        available: Vec<String>,
+    },
+    /// The parameter is not in the [`ParameterSchema`] of the solver.
+    UnknownParameter {
+        /// The name of the solver.
+        solver: String,
+        /// The name of the parameter.
+        parameter: String,
+    },
+    /// The required parameter is missing.
+    MissingParameter {
+        /// The name of the solver.
+        solver: String,
+        /// The name of the parameter.
+        parameter: String,
+    },
+    /// The value of the parameter cannot be parsed.
+    InvalidParameter {
+        /// The name of the solver.
+        solver: String,
+        /// The name of the parameter.
+        parameter: String,
+        /// The given value.
+        value: String,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownSolver { solver, available } => write!(
+                f,
+                "Unknown solver '{}' (available: {}).",
+                solver,
+                available.join(", ")
+            ),
+            RegistryError::UnknownParameter { solver, parameter } => {
+                write!(
+                    f,
+                    "Unknown parameter '{}' for solver '{}'.",
+                    parameter, solver
+                )
+            }
+            RegistryError::MissingParameter { solver, parameter } => write!(
+                f,
+                "Missing required parameter '{}' for solver '{}'.",
+                parameter, solver
+            ),
+            RegistryError::InvalidParameter {
+                solver,
+                parameter,
+                value,
+            } => write!(
+                f,
+                "Invalid value '{}' of parameter '{}' for solver '{}'.",
+                value, parameter, solver
+            ),
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+struct RegisteredSolver<S> {
+    description: String,
+    parameter_schema: Vec<ParameterSchema>,
+    builder: SolverBuilder<S>,
+}
+
+/// A registry of [`SolverBuilders`][SolverBuilder] by name, such that solvers can be constructed
+/// by a string at runtime.
+/// * Each solver is registered with a description and a [`ParameterSchema`] for each of its
+///   parameters.
+/// * [`build`][SolverRegistry::build] checks the given parameters against the schema, fills in
+///   the defaults and calls the builder.
+/// * The solvers are listed in alphabetical order.
+pub struct SolverRegistry<S> {
+    solvers: BTreeMap<String, RegisteredSolver<S>>,
+}
+
+impl<S> Default for SolverRegistry<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> SolverRegistry<S> {
+    /// Creates an empty [`SolverRegistry`].
+    pub fn new() -> SolverRegistry<S> {
+        SolverRegistry {
+            solvers: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the `builder` under the `name`. An existing solver with the same name is
+    /// replaced.
+    pub fn register(
+        &mut self,
+        name: &str,
+        description: &str,
+        parameter_schema: Vec<ParameterSchema>,
+        builder: SolverBuilder<S>,
+    ) {
+        self.solvers.insert(
+            name.to_string(),
+            RegisteredSolver {
+                description: description.to_string(),
+                parameter_schema,
+                builder,
+            },
+        );
+    }
+
+    /// Builds the solver registered under `name` with the given `parameters` (name-value pairs).
+    pub fn build(
+        &self,
+        name: &str,
+        parameters: &[(&str, &str)],
+    ) -> Result<Box<dyn Solver<S>>, RegistryError> {
+        let registered_solver =
+            self.solvers
+                .get(name)
+                .ok_or_else(|| RegistryError::UnknownSolver {
+                    solver: name.to_string(),
+                    available: self.solver_names(),
+                })?;
+
+        let mut values: BTreeMap<String, String> = registered_solver
+            .parameter_schema
+            .iter()
+            .filter_map(|schema| {
+                schema
+                    .default_value
+                    .as_ref()
+                    .map(|default_value| (schema.name.clone(), default_value.clone()))
+            })
+            .collect();
+        for (parameter, value) in parameters {
+            if !registered_solver
+                .parameter_schema
+                .iter()
+                .any(|schema| schema.name == *parameter)
+            {
+                return Err(RegistryError::UnknownParameter {
+                    solver: name.to_string(),
+                    parameter: parameter.to_string(),
+                });
+            }
+            values.insert(parameter.to_string(), value.to_string());
+        }
+        if let Some(schema) = registered_solver
+            .parameter_schema
+            .iter()
+            .find(|schema| !values.contains_key(&schema.name))
+        {
+            return Err(RegistryError::MissingParameter {
+                solver: name.to_string(),
+                parameter: schema.name.clone(),
+            });
+        }
+
+        (registered_solver.builder)(&SolverParameters {
+            solver_name: name.to_string(),
+            values,
+        })
+    }
+
+    /// Builds the solver registered under `name` with the given `parameters` like
+    /// [`build`][SolverRegistry::build] and returns its [`SolverDescription`], i.e., the
+    /// configuration that a run with these parameters would use.
+    pub fn describe(
+        &self,
+        name: &str,
+        parameters: &[(&str, &str)],
+    ) -> Result<SolverDescription, RegistryError> {
+        Ok(self.build(name, parameters)?.describe())
+    }
+
+    /// Returns the names of all registered solvers.
+    pub fn solver_names(&self) -> Vec<String> {
+        self.solvers.keys().cloned().collect()
+    }
+
+    /// Returns the description of the solver registered under `name`.
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.solvers
+            .get(name)
+            .map(|registered_solver| registered_solver.description.as_str())
+    }
+
+    /// Returns the [`ParameterSchema`] of the solver registered under `name`.
+    pub fn parameter_schema(&self, name: &str) -> Option<&[ParameterSchema]> {
+        self.solvers
+            .get(name)
+            .map(|registered_solver| registered_solver.parameter_schema.as_slice())
+    }
+
+    /// Prints all registered solvers with their descriptions and parameters.
+    pub fn print_available_solvers(&self) {
+        for (name, registered_solver) in self.solvers.iter() {
+            println!("{}: {}", name, registered_solver.description);
+            for schema in registered_solver.parameter_schema.iter() {
+                match &schema.default_value {
+                    Some(default_value) => println!(
+                        "    {} (default: {}): {}",
+                        schema.name, default_value, schema.description
+                    ),
+                    None => println!("    {} (required): {}", schema.name, schema.description),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::heuristics::common::TerminationCriteria;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::objective::Objective;
+    use crate::test_fixtures::{DistanceTo, PlusMinusOne};
+
+    #[test]
+    fn test_solver_registry() {
+        let mut registry = SolverRegistry::new();
+        registry.register(
+            "local_search",
+            "Local search with +-1 steps.",
+            vec![ParameterSchema::optional(
+                "iteration_limit",
+                "The maximal number of iterations.",
+                "3",
+            )],
+            Box::new(|parameters| {
+                let objective =
+                    Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+                        DistanceTo(10),
+                    )]));
+                Ok(Box::new(LocalSearchSolver::with_options(
+                    Arc::new(PlusMinusOne),
+                    objective,
+                    None,
+                    Some(Box::new(|_, _, _, _, _, _, _, _| {})),
+                    Some(TerminationCriteria::iteration_limit(
+                        parameters.get("iteration_limit")?,
+                    )),
+                )))
+            }),
+        );
+
+        assert_eq!(registry.solver_names(), vec!["local_search".to_string()]);
+        assert_eq!(registry.parameter_schema("local_search").unwrap().len(), 1);
+
+        let solver = registry.build("local_search", &[]).unwrap();
+        assert_eq!(*solver.solve(0).solution(), 3);
+        let solver = registry
+            .build("local_search", &[("iteration_limit", "20")])
+            .unwrap();
+        assert_eq!(*solver.solve(0).solution(), 10);
+
+        let description = registry
+            .describe("local_search", &[("iteration_limit", "20")])
+            .unwrap();
+        assert_eq!(description.solver(), "LocalSearchSolver");
+        assert_eq!(
+            description.parameter("termination_criteria"),
+            Some(format!("{:?}", TerminationCriteria::iteration_limit(20)).as_str())
+        );
+        assert_eq!(description.parameter("local_improver"), Some("Minimizer"));
+        assert_eq!(description.parameter("iteration_timeout"), Some("none"));
+
+        assert!(matches!(
+            registry.build("tabu_search", &[]),
+            Err(RegistryError::UnknownSolver { .. })
+        ));
+        assert!(matches!(
+            registry.build("local_search", &[("tenure", "5")]),
+            Err(RegistryError::UnknownParameter { .. })
+        ));
+        assert!(matches!(
+            registry.build("local_search", &[("iteration_limit", "many")]),
+            Err(RegistryError::InvalidParameter { .. })
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+//! This module contains the [`TabuPolicy`], which allows different tenures for different classes
+//! of tabus in the [`TabuSearchSolver`][crate::heuristics::tabu_search::TabuSearchSolver] and the
+//! [`ParallelTabuSearchSolver`][crate::heuristics::parallel_tabu_search::ParallelTabuSearchSolver],
+//! and the [`ReactiveTenure`], which adapts the tabu list size during the search.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Type for a function that assigns a class to each tabu (e.g., `"arc"` or `"node"` for an enum
+/// with arc-based and node-based tabus).
+pub type TabuClassFunction<T> = Box<dyn Fn(&T) -> &'static str + Send + Sync>;
+
+/// Defines how many tabus of each class are kept in the tabu list.
+/// * The tabu type `T` can be an enum with several attribute classes (e.g., arc-based tabus with
+///   short tenure and node-based tabus with long tenure).
+/// * The `class_of` function assigns a class name to each tabu and `tenures` maps class names to
+///   the maximal number of tabus of this class in the tabu list.
+/// * If the tabu list contains more tabus of a class than its tenure, the oldest tabus of this
+///   class are removed.
+/// * Classes that are not in `tenures` get the tabu list size of the solver as tenure.
+/// * Without a [`TabuPolicy`], all tabus belong to the same class and the tabu list size is the
+///   tenure.
+pub struct TabuPolicy<T> {
+    class_of: TabuClassFunction<T>,
+    tenures: HashMap<&'static str, usize>,
+    default_tenure: usize,
+}
+
+impl<T> TabuPolicy<T> {
+    /// Creates a new [`TabuPolicy`] with the given function `class_of` that assigns a class to
+    /// each tabu and the `tenures` of the classes.
+    pub fn new(
+        class_of: TabuClassFunction<T>,
+        tenures: HashMap<&'static str, usize>,
+    ) -> TabuPolicy<T> {
+        TabuPolicy {
+            class_of,
+            tenures,
+            default_tenure: usize::MAX,
+        }
+    }
+
+    /// Creates a [`TabuPolicy`] where all tabus belong to the same class with the given tenure
+    /// (i.e., the tabu list size).
+    pub fn uniform(tabu_list_size: usize) -> TabuPolicy<T> {
+        TabuPolicy {
+            class_of: Box::new(|_| ""),
+            tenures: HashMap::new(),
+            default_tenure: tabu_list_size,
+        }
+    }
+
+    /// Sets the tenure of all classes that are not in `tenures`. This is done by the solver with
+    /// its tabu list size.
+    pub(crate) fn set_default_tenure(&mut self, default_tenure: usize) {
+        self.default_tenure = default_tenure;
+    }
+
+    /// Returns the tenure of the given class.
+    pub fn tenure(&self, class: &str) -> usize {
+        *self.tenures.get(class).unwrap_or(&self.default_tenure)
+    }
+
+    /// Returns the tenure of all classes that are not in `tenures`.
+    pub(crate) fn default_tenure(&self) -> usize {
+        self.default_tenure
+    }
+
+    /// Appends the `new_tabus` to the `tabu_list` and removes the oldest tabus of each class that
+    /// exceeds its tenure.
+    pub fn add_tabus(&self, tabu_list: &mut VecDeque<T>, new_tabus: Vec<T>) {
+        self.add_tabus_with_default_tenure(tabu_list, new_tabus, self.default_tenure);
+    }
+
+    /// Same as [`add_tabus`][TabuPolicy::add_tabus], but the classes without explicit tenure use
+    /// the given `default_tenure` (e.g., adapted by a [`ReactiveTenure`]).
+    pub(crate) fn add_tabus_with_default_tenure(
+        &self,
+        tabu_list: &mut VecDeque<T>,
+        new_tabus: Vec<T>,
+        default_tenure: usize,
+    ) {
+        tabu_list.extend(new_tabus);
+        if self.tenures.is_empty() {
+            while tabu_list.len() > default_tenure {
+                tabu_list.pop_front();
+            }
+            return;
+        }
+
+        // count the tabus of each class from the newest to the oldest
+        let mut counts: HashMap<&'static str, usize> = HashMap::new();
+        let mut keep: Vec<bool> = tabu_list
+            .iter()
+            .rev()
+            .map(|tabu| {
+                let class = (self.class_of)(tabu);
+                let count = counts.entry(class).or_insert(0);
+                *count += 1;
+                *count <= *self.tenures.get(class).unwrap_or(&default_tenure)
+            })
+            .collect();
+        keep.reverse();
+        let mut keep_iter = keep.into_iter();
+        tabu_list.retain(|_| keep_iter.next().unwrap());
+    }
+}
+
+/// Type for a function that maps a solution to a fingerprint (e.g., a hash), which is used to
+/// detect that a solution is visited again.
+pub type SolutionFingerprint<S> = Box<dyn Fn(&S) -> u64 + Send + Sync>;
+
+/// Adapts the tabu list size during the search as in the reactive tabu search by Battiti and
+/// Tecchiolli, such that the tabu list size does not need to be tuned per instance.
+/// * Whenever a solution is visited again (i.e., its fingerprint was seen before), the search
+///   cycles, so the tenure is increased by 10% (at least by one) up to `max_tenure`.
+/// * If no solution was visited again for `max_tenure` iterations since the last change, the
+///   tenure is decreased by 10% (at least by one) down to `min_tenure`.
+/// * The tabu list size of the solver is the initial tenure. Only classes without explicit tenure
+///   in the [`TabuPolicy`] are adapted.
+/// * The fingerprints of all visited solutions are stored (8 bytes each per iteration).
+pub struct ReactiveTenure<S> {
+    min_tenure: usize,
+    max_tenure: usize,
+    fingerprint: SolutionFingerprint<S>,
+}
+
+/// The state of a [`ReactiveTenure`] during a single run (the current tenure and the visited
+/// solutions), which is part of a [`TabuSearchCheckpoint`][super::TabuSearchCheckpoint] with the
+/// `checkpoint` feature.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "checkpoint", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReactiveTenureState {
+    tenure: usize,
+    visited: HashMap<u64, u32>,
+    last_change: u32,
+}
+
+impl<S> ReactiveTenure<S> {
+    /// Creates a new [`ReactiveTenure`] that keeps the tenure between `min_tenure` and
+    /// `max_tenure` and detects revisited solutions by their `fingerprint`.
+    pub fn new(
+        min_tenure: usize,
+        max_tenure: usize,
+        fingerprint: SolutionFingerprint<S>,
+    ) -> ReactiveTenure<S> {
+        ReactiveTenure {
+            min_tenure,
+            max_tenure,
+            fingerprint,
+        }
+    }
+
+    /// Starts a new run with the given `initial_tenure`.
+    pub(crate) fn start(&self, initial_tenure: usize) -> ReactiveTenureState {
+        ReactiveTenureState {
+            tenure: initial_tenure.clamp(self.min_tenure, self.max_tenure),
+            visited: HashMap::new(),
+            last_change: 0,
+        }
+    }
+
+    /// Records the `solution` visited in `iteration` and returns the adapted tenure.
+    pub(crate) fn adapt(
+        &self,
+        state: &mut ReactiveTenureState,
+        solution: &S,
+        iteration: u32,
+    ) -> usize {
+        let revisited = state
+            .visited
+            .insert((self.fingerprint)(solution), iteration)
+            .is_some();
+        if revisited {
+            state.tenure = (state.tenure + (state.tenure / 10).max(1)).min(self.max_tenure);
+            state.last_change = iteration;
+            report!(Debug, "Solution revisited, tabu tenure: {}", state.tenure);
+        } else if iteration.saturating_sub(state.last_change) as usize >= self.max_tenure {
+            state.tenure = state
+                .tenure
+                .saturating_sub((state.tenure / 10).max(1))
+                .max(self.min_tenure);
+            state.last_change = iteration;
+            report!(Debug, "No cycles detected, tabu tenure: {}", state.tenure);
+        }
+        state.tenure
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Tabu {
+        Arc(usize, usize),
+        Node(usize),
+    }
+
+    #[test]
+    fn test_tabu_policy() {
+        let policy = TabuPolicy::new(
+            Box::new(|tabu: &Tabu| match tabu {
+                Tabu::Arc(_, _) => "arc",
+                Tabu::Node(_) => "node",
+            }),
+            HashMap::from([("arc", 2), ("node", 3)]),
+        );
+        let mut tabu_list = VecDeque::new();
+        policy.add_tabus(&mut tabu_list, vec![Tabu::Arc(0, 1), Tabu::Node(0)]);
+        policy.add_tabus(&mut tabu_list, vec![Tabu::Arc(1, 2), Tabu::Node(1)]);
+        policy.add_tabus(&mut tabu_list, vec![Tabu::Arc(2, 3), Tabu::Node(2)]);
+
+        // the oldest arc is removed, all nodes are kept
+        assert_eq!(
+            tabu_list,
+            VecDeque::from([
+                Tabu::Node(0),
+                Tabu::Arc(1, 2),
+                Tabu::Node(1),
+                Tabu::Arc(2, 3),
+                Tabu::Node(2)
+            ])
+        );
+
+        let uniform = TabuPolicy::uniform(2);
+        uniform.add_tabus(&mut tabu_list, vec![Tabu::Node(3)]);
+        assert_eq!(tabu_list, VecDeque::from([Tabu::Node(2), Tabu::Node(3)]));
+    }
+
+    #[test]
+    fn test_reactive_tenure() {
+        let reactive_tenure =
+            ReactiveTenure::new(2, 4, Box::new(|solution: &i64| *solution as u64));
+        let mut state = reactive_tenure.start(3);
+        assert_eq!(reactive_tenure.adapt(&mut state, &0, 1), 3);
+        assert_eq!(reactive_tenure.adapt(&mut state, &1, 2), 3);
+        // revisiting 0 increases the tenure
+        assert_eq!(reactive_tenure.adapt(&mut state, &0, 3), 4);
+        assert_eq!(reactive_tenure.adapt(&mut state, &0, 4), 4);
+        // no revisits for max_tenure iterations decreases the tenure
+        assert_eq!(reactive_tenure.adapt(&mut state, &5, 7), 4);
+        assert_eq!(reactive_tenure.adapt(&mut state, &6, 8), 3);
+    }
+}
@@ -0,0 +1,513 @@
+//! This module contains the [`TerminationCriteria`], which are shared by the solvers.
+
+use std::time as stdtime;
+
+#[cfg(feature = "checkpoint")]
+use serde::{Deserialize, Serialize};
+
+use super::TerminationReason;
+#[cfg(feature = "checkpoint")]
+use crate::objective::Objective;
+use crate::objective::ObjectiveValue;
+
+/// A composable termination criterion of a solver, which is checked after each iteration with
+/// the iteration counter, the elapsed time and the objective value of the best solution seen.
+/// * The basic criteria are a [`time_limit`][TerminationCriteria::time_limit], an
+///   [`iteration_limit`][TerminationCriteria::iteration_limit], a limit on the
+///   [`iterations_without_improvement`][TerminationCriteria::iterations_without_improvement], a
+///   [`target_objective_value`][TerminationCriteria::target_objective_value] and
+///   [`stagnation`][TerminationCriteria::stagnation] (no improvement by more than an epsilon).
+/// * Criteria are combined by [`any_of`][TerminationCriteria::any_of] (or
+///   [`or`][TerminationCriteria::or]) and [`all_of`][TerminationCriteria::all_of] (or
+///   [`and`][TerminationCriteria::and]), e.g., "stop after 60 seconds, or if the target is reached
+///   and there was no improvement for 100 iterations".
+/// * The solvers accept the criteria as `termination_criteria` in their `with_options`.
+/// * The criteria keep track of the best objective value (for the improvement-based criteria).
+///   The solvers clone the criteria at the start of each run, so the same criteria can be used for
+///   several runs.
+#[derive(Clone, Debug)]
+pub struct TerminationCriteria {
+    criterion: Criterion,
+}
+
+#[derive(Clone, Debug)]
+enum Criterion {
+    TimeLimit(stdtime::Duration),
+    IterationLimit(u32),
+    IterationsWithoutImprovement {
+        limit: u32,
+        best_objective_value: Option<ObjectiveValue>,
+        iterations_without_improvement: u32,
+    },
+    TargetObjectiveValue(ObjectiveValue),
+    Stagnation {
+        iterations: u32,
+        epsilon: f64,
+        reference: Option<(ObjectiveValue, u32)>,
+    },
+    AnyOf(Vec<TerminationCriteria>),
+    AllOf(Vec<TerminationCriteria>),
+}
+
+impl TerminationCriteria {
+    /// Met as soon as more than `time_limit` has elapsed since the start of the search.
+    pub fn time_limit(time_limit: stdtime::Duration) -> TerminationCriteria {
+        Criterion::TimeLimit(time_limit).into()
+    }
+
+    /// Met as soon as the iteration counter reaches `iteration_limit`.
+    pub fn iteration_limit(iteration_limit: u32) -> TerminationCriteria {
+        Criterion::IterationLimit(iteration_limit).into()
+    }
+
+    /// Met as soon as the best objective value was not improved for `limit` consecutive checks
+    /// (i.e., iterations).
+    pub fn iterations_without_improvement(limit: u32) -> TerminationCriteria {
+        Criterion::IterationsWithoutImprovement {
+            limit,
+            best_objective_value: None,
+            iterations_without_improvement: 0,
+        }
+        .into()
+    }
+
+    /// Met as soon as the best objective value is at most the `target_objective_value` (e.g., a
+    /// known optimum).
+    pub fn target_objective_value(target_objective_value: ObjectiveValue) -> TerminationCriteria {
+        Criterion::TargetObjectiveValue(target_objective_value).into()
+    }
+
+    /// Met as soon as the best objective value has not improved by more than `epsilon` within
+    /// the last `iterations` iterations.
+    /// * The improvement is measured on the most important level in which the objective values
+    ///   differ (see [`BaseValue::to_f64`][crate::objective::BaseValue::to_f64]).
+    /// * With `epsilon = 0.0`, this is equivalent to
+    ///   [`iterations_without_improvement`][TerminationCriteria::iterations_without_improvement]
+    ///   (up to the counting of the iterations).
+    pub fn stagnation(iterations: u32, epsilon: f64) -> TerminationCriteria {
+        Criterion::Stagnation {
+            iterations,
+            epsilon,
+            reference: None,
+        }
+        .into()
+    }
+
+    /// Met as soon as any of the `criteria` is met. Without criteria, it is never met.
+    pub fn any_of(criteria: Vec<TerminationCriteria>) -> TerminationCriteria {
+        Criterion::AnyOf(criteria).into()
+    }
+
+    /// Met as soon as all of the `criteria` are met at the same time. Without criteria, it is
+    /// never met.
+    pub fn all_of(criteria: Vec<TerminationCriteria>) -> TerminationCriteria {
+        Criterion::AllOf(criteria).into()
+    }
+
+    /// Met as soon as `self` or `other` is met.
+    pub fn or(self, other: TerminationCriteria) -> TerminationCriteria {
+        TerminationCriteria::any_of(vec![self, other])
+    }
+
+    /// Met as soon as `self` and `other` are met.
+    pub fn and(self, other: TerminationCriteria) -> TerminationCriteria {
+        TerminationCriteria::all_of(vec![self, other])
+    }
+
+    /// Checks the criteria after the iteration `iteration` (time `elapsed` since the start of the
+    /// search), where `best_objective_value` is the objective value of the best solution seen.
+    /// Returns the [`TerminationReason`] if the criteria are met and `None` otherwise.
+    /// * Each check counts as an iteration for the improvement-based criteria, so this should be
+    ///   called exactly once per iteration.
+    /// * If several criteria are met, the reason of the first one (in the given order) is
+    ///   returned.
+    pub fn is_met(
+        &mut self,
+        iteration: u32,
+        elapsed: stdtime::Duration,
+        best_objective_value: &ObjectiveValue,
+    ) -> Option<TerminationReason> {
+        match &mut self.criterion {
+            Criterion::TimeLimit(time_limit) => {
+                (elapsed > *time_limit).then_some(TerminationReason::TimeLimit)
+            }
+            Criterion::IterationLimit(iteration_limit) => {
+                (iteration >= *iteration_limit).then_some(TerminationReason::IterationLimit)
+            }
+            Criterion::IterationsWithoutImprovement {
+                limit,
+                best_objective_value: best,
+                iterations_without_improvement,
+            } => {
+                match best {
+                    Some(best) if *best_objective_value >= *best => {
+                        *iterations_without_improvement += 1;
+                    }
+                    _ => {
+                        *best = Some(best_objective_value.clone());
+                        *iterations_without_improvement = 0;
+                    }
+                }
+                (*iterations_without_improvement >= *limit).then_some(TerminationReason::Converged)
+            }
+            Criterion::TargetObjectiveValue(target_objective_value) => (best_objective_value
+                <= target_objective_value)
+                .then_some(TerminationReason::TargetObjectiveValue),
+            Criterion::Stagnation {
+                iterations,
+                epsilon,
+                reference,
+            } => match reference {
+                Some((reference_value, reference_iteration))
+                    if improvement(reference_value, best_objective_value) <= *epsilon =>
+                {
+                    (iteration.saturating_sub(*reference_iteration) >= *iterations)
+                        .then_some(TerminationReason::Converged)
+                }
+                _ => {
+                    *reference = Some((best_objective_value.clone(), iteration));
+                    None
+                }
+            },
+            Criterion::AnyOf(criteria) => {
+                // every criterion is checked, such that the improvement-based ones are up to date
+                let reasons: Vec<Option<TerminationReason>> = criteria
+                    .iter_mut()
+                    .map(|criteria| criteria.is_met(iteration, elapsed, best_objective_value))
+                    .collect();
+                reasons.into_iter().flatten().next()
+            }
+            Criterion::AllOf(criteria) => {
+                let reasons: Vec<Option<TerminationReason>> = criteria
+                    .iter_mut()
+                    .map(|criteria| criteria.is_met(iteration, elapsed, best_objective_value))
+                    .collect();
+                if reasons.is_empty() {
+                    return None;
+                }
+                reasons
+                    .into_iter()
+                    .collect::<Option<Vec<TerminationReason>>>()
+                    .map(|reasons| reasons[0])
+            }
+        }
+    }
+
+    /// The time after which the criteria are met for sure (e.g., to pass the time limit to the
+    /// [`FunctionBetweenSteps`][super::FunctionBetweenSteps] or to the wind-down phase). `None` if
+    /// the criteria do not bound the time.
+    pub(crate) fn effective_time_limit(&self) -> Option<stdtime::Duration> {
+        self.effective_limit(&|criterion| match criterion {
+            Criterion::TimeLimit(time_limit) => Some(*time_limit),
+            _ => None,
+        })
+    }
+
+    /// The iteration at which the criteria are met for sure. `None` if the criteria do not bound
+    /// the number of iterations.
+    pub(crate) fn effective_iteration_limit(&self) -> Option<u32> {
+        self.effective_limit(&|criterion| match criterion {
+            Criterion::IterationLimit(iteration_limit) => Some(*iteration_limit),
+            _ => None,
+        })
+    }
+
+    /// Combines the limits of the basic criteria given by `limit_of` (the smallest one for
+    /// [`any_of`][TerminationCriteria::any_of], the largest one for
+    /// [`all_of`][TerminationCriteria::all_of] if all of them are bounded).
+    fn effective_limit<L: Ord + Copy>(
+        &self,
+        limit_of: &dyn Fn(&Criterion) -> Option<L>,
+    ) -> Option<L> {
+        match &self.criterion {
+            Criterion::AnyOf(criteria) => criteria
+                .iter()
+                .filter_map(|criteria| criteria.effective_limit(limit_of))
+                .min(),
+            Criterion::AllOf(criteria) if !criteria.is_empty() => criteria
+                .iter()
+                .map(|criteria| criteria.effective_limit(limit_of))
+                .collect::<Option<Vec<L>>>()
+                .and_then(|limits| limits.into_iter().max()),
+            criterion => limit_of(criterion),
+        }
+    }
+}
+
+/// The progress of the improvement-based criteria of [`TerminationCriteria`] (e.g., the number of
+/// iterations without improvement), such that a resumed run does not count from zero (see
+/// [`TabuSearchCheckpoint`][super::TabuSearchCheckpoint]).
+/// * Contains one entry per improvement-based criterion (in the order of the criteria).
+/// * The objective values are stored as JSON (see
+///   [`Objective::objective_value_to_json`]).
+///
+/// Requires the `checkpoint` feature.
+#[cfg(feature = "checkpoint")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TerminationProgress {
+    criteria: Vec<CriterionProgress>,
+}
+
+#[cfg(feature = "checkpoint")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum CriterionProgress {
+    IterationsWithoutImprovement {
+        best_objective_value: Option<serde_json::Value>,
+        iterations_without_improvement: u32,
+    },
+    Stagnation {
+        reference: Option<(serde_json::Value, u32)>,
+    },
+}
+
+#[cfg(feature = "checkpoint")]
+impl TerminationCriteria {
+    /// Returns the [`TerminationProgress`] of the improvement-based criteria, where the objective
+    /// values are converted by the `objective`.
+    pub(crate) fn progress<S>(&self, objective: &Objective<S>) -> TerminationProgress {
+        let mut progress = TerminationProgress::default();
+        self.collect_progress(objective, &mut progress.criteria);
+        progress
+    }
+
+    /// Restores the `progress` of a previous run (see [`progress`][TerminationCriteria::progress]).
+    /// * The entries are assigned to the improvement-based criteria in order. From the first
+    ///   entry that does not match its criterion (e.g., if the criteria were changed in between),
+    ///   the criteria start anew.
+    /// * An objective value that cannot be converted by the `objective` is treated as unknown.
+    pub(crate) fn restore_progress<S>(
+        &mut self,
+        progress: &TerminationProgress,
+        objective: &Objective<S>,
+    ) {
+        let value_from_json =
+            |json: &serde_json::Value| objective.objective_value_from_json(json).ok();
+        let mut criteria = Vec::new();
+        self.collect_improvement_based_criteria(&mut criteria);
+        for (criterion, criterion_progress) in criteria.into_iter().zip(&progress.criteria) {
+            match (criterion, criterion_progress) {
+                (
+                    Criterion::IterationsWithoutImprovement {
+                        best_objective_value,
+                        iterations_without_improvement,
+                        ..
+                    },
+                    CriterionProgress::IterationsWithoutImprovement {
+                        best_objective_value: stored_best_objective_value,
+                        iterations_without_improvement: stored_iterations_without_improvement,
+                    },
+                ) => {
+                    *best_objective_value = stored_best_objective_value
+                        .as_ref()
+                        .and_then(value_from_json);
+                    *iterations_without_improvement = *stored_iterations_without_improvement;
+                }
+                (
+                    Criterion::Stagnation { reference, .. },
+                    CriterionProgress::Stagnation {
+                        reference: stored_reference,
+                    },
+                ) => {
+                    *reference = stored_reference.as_ref().and_then(|(value, iteration)| {
+                        value_from_json(value).map(|value| (value, *iteration))
+                    });
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn collect_progress<S>(&self, objective: &Objective<S>, progress: &mut Vec<CriterionProgress>) {
+        match &self.criterion {
+            Criterion::IterationsWithoutImprovement {
+                best_objective_value,
+                iterations_without_improvement,
+                ..
+            } => progress.push(CriterionProgress::IterationsWithoutImprovement {
+                best_objective_value: best_objective_value
+                    .as_ref()
+                    .map(|value| objective.objective_value_to_json(value)),
+                iterations_without_improvement: *iterations_without_improvement,
+            }),
+            Criterion::Stagnation { reference, .. } => {
+                progress.push(CriterionProgress::Stagnation {
+                    reference: reference.as_ref().map(|(value, iteration)| {
+                        (objective.objective_value_to_json(value), *iteration)
+                    }),
+                })
+            }
+            Criterion::AnyOf(criteria) | Criterion::AllOf(criteria) => {
+                for criteria in criteria {
+                    criteria.collect_progress(objective, progress);
+                }
+            }
+            Criterion::TimeLimit(_)
+            | Criterion::IterationLimit(_)
+            | Criterion::TargetObjectiveValue(_) => {}
+        }
+    }
+
+    /// Collects the improvement-based criteria (in the order of the criteria).
+    fn collect_improvement_based_criteria<'a>(&'a mut self, criteria: &mut Vec<&'a mut Criterion>) {
+        match &mut self.criterion {
+            Criterion::AnyOf(sub_criteria) | Criterion::AllOf(sub_criteria) => {
+                for sub_criteria in sub_criteria.iter_mut() {
+                    sub_criteria.collect_improvement_based_criteria(criteria);
+                }
+            }
+            criterion => {
+                if matches!(
+                    criterion,
+                    Criterion::IterationsWithoutImprovement { .. } | Criterion::Stagnation { .. }
+                ) {
+                    criteria.push(criterion);
+                }
+            }
+        }
+    }
+}
+
+impl From<Criterion> for TerminationCriteria {
+    fn from(criterion: Criterion) -> TerminationCriteria {
+        TerminationCriteria { criterion }
+    }
+}
+
+/// Reports the [`TerminationReason`] of a run that was stopped by [`TerminationCriteria`].
+pub(crate) fn report_termination(reason: TerminationReason) {
+    match reason {
+        TerminationReason::TimeLimit => report!(Info, "Time limit reached."),
+        TerminationReason::IterationLimit => report!(Info, "Iteration limit reached."),
+        TerminationReason::TargetObjectiveValue => {
+            report!(Info, "Target objective value reached.")
+        }
+        TerminationReason::Converged => report!(Info, "Search stagnated."),
+        TerminationReason::IterationTimeout => {
+            report!(
+                Info,
+                "Iteration timeout reached without improving neighbor."
+            )
+        }
+        // a caught panic is already reported by the PanicGuard
+        TerminationReason::LocalOptimum
+        | TerminationReason::Panicked
+        | TerminationReason::Unknown => {}
+    }
+}
+
+/// Returns by how much `new` is better than `reference` on the most important level in which they
+/// differ (negative if `new` is worse and 0 if they are equal).
+fn improvement(reference: &ObjectiveValue, new: &ObjectiveValue) -> f64 {
+    reference
+        .iter()
+        .zip(new.iter())
+        .find(|(reference_value, new_value)| reference_value != new_value)
+        .map_or(0.0, |(reference_value, new_value)| {
+            reference_value.to_f64() - new_value.to_f64()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::BaseValue;
+
+    fn value(v: i64) -> ObjectiveValue {
+        ObjectiveValue::new(vec![BaseValue::Integer(v)])
+    }
+
+    #[test]
+    fn test_termination_criteria() {
+        let no_time = stdtime::Duration::ZERO;
+
+        let mut criteria = TerminationCriteria::iteration_limit(3)
+            .or(TerminationCriteria::target_objective_value(value(0)));
+        assert_eq!(criteria.is_met(1, no_time, &value(5)), None);
+        assert_eq!(
+            criteria.is_met(2, no_time, &value(0)),
+            Some(TerminationReason::TargetObjectiveValue)
+        );
+        assert_eq!(
+            criteria.is_met(3, no_time, &value(5)),
+            Some(TerminationReason::IterationLimit)
+        );
+
+        // the improvement 10 -> 9 is below the epsilon, but 10 -> 5 is not
+        let mut criteria = TerminationCriteria::stagnation(2, 1.5);
+        let values = [10, 9, 5, 5, 5];
+        let reasons: Vec<Option<TerminationReason>> = values
+            .iter()
+            .enumerate()
+            .map(|(iteration, &v)| criteria.is_met(iteration as u32, no_time, &value(v)))
+            .collect();
+        assert_eq!(reasons[..4], [None; 4]);
+        assert_eq!(reasons[4], Some(TerminationReason::Converged));
+
+        let mut criteria = TerminationCriteria::all_of(vec![
+            TerminationCriteria::iterations_without_improvement(2),
+            TerminationCriteria::time_limit(stdtime::Duration::from_secs(1)),
+        ]);
+        assert_eq!(criteria.is_met(1, no_time, &value(5)), None);
+        assert_eq!(criteria.is_met(2, no_time, &value(5)), None);
+        assert_eq!(criteria.is_met(3, no_time, &value(5)), None);
+        assert_eq!(
+            criteria.is_met(4, stdtime::Duration::from_secs(2), &value(5)),
+            Some(TerminationReason::Converged)
+        );
+    }
+
+    #[test]
+    fn test_effective_limits() {
+        let second = stdtime::Duration::from_secs(1);
+        let criteria = TerminationCriteria::time_limit(2 * second)
+            .or(TerminationCriteria::iteration_limit(10))
+            .or(TerminationCriteria::time_limit(second));
+        assert_eq!(criteria.effective_time_limit(), Some(second));
+        assert_eq!(criteria.effective_iteration_limit(), Some(10));
+
+        let criteria = TerminationCriteria::time_limit(second)
+            .and(TerminationCriteria::iterations_without_improvement(5));
+        assert_eq!(criteria.effective_time_limit(), None);
+        assert_eq!(
+            TerminationCriteria::all_of(vec![]).effective_iteration_limit(),
+            None
+        );
+    }
+
+    #[cfg(feature = "checkpoint")]
+    #[test]
+    fn test_restore_progress() {
+        let no_time = stdtime::Duration::ZERO;
+        let objective = Objective::new_single_indicator(Box::new(crate::test_fixtures::Value));
+        let new_criteria = |iteration_limit| {
+            TerminationCriteria::iteration_limit(iteration_limit)
+                .or(TerminationCriteria::iterations_without_improvement(3))
+                .or(TerminationCriteria::stagnation(4, 0.0))
+        };
+
+        let mut criteria = new_criteria(10);
+        for iteration in 1..=3 {
+            assert_eq!(criteria.is_met(iteration, no_time, &value(5)), None);
+        }
+        let progress: TerminationProgress =
+            serde_json::from_str(&serde_json::to_string(&criteria.progress(&objective)).unwrap())
+                .unwrap();
+
+        // the restored criteria (with another iteration limit) continue counting
+        let mut restored_criteria = new_criteria(100);
+        restored_criteria.restore_progress(&progress, &objective);
+        assert_eq!(
+            restored_criteria.is_met(4, no_time, &value(5)),
+            Some(TerminationReason::Converged)
+        );
+
+        // without the progress, the criteria start anew
+        let mut fresh_criteria = new_criteria(100);
+        assert_eq!(fresh_criteria.is_met(4, no_time, &value(5)), None);
+
+        // the progress is not assigned to criteria of another kind
+        let mut other_criteria = TerminationCriteria::stagnation(1, 0.0);
+        other_criteria.restore_progress(&progress, &objective);
+        assert_eq!(other_criteria.is_met(4, no_time, &value(5)), None);
+    }
+}
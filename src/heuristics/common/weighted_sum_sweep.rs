@@ -0,0 +1,327 @@
+//! This module contains the [`WeightedSumSweep`], which scalarizes several
+//! [`Indicators`][Indicator] by weighted sums and runs a [`Solver`] for each weight vector to
+//! approximate the [`ParetoFront`].
+
+use std::sync::Arc;
+
+use crate::heuristics::Solver;
+use crate::objective::{
+    BaseValue, Coefficient, Indicator, LinearCombination, Objective, ObjectiveValue, Unit,
+};
+
+/// Shares an [`Indicator`] between the objectives of a [`WeightedSumSweep`].
+struct SharedIndicator<S>(Arc<dyn Indicator<S>>);
+
+impl<S> Indicator<S> for SharedIndicator<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        self.0.evaluate(solution)
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.0.evaluate_exactly(solution)
+    }
+
+    fn name(&self) -> String {
+        self.0.name()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.0.unit()
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.0.bounds()
+    }
+}
+
+/// A solution of the [`ParetoFront`] together with the weights of the run that found it.
+#[derive(Clone, Debug)]
+pub struct ParetoPoint<S> {
+    /// The weights of the scalarized objective of the run that found the solution (empty if the
+    /// solution was not found by a weighted sum, e.g., by the
+    /// [`Nsga2Solver`][crate::heuristics::nsga2::Nsga2Solver]).
+    pub weights: Vec<f64>,
+    /// The solution.
+    pub solution: S,
+    /// The values of the indicators of the solution (in the order of the indicators).
+    pub indicator_values: Vec<BaseValue>,
+}
+
+/// The non-dominated solutions w.r.t. several minimized indicators, e.g., collected by a
+/// [`WeightedSumSweep`].
+/// * A point dominates another point if it is at least as good in every indicator and strictly
+///   better in at least one.
+/// * Points with the same indicator values as a point of the front are not added.
+pub struct ParetoFront<S> {
+    points: Vec<ParetoPoint<S>>,
+}
+
+impl<S> ParetoFront<S> {
+    /// Creates an empty [`ParetoFront`].
+    pub fn new() -> ParetoFront<S> {
+        ParetoFront { points: Vec::new() }
+    }
+
+    /// Inserts the `point` if it is not dominated by (or equal to) a point of the front and
+    /// removes the points that are dominated by it. Returns `true` if the point was inserted.
+    pub fn insert(&mut self, point: ParetoPoint<S>) -> bool {
+        let point_value = ObjectiveValue::new(point.indicator_values.clone());
+        let indicators: Vec<usize> = (0..point.indicator_values.len()).collect();
+        if self.points.iter().any(|other| {
+            other.indicator_values == point.indicator_values
+                || ObjectiveValue::new(other.indicator_values.clone())
+                    .dominates(&point_value, &indicators)
+        }) {
+            return false;
+        }
+        self.points.retain(|other| {
+            !point_value.dominates(
+                &ObjectiveValue::new(other.indicator_values.clone()),
+                &indicators,
+            )
+        });
+        self.points.push(point);
+        true
+    }
+
+    /// Returns the points of the front (in the order they were inserted).
+    pub fn points(&self) -> &[ParetoPoint<S>] {
+        &self.points
+    }
+
+    /// Returns the number of points of the front.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns `true` if the front has no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Consumes the front and returns its points.
+    pub fn into_points(self) -> Vec<ParetoPoint<S>> {
+        self.points
+    }
+}
+
+impl<S> Default for ParetoFront<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Weighted-sum scalarization of several (minimized) [`Indicators`][Indicator] for basic
+/// multi-objective studies.
+/// * [`scalarized_objective`][WeightedSumSweep::scalarized_objective] builds a single-level
+///   [`Objective`] that minimizes the weighted sum of the indicators.
+/// * [`run`][WeightedSumSweep::run] solves the problem for each weight vector (e.g., from
+///   [`uniform_weights`][WeightedSumSweep::uniform_weights]) and collects the non-dominated
+///   solutions in a [`ParetoFront`].
+/// * Weighted sums only find solutions on the convex hull of the Pareto front, so the front is an
+///   approximation. Indicators of different magnitude should be normalized by the weights.
+pub struct WeightedSumSweep<S> {
+    indicators: Vec<Arc<dyn Indicator<S>>>,
+}
+
+impl<S: 'static> WeightedSumSweep<S> {
+    /// Creates a new [`WeightedSumSweep`] for the given indicators.
+    /// * Panics if no indicator is given.
+    pub fn new(indicators: Vec<Arc<dyn Indicator<S>>>) -> WeightedSumSweep<S> {
+        assert!(
+            !indicators.is_empty(),
+            "At least one indicator must be given."
+        );
+        WeightedSumSweep { indicators }
+    }
+
+    /// Returns the single-level [`Objective`] that minimizes the sum of the indicators
+    /// multiplied with the `weights` (one weight per indicator, zero weights are omitted).
+    /// * Panics if the number of weights differs from the number of indicators or all weights
+    ///   are zero.
+    pub fn scalarized_objective(&self, weights: &[f64]) -> Objective<S> {
+        assert_eq!(
+            weights.len(),
+            self.indicators.len(),
+            "{} weights are given for {} indicators.",
+            weights.len(),
+            self.indicators.len()
+        );
+        assert!(
+            weights.iter().any(|weight| *weight != 0.0),
+            "At least one weight must be non-zero."
+        );
+        Objective::new_single_level(LinearCombination::new(
+            weights
+                .iter()
+                .zip(self.indicators.iter())
+                .filter(|(weight, _)| **weight != 0.0)
+                .map(|(weight, indicator)| {
+                    (
+                        Coefficient::from(*weight),
+                        Box::new(SharedIndicator(indicator.clone())) as Box<dyn Indicator<S>>,
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// Returns all weight vectors whose weights are multiples of `1 / steps` and sum up to 1
+    /// (e.g., `[0, 1]`, `[0.5, 0.5]` and `[1, 0]` for two indicators and two steps).
+    /// * Panics if `steps` is zero.
+    pub fn uniform_weights(&self, steps: usize) -> Vec<Vec<f64>> {
+        assert!(steps > 0, "The number of steps must be at least 1.");
+        let mut weight_vectors = Vec::new();
+        let mut current = Vec::with_capacity(self.indicators.len());
+        collect_compositions(
+            self.indicators.len(),
+            steps,
+            &mut current,
+            &mut weight_vectors,
+        );
+        weight_vectors
+            .into_iter()
+            .map(|composition| {
+                composition
+                    .into_iter()
+                    .map(|part| part as f64 / steps as f64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Evaluates the indicators for the `solution` (in the order of the indicators).
+    pub fn indicator_values(&self, solution: &S) -> Vec<BaseValue> {
+        self.indicators
+            .iter()
+            .map(|indicator| indicator.evaluate(solution))
+            .collect()
+    }
+
+    /// Runs a solver for each of the `weight_vectors` and returns the approximated
+    /// [`ParetoFront`] of the final solutions.
+    /// * `build_solver` builds the solver for the scalarized objective of a weight vector.
+    /// * Each run starts from a clone of the `initial_solution`.
+    pub fn run(
+        &self,
+        weight_vectors: &[Vec<f64>],
+        build_solver: impl Fn(Arc<Objective<S>>) -> Box<dyn Solver<S>>,
+        initial_solution: S,
+    ) -> ParetoFront<S>
+    where
+        S: Clone,
+    {
+        let mut pareto_front = ParetoFront::new();
+        for weights in weight_vectors {
+            let solver = build_solver(Arc::new(self.scalarized_objective(weights)));
+            let solution = solver.solve(initial_solution.clone()).unwrap();
+            let indicator_values = self.indicator_values(&solution);
+            report!(
+                Info,
+                "Weights {:?} finished with indicator values {:?}.",
+                weights,
+                indicator_values
+            );
+            pareto_front.insert(ParetoPoint {
+                weights: weights.clone(),
+                solution,
+                indicator_values,
+            });
+        }
+        pareto_front
+    }
+}
+
+/// Collects all compositions of `total` into `parts` non-negative integers.
+fn collect_compositions(
+    parts: usize,
+    total: usize,
+    current: &mut Vec<usize>,
+    compositions: &mut Vec<Vec<usize>>,
+) {
+    if parts == 1 {
+        current.push(total);
+        compositions.push(current.clone());
+        current.pop();
+        return;
+    }
+    for part in 0..=total {
+        current.push(part);
+        collect_compositions(parts - 1, total - part, current, compositions);
+        current.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::silent_function_between_steps;
+    use crate::heuristics::local_search::LocalSearchSolver;
+    use crate::test_fixtures::PlusMinusOne;
+
+    struct SquaredDistanceTo(i64);
+
+    impl Indicator<i64> for SquaredDistanceTo {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer((solution - self.0) * (solution - self.0))
+        }
+        fn name(&self) -> String {
+            format!("SquaredDistanceTo{}", self.0)
+        }
+    }
+
+    #[test]
+    fn test_weighted_sum_sweep() {
+        let sweep = WeightedSumSweep::new(vec![
+            Arc::new(SquaredDistanceTo(0)),
+            Arc::new(SquaredDistanceTo(10)),
+        ]);
+        let weight_vectors = sweep.uniform_weights(2);
+        assert_eq!(
+            weight_vectors,
+            vec![vec![0.0, 1.0], vec![0.5, 0.5], vec![1.0, 0.0]]
+        );
+
+        let pareto_front = sweep.run(
+            &weight_vectors,
+            |objective| {
+                Box::new(LocalSearchSolver::with_options(
+                    Arc::new(PlusMinusOne),
+                    objective,
+                    None,
+                    Some(silent_function_between_steps()),
+                    None,
+                ))
+            },
+            3,
+        );
+
+        assert_eq!(
+            pareto_front
+                .points()
+                .iter()
+                .map(|point| point.solution)
+                .collect::<Vec<_>>(),
+            vec![10, 5, 0]
+        );
+        assert_eq!(
+            pareto_front.points()[1].indicator_values,
+            vec![BaseValue::Integer(25), BaseValue::Integer(25)]
+        );
+
+        // a dominated point is rejected, a dominating point replaces the dominated points
+        // (the front is named through its public re-export, which must not depend on rand)
+        let mut pareto_front: crate::heuristics::common::ParetoFront<i64> = pareto_front;
+        assert!(!pareto_front.insert(ParetoPoint {
+            weights: vec![],
+            solution: 11,
+            indicator_values: sweep.indicator_values(&11),
+        }));
+        assert!(pareto_front.insert(ParetoPoint {
+            weights: vec![],
+            solution: -1,
+            indicator_values: vec![BaseValue::Integer(0), BaseValue::Integer(0)],
+        }));
+        assert_eq!(pareto_front.len(), 1);
+    }
+}
@@ -0,0 +1,59 @@
+//! This module contains the wind-down phase, which is executed by the metaheuristics after their
+//! `soft_time_limit` is reached.
+
+use std::time as stdtime;
+
+use super::{NeighborCounts, SearchRecorder, TerminationReason};
+use crate::objective::EvaluatedSolution;
+
+/// Pure intensification: Replaces `solution` by the result of `improve` (usually a
+/// [`TakeFirst`][crate::heuristics::local_search::local_improver::TakeFirst] descent) until a local
+/// optimum is reached or the `hard_time_limit` (measured from `start_time`) is exceeded.
+/// * `improve` gets the current solution and the deadline given by the hard time limit (if any),
+///   at which the neighborhood exploration should be stopped. Besides the improved solution, it
+///   returns the [`NeighborCounts`] of the step (if counted).
+/// * Each step is passed to the `recorder`, where the iteration counter continues after the
+///   `iterations` performed so far.
+/// * Returns the last solution, the number of iterations (including the previous ones) and
+///   whether the search stopped in a local optimum or at the hard time limit.
+/// * As the neighborhood exploration is stopped at the hard time limit, the returned solution is
+///   only guaranteed to be a local optimum if the hard time limit is not reached.
+pub(crate) fn wind_down<S>(
+    recorder: &mut SearchRecorder<S>,
+    solution: EvaluatedSolution<S>,
+    mut improve: impl FnMut(
+        &EvaluatedSolution<S>,
+        Option<stdtime::Instant>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>),
+    mut iterations: u32,
+    start_time: stdtime::Instant,
+    hard_time_limit: Option<stdtime::Duration>,
+) -> (EvaluatedSolution<S>, u32, TerminationReason) {
+    report!(Info, "Soft time limit reached. Winding down.");
+    let deadline = hard_time_limit.map(|time_limit| start_time + time_limit);
+    let deadline_reached = || deadline.is_some_and(|deadline| stdtime::Instant::now() >= deadline);
+    let mut current_solution = solution;
+    while let (Some(new_solution), neighbor_counts) = improve(&current_solution, deadline) {
+        iterations += 1;
+        recorder.step(
+            iterations,
+            &new_solution,
+            Some(&current_solution),
+            neighbor_counts,
+        );
+        current_solution = new_solution;
+        if deadline_reached() {
+            break;
+        }
+    }
+    if deadline_reached() {
+        report!(Info, "Time limit reached.");
+        (current_solution, iterations, TerminationReason::TimeLimit)
+    } else {
+        (
+            current_solution,
+            iterations,
+            TerminationReason::LocalOptimum,
+        )
+    }
+}
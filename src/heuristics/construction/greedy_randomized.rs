@@ -0,0 +1,107 @@
+//! This module contains the [`greedy_randomized`] construction with an alpha-based restricted
+//! candidate list (RCL).
+use rand::Rng;
+
+/// Builds a sequence of all `elements` by a greedy randomized construction (as used in GRASP).
+/// * In each step, each remaining element is scored by `score_fn(partial_sequence, element)`,
+///   where `partial_sequence` contains the elements chosen so far. Lower scores are better.
+/// * The restricted candidate list (RCL) consists of all remaining elements with a score of at
+///   most `min + alpha * (max - min)`. One of them is chosen uniformly at random and appended to
+///   the sequence.
+/// * `alpha = 0.0` is the pure greedy construction (ties are broken by the order of `elements`),
+///   `alpha = 1.0` is a random permutation.
+/// * Non-finite scores (`NaN` or infinite) are never in the RCL. If all remaining elements have a
+///   non-finite score, the RCL consists of all of them.
+/// * The order of the remaining elements is preserved, so for a seeded `rng` (e.g.,
+///   [`StdRng::seed_from_u64`][rand::SeedableRng::seed_from_u64]) the result is deterministic.
+pub fn greedy_randomized<E, F, R>(elements: Vec<E>, score_fn: F, alpha: f64, rng: &mut R) -> Vec<E>
+where
+    F: Fn(&[E], &E) -> f64,
+    R: Rng,
+{
+    assert!(
+        (0.0..=1.0).contains(&alpha),
+        "alpha must be in [0, 1], but is {}",
+        alpha
+    );
+    let mut remaining = elements;
+    let mut sequence = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let scores: Vec<f64> = remaining
+            .iter()
+            .map(|element| score_fn(&sequence, element))
+            .collect();
+        let finite_scores = || scores.iter().cloned().filter(|score| score.is_finite());
+        let min = finite_scores().fold(f64::INFINITY, f64::min);
+        let max = finite_scores().fold(f64::NEG_INFINITY, f64::max);
+        let threshold = min + alpha * (max - min);
+
+        let mut restricted_candidate_list: Vec<usize> = scores
+            .iter()
+            .enumerate()
+            .filter(|(_, &score)| score.is_finite() && score <= threshold)
+            .map(|(idx, _)| idx)
+            .collect();
+        if restricted_candidate_list.is_empty() {
+            restricted_candidate_list = (0..remaining.len()).collect();
+        }
+
+        let chosen = restricted_candidate_list[rng.gen_range(0..restricted_candidate_list.len())];
+        sequence.push(remaining.remove(chosen));
+    }
+    sequence
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_greedy_randomized_is_greedy_for_alpha_zero() {
+        let sequence = greedy_randomized(
+            vec![3, 1, 2],
+            |_, &element| element as f64,
+            0.0,
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        assert_eq!(sequence, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_greedy_randomized_skips_non_finite_scores() {
+        let mut sequence = greedy_randomized(
+            vec![0, 1, 2, 3],
+            |_, &element| match element {
+                0 => f64::NAN,
+                1 => f64::NEG_INFINITY,
+                other => other as f64,
+            },
+            0.0,
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        assert_eq!(sequence[..2], [2, 3]);
+        sequence.sort();
+        assert_eq!(sequence, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_greedy_randomized_with_only_nan_scores() {
+        for alpha in [0.0, 0.5, 1.0] {
+            let mut sequence = greedy_randomized(
+                vec![0, 1, 2, 3],
+                |_, _| f64::NAN,
+                alpha,
+                &mut StdRng::seed_from_u64(7),
+            );
+
+            sequence.sort();
+            assert_eq!(sequence, vec![0, 1, 2, 3]);
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! This module contains construction heuristics that build an initial solution from scratch,
+//! e.g., to start a local search from or to generate diverse starting points for multi-start
+//! methods like GRASP.
+//! * [`greedy_randomized`] builds a sequence of elements by repeatedly choosing a random element
+//!   of the restricted candidate list (RCL).
+
+mod greedy_randomized;
+pub use greedy_randomized::greedy_randomized;
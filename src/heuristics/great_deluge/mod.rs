@@ -0,0 +1,394 @@
+//! This module contains the [`GreatDelugeSolver`] implementing the
+//! [great deluge algorithm](https://doi.org/10.1006/jcph.1993.1010) with hierarchy-aware water
+//! levels.
+//! * Starts with an initial solution and iteratively considers neighbors.
+//! * A neighbor is accepted if it is better than the current solution or if its objective value
+//!   is at most the current water level.
+//! * The water level is an [`ObjectiveValue`], i.e., there is one water level per hierarchy
+//!   level. After each iteration, the water levels are lowered by the rain speed, but only from
+//!   the first level of the current solution that is not zero onwards. The more important levels
+//!   are already zero (e.g., satisfied hard constraints) and their water level is kept at zero,
+//!   such that they are never relaxed again. No water level drops below zero.
+//! * The search stops after a certain number of iterations, after a certain time limit, if the
+//!   target objective value is reached, or if the whole neighborhood is explored without any
+//!   acceptance.
+//! * The best solution seen during this process is returned.
+//! * The water levels of all iterations can be obtained per hierarchy level from the
+//!   [`GreatDelugeStatistics`] (see [`solve_with_statistics`][GreatDelugeSolver::solve_with_statistics]).
+//! * Similar to the [threshold accepting heuristic][super::threshold_accepting], but the
+//!   neighbors are compared to an absolute water level instead of the current solution.
+use std::sync::Arc;
+use std::time as stdtime;
+
+use super::common::{
+    default_function_between_steps, report_termination, FunctionBetweenSteps, NeighborCounts,
+    Neighborhood, SearchRecorder, SearchResult, SolverDescription, TerminationCriteria,
+    TerminationReason,
+};
+use super::Solver;
+use crate::objective::{BaseValue, EvaluatedSolution, Objective, ObjectiveValue};
+
+/// The water levels of a run of the [`GreatDelugeSolver`] (see
+/// [`solve_with_statistics`][GreatDelugeSolver::solve_with_statistics]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct GreatDelugeStatistics {
+    /// The water level after each iteration (the first entry is the initial water level).
+    pub water_levels: Vec<ObjectiveValue>,
+}
+
+impl GreatDelugeStatistics {
+    /// Returns the water level of the hierarchy level with index `level` over the iterations
+    /// (the first entry is the initial water level).
+    /// Panics if the level does not exist.
+    pub fn level_trajectory(&self, level: usize) -> Vec<BaseValue> {
+        self.water_levels
+            .iter()
+            .map(|water_level| water_level.as_vec()[level])
+            .collect()
+    }
+}
+
+/// The great deluge solver uses a [`Neighborhood`], an [`Objective`] and a `rain_speed`
+/// ([`ObjectiveValue`]) to find a good solution, while accepting worse solutions as long as they
+/// are below the water level.
+/// * The initial water level is the objective value of the initial solution, unless an
+///   `initial_water_level` is given.
+/// * After each iteration, the water level is lowered by the `rain_speed` on all hierarchy levels
+///   from the first non-zero level of the current solution onwards, the more important levels are
+///   set to zero (see the [module documentation][super::great_deluge]).
+/// * The `function_between_steps` is executed after each step.
+/// * The default `function_between_steps` (if `None`) is printing the iteration number, the
+///   objective value (in comparison the the previous objective value) and the time elapsed since
+///   the start.
+/// * The solver stops if the [`TerminationCriteria`] (e.g., a number of iterations or a time
+///   limit) are met.
+/// * Without [`TerminationCriteria`], the solver runs until a whole neighborhood is explored
+///   without any acceptance.
+pub struct GreatDelugeSolver<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    objective: Arc<Objective<S>>,
+    rain_speed: ObjectiveValue,
+    initial_water_level: Option<ObjectiveValue>,
+    function_between_steps: FunctionBetweenSteps<S>,
+    termination_criteria: Option<TerminationCriteria>,
+}
+
+impl<S> GreatDelugeSolver<S> {
+    /// Creates a new [`GreatDelugeSolver`] with the given [`Neighborhood`], [`Objective`],
+    /// `rain_speed` (the decrease of the water level per iteration on each hierarchy level) and
+    /// `iteration_limit`.
+    pub fn initialize(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        rain_speed: ObjectiveValue,
+        iteration_limit: u32,
+    ) -> Self {
+        Self::with_options(
+            neighborhood,
+            objective,
+            rain_speed,
+            None,
+            None,
+            Some(TerminationCriteria::iteration_limit(iteration_limit)),
+        )
+    }
+
+    /// Creates a new [`GreatDelugeSolver`] with the given [`Neighborhood`], [`Objective`] and
+    /// `rain_speed` (the decrease of the water level per iteration on each hierarchy level).
+    /// * `initial_water_level` is the water level at the start. If `None`, the objective value of
+    ///   the initial solution is used.
+    /// * `function_between_steps` is executed after each step. If `None`, the default is printing
+    ///   the iteration number, the objective value (in comparison the the previous objective
+    ///   value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit, an iteration
+    ///   limit or a target objective value, are checked after each iteration with the best
+    ///   solution seen. The last iteration is allowed to finish.
+    /// * If `termination_criteria` is `None`, the solver runs until a whole neighborhood is
+    ///   explored without any acceptance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        rain_speed: ObjectiveValue,
+        initial_water_level: Option<ObjectiveValue>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> Self {
+        Self {
+            neighborhood,
+            objective,
+            rain_speed,
+            initial_water_level,
+            function_between_steps: function_between_steps
+                .unwrap_or(default_function_between_steps()),
+            termination_criteria,
+        }
+    }
+}
+
+impl<S: Clone> GreatDelugeSolver<S> {
+    /// Solves the problem like [`solve_with_report`][Solver::solve_with_report] and additionally
+    /// returns the water levels of all iterations.
+    pub fn solve_with_statistics(
+        &self,
+        initial_solution: S,
+    ) -> (SearchResult<S>, GreatDelugeStatistics) {
+        let start_time = stdtime::Instant::now();
+        let mut current_solution = self.objective.evaluate(initial_solution);
+        let mut best_solution_seen = current_solution.clone();
+        let mut water_level = self
+            .initial_water_level
+            .clone()
+            .unwrap_or_else(|| current_solution.objective_value().clone());
+        let mut water_levels = vec![water_level.clone()];
+        let mut termination_criteria = self.termination_criteria.clone();
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_time_limit),
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_iteration_limit),
+            current_solution.objective_value(),
+            true,
+        );
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        let mut iteration_counter = 0;
+
+        let mut neighbor_counts = NeighborCounts::default();
+        loop {
+            let Some(new_solution) =
+                self.explore_neighborhood(&current_solution, &water_level, &mut neighbor_counts)
+            else {
+                break;
+            };
+            iteration_counter += 1;
+            recorder.step(
+                iteration_counter,
+                &new_solution,
+                Some(&current_solution),
+                Some(neighbor_counts),
+            );
+            current_solution = new_solution;
+            if self
+                .objective
+                .is_better(&current_solution, &best_solution_seen)
+            {
+                best_solution_seen = current_solution.clone();
+            }
+            water_level = self.lower_water_level(&water_level, current_solution.objective_value());
+            water_levels.push(water_level.clone());
+
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
+            }
+        }
+
+        (
+            recorder.finish(best_solution_seen, iteration_counter, termination_reason),
+            GreatDelugeStatistics { water_levels },
+        )
+    }
+}
+
+impl<S: Clone> Solver<S> for GreatDelugeSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        self.solve_with_statistics(initial_solution).0
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("GreatDelugeSolver")
+            .with_objective_value("rain_speed", Some(&self.rain_speed))
+            .with_objective_value("initial_water_level", self.initial_water_level.as_ref())
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+    }
+}
+
+impl<S> GreatDelugeSolver<S> {
+    /// Returns the first neighbor that is better than the current solution or below the
+    /// `water_level` and overwrites the `neighbor_counts` with the counts of this exploration.
+    fn explore_neighborhood(
+        &self,
+        current_solution: &EvaluatedSolution<S>,
+        water_level: &ObjectiveValue,
+        neighbor_counts: &mut NeighborCounts,
+    ) -> Option<EvaluatedSolution<S>> {
+        let mut evaluated = 0;
+        let accepted_neighbor = self
+            .neighborhood
+            .neighbors_of(current_solution.solution())
+            .map(|neighbor| {
+                evaluated += 1;
+                self.objective.evaluate(neighbor)
+            })
+            .find(|neighbor| {
+                self.objective.is_better(neighbor, current_solution)
+                    || neighbor.objective_value() <= water_level
+            });
+        let accepted = u64::from(accepted_neighbor.is_some());
+        *neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            rejected_by_threshold: evaluated - accepted,
+            accepted,
+            ..NeighborCounts::default()
+        };
+        accepted_neighbor
+    }
+
+    /// Lowers the `water_level` by the rain speed from the first non-zero level of the
+    /// `current_objective_value` onwards and sets the more important levels to zero.
+    fn lower_water_level(
+        &self,
+        water_level: &ObjectiveValue,
+        current_objective_value: &ObjectiveValue,
+    ) -> ObjectiveValue {
+        let first_non_zero_level = current_objective_value
+            .iter()
+            .position(|value| !value.is_zero())
+            .unwrap_or(current_objective_value.as_vec().len());
+        ObjectiveValue::new(
+            water_level
+                .iter()
+                .zip(self.rain_speed.iter())
+                .enumerate()
+                .map(|(level, (&value, &rain_speed))| {
+                    if level < first_non_zero_level || value <= rain_speed {
+                        BaseValue::Zero
+                    } else {
+                        value - rain_speed
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::silent_function_between_steps;
+    use crate::objective::Indicator;
+
+    // the first level counts the negative entries, the second level is the distance of the sum to
+    // 100
+    type Solution = Vec<i64>;
+
+    struct NegativeEntries;
+
+    impl Indicator<Solution> for NegativeEntries {
+        fn evaluate(&self, solution: &Solution) -> BaseValue {
+            BaseValue::Integer(solution.iter().filter(|&&entry| entry < 0).count() as i64)
+        }
+        fn name(&self) -> String {
+            String::from("NegativeEntries")
+        }
+    }
+
+    struct DistanceToHundred;
+
+    impl Indicator<Solution> for DistanceToHundred {
+        fn evaluate(&self, solution: &Solution) -> BaseValue {
+            BaseValue::Integer((solution.iter().sum::<i64>() - 100).abs())
+        }
+        fn name(&self) -> String {
+            String::from("DistanceToHundred")
+        }
+    }
+
+    struct AddOrSubtract;
+
+    impl Neighborhood<Solution> for AddOrSubtract {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a Solution,
+        ) -> Box<dyn Iterator<Item = Solution> + Send + Sync + 'a> {
+            Box::new((0..solution.len()).flat_map(move |index| {
+                [-10, 10].into_iter().map(move |step| {
+                    let mut neighbor = solution.clone();
+                    neighbor[index] += step;
+                    neighbor
+                })
+            }))
+        }
+    }
+
+    #[test]
+    fn test_great_deluge() {
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![
+            Box::new(NegativeEntries),
+            Box::new(DistanceToHundred),
+        ]));
+        let solver = GreatDelugeSolver::with_options(
+            Arc::new(AddOrSubtract),
+            objective,
+            ObjectiveValue::new(vec![BaseValue::Integer(1), BaseValue::Integer(5)]),
+            None,
+            Some(silent_function_between_steps()),
+            Some(TerminationCriteria::iteration_limit(100)),
+        );
+
+        let (result, statistics) = solver.solve_with_statistics(vec![-20, -10, 0]);
+
+        assert_eq!(
+            result.best_solution.objective_value().as_vec(),
+            &vec![BaseValue::Zero, BaseValue::Zero]
+        );
+        assert_eq!(
+            statistics.water_levels.len() as u32,
+            result.iterations.unwrap() + 1
+        );
+        let first_level = statistics.level_trajectory(0);
+        let second_level = statistics.level_trajectory(1);
+        assert_eq!(first_level[0], BaseValue::Integer(2));
+        assert_eq!(second_level[0], BaseValue::Integer(130));
+        // as soon as there is no negative entry, the first water level is zero and stays there
+        let first_feasible_iteration = result
+            .trajectory
+            .iter()
+            .position(|point| point.objective_value.first_level_is_zero())
+            .unwrap();
+        assert!(first_level[first_feasible_iteration..]
+            .iter()
+            .all(|value| *value == BaseValue::Zero));
+        // the water levels never increase
+        assert!(second_level.windows(2).all(|w| w[1] <= w[0]));
+        // each iteration accepts one neighbor, the others are rejected by the water level
+        for point in result.trajectory.iter().skip(1) {
+            let counts = point.neighbor_counts.unwrap();
+            assert_eq!(counts.accepted, 1);
+            assert_eq!(counts.rejected_by_threshold, counts.evaluated - 1);
+        }
+        assert_eq!(
+            result.evaluated_neighbors(),
+            Some(
+                result
+                    .trajectory
+                    .iter()
+                    .filter_map(|point| point.neighbor_counts)
+                    .map(|counts| counts.evaluated)
+                    .sum()
+            )
+        );
+    }
+}
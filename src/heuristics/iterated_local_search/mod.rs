@@ -0,0 +1,313 @@
+//! This module contains the [`IteratedLocalSearchSolver`] implementing the
+//! [iterated local search (ILS)](https://en.wikipedia.org/wiki/Iterated_local_search)
+//! metaheuristic.
+//! * Starts with an initial solution and improves it to a local optimum with a
+//!   [`LocalImprover`] (as the [`LocalSearchSolver`][super::local_search::LocalSearchSolver]).
+//! * Then it iteratively perturbs the current local optimum with a user-defined [`Perturbation`]
+//!   and improves the perturbed solution to a new local optimum.
+//! * The new local optimum replaces the current solution if it is accepted by the
+//!   [`IlsAcceptance`] criterion (better, restart, or simulated-annealing-like).
+//! * Generic perturbations for sequences and assignments (random swaps, segment reversals and
+//!   reassignments) are provided by the [`perturbation`] module.
+//! * The search stops after a certain number of iterations or after a certain time limit.
+//! * The best local optimum seen is returned.
+//!
+//! For an example, see the [iterated local search solver for the
+//! TSP][crate::examples::tsp::solvers::iterated_local_search].
+pub mod perturbation;
+
+use std::sync::Arc;
+use std::time as stdtime;
+
+use rand::{Rng, RngCore};
+
+use super::common::default_rng_factory;
+use super::common::RngFactory;
+use super::common::SolverDescription;
+use super::common::{
+    default_function_between_steps, report_termination, FunctionBetweenSteps, Neighborhood,
+    SearchRecorder, SearchResult, TerminationCriteria,
+};
+use super::local_search::local_improver::{LocalImprover, Minimizer};
+use super::local_search::local_search_loop;
+use super::simulated_annealing::{AcceptanceProbabilityFunction, ScalingFactor, Temperature};
+use super::Solver;
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// Perturbs a local optimum (e.g., by applying a few random moves) such that the subsequent local
+/// search can escape from it.
+/// * The random number generator of the solver is provided to make the search reproducible.
+pub trait Perturbation<S>: Send + Sync {
+    /// Returns a perturbed copy of the `solution`.
+    fn perturb(&self, solution: &S, rng: &mut dyn RngCore) -> S;
+}
+
+/// The acceptance criterion of the [`IteratedLocalSearchSolver`], which decides whether a new
+/// local optimum replaces the current solution.
+pub enum IlsAcceptance {
+    /// The new local optimum is only accepted if it is better than the current solution.
+    Better,
+    /// As [`Better`][IlsAcceptance::Better], but if the best solution was not improved for
+    /// `iterations_without_improvement` iterations, the new local optimum is accepted regardless
+    /// of its objective value (i.e., the search restarts from there).
+    Restart {
+        /// The number of iterations without improvement after which the search restarts.
+        iterations_without_improvement: u32,
+    },
+    /// A better local optimum is always accepted, a worse one is accepted with the probability
+    /// given by the [`AcceptanceProbabilityFunction`] (as in the [simulated
+    /// annealing][super::simulated_annealing]). Whenever a worse local optimum is accepted, the
+    /// temperature is multiplied by the `cooling_factor`.
+    SimulatedAnnealingLike {
+        /// The temperature in the beginning (in the magnitude of the objective values).
+        initial_temperature: Temperature,
+        /// The factor (between 0 and 1) by which the temperature is reduced.
+        cooling_factor: ScalingFactor,
+        /// Computes the acceptance probability from the current objective value, the objective
+        /// value of the new local optimum and the current temperature.
+        acceptance_probability_function: AcceptanceProbabilityFunction,
+    },
+}
+
+/// An iterated local search solver that alternates between a [`LocalImprover`] (improving to a
+/// local optimum) and a [`Perturbation`] (escaping from the local optimum).
+/// * The deafult [`LocalImprover`] (if `None`) is [`Minimizer`].
+/// * The default [`IlsAcceptance`] (if `None`) is [`IlsAcceptance::Better`].
+/// * The `function_between_steps` is executed after each accepted local optimum.
+/// * The default `function_between_steps` (if `None`) is printing the iteration number, the
+///   objective value (in comparison the the previous objective value) and the time elapsed since
+///   the start.
+/// * The search stops if the [`TerminationCriteria`] (e.g., a time limit or a maximal number of
+///   iterations) are met. They must be set.
+/// * The best local optimum seen is returned.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the trajectory of the accepted local optima. The neighbors of the
+///   local searches are not counted.
+///
+/// For a high-level overview, see the [module documentation][super::iterated_local_search] and
+/// for an example, see the [iterated local search solver for the
+/// TSP][crate::examples::tsp::solvers::iterated_local_search].
+pub struct IteratedLocalSearchSolver<S> {
+    objective: Arc<Objective<S>>,
+    local_improver: Box<dyn LocalImprover<S>>,
+    perturbation: Box<dyn Perturbation<S>>,
+    acceptance: IlsAcceptance,
+    random_seed: Option<u64>,
+    rng_factory: RngFactory,
+    function_between_steps: FunctionBetweenSteps<S>,
+    termination_criteria: Option<TerminationCriteria>,
+}
+
+impl<S: 'static> IteratedLocalSearchSolver<S> {
+    /// Creates a new [`IteratedLocalSearchSolver`] with the given [`Neighborhood`],
+    /// [`Perturbation`], [`Objective`] and the maximal number of iterations (i.e., perturbations).
+    /// Uses the default [`LocalImprover`] ([`Minimizer`]) and [`IlsAcceptance::Better`].
+    /// * A `random_seed` can be provided to make the search reproducible.
+    pub fn initialize(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        perturbation: Box<dyn Perturbation<S>>,
+        objective: Arc<Objective<S>>,
+        iteration_limit: u32,
+        random_seed: Option<u64>,
+    ) -> Self {
+        Self::with_options(
+            neighborhood,
+            perturbation,
+            objective,
+            None,
+            None,
+            random_seed,
+            None,
+            Some(TerminationCriteria::iteration_limit(iteration_limit)),
+        )
+    }
+
+    /// Creates a new [`IteratedLocalSearchSolver`] with the given [`Neighborhood`],
+    /// [`Perturbation`] and [`Objective`].
+    /// * `local_improver` (implementing [`LocalImprover`]) specifies the how the neighborhood is
+    ///   explored. If `None`, the default is [`Minimizer`].
+    /// * `acceptance` decides whether a new local optimum replaces the current solution. If
+    ///   `None`, the default is [`IlsAcceptance::Better`].
+    /// * `random_seed` can be provided to make the search reproducible.
+    /// * `function_between_steps` is executed after each accepted local optimum. If `None`, the
+    ///   default is printing the iteration number, the objective value (in comparison the the
+    ///   previous objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit or a maximum
+    ///   number of perturbations, are checked after each iteration with the best local optimum
+    ///   seen. The last iteration is allowed to finish. They must be set, as the search does not
+    ///   stop by itself.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        perturbation: Box<dyn Perturbation<S>>,
+        objective: Arc<Objective<S>>,
+        local_improver: Option<Box<dyn LocalImprover<S>>>,
+        acceptance: Option<IlsAcceptance>,
+        random_seed: Option<u64>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> Self {
+        if termination_criteria.is_none() {
+            panic!("The `termination_criteria` must be set.");
+        }
+        let local_improver = match local_improver {
+            Some(local_improver) => local_improver,
+            None => Box::new(Minimizer::new(neighborhood, objective.clone()))
+                as Box<dyn LocalImprover<S>>,
+        };
+        Self {
+            objective,
+            local_improver,
+            perturbation,
+            acceptance: acceptance.unwrap_or(IlsAcceptance::Better),
+            random_seed,
+            rng_factory: default_rng_factory(),
+            function_between_steps: function_between_steps
+                .unwrap_or(default_function_between_steps()),
+            termination_criteria,
+        }
+    }
+
+    /// Replaces the [`RngFactory`] (default: [`StdRng`][rand::rngs::StdRng], see
+    /// [`default_rng_factory`]), which creates the random number generator of each run from the
+    /// `random_seed`. The generator is passed to the [`Perturbation`].
+    pub fn with_rng_factory(mut self, rng_factory: RngFactory) -> Self {
+        self.rng_factory = rng_factory;
+        self
+    }
+}
+
+impl<S: Clone> IteratedLocalSearchSolver<S> {
+    /// Improves the `solution` to a local optimum.
+    fn local_optimum(&self, solution: S) -> EvaluatedSolution<S> {
+        local_search_loop(
+            &self.objective,
+            solution,
+            |current_solution| self.local_improver.improve(current_solution),
+            |_, _, _| true,
+        )
+    }
+}
+
+impl<S: Clone> Solver<S> for IteratedLocalSearchSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        let start_time = stdtime::Instant::now();
+        let mut rng = (self.rng_factory)(self.random_seed);
+
+        let mut current_solution = self.local_optimum(initial_solution);
+        let mut best_solution_seen = current_solution.clone();
+        let mut iterations_without_improvement = 0;
+        let mut current_temperature = match self.acceptance {
+            IlsAcceptance::SimulatedAnnealingLike {
+                initial_temperature,
+                ..
+            } => initial_temperature,
+            _ => 0.0,
+        };
+
+        let mut termination_criteria = self.termination_criteria.clone();
+
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_time_limit),
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_iteration_limit),
+            current_solution.objective_value(),
+            true,
+        );
+        let mut iteration_counter = 1;
+        let termination_reason = loop {
+            let perturbed_solution = self
+                .perturbation
+                .perturb(current_solution.solution(), &mut rng);
+            let new_local_optimum = self.local_optimum(perturbed_solution);
+
+            if self
+                .objective
+                .is_better(&new_local_optimum, &best_solution_seen)
+            {
+                best_solution_seen = new_local_optimum.clone();
+                iterations_without_improvement = 0;
+            } else {
+                iterations_without_improvement += 1;
+            }
+
+            let accepted = new_local_optimum.objective_value() < current_solution.objective_value()
+                || match &self.acceptance {
+                    IlsAcceptance::Better => false,
+                    IlsAcceptance::Restart {
+                        iterations_without_improvement: restart_after,
+                    } => {
+                        if iterations_without_improvement >= *restart_after {
+                            iterations_without_improvement = 0;
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                    IlsAcceptance::SimulatedAnnealingLike {
+                        cooling_factor,
+                        acceptance_probability_function,
+                        ..
+                    } => {
+                        let acceptance_probability = acceptance_probability_function(
+                            current_solution.objective_value(),
+                            new_local_optimum.objective_value(),
+                            current_temperature,
+                        );
+                        if rng.gen::<f64>() < acceptance_probability {
+                            current_temperature *= cooling_factor;
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                };
+
+            if accepted {
+                recorder.step(
+                    iteration_counter,
+                    &new_local_optimum,
+                    Some(&current_solution),
+                    None,
+                );
+                current_solution = new_local_optimum;
+            }
+
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                break reason;
+            }
+            iteration_counter += 1;
+        };
+
+        recorder.finish(best_solution_seen, iteration_counter, termination_reason)
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("IteratedLocalSearchSolver")
+            .with_parameter("local_improver", self.local_improver.name())
+            .with_optional_parameter("random_seed", self.random_seed)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+    }
+}
@@ -0,0 +1,261 @@
+//! This module contains generic [`Perturbations`][Perturbation] that do not depend on the
+//! problem, only on the shape of the solution:
+//! * [`RandomSwaps`] and [`RandomSegmentReversals`] for permutations and other sequences (see
+//!   [`SequenceSolution`]).
+//! * [`RandomReassignments`] for solutions that assign each item to one of several options (see
+//!   [`AssignmentSolution`]).
+//!
+//! All operators draw from the random number generator of the solver, such that the perturbations
+//! are reproducible with the `random_seed` of the solver.
+use rand::{Rng, RngCore};
+
+use super::Perturbation;
+
+/// A solution that is a sequence of positions (e.g., a permutation or a tour), whose entries can
+/// be swapped and reversed.
+/// * Implemented for [`Vec`]. For other solution types, only
+///   [`number_of_positions`][SequenceSolution::number_of_positions] and
+///   [`swap_positions`][SequenceSolution::swap_positions] are required.
+pub trait SequenceSolution: Clone + Send + Sync {
+    /// Returns the number of positions of the sequence.
+    fn number_of_positions(&self) -> usize;
+
+    /// Swaps the entries at the positions `i` and `j`.
+    fn swap_positions(&mut self, i: usize, j: usize);
+
+    /// Reverses the entries from position `start` to position `end` (both inclusive).
+    fn reverse_segment(&mut self, start: usize, end: usize) {
+        let (mut i, mut j) = (start, end);
+        while i < j {
+            self.swap_positions(i, j);
+            i += 1;
+            j -= 1;
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync> SequenceSolution for Vec<T> {
+    fn number_of_positions(&self) -> usize {
+        self.len()
+    }
+
+    fn swap_positions(&mut self, i: usize, j: usize) {
+        self.swap(i, j);
+    }
+
+    fn reverse_segment(&mut self, start: usize, end: usize) {
+        self[start..=end].reverse();
+    }
+}
+
+/// A solution that assigns each item to one of several options (e.g., jobs to machines), where
+/// the options are given by their index.
+/// * Implemented for `Vec<usize>`, where the entry `i` is the option of item `i`.
+pub trait AssignmentSolution: Clone + Send + Sync {
+    /// Returns the number of items.
+    fn number_of_items(&self) -> usize;
+
+    /// Returns the option the `item` is currently assigned to.
+    fn option_of(&self, item: usize) -> usize;
+
+    /// Assigns the `item` to the `option`.
+    fn assign(&mut self, item: usize, option: usize);
+}
+
+impl AssignmentSolution for Vec<usize> {
+    fn number_of_items(&self) -> usize {
+        self.len()
+    }
+
+    fn option_of(&self, item: usize) -> usize {
+        self[item]
+    }
+
+    fn assign(&mut self, item: usize, option: usize) {
+        self[item] = option;
+    }
+}
+
+/// Perturbs a [`SequenceSolution`] by swapping the entries of `number_of_swaps` random pairs of
+/// distinct positions.
+/// * Sequences with less than two positions are returned unchanged.
+pub struct RandomSwaps {
+    number_of_swaps: usize,
+}
+
+impl RandomSwaps {
+    /// Creates a new [`RandomSwaps`] perturbation with `number_of_swaps` swaps.
+    pub fn new(number_of_swaps: usize) -> RandomSwaps {
+        RandomSwaps { number_of_swaps }
+    }
+}
+
+impl<S: SequenceSolution> Perturbation<S> for RandomSwaps {
+    fn perturb(&self, solution: &S, rng: &mut dyn RngCore) -> S {
+        let mut perturbed = solution.clone();
+        let number_of_positions = perturbed.number_of_positions();
+        if number_of_positions < 2 {
+            return perturbed;
+        }
+        for _ in 0..self.number_of_swaps {
+            let pair = rand::seq::index::sample(rng, number_of_positions, 2);
+            perturbed.swap_positions(pair.index(0), pair.index(1));
+        }
+        perturbed
+    }
+}
+
+/// Perturbs a [`SequenceSolution`] by reversing `number_of_reversals` random segments (e.g., a
+/// random 2-opt move of a tour).
+/// * Each segment consists of at least two positions and at most `maximal_segment_length`
+///   positions (at least 2, by default the whole sequence).
+/// * Sequences with less than two positions are returned unchanged.
+pub struct RandomSegmentReversals {
+    number_of_reversals: usize,
+    maximal_segment_length: Option<usize>,
+}
+
+impl RandomSegmentReversals {
+    /// Creates a new [`RandomSegmentReversals`] perturbation with `number_of_reversals` reversals
+    /// of segments of any length.
+    pub fn new(number_of_reversals: usize) -> RandomSegmentReversals {
+        RandomSegmentReversals {
+            number_of_reversals,
+            maximal_segment_length: None,
+        }
+    }
+
+    /// Limits the reversed segments to at most `maximal_segment_length` positions (at least 2).
+    pub fn with_maximal_segment_length(mut self, maximal_segment_length: usize) -> Self {
+        self.maximal_segment_length = Some(maximal_segment_length.max(2));
+        self
+    }
+}
+
+impl<S: SequenceSolution> Perturbation<S> for RandomSegmentReversals {
+    fn perturb(&self, solution: &S, rng: &mut dyn RngCore) -> S {
+        let mut perturbed = solution.clone();
+        let number_of_positions = perturbed.number_of_positions();
+        if number_of_positions < 2 {
+            return perturbed;
+        }
+        let maximal_segment_length = self
+            .maximal_segment_length
+            .unwrap_or(number_of_positions)
+            .min(number_of_positions);
+        for _ in 0..self.number_of_reversals {
+            let segment_length = rng.gen_range(2..=maximal_segment_length);
+            let start = rng.gen_range(0..=number_of_positions - segment_length);
+            perturbed.reverse_segment(start, start + segment_length - 1);
+        }
+        perturbed
+    }
+}
+
+/// Perturbs an [`AssignmentSolution`] by assigning `number_of_reassignments` random items to a
+/// random other option (out of `number_of_options` options).
+/// * The items are distinct (if there are less items than reassignments, every item is
+///   reassigned).
+/// * With less than two options, the solution is returned unchanged.
+pub struct RandomReassignments {
+    number_of_reassignments: usize,
+    number_of_options: usize,
+}
+
+impl RandomReassignments {
+    /// Creates a new [`RandomReassignments`] perturbation that reassigns
+    /// `number_of_reassignments` items to one of the other of the `number_of_options` options.
+    pub fn new(number_of_reassignments: usize, number_of_options: usize) -> RandomReassignments {
+        RandomReassignments {
+            number_of_reassignments,
+            number_of_options,
+        }
+    }
+}
+
+impl<S: AssignmentSolution> Perturbation<S> for RandomReassignments {
+    fn perturb(&self, solution: &S, rng: &mut dyn RngCore) -> S {
+        let mut perturbed = solution.clone();
+        if self.number_of_options < 2 {
+            return perturbed;
+        }
+        let number_of_items = perturbed.number_of_items();
+        let items = rand::seq::index::sample(
+            rng,
+            number_of_items,
+            self.number_of_reassignments.min(number_of_items),
+        );
+        for item in items.into_iter() {
+            // draw among the other options by skipping the current one
+            let current_option = perturbed.option_of(item);
+            let mut option = rng.gen_range(0..self.number_of_options - 1);
+            if option >= current_option {
+                option += 1;
+            }
+            perturbed.assign(item, option);
+        }
+        perturbed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn is_permutation(sequence: &[usize]) -> bool {
+        let mut sorted = sequence.to_vec();
+        sorted.sort();
+        sorted == (0..sequence.len()).collect::<Vec<usize>>()
+    }
+
+    #[test]
+    fn test_sequence_perturbations() {
+        let permutation: Vec<usize> = (0..10).collect();
+        let perturbations: Vec<Box<dyn Perturbation<Vec<usize>>>> = vec![
+            Box::new(RandomSwaps::new(3)),
+            Box::new(RandomSegmentReversals::new(2)),
+            Box::new(RandomSegmentReversals::new(1).with_maximal_segment_length(2)),
+        ];
+        for perturbation in perturbations {
+            let mut rng = StdRng::seed_from_u64(42);
+            let perturbed = perturbation.perturb(&permutation, &mut rng);
+            assert!(is_permutation(&perturbed));
+            assert_ne!(perturbed, permutation);
+            let mut rng = StdRng::seed_from_u64(42);
+            assert_eq!(perturbation.perturb(&permutation, &mut rng), perturbed);
+        }
+
+        // a single reversal of length 2 is a swap of neighboring positions
+        let mut rng = StdRng::seed_from_u64(7);
+        let perturbed = RandomSegmentReversals::new(1)
+            .with_maximal_segment_length(2)
+            .perturb(&permutation, &mut rng);
+        let changed: Vec<usize> = (0..10).filter(|&i| perturbed[i] != i).collect();
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[1], changed[0] + 1);
+
+        // too short sequences are unchanged
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(RandomSwaps::new(3).perturb(&vec![5], &mut rng), vec![5]);
+    }
+
+    #[test]
+    fn test_random_reassignments() {
+        let assignment: Vec<usize> = vec![0, 1, 2, 0, 1, 2];
+        let mut rng = StdRng::seed_from_u64(42);
+        let perturbed = RandomReassignments::new(2, 3).perturb(&assignment, &mut rng);
+        let changed = (0..6).filter(|&i| perturbed[i] != assignment[i]).count();
+        assert_eq!(changed, 2);
+        assert!(perturbed.iter().all(|&option| option < 3));
+
+        let perturbed = RandomReassignments::new(10, 3).perturb(&assignment, &mut rng);
+        assert!((0..6).all(|i| perturbed[i] != assignment[i]));
+
+        assert_eq!(
+            RandomReassignments::new(2, 1).perturb(&assignment, &mut rng),
+            assignment
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! This module contains the [`SubproblemExactSolver`] trait for exact solvers of small
+//! subproblems (e.g., a dynamic program or an ILP solver) and the [`ExactRepair`] operator, which
+//! uses such a solver to repair destroyed solutions in the [`LnsSolver`][super::LnsSolver].
+//! * Together with a [`DestroyOperator`][super::DestroyOperator] that frees a small part of the
+//!   solution, this yields a fix-and-optimize step: The remaining part is fixed and the freed part
+//!   is optimized exactly.
+//! * An ILP solver (e.g., via [`good_lp`](https://docs.rs/good_lp/)) can be integrated by
+//!   implementing [`SubproblemExactSolver`] for a model of the freed part behind a feature of the
+//!   user's crate, so `rapid_solve` does not depend on a specific solver.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time as stdtime;
+
+use rand::RngCore;
+
+use super::RepairOperator;
+
+/// Solves the subproblem given by a partially destroyed solution exactly, i.e., returns the best
+/// complete solution that keeps the fixed part of the `destroyed_solution`.
+/// * The solver should stop as soon as the `deadline` is reached and return `None` in this case.
+/// * `None` can also be returned if the subproblem is too large or infeasible, such that the
+///   [`ExactRepair`] falls back to a heuristic repair.
+pub trait SubproblemExactSolver<S>: Send + Sync {
+    /// Returns the optimal completion of the `destroyed_solution` (or `None` if it cannot be
+    /// found before the `deadline`).
+    fn solve_subproblem(&self, destroyed_solution: &S, deadline: stdtime::Instant) -> Option<S>;
+}
+
+/// A [`RepairOperator`] that completes the destroyed solution by a [`SubproblemExactSolver`] with
+/// a time budget per call.
+/// * If the exact solver returns `None` (e.g., as the time budget is exceeded), the `fallback`
+///   repair operator is applied instead.
+/// * The number of exact repairs and fallbacks are counted (see
+///   [`exact_repairs`][ExactRepair::exact_repairs] and [`fallbacks`][ExactRepair::fallbacks]), so
+///   one can check whether the time budget or the size of the destroyed part fits.
+pub struct ExactRepair<S> {
+    exact_solver: Box<dyn SubproblemExactSolver<S>>,
+    time_budget: stdtime::Duration,
+    fallback: Box<dyn RepairOperator<S>>,
+    exact_repairs: AtomicU64,
+    fallbacks: AtomicU64,
+}
+
+impl<S> ExactRepair<S> {
+    /// Creates a new [`ExactRepair`] that calls the `exact_solver` with the given `time_budget`
+    /// and applies the `fallback` if the exact solver fails.
+    pub fn new(
+        exact_solver: Box<dyn SubproblemExactSolver<S>>,
+        time_budget: stdtime::Duration,
+        fallback: Box<dyn RepairOperator<S>>,
+    ) -> ExactRepair<S> {
+        ExactRepair {
+            exact_solver,
+            time_budget,
+            fallback,
+            exact_repairs: AtomicU64::new(0),
+            fallbacks: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of repairs by the exact solver.
+    pub fn exact_repairs(&self) -> u64 {
+        self.exact_repairs.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of repairs by the fallback operator.
+    pub fn fallbacks(&self) -> u64 {
+        self.fallbacks.load(Ordering::Relaxed)
+    }
+}
+
+impl<S> RepairOperator<S> for ExactRepair<S> {
+    fn repair(&self, destroyed_solution: S, rng: &mut dyn RngCore) -> S {
+        let deadline = stdtime::Instant::now() + self.time_budget;
+        match self
+            .exact_solver
+            .solve_subproblem(&destroyed_solution, deadline)
+        {
+            Some(repaired_solution) => {
+                self.exact_repairs.fetch_add(1, Ordering::Relaxed);
+                repaired_solution
+            }
+            None => {
+                self.fallbacks.fetch_add(1, Ordering::Relaxed);
+                self.fallback.repair(destroyed_solution, rng)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::super::{DestroyOperator, LnsSolver};
+    use super::*;
+    use crate::heuristics::common::{silent_function_between_steps, TerminationCriteria};
+    use crate::heuristics::Solver;
+    use crate::objective::{BaseValue, Indicator, Objective};
+
+    // digits between 0 and 3, where `None` marks a destroyed position
+    type Digits = Vec<Option<i64>>;
+
+    const TARGET: [i64; 6] = [3, 0, 3, 0, 3, 0];
+
+    // squared distance to the target plus the differences of neighboring digits
+    struct Cost;
+
+    impl Indicator<Digits> for Cost {
+        fn evaluate(&self, solution: &Digits) -> BaseValue {
+            let digits: Vec<i64> = solution.iter().map(|digit| digit.unwrap()).collect();
+            let distance: i64 = digits
+                .iter()
+                .zip(TARGET)
+                .map(|(digit, target)| (digit - target).pow(2))
+                .sum();
+            let differences: i64 = digits.windows(2).map(|w| (w[0] - w[1]).abs()).sum();
+            BaseValue::Integer(distance + differences)
+        }
+        fn name(&self) -> String {
+            String::from("Cost")
+        }
+    }
+
+    struct DestroyTwo;
+
+    impl DestroyOperator<Digits> for DestroyTwo {
+        fn destroy(&self, solution: &Digits, rng: &mut dyn RngCore) -> Digits {
+            let mut destroyed = solution.clone();
+            destroyed[rng.gen_range(0..solution.len())] = None;
+            destroyed[rng.gen_range(0..solution.len())] = None;
+            destroyed
+        }
+    }
+
+    struct FillWithZeros;
+
+    impl RepairOperator<Digits> for FillWithZeros {
+        fn repair(&self, destroyed_solution: Digits, _rng: &mut dyn RngCore) -> Digits {
+            destroyed_solution
+                .into_iter()
+                .map(|digit| digit.or(Some(0)))
+                .collect()
+        }
+    }
+
+    // enumerates all completions of at most `max_free` destroyed positions
+    struct Enumeration {
+        max_free: usize,
+    }
+
+    impl SubproblemExactSolver<Digits> for Enumeration {
+        fn solve_subproblem(
+            &self,
+            destroyed_solution: &Digits,
+            deadline: stdtime::Instant,
+        ) -> Option<Digits> {
+            let free: Vec<usize> = (0..destroyed_solution.len())
+                .filter(|&i| destroyed_solution[i].is_none())
+                .collect();
+            if free.len() > self.max_free {
+                return None;
+            }
+            let mut best: Option<(BaseValue, Digits)> = None;
+            for assignment in 0..4_usize.pow(free.len() as u32) {
+                if stdtime::Instant::now() > deadline {
+                    return None;
+                }
+                let mut candidate = destroyed_solution.clone();
+                for (k, &i) in free.iter().enumerate() {
+                    candidate[i] = Some((assignment / 4_usize.pow(k as u32) % 4) as i64);
+                }
+                let cost = Cost.evaluate(&candidate);
+                if best.as_ref().is_none_or(|(best_cost, _)| cost < *best_cost) {
+                    best = Some((cost, candidate));
+                }
+            }
+            best.map(|(_, solution)| solution)
+        }
+    }
+
+    #[test]
+    fn test_exact_repair() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Cost)));
+        let exact_repair = ExactRepair::new(
+            Box::new(Enumeration { max_free: 1 }),
+            stdtime::Duration::from_secs(1),
+            Box::new(FillWithZeros),
+        );
+        let mut rng = StdRng::seed_from_u64(0);
+        let repaired = exact_repair.repair(
+            vec![Some(3), None, Some(3), Some(0), Some(3), Some(0)],
+            &mut rng,
+        );
+        assert_eq!(repaired[1], Some(1));
+        let repaired = exact_repair.repair(vec![None; 6], &mut rng);
+        assert_eq!(repaired, vec![Some(0); 6]);
+        assert_eq!(
+            (exact_repair.exact_repairs(), exact_repair.fallbacks()),
+            (1, 1)
+        );
+
+        let solver = LnsSolver::with_options(
+            vec![Box::new(DestroyTwo)],
+            vec![Box::new(ExactRepair::new(
+                Box::new(Enumeration { max_free: 2 }),
+                stdtime::Duration::from_secs(1),
+                Box::new(FillWithZeros),
+            ))],
+            objective.clone(),
+            None,
+            Some(42),
+            Some(silent_function_between_steps()),
+            Some(TerminationCriteria::iteration_limit(100)),
+        );
+        let solution = solver.solve(vec![Some(0); 6]);
+        assert!(
+            solution.objective_value() < objective.evaluate(vec![Some(0); 6]).objective_value()
+        );
+    }
+}
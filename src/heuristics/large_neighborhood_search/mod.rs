@@ -0,0 +1,297 @@
+//! This module contains the [`LnsSolver`] implementing the
+//! [large neighborhood search (LNS)](https://doi.org/10.1007/3-540-49481-2_30) metaheuristic
+//! (also known as ruin-and-recreate).
+//! * Starts with an initial solution and iteratively destroys a part of the current solution
+//!   with a [`DestroyOperator`] and repairs it with a [`RepairOperator`].
+//! * In each iteration, one destroy and one repair operator are chosen uniformly at random.
+//! * The repaired solution replaces the current solution if it is accepted by the
+//!   [`LnsAcceptanceFunction`] (by default, if it is not worse than the current solution).
+//! * With an [`ElitePool`], the search keeps the best diverse solutions and restarts from one of
+//!   them if no new best solution is found for a while.
+//! * The search stops after a certain number of iterations or after a certain time limit.
+//! * The best solution seen is returned.
+//! * Small destroyed parts can be repaired optimally by a
+//!   [`SubproblemExactSolver`][exact_repair::SubproblemExactSolver] (e.g., a dynamic program or an
+//!   ILP solver) with a time budget per call (see [`ExactRepair`][exact_repair::ExactRepair]).
+//! * LNS complements the local search family for problems where ruin-and-recreate works much
+//!   better than small-move neighborhoods.
+//!
+//! For an example, see the [LNS solver for the prize-collecting
+//! TSP][crate::examples::tsp::solvers::prize_collecting_lns].
+pub mod exact_repair;
+
+use std::sync::Arc;
+use std::time as stdtime;
+
+use rand::{Rng, RngCore};
+
+use super::common::default_rng_factory;
+use super::common::RngFactory;
+use super::common::SolverDescription;
+use super::common::{
+    default_function_between_steps, report_termination, ElitePool, FunctionBetweenSteps,
+    SearchRecorder, SearchResult, TerminationCriteria,
+};
+use super::Solver;
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// Destroys a part of a solution, e.g., by removing some randomly chosen elements.
+/// * The destroyed solution is of the same type `S`, so the solution type must be able to
+///   represent partial solutions (e.g., a tour that does not visit all nodes).
+/// * The random number generator of the solver is provided to make the search reproducible.
+pub trait DestroyOperator<S>: Send + Sync {
+    /// Returns a partially destroyed copy of the `solution`.
+    fn destroy(&self, solution: &S, rng: &mut dyn RngCore) -> S;
+}
+
+/// Repairs a partially destroyed solution, e.g., by greedily inserting the missing elements.
+pub trait RepairOperator<S>: Send + Sync {
+    /// Returns a complete solution built from the `destroyed_solution`.
+    fn repair(&self, destroyed_solution: S, rng: &mut dyn RngCore) -> S;
+}
+
+/// Type for the acceptance criterion of the [`LnsSolver`]. It takes the repaired solution, the
+/// current solution and the best solution seen so far and returns `true` if the repaired
+/// solution should become the current solution.
+pub type LnsAcceptanceFunction<S> = Box<
+    dyn Fn(&EvaluatedSolution<S>, &EvaluatedSolution<S>, &EvaluatedSolution<S>) -> bool
+        + Send
+        + Sync,
+>;
+
+/// The default [`LnsAcceptanceFunction`]: The repaired solution is accepted if it is not worse
+/// than the current solution.
+pub fn accept_if_not_worse<S>() -> LnsAcceptanceFunction<S> {
+    Box::new(|repaired_solution, current_solution, _| {
+        repaired_solution.objective_value() <= current_solution.objective_value()
+    })
+}
+
+/// A large neighborhood search solver that uses [`DestroyOperators`][DestroyOperator],
+/// [`RepairOperators`][RepairOperator] and an [`Objective`] to find a good solution.
+/// * In each iteration, a destroy and a repair operator are chosen uniformly at random and applied
+///   to the current solution.
+/// * The repaired solution becomes the current solution if it is accepted by the
+///   [`LnsAcceptanceFunction`] (default: [`accept_if_not_worse`]).
+/// * The `function_between_steps` is executed after each accepted solution.
+/// * The default `function_between_steps` (if `None`) is printing the iteration number, the
+///   objective value (in comparison the the previous objective value) and the time elapsed since
+///   the start.
+/// * The search stops if the [`TerminationCriteria`] (e.g., a time limit or a maximal number of
+///   iterations) are met. They must be set.
+/// * The best solution seen is returned.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the trajectory of the accepted solutions (without neighbor counts).
+///
+/// For a high-level overview, see the [module documentation][super::large_neighborhood_search]
+/// and for an example, see the [LNS solver for the prize-collecting
+/// TSP][crate::examples::tsp::solvers::prize_collecting_lns].
+pub struct LnsSolver<S> {
+    destroy_operators: Vec<Box<dyn DestroyOperator<S>>>,
+    repair_operators: Vec<Box<dyn RepairOperator<S>>>,
+    objective: Arc<Objective<S>>,
+    acceptance_function: LnsAcceptanceFunction<S>,
+    random_seed: Option<u64>,
+    rng_factory: RngFactory,
+    function_between_steps: FunctionBetweenSteps<S>,
+    termination_criteria: Option<TerminationCriteria>,
+    elite_pool: Option<Arc<ElitePool<S>>>,
+    elite_restart_after: Option<u32>,
+}
+
+impl<S> LnsSolver<S> {
+    /// Creates a new [`LnsSolver`] with the given [`DestroyOperators`][DestroyOperator],
+    /// [`RepairOperators`][RepairOperator], [`Objective`] and the maximal number of iterations.
+    /// * A `random_seed` can be provided to make the search reproducible.
+    pub fn initialize(
+        destroy_operators: Vec<Box<dyn DestroyOperator<S>>>,
+        repair_operators: Vec<Box<dyn RepairOperator<S>>>,
+        objective: Arc<Objective<S>>,
+        iteration_limit: u32,
+        random_seed: Option<u64>,
+    ) -> Self {
+        Self::with_options(
+            destroy_operators,
+            repair_operators,
+            objective,
+            None,
+            random_seed,
+            None,
+            Some(TerminationCriteria::iteration_limit(iteration_limit)),
+        )
+    }
+
+    /// Creates a new [`LnsSolver`] with the given [`DestroyOperators`][DestroyOperator],
+    /// [`RepairOperators`][RepairOperator] and [`Objective`].
+    /// * `acceptance_function` decides whether a repaired solution becomes the current solution.
+    ///   If `None`, the default is [`accept_if_not_worse`].
+    /// * `random_seed` can be provided to make the search reproducible.
+    /// * `function_between_steps` is executed after each accepted solution. If `None`, the
+    ///   default is printing the iteration number, the objective value (in comparison the the
+    ///   previous objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit or a maximum
+    ///   number of destroy-repair cycles, are checked after each iteration with the best solution
+    ///   seen. The last iteration is allowed to finish.
+    /// * The `termination_criteria` must be set (as the search does not stop by itself) and there
+    ///   must be at least one destroy and one repair operator.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        destroy_operators: Vec<Box<dyn DestroyOperator<S>>>,
+        repair_operators: Vec<Box<dyn RepairOperator<S>>>,
+        objective: Arc<Objective<S>>,
+        acceptance_function: Option<LnsAcceptanceFunction<S>>,
+        random_seed: Option<u64>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> Self {
+        if termination_criteria.is_none() {
+            panic!("The `termination_criteria` must be set.");
+        }
+        if destroy_operators.is_empty() || repair_operators.is_empty() {
+            panic!("At least one destroy operator and one repair operator must be provided.");
+        }
+        Self {
+            destroy_operators,
+            repair_operators,
+            objective,
+            acceptance_function: acceptance_function.unwrap_or(accept_if_not_worse()),
+            random_seed,
+            rng_factory: default_rng_factory(),
+            function_between_steps: function_between_steps
+                .unwrap_or(default_function_between_steps()),
+            termination_criteria,
+            elite_pool: None,
+            elite_restart_after: None,
+        }
+    }
+
+    /// Replaces the [`RngFactory`] (default: [`StdRng`][rand::rngs::StdRng], see
+    /// [`default_rng_factory`]), which creates the random number generator of each run from the
+    /// `random_seed`. The generator selects the operators and is passed to them.
+    pub fn with_rng_factory(mut self, rng_factory: RngFactory) -> Self {
+        self.rng_factory = rng_factory;
+        self
+    }
+
+    /// Offers each current solution to the `elite_pool` (see [`ElitePool`]). If `restart_after`
+    /// is set, the search restarts from the elite solution that is most distant from the current
+    /// solution whenever no new best solution is found for `restart_after` iterations.
+    pub fn with_elite_pool(
+        mut self,
+        elite_pool: Arc<ElitePool<S>>,
+        restart_after: Option<u32>,
+    ) -> Self {
+        self.elite_pool = Some(elite_pool);
+        self.elite_restart_after = restart_after;
+        self
+    }
+}
+
+impl<S: Clone> Solver<S> for LnsSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        let start_time = stdtime::Instant::now();
+        let mut current_solution = self.objective.evaluate(initial_solution);
+        let mut best_solution_seen = current_solution.clone();
+        let mut termination_criteria = self.termination_criteria.clone();
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_time_limit),
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_iteration_limit),
+            current_solution.objective_value(),
+            true,
+        );
+
+        let mut rng = (self.rng_factory)(self.random_seed);
+
+        let mut iteration_counter = 1;
+        let mut iterations_since_restart = 0;
+        let termination_reason = loop {
+            let destroy_operator =
+                &self.destroy_operators[rng.gen_range(0..self.destroy_operators.len())];
+            let repair_operator =
+                &self.repair_operators[rng.gen_range(0..self.repair_operators.len())];
+
+            let destroyed_solution =
+                destroy_operator.destroy(current_solution.solution(), &mut rng);
+            let repaired_solution = self
+                .objective
+                .evaluate(repair_operator.repair(destroyed_solution, &mut rng));
+
+            if (self.acceptance_function)(
+                &repaired_solution,
+                &current_solution,
+                &best_solution_seen,
+            ) {
+                recorder.step(
+                    iteration_counter,
+                    &repaired_solution,
+                    Some(&current_solution),
+                    None,
+                );
+                current_solution = repaired_solution;
+                if self
+                    .objective
+                    .is_better(&current_solution, &best_solution_seen)
+                {
+                    best_solution_seen = current_solution.clone();
+                    iterations_since_restart = 0;
+                } else {
+                    iterations_since_restart += 1;
+                }
+            } else {
+                iterations_since_restart += 1;
+            }
+            if let Some(elite_pool) = &self.elite_pool {
+                elite_pool.offer_or_restart(
+                    &mut current_solution,
+                    &mut iterations_since_restart,
+                    self.elite_restart_after,
+                );
+            }
+
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                break reason;
+            }
+            iteration_counter += 1;
+        };
+
+        recorder.finish(best_solution_seen, iteration_counter, termination_reason)
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("LnsSolver")
+            .with_parameter("destroy_operators", self.destroy_operators.len())
+            .with_parameter("repair_operators", self.repair_operators.len())
+            .with_optional_parameter("random_seed", self.random_seed)
+            .with_optional_parameter(
+                "elite_pool",
+                self.elite_pool
+                    .as_ref()
+                    .map(|elite_pool| elite_pool.capacity()),
+            )
+            .with_optional_parameter("elite_restart_after", self.elite_restart_after)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+    }
+}
@@ -0,0 +1,310 @@
+//! [`AdaptiveNeighborhoodImprover`] manages several [`Neighborhoods`][Neighborhood] and learns
+//! by a multi-armed bandit ([`BanditPolicy`]) which one to explore next.
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use super::super::Neighborhood;
+use super::{deadline_reached, Deadline, LocalImprover};
+use crate::heuristics::common::NeighborCounts;
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// The multi-armed bandit policy of the [`AdaptiveNeighborhoodImprover`], which selects the
+/// neighborhood (arm) to explore first.
+/// * The reward of an exploration is the improvement on the most important level in which the
+///   objective values differ (see [`BaseValue::to_f64`][crate::objective::BaseValue::to_f64])
+///   per second of exploration. An exploration without improvement has reward 0.
+/// * Neighborhoods that were never explored are tried first (in the given order).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BanditPolicy {
+    /// With probability `epsilon` a uniformly drawn neighborhood, otherwise the neighborhood
+    /// with the largest mean reward.
+    EpsilonGreedy {
+        /// The probability (between 0 and 1) of exploring a random neighborhood.
+        epsilon: f64,
+    },
+    /// The neighborhood with the largest upper confidence bound
+    /// `mean_reward + exploration * sqrt(ln(total_explorations) / explorations)` (UCB1).
+    /// The `exploration` constant should be in the order of magnitude of the rewards.
+    Ucb {
+        /// The weight of the confidence term.
+        exploration: f64,
+    },
+}
+
+/// The statistics of a neighborhood of an [`AdaptiveNeighborhoodImprover`] (see
+/// [`arm_statistics`][AdaptiveNeighborhoodImprover::arm_statistics]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArmStatistics {
+    /// The name of the neighborhood.
+    pub name: String,
+    /// The number of explorations of the neighborhood.
+    pub explorations: u64,
+    /// The number of explorations that found an improving neighbor.
+    pub improvements: u64,
+    /// The mean reward (improvement per second) of the explorations.
+    pub mean_reward: f64,
+}
+
+/// A [`LocalImprover`] that manages several [`Neighborhoods`][Neighborhood] (e.g., cheap swaps
+/// and expensive 3-opt moves) and learns by a [`BanditPolicy`] which neighborhood yields the
+/// largest improvement per CPU-second.
+/// * In each call, the neighborhood selected by the policy is explored first and the first
+///   improving neighbor is taken (as in [`TakeFirst`][super::TakeFirst]).
+/// * If the selected neighborhood has no improving neighbor, the other neighborhoods are explored
+///   in descending order of their mean rewards. Hence, `None` is only returned for a local
+///   minimum w.r.t. all neighborhoods.
+/// * As a [`LocalImprover`], it can be used by every solver that takes a local improver (e.g.,
+///   the [`LocalSearchSolver`][super::super::LocalSearchSolver], the iterated local search and
+///   the memetic solver).
+/// * The learned statistics persist over all calls (see
+///   [`arm_statistics`][AdaptiveNeighborhoodImprover::arm_statistics]).
+/// * Requires the `rand` feature.
+pub struct AdaptiveNeighborhoodImprover<S> {
+    names: Vec<String>,
+    neighborhoods: Vec<Arc<dyn Neighborhood<S>>>,
+    objective: Arc<Objective<S>>,
+    policy: BanditPolicy,
+    state: Mutex<BanditState>,
+}
+
+/// The learned statistics and the random number generator of the bandit.
+struct BanditState {
+    explorations: Vec<u64>,
+    improvements: Vec<u64>,
+    total_rewards: Vec<f64>,
+    rng: Box<dyn RngCore + Send>,
+}
+
+impl BanditState {
+    fn mean_reward(&self, arm: usize) -> f64 {
+        if self.explorations[arm] == 0 {
+            0.0
+        } else {
+            self.total_rewards[arm] / self.explorations[arm] as f64
+        }
+    }
+}
+
+impl<S> AdaptiveNeighborhoodImprover<S> {
+    /// Creates a new [`AdaptiveNeighborhoodImprover`] for the given named `neighborhoods`, the
+    /// [`Objective`] and the [`BanditPolicy`].
+    /// * A `random_seed` can be provided to make the selection reproducible.
+    /// * Panics if no neighborhood is given.
+    pub fn new(
+        neighborhoods: Vec<(&str, Arc<dyn Neighborhood<S>>)>,
+        objective: Arc<Objective<S>>,
+        policy: BanditPolicy,
+        random_seed: Option<u64>,
+    ) -> AdaptiveNeighborhoodImprover<S> {
+        assert!(
+            !neighborhoods.is_empty(),
+            "At least one neighborhood must be given."
+        );
+        let number_of_arms = neighborhoods.len();
+        let (names, neighborhoods) = neighborhoods
+            .into_iter()
+            .map(|(name, neighborhood)| (String::from(name), neighborhood))
+            .unzip();
+        AdaptiveNeighborhoodImprover {
+            names,
+            neighborhoods,
+            objective,
+            policy,
+            state: Mutex::new(BanditState {
+                explorations: vec![0; number_of_arms],
+                improvements: vec![0; number_of_arms],
+                total_rewards: vec![0.0; number_of_arms],
+                rng: match random_seed {
+                    Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+                    None => Box::new(StdRng::from_entropy()),
+                },
+            }),
+        }
+    }
+
+    /// Replaces the random number generator of the [`BanditPolicy`] (by default a [`StdRng`]
+    /// seeded by the `random_seed`), e.g., by a faster or a recorded one.
+    pub fn with_rng(self, rng: Box<dyn RngCore + Send>) -> AdaptiveNeighborhoodImprover<S> {
+        self.state.lock().unwrap().rng = rng;
+        self
+    }
+
+    /// Returns the learned statistics of each neighborhood (in the given order).
+    pub fn arm_statistics(&self) -> Vec<ArmStatistics> {
+        let state = self.state.lock().unwrap();
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(arm, name)| ArmStatistics {
+                name: name.clone(),
+                explorations: state.explorations[arm],
+                improvements: state.improvements[arm],
+                mean_reward: state.mean_reward(arm),
+            })
+            .collect()
+    }
+
+    /// Returns the order in which the neighborhoods are explored: the arm selected by the policy
+    /// first, then the others by descending mean reward.
+    fn exploration_order(&self) -> Vec<usize> {
+        let mut state = self.state.lock().unwrap();
+        let number_of_arms = self.neighborhoods.len();
+        let selected_arm = match (0..number_of_arms).find(|&arm| state.explorations[arm] == 0) {
+            Some(untried_arm) => untried_arm,
+            None => match self.policy {
+                BanditPolicy::EpsilonGreedy { epsilon } => {
+                    if state.rng.gen::<f64>() < epsilon {
+                        state.rng.gen_range(0..number_of_arms)
+                    } else {
+                        best_arm(number_of_arms, |arm| state.mean_reward(arm))
+                    }
+                }
+                BanditPolicy::Ucb { exploration } => {
+                    let total_explorations = state.explorations.iter().sum::<u64>() as f64;
+                    best_arm(number_of_arms, |arm| {
+                        state.mean_reward(arm)
+                            + exploration
+                                * (total_explorations.ln() / state.explorations[arm] as f64).sqrt()
+                    })
+                }
+            },
+        };
+        let mut order: Vec<usize> = (0..number_of_arms)
+            .filter(|&arm| arm != selected_arm)
+            .collect();
+        order.sort_by(|&a, &b| state.mean_reward(b).total_cmp(&state.mean_reward(a)));
+        order.insert(0, selected_arm);
+        order
+    }
+
+    /// Explores the neighborhoods in the [`exploration_order`][Self::exploration_order] until an
+    /// improving neighbor is found and records the rewards. The neighbor counts cover all explored
+    /// neighborhoods.
+    fn explore(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, NeighborCounts) {
+        let mut neighbor_counts = NeighborCounts::default();
+        for arm in self.exploration_order() {
+            let start_time = Instant::now();
+            let improving_neighbor = self.neighborhoods[arm]
+                .neighbors_of(solution.solution())
+                .take_while(|_| deadline.as_ref().is_none_or(|d| !deadline_reached(d)))
+                .inspect(|_| neighbor_counts.generated += 1)
+                .map(|neighbor| self.objective.evaluate(neighbor))
+                .inspect(|_| neighbor_counts.evaluated += 1)
+                .find(|neighbor| self.objective.is_better(neighbor, solution));
+            let elapsed_seconds = start_time.elapsed().as_secs_f64().max(1e-9);
+
+            let mut state = self.state.lock().unwrap();
+            state.explorations[arm] += 1;
+            if let Some(neighbor) = improving_neighbor {
+                let improvement = solution
+                    .objective_value()
+                    .iter()
+                    .zip(neighbor.objective_value().iter())
+                    .find(|(old_value, new_value)| old_value != new_value)
+                    .map_or(0.0, |(old_value, new_value)| {
+                        old_value.to_f64() - new_value.to_f64()
+                    });
+                state.improvements[arm] += 1;
+                state.total_rewards[arm] += improvement / elapsed_seconds;
+                neighbor_counts.accepted = 1;
+                return (Some(neighbor), neighbor_counts);
+            }
+            if deadline.as_ref().is_some_and(deadline_reached) {
+                report!(Info, "Iteration timeout reached.");
+                return (None, neighbor_counts);
+            }
+        }
+        (None, neighbor_counts)
+    }
+}
+
+/// Returns the arm with the largest score (the first one on ties).
+fn best_arm(number_of_arms: usize, score: impl Fn(usize) -> f64) -> usize {
+    (0..number_of_arms)
+        .rev()
+        .max_by(|&a, &b| score(a).total_cmp(&score(b)))
+        .unwrap()
+}
+
+impl<S> LocalImprover<S> for AdaptiveNeighborhoodImprover<S> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.explore(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.explore(solution, Some(deadline)).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let (improved_solution, neighbor_counts) = self.explore(solution, deadline);
+        (improved_solution, Some(neighbor_counts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::local_search::local_search_loop;
+    use crate::test_fixtures::Value;
+
+    /// Decreases the value by `step` (if the value stays non-negative).
+    struct Decrease(i64);
+
+    impl Neighborhood<i64> for Decrease {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new(std::iter::once(solution - self.0).filter(|neighbor| *neighbor >= 0))
+        }
+    }
+
+    #[test]
+    fn test_adaptive_neighborhood_improver() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        for policy in [
+            BanditPolicy::EpsilonGreedy { epsilon: 0.1 },
+            BanditPolicy::Ucb { exploration: 1.0 },
+        ] {
+            let improver = AdaptiveNeighborhoodImprover::new(
+                vec![
+                    ("decrease_by_1", Arc::new(Decrease(1))),
+                    ("decrease_by_100", Arc::new(Decrease(100))),
+                ],
+                objective.clone(),
+                policy,
+                Some(42),
+            );
+
+            let result = local_search_loop(
+                &objective,
+                10_050,
+                |solution| improver.improve(solution),
+                |_, _, _| true,
+            );
+
+            // a local minimum w.r.t. both neighborhoods is reached
+            assert_eq!(*result.solution(), 0);
+            let statistics = improver.arm_statistics();
+            assert_eq!(statistics[1].name, "decrease_by_100");
+            // the large steps are preferred as they yield more improvement per second
+            assert!(statistics[1].improvements > statistics[0].improvements);
+            assert!(statistics[1].mean_reward > statistics[0].mean_reward);
+        }
+    }
+}
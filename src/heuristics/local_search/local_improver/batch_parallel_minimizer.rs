@@ -0,0 +1,133 @@
+//! [`BatchParallelMinimizer`] searches the whole [`Neighborhood`] of a solution sequentially, but
+//! evaluates the neighbors in parallel batches.
+use super::super::Neighborhood;
+use super::LocalImprover;
+use crate::heuristics::common::NeighborCounts;
+use crate::objective::EvaluatedSolution;
+use crate::objective::Objective;
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::{IntoParallelIterator, ParallelIterator};
+use std::sync::Arc;
+use std::time as stdtime;
+
+/// [`BatchParallelMinimizer`] searches the whole [`Neighborhood`] of a solution and returns the
+/// best neighbor if it is better than the given solution.
+/// * The neighbors are generated sequentially (deterministic order of the [`Neighborhood`]
+///   iterator), but collected into batches of `batch_size` neighbors which are evaluated in
+///   parallel using [`rayon`](https://docs.rs/rayon/) (sequentially without the `rayon` feature).
+/// * The reduction is ordered, i.e., the result is exactly the same as for the
+///   [`Minimizer`][super::Minimizer] (for equally good neighbors, the first one is returned).
+/// * This is a middle ground between [`Minimizer`][super::Minimizer] and
+///   [`ParallelMinimizer`][crate::heuristics::parallel_local_search::parallel_local_improver::ParallelMinimizer]:
+///   It is useful if the computation of a neighbor is cheap but the evaluation of the
+///   [`Objective`] is the bottleneck and no
+///   [`ParallelNeighborhood`][crate::heuristics::common::ParallelNeighborhood] is available.
+/// * If no neighbor is better than the given solution (or no neighbor exists), `None` is returned.
+/// * If a deadline is given (see [`improve_until`][LocalImprover::improve_until]), no new batch
+///   is started after the deadline and the best neighbor found so far is considered.
+pub struct BatchParallelMinimizer<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    objective: Arc<Objective<S>>,
+    batch_size: usize,
+}
+
+impl<S> BatchParallelMinimizer<S> {
+    /// Creates a new [`BatchParallelMinimizer`] with the given [`Neighborhood`], [`Objective`] and
+    /// `batch_size` (number of neighbors that are evaluated in parallel at once).
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        batch_size: usize,
+    ) -> BatchParallelMinimizer<S> {
+        BatchParallelMinimizer {
+            neighborhood,
+            objective,
+            batch_size: batch_size.max(1),
+        }
+    }
+}
+
+impl<S: Send + Sync> LocalImprover<S> for BatchParallelMinimizer<S> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.improve_with_optional_deadline(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: stdtime::Instant,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.improve_with_optional_deadline(solution, Some(deadline))
+            .0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<stdtime::Instant>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        self.improve_with_optional_deadline(solution, deadline)
+    }
+}
+
+impl<S: Send + Sync> BatchParallelMinimizer<S> {
+    fn improve_with_optional_deadline(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<stdtime::Instant>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let mut timeout_reached = false;
+        let mut evaluated = 0;
+        let mut neighbors = self.neighborhood.neighbors_of(solution.solution());
+        let mut best_neighbor_opt: Option<EvaluatedSolution<S>> = None;
+        loop {
+            if deadline.is_some_and(|deadline| stdtime::Instant::now() >= deadline) {
+                report!(Info, "Iteration timeout reached.");
+                timeout_reached = true;
+                break;
+            }
+            let batch: Vec<S> = neighbors.by_ref().take(self.batch_size).collect();
+            if batch.is_empty() {
+                break;
+            }
+            evaluated += batch.len() as u64;
+            let evaluated_batch: Vec<EvaluatedSolution<S>> = batch
+                .into_par_iter()
+                .map(|neighbor| self.objective.evaluate(neighbor))
+                .collect(); // collect preserves the order of the batch
+            for evaluated_neighbor in evaluated_batch {
+                let is_better = match &best_neighbor_opt {
+                    Some(best_neighbor) => {
+                        self.objective.is_better(&evaluated_neighbor, best_neighbor)
+                    }
+                    None => true,
+                };
+                if is_better {
+                    best_neighbor_opt = Some(evaluated_neighbor);
+                }
+            }
+        }
+        let improved_solution = match best_neighbor_opt {
+            Some(best_neighbor) => {
+                if self.objective.is_better(&best_neighbor, solution) {
+                    Some(best_neighbor)
+                } else {
+                    None // no improvement found
+                }
+            }
+            None => {
+                if !timeout_reached {
+                    report!(Warning, "no swap possible.");
+                }
+                None
+            }
+        };
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(improved_solution.is_some()),
+            ..NeighborCounts::default()
+        };
+        (improved_solution, Some(neighbor_counts))
+    }
+}
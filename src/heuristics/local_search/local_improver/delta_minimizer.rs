@@ -0,0 +1,135 @@
+//! [`DeltaMinimizer`] searches the whole [`ModificationNeighborhood`] of a solution and evaluates
+//! the neighbors incrementally by a [`DeltaObjective`].
+use super::{deadline_reached, Deadline, LocalImprover};
+#[cfg(feature = "std")]
+use crate::heuristics::common::ModificationJournal;
+use crate::heuristics::common::{ModificationNeighborhood, NeighborCounts};
+use crate::objective::{DeltaObjective, EvaluatedSolution};
+use alloc::sync::Arc;
+
+/// [`DeltaMinimizer`] searches the whole [`ModificationNeighborhood`] of a solution and returns
+/// the best neighbor if it is better than the given solution.
+/// * Each neighbor is evaluated by the [`DeltaObjective`] from the objective value of the given
+///   solution and the modification, i.e., no indicator is evaluated from scratch.
+/// * No parallelism is used.
+/// * Is fast if the deltas are cheap to compute, even if a full evaluation is expensive.
+/// * If no neighbor is better than the given solution (or no neighbor exists), `None` is returned.
+/// * If a deadline is given (see [`improve_until`][LocalImprover::improve_until]), the
+///   exploration stops at the deadline and the best neighbor found so far is considered.
+/// * If a [`ModificationJournal`] is attached (see [`with_journal`][DeltaMinimizer::with_journal]),
+///   each applied modification is recorded.
+pub struct DeltaMinimizer<S, M> {
+    neighborhood: Arc<dyn ModificationNeighborhood<S, M>>,
+    delta_objective: Arc<DeltaObjective<S, M>>,
+    #[cfg(feature = "std")]
+    journal: Option<Arc<ModificationJournal<M>>>,
+}
+
+impl<S, M> DeltaMinimizer<S, M> {
+    /// Creates a new [`DeltaMinimizer`] with the given [`ModificationNeighborhood`] and
+    /// [`DeltaObjective`].
+    pub fn new(
+        neighborhood: Arc<dyn ModificationNeighborhood<S, M>>,
+        delta_objective: Arc<DeltaObjective<S, M>>,
+    ) -> DeltaMinimizer<S, M> {
+        DeltaMinimizer {
+            neighborhood,
+            delta_objective,
+            #[cfg(feature = "std")]
+            journal: None,
+        }
+    }
+
+    /// Records each applied modification (i.e., the modification of each returned improving
+    /// neighbor) in the given [`ModificationJournal`].
+    #[cfg(feature = "std")]
+    pub fn with_journal(mut self, journal: Arc<ModificationJournal<M>>) -> DeltaMinimizer<S, M> {
+        self.journal = Some(journal);
+        self
+    }
+}
+
+impl<S, M: Send> LocalImprover<S> for DeltaMinimizer<S, M> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.improve_with_optional_deadline(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.improve_with_optional_deadline(solution, Some(deadline))
+            .0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        self.improve_with_optional_deadline(solution, deadline)
+    }
+}
+
+impl<S, M> DeltaMinimizer<S, M> {
+    fn improve_with_optional_deadline(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let objective = self.delta_objective.objective();
+        let mut timeout_reached = false;
+        let mut evaluated = 0;
+        let best_neighbor_opt = self
+            .neighborhood
+            .neighbors_with_modifications_of(solution.solution())
+            .take_while(|_| {
+                timeout_reached = deadline.as_ref().is_some_and(deadline_reached);
+                !timeout_reached
+            })
+            .map(|(neighbor, modification)| {
+                evaluated += 1;
+                let neighbor =
+                    self.delta_objective
+                        .evaluate_neighbor(solution, neighbor, &modification);
+                (neighbor, modification)
+            })
+            .min_by(|(s1, _), (s2, _)| objective.compare(s1, s2));
+        if timeout_reached {
+            report!(Info, "Iteration timeout reached.");
+        }
+        let improved_solution = match best_neighbor_opt {
+            Some((best_neighbor, modification)) => {
+                if objective.is_better(&best_neighbor, solution) {
+                    #[cfg(feature = "std")]
+                    if let Some(journal) = &self.journal {
+                        journal.record(
+                            modification,
+                            solution.objective_value(),
+                            best_neighbor.objective_value(),
+                        );
+                    }
+                    #[cfg(not(feature = "std"))]
+                    let _ = modification;
+                    Some(best_neighbor)
+                } else {
+                    None // no improvement found
+                }
+            }
+            None => {
+                if !timeout_reached {
+                    report!(Warning, "no swap possible.");
+                }
+                None
+            }
+        };
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(improved_solution.is_some()),
+            ..NeighborCounts::default()
+        };
+        (improved_solution, Some(neighbor_counts))
+    }
+}
@@ -1,10 +1,13 @@
 //! [`Minimizer`] searches the whole [`Neighborhood`] of a solution and returns the best
 //! improving neighbor.
 use super::super::Neighborhood;
-use super::LocalImprover;
+use super::{deadline_reached, Deadline, LocalImprover};
+use crate::heuristics::common::NeighborCounts;
 use crate::objective::EvaluatedSolution;
 use crate::objective::Objective;
-use std::sync::Arc;
+use crate::objective::SurrogateScreening;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
 
 /// [`Minimizer`] searches the whole [`Neighborhood`] of a solution and returns the best neighbor
 /// if it is better than the given solution.
@@ -12,9 +15,15 @@ use std::sync::Arc;
 /// * Works for every solution type `S`.
 /// * Is fast if the computation and the evaluating of a neighbor is cheap.
 /// * If no neighbor is better than the given solution (or no neighbor exists), `None` is returned.
+/// * If a deadline is given (see [`improve_until`][LocalImprover::improve_until]), the
+///   exploration stops at the deadline and the best neighbor found so far is considered.
+/// * With a [`SurrogateScreening`] (see [`with_surrogate`][Minimizer::with_surrogate]), only
+///   the best `top_k` neighbors with respect to the cheap surrogate objective are evaluated by
+///   the exact objective.
 pub struct Minimizer<S> {
     neighborhood: Arc<dyn Neighborhood<S>>,
     objective: Arc<Objective<S>>,
+    surrogate_screening: Option<SurrogateScreening<S>>,
 }
 
 impl<S> Minimizer<S> {
@@ -26,33 +35,95 @@ impl<S> Minimizer<S> {
         Minimizer {
             neighborhood,
             objective,
+            surrogate_screening: None,
+        }
+    }
+
+    /// Creates a new [`Minimizer`] with two-stage evaluation: All neighbors are evaluated by the
+    /// cheap `surrogate_objective` and only the `top_k` best of them are evaluated by the exact
+    /// `objective` (see [`SurrogateScreening`]).
+    pub fn with_surrogate(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        surrogate_objective: Arc<Objective<S>>,
+        top_k: usize,
+    ) -> Minimizer<S> {
+        Minimizer {
+            neighborhood,
+            objective,
+            surrogate_screening: Some(SurrogateScreening::new(surrogate_objective, top_k)),
         }
     }
 }
 
 impl<S> LocalImprover<S> for Minimizer<S> {
     fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
-        let best_neighbor_opt = self
+        self.improve_counted(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.improve_counted(solution, Some(deadline)).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let mut timeout_reached = false;
+        let mut generated = 0;
+        let mut evaluated = 0;
+        let neighbors = self
             .neighborhood
             .neighbors_of(solution.solution())
-            .map(|neighbor| self.objective.evaluate(neighbor))
-            .min_by(|s1, s2| {
-                s1.objective_value()
-                    .partial_cmp(s2.objective_value())
-                    .unwrap()
-            });
-        match best_neighbor_opt {
+            .take_while(|_| {
+                timeout_reached = deadline.as_ref().is_some_and(deadline_reached);
+                !timeout_reached
+            })
+            .inspect(|_| generated += 1);
+        let candidates: Box<dyn Iterator<Item = S>> = match &self.surrogate_screening {
+            Some(surrogate_screening) => Box::new(
+                surrogate_screening
+                    .screen(neighbors.map(|neighbor| (neighbor, ())))
+                    .into_iter()
+                    .map(|(neighbor, _)| neighbor),
+            ),
+            None => Box::new(neighbors),
+        };
+        let best_neighbor_opt = candidates
+            .map(|neighbor| {
+                evaluated += 1;
+                self.objective.evaluate(neighbor)
+            })
+            .min_by(|s1, s2| self.objective.compare(s1, s2));
+        if timeout_reached {
+            report!(Info, "Iteration timeout reached.");
+        }
+        let improved_solution = match best_neighbor_opt {
             Some(best_neighbor) => {
-                if best_neighbor.objective_value() < solution.objective_value() {
+                if self.objective.is_better(&best_neighbor, solution) {
                     Some(best_neighbor)
                 } else {
                     None // no improvement found
                 }
             }
             None => {
-                println!("\x1b[31mwarning:\x1b[0m no swap possible.");
+                if !timeout_reached {
+                    report!(Warning, "no swap possible.");
+                }
                 None
             }
-        }
+        };
+        let neighbor_counts = NeighborCounts {
+            generated,
+            evaluated,
+            accepted: u64::from(improved_solution.is_some()),
+            ..NeighborCounts::default()
+        };
+        (improved_solution, Some(neighbor_counts))
     }
 }
@@ -1,22 +1,72 @@
 //! This module contains several [`LocalImprover`] implementations, which define the strategy to
 //! explore the neighborhood of a solution in each iteration of the
 //! [`LocalSearchSolver`][super::LocalSearchSolver].
+//! The [`AdaptiveNeighborhoodImprover`] (requires the `rand` feature) learns which of several
+//! neighborhoods to explore next. The [`MoveMinimizer`] explores a
+//! [`MoveNeighborhood`][crate::heuristics::common::MoveNeighborhood] and only applies the best
+//! move. The [`TakeKthImprovement`] takes the k-th improving neighbor (optionally in a shuffled
+//! order), which gives cheap stochasticity to a plain local search.
+#[cfg(feature = "rand")]
+mod adaptive_neighborhood;
+#[cfg(feature = "std")]
+mod batch_parallel_minimizer;
+mod delta_minimizer;
 mod minimizer;
+mod move_minimizer;
+mod recursion_depth_policy;
+mod recursion_memory_limit;
 mod take_first;
 mod take_first_recursion;
+mod take_kth_improvement;
 
+use crate::heuristics::common::NeighborCounts;
 use crate::objective::EvaluatedSolution;
+#[cfg(feature = "rand")]
+pub use adaptive_neighborhood::{AdaptiveNeighborhoodImprover, ArmStatistics, BanditPolicy};
+#[cfg(feature = "std")]
+pub use batch_parallel_minimizer::BatchParallelMinimizer;
+pub use delta_minimizer::DeltaMinimizer;
 pub use minimizer::Minimizer;
+pub use move_minimizer::MoveMinimizer;
+pub use recursion_depth_policy::{
+    full_recursion_depth, recursion_after_failures, recursion_near_target, RecursionDepthPolicy,
+    RecursionState,
+};
+pub use recursion_memory_limit::RecursionMemoryLimit;
+pub use recursion_memory_limit::RecursionMemoryStatistics;
+pub use recursion_memory_limit::SizeEstimate;
 pub use take_first::TakeFirst;
 pub use take_first_recursion::TakeFirstRecursion;
+pub use take_kth_improvement::TakeKthImprovement;
+
+/// The point in time at which the exploration of the neighborhood is stopped (see
+/// [`improve_until`][LocalImprover::improve_until]).
+#[cfg(feature = "std")]
+pub type Deadline = std::time::Instant;
+
+/// Without `std`, there is no clock, so no deadline can be constructed.
+#[cfg(not(feature = "std"))]
+pub type Deadline = core::convert::Infallible;
+
+/// Returns `true` if the `deadline` is reached.
+#[cfg(feature = "std")]
+pub(crate) fn deadline_reached(deadline: &Deadline) -> bool {
+    std::time::Instant::now() >= *deadline
+}
+
+/// Returns `true` if the `deadline` is reached.
+#[cfg(not(feature = "std"))]
+pub(crate) fn deadline_reached(deadline: &Deadline) -> bool {
+    match *deadline {}
+}
 
 /// Determines for a given solution (as [`EvaluatedSolution`]) the best neighbor that has an
 /// smaller [`ObjectiveValue`][crate::objective::ObjectiveValue].
 /// * A solver is equipped with only one [`LocalImprover`].
 /// * The [`LocalImprover`] is invoked in each iteration of the solver.
 /// * Depending on the problem and especially the
-/// computation costs of computing and evaluating neighbors, different [`LocalImprover`] might be
-/// better.
+///   computation costs of computing and evaluating neighbors, different [`LocalImprover`] might be
+///   better.
 /// * Returns `None` if there is no better solution in the [`Neighborhood`][super::Neighborhood].
 pub trait LocalImprover<S>: Send + Sync {
     /// Determines for a given [`EvaluatedSolution`] the best neighbor that has an smaller
@@ -25,4 +75,60 @@ pub trait LocalImprover<S>: Send + Sync {
     /// This method is called in each iteration of the
     /// [`LocalSearchSolver`][super::LocalSearchSolver].
     fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>>;
+
+    /// Same as [`improve`][LocalImprover::improve], but the exploration of the neighborhood is
+    /// stopped as soon as the `deadline` is reached. In this case, the best improving neighbor
+    /// found so far is returned (or `None` if no improving neighbor was found so far).
+    /// This method is called by the [`LocalSearchSolver`][super::LocalSearchSolver] if an
+    /// `iteration_timeout` is set.
+    /// The default implementation ignores the `deadline` and calls
+    /// [`improve`][LocalImprover::improve].
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        let _ = deadline;
+        self.improve(solution)
+    }
+
+    /// Same as [`improve_until`][LocalImprover::improve_until] (or
+    /// [`improve`][LocalImprover::improve] if there is no `deadline`), but additionally returns
+    /// the [`NeighborCounts`] of the exploration, which the solvers pass to the
+    /// `function_between_steps`.
+    /// The default implementation does not count the neighbors, i.e., it returns `None` as
+    /// counts.
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let improved_solution = match deadline {
+            Some(deadline) => self.improve_until(solution, deadline),
+            None => self.improve(solution),
+        };
+        (improved_solution, None)
+    }
+
+    /// Returns the name of the improver, which is listed by
+    /// [`Solver::describe`][crate::heuristics::Solver::describe]. The default implementation
+    /// returns the name of the type (e.g., `"Minimizer"`).
+    #[cfg(feature = "std")]
+    fn name(&self) -> String {
+        crate::heuristics::common::short_type_name::<Self>()
+    }
+
+    /// Returns the message of the panic the improver caught in the current run (see
+    /// [`PanicSafeImprover`][crate::heuristics::common::PanicSafeImprover]), such that the solver
+    /// reports [`Panicked`][crate::heuristics::common::TerminationReason::Panicked] instead of a
+    /// local optimum. The default implementation returns `None`.
+    #[cfg(feature = "std")]
+    fn panic_message(&self) -> Option<String> {
+        None
+    }
+
+    /// Forgets a panic caught in a previous run. The solver calls this at the start of each run.
+    /// The default implementation does nothing.
+    #[cfg(feature = "std")]
+    fn reset_panic(&self) {}
 }
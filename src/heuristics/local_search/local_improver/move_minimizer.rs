@@ -0,0 +1,203 @@
+//! [`MoveMinimizer`] ranks the [`Moves`][Move] of a [`MoveNeighborhood`] by their deltas and
+//! applies only the best move.
+use super::{deadline_reached, Deadline, LocalImprover};
+use crate::heuristics::common::{Move, MoveNeighborhood, NeighborCounts};
+use crate::objective::{EvaluatedSolution, Objective, ObjectiveValue};
+use alloc::sync::Arc;
+
+/// [`MoveMinimizer`] searches all [`Moves`][Move] of a [`MoveNeighborhood`] and returns the
+/// best neighbor if it is better than the given solution.
+/// * The objective value of a move is the objective value of the given solution plus its
+///   [`delta`][Move::delta]. Only moves without delta are applied and evaluated during the
+///   search, so for moves with deltas no neighbor is materialized except the best one.
+/// * The best move is applied and evaluated by the [`Objective`], such that the returned
+///   objective value does not depend on the correctness of the deltas. If the evaluated neighbor
+///   is not better than the given solution (i.e., the delta was wrong), a warning is reported and
+///   `None` is returned.
+/// * Moves are compared by their objective values only, i.e., the
+///   [`PreferenceFunction`][crate::objective::PreferenceFunction] of the objective is ignored.
+/// * No parallelism is used.
+/// * If no move is better than the given solution (or no move exists), `None` is returned.
+/// * If a deadline is given (see [`improve_until`][LocalImprover::improve_until]), the
+///   exploration stops at the deadline and the best move found so far is considered.
+pub struct MoveMinimizer<S, M> {
+    neighborhood: Arc<dyn MoveNeighborhood<S, Move = M>>,
+    objective: Arc<Objective<S>>,
+}
+
+impl<S, M> MoveMinimizer<S, M> {
+    /// Creates a new [`MoveMinimizer`] with the given [`MoveNeighborhood`] and [`Objective`].
+    pub fn new(
+        neighborhood: Arc<dyn MoveNeighborhood<S, Move = M>>,
+        objective: Arc<Objective<S>>,
+    ) -> MoveMinimizer<S, M> {
+        MoveMinimizer {
+            neighborhood,
+            objective,
+        }
+    }
+}
+
+impl<S, M: Move<S>> LocalImprover<S> for MoveMinimizer<S, M> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.improve_with_optional_deadline(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.improve_with_optional_deadline(solution, Some(deadline))
+            .0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        self.improve_with_optional_deadline(solution, deadline)
+    }
+}
+
+impl<S, M: Move<S>> MoveMinimizer<S, M> {
+    fn improve_with_optional_deadline(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let mut timeout_reached = false;
+        let mut generated = 0;
+        let mut evaluated = 0;
+        // the best move with its objective value and, if it had no delta, its evaluated neighbor
+        let mut best: Option<(ObjectiveValue, M, Option<EvaluatedSolution<S>>)> = None;
+        for m in self.neighborhood.moves_of(solution.solution()) {
+            timeout_reached = deadline.as_ref().is_some_and(deadline_reached);
+            if timeout_reached {
+                report!(Info, "Iteration timeout reached.");
+                break;
+            }
+            generated += 1;
+            let (objective_value, neighbor) = match m.delta(solution.solution()) {
+                Some(delta) => (solution.objective_value().clone() + delta, None),
+                None => {
+                    evaluated += 1;
+                    let neighbor = self.objective.evaluate(m.apply(solution.solution()));
+                    (neighbor.objective_value().clone(), Some(neighbor))
+                }
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(best_value, _, _)| objective_value < *best_value)
+            {
+                best = Some((objective_value, m, neighbor));
+            }
+        }
+        let improved_solution = match best {
+            Some((objective_value, m, neighbor)) => {
+                if objective_value >= *solution.objective_value() {
+                    None // no improvement found
+                } else {
+                    let neighbor = neighbor.unwrap_or_else(|| {
+                        evaluated += 1;
+                        self.objective.evaluate(m.apply(solution.solution()))
+                    });
+                    if neighbor.objective_value() < solution.objective_value() {
+                        Some(neighbor)
+                    } else {
+                        report!(
+                            Warning,
+                            "The delta of the best move does not match the evaluated neighbor."
+                        );
+                        None
+                    }
+                }
+            }
+            None => {
+                if !timeout_reached {
+                    report!(Warning, "no move possible.");
+                }
+                None
+            }
+        };
+        let neighbor_counts = NeighborCounts {
+            generated,
+            evaluated,
+            accepted: u64::from(improved_solution.is_some()),
+            ..NeighborCounts::default()
+        };
+        (improved_solution, Some(neighbor_counts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::local_search::local_search_loop;
+    use crate::objective::{BaseValue, Indicator};
+    use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+    struct Sum;
+
+    impl Indicator<Vec<i64>> for Sum {
+        fn evaluate(&self, solution: &Vec<i64>) -> BaseValue {
+            BaseValue::Integer(solution.iter().sum())
+        }
+
+        fn name(&self) -> String {
+            String::from("Sum")
+        }
+    }
+
+    /// Decrements the entry at the index (if it stays non-negative).
+    struct Decrement(usize);
+
+    impl Move<Vec<i64>> for Decrement {
+        fn apply(&self, solution: &Vec<i64>) -> Vec<i64> {
+            let mut neighbor = solution.clone();
+            neighbor[self.0] -= 1;
+            neighbor
+        }
+
+        fn delta(&self, _solution: &Vec<i64>) -> Option<ObjectiveValue> {
+            Some(ObjectiveValue::new(vec![BaseValue::Integer(-1)]))
+        }
+    }
+
+    struct DecrementNeighborhood;
+
+    impl MoveNeighborhood<Vec<i64>> for DecrementNeighborhood {
+        type Move = Decrement;
+
+        fn moves_of<'a>(
+            &'a self,
+            current_solution: &'a Vec<i64>,
+        ) -> Box<dyn Iterator<Item = Decrement> + Send + Sync + 'a> {
+            Box::new(
+                (0..current_solution.len())
+                    .filter(|&index| current_solution[index] > 0)
+                    .map(Decrement),
+            )
+        }
+    }
+
+    #[test]
+    fn test_move_minimizer() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Sum)));
+        let minimizer = MoveMinimizer::new(Arc::new(DecrementNeighborhood), objective.clone());
+
+        let result = local_search_loop(
+            &objective,
+            vec![3, 0, 2],
+            |solution| minimizer.improve(solution),
+            |_, _, _| true,
+        );
+
+        assert_eq!(result.solution(), &vec![0, 0, 0]);
+        assert_eq!(
+            result.objective_value().as_vec(),
+            &vec![BaseValue::Integer(0)]
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! [`RecursionDepthPolicy`] decides how deep the recursion improvers
+//! ([`TakeFirstRecursion`][super::TakeFirstRecursion] and `TakeAnyRecursion`) recurse, depending
+//! on the state of the search.
+use crate::objective::ObjectiveValue;
+use alloc::boxed::Box;
+
+/// The state of the search that is passed to a [`RecursionDepthPolicy`] whenever the simple pass
+/// (i.e., the exploration of the neighborhood of the solution to be improved) of a recursion
+/// improver found no improving neighbor.
+pub struct RecursionState<'a> {
+    /// The recursion depth configured in the improver, which is never exceeded.
+    pub max_recursion_depth: u8,
+    /// The number of calls of the improver in which the simple pass found no improving neighbor
+    /// (including the current call).
+    pub simple_pass_failures: u32,
+    /// The objective value of the solution to be improved.
+    pub objective_value: &'a ObjectiveValue,
+}
+
+/// Type for a function that returns the recursion depth (capped by the
+/// [`max_recursion_depth`][RecursionState::max_recursion_depth]) after the simple pass of a
+/// recursion improver failed. Depth 0 means that the improver gives up without recursion.
+/// * If an improver has no policy, it always recurses up to its configured depth (see
+///   [`full_recursion_depth`]).
+/// * Useful policies are [`recursion_after_failures`] and [`recursion_near_target`].
+pub type RecursionDepthPolicy = Box<dyn Fn(&RecursionState) -> u8 + Send + Sync>;
+
+/// Always recurses up to the configured depth (the behavior without policy).
+pub fn full_recursion_depth() -> RecursionDepthPolicy {
+    Box::new(|state| state.max_recursion_depth)
+}
+
+/// Recurses only after the simple pass has failed at least `failures` times, e.g., if the
+/// improver is reused by an iterated local search and the expensive recursion should only start
+/// once the simple moves are exhausted.
+pub fn recursion_after_failures(failures: u32) -> RecursionDepthPolicy {
+    Box::new(move |state| {
+        if state.simple_pass_failures >= failures {
+            state.max_recursion_depth
+        } else {
+            0
+        }
+    })
+}
+
+/// Recurses only if the objective value is close to the `target` objective value, i.e., the gap
+/// (as float, see [`BaseValue::to_f64`][crate::objective::BaseValue::to_f64]) on the most
+/// important level in which they differ is at most `max_gap`.
+pub fn recursion_near_target(target: ObjectiveValue, max_gap: f64) -> RecursionDepthPolicy {
+    Box::new(move |state| {
+        let gap = state
+            .objective_value
+            .iter()
+            .zip(target.iter())
+            .find(|(value, target_value)| value != target_value)
+            .map_or(0.0, |(value, target_value)| {
+                value.to_f64() - target_value.to_f64()
+            });
+        if gap <= max_gap {
+            state.max_recursion_depth
+        } else {
+            0
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::BaseValue;
+    use alloc::vec;
+
+    #[test]
+    fn test_policies() {
+        let objective_value =
+            ObjectiveValue::new(vec![BaseValue::Integer(0), BaseValue::Integer(7)]);
+        let state = |simple_pass_failures| RecursionState {
+            max_recursion_depth: 3,
+            simple_pass_failures,
+            objective_value: &objective_value,
+        };
+
+        assert_eq!(full_recursion_depth()(&state(1)), 3);
+        assert_eq!(recursion_after_failures(2)(&state(1)), 0);
+        assert_eq!(recursion_after_failures(2)(&state(2)), 3);
+
+        let target = ObjectiveValue::new(vec![BaseValue::Integer(0), BaseValue::Integer(5)]);
+        assert_eq!(recursion_near_target(target.clone(), 2.0)(&state(1)), 3);
+        assert_eq!(recursion_near_target(target, 1.5)(&state(1)), 0);
+    }
+}
@@ -0,0 +1,144 @@
+//! [`RecursionMemoryLimit`] bounds the number and the estimated size of the solutions that the
+//! recursion improvers ([`TakeFirstRecursion`][super::TakeFirstRecursion] and
+//! `TakeAnyRecursion`) retain for the next recursion level.
+use crate::objective::EvaluatedSolution;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Type for a function that estimates the number of bytes of a solution (including its heap
+/// allocations).
+pub type SizeEstimate<S> = Box<dyn Fn(&S) -> usize + Send + Sync>;
+
+/// A hard cap on the solutions that are retained for the recursion of
+/// [`TakeFirstRecursion`][super::TakeFirstRecursion] and `TakeAnyRecursion`, such that large
+/// solution types cannot exhaust the memory.
+/// * `max_solutions` is the maximal number of retained solutions (in addition to the
+///   `recursion_width`, which `TakeAnyRecursion` only applies per thread).
+/// * `max_bytes` is the maximal total size of the retained solutions, measured by the
+///   `size_estimate`. If no `size_estimate` is given, the stack size of `S` is used.
+/// * The best solutions are kept. If even the best solution exceeds `max_bytes`, no solution is
+///   retained (i.e., there is no recursion).
+/// * The limit counts how often it trims (see [`statistics`][RecursionMemoryLimit::statistics]).
+///   The same limit can be shared by several improvers (e.g., via an
+///   [`Arc`][alloc::sync::Arc]) to obtain joint statistics.
+pub struct RecursionMemoryLimit<S> {
+    max_solutions: Option<usize>,
+    max_bytes: Option<usize>,
+    size_estimate: Option<SizeEstimate<S>>,
+    trims_by_solution_count: AtomicUsize,
+    trims_by_bytes: AtomicUsize,
+    trimmed_solutions: AtomicUsize,
+}
+
+/// How often a [`RecursionMemoryLimit`] trimmed the retained solutions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecursionMemoryStatistics {
+    /// The number of trims because of `max_solutions`.
+    pub trims_by_solution_count: usize,
+    /// The number of trims because of `max_bytes`.
+    pub trims_by_bytes: usize,
+    /// The total number of solutions that were dropped by the trims.
+    pub trimmed_solutions: usize,
+}
+
+impl<S> RecursionMemoryLimit<S> {
+    /// Creates a new [`RecursionMemoryLimit`] with the given caps (`None` means no cap).
+    /// * `size_estimate` is only used for `max_bytes`. If `None`, the stack size of `S` is used,
+    ///   which underestimates solutions with heap allocations (e.g., [`Vec`]s).
+    pub fn new(
+        max_solutions: Option<usize>,
+        max_bytes: Option<usize>,
+        size_estimate: Option<SizeEstimate<S>>,
+    ) -> RecursionMemoryLimit<S> {
+        RecursionMemoryLimit {
+            max_solutions,
+            max_bytes,
+            size_estimate,
+            trims_by_solution_count: AtomicUsize::new(0),
+            trims_by_bytes: AtomicUsize::new(0),
+            trimmed_solutions: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns how often the retained solutions were trimmed so far.
+    pub fn statistics(&self) -> RecursionMemoryStatistics {
+        RecursionMemoryStatistics {
+            trims_by_solution_count: self.trims_by_solution_count.load(Ordering::Relaxed),
+            trims_by_bytes: self.trims_by_bytes.load(Ordering::Relaxed),
+            trimmed_solutions: self.trimmed_solutions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Trims the `solutions` (sorted best first) to the caps.
+    pub(crate) fn trim(&self, solutions: &mut Vec<EvaluatedSolution<S>>) {
+        let length_before = solutions.len();
+        if let Some(max_solutions) = self.max_solutions {
+            if solutions.len() > max_solutions {
+                solutions.truncate(max_solutions);
+                self.trims_by_solution_count.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            let mut total_bytes = 0;
+            let fitting = solutions
+                .iter()
+                .take_while(|solution| {
+                    total_bytes += match &self.size_estimate {
+                        Some(size_estimate) => size_estimate(solution.solution()),
+                        None => core::mem::size_of::<S>(),
+                    };
+                    total_bytes <= max_bytes
+                })
+                .count();
+            if fitting < solutions.len() {
+                solutions.truncate(fitting);
+                self.trims_by_bytes.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.trimmed_solutions
+            .fetch_add(length_before - solutions.len(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{BaseValue, Indicator, Objective};
+    use alloc::{string::String, vec};
+
+    struct Length;
+
+    impl Indicator<Vec<u8>> for Length {
+        fn evaluate(&self, solution: &Vec<u8>) -> BaseValue {
+            BaseValue::Integer(solution.len() as i64)
+        }
+        fn name(&self) -> String {
+            String::from("Length")
+        }
+    }
+
+    #[test]
+    fn test_trim() {
+        let objective = Objective::new_single_indicator_per_level(vec![Box::new(Length)]);
+        let solutions = || {
+            (1..=5)
+                .map(|length| objective.evaluate(vec![0; length]))
+                .collect::<Vec<_>>()
+        };
+
+        let limit = RecursionMemoryLimit::new(Some(4), Some(6), Some(Box::new(Vec::len)));
+        let mut retained = solutions();
+        limit.trim(&mut retained);
+        // 1 + 2 + 3 bytes fit, the fourth solution is too large
+        assert_eq!(retained.len(), 3);
+        assert_eq!(
+            limit.statistics(),
+            RecursionMemoryStatistics {
+                trims_by_solution_count: 1,
+                trims_by_bytes: 1,
+                trimmed_solutions: 2,
+            }
+        );
+    }
+}
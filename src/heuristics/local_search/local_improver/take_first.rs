@@ -1,19 +1,20 @@
 //! [`TakeFirst`] takes the first improving neighbor according to the order of the neighborhood
 //! iterator.
 use super::super::Neighborhood;
-use super::LocalImprover;
+use super::{deadline_reached, Deadline, LocalImprover};
+use crate::heuristics::common::NeighborCounts;
 use crate::objective::EvaluatedSolution;
 use crate::objective::Objective;
-use std::sync::Arc;
+use alloc::sync::Arc;
 
 /// Takes the first improving neighbor according to the order of the neighborhood iterator.
 /// * No parallelism is used.
 /// * Works for every solution type `S`.
 /// * Is fast if the computation and the evaluating of a neighbor is cheap.
 /// * Each step is faster than the [`Minimizer`][super::Minimizer], but it might take more steps until a local optimum is
-/// reached.
+///   reached.
 /// * Works best with 'smart' [`Neighborhoods`][`Neighborhood`], e.g., if the next neighborhood iterator continues at
-/// the swaps of the last neighborhood iterator.
+///   the swaps of the last neighborhood iterator.
 pub struct TakeFirst<S> {
     neighborhood: Arc<dyn Neighborhood<S>>,
     objective: Arc<Objective<S>>,
@@ -34,9 +35,41 @@ impl<S> TakeFirst<S> {
 
 impl<S> LocalImprover<S> for TakeFirst<S> {
     fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
-        self.neighborhood
+        self.improve_counted(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.improve_counted(solution, Some(deadline)).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let mut evaluated = 0;
+        let result = self
+            .neighborhood
             .neighbors_of(solution.solution())
-            .map(|neighbor| self.objective.evaluate(neighbor))
-            .find(|neighbor| neighbor.objective_value() < solution.objective_value())
+            .take_while(|_| !deadline.as_ref().is_some_and(deadline_reached))
+            .map(|neighbor| {
+                evaluated += 1;
+                self.objective.evaluate(neighbor)
+            })
+            .find(|neighbor| self.objective.is_better(neighbor, solution));
+        if result.is_none() && deadline.as_ref().is_some_and(deadline_reached) {
+            report!(Info, "Iteration timeout reached.");
+        }
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(result.is_some()),
+            ..NeighborCounts::default()
+        };
+        (result, Some(neighbor_counts))
     }
 }
@@ -1,24 +1,40 @@
 //! [`TakeFirstRecursion`] takes the first improving solution according to the
 //! neighborhood iterator. If no improvement is found, it takes the best neighbors into recursion.
 use super::super::Neighborhood;
-use super::LocalImprover;
+use super::{
+    deadline_reached, Deadline, LocalImprover, RecursionDepthPolicy, RecursionMemoryLimit,
+    RecursionState,
+};
+use crate::heuristics::common::NeighborCounts;
 use crate::objective::EvaluatedSolution;
 use crate::objective::{Objective, ObjectiveValue};
-use std::sync::Arc;
+use alloc::sync::Arc;
+use alloc::{vec, vec::Vec};
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// Takes the first improving solution according to the neighborhood iterator.
 /// If no improvement is found, it takes the best neighbors into recursion.
 /// * Works the same as [`TakeFirst`][super::take_first::TakeFirst] but with recursion.
 /// * Repeats recursion `recursion_depth` often.
 /// * Only the best `recursion_width`-many solution are considered for recursion. (Dublicates
-/// accordings to the objective value are removed.)
+///   accordings to the objective value are removed.)
 /// * The diversification for recursion is probably low.
 /// * As there is no parallelization this improver is fully deterministic.
+/// * For large solution types, a [`RecursionMemoryLimit`] can bound the memory of the solutions
+///   retained for recursion (see [`with_memory_limit`][TakeFirstRecursion::with_memory_limit]).
+/// * For large neighborhoods (e.g., O(n³)), the number of neighbors generated per solution in the
+///   recursion can be bounded (see [`with_neighbor_limit`][TakeFirstRecursion::with_neighbor_limit]).
+/// * The recursion depth can be adapted to the state of the search by a [`RecursionDepthPolicy`]
+///   (see [`with_recursion_depth_policy`][TakeFirstRecursion::with_recursion_depth_policy]).
 pub struct TakeFirstRecursion<S> {
     recursion_depth: u8,
     recursion_width: u8,
     neighborhood: Arc<dyn Neighborhood<S>>,
     objective: Arc<Objective<S>>,
+    memory_limit: Option<Arc<RecursionMemoryLimit<S>>>,
+    neighbor_limit: Option<usize>,
+    recursion_depth_policy: Option<RecursionDepthPolicy>,
+    simple_pass_failures: AtomicU32,
 }
 
 impl<S> TakeFirstRecursion<S> {
@@ -37,38 +53,110 @@ impl<S> TakeFirstRecursion<S> {
             recursion_width,
             neighborhood,
             objective,
+            memory_limit: None,
+            neighbor_limit: None,
+            recursion_depth_policy: None,
+            simple_pass_failures: AtomicU32::new(0),
         }
     }
+
+    /// Applies the `memory_limit` to the solutions that are retained for recursion. Keep a clone
+    /// of the [`Arc`] to read the [`statistics`][RecursionMemoryLimit::statistics] after the
+    /// search.
+    pub fn with_memory_limit(mut self, memory_limit: Arc<RecursionMemoryLimit<S>>) -> Self {
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Bounds the number of neighbors that are generated for each solution in the recursion to
+    /// `neighbor_limit` (the first neighbors of the neighborhood iterator), such that the number
+    /// of evaluations does not explode for deep recursions. The neighborhood of the solution to
+    /// be improved is still explored completely.
+    pub fn with_neighbor_limit(mut self, neighbor_limit: usize) -> Self {
+        self.neighbor_limit = Some(neighbor_limit);
+        self
+    }
+
+    /// Decides the recursion depth by the `recursion_depth_policy` whenever the simple pass found
+    /// no improving neighbor (e.g., recurse only if the objective value is close to a target).
+    /// The configured `recursion_depth` is never exceeded.
+    pub fn with_recursion_depth_policy(
+        mut self,
+        recursion_depth_policy: RecursionDepthPolicy,
+    ) -> Self {
+        self.recursion_depth_policy = Some(recursion_depth_policy);
+        self
+    }
 }
 
 impl<S: Clone> LocalImprover<S> for TakeFirstRecursion<S> {
     fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.improve_counted(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.improve_counted(solution, Some(deadline)).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
         let old_objective_value = solution.objective_value();
-        self.improve_recursion(
+        let mut evaluated = 0;
+        let improved_solution = self.improve_recursion(
             vec![solution.clone()],
             old_objective_value,
             self.recursion_depth,
-        )
+            deadline,
+            &mut evaluated,
+        );
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(improved_solution.is_some()),
+            ..NeighborCounts::default()
+        };
+        (improved_solution, Some(neighbor_counts))
     }
 }
 
 impl<S: Clone> TakeFirstRecursion<S> {
     /// Returns the first improving solution in the neighborhood of the given solutions.
-    /// If no improvement is found, None is returned.
+    /// If no improvement is found (or the `deadline` is reached), None is returned.
+    /// The evaluated neighbors (of all recursion levels) are added to `evaluated`.
     fn improve_recursion(
         &self,
         solutions: Vec<EvaluatedSolution<S>>,
         objective_to_beat: &ObjectiveValue,
         remaining_recursion: u8,
+        deadline: Option<Deadline>,
+        evaluated: &mut u64,
     ) -> Option<EvaluatedSolution<S>> {
-        let neighboorhood_union = solutions
-            .iter()
-            .flat_map(|sol| self.neighborhood.neighbors_of(sol.solution()));
+        let neighbor_limit = match self.neighbor_limit {
+            Some(neighbor_limit) if remaining_recursion < self.recursion_depth => neighbor_limit,
+            _ => usize::MAX,
+        };
+        let neighboorhood_union = solutions.iter().flat_map(|sol| {
+            self.neighborhood
+                .neighbors_of(sol.solution())
+                .take(neighbor_limit)
+        });
 
         let mut counter = 0;
         let mut solutions_for_recursion: Vec<EvaluatedSolution<S>> = Vec::new();
 
+        let mut timeout_reached = false;
         let result = neighboorhood_union
+            .take_while(|_| {
+                timeout_reached = deadline.as_ref().is_some_and(deadline_reached);
+                !timeout_reached
+            })
             .map(|neighbor| {
                 counter += 1;
                 self.objective.evaluate(neighbor)
@@ -76,21 +164,32 @@ impl<S: Clone> TakeFirstRecursion<S> {
             .find(|neighbor| {
                 if remaining_recursion > 0 {
                     solutions_for_recursion.push(neighbor.clone());
-                    solutions_for_recursion
-                        .sort_unstable_by(|a, b| a.objective_value().cmp(b.objective_value()));
-                    solutions_for_recursion
-                        .dedup_by(|a, b| a.objective_value() == b.objective_value());
+                    solutions_for_recursion.sort_unstable();
+                    solutions_for_recursion.dedup(); // remove duplicates according to objective_value
                     let width = (self.recursion_width as usize).min(solutions_for_recursion.len());
                     solutions_for_recursion.truncate(width);
+                    if let Some(memory_limit) = &self.memory_limit {
+                        memory_limit.trim(&mut solutions_for_recursion);
+                    }
                 }
                 neighbor.objective_value() < objective_to_beat
             });
+        *evaluated += counter;
 
-        if result.is_none() {
-            println!("No improvement found after {} swaps.", counter);
+        if result.is_none() && timeout_reached {
+            report!(Debug, "Iteration timeout reached after {} swaps.", counter);
+            None
+        } else if result.is_none() {
+            report!(Debug, "No improvement found after {} swaps.", counter);
 
+            let remaining_recursion = if remaining_recursion == self.recursion_depth {
+                self.adapted_recursion_depth(objective_to_beat)
+            } else {
+                remaining_recursion
+            };
             if remaining_recursion > 0 {
-                println!(
+                report!(
+                    Debug,
                     "Going into recursion. Remaining depth: {}. Schedule-count: {}",
                     remaining_recursion,
                     solutions_for_recursion.len()
@@ -100,14 +199,98 @@ impl<S: Clone> TakeFirstRecursion<S> {
                     solutions_for_recursion,
                     objective_to_beat,
                     remaining_recursion - 1,
+                    deadline,
+                    evaluated,
                 )
             } else {
-                println!("No recursion-depth left.");
+                report!(Debug, "No recursion-depth left.");
                 None
             }
         } else {
-            println!("Improvement found after {} swaps.", counter);
+            report!(Debug, "Improvement found after {} swaps.", counter);
             result
         }
     }
+
+    /// Returns the recursion depth after the simple pass failed (see
+    /// [`RecursionDepthPolicy`]).
+    fn adapted_recursion_depth(&self, objective_value: &ObjectiveValue) -> u8 {
+        let simple_pass_failures = self.simple_pass_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        match &self.recursion_depth_policy {
+            Some(recursion_depth_policy) => recursion_depth_policy(&RecursionState {
+                max_recursion_depth: self.recursion_depth,
+                simple_pass_failures,
+                objective_value,
+            })
+            .min(self.recursion_depth),
+            None => self.recursion_depth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::local_search::local_improver::recursion_after_failures;
+    use crate::test_fixtures::Value;
+    use alloc::boxed::Box;
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    /// Neighborhood with ten worse neighbors that counts the generated neighbors.
+    struct TenLarger {
+        counter: Arc<AtomicU64>,
+    }
+
+    impl Neighborhood<i64> for TenLarger {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new((1..=10).map(move |delta| {
+                self.counter.fetch_add(1, Ordering::Relaxed);
+                solution + delta
+            }))
+        }
+    }
+
+    #[test]
+    fn test_neighbor_limit() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let neighborhood = Arc::new(TenLarger {
+            counter: counter.clone(),
+        });
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let solution = objective.evaluate(0);
+
+        let improver = TakeFirstRecursion::new(1, 2, neighborhood.clone(), objective.clone());
+        assert!(improver.improve(&solution).is_none());
+        assert_eq!(counter.swap(0, Ordering::Relaxed), 10 + 2 * 10);
+
+        let improver =
+            TakeFirstRecursion::new(1, 2, neighborhood, objective).with_neighbor_limit(3);
+        assert!(improver.improve(&solution).is_none());
+        assert_eq!(counter.load(Ordering::Relaxed), 10 + 2 * 3);
+    }
+
+    #[test]
+    fn test_recursion_depth_policy() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let solution = objective.evaluate(0);
+        let improver = TakeFirstRecursion::new(
+            1,
+            2,
+            Arc::new(TenLarger {
+                counter: counter.clone(),
+            }),
+            objective,
+        )
+        .with_recursion_depth_policy(recursion_after_failures(2));
+
+        // the first failure of the simple pass does not go into recursion
+        assert!(improver.improve(&solution).is_none());
+        assert_eq!(counter.swap(0, Ordering::Relaxed), 10);
+        assert!(improver.improve(&solution).is_none());
+        assert_eq!(counter.load(Ordering::Relaxed), 10 + 2 * 10);
+    }
 }
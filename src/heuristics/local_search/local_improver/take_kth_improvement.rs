@@ -0,0 +1,186 @@
+//! [`TakeKthImprovement`] skips the first `k - 1` improving neighbors and takes the `k`-th one,
+//! optionally in a shuffled order of the neighborhood.
+use super::super::Neighborhood;
+use super::{deadline_reached, Deadline, LocalImprover};
+use crate::heuristics::common::NeighborCounts;
+use crate::objective::EvaluatedSolution;
+use crate::objective::Objective;
+#[cfg(feature = "rand")]
+use alloc::vec::Vec;
+use alloc::{boxed::Box, sync::Arc};
+#[cfg(feature = "rand")]
+use rand::{rngs::StdRng, seq::SliceRandom, RngCore, SeedableRng};
+#[cfg(feature = "rand")]
+use std::sync::Mutex;
+
+/// Takes the `k`-th improving neighbor according to the order of the neighborhood iterator, i.e.,
+/// the first `k - 1` improving neighbors are skipped.
+/// * With `k = 1`, it behaves like [`TakeFirst`][super::TakeFirst].
+/// * If there are fewer than `k` improving neighbors, the last improving neighbor is taken.
+///   Hence, `None` is only returned for a local optimum.
+/// * With the `rand` feature, the neighbors can be shuffled (with a seed) before scanning (see
+///   [`with_shuffled_order`][TakeKthImprovement::with_shuffled_order]), which gives cheap
+///   stochasticity to a plain local search. As the whole neighborhood is collected for
+///   shuffling, this is only suitable for small neighborhoods.
+/// * No parallelism is used.
+/// * If a deadline is given (see [`improve_until`][LocalImprover::improve_until]), the scan stops
+///   at the deadline and the last improving neighbor found so far is taken.
+pub struct TakeKthImprovement<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    objective: Arc<Objective<S>>,
+    k: usize,
+    #[cfg(feature = "rand")]
+    rng: Option<Mutex<Box<dyn RngCore + Send>>>,
+}
+
+impl<S> TakeKthImprovement<S> {
+    /// Creates a new [`TakeKthImprovement`] with the given [`Neighborhood`], [`Objective`] and
+    /// `k`.
+    /// * Panics if `k` is zero.
+    pub fn new(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        k: usize,
+    ) -> TakeKthImprovement<S> {
+        assert!(k > 0, "k must be at least 1.");
+        TakeKthImprovement {
+            neighborhood,
+            objective,
+            k,
+            #[cfg(feature = "rand")]
+            rng: None,
+        }
+    }
+
+    /// Shuffles the neighbors before scanning them. A `random_seed` can be provided to make the
+    /// order reproducible. Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn with_shuffled_order(self, random_seed: Option<u64>) -> TakeKthImprovement<S> {
+        self.with_shuffled_order_by(match random_seed {
+            Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+            None => Box::new(StdRng::from_entropy()),
+        })
+    }
+
+    /// Same as [`with_shuffled_order`][TakeKthImprovement::with_shuffled_order], but the
+    /// neighbors are shuffled by the given random number generator (e.g., a faster or a recorded
+    /// one). Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn with_shuffled_order_by(mut self, rng: Box<dyn RngCore + Send>) -> TakeKthImprovement<S> {
+        self.rng = Some(Mutex::new(rng));
+        self
+    }
+
+    /// Returns the neighbors of the `solution` in the order in which they are scanned.
+    fn neighbors_of<'a>(&'a self, solution: &'a S) -> Box<dyn Iterator<Item = S> + 'a> {
+        #[cfg(feature = "rand")]
+        if let Some(rng) = &self.rng {
+            let mut neighbors: Vec<S> = self.neighborhood.neighbors_of(solution).collect();
+            neighbors.shuffle(&mut *rng.lock().unwrap());
+            return Box::new(neighbors.into_iter());
+        }
+        Box::new(self.neighborhood.neighbors_of(solution))
+    }
+}
+
+impl<S> LocalImprover<S> for TakeKthImprovement<S> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        self.improve_counted(solution, None).0
+    }
+
+    fn improve_until(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Deadline,
+    ) -> Option<EvaluatedSolution<S>> {
+        self.improve_counted(solution, Some(deadline)).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        deadline: Option<Deadline>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        let mut timeout_reached = false;
+        let mut improvements = 0;
+        let mut evaluated = 0;
+        let mut last_improving_neighbor = None;
+        for neighbor in self.neighbors_of(solution.solution()) {
+            if deadline.as_ref().is_some_and(deadline_reached) {
+                timeout_reached = true;
+                break;
+            }
+            let neighbor = self.objective.evaluate(neighbor);
+            evaluated += 1;
+            if self.objective.is_better(&neighbor, solution) {
+                improvements += 1;
+                last_improving_neighbor = Some(neighbor);
+                if improvements == self.k {
+                    break;
+                }
+            }
+        }
+        if timeout_reached {
+            report!(Info, "Iteration timeout reached.");
+        }
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(last_improving_neighbor.is_some()),
+            ..NeighborCounts::default()
+        };
+        (last_improving_neighbor, Some(neighbor_counts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Value;
+
+    /// The neighbors are `solution - 1, ..., solution - 5` (all improving).
+    struct DecreaseByUpToFive;
+
+    impl Neighborhood<i64> for DecreaseByUpToFive {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new((1..=5).map(move |step| solution - step))
+        }
+    }
+
+    #[test]
+    fn test_take_kth_improvement() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let solution = objective.evaluate(100);
+
+        let take_third =
+            TakeKthImprovement::new(Arc::new(DecreaseByUpToFive), objective.clone(), 3);
+        assert_eq!(*take_third.improve(&solution).unwrap().solution(), 97);
+
+        // fewer than k improving neighbors: the last one is taken
+        let take_tenth =
+            TakeKthImprovement::new(Arc::new(DecreaseByUpToFive), objective.clone(), 10);
+        assert_eq!(*take_tenth.improve(&solution).unwrap().solution(), 95);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_shuffled_order_is_reproducible() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let solution = objective.evaluate(100);
+        let improvements = |seed| {
+            let improver =
+                TakeKthImprovement::new(Arc::new(DecreaseByUpToFive), objective.clone(), 1)
+                    .with_shuffled_order(Some(seed));
+            (0..20)
+                .map(|_| *improver.improve(&solution).unwrap().solution())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(improvements(7), improvements(7));
+        // the first improving neighbor is no longer always the first of the neighborhood
+        assert!(improvements(7).iter().any(|neighbor| *neighbor != 99));
+    }
+}
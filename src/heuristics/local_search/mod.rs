@@ -3,47 +3,116 @@
 //! There are several [`local_improvers`][`local_improver`] (neighborhood exploration stategies)
 //! to choose from.
 //! * Starts with an initial solution and iteratively improves it by exploring the neighborhood
-//! of the current solution.
+//!   of the current solution.
 //! * The search stops after a certain number of iterations, after a certain time limit, or if no
-//! improvement is found in the neighborhood (local minimum is reached).
+//!   improvement is found in the neighborhood (local minimum is reached).
 //! * The last solution (which is the best found) is returned.
+//! * [`LocalSearchSolver::preset_small`] and [`LocalSearchSolver::preset_large`] choose the
+//!   local improver and the limits for small and large
+//!   neighborhoods, which is a good starting point for first-time users.
 //!
 //! For examples, see the [basic local search solver][crate::examples::tsp::solvers::basic_local_search] and
 //! the [take first local search solver][crate::examples::tsp::solvers::take_first_local_search] for the TSP.
+//! If evaluating the neighbors is the bottleneck, see the [batch parallel local search
+//! solver][crate::examples::tsp::solvers::batch_parallel_local_search].
+//!
+//! The iteration itself is done by the [`local_search_loop`], which neither measures time nor
+//! prints and is therefore also available without the `std` feature.
+//!
+//! To advance the search one iteration at a time (e.g., in an interactive planning UI or a
+//! teaching demo), [`LocalSearchSolver::steps`] returns a [`LocalSearchSteps`] iterator, whose
+//! current solution can be inspected and replaced between the iterations.
 pub mod local_improver;
 
+#[cfg(feature = "std")]
+use std::cell::Cell;
+#[cfg(feature = "std")]
 use std::sync::Arc;
+#[cfg(feature = "std")]
 use std::time as stdtime;
 
 use crate::objective::EvaluatedSolution;
 use crate::objective::Objective;
+#[cfg(feature = "std")]
+use crate::objective::ObjectiveValue;
+#[cfg(feature = "std")]
 use local_improver::LocalImprover;
 
-use self::local_improver::Minimizer;
+#[cfg(feature = "std")]
+use self::local_improver::{deadline_reached, Minimizer};
+#[cfg(feature = "std")]
+use self::local_improver::{TakeFirst, TakeFirstRecursion};
 
+#[cfg(feature = "std")]
 use super::common::default_function_between_steps;
+#[cfg(feature = "std")]
+use super::common::report_termination;
+#[cfg(feature = "std")]
 use super::common::FunctionBetweenSteps;
 use super::common::Neighborhood;
+#[cfg(feature = "std")]
+use super::common::{
+    SearchRecorder, SearchResult, SolverDescription, TerminationCriteria, TerminationReason,
+};
+#[cfg(feature = "std")]
 use super::Solver;
 
+/// The sequential local search loop: Evaluates the `initial_solution` and replaces the current
+/// solution by the result of `improve` until `improve` returns `None` (local minimum is reached)
+/// or `after_step` returns `false`.
+/// * `improve` is usually the [`improve`][local_improver::LocalImprover::improve] method of one
+///   of the [`local_improvers`][local_improver].
+/// * `after_step` is called after each improvement step with the iteration number (starting
+///   with 1), the new solution and the previous solution. It returns `false` to stop the search
+///   (e.g., if an iteration limit is reached).
+/// * The last solution (which is the best found) is returned.
+/// * The loop neither measures time nor prints, so it is also available without the `std`
+///   feature. The [`LocalSearchSolver`] is built on top of it.
+pub fn local_search_loop<S>(
+    objective: &Objective<S>,
+    initial_solution: S,
+    mut improve: impl FnMut(&EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>>,
+    mut after_step: impl FnMut(u32, &EvaluatedSolution<S>, &EvaluatedSolution<S>) -> bool,
+) -> EvaluatedSolution<S> {
+    let mut current_solution = objective.evaluate(initial_solution);
+    let mut iteration_counter = 1;
+    while let Some(new_solution) = improve(&current_solution) {
+        let continue_search = after_step(iteration_counter, &new_solution, &current_solution);
+        current_solution = new_solution;
+        if !continue_search {
+            break;
+        }
+        iteration_counter += 1;
+    }
+    current_solution
+}
+
 /// A local search solver that uses a [`Neighborhood`] and an [`Objective`] to find a local minimum.
 /// * There are a variety of [`LocalImprovers`][`LocalImprover`] that can be used with this solver.
 /// * The `function_between_steps` is executed after each improvement step.
 /// * The deafult [`LocalImprover`] (if `None`) is [`Minimizer`].
 /// * The default `function_between_steps` (if `None`) is printing the iteration number, the objective value
-/// (in comparison the the previous objective value) and the time elapsed since the start.
+///   (in comparison the the previous objective value) and the time elapsed since the start.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason, the objective trajectory and the iterations in which the levels became
+///   zero. The neighbors are only counted if the [`LocalImprover`] counts them (see
+///   [`improve_counted`][LocalImprover::improve_counted]), otherwise the counts are `None`.
 ///
 /// For a high-level overview, see the [module documentation][super::local_search] and for examples, see the
 /// [basic local search solver][crate::examples::tsp::solvers::basic_local_search] and
 /// the [take first local search solver][crate::examples::tsp::solvers::take_first_local_search] for the TSP.
+/// Requires the `std` feature (use the [`local_search_loop`] otherwise).
+#[cfg(feature = "std")]
 pub struct LocalSearchSolver<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
     objective: Arc<Objective<S>>,
-    local_improver: Box<dyn LocalImprover<S>>,
+    local_improver: Option<Box<dyn LocalImprover<S>>>,
     function_between_steps: FunctionBetweenSteps<S>,
-    time_limit: Option<stdtime::Duration>,
-    iteration_limit: Option<u32>,
+    iteration_timeout: Option<stdtime::Duration>,
+    termination_criteria: Option<TerminationCriteria>,
 }
 
+#[cfg(feature = "std")]
 impl<S: 'static> LocalSearchSolver<S> {
     /// Creates a new [`LocalSearchSolver`] with the given [`Neighborhood`] and [`Objective`].
     /// Uses the default [`LocalImprover`] ([`Minimizer`]) and the default `function_between_steps` (print
@@ -52,76 +121,554 @@ impl<S: 'static> LocalSearchSolver<S> {
         neighborhood: Arc<dyn Neighborhood<S>>,
         objective: Arc<Objective<S>>,
     ) -> Self {
-        Self::with_options(neighborhood, objective, None, None, None, None)
+        Self::with_options(neighborhood, objective, None, None, None)
     }
 
     /// Creates a new [`LocalSearchSolver`] with the given [`Neighborhood`] and [`Objective`].
     /// * `local_improver` (implementing [`LocalImprover`]) specifies the how the neighborhood is
-    /// explored. If `None`, the default is [`Minimizer`].
+    ///   explored. If `None`, the default is [`Minimizer`].
     /// * `function_between_steps` is executed after each improvement step. If `None`, the default
-    /// is printing the iteration number, the objective value (in comparison the the previous
-    /// objective value) and the time elapsed since the start.
-    /// * `time_limit` is the maximum time allowed for the local search to start a new iteration.
-    /// The last iteration is allowed to finish. If `None`, there is no time limit.
-    /// * `iteration_limit` is the maximum number of iterations allowed for the local search. If
-    /// `None`, there is no iteration limit.
-    /// * If both `time_limit` and `iteration_limit` are set, the search stops when either limit is
-    /// reached.
+    ///   is printing the iteration number, the objective value (in comparison the the previous
+    ///   objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit, an iteration
+    ///   limit or a target objective value, are checked after each iteration with the current
+    ///   solution. The last iteration is allowed to finish. If `None`, the search stops only in a
+    ///   local minimum.
+    /// * Further options (e.g., an iteration timeout) are set by the `with_*` methods.
     pub fn with_options(
         neighborhood: Arc<dyn Neighborhood<S>>,
         objective: Arc<Objective<S>>,
         local_improver: Option<Box<dyn LocalImprover<S>>>,
         function_between_steps: Option<FunctionBetweenSteps<S>>,
-        time_limit: Option<stdtime::Duration>,
-        iteration_limit: Option<u32>,
+        termination_criteria: Option<TerminationCriteria>,
     ) -> Self {
-        let local_improver = match local_improver {
-            Some(local_improver) => local_improver,
-            None => Box::new(Minimizer::new(neighborhood, objective.clone()))
-                as Box<dyn LocalImprover<S>>,
-        };
         Self {
+            neighborhood,
             objective,
             local_improver,
             function_between_steps: function_between_steps
                 .unwrap_or(default_function_between_steps()),
-            time_limit,
-            iteration_limit,
+            iteration_timeout: None,
+            termination_criteria,
         }
     }
+
+    /// Limits the time of a single iteration (i.e., a single call of the [`LocalImprover`]) to
+    /// `iteration_timeout`, such that a huge neighborhood cannot stall the solver.
+    /// * On timeout, the best improving neighbor found so far is taken (see
+    ///   [`improve_until`][LocalImprover::improve_until]).
+    /// * If no improving neighbor was found until the timeout, the search stops with
+    ///   [`TerminationReason::IterationTimeout`] (as the solution is not necessarily a local
+    ///   optimum).
+    pub fn with_iteration_timeout(mut self, iteration_timeout: stdtime::Duration) -> Self {
+        self.iteration_timeout = Some(iteration_timeout);
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Clone + 'static> LocalSearchSolver<S> {
+    /// Creates a [`LocalSearchSolver`] with defaults for small problems, i.e., neighborhoods with
+    /// up to a few thousand neighbors, which are cheap to explore:
+    /// * The neighborhood is explored by a [`TakeFirstRecursion`] with recursion depth 2 and
+    ///   recursion width 5, such that shallow local minima are escaped.
+    /// * The search stops in a local minimum or after 60 seconds.
+    ///
+    /// For other defaults, see [`preset_large`][LocalSearchSolver::preset_large] and
+    /// [`with_options`][LocalSearchSolver::with_options].
+    pub fn preset_small(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+    ) -> Self {
+        let local_improver = TakeFirstRecursion::new(2, 5, neighborhood.clone(), objective.clone());
+        Self::with_options(
+            neighborhood,
+            objective,
+            Some(Box::new(local_improver)),
+            None,
+            Some(TerminationCriteria::time_limit(
+                stdtime::Duration::from_secs(60),
+            )),
+        )
+    }
+
+    /// Creates a [`LocalSearchSolver`] with defaults for large problems, i.e., neighborhoods with
+    /// millions of neighbors (e.g., O(n³) moves), whose complete exploration is expensive:
+    /// * The neighborhood is explored by [`TakeFirst`] (no recursion), i.e., each iteration ends
+    ///   with the first improving neighbor.
+    /// * A single iteration is stopped after 10 seconds (taking the improving neighbor found so
+    ///   far, see [`with_iteration_timeout`][LocalSearchSolver::with_iteration_timeout]).
+    /// * The search stops in a local minimum or after 300 seconds.
+    pub fn preset_large(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+    ) -> Self {
+        let local_improver = TakeFirst::new(neighborhood.clone(), objective.clone());
+        Self::with_options(
+            neighborhood,
+            objective,
+            Some(Box::new(local_improver)),
+            None,
+            Some(TerminationCriteria::time_limit(
+                stdtime::Duration::from_secs(300),
+            )),
+        )
+        .with_iteration_timeout(stdtime::Duration::from_secs(10))
+    }
 }
 
-impl<S> Solver<S> for LocalSearchSolver<S> {
+#[cfg(feature = "std")]
+impl<S: 'static> Solver<S> for LocalSearchSolver<S> {
     fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
         let start_time = stdtime::Instant::now();
 
-        let mut current_solution = self.objective.evaluate(initial_solution);
-        let mut iteration_counter = 1;
-        while let Some(new_solution) = self.local_improver.improve(&current_solution) {
-            (self.function_between_steps)(
-                iteration_counter,
-                &new_solution,
-                Some(&current_solution),
-                self.objective.clone(),
-                Some(start_time),
-                self.time_limit,
-                self.iteration_limit,
-            );
-            current_solution = new_solution;
-            if let Some(time_limit) = self.time_limit {
-                if stdtime::Instant::now().duration_since(start_time) > time_limit {
-                    println!("Time limit reached.");
-                    break;
-                }
+        let default_local_improver;
+        let local_improver: &dyn LocalImprover<S> = match &self.local_improver {
+            Some(local_improver) => local_improver.as_ref(),
+            None => {
+                default_local_improver =
+                    Minimizer::new(self.neighborhood.clone(), self.objective.clone());
+                &default_local_improver
             }
-            if let Some(iteration_limit) = self.iteration_limit {
-                if iteration_counter >= iteration_limit {
-                    println!("Iteration limit reached.");
-                    break;
+        };
+        local_improver.reset_panic();
+
+        let mut iterations = 0;
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        let mut termination_criteria = self.termination_criteria.clone();
+        let time_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_time_limit);
+        let iteration_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_iteration_limit);
+        // the recorder starts with the evaluated initial solution, which is first seen in the
+        // first step (or at the end if there is no step)
+        let new_recorder = |initial_objective_value: &ObjectiveValue| {
+            SearchRecorder::new(
+                &self.function_between_steps,
+                self.objective.clone(),
+                start_time,
+                time_limit,
+                iteration_limit,
+                initial_objective_value,
+                true,
+            )
+        };
+        let mut recorder = None;
+        // the counts of the last exploration are passed from `improve` to `after_step`
+        let neighbor_counts_of_step = Cell::new(None);
+        // whether the last exploration was stopped by the iteration timeout without improvement
+        let iteration_timed_out = Cell::new(false);
+        let best_solution = local_search_loop(
+            &self.objective,
+            initial_solution,
+            |current_solution| {
+                let deadline = self
+                    .iteration_timeout
+                    .map(|iteration_timeout| stdtime::Instant::now() + iteration_timeout);
+                let (improved_solution, neighbor_counts) =
+                    local_improver.improve_counted(current_solution, deadline);
+                neighbor_counts_of_step.set(neighbor_counts);
+                iteration_timed_out.set(
+                    improved_solution.is_none() && deadline.as_ref().is_some_and(deadline_reached),
+                );
+                improved_solution
+            },
+            |iteration_counter, new_solution, previous_solution| {
+                recorder
+                    .get_or_insert_with(|| new_recorder(previous_solution.objective_value()))
+                    .step(
+                        iteration_counter,
+                        new_solution,
+                        Some(previous_solution),
+                        neighbor_counts_of_step.get(),
+                    );
+                iterations = iteration_counter;
+                if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                    criteria.is_met(
+                        iteration_counter,
+                        start_time.elapsed(),
+                        new_solution.objective_value(),
+                    )
+                }) {
+                    report_termination(reason);
+                    termination_reason = reason;
+                    return false;
                 }
-            }
-            iteration_counter += 1;
+                true
+            },
+        );
+        let panic_message = local_improver.panic_message();
+        if termination_reason == TerminationReason::LocalOptimum && panic_message.is_some() {
+            termination_reason = TerminationReason::Panicked;
+        } else if termination_reason == TerminationReason::LocalOptimum && iteration_timed_out.get()
+        {
+            termination_reason = TerminationReason::IterationTimeout;
+            report_termination(termination_reason);
+        }
+
+        SearchResult {
+            panic_message,
+            ..recorder
+                .unwrap_or_else(|| new_recorder(best_solution.objective_value()))
+                .finish(best_solution, iterations, termination_reason)
+        }
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("LocalSearchSolver")
+            .with_parameter(
+                "local_improver",
+                self.local_improver
+                    .as_ref()
+                    .map_or(String::from("Minimizer"), |local_improver| {
+                        local_improver.name()
+                    }),
+            )
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+            .with_duration("iteration_timeout", self.iteration_timeout)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S> LocalSearchSolver<S> {
+    /// Returns a [`LocalSearchSteps`] iterator that advances the search from the
+    /// `initial_solution` one iteration at a time.
+    pub fn steps(&self, initial_solution: S) -> LocalSearchSteps<'_, S> {
+        LocalSearchSteps {
+            default_local_improver: self
+                .local_improver
+                .is_none()
+                .then(|| Minimizer::new(self.neighborhood.clone(), self.objective.clone())),
+            current_solution: self.objective.evaluate(initial_solution),
+            iteration: 0,
+            solver: self,
         }
-        current_solution
+    }
+}
+
+/// Advances the search of a [`LocalSearchSolver`] one iteration at a time (see
+/// [`LocalSearchSolver::steps`]).
+/// * Each call of [`step`][LocalSearchSteps::step] (or [`next`][Iterator::next]) replaces the
+///   current solution by an improving neighbor (found by the [`LocalImprover`] of the solver
+///   within the `iteration_timeout`). It returns `None` if the current solution is a local
+///   minimum (or if no improving neighbor was found within the `iteration_timeout`). The search
+///   can be resumed after the current solution was replaced.
+/// * Between the iterations, the current solution can be inspected by
+///   [`current_solution`][LocalSearchSteps::current_solution] and replaced by
+///   [`set_current_solution`][LocalSearchSteps::set_current_solution].
+/// * The caller controls the loop, so the `function_between_steps`, the limits and the target
+///   objective value of the solver are not applied.
+#[cfg(feature = "std")]
+pub struct LocalSearchSteps<'a, S> {
+    solver: &'a LocalSearchSolver<S>,
+    default_local_improver: Option<Minimizer<S>>,
+    current_solution: EvaluatedSolution<S>,
+    iteration: u32,
+}
+
+#[cfg(feature = "std")]
+impl<S> LocalSearchSteps<'_, S> {
+    /// Performs one iteration and returns the new current solution, or `None` if the current
+    /// solution is a local minimum.
+    pub fn step(&mut self) -> Option<&EvaluatedSolution<S>> {
+        let local_improver: &dyn LocalImprover<S> = match &self.solver.local_improver {
+            Some(local_improver) => local_improver.as_ref(),
+            None => self.default_local_improver.as_ref().unwrap(),
+        };
+        let new_solution = match self.solver.iteration_timeout {
+            Some(iteration_timeout) => local_improver.improve_until(
+                &self.current_solution,
+                stdtime::Instant::now() + iteration_timeout,
+            ),
+            None => local_improver.improve(&self.current_solution),
+        }?;
+        self.current_solution = new_solution;
+        self.iteration += 1;
+        Some(&self.current_solution)
+    }
+
+    /// Returns the current solution.
+    pub fn current_solution(&self) -> &EvaluatedSolution<S> {
+        &self.current_solution
+    }
+
+    /// Replaces the current solution (e.g., after a manual change), from which the next
+    /// iteration continues.
+    pub fn set_current_solution(&mut self, solution: S) {
+        self.current_solution = self.solver.objective.evaluate(solution);
+    }
+
+    /// Returns the number of iterations performed so far.
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    /// Consumes the steps and returns the current solution.
+    pub fn into_current_solution(self) -> EvaluatedSolution<S> {
+        self.current_solution
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Clone> Iterator for LocalSearchSteps<'_, S> {
+    type Item = EvaluatedSolution<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step().cloned()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::NeighborCounts;
+    use crate::objective::{BaseValue, Indicator};
+    use crate::test_fixtures::{DistanceTo, Value};
+
+    /// An infinite neighborhood, which can only be stopped by the iteration timeout.
+    struct EndlessNeighborhood;
+
+    impl Neighborhood<i64> for EndlessNeighborhood {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new((1..).map(move |delta| solution - delta))
+        }
+    }
+
+    #[test]
+    fn test_iteration_timeout() {
+        let solver = LocalSearchSolver::with_options(
+            Arc::new(EndlessNeighborhood),
+            Arc::new(Objective::new_single_indicator(Box::new(Value))),
+            None,
+            None,
+            Some(TerminationCriteria::iteration_limit(3)),
+        )
+        .with_iteration_timeout(stdtime::Duration::from_millis(1));
+
+        let result = solver.solve(0);
+
+        // each of the three iterations returns the best neighbor found within the timeout
+        assert!(*result.solution() <= -3);
+    }
+
+    #[test]
+    fn test_iteration_timeout_without_improving_neighbor() {
+        let solver = LocalSearchSolver::with_options(
+            Arc::new(EndlessNeighborhood),
+            Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(0)))),
+            Some(Box::new(TakeFirst::new(
+                Arc::new(EndlessNeighborhood),
+                Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(0)))),
+            ))),
+            None,
+            None,
+        )
+        .with_iteration_timeout(stdtime::Duration::from_millis(1));
+
+        let result = solver.solve_with_report(0);
+
+        // all neighbors are worse, but the neighborhood is not explored completely
+        assert_eq!(*result.best_solution.solution(), 0);
+        assert_eq!(
+            result.termination_reason,
+            TerminationReason::IterationTimeout
+        );
+    }
+
+    /// Neighborhood that only decreases the value by one.
+    struct DecreaseByOne;
+
+    impl Neighborhood<i64> for DecreaseByOne {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new(std::iter::once(solution - 1))
+        }
+    }
+
+    /// Decreases the value by one or two, but not below zero.
+    struct DecreaseToZero;
+
+    impl Neighborhood<i64> for DecreaseToZero {
+        fn neighbors_of<'a>(
+            &'a self,
+            solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new([solution - 1, solution - 2].into_iter().filter(|n| *n >= 0))
+        }
+    }
+
+    #[test]
+    fn test_presets() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        for solver in [
+            LocalSearchSolver::preset_small(Arc::new(DecreaseToZero), objective.clone()),
+            LocalSearchSolver::preset_large(Arc::new(DecreaseToZero), objective.clone()),
+        ] {
+            let report = solver.solve_with_report(10);
+            assert_eq!(*report.best_solution.solution(), 0);
+            assert_eq!(report.termination_reason, TerminationReason::LocalOptimum);
+        }
+        let large = LocalSearchSolver::preset_large(Arc::new(DecreaseToZero), objective);
+        assert_eq!(
+            large.iteration_timeout,
+            Some(stdtime::Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn test_target_objective_value() {
+        let solver = LocalSearchSolver::with_options(
+            Arc::new(DecreaseByOne),
+            Arc::new(Objective::new_single_indicator(Box::new(Value))),
+            None,
+            None,
+            Some(
+                TerminationCriteria::target_objective_value(ObjectiveValue::new(vec![
+                    BaseValue::Integer(5),
+                ]))
+                .or(TerminationCriteria::iteration_limit(100)),
+            ),
+        );
+
+        // the search stops as soon as the target is reached instead of running 100 iterations
+        assert_eq!(*solver.solve(10).solution(), 5);
+
+        let report = solver.solve_with_report(10);
+        assert_eq!(report.iterations, Some(5));
+        assert_eq!(report.evaluated_neighbors(), Some(5));
+        assert_eq!(
+            report.trajectory[1].neighbor_counts,
+            Some(NeighborCounts {
+                generated: 1,
+                evaluated: 1,
+                accepted: 1,
+                ..NeighborCounts::default()
+            })
+        );
+        assert_eq!(
+            report.total_neighbor_counts().map(|counts| counts.accepted),
+            Some(5)
+        );
+        assert_eq!(
+            report.termination_reason,
+            TerminationReason::TargetObjectiveValue
+        );
+        assert_eq!(
+            report
+                .trajectory
+                .iter()
+                .map(|point| point.objective_value.as_vec()[0])
+                .collect::<Vec<_>>(),
+            (5..=10).rev().map(BaseValue::Integer).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_termination_criteria() {
+        let solver = LocalSearchSolver::with_options(
+            Arc::new(DecreaseByOne),
+            Arc::new(Objective::new_single_indicator(Box::new(Value))),
+            None,
+            None,
+            Some(TerminationCriteria::all_of(vec![
+                TerminationCriteria::iteration_limit(2),
+                TerminationCriteria::target_objective_value(ObjectiveValue::new(vec![
+                    BaseValue::Integer(5),
+                ])),
+            ])),
+        );
+
+        // both criteria have to be met, so the search does not stop after two iterations
+        let report = solver.solve_with_report(10);
+        assert_eq!(*report.best_solution.solution(), 5);
+        assert_eq!(report.termination_reason, TerminationReason::IterationLimit);
+    }
+
+    struct ExcessOverThree;
+
+    impl Indicator<i64> for ExcessOverThree {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer((solution - 3).max(0))
+        }
+
+        fn name(&self) -> String {
+            String::from("ExcessOverThree")
+        }
+    }
+
+    #[test]
+    fn test_milestones() {
+        let objective = Objective::builder()
+            .level()
+            .indicator(ExcessOverThree)
+            .named("violation")
+            .next_level()
+            .indicator(Value)
+            .build();
+        let solver = LocalSearchSolver::with_options(
+            Arc::new(DecreaseByOne),
+            Arc::new(objective),
+            None,
+            None,
+            Some(TerminationCriteria::iteration_limit(6)),
+        );
+
+        let report = solver.solve_with_report(6);
+
+        assert_eq!(
+            report
+                .milestones
+                .iter()
+                .map(|milestone| (milestone.level_name.as_str(), milestone.iteration))
+                .collect::<Vec<_>>(),
+            vec![("violation", 3), ("Value", 6)]
+        );
+        assert_eq!(report.milestone(1).unwrap().level, 1);
+        assert_eq!(
+            solver.solve_with_report(2).milestone(0).unwrap().iteration,
+            0
+        );
+    }
+
+    #[test]
+    fn test_steps() {
+        let solver = LocalSearchSolver::initialize(
+            Arc::new(DecreaseByOne),
+            Arc::new(Objective::new_single_indicator(Box::new(Value))),
+        );
+        let mut steps = solver.steps(10);
+
+        assert_eq!(*steps.step().unwrap().solution(), 9);
+        assert_eq!(
+            steps
+                .by_ref()
+                .take(2)
+                .map(|solution| *solution.solution())
+                .collect::<Vec<_>>(),
+            vec![8, 7]
+        );
+        assert_eq!(*steps.current_solution().solution(), 7);
+
+        // the current solution can be replaced between the iterations
+        steps.set_current_solution(100);
+        assert_eq!(*steps.step().unwrap().solution(), 99);
+        assert_eq!(steps.iteration(), 4);
+        assert_eq!(*steps.into_current_solution().solution(), 99);
     }
 }
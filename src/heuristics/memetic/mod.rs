@@ -0,0 +1,330 @@
+//! This module contains the [`MemeticSolver`] implementing a
+//! [memetic algorithm](https://en.wikipedia.org/wiki/Memetic_algorithm), i.e., a genetic
+//! algorithm whose offspring are improved by a local search.
+//! * Starts with a [`Population`] of the initial solution and further solutions of an
+//!   [`InitialSolutionGenerator`] (each improved to a local optimum).
+//! * In each iteration, two parents are chosen by tournament selection and combined by a
+//!   [`Crossover`]. The offspring is mutated by a [`Mutation`] with a certain probability and
+//!   then improved to a local optimum by an [`OffspringImprover`].
+//! * The offspring replaces the worst member of the population if it is better (steady-state
+//!   replacement, see [`Population::insert`]).
+//! * Without an [`OffspringImprover`], the solver is a plain (steady-state) genetic algorithm.
+//! * The search stops after a certain number of iterations or after a certain time limit.
+//! * The best member of the population is returned.
+//!
+//! For an example, see the [memetic solver for the TSP][crate::examples::tsp::solvers::memetic].
+use std::sync::Arc;
+use std::time as stdtime;
+
+use rand::Rng;
+
+use super::common::default_rng_factory;
+use super::common::RngFactory;
+use super::common::SolverDescription;
+use super::common::{
+    default_function_between_steps, report_termination, Crossover, FunctionBetweenSteps,
+    InitialSolutionGenerator, Mutation, Neighborhood, Population, SearchRecorder, SearchResult,
+    TerminationCriteria,
+};
+use super::local_search::local_improver::{LocalImprover, Minimizer};
+use super::local_search::local_search_loop;
+use super::parallel_local_search::parallel_local_improver::ParallelLocalImprover;
+use super::Solver;
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// Improves the offspring of the [`MemeticSolver`] to a local optimum, either by a sequential
+/// [`LocalImprover`] or by a [`ParallelLocalImprover`] (which runs sequentially without the
+/// `rayon` feature).
+pub enum OffspringImprover<S> {
+    /// A sequential [`LocalImprover`] (e.g., [`Minimizer`]).
+    Sequential(Box<dyn LocalImprover<S>>),
+    /// A [`ParallelLocalImprover`] (e.g.,
+    /// [`ParallelMinimizer`][super::parallel_local_search::parallel_local_improver::ParallelMinimizer]).
+    Parallel(Box<dyn ParallelLocalImprover<S>>),
+}
+
+impl<S> OffspringImprover<S> {
+    fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
+        match self {
+            OffspringImprover::Sequential(local_improver) => local_improver.improve(solution),
+            OffspringImprover::Parallel(local_improver) => local_improver.improve(solution),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            OffspringImprover::Sequential(local_improver) => local_improver.name(),
+            OffspringImprover::Parallel(local_improver) => local_improver.name(),
+        }
+    }
+}
+
+/// A memetic solver that combines a genetic algorithm ([`Crossover`] and [`Mutation`] on a
+/// [`Population`]) with an [`OffspringImprover`] (local search).
+/// * The initial population consists of the initial solution and `population_size - 1`
+///   solutions of the [`InitialSolutionGenerator`] (with `start_index` 1, 2, ...).
+/// * The default [`OffspringImprover`] of [`initialize`][MemeticSolver::initialize] is the
+///   [`Minimizer`]. [`genetic_algorithm`][MemeticSolver::genetic_algorithm] creates a solver
+///   without improver.
+/// * The `function_between_steps` is executed whenever a new best solution is found.
+/// * The default `function_between_steps` (if `None`) is printing the iteration number, the
+///   objective value (in comparison the the previous best objective value) and the time elapsed
+///   since the start.
+/// * The search stops if the [`TerminationCriteria`] (e.g., a time limit or a maximal number of
+///   iterations, i.e., offspring) are met. They must be set.
+/// * The best member of the population is returned.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the trajectory of the best solution seen (without neighbor counts).
+///
+/// For a high-level overview, see the [module documentation][super::memetic] and for an example,
+/// see the [memetic solver for the TSP][crate::examples::tsp::solvers::memetic].
+pub struct MemeticSolver<S> {
+    objective: Arc<Objective<S>>,
+    initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+    crossover: Box<dyn Crossover<S>>,
+    mutation: Option<Box<dyn Mutation<S>>>,
+    offspring_improver: Option<OffspringImprover<S>>,
+    population_size: usize,
+    tournament_size: usize,
+    mutation_probability: f64,
+    random_seed: Option<u64>,
+    rng_factory: RngFactory,
+    function_between_steps: FunctionBetweenSteps<S>,
+    termination_criteria: Option<TerminationCriteria>,
+}
+
+impl<S: 'static> MemeticSolver<S> {
+    /// Creates a new [`MemeticSolver`] with the given [`Neighborhood`] (explored by the
+    /// [`Minimizer`]), [`Crossover`], [`Mutation`], [`InitialSolutionGenerator`], [`Objective`],
+    /// population size and the maximal number of iterations (i.e., offspring).
+    /// * The tournament size is 2 and each offspring is mutated.
+    /// * A `random_seed` can be provided to make the search reproducible.
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        crossover: Box<dyn Crossover<S>>,
+        mutation: Box<dyn Mutation<S>>,
+        initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+        objective: Arc<Objective<S>>,
+        population_size: usize,
+        iteration_limit: u32,
+        random_seed: Option<u64>,
+    ) -> Self {
+        let offspring_improver = OffspringImprover::Sequential(Box::new(Minimizer::new(
+            neighborhood,
+            objective.clone(),
+        )));
+        Self::with_options(
+            crossover,
+            initial_solution_generator,
+            objective,
+            population_size,
+            Some(mutation),
+            Some(offspring_improver),
+            None,
+            None,
+            random_seed,
+            None,
+            Some(TerminationCriteria::iteration_limit(iteration_limit)),
+        )
+    }
+
+    /// Creates a new [`MemeticSolver`] without [`OffspringImprover`], i.e., a plain
+    /// (steady-state) genetic algorithm with the given [`Crossover`], [`Mutation`],
+    /// [`InitialSolutionGenerator`], [`Objective`], population size and the maximal number of
+    /// iterations (i.e., offspring).
+    /// * The tournament size is 2 and each offspring is mutated.
+    /// * A `random_seed` can be provided to make the search reproducible.
+    pub fn genetic_algorithm(
+        crossover: Box<dyn Crossover<S>>,
+        mutation: Box<dyn Mutation<S>>,
+        initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+        objective: Arc<Objective<S>>,
+        population_size: usize,
+        iteration_limit: u32,
+        random_seed: Option<u64>,
+    ) -> Self {
+        Self::with_options(
+            crossover,
+            initial_solution_generator,
+            objective,
+            population_size,
+            Some(mutation),
+            None,
+            None,
+            None,
+            random_seed,
+            None,
+            Some(TerminationCriteria::iteration_limit(iteration_limit)),
+        )
+    }
+
+    /// Creates a new [`MemeticSolver`] with the given [`Crossover`], [`InitialSolutionGenerator`],
+    /// [`Objective`] and `population_size` (at least 2).
+    /// * `mutation` is applied to the offspring with the `mutation_probability`. If `None`, no
+    ///   mutation is applied.
+    /// * `offspring_improver` improves each offspring (and the initial population) to a local
+    ///   optimum. If `None`, the offspring are not improved (genetic algorithm).
+    /// * `tournament_size` is the number of members drawn for the selection of each parent. If
+    ///   `None`, the default is 2.
+    /// * `mutation_probability` is the probability that an offspring is mutated. If `None`, the
+    ///   default is 1.0 (i.e., each offspring is mutated).
+    /// * `random_seed` can be provided to make the search reproducible.
+    /// * `function_between_steps` is executed whenever a new best solution is found. If `None`,
+    ///   the default is printing the iteration number, the objective value (in comparison the
+    ///   the previous best objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit or a maximum
+    ///   number of offspring, are checked after each offspring with the best solution seen. The
+    ///   last iteration is allowed to finish. They must be set, as the search does not stop by
+    ///   itself.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        crossover: Box<dyn Crossover<S>>,
+        initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+        objective: Arc<Objective<S>>,
+        population_size: usize,
+        mutation: Option<Box<dyn Mutation<S>>>,
+        offspring_improver: Option<OffspringImprover<S>>,
+        tournament_size: Option<usize>,
+        mutation_probability: Option<f64>,
+        random_seed: Option<u64>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> Self {
+        if termination_criteria.is_none() {
+            panic!("The `termination_criteria` must be set.");
+        }
+        Self {
+            objective,
+            initial_solution_generator,
+            crossover,
+            mutation,
+            offspring_improver,
+            population_size: population_size.max(2),
+            tournament_size: tournament_size.unwrap_or(2),
+            mutation_probability: mutation_probability.unwrap_or(1.0),
+            random_seed,
+            rng_factory: default_rng_factory(),
+            function_between_steps: function_between_steps
+                .unwrap_or(default_function_between_steps()),
+            termination_criteria,
+        }
+    }
+
+    /// Replaces the [`RngFactory`] (default: [`StdRng`][rand::rngs::StdRng], see
+    /// [`default_rng_factory`]), which creates the random number generator of each run from the
+    /// `random_seed`. The generator is used for the parent selection and is passed to the
+    /// [`Crossover`] and the [`Mutation`].
+    pub fn with_rng_factory(mut self, rng_factory: RngFactory) -> Self {
+        self.rng_factory = rng_factory;
+        self
+    }
+}
+
+impl<S> MemeticSolver<S> {
+    /// Evaluates the `solution` and improves it to a local optimum (if there is an
+    /// [`OffspringImprover`]).
+    fn improve(&self, solution: S) -> EvaluatedSolution<S> {
+        match &self.offspring_improver {
+            Some(offspring_improver) => local_search_loop(
+                &self.objective,
+                solution,
+                |current_solution| offspring_improver.improve(current_solution),
+                |_, _, _| true,
+            ),
+            None => self.objective.evaluate(solution),
+        }
+    }
+}
+
+impl<S: Clone> Solver<S> for MemeticSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        let start_time = stdtime::Instant::now();
+        let mut rng = (self.rng_factory)(self.random_seed);
+
+        let mut population = Population::new(self.population_size, self.objective.clone());
+        population.insert(self.improve(initial_solution));
+        for start_index in 1..self.population_size {
+            population.insert(self.improve(self.initial_solution_generator.generate(start_index)));
+        }
+        let mut best_solution_seen = population.best().unwrap().clone();
+        let mut termination_criteria = self.termination_criteria.clone();
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_time_limit),
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_iteration_limit),
+            best_solution_seen.objective_value(),
+            true,
+        );
+
+        let mut iteration_counter = 1;
+        let termination_reason = loop {
+            let parent1 = population.select_parent(self.tournament_size, &mut rng);
+            let parent2 = population.select_parent(self.tournament_size, &mut rng);
+            let mut offspring =
+                self.crossover
+                    .crossover(parent1.solution(), parent2.solution(), &mut rng);
+            if let Some(mutation) = &self.mutation {
+                if rng.gen::<f64>() < self.mutation_probability {
+                    offspring = mutation.mutate(offspring, &mut rng);
+                }
+            }
+            let offspring = self.improve(offspring);
+
+            if self.objective.is_better(&offspring, &best_solution_seen) {
+                recorder.step(
+                    iteration_counter,
+                    &offspring,
+                    Some(&best_solution_seen),
+                    None,
+                );
+                best_solution_seen = offspring.clone();
+            }
+            population.insert(offspring);
+
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                break reason;
+            }
+            iteration_counter += 1;
+        };
+
+        recorder.finish(best_solution_seen, iteration_counter, termination_reason)
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("MemeticSolver")
+            .with_parameter("population_size", self.population_size)
+            .with_parameter("tournament_size", self.tournament_size)
+            .with_parameter("mutation_probability", self.mutation_probability)
+            .with_optional_parameter(
+                "offspring_improver",
+                self.offspring_improver
+                    .as_ref()
+                    .map(|offspring_improver| offspring_improver.name()),
+            )
+            .with_optional_parameter("random_seed", self.random_seed)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+    }
+}
@@ -2,15 +2,97 @@
 
 use crate::objective::EvaluatedSolution;
 pub mod common;
+#[cfg(feature = "std")]
+use common::{RunSummary, SearchResult, SolverDescription};
+#[cfg(feature = "rand")]
+pub mod construction;
+#[cfg(feature = "std")]
+pub mod great_deluge;
+#[cfg(feature = "rand")]
+pub mod iterated_local_search;
+#[cfg(feature = "rand")]
+pub mod large_neighborhood_search;
 pub mod local_search;
+#[cfg(feature = "rand")]
+pub mod memetic;
+#[cfg(feature = "rand")]
+pub mod nsga2;
+#[cfg(feature = "std")]
 pub mod parallel_local_search;
+#[cfg(feature = "rand")]
+pub mod parallel_simulated_annealing;
+#[cfg(feature = "std")]
 pub mod parallel_tabu_search;
+#[cfg(feature = "std")]
+pub mod pareto_local_search;
+#[cfg(feature = "rand")]
 pub mod simulated_annealing;
+#[cfg(feature = "std")]
 pub mod tabu_search;
+#[cfg(feature = "std")]
 pub mod threshold_accepting;
 
 /// All local-search-based solvers implement this trait.
 pub trait Solver<S> {
     /// Solves the problem starting from the given initial solution.
     fn solve(&self, initial_solution: S) -> EvaluatedSolution<S>;
+
+    /// Solves the problem like [`solve`][Solver::solve], but returns a [`SearchResult`] with the
+    /// number of iterations, the wall-clock time, the termination reason, the objective
+    /// trajectory with the neighbor counts of each step and the level milestones (e.g., when the
+    /// first feasible solution was found).
+    /// * The default implementation only measures the wall-clock time (see
+    ///   [`SearchResult::without_details`]). The solvers of this crate report all details, where
+    ///   the trajectory consists of the steps passed to their `function_between_steps`.
+    /// * Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        let start_time = std::time::Instant::now();
+        let best_solution = self.solve(initial_solution);
+        SearchResult::without_details(best_solution, start_time.elapsed())
+    }
+
+    /// Returns the configured parameters of the solver (e.g., cooling factor, tabu list size,
+    /// limits and the name of the improver), e.g., for printing the configuration at the start
+    /// of a run.
+    /// * The default implementation only returns the name of the solver type without
+    ///   parameters.
+    /// * Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new(&common::short_type_name::<Self>())
+    }
+
+    /// Solves each of the independent initial solutions `instances` with this (configured)
+    /// solver one after another and returns the [`RunSummary`] with the result of each instance
+    /// (identified by its index in `instances`) and statistics over all results.
+    /// * Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn solve_batch(&self, instances: Vec<S>) -> RunSummary<S> {
+        RunSummary::new(
+            instances
+                .into_iter()
+                .map(|instance| self.solve(instance))
+                .enumerate()
+                .collect(),
+        )
+    }
+
+    /// Same as [`solve_batch`][Solver::solve_batch], but the instances are solved in parallel.
+    /// * Without the `rayon` feature, the instances are solved sequentially.
+    /// * Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn solve_batch_parallel(&self, instances: Vec<S>) -> RunSummary<S>
+    where
+        Self: Sized + Sync,
+        S: Send,
+    {
+        #[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+        use crate::parallel::{IntoParallelIterator, ParallelIterator};
+        let results: Vec<EvaluatedSolution<S>> = instances
+            .into_par_iter()
+            .map(|instance| self.solve(instance))
+            .collect();
+        RunSummary::new(results.into_iter().enumerate().collect())
+    }
 }
@@ -0,0 +1,455 @@
+//! This module contains the [`Nsga2Solver`] implementing the evolutionary multi-objective
+//! algorithm NSGA-II, which approximates the Pareto front of several (minimized)
+//! [`Indicators`][Indicator] instead of optimizing a hierarchical [`Objective`][crate::objective::Objective].
+//! * Starts with a population of the initial solution and further solutions of an
+//!   [`InitialSolutionGenerator`].
+//! * In each generation, as many offspring as the population size are created by a
+//!   [`Crossover`] of two parents (chosen by binary tournaments) and a [`Mutation`] (with a
+//!   certain probability).
+//! * Parents and offspring are sorted into fronts by [`non_dominated_sorting`]. The next
+//!   population is filled front by front. The last front that does not fit completely is
+//!   truncated by the [`crowding_distances`], preferring solutions in less crowded regions.
+//! * The binary tournaments prefer the lower front and, within the same front, the larger
+//!   crowding distance.
+//! * The search stops if the [`TerminationCriteria`] are met, e.g., after a certain number of
+//!   generations or after a certain time limit.
+//! * The first front of the final population is returned as [`ParetoFront`].
+//!
+//! For hard constraints, the hierarchical objective with its constraint levels is still
+//! preferable (e.g., by a [`ParetoLocalSearchSolver`][super::pareto_local_search::ParetoLocalSearchSolver]
+//! on selected levels). Constraints can also be added as an indicator of the violation, which is
+//! then one of the objectives of the front.
+use std::cmp::Ordering;
+use std::sync::Arc;
+use std::time as stdtime;
+
+use rand::{Rng, RngCore};
+
+use super::common::default_rng_factory;
+use super::common::RngFactory;
+use super::common::{
+    report_termination, Crossover, InitialSolutionGenerator, Mutation, ParetoFront, ParetoPoint,
+    SolverDescription, TerminationCriteria,
+};
+use crate::objective::{BaseValue, Indicator, ObjectiveValue};
+
+/// Sorts the `values` (one vector of minimized indicator values per solution) into fronts by
+/// Pareto dominance: The first front contains the indices of the non-dominated solutions, the
+/// second front the solutions that are only dominated by solutions of the first front, and so
+/// on.
+pub fn non_dominated_sorting(values: &[Vec<BaseValue>]) -> Vec<Vec<usize>> {
+    let objective_values: Vec<ObjectiveValue> =
+        values.iter().cloned().map(ObjectiveValue::new).collect();
+    let indicators: Vec<usize> = (0..values.first().map_or(0, Vec::len)).collect();
+    let mut dominated_solutions: Vec<Vec<usize>> = vec![Vec::new(); values.len()];
+    let mut domination_counts = vec![0; values.len()];
+    for i in 0..values.len() {
+        for j in 0..values.len() {
+            if objective_values[i].dominates(&objective_values[j], &indicators) {
+                dominated_solutions[i].push(j);
+            } else if objective_values[j].dominates(&objective_values[i], &indicators) {
+                domination_counts[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut current_front: Vec<usize> = (0..values.len())
+        .filter(|&i| domination_counts[i] == 0)
+        .collect();
+    while !current_front.is_empty() {
+        let mut next_front = Vec::new();
+        for &i in &current_front {
+            for &j in &dominated_solutions[i] {
+                domination_counts[j] -= 1;
+                if domination_counts[j] == 0 {
+                    next_front.push(j);
+                }
+            }
+        }
+        fronts.push(current_front);
+        current_front = next_front;
+    }
+    fronts
+}
+
+/// Returns the crowding distance of each solution of the `front` (indices into `values`, in the
+/// order of the front): the sum over all indicators of the distance between the two neighbors of
+/// the solution (normalized by the range of the indicator in the front). The extreme solutions
+/// of each indicator have an infinite crowding distance.
+pub fn crowding_distances(values: &[Vec<BaseValue>], front: &[usize]) -> Vec<f64> {
+    let mut distances = vec![0.0; front.len()];
+    if front.is_empty() {
+        return distances;
+    }
+    for (indicator, _) in values[front[0]].iter().enumerate() {
+        let value = |position: usize| values[front[position]][indicator].to_f64();
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+        let range = value(order[order.len() - 1]) - value(order[0]);
+        distances[order[0]] = f64::INFINITY;
+        distances[order[order.len() - 1]] = f64::INFINITY;
+        if range > 0.0 {
+            for window in order.windows(3) {
+                distances[window[1]] += (value(window[2]) - value(window[0])) / range;
+            }
+        }
+    }
+    distances
+}
+
+/// A solution of the population together with its indicator values, front rank and crowding
+/// distance.
+struct Individual<S> {
+    solution: S,
+    indicator_values: Vec<BaseValue>,
+    rank: usize,
+    crowding_distance: f64,
+}
+
+impl<S> Individual<S> {
+    /// Returns [`Ordering::Less`] if `self` wins a binary tournament against `other`.
+    fn crowded_comparison(&self, other: &Individual<S>) -> Ordering {
+        self.rank
+            .cmp(&other.rank)
+            .then_with(|| other.crowding_distance.total_cmp(&self.crowding_distance))
+    }
+}
+
+/// The NSGA-II solver uses a [`Crossover`], a [`Mutation`] and an [`InitialSolutionGenerator`]
+/// to approximate the Pareto front of several (minimized) [`Indicators`][Indicator].
+/// * The initial population consists of the initial solution and `population_size - 1`
+///   solutions of the [`InitialSolutionGenerator`] (with `start_index` 1, 2, ...).
+/// * The [`TerminationCriteria`] are checked after each generation. The generations count as
+///   iterations and the lexicographically smallest indicator values of the first front as the
+///   best objective value.
+/// * In contrast to the other solvers, [`solve`][Nsga2Solver::solve] returns a [`ParetoFront`]
+///   (whose points have no weights) instead of a single solution, so the solver does not
+///   implement [`Solver`][super::Solver].
+///
+/// For a high-level overview, see the [module documentation][super::nsga2].
+pub struct Nsga2Solver<S> {
+    indicators: Vec<Arc<dyn Indicator<S>>>,
+    initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+    crossover: Box<dyn Crossover<S>>,
+    mutation: Box<dyn Mutation<S>>,
+    population_size: usize,
+    mutation_probability: f64,
+    random_seed: Option<u64>,
+    rng_factory: RngFactory,
+    termination_criteria: Option<TerminationCriteria>,
+}
+
+impl<S> Nsga2Solver<S> {
+    /// Creates a new [`Nsga2Solver`] with the given indicators, [`Crossover`], [`Mutation`],
+    /// [`InitialSolutionGenerator`], population size and the maximal number of generations.
+    /// * Each offspring is mutated.
+    /// * A `random_seed` can be provided to make the search reproducible.
+    pub fn initialize(
+        indicators: Vec<Arc<dyn Indicator<S>>>,
+        crossover: Box<dyn Crossover<S>>,
+        mutation: Box<dyn Mutation<S>>,
+        initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+        population_size: usize,
+        generation_limit: u32,
+        random_seed: Option<u64>,
+    ) -> Self {
+        Self::with_options(
+            indicators,
+            crossover,
+            mutation,
+            initial_solution_generator,
+            population_size,
+            None,
+            random_seed,
+            Some(TerminationCriteria::iteration_limit(generation_limit)),
+        )
+    }
+
+    /// Creates a new [`Nsga2Solver`] with the given indicators, [`Crossover`], [`Mutation`],
+    /// [`InitialSolutionGenerator`] and `population_size` (at least 2).
+    /// * `mutation_probability` is the probability that an offspring is mutated. If `None`, the
+    ///   default is 1.0 (i.e., each offspring is mutated).
+    /// * `random_seed` can be provided to make the search reproducible.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit or a maximal
+    ///   number of generations, are checked after each generation. The last generation is allowed
+    ///   to finish.
+    /// * The `termination_criteria` must be set and at least one indicator must be given.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        indicators: Vec<Arc<dyn Indicator<S>>>,
+        crossover: Box<dyn Crossover<S>>,
+        mutation: Box<dyn Mutation<S>>,
+        initial_solution_generator: Box<dyn InitialSolutionGenerator<S>>,
+        population_size: usize,
+        mutation_probability: Option<f64>,
+        random_seed: Option<u64>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> Self {
+        if termination_criteria.is_none() {
+            panic!("The `termination_criteria` must be set.");
+        }
+        assert!(
+            !indicators.is_empty(),
+            "At least one indicator must be given."
+        );
+        Self {
+            indicators,
+            initial_solution_generator,
+            crossover,
+            mutation,
+            population_size: population_size.max(2),
+            mutation_probability: mutation_probability.unwrap_or(1.0),
+            random_seed,
+            rng_factory: default_rng_factory(),
+            termination_criteria,
+        }
+    }
+
+    /// Replaces the [`RngFactory`] (default: [`StdRng`][rand::rngs::StdRng], see
+    /// [`default_rng_factory`]), which creates the random number generator of each run from the
+    /// `random_seed`. The generator is used for the tournaments and is passed to the
+    /// [`Crossover`] and the [`Mutation`].
+    pub fn with_rng_factory(mut self, rng_factory: RngFactory) -> Self {
+        self.rng_factory = rng_factory;
+        self
+    }
+
+    /// Returns the configured parameters of the solver.
+    pub fn describe(&self) -> SolverDescription {
+        SolverDescription::new("Nsga2Solver")
+            .with_parameter(
+                "indicators",
+                self.indicators
+                    .iter()
+                    .map(|indicator| indicator.name())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+            .with_parameter("population_size", self.population_size)
+            .with_parameter("mutation_probability", self.mutation_probability)
+            .with_optional_parameter("random_seed", self.random_seed)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+    }
+
+    fn individual(&self, solution: S) -> Individual<S> {
+        let indicator_values = self
+            .indicators
+            .iter()
+            .map(|indicator| indicator.evaluate(&solution))
+            .collect();
+        Individual {
+            solution,
+            indicator_values,
+            rank: 0,
+            crowding_distance: 0.0,
+        }
+    }
+
+    /// Selects the `population_size` best individuals by fronts and crowding distances and sets
+    /// their ranks and crowding distances.
+    fn select_survivors(&self, individuals: Vec<Individual<S>>) -> Vec<Individual<S>> {
+        let values: Vec<Vec<BaseValue>> = individuals
+            .iter()
+            .map(|individual| individual.indicator_values.clone())
+            .collect();
+        let mut individuals: Vec<Option<Individual<S>>> =
+            individuals.into_iter().map(Some).collect();
+        let mut survivors = Vec::with_capacity(self.population_size);
+        for (rank, front) in non_dominated_sorting(&values).into_iter().enumerate() {
+            if survivors.len() >= self.population_size {
+                break;
+            }
+            let distances = crowding_distances(&values, &front);
+            let mut positions: Vec<usize> = (0..front.len()).collect();
+            positions.sort_by(|&a, &b| distances[b].total_cmp(&distances[a]));
+            positions.truncate(self.population_size - survivors.len());
+            for position in positions {
+                let mut individual = individuals[front[position]].take().unwrap();
+                individual.rank = rank;
+                individual.crowding_distance = distances[position];
+                survivors.push(individual);
+            }
+        }
+        survivors
+    }
+}
+
+impl<S: Clone> Nsga2Solver<S> {
+    /// Runs the search starting from the `initial_solution` and returns the first front of the
+    /// final population.
+    pub fn solve(&self, initial_solution: S) -> ParetoFront<S> {
+        let start_time = stdtime::Instant::now();
+        let mut rng = (self.rng_factory)(self.random_seed);
+        let mut termination_criteria = self.termination_criteria.clone();
+
+        let mut population: Vec<Individual<S>> = Some(initial_solution)
+            .into_iter()
+            .chain(
+                (1..self.population_size)
+                    .map(|start_index| self.initial_solution_generator.generate(start_index)),
+            )
+            .map(|solution| self.individual(solution))
+            .collect();
+        population = self.select_survivors(population);
+
+        let mut generation_counter = 1;
+        loop {
+            let offspring: Vec<Individual<S>> = (0..self.population_size)
+                .map(|_| {
+                    let parent1 = binary_tournament(&population, &mut rng);
+                    let parent2 = binary_tournament(&population, &mut rng);
+                    let mut offspring =
+                        self.crossover
+                            .crossover(&parent1.solution, &parent2.solution, &mut rng);
+                    if rng.gen::<f64>() < self.mutation_probability {
+                        offspring = self.mutation.mutate(offspring, &mut rng);
+                    }
+                    self.individual(offspring)
+                })
+                .collect();
+            population.extend(offspring);
+            population = self.select_survivors(population);
+            report!(
+                Debug,
+                "Generation {}: {} solutions in the first front.",
+                generation_counter,
+                population
+                    .iter()
+                    .filter(|individual| individual.rank == 0)
+                    .count()
+            );
+
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                let best_objective_value = population
+                    .iter()
+                    .filter(|individual| individual.rank == 0)
+                    .map(|individual| ObjectiveValue::new(individual.indicator_values.clone()))
+                    .min()
+                    .unwrap();
+                criteria.is_met(
+                    generation_counter,
+                    start_time.elapsed(),
+                    &best_objective_value,
+                )
+            }) {
+                report_termination(reason);
+                break;
+            }
+            generation_counter += 1;
+        }
+
+        let mut pareto_front = ParetoFront::new();
+        for individual in population
+            .into_iter()
+            .filter(|individual| individual.rank == 0)
+        {
+            pareto_front.insert(ParetoPoint {
+                weights: Vec::new(),
+                solution: individual.solution,
+                indicator_values: individual.indicator_values,
+            });
+        }
+        pareto_front
+    }
+}
+
+/// Returns the winner of a tournament of two uniformly drawn individuals.
+fn binary_tournament<'a, S>(
+    population: &'a [Individual<S>],
+    rng: &mut dyn RngCore,
+) -> &'a Individual<S> {
+    let a = &population[rng.gen_range(0..population.len())];
+    let b = &population[rng.gen_range(0..population.len())];
+    if b.crowded_comparison(a).is_lt() {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SquaredDistanceTo(i64);
+
+    impl Indicator<i64> for SquaredDistanceTo {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer((solution - self.0) * (solution - self.0))
+        }
+        fn name(&self) -> String {
+            format!("SquaredDistanceTo{}", self.0)
+        }
+    }
+
+    struct Average;
+
+    impl Crossover<i64> for Average {
+        fn crossover(&self, parent1: &i64, parent2: &i64, _rng: &mut dyn RngCore) -> i64 {
+            (parent1 + parent2).div_euclid(2)
+        }
+    }
+
+    struct RandomStep;
+
+    impl Mutation<i64> for RandomStep {
+        fn mutate(&self, solution: i64, rng: &mut dyn RngCore) -> i64 {
+            solution + rng.gen_range(-2..=2)
+        }
+    }
+
+    struct Spread;
+
+    impl InitialSolutionGenerator<i64> for Spread {
+        fn generate(&self, start_index: usize) -> i64 {
+            (start_index as i64 * 7) % 40 - 20
+        }
+    }
+
+    #[test]
+    fn test_non_dominated_sorting() {
+        let values: Vec<Vec<BaseValue>> = [[1, 5], [2, 2], [5, 1], [3, 3], [6, 6]]
+            .iter()
+            .map(|v| v.iter().map(|&x| BaseValue::Integer(x)).collect())
+            .collect();
+
+        assert_eq!(
+            non_dominated_sorting(&values),
+            vec![vec![0, 1, 2], vec![3], vec![4]]
+        );
+        let distances = crowding_distances(&values, &[0, 1, 2]);
+        assert_eq!(distances[0], f64::INFINITY);
+        assert_eq!(distances[1], 2.0);
+        assert_eq!(distances[2], f64::INFINITY);
+    }
+
+    #[test]
+    fn test_nsga2() {
+        let solver = Nsga2Solver::initialize(
+            vec![
+                Arc::new(SquaredDistanceTo(0)),
+                Arc::new(SquaredDistanceTo(10)),
+            ],
+            Box::new(Average),
+            Box::new(RandomStep),
+            Box::new(Spread),
+            12,
+            30,
+            Some(42),
+        );
+
+        let pareto_front = solver.solve(30);
+
+        assert!(pareto_front.len() >= 5);
+        assert!(pareto_front
+            .points()
+            .iter()
+            .all(|point| (0..=10).contains(&point.solution)));
+    }
+}
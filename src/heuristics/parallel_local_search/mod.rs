@@ -1,10 +1,12 @@
 //! This module contains the [`ParallelLocalSearchSolver`] implementing the
 //! [local search heuristic](https://en.wikipedia.org/wiki/Local_search_(optimization)).
 //! * Starts with an initial solution and improves it by exploring the neighborhood
-//! of the current solution in parallel.
+//!   of the current solution in parallel.
 //! * The search stops after a certain number of iterations, after a certain time limit, or if no
-//! improvement is found in the neighborhood (local minimum is reached).
+//!   improvement is found in the neighborhood (local minimum is reached).
 //! * The last solution (which is the best found) is returned.
+//! * Without the `rayon` feature, the solver is still available, but the neighborhood is explored
+//!   sequentially (see the [`parallel`][crate::parallel] module).
 //!
 //! For examples, see the [parallel local search solver][crate::examples::tsp::solvers::parallel_local_search] for the TSP.
 pub mod parallel_local_improver;
@@ -14,19 +16,33 @@ use std::time as stdtime;
 
 use crate::objective::EvaluatedSolution;
 use crate::objective::Objective;
+use crate::parallel::{install, thread_pool, ThreadPool};
 
 use self::parallel_local_improver::ParallelLocalImprover;
 use self::parallel_local_improver::ParallelMinimizer;
 
 use super::common::default_function_between_steps;
+use super::common::report_termination;
 use super::common::FunctionBetweenSteps;
 use super::common::ParallelNeighborhood;
+use super::common::SearchRecorder;
+use super::common::SearchResult;
+use super::common::SolverDescription;
+use super::common::TerminationCriteria;
+use super::common::TerminationReason;
 use super::Solver;
 
 /// A local search solver that uses a [`ParallelNeighborhood`] and an [`Objective`] to find a local minimum.
 /// * The `function_between_steps` is executed after each improvement step.
 /// * The default `function_between_steps` (if `None`) is printing the iteration number, the objective value
-/// (in comparison the the previous objective value) and the time elapsed since the start.
+///   (in comparison the the previous objective value) and the time elapsed since the start.
+/// * The search runs in the global thread pool of [`rayon`](https://docs.rs/rayon/) unless a
+///   dedicated [`ThreadPool`] is set by [`with_thread_pool`][ParallelLocalSearchSolver::with_thread_pool]
+///   or [`with_num_threads`][ParallelLocalSearchSolver::with_num_threads].
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the objective trajectory with the
+///   [`NeighborCounts`][super::common::NeighborCounts] of the [`ParallelLocalImprover`] (see
+///   [`improve_counted`][ParallelLocalImprover::improve_counted]).
 ///
 /// For a high-level overview, see the [module documentation][super::parallel_local_search] and for examples, see the
 /// [parallel local search solver][crate::examples::tsp::solvers::parallel_local_search] for the
@@ -35,8 +51,8 @@ pub struct ParallelLocalSearchSolver<S> {
     objective: Arc<Objective<S>>,
     local_improver: Box<dyn ParallelLocalImprover<S>>,
     function_between_steps: FunctionBetweenSteps<S>,
-    time_limit: Option<stdtime::Duration>,
-    iteration_limit: Option<u32>,
+    termination_criteria: Option<TerminationCriteria>,
+    thread_pool: Option<Arc<ThreadPool>>,
 }
 
 impl<S: Send + Sync + 'static> ParallelLocalSearchSolver<S> {
@@ -47,28 +63,25 @@ impl<S: Send + Sync + 'static> ParallelLocalSearchSolver<S> {
         neighborhood: Arc<impl ParallelNeighborhood<S> + 'static>,
         objective: Arc<Objective<S>>,
     ) -> Self {
-        Self::with_options(neighborhood, objective, None, None, None, None)
+        Self::with_options(neighborhood, objective, None, None, None)
     }
 
     /// Creates a new [`ParallelLocalSearchSolver`] with the given [`ParallelNeighborhood`] and [`Objective`].
     /// * `local_improver` (implementing [`ParallelLocalImprover`]) specifies the how the neighborhood is
-    /// explored. If `None`, the default is [`ParallelMinimizer`].
+    ///   explored. If `None`, the default is [`ParallelMinimizer`].
     /// * `function_between_steps` is executed after each improvement step. If `None`, the default
-    /// is printing the iteration number, the objective value (in comparison the the previous
-    /// objective value) and the time elapsed since the start.
-    /// * `time_limit` is the maximum time allowed for the local search to start a new iteration.
-    /// The last iteration is allowed to finish. If `None`, there is no time limit.
-    /// * `iteration_limit` is the maximum number of iterations allowed for the local search. If
-    /// `None`, there is no iteration limit.
-    /// * If both `time_limit` and `iteration_limit` are set, the search stops when either limit is
-    /// reached.
+    ///   is printing the iteration number, the objective value (in comparison the the previous
+    ///   objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit, an iteration
+    ///   limit or a target objective value, are checked after each iteration with the current
+    ///   solution. The last iteration is allowed to finish. If `None`, the search stops only in a
+    ///   local minimum.
     pub fn with_options(
         neighborhood: Arc<impl ParallelNeighborhood<S> + 'static>,
         objective: Arc<Objective<S>>,
         local_improver: Option<Box<dyn ParallelLocalImprover<S>>>,
         function_between_steps: Option<FunctionBetweenSteps<S>>,
-        time_limit: Option<stdtime::Duration>,
-        iteration_limit: Option<u32>,
+        termination_criteria: Option<TerminationCriteria>,
     ) -> Self {
         let local_improver = match local_improver {
             Some(local_improver) => local_improver,
@@ -80,43 +93,109 @@ impl<S: Send + Sync + 'static> ParallelLocalSearchSolver<S> {
             local_improver,
             function_between_steps: function_between_steps
                 .unwrap_or(default_function_between_steps()),
-            time_limit,
-            iteration_limit,
+            termination_criteria,
+            thread_pool: None,
         }
     }
+
+    /// Runs the search in the given [`ThreadPool`] instead of the global thread pool of
+    /// [`rayon`](https://docs.rs/rayon/), e.g., to share a pool with other parts of an
+    /// application.
+    pub fn with_thread_pool(self, thread_pool: Arc<ThreadPool>) -> Self {
+        Self {
+            thread_pool: Some(thread_pool),
+            ..self
+        }
+    }
+
+    /// Runs the search in a dedicated [`ThreadPool`] with `num_threads` threads (see
+    /// [`thread_pool`]), e.g., to benchmark at a fixed parallelism.
+    pub fn with_num_threads(self, num_threads: usize) -> Self {
+        self.with_thread_pool(Arc::new(thread_pool(num_threads)))
+    }
 }
 
-impl<S> Solver<S> for ParallelLocalSearchSolver<S> {
+impl<S: Send + Sync> Solver<S> for ParallelLocalSearchSolver<S> {
     fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        install(self.thread_pool.as_deref(), || {
+            self.local_search(initial_solution)
+        })
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("ParallelLocalSearchSolver")
+            .with_parameter("local_improver", self.local_improver.name())
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+            .with_optional_parameter(
+                "num_threads",
+                self.thread_pool
+                    .as_ref()
+                    .map(|thread_pool| thread_pool.current_num_threads()),
+            )
+    }
+}
+
+impl<S> ParallelLocalSearchSolver<S> {
+    fn local_search(&self, initial_solution: S) -> SearchResult<S> {
         let start_time = stdtime::Instant::now();
+        self.local_improver.reset_panic();
 
+        let mut termination_criteria = self.termination_criteria.clone();
         let mut current_solution = self.objective.evaluate(initial_solution);
-        let mut iteration_counter = 1;
-        while let Some(new_solution) = self.local_improver.improve(&current_solution) {
-            (self.function_between_steps)(
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_time_limit),
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_iteration_limit),
+            current_solution.objective_value(),
+            true,
+        );
+        let mut iteration_counter = 0;
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        while let (Some(new_solution), neighbor_counts) =
+            self.local_improver.improve_counted(&current_solution)
+        {
+            iteration_counter += 1;
+            recorder.step(
                 iteration_counter,
                 &new_solution,
                 Some(&current_solution),
-                self.objective.clone(),
-                Some(start_time),
-                self.time_limit,
-                self.iteration_limit,
+                neighbor_counts,
             );
             current_solution = new_solution;
-            if let Some(time_limit) = self.time_limit {
-                if stdtime::Instant::now().duration_since(start_time) > time_limit {
-                    println!("Time limit reached.");
-                    break;
-                }
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    current_solution.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
             }
-            if let Some(iteration_limit) = self.iteration_limit {
-                if iteration_counter >= iteration_limit {
-                    println!("Iteration limit reached.");
-                    break;
-                }
-            }
-            iteration_counter += 1;
         }
-        current_solution
+        let panic_message = self.local_improver.panic_message();
+        if termination_reason == TerminationReason::LocalOptimum && panic_message.is_some() {
+            termination_reason = TerminationReason::Panicked;
+        }
+        SearchResult {
+            panic_message,
+            ..recorder.finish(current_solution, iteration_counter, termination_reason)
+        }
     }
 }
@@ -4,6 +4,7 @@
 mod parallel_minimizer;
 mod take_any_recursion;
 
+use crate::heuristics::common::NeighborCounts;
 use crate::objective::EvaluatedSolution;
 pub use parallel_minimizer::ParallelMinimizer;
 pub use take_any_recursion::TakeAnyRecursion;
@@ -13,8 +14,8 @@ pub use take_any_recursion::TakeAnyRecursion;
 /// * A solver is equipped with only one [`ParallelLocalImprover`].
 /// * The [`ParallelLocalImprover`] is invoked in each iteration of the solver.
 /// * Depending on the problem and especially the
-/// computation costs of computing and evaluating neighbors, different [`ParallelLocalImprover`] might be
-/// better.
+///   computation costs of computing and evaluating neighbors, different [`ParallelLocalImprover`] might be
+///   better.
 /// * Returns `None` if there is no better solution in the [`ParallelNeighborhood`][super::ParallelNeighborhood].
 pub trait ParallelLocalImprover<S>: Send + Sync {
     /// Determines for a given [`EvaluatedSolution`] the best neighbor that has an smaller
@@ -23,4 +24,36 @@ pub trait ParallelLocalImprover<S>: Send + Sync {
     /// This method is called in each iteration of the
     /// [`ParallelLocalSearchSolver`][super::ParallelLocalSearchSolver].
     fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>>;
+
+    /// Same as [`improve`][ParallelLocalImprover::improve], but additionally returns the
+    /// [`NeighborCounts`] of the exploration, which the
+    /// [`ParallelLocalSearchSolver`][super::ParallelLocalSearchSolver] passes to the
+    /// `function_between_steps`.
+    /// The default implementation does not count the neighbors, i.e., it returns `None` as
+    /// counts.
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        (self.improve(solution), None)
+    }
+
+    /// Returns the name of the improver, which is listed by
+    /// [`Solver::describe`][crate::heuristics::Solver::describe]. The default implementation
+    /// returns the name of the type (e.g., `"ParallelMinimizer"`).
+    fn name(&self) -> String {
+        crate::heuristics::common::short_type_name::<Self>()
+    }
+
+    /// Returns the message of the panic the improver caught in the current run (see
+    /// [`PanicSafeImprover`][crate::heuristics::common::PanicSafeImprover]), such that the solver
+    /// reports [`Panicked`][crate::heuristics::common::TerminationReason::Panicked] instead of a
+    /// local optimum. The default implementation returns `None`.
+    fn panic_message(&self) -> Option<String> {
+        None
+    }
+
+    /// Forgets a panic caught in a previous run. The solver calls this at the start of each run.
+    /// The default implementation does nothing.
+    fn reset_panic(&self) {}
 }
@@ -3,19 +3,31 @@
 //! [`ParallelMinimizer`] can evaluate the neighbors in parallel.
 use super::super::ParallelNeighborhood;
 use super::ParallelLocalImprover;
+use crate::heuristics::common::NeighborCounts;
 use crate::objective::EvaluatedSolution;
 use crate::objective::Objective;
-use rayon::iter::ParallelIterator;
+use crate::objective::{best_of_batches, BatchObjective};
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::ParallelIterator;
+use crate::parallel::{install, thread_pool, ThreadPool};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// [`ParallelMinimizer`] searches the whole [`ParallelNeighborhood`] of a solution in parallel and returns the best neighbor
 /// if it is better than the given solution.
-/// * This is done in parallel using the [`ParallelIterator`] of [`rayon`].
+/// * This is done in parallel using the [`ParallelIterator`] of [`rayon`](https://docs.rs/rayon/)
+///   (sequentially without the `rayon` feature).
 /// * If the computation or the evaluation of a neighbor is CPU-heavy this might be a good choice.
 /// * Solution type `S` must implement [`Send`] and [`Sync`].
+/// * The neighborhood is explored in the current thread pool unless a dedicated [`ThreadPool`]
+///   is set (see [`with_thread_pool`][ParallelMinimizer::with_thread_pool]).
+/// * If the objective is faster when evaluated in batches, the neighbors can be evaluated by a
+///   [`BatchObjective`] (see [`with_batch_objective`][ParallelMinimizer::with_batch_objective]).
 pub struct ParallelMinimizer<S, N> {
     neighborhood: Arc<N>,
     objective: Arc<Objective<S>>,
+    thread_pool: Option<Arc<ThreadPool>>,
+    batch_objective: Option<(Arc<dyn BatchObjective<S>>, usize)>,
 }
 
 impl<S, N> ParallelMinimizer<S, N> {
@@ -24,35 +36,89 @@ impl<S, N> ParallelMinimizer<S, N> {
         ParallelMinimizer {
             neighborhood,
             objective,
+            thread_pool: None,
+            batch_objective: None,
         }
     }
+
+    /// Evaluates the neighbors by the [`BatchObjective`] in batches of `batch_size` neighbors
+    /// (each batch is a parallel task) instead of one at a time by the [`Objective`].
+    /// * Ties between neighbors with the same objective value are not broken by the
+    ///   [`preference`][Objective::with_preference] of the [`Objective`].
+    pub fn with_batch_objective(
+        mut self,
+        batch_objective: Arc<dyn BatchObjective<S>>,
+        batch_size: usize,
+    ) -> Self {
+        self.batch_objective = Some((batch_objective, batch_size));
+        self
+    }
+
+    /// Explores the neighborhood in the given [`ThreadPool`] instead of the global thread pool
+    /// of [`rayon`](https://docs.rs/rayon/) (or of the solver).
+    pub fn with_thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Explores the neighborhood in a dedicated [`ThreadPool`] with `num_threads` threads (see
+    /// [`thread_pool`]).
+    pub fn with_num_threads(self, num_threads: usize) -> Self {
+        self.with_thread_pool(Arc::new(thread_pool(num_threads)))
+    }
 }
 
 impl<S: Send + Sync, N: ParallelNeighborhood<S>> ParallelLocalImprover<S>
     for ParallelMinimizer<S, N>
 {
     fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
-        let best_neighbor_opt = self
-            .neighborhood
-            .neighbors_of(solution.solution())
-            .map(|neighbor| self.objective.evaluate(neighbor))
-            .min_by(|s1, s2| {
-                s1.objective_value()
-                    .partial_cmp(s2.objective_value())
-                    .unwrap()
-            });
-        match best_neighbor_opt {
-            Some(best_neighbor) => {
-                if best_neighbor.objective_value() < solution.objective_value() {
-                    Some(best_neighbor)
-                } else {
-                    None // no improvement found
+        self.improve_counted(solution).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        install(self.thread_pool.as_deref(), || {
+            let evaluated = AtomicU64::new(0);
+            let neighbors = self
+                .neighborhood
+                .neighbors_of(solution.solution())
+                .inspect(|_| {
+                    evaluated.fetch_add(1, Ordering::Relaxed);
+                });
+            let best_neighbor_opt = match &self.batch_objective {
+                Some((batch_objective, batch_size)) => best_of_batches(
+                    batch_objective.as_ref(),
+                    *batch_size,
+                    neighbors.map(|neighbor| (neighbor, ())),
+                )
+                .map(|(best_neighbor, _)| best_neighbor),
+                None => neighbors
+                    .map(|neighbor| self.objective.evaluate(neighbor))
+                    .min_by(|s1, s2| self.objective.compare(s1, s2)),
+            };
+            let improved_solution = match best_neighbor_opt {
+                Some(best_neighbor) => {
+                    if self.objective.is_better(&best_neighbor, solution) {
+                        Some(best_neighbor)
+                    } else {
+                        None // no improvement found
+                    }
                 }
-            }
-            None => {
-                println!("\x1b[31mwarning:\x1b[0m no swap possible.");
-                None
-            }
-        }
+                None => {
+                    report!(Warning, "no swap possible.");
+                    None
+                }
+            };
+            let evaluated = evaluated.into_inner();
+            let neighbor_counts = NeighborCounts {
+                generated: evaluated,
+                evaluated,
+                accepted: u64::from(improved_solution.is_some()),
+                ..NeighborCounts::default()
+            };
+            (improved_solution, Some(neighbor_counts))
+        })
     }
 }
@@ -3,9 +3,21 @@
 //! recursion.
 use super::super::ParallelNeighborhood;
 use super::ParallelLocalImprover;
+use crate::heuristics::common::NeighborCounts;
+use crate::heuristics::local_search::local_improver::{
+    RecursionDepthPolicy, RecursionMemoryLimit, RecursionState,
+};
 use crate::objective::EvaluatedSolution;
 use crate::objective::{Objective, ObjectiveValue};
-use rayon::prelude::*;
+use crate::parallel::prelude::*;
+use crate::parallel::{install, thread_pool, ThreadPool};
+#[cfg(feature = "evaluation_cache")]
+use dashmap::DashMap;
+#[cfg(feature = "evaluation_cache")]
+use std::hash::Hash;
+#[cfg(not(feature = "evaluation_cache"))]
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::mpsc::channel;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -16,22 +28,79 @@ use std::sync::Mutex;
 /// * uses parallel computation at two steps:
 ///   - In the recursion when multiple solutions are given, each solution get its own thread.
 ///   - Within each thread the neighborhood is given as [`ParallelIterator`] from the
-///   [`ParallelNeighborhood`].
+///     [`ParallelNeighborhood`].
 /// * As soon as an improving solution is found a terminus-signal is broadcast to all other threads.
 /// * If no improving solution is found the best `recursion_width`-many solutions per thread (!) are
-/// taken to recursion (dublicates according to the objective value are removed).
+///   taken to recursion (dublicates according to the objective value are removed).
 /// * Is can be fast if the computation or evaluation of a neighbor is CPU-heavy and the [`ParallelNeighborhood`]
-/// is large.
+///   is large.
 /// * Produces quite a bit of overhead.
 /// * Is not deterministic.
+/// * Without the `rayon` feature, the solutions of a recursion level are explored one after the
+///   other (each until an improving neighbor is found).
 /// * The diversification for recursion is probably low.
+/// * As the number of solutions for recursion grows with the number of threads, a
+///   [`RecursionMemoryLimit`] can bound the memory of the retained solutions (see
+///   [`with_memory_limit`][TakeAnyRecursion::with_memory_limit]). It is applied per thread and
+///   to the union of all threads.
+/// * For large neighborhoods (e.g., O(n³)), the number of neighbors generated per solution in the
+///   recursion can be bounded (see [`with_neighbor_limit`][TakeAnyRecursion::with_neighbor_limit]).
+/// * The recursion depth can be adapted to the state of the search by a [`RecursionDepthPolicy`]
+///   (see [`with_recursion_depth_policy`][TakeAnyRecursion::with_recursion_depth_policy]).
+/// * With the `evaluation_cache` feature, the threads can share the objective values of the
+///   neighbors in a concurrent cache, such that overlapping neighborhoods in the recursion tree
+///   are evaluated only once (see `with_evaluation_cache`).
+/// * The threads are taken from the current thread pool unless a dedicated [`ThreadPool`] is set
+///   (see [`with_thread_pool`][TakeAnyRecursion::with_thread_pool]).
 pub struct TakeAnyRecursion<S, N> {
     recursion_depth: u8,
     recursion_width: u8,
     neighborhood: Arc<N>,
     objective: Arc<Objective<S>>,
+    memory_limit: Option<Arc<RecursionMemoryLimit<S>>>,
+    neighbor_limit: Option<usize>,
+    recursion_depth_policy: Option<RecursionDepthPolicy>,
+    simple_pass_failures: AtomicU32,
+    thread_pool: Option<Arc<ThreadPool>>,
+    #[cfg(feature = "evaluation_cache")]
+    new_evaluation_cache: Option<NewSolutionCache<S>>,
+    #[cfg(feature = "evaluation_cache")]
+    evaluation_cache_hits: AtomicU64,
 }
 
+/// A concurrent map from solutions to their objective values (see
+/// [`with_evaluation_cache`][TakeAnyRecursion::with_evaluation_cache]).
+#[cfg(feature = "evaluation_cache")]
+trait SolutionCache<S>: Send + Sync {
+    fn get(&self, solution: &S) -> Option<ObjectiveValue>;
+    fn insert(&self, solution: S, objective_value: ObjectiveValue);
+}
+
+#[cfg(feature = "evaluation_cache")]
+impl<S: Hash + Eq + Send + Sync> SolutionCache<S> for DashMap<S, ObjectiveValue> {
+    fn get(&self, solution: &S) -> Option<ObjectiveValue> {
+        DashMap::get(self, solution).map(|objective_value| objective_value.clone())
+    }
+
+    fn insert(&self, solution: S, objective_value: ObjectiveValue) {
+        DashMap::insert(self, solution, objective_value);
+    }
+}
+
+/// Creates an empty [`SolutionCache`].
+#[cfg(feature = "evaluation_cache")]
+type NewSolutionCache<S> = fn() -> Box<dyn SolutionCache<S>>;
+
+/// The objective values of the neighbors evaluated during a single
+/// [`improve`][ParallelLocalImprover::improve] call, keyed by the neighbor (`None` if the cache
+/// is disabled).
+#[cfg(feature = "evaluation_cache")]
+type EvaluationCache<S> = Option<Box<dyn SolutionCache<S>>>;
+
+/// Without the `evaluation_cache` feature, every neighbor is evaluated.
+#[cfg(not(feature = "evaluation_cache"))]
+struct EvaluationCache<S>(PhantomData<fn(&S)>);
+
 impl<S, N> TakeAnyRecursion<S, N> {
     /// Creates a new instance of [`TakeAnyRecursion`]. In addition to the [`ParallelNeighborhood`]
     /// and the [`Objective`] the following parameters are needed:
@@ -48,29 +117,179 @@ impl<S, N> TakeAnyRecursion<S, N> {
             recursion_width,
             neighborhood,
             objective,
+            memory_limit: None,
+            neighbor_limit: None,
+            recursion_depth_policy: None,
+            simple_pass_failures: AtomicU32::new(0),
+            thread_pool: None,
+            #[cfg(feature = "evaluation_cache")]
+            new_evaluation_cache: None,
+            #[cfg(feature = "evaluation_cache")]
+            evaluation_cache_hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Explores the recursion tree in the given [`ThreadPool`] instead of the global thread pool
+    /// of [`rayon`](https://docs.rs/rayon/) (or of the solver).
+    pub fn with_thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Explores the recursion tree in a dedicated [`ThreadPool`] with `num_threads` threads (see
+    /// [`thread_pool`]).
+    pub fn with_num_threads(self, num_threads: usize) -> Self {
+        self.with_thread_pool(Arc::new(thread_pool(num_threads)))
+    }
+
+    /// Returns the number of evaluations that were saved by the evaluation cache so far (see
+    /// [`with_evaluation_cache`][TakeAnyRecursion::with_evaluation_cache]).
+    #[cfg(feature = "evaluation_cache")]
+    pub fn evaluation_cache_hits(&self) -> u64 {
+        self.evaluation_cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// Applies the `memory_limit` to the solutions that are retained for recursion. Keep a clone
+    /// of the [`Arc`] to read the [`statistics`][RecursionMemoryLimit::statistics] after the
+    /// search.
+    pub fn with_memory_limit(mut self, memory_limit: Arc<RecursionMemoryLimit<S>>) -> Self {
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Bounds the number of neighbors that are generated for each solution in the recursion to
+    /// `neighbor_limit`, such that the number of evaluations does not explode for deep
+    /// recursions. As the neighbors are generated in parallel, it is not deterministic which
+    /// neighbors are taken. The neighborhood of the solution to be improved is still explored
+    /// completely.
+    pub fn with_neighbor_limit(mut self, neighbor_limit: usize) -> Self {
+        self.neighbor_limit = Some(neighbor_limit);
+        self
+    }
+
+    /// Decides the recursion depth by the `recursion_depth_policy` whenever the simple pass found
+    /// no improving neighbor (e.g., recurse only if the objective value is close to a target).
+    /// The configured `recursion_depth` is never exceeded.
+    pub fn with_recursion_depth_policy(
+        mut self,
+        recursion_depth_policy: RecursionDepthPolicy,
+    ) -> Self {
+        self.recursion_depth_policy = Some(recursion_depth_policy);
+        self
+    }
+
+    /// Returns the recursion depth after the simple pass failed (see
+    /// [`RecursionDepthPolicy`]).
+    fn adapted_recursion_depth(&self, objective_value: &ObjectiveValue) -> u8 {
+        let simple_pass_failures = self.simple_pass_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        match &self.recursion_depth_policy {
+            Some(recursion_depth_policy) => recursion_depth_policy(&RecursionState {
+                max_recursion_depth: self.recursion_depth,
+                simple_pass_failures,
+                objective_value,
+            })
+            .min(self.recursion_depth),
+            None => self.recursion_depth,
         }
     }
 }
 
+#[cfg(feature = "evaluation_cache")]
+impl<S: Hash + Eq + Send + Sync + 'static, N> TakeAnyRecursion<S, N> {
+    /// Shares the objective values of all neighbors evaluated during an
+    /// [`improve`][ParallelLocalImprover::improve] call between the threads (in a concurrent
+    /// [`DashMap`] keyed by the neighbor), such that neighbors that are generated by several
+    /// solutions of the recursion are evaluated only once.
+    /// * The cache stores a clone of each evaluated neighbor. It is cleared after each call, so
+    ///   its memory is bounded by the number of neighbors of the recursion tree.
+    /// * Requires the `evaluation_cache` feature.
+    pub fn with_evaluation_cache(mut self) -> Self {
+        self.new_evaluation_cache = Some(|| Box::new(DashMap::<S, ObjectiveValue>::new()));
+        self
+    }
+}
+
 impl<S: Send + Sync + Clone, N: ParallelNeighborhood<S>> ParallelLocalImprover<S>
     for TakeAnyRecursion<S, N>
 {
     fn improve(&self, solution: &EvaluatedSolution<S>) -> Option<EvaluatedSolution<S>> {
-        let old_objective = solution.objective_value();
-        self.improve_recursion(vec![solution.clone()], old_objective, self.recursion_depth)
+        self.improve_counted(solution).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+    ) -> (Option<EvaluatedSolution<S>>, Option<NeighborCounts>) {
+        install(self.thread_pool.as_deref(), || {
+            let old_objective = solution.objective_value();
+            let evaluation_cache = self.new_evaluation_cache();
+            let evaluated = AtomicU64::new(0);
+            let improved_solution = self.improve_recursion(
+                vec![solution.clone()],
+                old_objective,
+                self.recursion_depth,
+                &evaluation_cache,
+                &evaluated,
+            );
+            let evaluated = evaluated.into_inner();
+            let neighbor_counts = NeighborCounts {
+                generated: evaluated,
+                evaluated,
+                accepted: u64::from(improved_solution.is_some()),
+                ..NeighborCounts::default()
+            };
+            (improved_solution, Some(neighbor_counts))
+        })
     }
 }
 
 impl<S: Send + Sync + Clone, N: ParallelNeighborhood<S>> TakeAnyRecursion<S, N> {
+    /// Creates the (empty) [`EvaluationCache`] of an [`improve`][ParallelLocalImprover::improve]
+    /// call.
+    fn new_evaluation_cache(&self) -> EvaluationCache<S> {
+        #[cfg(feature = "evaluation_cache")]
+        return self
+            .new_evaluation_cache
+            .map(|new_evaluation_cache| new_evaluation_cache());
+        #[cfg(not(feature = "evaluation_cache"))]
+        EvaluationCache(PhantomData)
+    }
+
+    /// Evaluates the `neighbor`, reusing its objective value from the `evaluation_cache` if it
+    /// was evaluated before during this call.
+    #[allow(unused_variables)]
+    fn evaluate(&self, neighbor: S, evaluation_cache: &EvaluationCache<S>) -> EvaluatedSolution<S> {
+        #[cfg(feature = "evaluation_cache")]
+        if let Some(evaluation_cache) = evaluation_cache {
+            if let Some(objective_value) = evaluation_cache.get(&neighbor) {
+                self.evaluation_cache_hits.fetch_add(1, Ordering::Relaxed);
+                return EvaluatedSolution::new(neighbor, objective_value);
+            }
+            let evaluated_neighbor = self.objective.evaluate(neighbor);
+            evaluation_cache.insert(
+                evaluated_neighbor.solution().clone(),
+                evaluated_neighbor.objective_value().clone(),
+            );
+            return evaluated_neighbor;
+        }
+        self.objective.evaluate(neighbor)
+    }
+
     fn improve_recursion(
         &self,
         solutions: Vec<EvaluatedSolution<S>>,
         objective_to_beat: &ObjectiveValue,
         remaining_recursion: u8,
+        evaluation_cache: &EvaluationCache<S>,
+        evaluated: &AtomicU64,
     ) -> Option<EvaluatedSolution<S>> {
+        let neighbor_limit = match self.neighbor_limit {
+            Some(neighbor_limit) if remaining_recursion < self.recursion_depth => neighbor_limit,
+            _ => usize::MAX,
+        };
         let mut solution_collection: Vec<Vec<EvaluatedSolution<S>>> = Vec::new();
         let mut result: Option<EvaluatedSolution<S>> = None;
-        rayon::scope(|s| {
+        crate::parallel::scope(|s| {
             let mut found_senders = Vec::new();
             let (success_sender, success_receiver) = channel();
             let (failure_sender, failure_receiver) = channel();
@@ -91,22 +310,25 @@ impl<S: Send + Sync + Clone, N: ParallelNeighborhood<S>> TakeAnyRecursion<S, N>
                     let result = self
                         .neighborhood
                         .neighbors_of(sol.solution())
-                        .map(|neighbor| self.objective.evaluate(neighbor))
+                        .take_any(neighbor_limit)
+                        .map(|neighbor| {
+                            evaluated.fetch_add(1, Ordering::Relaxed);
+                            self.evaluate(neighbor, evaluation_cache)
+                        })
                         .find_any(|evaluated_neighbor| {
                             if remaining_recursion > 0 {
                                 let mut schedules_mutex = new_solutions_mutex.lock().unwrap();
 
                                 schedules_mutex.push(evaluated_neighbor.clone());
 
-                                schedules_mutex.sort_unstable_by(|a, b| {
-                                    a.objective_value().cmp(b.objective_value())
-                                });
-                                schedules_mutex.dedup_by(|s1, s2| {
-                                    s1.objective_value().cmp(s2.objective_value()).is_eq()
-                                }); //remove dublicates according to objective_value
+                                schedules_mutex.sort_unstable();
+                                schedules_mutex.dedup(); //remove dublicates according to objective_value
                                 let width =
                                     (self.recursion_width as usize).min(schedules_mutex.len());
                                 schedules_mutex.truncate(width);
+                                if let Some(memory_limit) = &self.memory_limit {
+                                    memory_limit.trim(&mut schedules_mutex);
+                                }
                             }
 
                             let found_receiver_mutex = found_receiver_mutex.lock().unwrap();
@@ -155,18 +377,27 @@ impl<S: Send + Sync + Clone, N: ParallelNeighborhood<S>> TakeAnyRecursion<S, N>
         });
 
         if result.is_none() {
+            let remaining_recursion = if remaining_recursion == self.recursion_depth {
+                self.adapted_recursion_depth(objective_to_beat)
+            } else {
+                remaining_recursion
+            };
             if remaining_recursion > 0 {
                 let mut schedules_for_recursion: Vec<EvaluatedSolution<S>> =
                     solution_collection.into_iter().flatten().collect();
 
-                schedules_for_recursion
-                    .sort_unstable_by(|a, b| a.objective_value().cmp(b.objective_value()));
-                schedules_for_recursion.dedup_by(|a, b| a.objective_value() == b.objective_value());
+                schedules_for_recursion.sort_unstable();
+                schedules_for_recursion.dedup();
+                if let Some(memory_limit) = &self.memory_limit {
+                    memory_limit.trim(&mut schedules_for_recursion);
+                }
 
                 self.improve_recursion(
                     schedules_for_recursion,
                     objective_to_beat,
                     remaining_recursion - 1,
+                    evaluation_cache,
+                    evaluated,
                 )
             } else {
                 None
@@ -176,3 +407,56 @@ impl<S: Send + Sync + Clone, N: ParallelNeighborhood<S>> TakeAnyRecursion<S, N>
         }
     }
 }
+
+#[cfg(all(test, feature = "evaluation_cache"))]
+mod tests {
+    use super::*;
+    use crate::objective::{BaseValue, Indicator};
+    use crate::test_fixtures::PlusMinusOne;
+
+    /// A local minimum at 0 (value 5) and the global minimum at 3 (value 4).
+    struct Value;
+
+    impl Indicator<i64> for Value {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer(match solution {
+                0 => 5,
+                1 => 6,
+                -1 => 7,
+                2 => 8,
+                -2 => 9,
+                3 => 4,
+                _ => 100,
+            })
+        }
+
+        fn name(&self) -> String {
+            String::from("Value")
+        }
+    }
+
+    #[test]
+    fn test_evaluation_cache() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Value)));
+        let improver = TakeAnyRecursion::new(2, 2, Arc::new(PlusMinusOne), objective.clone())
+            .with_evaluation_cache();
+
+        // the scope blocks a worker thread while waiting for the others, so at least two
+        // threads are needed
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let improved = thread_pool
+            .install(|| improver.improve(&objective.evaluate(0)))
+            .unwrap();
+
+        assert_eq!(*improved.solution(), 3);
+        assert_eq!(
+            improved.objective_value().as_vec(),
+            &vec![BaseValue::Integer(4)]
+        );
+        // the neighbors of 0 are generated again in the second recursion level
+        assert!(improver.evaluation_cache_hits() >= 1);
+    }
+}
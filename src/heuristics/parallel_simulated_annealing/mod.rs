@@ -0,0 +1,459 @@
+//! This module contains the [`ParallelSimulatedAnnealingSolver`], which runs several
+//! [simulated annealing][super::simulated_annealing] chains in parallel that periodically
+//! exchange their solutions.
+//! * Each chain has its own temperature, random number generator and current solution. It
+//!   performs `exchange_interval` iterations like the
+//!   [`SimulatedAnnealingSolver`][super::simulated_annealing::SimulatedAnnealingSolver] (the
+//!   neighbors are considered in order and the temperature of a chain is multiplied by the
+//!   `cooling_factor` whenever it accepts a worse neighbor).
+//! * After each such epoch, the chains interact according to the [`ChainExchange`]: either all
+//!   chains continue from the best solution found so far, or the chains swap their solutions
+//!   with their neighbors in the temperature ladder (parallel tempering / replica exchange).
+//! * The search stops after a certain number of iterations (per chain), after a certain time
+//!   limit, or if all chains explored their whole neighborhood without any acceptance.
+//! * The best solution seen by any chain is returned.
+//! * The chains run on the [`rayon`](https://docs.rs/rayon/) thread pool. Without the `rayon`
+//!   feature, the solver is still available, but the chains run one after another (see the
+//!   [`parallel`][crate::parallel] module).
+//!
+//! For an example, see the [parallel simulated annealing solver for the
+//! TSP][crate::examples::tsp::solvers::parallel_simulated_annealing].
+
+use std::{sync::Arc, time as stdtime};
+
+use rand::Rng;
+use rand::RngCore;
+
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::{IntoParallelIterator, ParallelIterator};
+
+use super::common::default_rng_factory;
+use super::common::RngFactory;
+use super::common::SolverDescription;
+use super::common::{
+    default_function_between_steps, FunctionBetweenSteps, NeighborCounts, Neighborhood,
+    SearchRecorder, SearchResult, TerminationReason,
+};
+use super::common::{report_termination, TerminationCriteria};
+use super::simulated_annealing::{
+    AcceptanceProbabilityFunction, Probability, ScalingFactor, Temperature,
+};
+use super::Solver;
+use crate::objective::{EvaluatedSolution, Objective};
+
+/// Defines how the chains of a [`ParallelSimulatedAnnealingSolver`] interact after each epoch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChainExchange {
+    /// Each chain whose current solution is worse than the best solution seen by any chain
+    /// continues from this best solution (keeping its own temperature).
+    #[default]
+    BestSolution,
+    /// Parallel tempering: the chains are ordered by their position in `temperatures` (which
+    /// should be increasing). For each pair of adjacent chains, the current solutions are swapped
+    /// with the probability of the [`AcceptanceProbabilityFunction`] for moving the colder chain
+    /// to the solution of the hotter chain at the temperature
+    /// 1/(1/T<sub>cold</sub> - 1/T<sub>hot</sub>). For the exponential acceptance probability,
+    /// this is the usual replica exchange probability
+    /// min(1, e<sup>(f<sub>cold</sub> - f<sub>hot</sub>)(1/T<sub>cold</sub> - 1/T<sub>hot</sub>)</sup>).
+    /// In this mode, the `cooling_factor` is usually 1.0, such that the temperatures are fixed.
+    ReplicaExchange,
+}
+
+/// The state of one annealing chain.
+struct Chain<S> {
+    current_solution: EvaluatedSolution<S>,
+    best_solution: EvaluatedSolution<S>,
+    temperature: Temperature,
+    rng: Box<dyn RngCore + Send>,
+    frozen: bool,
+    /// The [`NeighborCounts`] of the current epoch.
+    neighbor_counts: NeighborCounts,
+}
+
+/// A simulated annealing solver that runs one chain per temperature in `temperatures` in parallel
+/// and lets them interact every `exchange_interval` iterations (see [`ChainExchange`]).
+/// * The `function_between_steps` is executed after each epoch with the best solution seen by
+///   any chain. The iteration passed to it is the number of iterations per chain so far, the
+///   [`NeighborCounts`] are summed over all chains of the epoch.
+/// * The default `function_between_steps` (if `None`) is printing the iteration number, the
+///   objective value (in comparison the the previous objective value) and the time elapsed since
+///   the start.
+/// * The solver stops if the [`TerminationCriteria`] (e.g., a number of iterations per chain or
+///   a time limit) are met (checked after each epoch).
+/// * Without [`TerminationCriteria`], the solver runs until all chains explored a whole
+///   neighborhood without any acceptance.
+/// * Further options (e.g., the [`ChainExchange`]) are set by the `with_*` methods.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the trajectory of the best solution
+///   seen with one point per epoch (as passed to the `function_between_steps`).
+///
+/// For a high-level overview, see the [module documentation][super::parallel_simulated_annealing]
+/// and for an example, see the [parallel simulated annealing solver for the
+/// TSP][crate::examples::tsp::solvers::parallel_simulated_annealing].
+pub struct ParallelSimulatedAnnealingSolver<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    objective: Arc<Objective<S>>,
+    temperatures: Vec<Temperature>,
+    cooling_factor: ScalingFactor,
+    acceptance_probability_function: AcceptanceProbabilityFunction,
+    exchange_interval: u32,
+    exchange: ChainExchange,
+    random_seed: Option<u64>,
+    rng_factory: RngFactory,
+    function_between_steps: FunctionBetweenSteps<S>,
+    termination_criteria: Option<TerminationCriteria>,
+}
+
+impl<S> ParallelSimulatedAnnealingSolver<S> {
+    /// Creates a new [`ParallelSimulatedAnnealingSolver`] with the given [`Neighborhood`],
+    /// [`Objective`], initial `temperatures` (one chain per temperature, at least one),
+    /// `cooling_factor`, [`AcceptanceProbabilityFunction`] and `exchange_interval` (the number of
+    /// iterations of each chain between two exchanges, at least 1). The chains continue from the
+    /// best solution after each epoch ([`ChainExchange::BestSolution`]).
+    /// * A `random_seed` can be provided to make the search reproducible. The chain with index
+    ///   `i` uses the seed `random_seed + i + 1`.
+    pub fn initialize(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        temperatures: Vec<Temperature>,
+        cooling_factor: ScalingFactor,
+        acceptance_probability_function: AcceptanceProbabilityFunction,
+        exchange_interval: u32,
+        random_seed: Option<u64>,
+    ) -> Self {
+        Self::with_options(
+            neighborhood,
+            objective,
+            temperatures,
+            cooling_factor,
+            acceptance_probability_function,
+            exchange_interval,
+            random_seed,
+            None,
+            None,
+        )
+    }
+
+    /// Creates a new [`ParallelSimulatedAnnealingSolver`] with the given [`Neighborhood`],
+    /// [`Objective`], initial `temperatures` (one chain per temperature, at least one),
+    /// `cooling_factor`, [`AcceptanceProbabilityFunction`] and `exchange_interval` (the number of
+    /// iterations of each chain between two exchanges, at least 1).
+    /// * `random_seed` can be provided to make the search reproducible (the result does not
+    ///   depend on the number of threads).
+    /// * `function_between_steps` is executed after each epoch. If `None`, the default is
+    ///   printing the iteration number, the objective value (in comparison the the previous
+    ///   objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit or an iteration
+    ///   limit (per chain), are checked after each epoch with the best solution seen. The chains
+    ///   stop their epoch as soon as the time limit is exceeded, and the last epoch is shortened to
+    ///   the iteration limit. If `None`, the solver runs until all chains are frozen.
+    /// * The chains continue from the best solution after each epoch
+    ///   ([`ChainExchange::BestSolution`]), unless another [`ChainExchange`] is set by
+    ///   [`with_exchange`][ParallelSimulatedAnnealingSolver::with_exchange].
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        temperatures: Vec<Temperature>,
+        cooling_factor: ScalingFactor,
+        acceptance_probability_function: AcceptanceProbabilityFunction,
+        exchange_interval: u32,
+        random_seed: Option<u64>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> Self {
+        assert!(
+            !temperatures.is_empty(),
+            "At least one temperature (chain) is required."
+        );
+        Self {
+            neighborhood,
+            objective,
+            temperatures,
+            cooling_factor,
+            acceptance_probability_function,
+            exchange_interval: exchange_interval.max(1),
+            exchange: ChainExchange::default(),
+            random_seed,
+            rng_factory: default_rng_factory(),
+            function_between_steps: function_between_steps
+                .unwrap_or(default_function_between_steps()),
+            termination_criteria,
+        }
+    }
+
+    /// Defines how the chains interact after each epoch (see [`ChainExchange`]). The default is
+    /// [`ChainExchange::BestSolution`].
+    pub fn with_exchange(mut self, exchange: ChainExchange) -> Self {
+        self.exchange = exchange;
+        self
+    }
+
+    /// Replaces the [`RngFactory`] (default: [`StdRng`][rand::rngs::StdRng], see
+    /// [`default_rng_factory`]). It creates one generator per chain (for its acceptance
+    /// decisions) and one for the exchanges.
+    pub fn with_rng_factory(mut self, rng_factory: RngFactory) -> Self {
+        self.rng_factory = rng_factory;
+        self
+    }
+}
+
+impl<S: Clone + Send + Sync> Solver<S> for ParallelSimulatedAnnealingSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        let start_time = stdtime::Instant::now();
+        let initial_solution = self.objective.evaluate(initial_solution);
+        let mut best_solution_seen = initial_solution.clone();
+        let mut exchange_rng = (self.rng_factory)(self.random_seed);
+        let mut chains: Vec<Chain<S>> = self
+            .temperatures
+            .iter()
+            .enumerate()
+            .map(|(index, &temperature)| Chain {
+                current_solution: initial_solution.clone(),
+                best_solution: initial_solution.clone(),
+                temperature,
+                rng: (self.rng_factory)(
+                    self.random_seed
+                        .map(|seed| seed.wrapping_add(index as u64 + 1)),
+                ),
+                frozen: false,
+                neighbor_counts: NeighborCounts::default(),
+            })
+            .collect();
+        let mut termination_criteria = self.termination_criteria.clone();
+        let time_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_time_limit);
+        let iteration_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_iteration_limit);
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            time_limit,
+            iteration_limit,
+            initial_solution.objective_value(),
+            true,
+        );
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        let mut iterations = 0;
+
+        loop {
+            let epoch_length = match iteration_limit {
+                Some(iteration_limit) => self
+                    .exchange_interval
+                    .min(iteration_limit.saturating_sub(iterations)),
+                None => self.exchange_interval,
+            };
+            chains = chains
+                .into_par_iter()
+                .map(|chain| self.run_chain(chain, epoch_length, start_time, time_limit))
+                .collect();
+            iterations += epoch_length;
+
+            let previous_best_solution = best_solution_seen.clone();
+            for chain in chains.iter() {
+                if self
+                    .objective
+                    .is_better(&chain.best_solution, &best_solution_seen)
+                {
+                    best_solution_seen = chain.best_solution.clone();
+                }
+            }
+            let neighbor_counts = chains
+                .iter_mut()
+                .map(|chain| std::mem::take(&mut chain.neighbor_counts))
+                .fold(NeighborCounts::default(), |sum, counts| sum + counts);
+            recorder.step(
+                iterations,
+                &best_solution_seen,
+                Some(&previous_best_solution),
+                Some(neighbor_counts),
+            );
+
+            if chains.iter().all(|chain| chain.frozen) {
+                report!(Info, "All chains are frozen.");
+                break;
+            }
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iterations,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
+            }
+
+            match self.exchange {
+                ChainExchange::BestSolution => {
+                    for chain in chains.iter_mut() {
+                        if self
+                            .objective
+                            .is_better(&best_solution_seen, &chain.current_solution)
+                        {
+                            chain.current_solution = best_solution_seen.clone();
+                            chain.frozen = false;
+                        }
+                    }
+                }
+                ChainExchange::ReplicaExchange => {
+                    self.exchange_replicas(&mut chains, &mut exchange_rng);
+                }
+            }
+        }
+
+        recorder.finish(best_solution_seen, iterations, termination_reason)
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("ParallelSimulatedAnnealingSolver")
+            .with_parameter("chains", self.temperatures.len())
+            .with_parameter("temperatures", format!("{:?}", self.temperatures))
+            .with_parameter("cooling_factor", self.cooling_factor)
+            .with_parameter("exchange_interval", self.exchange_interval)
+            .with_parameter("exchange", format!("{:?}", self.exchange))
+            .with_optional_parameter("random_seed", self.random_seed)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+    }
+}
+
+impl<S: Clone> ParallelSimulatedAnnealingSolver<S> {
+    /// Performs up to `epoch_length` annealing iterations on the `chain`. Stops early if the
+    /// `time_limit` is exceeded or no neighbor is accepted (the chain is frozen). The neighbors
+    /// are counted in the [`NeighborCounts`] of the chain.
+    fn run_chain(
+        &self,
+        mut chain: Chain<S>,
+        epoch_length: u32,
+        start_time: stdtime::Instant,
+        time_limit: Option<stdtime::Duration>,
+    ) -> Chain<S> {
+        for _ in 0..epoch_length {
+            if time_limit.is_some_and(|time_limit| start_time.elapsed() > time_limit) {
+                break;
+            }
+            let mut evaluated = 0;
+            let accepted_neighbor = self
+                .neighborhood
+                .neighbors_of(chain.current_solution.solution())
+                .find_map(|neighbor| {
+                    evaluated += 1;
+                    let neighbor_solution = self.objective.evaluate(neighbor);
+                    let acceptance_probability = (self.acceptance_probability_function)(
+                        chain.current_solution.objective_value(),
+                        neighbor_solution.objective_value(),
+                        chain.temperature,
+                    );
+                    (acceptance_probability > chain.rng.gen::<Probability>())
+                        .then_some(neighbor_solution)
+                });
+            let accepted = u64::from(accepted_neighbor.is_some());
+            chain.neighbor_counts = chain.neighbor_counts
+                + NeighborCounts {
+                    generated: evaluated,
+                    evaluated,
+                    rejected_as_tabu: 0,
+                    rejected_by_threshold: evaluated - accepted,
+                    accepted,
+                };
+            let Some(new_solution) = accepted_neighbor else {
+                chain.frozen = true;
+                break;
+            };
+            if new_solution.objective_value() >= chain.current_solution.objective_value() {
+                chain.temperature *= self.cooling_factor;
+            }
+            chain.current_solution = new_solution;
+            if self
+                .objective
+                .is_better(&chain.current_solution, &chain.best_solution)
+            {
+                chain.best_solution = chain.current_solution.clone();
+            }
+        }
+        chain
+    }
+
+    /// Swaps the current solutions of adjacent chains with the replica exchange probability (see
+    /// [`ChainExchange::ReplicaExchange`]).
+    fn exchange_replicas(&self, chains: &mut [Chain<S>], rng: &mut dyn RngCore) {
+        for cold in 0..chains.len().saturating_sub(1) {
+            let hot = cold + 1;
+            let inverse_temperature_difference =
+                1.0 / chains[cold].temperature - 1.0 / chains[hot].temperature;
+            let swap_probability = if inverse_temperature_difference <= 0.0 {
+                1.0
+            } else {
+                (self.acceptance_probability_function)(
+                    chains[cold].current_solution.objective_value(),
+                    chains[hot].current_solution.objective_value(),
+                    1.0 / inverse_temperature_difference,
+                )
+            };
+            if swap_probability > rng.gen::<Probability>() {
+                let (cold_chains, hot_chains) = chains.split_at_mut(hot);
+                std::mem::swap(
+                    &mut cold_chains[cold].current_solution,
+                    &mut hot_chains[0].current_solution,
+                );
+                cold_chains[cold].frozen = false;
+                hot_chains[0].frozen = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::silent_function_between_steps;
+    use crate::objective::{BaseValue, ObjectiveValue};
+    use crate::test_fixtures::{DistanceTo, PlusMinusOne};
+
+    fn exponential_acceptance() -> AcceptanceProbabilityFunction {
+        Box::new(|current, new, temperature| {
+            let difference = (new.iter().next().unwrap().to_f64()
+                - current.iter().next().unwrap().to_f64())
+            .max(0.0);
+            (-difference / temperature).exp()
+        })
+    }
+
+    #[test]
+    fn test_parallel_simulated_annealing() {
+        for exchange in [ChainExchange::BestSolution, ChainExchange::ReplicaExchange] {
+            let solver = ParallelSimulatedAnnealingSolver::with_options(
+                Arc::new(PlusMinusOne),
+                Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(100)))),
+                vec![0.5, 2.0, 8.0, 32.0],
+                1.0,
+                exponential_acceptance(),
+                10,
+                Some(7),
+                Some(silent_function_between_steps()),
+                Some(TerminationCriteria::iteration_limit(2000).or(
+                    TerminationCriteria::target_objective_value(ObjectiveValue::new(vec![
+                        BaseValue::Integer(0),
+                    ])),
+                )),
+            )
+            .with_exchange(exchange);
+
+            let solution = solver.solve(20);
+
+            assert_eq!(*solution.solution(), 100, "{:?}", exchange);
+        }
+    }
+}
@@ -2,38 +2,54 @@
 //! [tabu search metaheuristic](https://en.wikipedia.org/wiki/Tabu_search), where the neighborhood
 //! exploration is done in parallel.
 //! * This solver requires a [`ParallelTabuNeighborhood`], which, in comparison to a regular
-//! [`Neighborhood`][crate::heuristics::common::Neighborhood],
-//! requires a tabu list as an additional argument and returns
-//! a [`ParallelIterator`] (from the [`rayon`] crate) over the neighbors of the solution together with a list of tabus that
-//! should be added to the tabu list.
+//!   [`Neighborhood`][crate::heuristics::common::Neighborhood],
+//!   requires a tabu list as an additional argument and returns
+//!   a [`ParallelIterator`] (from the
+//!   [`parallel`][crate::parallel] module) over the neighbors of the solution together with a list
+//!   of tabus that should be added to the tabu list.
 //! * Starts with an initial solution and explores the neighborhood of the current
-//! solution in parallel, while ignoring tabu solutions.
+//!   solution in parallel, while ignoring tabu solutions.
 //! * The best non-tabu neighbor, even if it is worse than the current solution, is chosen.
 //! * Each neighbor is paired with a list of tabus that should be added to the tabu list.
 //! * A good tabu should forbid to return to the previous solution.
 //! * The list of tabus is limited in size, and the oldest tabus are removed when the list is full.
+//! * With a [`TabuPolicy`], tabus of different classes
+//!   (e.g., arc-based and node-based tabus) can have different tenures.
+//! * An [`AspirationCriterion`] accepts tabu neighbors anyway. By default, a tabu neighbor that
+//!   is better than the best solution seen is accepted ([`NewGlobalBestAspiration`]). The tabu
+//!   neighbors are checked in the same exploration as the non-tabu neighbors, if the neighborhood
+//!   reports them (see [`ParallelTabuNeighborhood::neighbors_with_tabu_status_of`]).
 //! * The search stops after a certain number of iterations, after a certain time limit, or if no
-//! global improvement is found after a certain number of iterations.
+//!   global improvement is found after a certain number of iterations.
 //! * The best solution  seen is returned.
+//! * Without the `rayon` feature, the solver is still available, but the neighborhood is explored
+//!   sequentially (see the [`parallel`][crate::parallel] module).
 //!
 //! For examples, see the [tabu search solver][crate::examples::tsp::solvers::tabu_search] for the TSP.
 pub mod parallel_tabu_improver;
 
-use rayon::iter::ParallelIterator;
+use crate::parallel::ParallelIterator;
 
 use self::parallel_tabu_improver::{ParallelTabuImprover, ParallelTabuMinimizer};
 
-use super::common::{default_function_between_steps, FunctionBetweenSteps};
+use super::common::SolverDescription;
+use super::common::{
+    counts_with_aspiration, default_function_between_steps, log_aspiration_decisions,
+    prefer_aspirating_neighbor, report_termination, AcceptanceLogger, Aspiration,
+    AspirationCriterion, FunctionBetweenSteps, NeighborCounts, NewGlobalBestAspiration,
+    SearchRecorder, SearchResult, TabuPolicy, TerminationCriteria, TerminationReason,
+};
 use super::Solver;
 use crate::objective::{EvaluatedSolution, Objective};
+use crate::parallel::{install, thread_pool, ThreadPool};
 use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time as stdtime;
 
 /// Defines a neighborhood for a tabu search. Compared to a regular neighborhood, a tabu
 /// neighborhood takes a tabu list as an additional argument and returns
-/// a [`ParallelIterator`] (from the [`rayon`] crate) over the neighbors of the solution together with
-/// a list of tabus that should be added to the tabu list.
+/// a [`ParallelIterator`] (from the [`parallel`][crate::parallel] module) over the neighbors of the
+/// solution together with a list of tabus that should be added to the tabu list.
 pub trait ParallelTabuNeighborhood<S: Send, T: Send>: Send + Sync {
     /// Returns an [`ParallelIterator`] over all neighbors of `solution` together with a list of
     /// tabus that should be added to the tabu list.
@@ -42,28 +58,53 @@ pub trait ParallelTabuNeighborhood<S: Send, T: Send>: Send + Sync {
         solution: &'a S,
         tabu_list: &'a VecDeque<T>,
     ) -> impl ParallelIterator<Item = (S, Vec<T>)> + 'a;
+
+    /// Same as [`neighbors_of`][ParallelTabuNeighborhood::neighbors_of], but the tabu neighbors
+    /// are not skipped: Each neighbor is additionally flagged `true` if it is tabu, such that the
+    /// [`AspirationCriterion`] is checked in the same exploration (see
+    /// [`TabuNeighborhood::neighbors_with_tabu_status_of`][super::tabu_search::TabuNeighborhood::neighbors_with_tabu_status_of]).
+    /// The default implementation returns the neighbors of
+    /// [`neighbors_of`][ParallelTabuNeighborhood::neighbors_of] (all flagged `false`).
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        solution: &'a S,
+        tabu_list: &'a VecDeque<T>,
+    ) -> impl ParallelIterator<Item = (S, Vec<T>, bool)> + 'a {
+        self.neighbors_of(solution, tabu_list)
+            .map(|(neighbor, new_tabus)| (neighbor, new_tabus, false))
+    }
 }
 
 /// A tabu search solver that uses a [`ParallelTabuNeighborhood`], an [`Objective`], a tabu list size, as
 /// well as a termination criterion to find a good solution.
 /// * The `function_between_steps` is executed after each improvement step.
 /// * The default `function_between_steps` (if `None`) is printing the iteration number, the
-/// objective value (in comparison the the previous objective value) and the time elapsed since the
-/// start.
-/// * The termination criterion can be either the maximal number of iterations without global
-/// improvement, a time limit, or a maximal number of iterations. (One of them must be set.)
+///   objective value (in comparison the the previous objective value) and the time elapsed since the
+///   start.
+/// * The search stops if the [`TerminationCriteria`] (e.g., a time limit, a maximal number of
+///   iterations or a maximal number of iterations without global improvement) are met. (They
+///   must be set.)
+/// * Further options (e.g., a [`TabuPolicy`], an [`AspirationCriterion`], an
+///   [`AcceptanceLogger`]) are set by the `with_*` methods.
+/// * The search runs in the global thread pool of [`rayon`](https://docs.rs/rayon/) unless a
+///   dedicated [`ThreadPool`] is set by [`with_thread_pool`][ParallelTabuSearchSolver::with_thread_pool]
+///   or [`with_num_threads`][ParallelTabuSearchSolver::with_num_threads].
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the objective trajectory with the neighbor counts of each step (if
+///   the [`ParallelTabuImprover`] counts them).
 ///
 /// For a high-level overview, see the [module documentation][super::parallel_tabu_search] and for examples,
 /// see the [parallel tabu search solver][crate::examples::tsp::solvers::parallel_tabu_search] for the
 /// TSP.
 pub struct ParallelTabuSearchSolver<S, T> {
     objective: Arc<Objective<S>>,
-    tabu_list_size: usize,
+    tabu_policy: TabuPolicy<T>,
     local_improver: Box<dyn ParallelTabuImprover<S, T>>,
     function_between_steps: FunctionBetweenSteps<S>,
-    iteration_without_global_improvement_limit: Option<u32>,
-    time_limit: Option<stdtime::Duration>,
-    iteration_limit: Option<u32>,
+    termination_criteria: Option<TerminationCriteria>,
+    aspiration_criterion: Box<dyn AspirationCriterion<S, T>>,
+    acceptance_logger: Option<Arc<AcceptanceLogger>>,
+    thread_pool: Option<Arc<ThreadPool>>,
 }
 
 impl<S: 'static + Send + Sync, T: 'static + Send + Sync> ParallelTabuSearchSolver<S, T> {
@@ -82,42 +123,33 @@ impl<S: 'static + Send + Sync, T: 'static + Send + Sync> ParallelTabuSearchSolve
             tabu_list_size,
             None,
             None,
-            Some(iteration_without_global_improvement_limit),
-            None,
-            None,
+            Some(TerminationCriteria::iterations_without_improvement(
+                iteration_without_global_improvement_limit,
+            )),
         )
     }
 
     /// Creates a new [`ParallelTabuSearchSolver`] with the given [`ParallelTabuNeighborhood`], [`Objective`], tabu
     /// list size.
+    /// * `local_improver` (implementing [`ParallelTabuImprover`]) specifies the how the
+    ///   neighborhood is explored. If `None`, the default is [`ParallelTabuMinimizer`].
     /// * `function_between_steps` is executed after each improvement step. If `None`, the default
-    /// is printing the iteration number, the objective value (in comparison the the previous
-    /// objective value) and the time elapsed since the start.
-    /// * `iteration_without_global_improvement_limit` is the maximum number of iterations allowed
-    /// without global improvement. If `None`, there is no limit.
-    /// * `time_limit` is the maximum time allowed for the local search to start a new iteration.
-    /// The last iteration is allowed to finish. If `None`, there is no time limit.
-    /// * `iteration_limit` is the maximum number of iterations allowed for the local search. If
-    /// `None`, there is no iteration limit.
-    /// * At least one of `iteration_without_global_improvement_limit`, `time_limit` or
-    /// `iteration_limit` must be set.
-    /// * If multiple termination criteria are set, the search stops when any of them is reached.
-    #[allow(clippy::too_many_arguments)]
+    ///   is printing the iteration number, the objective value (in comparison the the previous
+    ///   objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit, an iteration
+    ///   limit or a limit on the iterations without global improvement, are checked after each
+    ///   iteration with the best solution seen. The last iteration is allowed to finish.
+    /// * The `termination_criteria` must be set.
     pub fn with_options(
         neighborhood: Arc<impl ParallelTabuNeighborhood<S, T> + 'static>,
         objective: Arc<Objective<S>>,
         tabu_list_size: usize,
         local_improver: Option<Box<dyn ParallelTabuImprover<S, T>>>,
         function_between_steps: Option<FunctionBetweenSteps<S>>,
-        iteration_without_global_improvement_limit: Option<u32>,
-        time_limit: Option<stdtime::Duration>,
-        iteration_limit: Option<u32>,
+        termination_criteria: Option<TerminationCriteria>,
     ) -> Self {
-        if iteration_without_global_improvement_limit.is_none()
-            && time_limit.is_none()
-            && iteration_limit.is_none()
-        {
-            panic!("At least one of `iteration_without_global_improvement_limit`, `time_limit` or `iteration_limit` must be set.");
+        if termination_criteria.is_none() {
+            panic!("The `termination_criteria` must be set.");
         }
 
         let local_improver = match local_improver {
@@ -127,75 +159,210 @@ impl<S: 'static + Send + Sync, T: 'static + Send + Sync> ParallelTabuSearchSolve
         };
         Self {
             objective,
-            tabu_list_size,
+            tabu_policy: TabuPolicy::uniform(tabu_list_size),
             local_improver,
             function_between_steps: function_between_steps
                 .unwrap_or(default_function_between_steps()),
-            iteration_without_global_improvement_limit,
-            time_limit,
-            iteration_limit,
+            termination_criteria,
+            aspiration_criterion: Box::new(NewGlobalBestAspiration),
+            acceptance_logger: None,
+            thread_pool: None,
+        }
+    }
+
+    /// Defines separate tenures for different classes of tabus (see [`TabuPolicy`]). Classes
+    /// without explicit tenure use the `tabu_list_size`. By default, all tabus share the tabu list
+    /// of size `tabu_list_size`.
+    pub fn with_tabu_policy(mut self, mut tabu_policy: TabuPolicy<T>) -> Self {
+        tabu_policy.set_default_tenure(self.tabu_policy.default_tenure());
+        self.tabu_policy = tabu_policy;
+        self
+    }
+
+    /// Replaces the [`AspirationCriterion`], which decides whether a tabu neighbor is accepted
+    /// anyway. The default is [`NewGlobalBestAspiration`], i.e., a tabu neighbor is accepted if it
+    /// is better than the best solution seen. Use [`NoAspiration`][super::common::NoAspiration]
+    /// for a strict tabu list.
+    pub fn with_aspiration_criterion(
+        mut self,
+        aspiration_criterion: Box<dyn AspirationCriterion<S, T>>,
+    ) -> Self {
+        self.aspiration_criterion = aspiration_criterion;
+        self
+    }
+
+    /// Logs the acceptance and rejection decisions (rate-limited) for debugging (see
+    /// [`AcceptanceLogger`]): In each iteration, the best non-tabu neighbor and the best tabu
+    /// neighbor that satisfies the [`AspirationCriterion`] (if any) are logged with their tabu
+    /// and aspiration status.
+    pub fn with_acceptance_logger(mut self, acceptance_logger: Arc<AcceptanceLogger>) -> Self {
+        self.acceptance_logger = Some(acceptance_logger);
+        self
+    }
+
+    /// Runs the search in the given [`ThreadPool`] instead of the global thread pool of
+    /// [`rayon`](https://docs.rs/rayon/), e.g., to share a pool with other parts of an
+    /// application.
+    pub fn with_thread_pool(self, thread_pool: Arc<ThreadPool>) -> Self {
+        Self {
+            thread_pool: Some(thread_pool),
+            ..self
         }
     }
+
+    /// Runs the search in a dedicated [`ThreadPool`] with `num_threads` threads (see
+    /// [`thread_pool`]), e.g., to benchmark at a fixed parallelism.
+    pub fn with_num_threads(self, num_threads: usize) -> Self {
+        self.with_thread_pool(Arc::new(thread_pool(num_threads)))
+    }
 }
 
-impl<S: Clone, T: std::fmt::Debug> Solver<S> for ParallelTabuSearchSolver<S, T> {
+impl<S: Clone + Send + Sync, T: std::fmt::Debug + Send + Sync> Solver<S>
+    for ParallelTabuSearchSolver<S, T>
+{
     fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        install(self.thread_pool.as_deref(), || {
+            self.tabu_search(initial_solution)
+        })
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("ParallelTabuSearchSolver")
+            .with_parameter("tabu_list_size", self.tabu_policy.default_tenure())
+            .with_parameter("local_improver", self.local_improver.name())
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+            .with_optional_parameter(
+                "num_threads",
+                self.thread_pool
+                    .as_ref()
+                    .map(|thread_pool| thread_pool.current_num_threads()),
+            )
+    }
+}
+
+impl<S: Clone, T: std::fmt::Debug> ParallelTabuSearchSolver<S, T> {
+    fn tabu_search(&self, initial_solution: S) -> SearchResult<S> {
         let start_time = stdtime::Instant::now();
+        self.local_improver.reset_panic();
 
+        let mut termination_criteria = self.termination_criteria.clone();
         let mut current_solution = self.objective.evaluate(initial_solution);
         let mut best_solution_seen = current_solution.clone();
-        let mut tabu_list = VecDeque::with_capacity(self.tabu_list_size);
-        let mut iteration_counter = 1;
-        let mut iteration_without_global_improvement = 0;
-        while let Some((new_solution, new_tabus)) =
-            self.local_improver.improve(&current_solution, &tabu_list)
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_time_limit),
+            termination_criteria
+                .as_ref()
+                .and_then(TerminationCriteria::effective_iteration_limit),
+            current_solution.objective_value(),
+            true,
+        );
+        let mut tabu_list = VecDeque::new();
+        let mut iteration_counter = 0;
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        while let (Some((new_solution, new_tabus)), neighbor_counts) =
+            self.improve(&current_solution, &best_solution_seen, &tabu_list)
         {
-            tabu_list.extend(new_tabus.into_iter());
-            while tabu_list.len() > self.tabu_list_size {
-                tabu_list.pop_front();
-            }
-            (self.function_between_steps)(
+            iteration_counter += 1;
+            self.tabu_policy.add_tabus(&mut tabu_list, new_tabus);
+            recorder.step(
                 iteration_counter,
                 &new_solution,
                 Some(&current_solution),
-                self.objective.clone(),
-                Some(start_time),
-                self.time_limit,
-                self.iteration_limit,
+                neighbor_counts,
             );
             current_solution = new_solution;
-            if current_solution.objective_value() < best_solution_seen.objective_value() {
+            if self
+                .objective
+                .is_better(&current_solution, &best_solution_seen)
+            {
                 best_solution_seen = current_solution.clone();
-                iteration_without_global_improvement = 0;
-            } else {
-                iteration_without_global_improvement += 1;
             }
 
-            if let Some(iteration_without_global_improvement_limit) =
-                self.iteration_without_global_improvement_limit
-            {
-                if iteration_without_global_improvement
-                    >= iteration_without_global_improvement_limit
-                {
-                    println!("Iteration without global improvement limit reached.");
-                    break;
-                }
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
             }
+        }
+        let panic_message = self.local_improver.panic_message();
+        if termination_reason == TerminationReason::LocalOptimum && panic_message.is_some() {
+            termination_reason = TerminationReason::Panicked;
+        }
+        SearchResult {
+            panic_message,
+            ..recorder.finish(best_solution_seen, iteration_counter, termination_reason)
+        }
+    }
+}
 
-            if let Some(time_limit) = self.time_limit {
-                if stdtime::Instant::now().duration_since(start_time) > time_limit {
-                    println!("Time limit reached.");
-                    break;
-                }
-            }
-            if let Some(iteration_limit) = self.iteration_limit {
-                if iteration_counter >= iteration_limit {
-                    println!("Iteration limit reached.");
-                    break;
-                }
-            }
-            iteration_counter += 1;
+impl<S, T> ParallelTabuSearchSolver<S, T> {
+    /// Returns the best non-tabu neighbor of the [`ParallelTabuImprover`] or, if it is better, the
+    /// best tabu neighbor that satisfies the [`AspirationCriterion`], which the
+    /// [`ParallelTabuImprover`] finds in the same exploration (see [`Aspiration`]).
+    /// * The [`NeighborCounts`] are extended by the tabu neighbors offered to the [`Aspiration`]
+    ///   (see [`TabuSearchSolver`][super::tabu_search::TabuSearchSolver]).
+    #[allow(clippy::type_complexity)]
+    fn improve(
+        &self,
+        current_solution: &EvaluatedSolution<S>,
+        best_solution_seen: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let aspiration = Aspiration::new(
+            self.aspiration_criterion.as_ref(),
+            current_solution,
+            best_solution_seen,
+            &self.objective,
+            !tabu_list.is_empty(),
+        );
+        let (non_tabu_neighbor, neighbor_counts) =
+            self.local_improver
+                .improve_with_aspiration(current_solution, tabu_list, &aspiration);
+        let (aspirating_neighbor, tabu_offered) = aspiration.finish();
+        let neighbor_counts = neighbor_counts.map(|neighbor_counts| {
+            counts_with_aspiration(
+                &self.objective,
+                &non_tabu_neighbor,
+                &aspirating_neighbor,
+                neighbor_counts,
+                tabu_offered,
+            )
+        });
+        if let Some(acceptance_logger) = &self.acceptance_logger {
+            log_aspiration_decisions(
+                acceptance_logger,
+                &self.objective,
+                current_solution,
+                &non_tabu_neighbor,
+                &aspirating_neighbor,
+            );
         }
-        best_solution_seen
+        (
+            prefer_aspirating_neighbor(&self.objective, non_tabu_neighbor, aspirating_neighbor),
+            neighbor_counts,
+        )
     }
 }
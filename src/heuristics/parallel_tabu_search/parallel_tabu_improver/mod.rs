@@ -3,6 +3,7 @@
 //! [`ParallelTabuSearchSolver`][super::ParallelTabuSearchSolver].
 pub mod parallel_tabu_minimizer;
 
+use crate::heuristics::common::{Aspiration, NeighborCounts};
 use crate::objective::EvaluatedSolution;
 pub use parallel_tabu_minimizer::ParallelTabuMinimizer;
 use std::collections::VecDeque;
@@ -24,4 +25,61 @@ pub trait ParallelTabuImprover<S, T>: Send + Sync {
         solution: &EvaluatedSolution<S>,
         tabu_list: &VecDeque<T>,
     ) -> Option<(EvaluatedSolution<S>, Vec<T>)>;
+
+    /// Same as [`improve`][ParallelTabuImprover::improve], but additionally returns the
+    /// [`NeighborCounts`] of the exploration, which the
+    /// [`ParallelTabuSearchSolver`][super::ParallelTabuSearchSolver] passes to the
+    /// `function_between_steps`.
+    /// The default implementation does not count the neighbors, i.e., it returns `None` as
+    /// counts.
+    #[allow(clippy::type_complexity)]
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        (self.improve(solution, tabu_list), None)
+    }
+
+    /// Same as [`improve_counted`][ParallelTabuImprover::improve_counted], but if the
+    /// `aspiration` is [active][Aspiration::is_active], the tabu neighbors are explored as well
+    /// (see
+    /// [`neighbors_with_tabu_status_of`][super::ParallelTabuNeighborhood::neighbors_with_tabu_status_of])
+    /// and handed to the `aspiration` instead of being skipped (see
+    /// [`TabuImprover::improve_with_aspiration`][crate::heuristics::tabu_search::tabu_improver::TabuImprover::improve_with_aspiration]).
+    /// * The default implementation ignores the `aspiration`, i.e., the tabu list is strict.
+    #[allow(clippy::type_complexity)]
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        _aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.improve_counted(solution, tabu_list)
+    }
+
+    /// Returns the name of the improver, which is listed by
+    /// [`Solver::describe`][crate::heuristics::Solver::describe]. The default implementation
+    /// returns the name of the type (e.g., `"ParallelTabuMinimizer"`).
+    fn name(&self) -> String {
+        crate::heuristics::common::short_type_name::<Self>()
+    }
+
+    /// Returns the message of the panic the improver caught in the current run (see
+    /// [`PanicSafeImprover`][crate::heuristics::common::PanicSafeImprover]), such that the solver
+    /// reports [`Panicked`][crate::heuristics::common::TerminationReason::Panicked] instead of a
+    /// local optimum. The default implementation returns `None`.
+    fn panic_message(&self) -> Option<String> {
+        None
+    }
+
+    /// Forgets a panic caught in a previous run. The solver calls this at the start of each run.
+    /// The default implementation does nothing.
+    fn reset_panic(&self) {}
 }
@@ -1,24 +1,42 @@
 //! [`ParallelTabuMinimizer`] searches the whole [`ParallelTabuNeighborhood`] of a solution in parallel
 //! and returns the best non-tabu neighbor.
 
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::ParallelIterator;
+use crate::parallel::{install, thread_pool, ThreadPool};
 use crate::{
-    heuristics::parallel_tabu_search::ParallelTabuNeighborhood,
-    objective::{EvaluatedSolution, Objective},
+    heuristics::{
+        common::{Aspiration, NeighborCounts},
+        parallel_tabu_search::ParallelTabuNeighborhood,
+    },
+    objective::{best_of_batches, BatchObjective, EvaluatedSolution, Objective},
+};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
-use rayon::iter::ParallelIterator;
-use std::{collections::VecDeque, sync::Arc};
 
 use super::ParallelTabuImprover;
 
 /// [`ParallelTabuMinimizer`] searches the whole [`ParallelTabuNeighborhood`] of a solution (and a tabu list)
 /// and returns the best non-tabu neighbor with new tabus.
-/// * This is done in parallel using the [`ParallelIterator`] of [`rayon`].
+/// * This is done in parallel using the [`ParallelIterator`] of [`rayon`](https://docs.rs/rayon/)
+///   (sequentially without the `rayon` feature).
 /// * Solution type `S` and the tabu type `T` must implement [`Send`] and [`Sync`].
 /// * If the computation or the evaluation of a neighbor is CPU-heavy this might be a good choice.
 /// * If all neighbors are tabu, `None` is returned.
+/// * The neighborhood is explored in the current thread pool unless a dedicated [`ThreadPool`]
+///   is set (see [`with_thread_pool`][ParallelTabuMinimizer::with_thread_pool]).
+/// * The neighbors can be evaluated in batches by a [`BatchObjective`] (see
+///   [`with_batch_objective`][ParallelTabuMinimizer::with_batch_objective]).
 pub struct ParallelTabuMinimizer<S, N> {
     neighborhood: Arc<N>,
     objective: Arc<Objective<S>>,
+    thread_pool: Option<Arc<ThreadPool>>,
+    batch_objective: Option<(Arc<dyn BatchObjective<S>>, usize)>,
 }
 
 impl<S, N> ParallelTabuMinimizer<S, N> {
@@ -27,7 +45,75 @@ impl<S, N> ParallelTabuMinimizer<S, N> {
         Self {
             neighborhood,
             objective,
+            thread_pool: None,
+            batch_objective: None,
+        }
+    }
+
+    /// Evaluates the non-tabu neighbors by the [`BatchObjective`] in batches of `batch_size`
+    /// neighbors (each batch is a parallel task) instead of one at a time by the [`Objective`].
+    pub fn with_batch_objective(
+        mut self,
+        batch_objective: Arc<dyn BatchObjective<S>>,
+        batch_size: usize,
+    ) -> Self {
+        self.batch_objective = Some((batch_objective, batch_size));
+        self
+    }
+
+    /// Explores the neighborhood in the given [`ThreadPool`] instead of the global thread pool
+    /// of [`rayon`](https://docs.rs/rayon/) (or of the solver).
+    pub fn with_thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Explores the neighborhood in a dedicated [`ThreadPool`] with `num_threads` threads (see
+    /// [`thread_pool`]).
+    pub fn with_num_threads(self, num_threads: usize) -> Self {
+        self.with_thread_pool(Arc::new(thread_pool(num_threads)))
+    }
+}
+
+impl<S: Send + Sync, N: Send + Sync> ParallelTabuMinimizer<S, N> {
+    /// Returns the best of the non-tabu `neighbors` (explored in parallel) and the
+    /// [`NeighborCounts`].
+    #[allow(clippy::type_complexity)]
+    fn best_neighbor<T: Send>(
+        &self,
+        neighbors: impl ParallelIterator<Item = (S, Vec<T>)>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let evaluated = AtomicU64::new(0);
+        let neighbors = neighbors.inspect(|_| {
+            evaluated.fetch_add(1, Ordering::Relaxed);
+        });
+        let best_neighbor_with_new_tabus = match &self.batch_objective {
+            Some((batch_objective, batch_size)) => {
+                best_of_batches(batch_objective.as_ref(), *batch_size, neighbors)
+            }
+            None => neighbors
+                .map(|(neighbor, new_tabus)| (self.objective.evaluate(neighbor), new_tabus))
+                .min_by(|(s1, _), (s2, _)| {
+                    s1.objective_value()
+                        .partial_cmp(s2.objective_value())
+                        .unwrap()
+                }),
+        };
+        if best_neighbor_with_new_tabus.is_none() {
+            report!(Warning, "no swap possible.");
         }
+
+        let evaluated = evaluated.into_inner();
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(best_neighbor_with_new_tabus.is_some()),
+            ..NeighborCounts::default()
+        };
+        (best_neighbor_with_new_tabus, Some(neighbor_counts))
     }
 }
 
@@ -39,19 +125,47 @@ impl<S: Send + Sync, T: Send + Sync, N: ParallelTabuNeighborhood<S, T>> Parallel
         solution: &EvaluatedSolution<S>,
         tabu_list: &VecDeque<T>,
     ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
-        let best_neighbor_with_new_tabus = self
-            .neighborhood
-            .neighbors_of(solution.solution(), tabu_list)
-            .map(|(neighbor, new_tabus)| (self.objective.evaluate(neighbor), new_tabus))
-            .min_by(|(s1, _), (s2, _)| {
-                s1.objective_value()
-                    .partial_cmp(s2.objective_value())
-                    .unwrap()
-            });
-        if best_neighbor_with_new_tabus.is_none() {
-            println!("\x1b[31mwarning:\x1b[0m no swap possible.");
-        }
+        self.improve_counted(solution, tabu_list).0
+    }
 
-        best_neighbor_with_new_tabus
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        install(self.thread_pool.as_deref(), || {
+            self.best_neighbor(
+                self.neighborhood
+                    .neighbors_of(solution.solution(), tabu_list),
+            )
+        })
+    }
+
+    /// The tabu neighbors are evaluated one at a time by the [`Objective`] (also with a
+    /// [`BatchObjective`]).
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        if !aspiration.is_active() {
+            return self.improve_counted(solution, tabu_list);
+        }
+        install(self.thread_pool.as_deref(), || {
+            self.best_neighbor(
+                self.neighborhood
+                    .neighbors_with_tabu_status_of(solution.solution(), tabu_list)
+                    .filter_map(|(neighbor, new_tabus, is_tabu)| {
+                        aspiration.filter_tabu(neighbor, new_tabus, is_tabu)
+                    }),
+            )
+        })
     }
 }
@@ -0,0 +1,393 @@
+//! This module contains the [`ParetoLocalSearchSolver`] implementing Pareto local search on
+//! selected levels of the [`Objective`].
+//! * Instead of comparing the objective values lexicographically, the solver considers the
+//!   selected levels as equally important objectives and compares solutions by
+//!   [Pareto dominance][ObjectiveValue::dominates].
+//! * It maintains a [`ParetoArchive`] of mutually non-dominated solutions, which starts with the
+//!   initial solution.
+//! * In each iteration, the neighborhood of an unexplored archive member is explored and each
+//!   neighbor is inserted into the archive, unless it is dominated by (or has the same values as)
+//!   a member. Members dominated by the neighbor are removed.
+//! * If the archive exceeds its capacity, the member with the smallest crowding distance (i.e.,
+//!   in the most crowded region of the front) is removed.
+//! * The search stops if all archive members are explored or if the [`TerminationCriteria`] are
+//!   met.
+//! * The archive is returned by [`solve_with_archive`][ParetoLocalSearchSolver::solve_with_archive].
+//!   As [`Solver`], the lexicographically best archive member is returned.
+use std::sync::Arc;
+use std::time as stdtime;
+
+use super::common::{
+    default_function_between_steps, report_termination, FunctionBetweenSteps, NeighborCounts,
+    Neighborhood, SearchRecorder, SearchResult, SolverDescription, TerminationCriteria,
+    TerminationReason,
+};
+use super::Solver;
+use crate::objective::{EvaluatedSolution, MinByObjective, Objective, ObjectiveValue};
+
+/// An archive of mutually non-dominated [`EvaluatedSolutions`][EvaluatedSolution], where
+/// dominance is defined over selected levels of the [`ObjectiveValue`] (see
+/// [`ObjectiveValue::dominates`]).
+/// * Solutions with the same values on the selected levels as a member are not added.
+/// * If a `capacity` is given and exceeded, the member with the smallest crowding distance is
+///   pruned. The extreme members of each level have an infinite crowding distance and are only
+///   pruned if all members are extreme.
+pub struct ParetoArchive<S> {
+    levels: Vec<usize>,
+    capacity: Option<usize>,
+    members: Vec<EvaluatedSolution<S>>,
+    explored: Vec<bool>,
+}
+
+impl<S> ParetoArchive<S> {
+    /// Creates an empty [`ParetoArchive`] that compares the objective values on the given
+    /// `levels` (indices of hierarchy levels) and keeps at most `capacity` members (if given).
+    /// * Panics if no level is given or the capacity is zero.
+    pub fn new(levels: Vec<usize>, capacity: Option<usize>) -> ParetoArchive<S> {
+        assert!(!levels.is_empty(), "At least one level must be given.");
+        assert!(capacity != Some(0), "The capacity must be at least 1.");
+        ParetoArchive {
+            levels,
+            capacity,
+            members: Vec::new(),
+            explored: Vec::new(),
+        }
+    }
+
+    /// Inserts the `solution` if it is not dominated by (or equal on the selected levels to) a
+    /// member, removes the members that are dominated by it and prunes the archive to its
+    /// capacity. Returns `true` if the solution is a member afterwards.
+    pub fn insert(&mut self, solution: EvaluatedSolution<S>) -> bool {
+        if self.members.iter().any(|member| {
+            self.equal_on_levels(member.objective_value(), solution.objective_value())
+                || member
+                    .objective_value()
+                    .dominates(solution.objective_value(), &self.levels)
+        }) {
+            return false;
+        }
+        let mut index = 0;
+        while index < self.members.len() {
+            if solution
+                .objective_value()
+                .dominates(self.members[index].objective_value(), &self.levels)
+            {
+                self.members.remove(index);
+                self.explored.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        self.members.push(solution);
+        self.explored.push(false);
+
+        if let Some(capacity) = self.capacity {
+            if self.members.len() > capacity {
+                let pruned = self.most_crowded_member();
+                self.members.remove(pruned);
+                self.explored.remove(pruned);
+                return pruned != self.members.len();
+            }
+        }
+        true
+    }
+
+    /// Returns the members of the archive.
+    pub fn members(&self) -> &[EvaluatedSolution<S>] {
+        &self.members
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the archive has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns the levels on which the objective values are compared.
+    pub fn levels(&self) -> &[usize] {
+        &self.levels
+    }
+
+    /// Consumes the archive and returns its members.
+    pub fn into_members(self) -> Vec<EvaluatedSolution<S>> {
+        self.members
+    }
+
+    fn equal_on_levels(&self, a: &ObjectiveValue, b: &ObjectiveValue) -> bool {
+        self.levels
+            .iter()
+            .all(|&level| a.as_vec()[level] == b.as_vec()[level])
+    }
+
+    /// Returns the index of the member with the smallest crowding distance (the first one on
+    /// ties).
+    fn most_crowded_member(&self) -> usize {
+        let mut crowding_distances = vec![0.0; self.members.len()];
+        for &level in &self.levels {
+            let value =
+                |index: usize| self.members[index].objective_value().as_vec()[level].to_f64();
+            let mut order: Vec<usize> = (0..self.members.len()).collect();
+            order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+            let range = value(order[order.len() - 1]) - value(order[0]);
+            crowding_distances[order[0]] = f64::INFINITY;
+            crowding_distances[order[order.len() - 1]] = f64::INFINITY;
+            if range > 0.0 {
+                for window in order.windows(3) {
+                    crowding_distances[window[1]] += (value(window[2]) - value(window[0])) / range;
+                }
+            }
+        }
+        (0..crowding_distances.len())
+            .min_by(|&a, &b| crowding_distances[a].total_cmp(&crowding_distances[b]))
+            .unwrap()
+    }
+}
+
+/// The Pareto local search solver uses a [`Neighborhood`] and an [`Objective`] to approximate
+/// the Pareto front of the selected levels of the objective by a [`ParetoArchive`].
+/// * The `function_between_steps` is executed after each exploration of an archive member.
+/// * The default `function_between_steps` (if `None`) is printing the iteration number, the
+///   objective value (in comparison the the previous objective value) and the time elapsed since
+///   the start.
+/// * The solver stops if all archive members are explored or if the [`TerminationCriteria`] are
+///   met.
+pub struct ParetoLocalSearchSolver<S> {
+    neighborhood: Arc<dyn Neighborhood<S>>,
+    objective: Arc<Objective<S>>,
+    levels: Vec<usize>,
+    archive_capacity: Option<usize>,
+    function_between_steps: FunctionBetweenSteps<S>,
+    termination_criteria: Option<TerminationCriteria>,
+}
+
+impl<S> ParetoLocalSearchSolver<S> {
+    /// Creates a new [`ParetoLocalSearchSolver`] with the given [`Neighborhood`] and
+    /// [`Objective`], which compares the objective values on all levels.
+    pub fn initialize(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+    ) -> Self {
+        Self::with_options(neighborhood, objective, None, None, None, None)
+    }
+
+    /// Creates a new [`ParetoLocalSearchSolver`] with the given [`Neighborhood`] and
+    /// [`Objective`].
+    /// * `levels` are the indices of the hierarchy levels on which the solutions are compared by
+    ///   Pareto dominance. If `None`, all levels are used.
+    /// * `archive_capacity` is the maximal number of members of the [`ParetoArchive`]. If
+    ///   `None`, the archive is unbounded.
+    /// * `function_between_steps` is executed after each step. If `None`, the default is printing
+    ///   the iteration number, the objective value (in comparison the the previous objective
+    ///   value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit or an iteration
+    ///   limit (i.e., a number of explored archive members), are checked after each iteration
+    ///   with the lexicographically best archive member. The last iteration is allowed to finish.
+    ///   If `None`, the search runs until all archive members are explored.
+    /// * Panics if a level does not exist.
+    pub fn with_options(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        levels: Option<Vec<usize>>,
+        archive_capacity: Option<usize>,
+        function_between_steps: Option<FunctionBetweenSteps<S>>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> Self {
+        let number_of_levels = objective.level_names().len();
+        let levels = levels.unwrap_or_else(|| (0..number_of_levels).collect());
+        if let Some(level) = levels.iter().find(|&&level| level >= number_of_levels) {
+            panic!(
+                "Level {} does not exist, the objective has {} levels.",
+                level, number_of_levels
+            );
+        }
+        Self {
+            neighborhood,
+            objective,
+            levels,
+            archive_capacity,
+            function_between_steps: function_between_steps
+                .unwrap_or(default_function_between_steps()),
+            termination_criteria,
+        }
+    }
+}
+
+impl<S: Clone> ParetoLocalSearchSolver<S> {
+    /// Solves the problem like [`solve_with_report`][Solver::solve_with_report] and additionally
+    /// returns the final [`ParetoArchive`].
+    pub fn solve_with_archive(&self, initial_solution: S) -> (SearchResult<S>, ParetoArchive<S>) {
+        let start_time = stdtime::Instant::now();
+        let initial_solution = self.objective.evaluate(initial_solution);
+        let mut termination_criteria = self.termination_criteria.clone();
+        let time_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_time_limit);
+        let iteration_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_iteration_limit);
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            time_limit,
+            iteration_limit,
+            initial_solution.objective_value(),
+            true,
+        );
+        let mut archive = ParetoArchive::new(self.levels.clone(), self.archive_capacity);
+        archive.insert(initial_solution);
+        let mut previous_solution: Option<EvaluatedSolution<S>> = None;
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        let mut iteration_counter = 0;
+
+        while let Some(index) = archive.explored.iter().position(|explored| !explored) {
+            iteration_counter += 1;
+            archive.explored[index] = true;
+            let current_solution = archive.members[index].clone();
+            let mut neighbor_counts = NeighborCounts::default();
+            for neighbor in self.neighborhood.neighbors_of(current_solution.solution()) {
+                neighbor_counts.generated += 1;
+                neighbor_counts.evaluated += 1;
+                if archive.insert(self.objective.evaluate(neighbor)) {
+                    neighbor_counts.accepted += 1;
+                }
+            }
+            recorder.step(
+                iteration_counter,
+                &current_solution,
+                previous_solution.as_ref(),
+                Some(neighbor_counts),
+            );
+            previous_solution = Some(current_solution);
+
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                let best_objective_value = archive
+                    .members()
+                    .iter()
+                    .map(EvaluatedSolution::objective_value)
+                    .min()
+                    .unwrap();
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_objective_value,
+                )
+            }) {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
+            }
+        }
+        report!(
+            Info,
+            "Pareto archive with {} members after {} iterations.",
+            archive.len(),
+            iteration_counter
+        );
+
+        let best_solution = archive
+            .members()
+            .iter()
+            .cloned()
+            .min_by_objective()
+            .unwrap();
+        (
+            recorder.finish(best_solution, iteration_counter, termination_reason),
+            archive,
+        )
+    }
+}
+
+impl<S: Clone> Solver<S> for ParetoLocalSearchSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.solve_with_report(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        self.solve_with_archive(initial_solution).0
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("ParetoLocalSearchSolver")
+            .with_parameter("levels", format!("{:?}", self.levels))
+            .with_optional_parameter("archive_capacity", self.archive_capacity)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::silent_function_between_steps;
+    use crate::objective::{BaseValue, Indicator};
+    use crate::test_fixtures::PlusMinusOne;
+
+    struct SquaredDistanceTo(i64);
+
+    impl Indicator<i64> for SquaredDistanceTo {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer((solution - self.0) * (solution - self.0))
+        }
+        fn name(&self) -> String {
+            format!("SquaredDistanceTo{}", self.0)
+        }
+    }
+
+    fn solver(
+        archive_capacity: Option<usize>,
+        termination_criteria: Option<TerminationCriteria>,
+    ) -> ParetoLocalSearchSolver<i64> {
+        ParetoLocalSearchSolver::with_options(
+            Arc::new(PlusMinusOne),
+            Arc::new(Objective::new_single_indicator_per_level(vec![
+                Box::new(SquaredDistanceTo(0)),
+                Box::new(SquaredDistanceTo(10)),
+            ])),
+            None,
+            archive_capacity,
+            Some(silent_function_between_steps()),
+            termination_criteria,
+        )
+    }
+
+    #[test]
+    fn test_pareto_local_search() {
+        let (result, archive) = solver(None, None).solve_with_archive(3);
+
+        let mut solutions: Vec<i64> = archive.members().iter().map(|m| *m.solution()).collect();
+        solutions.sort();
+        assert_eq!(solutions, (0..=10).collect::<Vec<_>>());
+        assert_eq!(result.termination_reason, TerminationReason::LocalOptimum);
+        // lexicographically, the first level is most important
+        assert_eq!(*result.best_solution.solution(), 0);
+    }
+
+    #[test]
+    fn test_archive_pruning() {
+        let (_, archive) = solver(Some(4), None).solve_with_archive(5);
+
+        let solutions: Vec<i64> = archive.members().iter().map(|m| *m.solution()).collect();
+        assert_eq!(archive.len(), 4);
+        // the extreme members are never pruned
+        assert!(solutions.contains(&0) && solutions.contains(&10));
+    }
+
+    #[test]
+    fn test_termination_criteria() {
+        let (result, archive) =
+            solver(None, Some(TerminationCriteria::iteration_limit(2))).solve_with_archive(5);
+
+        assert_eq!(result.termination_reason, TerminationReason::IterationLimit);
+        assert_eq!(result.iterations, Some(2));
+        assert!(archive.len() < 11);
+    }
+}
@@ -0,0 +1,181 @@
+//! This module contains the [`CoolingSchedule`] trait, which defines how the temperature of the
+//! [`SimulatedAnnealingSolver`][super::SimulatedAnnealingSolver] changes, and the [`Reheating`],
+//! which raises the temperature again if the search is stuck.
+//! There are three cooling schedules to choose from:
+//! * [`GeometricCooling`]: multiplies the temperature by a factor whenever a worse neighbor is
+//!   accepted (this is the default, using the `cooling_factor` of the solver).
+//! * [`LinearCooling`]: subtracts a constant whenever a worse neighbor is accepted.
+//! * [`AdaptiveCooling`]: a Lam-like schedule that keeps the acceptance rate close to a target by
+//!   cooling if too many neighbors are accepted and heating otherwise.
+use super::{ScalingFactor, Temperature};
+
+/// The information about the last iteration that is passed to a [`CoolingSchedule`].
+#[derive(Clone, Copy, Debug)]
+pub struct CoolingStep {
+    /// The iteration (starting with 1).
+    pub iteration: u32,
+    /// The initial temperature of the solver.
+    pub initial_temperature: Temperature,
+    /// `true` if the accepted neighbor is not better than the previous solution.
+    pub accepted_worse: bool,
+    /// The acceptance rate of this iteration, i.e., one divided by the number of neighbors that
+    /// were evaluated until the acceptance.
+    pub acceptance_rate: f64,
+}
+
+/// Defines how the temperature of the [`SimulatedAnnealingSolver`][super::SimulatedAnnealingSolver]
+/// changes after each iteration.
+pub trait CoolingSchedule: Send + Sync {
+    /// Returns the temperature for the next iteration given the current `temperature` and the
+    /// information about the last iteration.
+    fn next_temperature(&self, temperature: Temperature, step: &CoolingStep) -> Temperature;
+
+    /// Returns the name of the cooling schedule, which is listed by
+    /// [`Solver::describe`][crate::heuristics::Solver::describe]. The default implementation
+    /// returns the name of the type (e.g., `"GeometricCooling"`).
+    fn name(&self) -> String {
+        crate::heuristics::common::short_type_name::<Self>()
+    }
+}
+
+/// Multiplies the temperature by the `cooling_factor` (between 0 and 1) whenever a worse neighbor
+/// is accepted.
+pub struct GeometricCooling {
+    cooling_factor: ScalingFactor,
+}
+
+impl GeometricCooling {
+    /// Creates a new [`GeometricCooling`] with the given `cooling_factor` (e.g., 0.9).
+    pub fn new(cooling_factor: ScalingFactor) -> GeometricCooling {
+        GeometricCooling { cooling_factor }
+    }
+}
+
+impl CoolingSchedule for GeometricCooling {
+    fn next_temperature(&self, temperature: Temperature, step: &CoolingStep) -> Temperature {
+        if step.accepted_worse {
+            temperature * self.cooling_factor
+        } else {
+            temperature
+        }
+    }
+}
+
+/// Subtracts the `decrement` from the temperature whenever a worse neighbor is accepted (the
+/// temperature never drops below 0).
+pub struct LinearCooling {
+    decrement: Temperature,
+}
+
+impl LinearCooling {
+    /// Creates a new [`LinearCooling`] with the given `decrement`, e.g., the initial temperature
+    /// divided by the expected number of accepted worse neighbors.
+    pub fn new(decrement: Temperature) -> LinearCooling {
+        LinearCooling { decrement }
+    }
+}
+
+impl CoolingSchedule for LinearCooling {
+    fn next_temperature(&self, temperature: Temperature, step: &CoolingStep) -> Temperature {
+        if step.accepted_worse {
+            (temperature - self.decrement).max(0.0)
+        } else {
+            temperature
+        }
+    }
+}
+
+/// An adaptive schedule in the spirit of Lam and Delosme: after each iteration, the temperature
+/// is multiplied by the `factor` (between 0 and 1) if the acceptance rate is above the
+/// `target_acceptance_rate` and divided by it otherwise.
+/// * Hence, the temperature oscillates around the value at which the target acceptance rate is
+///   reached.
+/// * The temperature is never raised above the initial temperature.
+pub struct AdaptiveCooling {
+    target_acceptance_rate: f64,
+    factor: ScalingFactor,
+}
+
+impl AdaptiveCooling {
+    /// Creates a new [`AdaptiveCooling`] with the given `target_acceptance_rate` (e.g., 0.44)
+    /// and `factor` (e.g., 0.95).
+    pub fn new(target_acceptance_rate: f64, factor: ScalingFactor) -> AdaptiveCooling {
+        AdaptiveCooling {
+            target_acceptance_rate,
+            factor,
+        }
+    }
+}
+
+impl CoolingSchedule for AdaptiveCooling {
+    fn next_temperature(&self, temperature: Temperature, step: &CoolingStep) -> Temperature {
+        if step.acceptance_rate > self.target_acceptance_rate {
+            temperature * self.factor
+        } else {
+            (temperature / self.factor).min(step.initial_temperature)
+        }
+    }
+}
+
+/// Raises the temperature to `temperature_fraction` times the initial temperature after
+/// `non_improving_iterations` iterations without a new best solution.
+#[derive(Clone, Copy, Debug)]
+pub struct Reheating {
+    non_improving_iterations: u32,
+    temperature_fraction: f64,
+}
+
+impl Reheating {
+    /// Creates a new [`Reheating`] that raises the temperature to `temperature_fraction` (e.g.,
+    /// 0.5) times the initial temperature after `non_improving_iterations` iterations without a
+    /// new best solution.
+    pub fn new(non_improving_iterations: u32, temperature_fraction: f64) -> Reheating {
+        Reheating {
+            non_improving_iterations,
+            temperature_fraction,
+        }
+    }
+
+    /// Returns the reheated temperature if the search is stuck for `non_improving_iterations`
+    /// iterations.
+    pub(super) fn reheat(
+        &self,
+        iterations_without_improvement: u32,
+        initial_temperature: Temperature,
+    ) -> Option<Temperature> {
+        (iterations_without_improvement >= self.non_improving_iterations)
+            .then_some(initial_temperature * self.temperature_fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooling_schedules() {
+        let step = |accepted_worse: bool, acceptance_rate: f64| CoolingStep {
+            iteration: 1,
+            initial_temperature: 100.0,
+            accepted_worse,
+            acceptance_rate,
+        };
+
+        let geometric = GeometricCooling::new(0.5);
+        assert_eq!(geometric.next_temperature(10.0, &step(true, 1.0)), 5.0);
+        assert_eq!(geometric.next_temperature(10.0, &step(false, 1.0)), 10.0);
+
+        let linear = LinearCooling::new(4.0);
+        assert_eq!(linear.next_temperature(10.0, &step(true, 1.0)), 6.0);
+        assert_eq!(linear.next_temperature(3.0, &step(true, 1.0)), 0.0);
+
+        let adaptive = AdaptiveCooling::new(0.4, 0.5);
+        assert_eq!(adaptive.next_temperature(10.0, &step(false, 0.5)), 5.0);
+        assert_eq!(adaptive.next_temperature(10.0, &step(false, 0.25)), 20.0);
+        assert_eq!(adaptive.next_temperature(80.0, &step(false, 0.25)), 100.0);
+
+        let reheating = Reheating::new(3, 0.5);
+        assert_eq!(reheating.reheat(2, 100.0), None);
+        assert_eq!(reheating.reheat(3, 100.0), Some(50.0));
+    }
+}
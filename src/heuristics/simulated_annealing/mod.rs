@@ -2,36 +2,58 @@
 //! [simulated annealing metaheuristic](https://en.wikipedia.org/wiki/Simulated_annealing).
 //! * Starts with an initial solution and iteratively considers neighbors.
 //! * An improvement is always accepted, but a worse neighbor is also accepted with a certain
-//! probability.
+//!   probability.
 //! * This probability is based on the difference in objective value and the current
-//! temperature.
-//! * The temperature is reduced whenever a worse neighbor is accepted.
+//!   temperature.
+//! * The temperature is reduced whenever a worse neighbor is accepted (or according to another
+//!   [`CoolingSchedule`]). Optionally, it is raised again by a [`Reheating`] if no new best
+//!   solution is found for a while.
+//! * With an [`ElitePool`], the search keeps the best diverse solutions
+//!   and restarts from one of them if no new best solution is found for a while.
 //! * The search stops after a certain number of iterations, or after a certain time limit, or if the
-//! whole neighborhood is explored without any acceptance.
+//!   whole neighborhood is explored without any acceptance.
 //! * The best solution seen during this process is returned.
+//! * To advance the annealing one iteration at a time (e.g., in a teaching demo),
+//!   [`SimulatedAnnealingSolver::steps`] returns a [`SimulatedAnnealingSteps`] iterator, whose
+//!   current solution can be inspected and replaced between the iterations.
 //! * The acceptance probability usualy depends exponentially on the difference in objective value
-//! and the current temperature, i.e., e<sup>-∆f/T</sup>, where ∆f is the difference in
-//! objective value and T is the current temperature.
+//!   and the current temperature, i.e., e<sup>-∆f/T</sup>, where ∆f is the difference in
+//!   objective value and T is the current temperature.
 //! * The simulated annealing heuristic is similar to the deterministic [threshold accepting
-//! heuristic][super::threshold_accepting], which performs similar, but does not require
-//! computing the acceptance probability.
+//!   heuristic][super::threshold_accepting], which performs similar, but does not require
+//!   computing the acceptance probability.
 //!
 //! For an example, see the [simulated annealing solver for the
 //! TSP][crate::examples::tsp::solvers::simulated_annealing].
+pub mod cooling_schedule;
+
+use std::collections::HashSet;
 use std::{sync::Arc, time as stdtime};
 
-use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use rand::Rng;
-use rand::SeedableRng;
+use rand::RngCore;
 
 use crate::objective::ObjectiveValue;
 use crate::objective::{EvaluatedSolution, Objective};
 
-use super::common::default_function_between_steps;
+use super::common::default_rng_factory;
+use super::common::RngFactory;
+use super::common::SolverDescription;
+use super::common::{
+    default_function_between_steps, AcceptanceCriterion, AcceptanceDecision, AcceptanceLogger,
+    ConvergenceDetector, ElitePool, NeighborCounts,
+};
+use super::common::{
+    report_termination, wind_down, SearchRecorder, SearchResult, TerminationCriteria,
+    TerminationReason,
+};
+use super::local_search::local_improver::{LocalImprover, TakeFirst};
 use super::{
     common::{FunctionBetweenSteps, Neighborhood},
     Solver,
 };
+use cooling_schedule::{CoolingSchedule, CoolingStep, GeometricCooling, Reheating};
 
 /// Type for the temperature, which should be in the magnitude of the objective values in the
 /// beginning.
@@ -41,39 +63,127 @@ pub type Probability = f64;
 /// Type for the `cooling_factor`, which is a value between 0 and 1 (e.g., 0.9).
 pub type ScalingFactor = f64;
 
+/// Defines in which order the neighbors are considered by the [`SimulatedAnnealingSolver`].
+/// * In each iteration, the considered neighbors are evaluated one after another and the first
+///   accepted neighbor becomes the new current solution.
+/// * If no neighbor is accepted, the search stops.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NeighborSampling {
+    /// The neighbors are considered in the order of the [`Neighborhood`] iterator (default). This
+    /// is the fastest, but biased towards the first neighbors of the iterator.
+    #[default]
+    InOrder,
+    /// The neighbors are collected and considered in a random order (i.e., each neighbor is
+    /// considered at most once per iteration).
+    Shuffled,
+    /// The neighbors are collected and for each attempt a uniformly random neighbor is drawn
+    /// among the neighbors that were not yet tried in this iteration, as in textbook simulated
+    /// annealing. In contrast to [`Shuffled`][NeighborSampling::Shuffled], the random draws are
+    /// only made for the evaluated neighbors, which is cheaper if neighbors are accepted early.
+    UniformRandom,
+    /// Like [`UniformRandom`][NeighborSampling::UniformRandom], but each neighbor is drawn by
+    /// reservoir sampling over a new neighborhood iterator, such that the neighbors are never
+    /// collected (for large neighborhoods or expensive solutions). Only the indices of the drawn
+    /// neighbors are stored, but the neighborhood is enumerated once per attempt.
+    Reservoir,
+}
+
 /// Type for the `acceptance_probability_function`.
 pub type AcceptanceProbabilityFunction =
-    Box<dyn Fn(&ObjectiveValue, &ObjectiveValue, Temperature) -> Probability>;
+    Box<dyn Fn(&ObjectiveValue, &ObjectiveValue, Temperature) -> Probability + Send + Sync>;
+
+/// Returns the [`AcceptanceProbabilityFunction`] e<sup>-∆f/(rT)</sup>, where ∆f is the
+/// difference in the most important level in which the objective values differ and r is the
+/// expected range of this level (see [`Objective::level_ranges`]).
+/// * Improvements (and equal objective values) are always accepted.
+/// * As the difference is normalized by the range, the temperature does not need to be in the
+///   magnitude of the objective values (see
+///   [`initialize_normalized`][SimulatedAnnealingSolver::initialize_normalized]).
+/// * For a level without declared [`bounds`][crate::objective::Indicator::bounds] (or with an
+///   empty range), the difference is not normalized and a warning is printed.
+pub fn normalized_acceptance_probability_function<S>(
+    objective: &Objective<S>,
+) -> AcceptanceProbabilityFunction {
+    let level_ranges: Vec<Option<f64>> = objective
+        .level_ranges()
+        .into_iter()
+        .map(|range| {
+            range
+                .map(|range| range.to_f64())
+                .filter(|range| *range > 0.0)
+        })
+        .collect();
+    if level_ranges.iter().any(|range| range.is_none()) {
+        report!(
+            Warning,
+            "Not all hierarchy levels have declared bounds, their differences are not normalized."
+        );
+    }
+    Box::new(
+        move |current_objective_value: &ObjectiveValue,
+              neighbor_objective_value: &ObjectiveValue,
+              temperature: Temperature| {
+            if neighbor_objective_value <= current_objective_value {
+                return 1.0;
+            }
+            current_objective_value
+                .iter()
+                .zip(neighbor_objective_value.iter())
+                .zip(level_ranges.iter())
+                .find(|((current_value, neighbor_value), _)| current_value != neighbor_value)
+                .map(|((current_value, neighbor_value), range)| {
+                    let difference = (*neighbor_value - *current_value).to_f64();
+                    (-difference / range.unwrap_or(1.0) / temperature).exp()
+                })
+                .unwrap_or(1.0)
+        },
+    )
+}
 
 /// A simulated annealing solver that uses a [`Neighborhood`] and an [`Objective`], an
 /// `initial_temperature` (`f32` in the magnitute of the objective values),
 /// a `cooling_factor` (`f32`between 0 and 1, e.g., 0.9), and an
 /// [`AcceptanceProbabilityFunction`] to find a good solution.
 /// * The [`AcceptanceProbabilityFunction`] is a function that takes the current objective value,
-/// the objective value of a neighbor, and the current temperature and returns the accpetance probability
-/// (which should be 1 if the neighbor is and improvement and it should decrease with
-/// increasing difference in objective value and decreasing temperature). Typical it is an
-/// exponential function, e.g., e<sup>-∆f/T</sup>, where ∆f is the difference in objective value
-/// and T is the current temperature.
-/// * Whenever a worse neighbor is accepted, the `current_temperature` is reduced by the `cooling_factor`.
+///   the objective value of a neighbor, and the current temperature and returns the accpetance probability
+///   (which should be 1 if the neighbor is and improvement and it should decrease with
+///   increasing difference in objective value and decreasing temperature). Typical it is an
+///   exponential function, e.g., e<sup>-∆f/T</sup>, where ∆f is the difference in objective value
+///   and T is the current temperature.
+/// * Whenever a worse neighbor is accepted, the `current_temperature` is reduced by the
+///   `cooling_factor`, unless another [`CoolingSchedule`] is provided.
+/// * An optional [`Reheating`] raises the temperature again after a number of iterations
+///   without a new best solution.
 /// * The `function_between_steps` is executed after each improvement step.
 /// * The default `function_between_steps` (if `None`) is printing the iteration number, the
-/// objective value (in comparison the the previous objective value) and the time elapsed since the start.
-/// * The solver stops after a certain number of iterations or after a certain time limit.
-/// * If `iteration_limit` and `time_limit` is `None`, the solver runs until a whole neighborhood is explored
-/// without any acceptance.
-/// For a high-level overview, see the [module documentation][super::simulated_annealing] and for an example, see the
-/// [simulated annealing solver for the TSP][crate::examples::tsp::solvers::simulated_annealing].
+///   objective value (in comparison the the previous objective value) and the time elapsed since the start.
+/// * The solver stops if the [`TerminationCriteria`] (e.g., a number of iterations or a time
+///   limit) are met.
+/// * Without [`TerminationCriteria`], the solver runs until a whole neighborhood is explored
+///   without any acceptance.
+/// * Further options (e.g., a [`CoolingSchedule`], a [`Reheating`], a soft time limit) are set
+///   by the `with_*` methods.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the objective trajectory with the neighbor counts of each step.
+///   For a high-level overview, see the [module documentation][super::simulated_annealing] and for an example, see the
+///   [simulated annealing solver for the TSP][crate::examples::tsp::solvers::simulated_annealing].
 pub struct SimulatedAnnealingSolver<S> {
     neighborhood: Arc<dyn Neighborhood<S>>,
     objective: Arc<Objective<S>>,
     initial_temperature: Temperature,
-    cooling_factor: ScalingFactor,
+    cooling_schedule: Box<dyn CoolingSchedule>,
     acceptance_probability_function: AcceptanceProbabilityFunction,
     function_between_steps: FunctionBetweenSteps<S>,
-    time_limit: Option<stdtime::Duration>,
-    iteration_limit: Option<u32>,
     random_seed: Option<u64>,
+    rng_factory: RngFactory,
+    acceptance_logger: Option<Arc<AcceptanceLogger>>,
+    convergence_detector: Option<ConvergenceDetector>,
+    soft_time_limit: Option<stdtime::Duration>,
+    termination_criteria: Option<TerminationCriteria>,
+    reheating: Option<Reheating>,
+    neighbor_sampling: NeighborSampling,
+    elite_pool: Option<Arc<ElitePool<S>>>,
+    elite_restart_after: Option<u32>,
 }
 
 impl<S> SimulatedAnnealingSolver<S> {
@@ -97,7 +207,33 @@ impl<S> SimulatedAnnealingSolver<S> {
             random_seed,
             None,
             None,
-            None,
+        )
+    }
+
+    /// Creates a new [`SimulatedAnnealingSolver`] with the given [`Neighborhood`], [`Objective`],
+    /// `initial_temperature` and `cooling_factor` that uses the
+    /// [`normalized_acceptance_probability_function`] for the [`Objective`].
+    /// * The temperature is relative to the expected range of the objective values derived from
+    ///   the declared [`bounds`][crate::objective::Indicator::bounds], e.g., with
+    ///   `initial_temperature` 0.05 a neighbor that is worse by 5% of the range is accepted with
+    ///   probability e<sup>-1</sup>, regardless of the magnitude of the objective values.
+    /// * A `random_seed` can be provided to make the search reproducible.
+    pub fn initialize_normalized(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        initial_temperature: Temperature,
+        cooling_factor: ScalingFactor,
+        random_seed: Option<u64>,
+    ) -> Self {
+        let acceptance_probability_function =
+            normalized_acceptance_probability_function(&objective);
+        Self::initialize(
+            neighborhood,
+            objective,
+            initial_temperature,
+            cooling_factor,
+            acceptance_probability_function,
+            random_seed,
         )
     }
 
@@ -105,16 +241,16 @@ impl<S> SimulatedAnnealingSolver<S> {
     /// `initial_temperature`, `cooling_factor`, and [`AcceptanceProbabilityFunction`].
     /// * `random_seed` can be provided to make the search reproducible.
     /// * `function_between_steps` is executed after each improvement step. If `None`, the default
-    /// is printing the iteration number, the objective value (in comparison the the previous
-    /// objective value) and the time elapsed since the start.
-    /// * `time_limit` is the maximum time allowed for the local search to start a new iteration.
-    /// The last iteration is allowed to finish. If `None`, there is no time limit.
-    /// * `iteration_limit` is the maximum number of iterations allowed for the local search. If
-    /// `None`, there is no iteration limit.
-    /// * If `max_iterations` and `max_time` is `None`, the solver runs until a whole neighborhood
-    /// is explored without any accpetance.
-    /// * If `max_iterations` and `max_time` are both set, the search stops when either limit is
-    /// reached first.
+    ///   is printing the iteration number, the objective value (in comparison the the previous
+    ///   objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit or an iteration
+    ///   limit, are checked after each iteration with the best solution seen. The last iteration
+    ///   is allowed to finish.
+    /// * If `termination_criteria` is `None`, the solver runs until a whole neighborhood is
+    ///   explored without any accpetance.
+    /// * The temperature is multiplied by the `cooling_factor` whenever a worse neighbor is
+    ///   accepted (i.e., [`GeometricCooling`]), unless another [`CoolingSchedule`] is set by
+    ///   [`with_cooling_schedule`][SimulatedAnnealingSolver::with_cooling_schedule].
     #[allow(clippy::too_many_arguments)]
     pub fn with_options(
         neighborhood: Arc<dyn Neighborhood<S>>,
@@ -124,103 +260,646 @@ impl<S> SimulatedAnnealingSolver<S> {
         acceptance_probability_function: AcceptanceProbabilityFunction,
         random_seed: Option<u64>,
         function_between_steps: Option<FunctionBetweenSteps<S>>,
-        time_limit: Option<stdtime::Duration>,
-        iteration_limit: Option<u32>,
+        termination_criteria: Option<TerminationCriteria>,
     ) -> Self {
         Self {
             neighborhood,
             objective,
             initial_temperature,
-            cooling_factor,
+            cooling_schedule: Box::new(GeometricCooling::new(cooling_factor)),
             acceptance_probability_function,
             function_between_steps: function_between_steps
                 .unwrap_or(default_function_between_steps()),
-            time_limit,
-            iteration_limit,
             random_seed,
+            rng_factory: default_rng_factory(),
+            acceptance_logger: None,
+            convergence_detector: None,
+            soft_time_limit: None,
+            termination_criteria,
+            reheating: None,
+            neighbor_sampling: NeighborSampling::default(),
+            elite_pool: None,
+            elite_restart_after: None,
         }
     }
+
+    /// Replaces the [`GeometricCooling`] with the `cooling_factor` by another
+    /// [`CoolingSchedule`], which defines how the temperature changes after each iteration (see
+    /// [`cooling_schedule`]). The `cooling_factor` is ignored then.
+    pub fn with_cooling_schedule(mut self, cooling_schedule: Box<dyn CoolingSchedule>) -> Self {
+        self.cooling_schedule = cooling_schedule;
+        self
+    }
+
+    /// Raises the temperature again if there is no new best solution for a number of iterations
+    /// (see [`Reheating`]).
+    pub fn with_reheating(mut self, reheating: Reheating) -> Self {
+        self.reheating = Some(reheating);
+        self
+    }
+
+    /// Defines in which order the neighbors are considered (see [`NeighborSampling`]). By
+    /// default, the neighbors are considered in the order of the [`Neighborhood`] iterator.
+    pub fn with_neighbor_sampling(mut self, neighbor_sampling: NeighborSampling) -> Self {
+        self.neighbor_sampling = neighbor_sampling;
+        self
+    }
+
+    /// Logs every acceptance and rejection decision (rate-limited) for debugging (see
+    /// [`AcceptanceLogger`]).
+    pub fn with_acceptance_logger(mut self, acceptance_logger: Arc<AcceptanceLogger>) -> Self {
+        self.acceptance_logger = Some(acceptance_logger);
+        self
+    }
+
+    /// Stops the search as soon as the coefficient of variation of the best objective value over
+    /// the last iterations is below a threshold (see [`ConvergenceDetector`]).
+    pub fn with_convergence_detector(mut self, convergence_detector: ConvergenceDetector) -> Self {
+        self.convergence_detector = Some(convergence_detector);
+        self
+    }
+
+    /// After `soft_time_limit`, the annealing is stopped and the best solution seen is improved by
+    /// a [`TakeFirst`] descent (i.e., temperature 0) until a local optimum is reached or the
+    /// (hard) time limit of the [`TerminationCriteria`] is exceeded.
+    pub fn with_soft_time_limit(mut self, soft_time_limit: stdtime::Duration) -> Self {
+        self.soft_time_limit = Some(soft_time_limit);
+        self
+    }
+
+    /// Replaces the [`RngFactory`] (default: [`StdRng`][rand::rngs::StdRng], see
+    /// [`default_rng_factory`]), which creates the random number generator of each run from the
+    /// `random_seed`. The generator draws the acceptance decisions and the sampled neighbors.
+    pub fn with_rng_factory(mut self, rng_factory: RngFactory) -> Self {
+        self.rng_factory = rng_factory;
+        self
+    }
+
+    /// Offers each new current solution to the `elite_pool` (see [`ElitePool`]). If
+    /// `restart_after` is set, the search restarts from the elite solution that is most distant
+    /// from the current solution whenever no new best solution is found for `restart_after`
+    /// iterations. The temperature is not changed by a restart.
+    pub fn with_elite_pool(
+        mut self,
+        elite_pool: Arc<ElitePool<S>>,
+        restart_after: Option<u32>,
+    ) -> Self {
+        self.elite_pool = Some(elite_pool);
+        self.elite_restart_after = restart_after;
+        self
+    }
 }
 
-impl<S: Clone> Solver<S> for SimulatedAnnealingSolver<S> {
-    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+impl<S: Clone> SimulatedAnnealingSolver<S> {
+    /// Runs the annealing, where each step is passed to the `function_between_steps` by a
+    /// [`SearchRecorder`], which additionally records the trajectory if `record` is `true`.
+    fn search(&self, initial_solution: S, record: bool) -> SearchResult<S> {
         let start_time = stdtime::Instant::now();
         let mut current_solution = self.objective.evaluate(initial_solution);
         let mut best_solution_seen = current_solution.clone();
         let mut current_temperature = self.initial_temperature;
 
-        let mut rng = match self.random_seed {
-            Some(seed) => StdRng::seed_from_u64(seed),
-            None => StdRng::from_entropy(),
-        };
+        let mut rng = (self.rng_factory)(self.random_seed);
 
-        let mut iteration_counter = 1;
+        let mut convergence_detector = self.convergence_detector.clone();
+        let mut termination_criteria = self.termination_criteria.clone();
+        let time_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_time_limit);
+        let iteration_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_iteration_limit);
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            time_limit,
+            iteration_limit,
+            current_solution.objective_value(),
+            record,
+        );
+        let mut iteration_counter = 0;
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        let mut iterations_without_improvement = 0;
+        let mut iterations_since_restart = 0;
 
-        while let Some(new_solution) =
+        while let Some((new_solution, neighbor_counts)) =
             self.explore_neihborhood(&current_solution, current_temperature, &mut rng)
         {
-            (self.function_between_steps)(
+            iteration_counter += 1;
+            recorder.step(
                 iteration_counter,
                 &new_solution,
                 Some(&current_solution),
-                self.objective.clone(),
-                Some(start_time),
-                self.time_limit,
-                self.iteration_limit,
+                Some(neighbor_counts),
             );
 
-            if new_solution.objective_value() >= current_solution.objective_value() {
-                current_temperature *= self.cooling_factor;
-                println!("New temperature: {:0.2}", current_temperature);
-            }
-
+            current_temperature = self.cool(
+                current_temperature,
+                iteration_counter,
+                &new_solution,
+                &current_solution,
+                &neighbor_counts,
+            );
             current_solution = new_solution;
 
-            if current_solution.objective_value() < best_solution_seen.objective_value() {
+            if self
+                .objective
+                .is_better(&current_solution, &best_solution_seen)
+            {
                 best_solution_seen = current_solution.clone();
+                iterations_without_improvement = 0;
+                iterations_since_restart = 0;
+            } else {
+                iterations_without_improvement += 1;
+                iterations_since_restart += 1;
+            }
+            if let Some(elite_pool) = &self.elite_pool {
+                elite_pool.offer_or_restart(
+                    &mut current_solution,
+                    &mut iterations_since_restart,
+                    self.elite_restart_after,
+                );
             }
 
-            if let Some(time_limit) = self.time_limit {
-                if stdtime::Instant::now().duration_since(start_time) > time_limit {
-                    println!("Time limit reached.");
-                    break;
+            self.reheat(
+                &mut current_temperature,
+                &mut iterations_without_improvement,
+            );
+
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
+            }
+            if let Some(soft_time_limit) = self.soft_time_limit {
+                if stdtime::Instant::now().duration_since(start_time) > soft_time_limit {
+                    let take_first =
+                        TakeFirst::new(self.neighborhood.clone(), self.objective.clone());
+                    let (best_solution, iterations, termination_reason) = wind_down(
+                        &mut recorder,
+                        best_solution_seen,
+                        |current_solution, deadline| {
+                            take_first.improve_counted(current_solution, deadline)
+                        },
+                        iteration_counter,
+                        start_time,
+                        time_limit,
+                    );
+                    return recorder.finish(best_solution, iterations, termination_reason);
                 }
             }
-            if let Some(iteration_limit) = self.iteration_limit {
-                if iteration_counter >= iteration_limit {
-                    println!("Iteration limit reached.");
+            if let Some(convergence_detector) = convergence_detector.as_mut() {
+                if convergence_detector.record(best_solution_seen.objective_value()) {
+                    report!(Info, "Convergence detected.");
+                    termination_reason = TerminationReason::Converged;
                     break;
                 }
             }
-            iteration_counter += 1;
         }
 
-        best_solution_seen
+        recorder.finish(best_solution_seen, iteration_counter, termination_reason)
     }
 }
 
 impl<S> SimulatedAnnealingSolver<S> {
+    /// Returns a [`SimulatedAnnealingSteps`] iterator that advances the annealing from the
+    /// `initial_solution` one iteration at a time.
+    pub fn steps(&self, initial_solution: S) -> SimulatedAnnealingSteps<'_, S>
+    where
+        S: Clone,
+    {
+        let current_solution = self.objective.evaluate(initial_solution);
+        SimulatedAnnealingSteps {
+            solver: self,
+            rng: (self.rng_factory)(self.random_seed),
+            best_solution: current_solution.clone(),
+            current_solution,
+            current_temperature: self.initial_temperature,
+            iteration: 0,
+            iterations_without_improvement: 0,
+        }
+    }
+}
+
+impl<S: Clone> Solver<S> for SimulatedAnnealingSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.search(initial_solution, false).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        self.search(initial_solution, true)
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("SimulatedAnnealingSolver")
+            .with_parameter("initial_temperature", self.initial_temperature)
+            .with_parameter("cooling_schedule", self.cooling_schedule.name())
+            .with_parameter("neighbor_sampling", format!("{:?}", self.neighbor_sampling))
+            .with_optional_parameter(
+                "elite_pool",
+                self.elite_pool
+                    .as_ref()
+                    .map(|elite_pool| elite_pool.capacity()),
+            )
+            .with_optional_parameter("elite_restart_after", self.elite_restart_after)
+            .with_optional_parameter("random_seed", self.random_seed)
+            .with_optional_parameter(
+                "reheating",
+                self.reheating.map(|reheating| format!("{:?}", reheating)),
+            )
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+            .with_parameter("convergence_detector", self.convergence_detector.is_some())
+            .with_duration("soft_time_limit", self.soft_time_limit)
+    }
+}
+
+impl<S> SimulatedAnnealingSolver<S> {
+    /// Returns the temperature after the step from `current_solution` to `new_solution` in
+    /// `iteration` according to the [`CoolingSchedule`].
+    fn cool(
+        &self,
+        current_temperature: Temperature,
+        iteration: u32,
+        new_solution: &EvaluatedSolution<S>,
+        current_solution: &EvaluatedSolution<S>,
+        neighbor_counts: &NeighborCounts,
+    ) -> Temperature {
+        let new_temperature = self.cooling_schedule.next_temperature(
+            current_temperature,
+            &CoolingStep {
+                iteration,
+                initial_temperature: self.initial_temperature,
+                accepted_worse: new_solution.objective_value()
+                    >= current_solution.objective_value(),
+                acceptance_rate: 1.0 / neighbor_counts.evaluated as f64,
+            },
+        );
+        if new_temperature != current_temperature {
+            report!(Info, "New temperature: {:0.2}", new_temperature);
+        }
+        new_temperature
+    }
+
+    /// Raises the `current_temperature` by the [`Reheating`] (if configured and due) and resets
+    /// the `iterations_without_improvement` in this case.
+    fn reheat(
+        &self,
+        current_temperature: &mut Temperature,
+        iterations_without_improvement: &mut u32,
+    ) {
+        if let Some(reheated_temperature) = self.reheating.and_then(|reheating| {
+            reheating.reheat(*iterations_without_improvement, self.initial_temperature)
+        }) {
+            *current_temperature = reheated_temperature;
+            *iterations_without_improvement = 0;
+            report!(
+                Info,
+                "Reheating to temperature: {:0.2}",
+                current_temperature
+            );
+        }
+    }
+
+    /// Returns the first accepted neighbor (according to the [`NeighborSampling`]) together with
+    /// the [`NeighborCounts`] of the exploration. All evaluated neighbors except the accepted one
+    /// are rejected by the acceptance probability.
     fn explore_neihborhood(
         &self,
         current_solution: &EvaluatedSolution<S>,
         current_temperature: Temperature,
-        rng: &mut StdRng,
-    ) -> Option<EvaluatedSolution<S>> {
-        self.neighborhood
-            .neighbors_of(current_solution.solution())
-            .find_map(|neighbor| {
-                let neighbor_solution = self.objective.evaluate(neighbor);
-                let acceptance_probability = (self.acceptance_probability_function)(
-                    current_solution.objective_value(),
-                    neighbor_solution.objective_value(),
-                    current_temperature,
-                );
-                let random_number = rng.gen::<Probability>();
-                if acceptance_probability > random_number {
-                    Some(neighbor_solution)
-                } else {
-                    None
+        rng: &mut dyn RngCore,
+    ) -> Option<(EvaluatedSolution<S>, NeighborCounts)> {
+        let neighbors = || self.neighborhood.neighbors_of(current_solution.solution());
+        let mut generated = 0;
+        let mut evaluated = 0;
+        let accepted_neighbor = match self.neighbor_sampling {
+            NeighborSampling::InOrder => neighbors().find_map(|neighbor| {
+                generated += 1;
+                evaluated += 1;
+                self.accept(current_solution, neighbor, current_temperature, rng)
+            }),
+            NeighborSampling::Shuffled => {
+                let mut neighbors: Vec<S> = neighbors().collect();
+                generated = neighbors.len() as u64;
+                neighbors.shuffle(rng);
+                neighbors.into_iter().find_map(|neighbor| {
+                    evaluated += 1;
+                    self.accept(current_solution, neighbor, current_temperature, rng)
+                })
+            }
+            NeighborSampling::UniformRandom => {
+                let mut neighbors: Vec<S> = neighbors().collect();
+                generated = neighbors.len() as u64;
+                let mut accepted_neighbor = None;
+                while !neighbors.is_empty() {
+                    let index = rng.gen_range(0..neighbors.len());
+                    let neighbor = neighbors.swap_remove(index);
+                    evaluated += 1;
+                    accepted_neighbor =
+                        self.accept(current_solution, neighbor, current_temperature, rng);
+                    if accepted_neighbor.is_some() {
+                        break;
+                    }
                 }
-            })
+                accepted_neighbor
+            }
+            NeighborSampling::Reservoir => {
+                let mut drawn_indices = HashSet::new();
+                let mut accepted_neighbor = None;
+                while let Some((index, neighbor)) =
+                    reservoir_sample(neighbors().inspect(|_| generated += 1), &drawn_indices, rng)
+                {
+                    drawn_indices.insert(index);
+                    evaluated += 1;
+                    accepted_neighbor =
+                        self.accept(current_solution, neighbor, current_temperature, rng);
+                    if accepted_neighbor.is_some() {
+                        break;
+                    }
+                }
+                accepted_neighbor
+            }
+        };
+        accepted_neighbor.map(|neighbor_solution| {
+            let neighbor_counts = NeighborCounts {
+                generated,
+                evaluated,
+                rejected_as_tabu: 0,
+                rejected_by_threshold: evaluated - 1,
+                accepted: 1,
+            };
+            (neighbor_solution, neighbor_counts)
+        })
+    }
+
+    /// Evaluates the `neighbor` and returns it if it is accepted.
+    fn accept(
+        &self,
+        current_solution: &EvaluatedSolution<S>,
+        neighbor: S,
+        current_temperature: Temperature,
+        rng: &mut dyn RngCore,
+    ) -> Option<EvaluatedSolution<S>> {
+        let neighbor_solution = self.objective.evaluate(neighbor);
+        let acceptance_probability = (self.acceptance_probability_function)(
+            current_solution.objective_value(),
+            neighbor_solution.objective_value(),
+            current_temperature,
+        );
+        let random_number = rng.gen::<Probability>();
+        let accepted = acceptance_probability > random_number;
+        if let Some(acceptance_logger) = &self.acceptance_logger {
+            acceptance_logger.log(&AcceptanceDecision {
+                accepted,
+                current_objective_value: current_solution.objective_value(),
+                neighbor_objective_value: neighbor_solution.objective_value(),
+                criterion: AcceptanceCriterion::Temperature {
+                    temperature: current_temperature,
+                    acceptance_probability,
+                    random_number,
+                },
+            });
+        }
+        if accepted {
+            Some(neighbor_solution)
+        } else {
+            None
+        }
+    }
+}
+
+/// Advances the annealing of a [`SimulatedAnnealingSolver`] one iteration at a time (see
+/// [`SimulatedAnnealingSolver::steps`]).
+/// * Each call of [`step`][SimulatedAnnealingSteps::step] (or [`next`][Iterator::next]) replaces
+///   the current solution by the first accepted neighbor (according to the [`NeighborSampling`]),
+///   updates the temperature by the [`CoolingSchedule`] (and the [`Reheating`]) and the best
+///   solution seen. It returns `None` if no neighbor is accepted. The search can be resumed after
+///   the current solution was replaced.
+/// * Between the iterations, the current solution can be inspected by
+///   [`current_solution`][SimulatedAnnealingSteps::current_solution] and replaced by
+///   [`set_current_solution`][SimulatedAnnealingSteps::set_current_solution].
+/// * The caller controls the loop, so the `function_between_steps`, the limits, the convergence
+///   detector and the elite pool of the solver are not applied.
+pub struct SimulatedAnnealingSteps<'a, S> {
+    solver: &'a SimulatedAnnealingSolver<S>,
+    rng: Box<dyn RngCore + Send>,
+    current_solution: EvaluatedSolution<S>,
+    best_solution: EvaluatedSolution<S>,
+    current_temperature: Temperature,
+    iteration: u32,
+    iterations_without_improvement: u32,
+}
+
+impl<S: Clone> SimulatedAnnealingSteps<'_, S> {
+    /// Performs one iteration and returns the new current solution, or `None` if no neighbor is
+    /// accepted.
+    pub fn step(&mut self) -> Option<&EvaluatedSolution<S>> {
+        let (new_solution, neighbor_counts) = self.solver.explore_neihborhood(
+            &self.current_solution,
+            self.current_temperature,
+            &mut self.rng,
+        )?;
+        self.iteration += 1;
+        self.current_temperature = self.solver.cool(
+            self.current_temperature,
+            self.iteration,
+            &new_solution,
+            &self.current_solution,
+            &neighbor_counts,
+        );
+        self.current_solution = new_solution;
+        if self
+            .solver
+            .objective
+            .is_better(&self.current_solution, &self.best_solution)
+        {
+            self.best_solution = self.current_solution.clone();
+            self.iterations_without_improvement = 0;
+        } else {
+            self.iterations_without_improvement += 1;
+        }
+        self.solver.reheat(
+            &mut self.current_temperature,
+            &mut self.iterations_without_improvement,
+        );
+        Some(&self.current_solution)
+    }
+
+    /// Returns the current solution.
+    pub fn current_solution(&self) -> &EvaluatedSolution<S> {
+        &self.current_solution
+    }
+
+    /// Returns the best solution seen so far.
+    pub fn best_solution(&self) -> &EvaluatedSolution<S> {
+        &self.best_solution
+    }
+
+    /// Returns the current temperature.
+    pub fn temperature(&self) -> Temperature {
+        self.current_temperature
+    }
+
+    /// Replaces the current solution (e.g., after a manual change), from which the next
+    /// iteration continues. The best solution is updated if the new current solution is better.
+    pub fn set_current_solution(&mut self, solution: S) {
+        self.current_solution = self.solver.objective.evaluate(solution);
+        if self
+            .solver
+            .objective
+            .is_better(&self.current_solution, &self.best_solution)
+        {
+            self.best_solution = self.current_solution.clone();
+        }
+    }
+
+    /// Returns the number of iterations performed so far.
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    /// Consumes the steps and returns the best solution seen.
+    pub fn into_best_solution(self) -> EvaluatedSolution<S> {
+        self.best_solution
+    }
+}
+
+impl<S: Clone> Iterator for SimulatedAnnealingSteps<'_, S> {
+    type Item = EvaluatedSolution<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step().cloned()
+    }
+}
+
+/// Picks a uniformly random neighbor (together with its index) whose index is not in
+/// `drawn_indices` in a single pass without collecting the neighbors.
+fn reservoir_sample<S>(
+    neighbors: impl Iterator<Item = S>,
+    drawn_indices: &HashSet<usize>,
+    rng: &mut dyn RngCore,
+) -> Option<(usize, S)> {
+    let mut chosen = None;
+    let mut number_of_candidates = 0;
+    for (index, neighbor) in neighbors.enumerate() {
+        if drawn_indices.contains(&index) {
+            continue;
+        }
+        number_of_candidates += 1;
+        if rng.gen_range(0..number_of_candidates) == 0 {
+            chosen = Some((index, neighbor));
+        }
+    }
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::silent_function_between_steps;
+    use crate::objective::{BaseValue, BoundedIndicator};
+    use crate::test_fixtures::{DistanceTo, PlusMinusOne};
+
+    #[test]
+    fn test_neighbor_sampling() {
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            DistanceTo(100),
+        )]));
+        let target_objective_value = objective.evaluate(100).objective_value().clone();
+        for neighbor_sampling in [
+            NeighborSampling::InOrder,
+            NeighborSampling::Shuffled,
+            NeighborSampling::UniformRandom,
+            NeighborSampling::Reservoir,
+        ] {
+            let solver = SimulatedAnnealingSolver::with_options(
+                Arc::new(PlusMinusOne),
+                objective.clone(),
+                1.0,
+                0.9,
+                Box::new(|current, neighbor, temperature| {
+                    let difference = neighbor.iter().next().unwrap().unwrap_integer()
+                        - current.iter().next().unwrap().unwrap_integer();
+                    (-(difference as f64) / temperature).exp()
+                }),
+                Some(7),
+                Some(silent_function_between_steps()),
+                Some(TerminationCriteria::iteration_limit(10_000).or(
+                    TerminationCriteria::target_objective_value(target_objective_value.clone()),
+                )),
+            )
+            .with_neighbor_sampling(neighbor_sampling);
+            assert_eq!(*solver.solve(0).solution(), 100, "{:?}", neighbor_sampling);
+        }
+    }
+
+    #[test]
+    fn test_normalized_acceptance_probability_function() {
+        // the same relative deterioration is accepted with the same probability, regardless of
+        // the magnitude of the declared range
+        let probability_for_range = |max: i64, difference: i64| {
+            let objective: Objective<i64> =
+                Objective::new_single_indicator(Box::new(BoundedIndicator::new(
+                    Box::new(DistanceTo(100)),
+                    BaseValue::Integer(0),
+                    BaseValue::Integer(max),
+                )));
+            let acceptance_probability_function =
+                normalized_acceptance_probability_function(&objective);
+            acceptance_probability_function(
+                &ObjectiveValue::new(vec![BaseValue::Integer(0)]),
+                &ObjectiveValue::new(vec![BaseValue::Integer(difference)]),
+                0.1,
+            )
+        };
+        assert!((probability_for_range(100, 10) - (-1.0f64).exp()).abs() < 1e-9);
+        assert!((probability_for_range(10_000, 1_000) - (-1.0f64).exp()).abs() < 1e-9);
+        assert_eq!(probability_for_range(100, -10), 1.0);
+    }
+
+    #[test]
+    fn test_steps() {
+        let solver = SimulatedAnnealingSolver::with_options(
+            Arc::new(PlusMinusOne),
+            Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+                DistanceTo(100),
+            )])),
+            1.0,
+            0.9,
+            Box::new(|current, neighbor, temperature| {
+                let difference = neighbor.iter().next().unwrap().unwrap_integer()
+                    - current.iter().next().unwrap().unwrap_integer();
+                (-(difference as f64) / temperature).exp()
+            }),
+            Some(7),
+            Some(silent_function_between_steps()),
+            Some(TerminationCriteria::iteration_limit(50)),
+        );
+
+        // with the same seed, the steps perform the same iterations as a run
+        let mut steps = solver.steps(0);
+        assert_eq!(steps.by_ref().take(50).count(), 50);
+        assert_eq!(steps.iteration(), 50);
+        assert_eq!(steps.best_solution().solution(), solver.solve(0).solution());
+        assert!(steps.temperature() < 1.0);
+
+        // the search continues from a replaced current solution
+        steps.set_current_solution(100);
+        assert_eq!(*steps.best_solution().solution(), 100);
+        steps.step().unwrap();
+        assert_eq!(steps.iteration(), 51);
+        assert_eq!(*steps.into_best_solution().solution(), 100);
     }
 }
@@ -3,25 +3,60 @@
 //! There are several [`tabu_improvers`][`tabu_improver`] (neighborhood exploration stategies)
 //! to choose from.
 //! * This solver requires a [`TabuNeighborhood`], which, in comparison to a regular
-//! [`Neighborhood`][crate::heuristics::common::Neighborhood],
-//! requires a tabu list as an additional argument and returns in addition to the neighbors a list
-//! of tabus that should be added to the tabu list.
+//!   [`Neighborhood`][crate::heuristics::common::Neighborhood],
+//!   requires a tabu list as an additional argument and returns in addition to the neighbors a list
+//!   of tabus that should be added to the tabu list.
 //! * Starts with an initial solution and iteratively explores the neighborhood of the current
-//! solution, while ignoring tabu solutions.
+//!   solution, while ignoring tabu solutions.
 //! * The best non-tabu neighbor, even if it is worse than the current solution, is chosen.
 //! * Each neighbor is paired with a list of tabus that should be added to the tabu list.
 //! * A good tabu should forbid to return to the previous solution.
 //! * The list of tabus is limited in size, and the oldest tabus are removed when the list is full.
-//! * The search stops after a certain number of iterations, after a certain time limit, or if no
-//! global improvement is found after a certain number of iterations.
+//! * With a [`TabuPolicy`], tabus of different classes
+//!   (e.g., arc-based and node-based tabus) can have different tenures.
+//! * With a [`ReactiveTenure`], the tabu list size is increased whenever the search cycles and
+//!   decreased again otherwise.
+//! * For permutation solutions (e.g., tours), the [`permutation_tabu`] module provides reusable
+//!   tabus that forbid to recreate removed arcs or to move elements back to their old positions.
+//! * An [`AspirationCriterion`] accepts tabu neighbors anyway. By default, a tabu neighbor that
+//!   is better than the best solution seen is accepted ([`NewGlobalBestAspiration`]). The tabu
+//!   neighbors are checked in the same exploration as the non-tabu neighbors, if the neighborhood
+//!   reports them (see [`TabuNeighborhood::neighbors_with_tabu_status_of`]).
+//! * The [`FrequencyPenalizedTabuMinimizer`][tabu_improver::FrequencyPenalizedTabuMinimizer]
+//!   penalizes frequently applied moves (long-term memory) to diversify the search.
+//! * The [`PhasedTabuMinimizer`] cycles the emphasis of the
+//!   objective levels on a fixed schedule of intensification and diversification phases (see
+//!   [`with_objective_phases`][TabuSearchSolver::with_objective_phases]).
+//! * The [`DeltaTabuMinimizer`][tabu_improver::DeltaTabuMinimizer] evaluates the moves of a
+//!   [`TabuMoveNeighborhood`] incrementally by a [`DeltaObjective`][crate::objective::DeltaObjective].
+//! * The search stops if the [`TerminationCriteria`] are met, e.g., after a certain number of
+//!   iterations, after a certain time limit, or if no global improvement is found after a certain
+//!   number of iterations.
 //! * The best solution  seen is returned.
+//! * [`TabuSearchSolver::preset_default_for`] chooses the tabu list size and the limits based on
+//!   the size of the neighborhood, which is a good starting point for first-time users.
+//! * To advance the search one iteration at a time (e.g., in an interactive planning UI),
+//!   [`TabuSearchSolver::steps`] returns a [`TabuSearchSteps`] iterator, whose current solution
+//!   can be inspected and replaced between the iterations.
 //!
 //! For examples, see the [tabu search solver][crate::examples::tsp::solvers::tabu_search] for the TSP.
+pub mod permutation_tabu;
 pub mod tabu_improver;
 
-use self::tabu_improver::{TabuImprover, TabuMinimizer};
+use self::tabu_improver::{
+    ObjectivePhase, PhaseListener, PhasedTabuMinimizer, TabuImprover, TabuMinimizer,
+};
 
-use super::common::{default_function_between_steps, FunctionBetweenSteps};
+use super::common::SolverDescription;
+use super::common::{
+    counts_with_aspiration, default_function_between_steps, log_aspiration_decisions,
+    prefer_aspirating_neighbor, report_termination, wind_down, AcceptanceLogger, Aspiration,
+    AspirationCriterion, ElitePool, FunctionBetweenSteps, NeighborCounts, NewGlobalBestAspiration,
+    ReactiveTenure, ReactiveTenureState, SearchRecorder, SearchResult, TabuPolicy,
+    TerminationCriteria, TerminationReason,
+};
+#[cfg(feature = "checkpoint")]
+use super::common::{CheckpointWriter, TabuSearchCheckpoint};
 use super::Solver;
 use crate::objective::{EvaluatedSolution, Objective};
 use std::collections::VecDeque;
@@ -39,6 +74,103 @@ pub trait TabuNeighborhood<S, T>: Send + Sync {
         solution: &'a S,
         tabu_list: &'a VecDeque<T>,
     ) -> Box<dyn Iterator<Item = (S, Vec<T>)> + Send + Sync + 'a>;
+
+    /// Same as [`neighbors_of`][TabuNeighborhood::neighbors_of], but the tabu neighbors are not
+    /// skipped: Each neighbor is additionally flagged `true` if it is tabu. This allows the
+    /// [`TabuImprovers`][TabuImprover] to check the [`AspirationCriterion`] for the tabu
+    /// neighbors in the same exploration.
+    /// The default implementation returns the neighbors of
+    /// [`neighbors_of`][TabuNeighborhood::neighbors_of] (all flagged `false`), i.e., tabu
+    /// neighbors are never accepted by an aspiration criterion.
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        solution: &'a S,
+        tabu_list: &'a VecDeque<T>,
+    ) -> Box<dyn Iterator<Item = (S, Vec<T>, bool)> + Send + Sync + 'a> {
+        Box::new(
+            self.neighbors_of(solution, tabu_list)
+                .map(|(neighbor, new_tabus)| (neighbor, new_tabus, false)),
+        )
+    }
+}
+
+/// Defines a move-based neighborhood for a tabu search, which allows to identify neighbors by
+/// their move `M` (e.g., the indices of a 3-opt move). This is used by the
+/// [`MemoizedTabuMinimizer`][tabu_improver::MemoizedTabuMinimizer] to reuse evaluations between
+/// consecutive iterations.
+pub trait TabuMoveNeighborhood<S, T, M>: Send + Sync {
+    /// For a given solution and a provided tabu list, it returns an iterator over all non-tabu
+    /// moves.
+    fn moves_of<'a>(
+        &'a self,
+        solution: &'a S,
+        tabu_list: &'a VecDeque<T>,
+    ) -> Box<dyn Iterator<Item = M> + Send + Sync + 'a>;
+
+    /// Same as [`moves_of`][TabuMoveNeighborhood::moves_of], but the tabu moves are not skipped:
+    /// Each move is additionally flagged `true` if it is tabu (see
+    /// [`TabuNeighborhood::neighbors_with_tabu_status_of`]).
+    /// The default implementation returns the moves of
+    /// [`moves_of`][TabuMoveNeighborhood::moves_of] (all flagged `false`).
+    fn moves_with_tabu_status_of<'a>(
+        &'a self,
+        solution: &'a S,
+        tabu_list: &'a VecDeque<T>,
+    ) -> Box<dyn Iterator<Item = (M, bool)> + Send + Sync + 'a>
+    where
+        M: 'a,
+    {
+        Box::new(self.moves_of(solution, tabu_list).map(|mv| (mv, false)))
+    }
+
+    /// Applies the move to the solution and returns the neighbor together with a list of tabus
+    /// that should be added to the tabu list.
+    fn apply(&self, solution: &S, mv: &M) -> (S, Vec<T>);
+
+    /// Returns `true` if the change of the objective value caused by `mv` is not affected by
+    /// applying `applied_move` before, i.e., `mv` only touches parts of the solution that are
+    /// unchanged by `applied_move`.
+    fn is_independent(&self, applied_move: &M, mv: &M) -> bool;
+}
+
+/// The mutable state of a [`TabuSearchSolver`] at the end of a run (see
+/// [`solve_with_state`][TabuSearchSolver::solve_with_state]), which can seed a subsequent run
+/// (see [`continue_from_state`][TabuSearchSolver::continue_from_state]).
+/// * Besides the current solution, the best solution seen and the tabu list, the state contains
+///   the adapted tenure of a [`ReactiveTenure`] (if configured).
+/// * The iteration counters are not part of the state, i.e., each run has its own termination
+///   criterion.
+/// * Between two runs, the current solution can be replaced (e.g., after a manual change in an
+///   interactive planning session) by
+///   [`with_current_solution`][TabuSearchState::with_current_solution].
+#[derive(Clone)]
+pub struct TabuSearchState<S, T> {
+    /// The current solution at the end of the run.
+    pub current_solution: EvaluatedSolution<S>,
+    /// The best solution seen so far (in this and all previous runs on this state).
+    pub best_solution: EvaluatedSolution<S>,
+    /// The tabu list at the end of the run (the oldest tabu first).
+    pub tabu_list: VecDeque<T>,
+    reactive_tenure_state: Option<ReactiveTenureState>,
+}
+
+impl<S, T> TabuSearchState<S, T> {
+    /// Replaces the current solution, from which the next run continues. The best solution
+    /// is updated if the new current solution is better w.r.t. the `objective`.
+    pub fn with_current_solution(
+        mut self,
+        current_solution: EvaluatedSolution<S>,
+        objective: &Objective<S>,
+    ) -> Self
+    where
+        S: Clone,
+    {
+        if objective.is_better(&current_solution, &self.best_solution) {
+            self.best_solution = current_solution.clone();
+        }
+        self.current_solution = current_solution;
+        self
+    }
 }
 
 /// A tabu search solver that uses a [`TabuNeighborhood`], an [`Objective`], a tabu list size, as
@@ -47,24 +179,61 @@ pub trait TabuNeighborhood<S, T>: Send + Sync {
 /// * The `function_between_steps` is executed after each improvement step.
 /// * The deafult [`TabuImprover`] (if `None`) is [`TabuMinimizer`].
 /// * The default `function_between_steps` (if `None`) is printing the iteration number, the
-/// objective value (in comparison the the previous objective value) and the time elapsed since the
-/// start.
-/// * The termination criterion can be either the maximal number of iterations without global
-/// improvement, a time limit, or a maximal number of iterations. (One of them must be set.)
+///   objective value (in comparison the the previous objective value) and the time elapsed since the
+///   start.
+/// * The search stops if the [`TerminationCriteria`] (e.g., a time limit, a maximal number of
+///   iterations or a maximal number of iterations without global improvement) are met. (They
+///   must be set.)
+/// * Further options (e.g., a [`TabuPolicy`], a [`ReactiveTenure`], an [`AspirationCriterion`],
+///   an [`AcceptanceLogger`]) are set by the `with_*` methods.
+/// * With the `checkpoint` feature, the state of the search can be written periodically (see
+///   `with_checkpoints`) and a crashed run can be continued by `resume_from_checkpoint`.
+/// * With an [`ElitePool`] (see [`with_elite_pool`][TabuSearchSolver::with_elite_pool]), the
+///   search restarts from an elite solution if it stagnates.
+/// * [`solve_with_state`][TabuSearchSolver::solve_with_state] returns the [`TabuSearchState`]
+///   at the end of the run, which can seed a subsequent run by
+///   [`continue_from_state`][TabuSearchSolver::continue_from_state] (e.g., for segmented
+///   optimization sessions) without forgetting the recent tabus.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the objective trajectory with the neighbor counts of each step (if
+///   the [`TabuImprover`] counts them).
 ///
 /// For a high-level overview, see the [module documentation][super::tabu_search] and for examples,
 /// see the [tabu search solver][crate::examples::tsp::solvers::tabu_search] for the
 /// TSP.
 pub struct TabuSearchSolver<S, T> {
+    neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
     objective: Arc<Objective<S>>,
-    tabu_list_size: usize,
+    tabu_policy: TabuPolicy<T>,
     local_improver: Box<dyn TabuImprover<S, T>>,
     function_between_steps: FunctionBetweenSteps<S>,
-    iteration_without_global_improvement_limit: Option<u32>,
-    time_limit: Option<stdtime::Duration>,
-    iteration_limit: Option<u32>,
+    soft_time_limit: Option<stdtime::Duration>,
+    termination_criteria: Option<TerminationCriteria>,
+    reactive_tenure: Option<ReactiveTenure<S>>,
+    aspiration_criterion: Box<dyn AspirationCriterion<S, T>>,
+    acceptance_logger: Option<Arc<AcceptanceLogger>>,
+    elite_pool: Option<Arc<ElitePool<S>>>,
+    elite_restart_after: Option<u32>,
+    #[cfg(feature = "checkpoint")]
+    checkpoint_function: Option<CheckpointFunction<S, T>>,
 }
 
+/// The state of the search after an iteration, which is passed to the [`CheckpointFunction`].
+#[cfg(feature = "checkpoint")]
+struct CheckpointState<'a, S, T> {
+    current_solution: &'a EvaluatedSolution<S>,
+    best_solution: &'a EvaluatedSolution<S>,
+    tabu_list: &'a VecDeque<T>,
+    reactive_tenure_state: Option<&'a ReactiveTenureState>,
+    termination_criteria: Option<&'a TerminationCriteria>,
+    iteration: u32,
+    elapsed: stdtime::Duration,
+}
+
+/// Type for a function that writes a checkpoint (if due) given the [`CheckpointState`].
+#[cfg(feature = "checkpoint")]
+type CheckpointFunction<S, T> = Box<dyn Fn(&CheckpointState<'_, S, T>) + Send + Sync>;
+
 impl<S: 'static, T: 'static> TabuSearchSolver<S, T> {
     /// Creates a new [`TabuSearchSolver`] with the given [`TabuNeighborhood`], [`Objective`], tabu
     /// list size, and as a termination criterion the maximal number of iterations without global
@@ -81,122 +250,828 @@ impl<S: 'static, T: 'static> TabuSearchSolver<S, T> {
             tabu_list_size,
             None,
             None,
-            Some(iteration_without_global_improvement_limit),
-            None,
-            None,
+            Some(TerminationCriteria::iterations_without_improvement(
+                iteration_without_global_improvement_limit,
+            )),
         )
     }
 
     /// Creates a new [`TabuSearchSolver`] with the given [`TabuNeighborhood`], [`Objective`], tabu
     /// list size.
     /// * `local_improver` (implementing [`TabuImprover`]) specifies the how the neighborhood is
-    /// explored. If `None`, the default is [`TabuMinimizer`].
+    ///   explored. If `None`, the default is [`TabuMinimizer`].
     /// * `function_between_steps` is executed after each improvement step. If `None`, the default
-    /// is printing the iteration number, the objective value (in comparison the the previous
-    /// objective value) and the time elapsed since the start.
-    /// * `iteration_without_global_improvement_limit` is the maximum number of iterations allowed
-    /// without global improvement. If `None`, there is no limit.
-    /// * `time_limit` is the maximum time allowed for the local search to start a new iteration.
-    /// The last iteration is allowed to finish. If `None`, there is no time limit.
-    /// * `iteration_limit` is the maximum number of iterations allowed for the local search. If
-    /// `None`, there is no iteration limit.
-    /// * At least one of `iteration_without_global_improvement_limit`, `time_limit` or
-    /// `iteration_limit` must be set.
-    /// * If multiple termination criteria are set, the search stops when any of them is reached.
-    #[allow(clippy::too_many_arguments)]
+    ///   is printing the iteration number, the objective value (in comparison the the previous
+    ///   objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit, an iteration
+    ///   limit or a limit on the iterations without global improvement, are checked after each
+    ///   iteration with the best solution seen. The last iteration is allowed to finish.
+    /// * The `termination_criteria` must be set.
     pub fn with_options(
         neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
         objective: Arc<Objective<S>>,
         tabu_list_size: usize,
         local_improver: Option<Box<dyn TabuImprover<S, T>>>,
         function_between_steps: Option<FunctionBetweenSteps<S>>,
-        iteration_without_global_improvement_limit: Option<u32>,
-        time_limit: Option<stdtime::Duration>,
-        iteration_limit: Option<u32>,
+        termination_criteria: Option<TerminationCriteria>,
     ) -> Self {
-        if iteration_without_global_improvement_limit.is_none()
-            && time_limit.is_none()
-            && iteration_limit.is_none()
-        {
-            panic!("At least one of `iteration_without_global_improvement_limit`, `time_limit` or `iteration_limit` must be set.");
+        if termination_criteria.is_none() {
+            panic!("The `termination_criteria` must be set.");
         }
 
         let local_improver = match local_improver {
             Some(local_improver) => local_improver,
-            None => Box::new(TabuMinimizer::new(neighborhood, objective.clone()))
+            None => Box::new(TabuMinimizer::new(neighborhood.clone(), objective.clone()))
                 as Box<dyn TabuImprover<S, T>>,
         };
         Self {
+            neighborhood,
             objective,
-            tabu_list_size,
+            tabu_policy: TabuPolicy::uniform(tabu_list_size),
             local_improver,
             function_between_steps: function_between_steps
                 .unwrap_or(default_function_between_steps()),
-            iteration_without_global_improvement_limit,
-            time_limit,
-            iteration_limit,
+            soft_time_limit: None,
+            termination_criteria,
+            reactive_tenure: None,
+            aspiration_criterion: Box::new(NewGlobalBestAspiration),
+            acceptance_logger: None,
+            elite_pool: None,
+            elite_restart_after: None,
+            #[cfg(feature = "checkpoint")]
+            checkpoint_function: None,
         }
     }
+
+    /// Defines separate tenures for different classes of tabus (see [`TabuPolicy`]). Classes
+    /// without explicit tenure use the `tabu_list_size`. By default, all tabus share the tabu list
+    /// of size `tabu_list_size`.
+    pub fn with_tabu_policy(mut self, mut tabu_policy: TabuPolicy<T>) -> Self {
+        tabu_policy.set_default_tenure(self.tabu_policy.default_tenure());
+        self.tabu_policy = tabu_policy;
+        self
+    }
+
+    /// Adapts the tabu list size (starting with `tabu_list_size`) whenever the search cycles (see
+    /// [`ReactiveTenure`]). By default, the tabu list size is static.
+    pub fn with_reactive_tenure(mut self, reactive_tenure: ReactiveTenure<S>) -> Self {
+        self.reactive_tenure = Some(reactive_tenure);
+        self
+    }
+
+    /// Replaces the [`AspirationCriterion`], which decides whether a tabu neighbor is accepted
+    /// anyway. The default is [`NewGlobalBestAspiration`], i.e., a tabu neighbor is accepted if it
+    /// is better than the best solution seen. Use [`NoAspiration`][super::common::NoAspiration]
+    /// for a strict tabu list.
+    pub fn with_aspiration_criterion(
+        mut self,
+        aspiration_criterion: Box<dyn AspirationCriterion<S, T>>,
+    ) -> Self {
+        self.aspiration_criterion = aspiration_criterion;
+        self
+    }
+
+    /// Logs the acceptance and rejection decisions (rate-limited) for debugging (see
+    /// [`AcceptanceLogger`]): In each iteration, the best non-tabu neighbor and the best tabu
+    /// neighbor that satisfies the [`AspirationCriterion`] (if any) are logged with their tabu
+    /// and aspiration status.
+    pub fn with_acceptance_logger(mut self, acceptance_logger: Arc<AcceptanceLogger>) -> Self {
+        self.acceptance_logger = Some(acceptance_logger);
+        self
+    }
+
+    /// After `soft_time_limit`, the tabu search is stopped and the best solution seen is improved
+    /// by a take-first descent that ignores all tabus until a local optimum is reached or the
+    /// (hard) time limit of the [`TerminationCriteria`] is exceeded.
+    pub fn with_soft_time_limit(mut self, soft_time_limit: stdtime::Duration) -> Self {
+        self.soft_time_limit = Some(soft_time_limit);
+        self
+    }
+
+    /// Creates a [`TabuSearchSolver`] with defaults derived from the (approximate) number of
+    /// neighbors of a solution, `neighborhood_size_hint`:
+    /// * The tabu list size is the square root of the neighborhood size (at least 7 and at most
+    ///   50).
+    /// * The search stops after 10 times the tabu list size iterations without global
+    ///   improvement, or after 60 seconds (300 seconds for neighborhoods with more than 10,000
+    ///   neighbors).
+    /// * The default [`TabuMinimizer`] and [`NewGlobalBestAspiration`] are used.
+    ///
+    /// For other defaults, see [`with_options`][TabuSearchSolver::with_options].
+    pub fn preset_default_for(
+        neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
+        objective: Arc<Objective<S>>,
+        neighborhood_size_hint: usize,
+    ) -> Self {
+        let tabu_list_size = ((neighborhood_size_hint as f64).sqrt().round() as usize).clamp(7, 50);
+        let time_limit = if neighborhood_size_hint > 10_000 {
+            stdtime::Duration::from_secs(300)
+        } else {
+            stdtime::Duration::from_secs(60)
+        };
+        Self::with_options(
+            neighborhood,
+            objective,
+            tabu_list_size,
+            None,
+            None,
+            Some(
+                TerminationCriteria::iterations_without_improvement(10 * tabu_list_size as u32)
+                    .or(TerminationCriteria::time_limit(time_limit)),
+            ),
+        )
+    }
+
+    /// Offers each new current solution to the `elite_pool` (see [`ElitePool`]). If
+    /// `restart_after` is set, the search restarts from the elite solution that is most distant
+    /// from the current solution (with an empty tabu list) whenever there is no global
+    /// improvement for `restart_after` iterations.
+    /// * The [`iterations_without_improvement`][TerminationCriteria::iterations_without_improvement]
+    ///   of the [`TerminationCriteria`] count the iterations without global improvement regardless
+    ///   of the restarts.
+    pub fn with_elite_pool(
+        mut self,
+        elite_pool: Arc<ElitePool<S>>,
+        restart_after: Option<u32>,
+    ) -> Self {
+        self.elite_pool = Some(elite_pool);
+        self.elite_restart_after = restart_after;
+        self
+    }
+
+    /// Replaces the [`TabuImprover`] by a [`PhasedTabuMinimizer`], which cycles through the
+    /// given [`ObjectivePhases`][ObjectivePhase] (e.g., intensification and diversification
+    /// phases with different weights of the hierarchy levels).
+    /// * `phase_listener` is called whenever a phase starts (see
+    ///   [`PhaseChange`][tabu_improver::PhaseChange]). If `None`, the phase changes are only
+    ///   reported as info.
+    /// * The best solution seen is still determined by the hierarchical [`Objective`].
+    pub fn with_objective_phases(
+        mut self,
+        phases: Vec<ObjectivePhase>,
+        phase_listener: Option<PhaseListener>,
+    ) -> Self {
+        self.local_improver = Box::new(PhasedTabuMinimizer::new(
+            self.neighborhood.clone(),
+            self.objective.clone(),
+            phases,
+            phase_listener,
+        ));
+        self
+    }
+}
+
+#[cfg(feature = "checkpoint")]
+impl<S, T> TabuSearchSolver<S, T>
+where
+    S: Clone + serde::Serialize + 'static,
+    T: Clone + serde::Serialize + 'static,
+{
+    /// Writes a [`TabuSearchCheckpoint`] after each iteration in which the interval of the
+    /// `checkpoint_writer` has passed. Requires the `checkpoint` feature.
+    pub fn with_checkpoints(mut self, checkpoint_writer: CheckpointWriter) -> Self {
+        let objective = self.objective.clone();
+        self.checkpoint_function = Some(Box::new(move |state| {
+            if !checkpoint_writer.is_due() {
+                return;
+            }
+            let checkpoint = TabuSearchCheckpoint {
+                current_solution: state.current_solution.solution().clone(),
+                best_solution: state.best_solution.solution().clone(),
+                tabu_list: state.tabu_list.clone(),
+                iteration: state.iteration,
+                elapsed_seconds: state.elapsed.as_secs_f64(),
+                reactive_tenure_state: state.reactive_tenure_state.cloned(),
+                termination_progress: state
+                    .termination_criteria
+                    .map(|criteria| criteria.progress(&objective)),
+            };
+            if let Err(error) = checkpoint_writer.write(&checkpoint) {
+                report!(Warning, "could not write checkpoint: {}", error);
+            }
+        }));
+        self
+    }
 }
 
 impl<S: Clone, T: std::fmt::Debug> Solver<S> for TabuSearchSolver<S, T> {
     fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
-        let start_time = stdtime::Instant::now();
-
-        let mut current_solution = self.objective.evaluate(initial_solution);
-        let mut best_solution_seen = current_solution.clone();
-        let mut tabu_list = VecDeque::with_capacity(self.tabu_list_size);
-        let mut iteration_counter = 1;
-        let mut iteration_without_global_improvement = 0;
-        while let Some((new_solution, new_tabus)) =
-            self.local_improver.improve(&current_solution, &tabu_list)
+        self.solve_with_state(initial_solution).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        let current_solution = self.objective.evaluate(initial_solution);
+        self.search(
+            current_solution.clone(),
+            current_solution,
+            VecDeque::new(),
+            None,
+            self.termination_criteria.clone(),
+            1,
+            stdtime::Instant::now(),
+            true,
+        )
+        .1
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("TabuSearchSolver")
+            .with_parameter("tabu_list_size", self.tabu_policy.default_tenure())
+            .with_parameter("local_improver", self.local_improver.name())
+            .with_parameter("reactive_tenure", self.reactive_tenure.is_some())
+            .with_optional_parameter(
+                "elite_pool",
+                self.elite_pool
+                    .as_ref()
+                    .map(|elite_pool| elite_pool.capacity()),
+            )
+            .with_optional_parameter("elite_restart_after", self.elite_restart_after)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
+            .with_duration("soft_time_limit", self.soft_time_limit)
+    }
+}
+
+impl<S: Clone, T: std::fmt::Debug> TabuSearchSolver<S, T> {
+    /// Solves the problem like [`solve`][Solver::solve], but returns the [`TabuSearchState`] at
+    /// the end of the run (including the best solution seen), which can seed a subsequent run by
+    /// [`continue_from_state`][TabuSearchSolver::continue_from_state].
+    pub fn solve_with_state(&self, initial_solution: S) -> TabuSearchState<S, T> {
+        let current_solution = self.objective.evaluate(initial_solution);
+        self.search(
+            current_solution.clone(),
+            current_solution,
+            VecDeque::new(),
+            None,
+            self.termination_criteria.clone(),
+            1,
+            stdtime::Instant::now(),
+            false,
+        )
+        .0
+    }
+
+    /// Continues the search from the `state` of a previous run (see
+    /// [`solve_with_state`][TabuSearchSolver::solve_with_state]) with its tabu list, best
+    /// solution seen and reactive tenure. The iteration counters and the time limits start anew.
+    pub fn continue_from_state(&self, state: TabuSearchState<S, T>) -> TabuSearchState<S, T> {
+        self.search(
+            state.current_solution,
+            state.best_solution,
+            state.tabu_list,
+            state.reactive_tenure_state,
+            self.termination_criteria.clone(),
+            1,
+            stdtime::Instant::now(),
+            false,
+        )
+        .0
+    }
+
+    /// Returns a [`TabuSearchSteps`] iterator that advances the search from the
+    /// `initial_solution` one iteration at a time.
+    pub fn steps(&self, initial_solution: S) -> TabuSearchSteps<'_, S, T> {
+        let current_solution = self.objective.evaluate(initial_solution);
+        TabuSearchSteps {
+            solver: self,
+            state: TabuSearchState {
+                current_solution: current_solution.clone(),
+                best_solution: current_solution,
+                tabu_list: VecDeque::new(),
+                reactive_tenure_state: self.start_reactive_tenure(),
+            },
+            iteration: 0,
+        }
+    }
+
+    /// Continues the run that wrote the `checkpoint` (see
+    /// [`with_checkpoints`][TabuSearchSolver::with_checkpoints]), where the iteration counters, the
+    /// tabu list, the state of the [`ReactiveTenure`], the progress of the termination criteria
+    /// and the elapsed time are restored. Requires the `checkpoint` feature.
+    #[cfg(feature = "checkpoint")]
+    pub fn resume_from_checkpoint(
+        &self,
+        checkpoint: TabuSearchCheckpoint<S, T>,
+    ) -> EvaluatedSolution<S> {
+        let elapsed = stdtime::Duration::from_secs_f64(checkpoint.elapsed_seconds);
+        let mut termination_criteria = self.termination_criteria.clone();
+        if let (Some(criteria), Some(progress)) = (
+            termination_criteria.as_mut(),
+            checkpoint.termination_progress.as_ref(),
+        ) {
+            criteria.restore_progress(progress, &self.objective);
+        }
+        let start_time = stdtime::Instant::now()
+            .checked_sub(elapsed)
+            .unwrap_or_else(stdtime::Instant::now);
+        self.search(
+            self.objective.evaluate(checkpoint.current_solution),
+            self.objective.evaluate(checkpoint.best_solution),
+            checkpoint.tabu_list,
+            checkpoint.reactive_tenure_state,
+            termination_criteria,
+            checkpoint.iteration + 1,
+            start_time,
+            false,
+        )
+        .0
+        .best_solution
+    }
+
+    /// The tabu search loop starting in iteration `iteration_counter` (time measured from
+    /// `start_time`). If no `reactive_tenure_state` is given, the [`ReactiveTenure`] (if
+    /// configured) starts anew. The `termination_criteria` (usually a clone of the criteria of the
+    /// solver) are checked after each iteration.
+    /// * Each step is passed to the `function_between_steps` by a [`SearchRecorder`], which
+    ///   additionally records the trajectory if `record` is `true`.
+    /// * Returns the [`TabuSearchState`] at the end of the run and the [`SearchResult`].
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn search(
+        &self,
+        mut current_solution: EvaluatedSolution<S>,
+        mut best_solution_seen: EvaluatedSolution<S>,
+        mut tabu_list: VecDeque<T>,
+        reactive_tenure_state: Option<ReactiveTenureState>,
+        mut termination_criteria: Option<TerminationCriteria>,
+        mut iteration_counter: u32,
+        start_time: stdtime::Instant,
+        record: bool,
+    ) -> (TabuSearchState<S, T>, SearchResult<S>) {
+        self.local_improver.reset_panic();
+        let mut reactive_tenure_state =
+            reactive_tenure_state.or_else(|| self.start_reactive_tenure());
+        let time_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_time_limit);
+        let iteration_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_iteration_limit);
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            time_limit,
+            iteration_limit,
+            current_solution.objective_value(),
+            record,
+        );
+        let mut termination_reason = TerminationReason::LocalOptimum;
+        let mut iterations_since_restart = 0;
+        while let (Some((new_solution, new_tabus)), neighbor_counts) =
+            self.improve(&current_solution, &best_solution_seen, &tabu_list)
         {
-            tabu_list.extend(new_tabus.into_iter());
-            while tabu_list.len() > self.tabu_list_size {
-                tabu_list.pop_front();
-            }
-            (self.function_between_steps)(
+            self.add_tabus(
+                &mut tabu_list,
+                new_tabus,
+                &new_solution,
+                reactive_tenure_state.as_mut(),
+                iteration_counter,
+            );
+            recorder.step(
                 iteration_counter,
                 &new_solution,
                 Some(&current_solution),
-                self.objective.clone(),
-                Some(start_time),
-                self.time_limit,
-                self.iteration_limit,
+                neighbor_counts,
             );
             current_solution = new_solution;
-            if current_solution.objective_value() < best_solution_seen.objective_value() {
+            if self
+                .objective
+                .is_better(&current_solution, &best_solution_seen)
+            {
                 best_solution_seen = current_solution.clone();
-                iteration_without_global_improvement = 0;
+                iterations_since_restart = 0;
             } else {
-                iteration_without_global_improvement += 1;
+                iterations_since_restart += 1;
             }
-
-            if let Some(iteration_without_global_improvement_limit) =
-                self.iteration_without_global_improvement_limit
-            {
-                if iteration_without_global_improvement
-                    >= iteration_without_global_improvement_limit
-                {
-                    println!("Iteration without global improvement limit reached.");
-                    break;
+            if let Some(elite_pool) = &self.elite_pool {
+                if elite_pool.offer_or_restart(
+                    &mut current_solution,
+                    &mut iterations_since_restart,
+                    self.elite_restart_after,
+                ) {
+                    tabu_list.clear();
                 }
             }
+            // checked before the checkpoint, such that the checkpoint contains the progress of the
+            // termination criteria after this iteration
+            let reason = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            });
+            #[cfg(feature = "checkpoint")]
+            if let Some(checkpoint_function) = &self.checkpoint_function {
+                checkpoint_function(&CheckpointState {
+                    current_solution: &current_solution,
+                    best_solution: &best_solution_seen,
+                    tabu_list: &tabu_list,
+                    reactive_tenure_state: reactive_tenure_state.as_ref(),
+                    termination_criteria: termination_criteria.as_ref(),
+                    iteration: iteration_counter,
+                    elapsed: start_time.elapsed(),
+                });
+            }
 
-            if let Some(time_limit) = self.time_limit {
-                if stdtime::Instant::now().duration_since(start_time) > time_limit {
-                    println!("Time limit reached.");
-                    break;
-                }
+            if let Some(reason) = reason {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
             }
-            if let Some(iteration_limit) = self.iteration_limit {
-                if iteration_counter >= iteration_limit {
-                    println!("Iteration limit reached.");
-                    break;
+            if let Some(soft_time_limit) = self.soft_time_limit {
+                if stdtime::Instant::now().duration_since(start_time) > soft_time_limit {
+                    let empty_tabu_list = VecDeque::new();
+                    let (best_solution, iterations, termination_reason) = wind_down(
+                        &mut recorder,
+                        best_solution_seen,
+                        |current_solution, deadline| {
+                            let mut evaluated = 0;
+                            let improved_solution = self
+                                .neighborhood
+                                .neighbors_of(current_solution.solution(), &empty_tabu_list)
+                                .take_while(|_| {
+                                    deadline
+                                        .is_none_or(|deadline| stdtime::Instant::now() < deadline)
+                                })
+                                .map(|(neighbor, _)| self.objective.evaluate(neighbor))
+                                .inspect(|_| evaluated += 1)
+                                .find(|neighbor| {
+                                    self.objective.is_better(neighbor, current_solution)
+                                });
+                            let neighbor_counts = NeighborCounts {
+                                generated: evaluated,
+                                evaluated,
+                                accepted: u64::from(improved_solution.is_some()),
+                                ..NeighborCounts::default()
+                            };
+                            (improved_solution, Some(neighbor_counts))
+                        },
+                        iteration_counter,
+                        start_time,
+                        time_limit,
+                    );
+                    let state = TabuSearchState {
+                        current_solution: best_solution.clone(),
+                        best_solution: best_solution.clone(),
+                        tabu_list,
+                        reactive_tenure_state,
+                    };
+                    return (
+                        state,
+                        recorder.finish(best_solution, iterations, termination_reason),
+                    );
                 }
             }
             iteration_counter += 1;
         }
-        best_solution_seen
+        // without break, the loop stops in an iteration without step
+        let iterations = if termination_reason == TerminationReason::LocalOptimum {
+            iteration_counter - 1
+        } else {
+            iteration_counter
+        };
+        let panic_message = self.local_improver.panic_message();
+        if termination_reason == TerminationReason::LocalOptimum && panic_message.is_some() {
+            termination_reason = TerminationReason::Panicked;
+        }
+        let result = SearchResult {
+            panic_message,
+            ..recorder.finish(best_solution_seen.clone(), iterations, termination_reason)
+        };
+        let state = TabuSearchState {
+            current_solution,
+            best_solution: best_solution_seen,
+            tabu_list,
+            reactive_tenure_state,
+        };
+        (state, result)
+    }
+
+    /// Starts the [`ReactiveTenure`] (if configured) with the tabu list size of the solver.
+    fn start_reactive_tenure(&self) -> Option<ReactiveTenureState> {
+        self.reactive_tenure
+            .as_ref()
+            .map(|reactive_tenure| reactive_tenure.start(self.tabu_policy.default_tenure()))
+    }
+
+    /// Adds the `new_tabus` of the step to `new_solution` in `iteration` to the `tabu_list`, where
+    /// the tenure is adapted by the [`ReactiveTenure`] if a `reactive_tenure_state` is given.
+    fn add_tabus(
+        &self,
+        tabu_list: &mut VecDeque<T>,
+        new_tabus: Vec<T>,
+        new_solution: &EvaluatedSolution<S>,
+        reactive_tenure_state: Option<&mut ReactiveTenureState>,
+        iteration: u32,
+    ) {
+        match (&self.reactive_tenure, reactive_tenure_state) {
+            (Some(reactive_tenure), Some(state)) => {
+                let tenure = reactive_tenure.adapt(state, new_solution.solution(), iteration);
+                self.tabu_policy
+                    .add_tabus_with_default_tenure(tabu_list, new_tabus, tenure);
+            }
+            _ => self.tabu_policy.add_tabus(tabu_list, new_tabus),
+        }
+    }
+
+    /// Returns the best non-tabu neighbor of the [`TabuImprover`] or, if it is better, the best
+    /// tabu neighbor that satisfies the [`AspirationCriterion`], which the [`TabuImprover`] finds
+    /// in the same exploration (see [`Aspiration`]).
+    /// * The [`NeighborCounts`] of the [`TabuImprover`] are extended by the tabu neighbors offered
+    ///   to the [`Aspiration`]: They are counted as rejected as tabu (unless one of them is
+    ///   accepted by the aspiration criterion).
+    /// * If the [`Aspiration`] is not active (e.g., with
+    ///   [`NoAspiration`][super::common::NoAspiration]), the tabu neighbors are never generated,
+    ///   so they are not counted.
+    /// * If the [`TabuImprover`] does not count its neighbors, the counts are `None`.
+    #[allow(clippy::type_complexity)]
+    fn improve(
+        &self,
+        current_solution: &EvaluatedSolution<S>,
+        best_solution_seen: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let aspiration = Aspiration::new(
+            self.aspiration_criterion.as_ref(),
+            current_solution,
+            best_solution_seen,
+            &self.objective,
+            !tabu_list.is_empty(),
+        );
+        let (non_tabu_neighbor, neighbor_counts) =
+            self.local_improver
+                .improve_with_aspiration(current_solution, tabu_list, &aspiration);
+        let (aspirating_neighbor, tabu_offered) = aspiration.finish();
+        let neighbor_counts = neighbor_counts.map(|neighbor_counts| {
+            counts_with_aspiration(
+                &self.objective,
+                &non_tabu_neighbor,
+                &aspirating_neighbor,
+                neighbor_counts,
+                tabu_offered,
+            )
+        });
+        if let Some(acceptance_logger) = &self.acceptance_logger {
+            log_aspiration_decisions(
+                acceptance_logger,
+                &self.objective,
+                current_solution,
+                &non_tabu_neighbor,
+                &aspirating_neighbor,
+            );
+        }
+        (
+            prefer_aspirating_neighbor(&self.objective, non_tabu_neighbor, aspirating_neighbor),
+            neighbor_counts,
+        )
+    }
+}
+
+/// Advances the search of a [`TabuSearchSolver`] one iteration at a time (see
+/// [`TabuSearchSolver::steps`]).
+/// * Each call of [`step`][TabuSearchSteps::step] (or [`next`][Iterator::next]) replaces the
+///   current solution by the best non-tabu neighbor (or an aspirating tabu neighbor), even if it
+///   is worse, and updates the tabu list and the best solution seen. It returns `None` if there
+///   is no such neighbor (e.g., if all neighbors are tabu). The search can be resumed after the
+///   current solution was replaced.
+/// * Between the iterations, the current solution can be inspected by
+///   [`current_solution`][TabuSearchSteps::current_solution] and replaced by
+///   [`set_current_solution`][TabuSearchSteps::set_current_solution].
+/// * The caller controls the loop, so the `function_between_steps`, the limits, the elite pool
+///   and the checkpoints of the solver are not applied.
+/// * [`into_state`][TabuSearchSteps::into_state] returns the [`TabuSearchState`], such that a
+///   regular run can continue (see [`continue_from_state`][TabuSearchSolver::continue_from_state]).
+pub struct TabuSearchSteps<'a, S, T> {
+    solver: &'a TabuSearchSolver<S, T>,
+    state: TabuSearchState<S, T>,
+    iteration: u32,
+}
+
+impl<S: Clone, T: std::fmt::Debug> TabuSearchSteps<'_, S, T> {
+    /// Performs one iteration and returns the new current solution, or `None` if there is no
+    /// non-tabu (or aspirating) neighbor.
+    pub fn step(&mut self) -> Option<&EvaluatedSolution<S>> {
+        let (new_solution, new_tabus) = self
+            .solver
+            .improve(
+                &self.state.current_solution,
+                &self.state.best_solution,
+                &self.state.tabu_list,
+            )
+            .0?;
+        self.iteration += 1;
+        self.solver.add_tabus(
+            &mut self.state.tabu_list,
+            new_tabus,
+            &new_solution,
+            self.state.reactive_tenure_state.as_mut(),
+            self.iteration,
+        );
+        if self
+            .solver
+            .objective
+            .is_better(&new_solution, &self.state.best_solution)
+        {
+            self.state.best_solution = new_solution.clone();
+        }
+        self.state.current_solution = new_solution;
+        Some(&self.state.current_solution)
+    }
+
+    /// Returns the current solution.
+    pub fn current_solution(&self) -> &EvaluatedSolution<S> {
+        &self.state.current_solution
+    }
+
+    /// Returns the best solution seen so far.
+    pub fn best_solution(&self) -> &EvaluatedSolution<S> {
+        &self.state.best_solution
+    }
+
+    /// Returns the tabu list (the oldest tabu first).
+    pub fn tabu_list(&self) -> &VecDeque<T> {
+        &self.state.tabu_list
+    }
+
+    /// Replaces the current solution (e.g., after a manual change), from which the next
+    /// iteration continues. The best solution is updated if the new current solution is better.
+    pub fn set_current_solution(&mut self, solution: S) {
+        let current_solution = self.solver.objective.evaluate(solution);
+        if self
+            .solver
+            .objective
+            .is_better(&current_solution, &self.state.best_solution)
+        {
+            self.state.best_solution = current_solution.clone();
+        }
+        self.state.current_solution = current_solution;
+    }
+
+    /// Returns the number of iterations performed so far.
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    /// Consumes the steps and returns the [`TabuSearchState`].
+    pub fn into_state(self) -> TabuSearchState<S, T> {
+        self.state
+    }
+}
+
+impl<S: Clone, T: std::fmt::Debug> Iterator for TabuSearchSteps<'_, S, T> {
+    type Item = EvaluatedSolution<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heuristics::common::NoAspiration;
+    use crate::test_fixtures::{DistanceTo, PlusMinusOne};
+    use std::sync::Mutex;
+
+    /// Returns a [`TabuSearchSolver`] for the [`DistanceTo(100)`][DistanceTo] objective that
+    /// records the [`NeighborCounts`] of each step.
+    fn counting_solver(
+        tabu_list_size: usize,
+        iteration_limit: u32,
+    ) -> (TabuSearchSolver<i64, i64>, Arc<Mutex<Vec<NeighborCounts>>>) {
+        let neighbor_counts_per_iteration = Arc::new(Mutex::new(Vec::new()));
+        let recorded_counts = neighbor_counts_per_iteration.clone();
+        let solver = TabuSearchSolver::with_options(
+            Arc::new(PlusMinusOne),
+            Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(100)))),
+            tabu_list_size,
+            None,
+            Some(Box::new(move |_, _, _, _, _, _, _, neighbor_counts| {
+                recorded_counts
+                    .lock()
+                    .unwrap()
+                    .push(neighbor_counts.unwrap());
+            })),
+            Some(TerminationCriteria::iteration_limit(iteration_limit)),
+        );
+        (solver, neighbor_counts_per_iteration)
+    }
+
+    #[test]
+    fn test_neighbor_counts() {
+        let (solver, neighbor_counts_per_iteration) = counting_solver(2, 8);
+
+        assert_eq!(*solver.solve(95).solution(), 100);
+        let neighbor_counts_per_iteration = neighbor_counts_per_iteration.lock().unwrap();
+        assert_eq!(neighbor_counts_per_iteration.len(), 8);
+        // the tabu list is empty in the first iteration
+        assert_eq!(
+            neighbor_counts_per_iteration[0],
+            NeighborCounts {
+                generated: 2,
+                evaluated: 2,
+                accepted: 1,
+                ..NeighborCounts::default()
+            }
+        );
+        // afterwards, the previous solution is tabu and checked for aspiration in the same
+        // exploration
+        assert_eq!(
+            neighbor_counts_per_iteration[1],
+            NeighborCounts {
+                generated: 2,
+                evaluated: 2,
+                rejected_as_tabu: 1,
+                accepted: 1,
+                ..NeighborCounts::default()
+            }
+        );
+        assert!(neighbor_counts_per_iteration[1..]
+            .iter()
+            .all(|neighbor_counts| neighbor_counts.rejected_as_tabu == 1));
+    }
+
+    #[test]
+    fn test_aspiration_accepts_new_global_best() {
+        // 96 is tabu, but better than the best solution seen (95)
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(DistanceTo(100))));
+        let initial_solution = objective.evaluate(95);
+        let run_with = |aspiration_criterion: Box<dyn AspirationCriterion<i64, i64>>| {
+            let (solver, neighbor_counts_per_iteration) = counting_solver(2, 1);
+            let solver = solver.with_aspiration_criterion(aspiration_criterion);
+            let (state, _) = solver.search(
+                initial_solution.clone(),
+                initial_solution.clone(),
+                VecDeque::from([96]),
+                None,
+                solver.termination_criteria.clone(),
+                1,
+                stdtime::Instant::now(),
+                false,
+            );
+            let neighbor_counts = neighbor_counts_per_iteration.lock().unwrap()[0];
+            (*state.current_solution.solution(), neighbor_counts)
+        };
+
+        assert_eq!(
+            run_with(Box::new(NewGlobalBestAspiration)),
+            (
+                96,
+                NeighborCounts {
+                    generated: 2,
+                    evaluated: 2,
+                    accepted: 1,
+                    ..NeighborCounts::default()
+                }
+            )
+        );
+        // with a strict tabu list, the tabu neighbor is not even generated
+        assert_eq!(
+            run_with(Box::new(NoAspiration)),
+            (
+                94,
+                NeighborCounts {
+                    generated: 1,
+                    evaluated: 1,
+                    accepted: 1,
+                    ..NeighborCounts::default()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_steps() {
+        let (solver, _) = counting_solver(2, 5);
+
+        // the steps perform the same iterations as a run
+        let mut steps = solver.steps(97);
+        assert_eq!(steps.by_ref().take(5).count(), 5);
+        let state = solver.solve_with_state(97);
+        assert_eq!(steps.iteration(), 5);
+        assert_eq!(
+            steps.current_solution().solution(),
+            state.current_solution.solution()
+        );
+        assert_eq!(steps.tabu_list(), &state.tabu_list);
+        // 97 -> 98 -> 99 -> 100 -> 101 -> 102
+        assert_eq!(*steps.current_solution().solution(), 102);
+        assert_eq!(*steps.best_solution().solution(), 100);
+
+        // the search continues from a replaced current solution
+        steps.set_current_solution(50);
+        assert_eq!(*steps.step().unwrap().solution(), 51);
+        assert_eq!(*steps.best_solution().solution(), 100);
+        let state = steps.into_state();
+        assert_eq!(*state.current_solution.solution(), 51);
+        assert_eq!(state.tabu_list, VecDeque::from([101, 50]));
     }
 }
@@ -0,0 +1,307 @@
+//! This module contains reusable tabus for permutation solutions (e.g., tours, sequences or
+//! orderings), such that a new permutation problem does not have to implement its own tabus.
+//! * An [`ArcTabu`] forbids to recreate an arc (two consecutive elements) that was removed by a
+//!   move.
+//! * A [`PositionTabu`] forbids to move an element back to the position it had before a move.
+//! * A [`PermutationTabu`] combines both, such that a [`TabuPolicy`][crate::heuristics::common::TabuPolicy]
+//!   can give them different tenures (see [`PermutationTabu::class`]).
+//!
+//! A tabu is active as long as it is in the tabu list, i.e., for as many iterations as the tabu
+//! list size (or the tenure of its class) allows.
+//!
+//! For an example, see the [tabu search solver][crate::examples::tsp::solvers::tabu_search] for
+//! the TSP, which uses [`ArcTabus`][ArcTabu] for 3-opt moves.
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A directed arc from the element `start` to the element `end` that was removed by a move.
+/// Neighbors that would insert this arc again are tabu.
+/// * The arcs of a permutation are given by indices: the arc at index `i` connects the elements
+///   at the indices `i` and `i+1` (modulo the length, so for cyclic permutations like tours, the
+///   arc at the last index connects the last and the first element).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ArcTabu<E> {
+    start: E,
+    end: E,
+}
+
+impl<E: Copy + Eq> ArcTabu<E> {
+    /// Creates a new [`ArcTabu`] that forbids the arc from `start` to `end`.
+    pub fn new(start: E, end: E) -> ArcTabu<E> {
+        ArcTabu { start, end }
+    }
+
+    /// Returns the start element of the forbidden arc.
+    pub fn start(&self) -> E {
+        self.start
+    }
+
+    /// Returns the end element of the forbidden arc.
+    pub fn end(&self) -> E {
+        self.end
+    }
+
+    /// Returns `true` if the arc from `start` to `end` is forbidden by this tabu.
+    pub fn forbids(&self, start: E, end: E) -> bool {
+        self.start == start && self.end == end
+    }
+
+    /// Creates one tabu for each arc of the `permutation` that is removed by a move, where the
+    /// removed arcs are given by their indices `removed_arcs` (see [`ArcTabu`]).
+    pub fn for_removed_arcs(permutation: &[E], removed_arcs: &[usize]) -> Vec<ArcTabu<E>> {
+        let n = permutation.len();
+        removed_arcs
+            .iter()
+            .map(|&i| ArcTabu::new(permutation[i % n], permutation[(i + 1) % n]))
+            .collect()
+    }
+
+    /// Returns `true` if one of the arcs added by a move is tabu. The added arcs are given as
+    /// pairs of indices `(a, b)` of the `permutation` before the move, i.e., the move connects the
+    /// element at index `a` to the element at index `b` (modulo the length).
+    pub fn any_forbids(
+        tabu_list: &VecDeque<ArcTabu<E>>,
+        permutation: &[E],
+        added_arcs: &[(usize, usize)],
+    ) -> bool {
+        let n = permutation.len();
+        added_arcs.iter().any(|&(a, b)| {
+            tabu_list
+                .iter()
+                .any(|tabu| tabu.forbids(permutation[a % n], permutation[b % n]))
+        })
+    }
+}
+
+impl<E: Copy + Eq + Hash> ArcTabu<E> {
+    /// Creates one tabu for each arc of the `old_permutation` that is not an arc of the
+    /// `new_permutation` (for arbitrary moves, where the removed arcs are not known). If `cyclic`
+    /// is `true`, the arc from the last to the first element is considered.
+    pub fn removed_arcs(
+        old_permutation: &[E],
+        new_permutation: &[E],
+        cyclic: bool,
+    ) -> Vec<ArcTabu<E>> {
+        let new_arcs: HashSet<ArcTabu<E>> = arcs_of(new_permutation, cyclic).collect();
+        arcs_of(old_permutation, cyclic)
+            .filter(|arc| !new_arcs.contains(arc))
+            .collect()
+    }
+
+    /// Returns `true` if the `permutation` contains an arc of the `tabu_list`. If `cyclic` is
+    /// `true`, the arc from the last to the first element is considered.
+    pub fn is_recreated_by(
+        tabu_list: &VecDeque<ArcTabu<E>>,
+        permutation: &[E],
+        cyclic: bool,
+    ) -> bool {
+        let tabu_arcs: HashSet<&ArcTabu<E>> = tabu_list.iter().collect();
+        arcs_of(permutation, cyclic).any(|arc| tabu_arcs.contains(&arc))
+    }
+}
+
+/// Returns the arcs of the `permutation` (with the arc from the last to the first element if
+/// `cyclic`).
+fn arcs_of<E: Copy + Eq>(permutation: &[E], cyclic: bool) -> impl Iterator<Item = ArcTabu<E>> + '_ {
+    let n = permutation.len();
+    let number_of_arcs = if cyclic { n } else { n.saturating_sub(1) };
+    (0..number_of_arcs).map(move |i| ArcTabu::new(permutation[i], permutation[(i + 1) % n]))
+}
+
+/// The `element` was at index `position` before a move. Neighbors that would move the element
+/// back to this position are tabu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PositionTabu<E> {
+    element: E,
+    position: usize,
+}
+
+impl<E: Copy + Eq> PositionTabu<E> {
+    /// Creates a new [`PositionTabu`] that forbids the `element` at index `position`.
+    pub fn new(element: E, position: usize) -> PositionTabu<E> {
+        PositionTabu { element, position }
+    }
+
+    /// Returns the element of the tabu.
+    pub fn element(&self) -> E {
+        self.element
+    }
+
+    /// Returns the forbidden position of the element.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns `true` if placing the `element` at index `position` is forbidden by this tabu.
+    pub fn forbids(&self, element: E, position: usize) -> bool {
+        self.element == element && self.position == position
+    }
+
+    /// Creates one tabu for each element whose position differs between the `old_permutation`
+    /// and the `new_permutation`, which forbids the element to return to its old position.
+    pub fn for_moved_elements(
+        old_permutation: &[E],
+        new_permutation: &[E],
+    ) -> Vec<PositionTabu<E>> {
+        old_permutation
+            .iter()
+            .zip(new_permutation.iter())
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(position, (&old, _))| PositionTabu::new(old, position))
+            .collect()
+    }
+
+    /// Returns `true` if an element of the `permutation` is at a position that is forbidden by
+    /// the `tabu_list`.
+    pub fn any_violated_by(tabu_list: &VecDeque<PositionTabu<E>>, permutation: &[E]) -> bool {
+        tabu_list.iter().any(|tabu| {
+            permutation
+                .get(tabu.position)
+                .is_some_and(|&element| element == tabu.element)
+        })
+    }
+}
+
+/// Either an [`ArcTabu`] or a [`PositionTabu`], such that both kinds can be in the same tabu list
+/// (with different tenures via a [`TabuPolicy`][crate::heuristics::common::TabuPolicy]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PermutationTabu<E> {
+    /// An arc that must not be recreated.
+    Arc(ArcTabu<E>),
+    /// A position that an element must not return to.
+    Position(PositionTabu<E>),
+}
+
+impl<E> PermutationTabu<E> {
+    /// Returns the class of the tabu (`"arc"` or `"position"`), which can be used as
+    /// [`TabuClassFunction`][crate::heuristics::common::TabuClassFunction] of a
+    /// [`TabuPolicy`][crate::heuristics::common::TabuPolicy].
+    pub fn class(&self) -> &'static str {
+        match self {
+            PermutationTabu::Arc(_) => "arc",
+            PermutationTabu::Position(_) => "position",
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash> PermutationTabu<E> {
+    /// Creates the tabus for a move from the `old_permutation` to the `new_permutation`: An
+    /// [`ArcTabu`] for each removed arc and a [`PositionTabu`] for each moved element.
+    pub fn for_move(
+        old_permutation: &[E],
+        new_permutation: &[E],
+        cyclic: bool,
+    ) -> Vec<PermutationTabu<E>> {
+        ArcTabu::removed_arcs(old_permutation, new_permutation, cyclic)
+            .into_iter()
+            .map(PermutationTabu::Arc)
+            .chain(
+                PositionTabu::for_moved_elements(old_permutation, new_permutation)
+                    .into_iter()
+                    .map(PermutationTabu::Position),
+            )
+            .collect()
+    }
+
+    /// Returns `true` if the `permutation` recreates a tabu arc or places an element at a tabu
+    /// position.
+    pub fn is_tabu(
+        tabu_list: &VecDeque<PermutationTabu<E>>,
+        permutation: &[E],
+        cyclic: bool,
+    ) -> bool {
+        let arc_tabus: VecDeque<ArcTabu<E>> = tabu_list
+            .iter()
+            .filter_map(|tabu| match tabu {
+                PermutationTabu::Arc(arc_tabu) => Some(*arc_tabu),
+                PermutationTabu::Position(_) => None,
+            })
+            .collect();
+        let position_tabus: VecDeque<PositionTabu<E>> = tabu_list
+            .iter()
+            .filter_map(|tabu| match tabu {
+                PermutationTabu::Arc(_) => None,
+                PermutationTabu::Position(position_tabu) => Some(*position_tabu),
+            })
+            .collect();
+        ArcTabu::is_recreated_by(&arc_tabus, permutation, cyclic)
+            || PositionTabu::any_violated_by(&position_tabus, permutation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::heuristics::common::TabuPolicy;
+
+    #[test]
+    fn test_arc_tabu() {
+        let tour = [0, 1, 2, 3, 4];
+        // a 2-opt move removes the arcs at the indices 0 and 2 and adds (0, 2) and (1, 3)
+        let tabus = ArcTabu::for_removed_arcs(&tour, &[0, 2]);
+        assert_eq!(tabus, vec![ArcTabu::new(0, 1), ArcTabu::new(2, 3)]);
+
+        let tabu_list = VecDeque::from(tabus);
+        assert!(!ArcTabu::any_forbids(&tabu_list, &tour, &[(0, 2), (1, 3)]));
+        assert!(ArcTabu::any_forbids(&tabu_list, &tour, &[(2, 3)]));
+        assert_eq!(
+            ArcTabu::removed_arcs(&tour, &[0, 2, 1, 3, 4], true),
+            vec![ArcTabu::new(0, 1), ArcTabu::new(1, 2), ArcTabu::new(2, 3)]
+        );
+        assert!(ArcTabu::is_recreated_by(
+            &tabu_list,
+            &[4, 2, 3, 0, 1],
+            false
+        ));
+        // the arc from the last to the first element is only considered for cyclic permutations
+        assert!(!ArcTabu::is_recreated_by(
+            &VecDeque::from([ArcTabu::new(4, 0)]),
+            &tour,
+            false
+        ));
+        assert!(ArcTabu::is_recreated_by(
+            &VecDeque::from([ArcTabu::new(4, 0)]),
+            &tour,
+            true
+        ));
+    }
+
+    #[test]
+    fn test_permutation_tabu() {
+        let old = [0, 1, 2, 3];
+        let new = [0, 2, 1, 3];
+        let tabus = PermutationTabu::for_move(&old, &new, false);
+        assert_eq!(
+            tabus,
+            vec![
+                PermutationTabu::Arc(ArcTabu::new(0, 1)),
+                PermutationTabu::Arc(ArcTabu::new(1, 2)),
+                PermutationTabu::Arc(ArcTabu::new(2, 3)),
+                PermutationTabu::Position(PositionTabu::new(1, 1)),
+                PermutationTabu::Position(PositionTabu::new(2, 2)),
+            ]
+        );
+
+        // at most 3 arc tabus and 4 position tabus are kept
+        let policy = TabuPolicy::new(
+            Box::new(PermutationTabu::class),
+            HashMap::from([("arc", 3), ("position", 4)]),
+        );
+        let mut tabu_list = VecDeque::new();
+        policy.add_tabus(&mut tabu_list, tabus);
+        assert!(PermutationTabu::is_tabu(&tabu_list, &old, false));
+        let next = [3, 2, 1, 0];
+        policy.add_tabus(
+            &mut tabu_list,
+            PermutationTabu::for_move(&new, &next, false),
+        );
+        assert_eq!(tabu_list.len(), 7);
+        // the arcs (0, 1) and (1, 2) are not tabu anymore
+        assert!(!PermutationTabu::is_tabu(&tabu_list, &[3, 0, 1, 2], false));
+        // 0 would return to its old position 0
+        assert!(PermutationTabu::is_tabu(&tabu_list, &[0, 3, 1, 2], false));
+    }
+}
@@ -0,0 +1,134 @@
+//! [`DeltaTabuMinimizer`] searches the whole [`TabuMoveNeighborhood`] of a solution, evaluates
+//! the moves incrementally by a [`DeltaObjective`] and only applies the best non-tabu move.
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    heuristics::{
+        common::{Aspiration, NeighborCounts},
+        tabu_search::TabuMoveNeighborhood,
+    },
+    objective::{DeltaObjective, EvaluatedSolution, ObjectiveValue},
+};
+
+use super::TabuImprover;
+
+/// [`DeltaTabuMinimizer`] searches the whole [`TabuMoveNeighborhood`] of a solution (and a tabu
+/// list) and returns the best non-tabu neighbor with new tabus.
+/// * The moves are the modifications of the [`DeltaObjective`], so each move is evaluated from
+///   the objective value of the given solution without applying it.
+/// * Only the best move is applied, its objective value is also taken from the deltas.
+/// * No parallelism is used.
+/// * If all neighbors are tabu, `None` is returned.
+pub struct DeltaTabuMinimizer<S, T, M> {
+    neighborhood: Arc<dyn TabuMoveNeighborhood<S, T, M>>,
+    delta_objective: Arc<DeltaObjective<S, M>>,
+}
+
+impl<S, T, M> DeltaTabuMinimizer<S, T, M> {
+    /// Creates a new [`DeltaTabuMinimizer`] with the given [`TabuMoveNeighborhood`] and
+    /// [`DeltaObjective`].
+    pub fn new(
+        neighborhood: Arc<dyn TabuMoveNeighborhood<S, T, M>>,
+        delta_objective: Arc<DeltaObjective<S, M>>,
+    ) -> Self {
+        Self {
+            neighborhood,
+            delta_objective,
+        }
+    }
+}
+
+impl<S, T, M> DeltaTabuMinimizer<S, T, M> {
+    /// Returns the best of the non-tabu `moves` applied to the `solution` and the
+    /// [`NeighborCounts`].
+    #[allow(clippy::type_complexity)]
+    fn best_neighbor(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        moves: impl Iterator<Item = M>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let mut evaluated = 0;
+        let best_move: Option<(M, ObjectiveValue)> = moves
+            .map(|mv| {
+                evaluated += 1;
+                let objective_value = self.delta_objective.evaluate_modification(solution, &mv);
+                (mv, objective_value)
+            })
+            .min_by(|(_, value1), (_, value2)| value1.cmp(value2));
+
+        let best_neighbor_with_new_tabus = match best_move {
+            Some((mv, objective_value)) => {
+                let (neighbor, new_tabus) = self.neighborhood.apply(solution.solution(), &mv);
+                Some((EvaluatedSolution::new(neighbor, objective_value), new_tabus))
+            }
+            None => {
+                report!(Warning, "no swap possible.");
+                None
+            }
+        };
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(best_neighbor_with_new_tabus.is_some()),
+            ..NeighborCounts::default()
+        };
+        (best_neighbor_with_new_tabus, Some(neighbor_counts))
+    }
+}
+
+impl<S, T, M: Send + Sync> TabuImprover<S, T> for DeltaTabuMinimizer<S, T, M> {
+    fn improve(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+        self.improve_counted(solution, tabu_list).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.best_neighbor(
+            solution,
+            self.neighborhood.moves_of(solution.solution(), tabu_list),
+        )
+    }
+
+    /// The tabu moves are evaluated by the [`DeltaObjective`] as well, but each of them is
+    /// applied to check the aspiration criterion.
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        if !aspiration.is_active() {
+            return self.improve_counted(solution, tabu_list);
+        }
+        self.best_neighbor(
+            solution,
+            self.neighborhood
+                .moves_with_tabu_status_of(solution.solution(), tabu_list)
+                .filter_map(|(mv, is_tabu)| {
+                    if !is_tabu {
+                        return Some(mv);
+                    }
+                    let objective_value = self.delta_objective.evaluate_modification(solution, &mv);
+                    let (neighbor, new_tabus) = self.neighborhood.apply(solution.solution(), &mv);
+                    aspiration.offer(EvaluatedSolution::new(neighbor, objective_value), new_tabus);
+                    None
+                }),
+        )
+    }
+}
@@ -0,0 +1,179 @@
+//! [`FrequencyPenalizedTabuMinimizer`] searches the whole [`TabuNeighborhood`] of a solution and
+//! returns the best non-tabu neighbor, where frequently applied moves are penalized.
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    heuristics::{
+        common::{Aspiration, NeighborCounts},
+        tabu_search::TabuNeighborhood,
+    },
+    objective::{BaseValue, Coefficient, EvaluatedSolution, Objective, ObjectiveValue},
+};
+
+use super::TabuImprover;
+
+/// [`FrequencyPenalizedTabuMinimizer`] searches the whole [`TabuNeighborhood`] of a solution (and
+/// a tabu list) and returns the best non-tabu neighbor with new tabus, where the neighbors are
+/// compared by a penalized objective value (frequency-based long-term memory).
+/// * The tabus returned together with a neighbor are the attributes of the move (e.g., the
+///   removed arcs). The improver counts how often each attribute was part of a returned neighbor.
+/// * A non-improving neighbor is penalized by `penalty_per_use` times the sum of the counts of its
+///   tabus on level `penalty_level` of the objective value. Improving neighbors are not
+///   penalized, such that improvements are never missed.
+/// * The returned neighbor is evaluated without penalty.
+/// * Hence, moves that were applied often are avoided when the search stagnates, which
+///   diversifies the search in the long run (while the tabu list is the short-term memory).
+/// * The counts are kept between calls, so a new instance should be used for each run.
+/// * If all neighbors are tabu, `None` is returned.
+pub struct FrequencyPenalizedTabuMinimizer<S, T> {
+    neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
+    objective: Arc<Objective<S>>,
+    penalty_level: usize,
+    penalty_per_use: BaseValue,
+    frequencies: Mutex<HashMap<T, i32>>,
+}
+
+impl<S, T> FrequencyPenalizedTabuMinimizer<S, T> {
+    /// Creates a new [`FrequencyPenalizedTabuMinimizer`] with the given [`TabuNeighborhood`] and
+    /// [`Objective`].
+    /// * `penalty_level` is the index of the level of the objective that is penalized (usually the
+    ///   last level, i.e., the costs).
+    /// * `penalty_per_use` is added to this level for each time an attribute of the move was used
+    ///   before. It must be of the same [`BaseValue`] variant as the level.
+    pub fn new(
+        neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
+        objective: Arc<Objective<S>>,
+        penalty_level: usize,
+        penalty_per_use: BaseValue,
+    ) -> Self {
+        Self {
+            neighborhood,
+            objective,
+            penalty_level,
+            penalty_per_use,
+            frequencies: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the objective value with the penalty for a move of the given `frequency`.
+    fn penalized(&self, objective_value: &ObjectiveValue, frequency: i32) -> ObjectiveValue {
+        let penalty = Coefficient::Integer(frequency) * self.penalty_per_use;
+        ObjectiveValue::new(
+            objective_value
+                .iter()
+                .enumerate()
+                .map(|(level, value)| {
+                    if level == self.penalty_level {
+                        *value + penalty
+                    } else {
+                        *value
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<S, T: Clone + Eq + Hash> FrequencyPenalizedTabuMinimizer<S, T> {
+    /// Returns the best of the non-tabu `neighbors` of the `solution` with respect to the
+    /// penalized objective value, updates the frequencies and returns the [`NeighborCounts`].
+    #[allow(clippy::type_complexity)]
+    fn best_neighbor(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        neighbors: impl Iterator<Item = (S, Vec<T>)>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let mut frequencies = self.frequencies.lock().unwrap();
+        let mut evaluated = 0;
+        let best_neighbor_with_new_tabus = neighbors
+            .map(|(neighbor, new_tabus)| {
+                evaluated += 1;
+                let neighbor = self.objective.evaluate(neighbor);
+                let penalized_objective_value =
+                    if neighbor.objective_value() < solution.objective_value() {
+                        neighbor.objective_value().clone()
+                    } else {
+                        let frequency = new_tabus
+                            .iter()
+                            .map(|tabu| frequencies.get(tabu).copied().unwrap_or(0))
+                            .sum();
+                        self.penalized(neighbor.objective_value(), frequency)
+                    };
+                (penalized_objective_value, neighbor, new_tabus)
+            })
+            .min_by(|(v1, _, _), (v2, _, _)| v1.partial_cmp(v2).unwrap())
+            .map(|(_, neighbor, new_tabus)| (neighbor, new_tabus));
+
+        match &best_neighbor_with_new_tabus {
+            Some((_, new_tabus)) => {
+                for tabu in new_tabus {
+                    *frequencies.entry(tabu.clone()).or_insert(0) += 1;
+                }
+            }
+            None => report!(Warning, "no swap possible."),
+        }
+
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(best_neighbor_with_new_tabus.is_some()),
+            ..NeighborCounts::default()
+        };
+        (best_neighbor_with_new_tabus, Some(neighbor_counts))
+    }
+}
+
+impl<S, T: Clone + Eq + Hash + Send> TabuImprover<S, T> for FrequencyPenalizedTabuMinimizer<S, T> {
+    fn improve(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+        self.improve_counted(solution, tabu_list).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.best_neighbor(
+            solution,
+            self.neighborhood
+                .neighbors_of(solution.solution(), tabu_list),
+        )
+    }
+
+    /// The tabu neighbors are compared without penalty.
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        if !aspiration.is_active() {
+            return self.improve_counted(solution, tabu_list);
+        }
+        self.best_neighbor(
+            solution,
+            self.neighborhood
+                .neighbors_with_tabu_status_of(solution.solution(), tabu_list)
+                .filter_map(|(neighbor, new_tabus, is_tabu)| {
+                    aspiration.filter_tabu(neighbor, new_tabus, is_tabu)
+                }),
+        )
+    }
+}
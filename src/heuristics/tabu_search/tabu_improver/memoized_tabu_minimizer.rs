@@ -0,0 +1,272 @@
+//! [`MemoizedTabuMinimizer`] searches the whole [`TabuMoveNeighborhood`] of a solution and
+//! returns the best non-tabu neighbor, reusing the evaluations of the previous iteration for moves
+//! that are not affected by the last applied move.
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    heuristics::{
+        common::{Aspiration, NeighborCounts},
+        tabu_search::TabuMoveNeighborhood,
+    },
+    objective::{EvaluatedSolution, Objective, ObjectiveValue},
+};
+
+use super::TabuImprover;
+
+/// The evaluations of the previous iteration.
+struct EvaluationMemo<S, M> {
+    /// The move that was applied in the previous iteration.
+    applied_move: M,
+    /// The objective value of the solution in the previous iteration.
+    previous_objective_value: ObjectiveValue,
+    /// The neighbor returned in the previous iteration.
+    returned_solution: S,
+    /// The (estimated) objective value of the neighbor of the previous solution for each move.
+    neighbor_objective_values: HashMap<M, ObjectiveValue>,
+}
+
+/// [`MemoizedTabuMinimizer`] searches the whole [`TabuMoveNeighborhood`] of a solution (and a
+/// tabu list) and returns the best non-tabu neighbor with new tabus.
+/// * In consecutive iterations of a tabu search, the current solution changes only a little, so
+///   most neighbors are regenerated with the same change of the objective value. Hence, the
+///   objective values of all neighbors are stored (keyed by the move) and reused in the next
+///   iteration for all moves that are
+///   [independent][TabuMoveNeighborhood::is_independent] of the applied move.
+/// * A reused objective value is estimated as `old neighbor value + current value - old value`,
+///   hence, the objective must be additive with respect to the independent parts of the
+///   solution (e.g., the length of a tour, where only the changed arcs matter).
+/// * Only the best move is applied and evaluated exactly, all other dependent moves are applied
+///   and evaluated as in the [`TabuMinimizer`][super::TabuMinimizer].
+/// * The memo is only reused if the given solution is the one returned in the previous call (as
+///   in the [`TabuSearchSolver`][super::super::TabuSearchSolver]), which is checked by comparing
+///   the solutions (hence, `S` must implement [`PartialEq`]). Otherwise (e.g., at the start of a
+///   new run, after a restart or if a tabu neighbor was accepted by the aspiration criterion),
+///   all moves are evaluated.
+/// * If all neighbors are tabu, `None` is returned.
+pub struct MemoizedTabuMinimizer<S, T, M> {
+    neighborhood: Arc<dyn TabuMoveNeighborhood<S, T, M>>,
+    objective: Arc<Objective<S>>,
+    memo: Mutex<Option<EvaluationMemo<S, M>>>,
+}
+
+impl<S, T, M> MemoizedTabuMinimizer<S, T, M> {
+    /// Creates a new [`MemoizedTabuMinimizer`] with the given [`TabuMoveNeighborhood`] and
+    /// [`Objective`].
+    pub fn new(
+        neighborhood: Arc<dyn TabuMoveNeighborhood<S, T, M>>,
+        objective: Arc<Objective<S>>,
+    ) -> Self {
+        Self {
+            neighborhood,
+            objective,
+            memo: Mutex::new(None),
+        }
+    }
+}
+
+impl<S: Clone + PartialEq, T, M: Clone + Eq + Hash> MemoizedTabuMinimizer<S, T, M> {
+    /// Returns the best of the non-tabu `moves` applied to the `solution` and the
+    /// [`NeighborCounts`]. Moves flagged as tabu are evaluated exactly and offered to the
+    /// `aspiration` (their objective values are memoized as well).
+    #[allow(clippy::type_complexity)]
+    fn best_neighbor(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        moves: impl Iterator<Item = (M, bool)>,
+        aspiration: Option<&Aspiration<S, T>>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let mut neighbor_counts = NeighborCounts::default();
+        let mut memo_guard = self.memo.lock().unwrap();
+        let previous_memo = memo_guard
+            .take()
+            .filter(|memo| memo.returned_solution == *solution.solution());
+
+        let mut neighbor_objective_values = HashMap::new();
+        let mut best_move: Option<(M, ObjectiveValue)> = None;
+        for (mv, is_tabu) in moves {
+            if is_tabu {
+                if let Some(aspiration) = aspiration {
+                    let (neighbor, new_tabus) = self.neighborhood.apply(solution.solution(), &mv);
+                    let neighbor = self.objective.evaluate(neighbor);
+                    neighbor_objective_values.insert(mv, neighbor.objective_value().clone());
+                    aspiration.offer(neighbor, new_tabus);
+                }
+                continue;
+            }
+            neighbor_counts.generated += 1;
+            let reused_objective_value = previous_memo.as_ref().and_then(|memo| {
+                if self.neighborhood.is_independent(&memo.applied_move, &mv) {
+                    memo.neighbor_objective_values.get(&mv).map(|old_value| {
+                        old_value.clone() + solution.objective_value().clone()
+                            - memo.previous_objective_value.clone()
+                    })
+                } else {
+                    None
+                }
+            });
+            let objective_value = reused_objective_value.unwrap_or_else(|| {
+                neighbor_counts.evaluated += 1;
+                let (neighbor, _) = self.neighborhood.apply(solution.solution(), &mv);
+                self.objective.evaluate(neighbor).objective_value().clone()
+            });
+            let is_better = match &best_move {
+                Some((_, best_objective_value)) => objective_value < *best_objective_value,
+                None => true,
+            };
+            if is_better {
+                best_move = Some((mv.clone(), objective_value.clone()));
+            }
+            neighbor_objective_values.insert(mv, objective_value);
+        }
+
+        let best_neighbor_with_new_tabus = match best_move {
+            Some((mv, _)) => {
+                let (neighbor, new_tabus) = self.neighborhood.apply(solution.solution(), &mv);
+                let best_neighbor = self.objective.evaluate(neighbor);
+                neighbor_counts.evaluated += 1;
+                neighbor_counts.accepted = 1;
+                *memo_guard = Some(EvaluationMemo {
+                    applied_move: mv,
+                    previous_objective_value: solution.objective_value().clone(),
+                    returned_solution: best_neighbor.solution().clone(),
+                    neighbor_objective_values,
+                });
+                Some((best_neighbor, new_tabus))
+            }
+            None => {
+                report!(Warning, "no swap possible.");
+                None
+            }
+        };
+        (best_neighbor_with_new_tabus, Some(neighbor_counts))
+    }
+}
+
+impl<S: Clone + PartialEq + Send, T, M: Clone + Eq + Hash + Send> TabuImprover<S, T>
+    for MemoizedTabuMinimizer<S, T, M>
+{
+    fn improve(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+        self.improve_counted(solution, tabu_list).0
+    }
+
+    /// Reused objective values count as generated but not as evaluated neighbors.
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.best_neighbor(
+            solution,
+            self.neighborhood
+                .moves_of(solution.solution(), tabu_list)
+                .map(|mv| (mv, false)),
+            None,
+        )
+    }
+
+    /// The tabu moves are always evaluated exactly (without reusing the memo).
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        if !aspiration.is_active() {
+            return self.improve_counted(solution, tabu_list);
+        }
+        self.best_neighbor(
+            solution,
+            self.neighborhood
+                .moves_with_tabu_status_of(solution.solution(), tabu_list),
+            Some(aspiration),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{BaseValue, Indicator};
+
+    struct Sum;
+
+    impl Indicator<Vec<i64>> for Sum {
+        fn evaluate(&self, solution: &Vec<i64>) -> BaseValue {
+            BaseValue::Integer(solution.iter().sum())
+        }
+
+        fn name(&self) -> String {
+            String::from("Sum")
+        }
+    }
+
+    /// Decreases or increases a single entry by one (the move is the index and the change).
+    struct ChangeEntryByOne;
+
+    impl TabuMoveNeighborhood<Vec<i64>, (), (usize, i64)> for ChangeEntryByOne {
+        fn moves_of<'a>(
+            &'a self,
+            solution: &'a Vec<i64>,
+            _tabu_list: &'a VecDeque<()>,
+        ) -> Box<dyn Iterator<Item = (usize, i64)> + Send + Sync + 'a> {
+            Box::new((0..solution.len()).flat_map(|index| [(index, -1), (index, 1)]))
+        }
+
+        fn apply(
+            &self,
+            solution: &Vec<i64>,
+            &(index, change): &(usize, i64),
+        ) -> (Vec<i64>, Vec<()>) {
+            let mut neighbor = solution.clone();
+            neighbor[index] += change;
+            (neighbor, Vec::new())
+        }
+
+        fn is_independent(&self, applied_move: &(usize, i64), mv: &(usize, i64)) -> bool {
+            applied_move.0 != mv.0
+        }
+    }
+
+    #[test]
+    fn test_memo_is_only_reused_for_the_returned_solution() {
+        let objective = Arc::new(Objective::new_single_indicator(Box::new(Sum)));
+        let improver = MemoizedTabuMinimizer::new(Arc::new(ChangeEntryByOne), objective.clone());
+        let tabu_list = VecDeque::new();
+
+        let (returned, counts) =
+            improver.improve_counted(&objective.evaluate(vec![5, 5]), &tabu_list);
+        let (returned, _) = returned.unwrap();
+        assert_eq!(*returned.solution(), vec![4, 5]);
+        assert_eq!(counts.unwrap().evaluated, 5);
+
+        // same objective value as the returned solution, but a different solution
+        let (returned, counts) =
+            improver.improve_counted(&objective.evaluate(vec![5, 4]), &tabu_list);
+        assert_eq!(*returned.unwrap().0.solution(), vec![4, 4]);
+        assert_eq!(counts.unwrap().evaluated, 5);
+
+        // the returned solution [4, 4] reuses the two moves that are independent of the applied
+        // move
+        let (returned, counts) =
+            improver.improve_counted(&objective.evaluate(vec![4, 4]), &tabu_list);
+        assert_eq!(*returned.unwrap().0.solution(), vec![3, 4]);
+        assert_eq!(counts.unwrap().evaluated, 3);
+    }
+}
@@ -1,9 +1,18 @@
 //! This module contains several [`TabuImprover`] implementation, which define the strategy to
 //! explore the neighborhood of a solution in each iteration of the
 //! [`TabuSearchSolver`][super::TabuSearchSolver].
+pub mod delta_tabu_minimizer;
+pub mod frequency_penalized_tabu_minimizer;
+pub mod memoized_tabu_minimizer;
+pub mod phased_tabu_minimizer;
 pub mod tabu_minimizer;
 
+use crate::heuristics::common::{Aspiration, NeighborCounts};
 use crate::objective::EvaluatedSolution;
+pub use delta_tabu_minimizer::DeltaTabuMinimizer;
+pub use frequency_penalized_tabu_minimizer::FrequencyPenalizedTabuMinimizer;
+pub use memoized_tabu_minimizer::MemoizedTabuMinimizer;
+pub use phased_tabu_minimizer::{ObjectivePhase, PhaseChange, PhaseListener, PhasedTabuMinimizer};
 use std::collections::VecDeque;
 pub use tabu_minimizer::TabuMinimizer;
 
@@ -23,4 +32,65 @@ pub trait TabuImprover<S, T>: Send + Sync {
         solution: &EvaluatedSolution<S>,
         tabu_list: &VecDeque<T>,
     ) -> Option<(EvaluatedSolution<S>, Vec<T>)>;
+
+    /// Same as [`improve`][TabuImprover::improve], but additionally returns the
+    /// [`NeighborCounts`] of the exploration, which the
+    /// [`TabuSearchSolver`][super::TabuSearchSolver] passes to the `function_between_steps`.
+    /// Neighbors that are tabu are not generated by the
+    /// [`TabuNeighborhood`][super::TabuNeighborhood], so they are not counted here.
+    /// The default implementation does not count the neighbors, i.e., it returns `None` as
+    /// counts.
+    #[allow(clippy::type_complexity)]
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        (self.improve(solution, tabu_list), None)
+    }
+
+    /// Same as [`improve_counted`][TabuImprover::improve_counted], but if the `aspiration` is
+    /// [active][Aspiration::is_active], the tabu neighbors are explored as well (see
+    /// [`neighbors_with_tabu_status_of`][super::TabuNeighborhood::neighbors_with_tabu_status_of])
+    /// and handed to the `aspiration` instead of being skipped. Hence, the
+    /// [`AspirationCriterion`][crate::heuristics::common::AspirationCriterion] is checked in the
+    /// same exploration.
+    /// * The returned neighbor and counts only cover the non-tabu neighbors, the
+    ///   [`TabuSearchSolver`][super::TabuSearchSolver] takes the tabu neighbors from the
+    ///   `aspiration`.
+    /// * The default implementation ignores the `aspiration`, i.e., the tabu list is strict.
+    #[allow(clippy::type_complexity)]
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        _aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.improve_counted(solution, tabu_list)
+    }
+
+    /// Returns the name of the improver, which is listed by
+    /// [`Solver::describe`][crate::heuristics::Solver::describe]. The default implementation
+    /// returns the name of the type (e.g., `"TabuMinimizer"`).
+    fn name(&self) -> String {
+        crate::heuristics::common::short_type_name::<Self>()
+    }
+
+    /// Returns the message of the panic the improver caught in the current run (see
+    /// [`PanicSafeImprover`][crate::heuristics::common::PanicSafeImprover]), such that the solver
+    /// reports [`Panicked`][crate::heuristics::common::TerminationReason::Panicked] instead of a
+    /// local optimum. The default implementation returns `None`.
+    fn panic_message(&self) -> Option<String> {
+        None
+    }
+
+    /// Forgets a panic caught in a previous run. The solver calls this at the start of each run.
+    /// The default implementation does nothing.
+    fn reset_panic(&self) {}
 }
@@ -0,0 +1,257 @@
+//! [`PhasedTabuMinimizer`] searches the whole [`TabuNeighborhood`] of a solution and returns the
+//! best non-tabu neighbor w.r.t. level weights that change according to a cyclic schedule of
+//! [`ObjectivePhases`][ObjectivePhase].
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU32, Ordering as AtomicOrdering},
+        Arc,
+    },
+};
+
+use crate::{
+    heuristics::{
+        common::{Aspiration, NeighborCounts},
+        tabu_search::TabuNeighborhood,
+    },
+    objective::{EvaluatedSolution, Objective, ObjectiveValue},
+};
+
+use super::TabuImprover;
+
+/// A phase of the schedule of a [`PhasedTabuMinimizer`], e.g., an intensification phase that
+/// focuses on the costs or a diversification phase that relaxes the constraint levels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectivePhase {
+    name: String,
+    iterations: u32,
+    level_weights: Vec<f64>,
+}
+
+impl ObjectivePhase {
+    /// Creates a new [`ObjectivePhase`] with the given `name` that lasts for `iterations`
+    /// iterations, in which the neighbors are compared by the sum of the levels of their
+    /// objective values multiplied with the `level_weights` (one weight per level).
+    /// * Panics if `iterations` is zero.
+    pub fn new(name: &str, iterations: u32, level_weights: Vec<f64>) -> ObjectivePhase {
+        assert!(
+            iterations > 0,
+            "A phase must last for at least one iteration."
+        );
+        ObjectivePhase {
+            name: String::from(name),
+            iterations,
+            level_weights,
+        }
+    }
+
+    /// Returns the name of the phase.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the weighted sum of the levels of the `objective_value` (see
+    /// [`BaseValue::to_f64`][crate::objective::BaseValue::to_f64]).
+    fn weighted_sum(&self, objective_value: &ObjectiveValue) -> f64 {
+        self.level_weights
+            .iter()
+            .zip(objective_value.iter())
+            .filter(|(weight, _)| **weight != 0.0)
+            .map(|(weight, value)| weight * value.to_f64())
+            .sum()
+    }
+}
+
+/// The event that is reported by a [`PhasedTabuMinimizer`] whenever a phase starts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhaseChange {
+    /// The iteration (i.e., the number of the call of the improver, starting at 1) in which the
+    /// phase starts.
+    pub iteration: u32,
+    /// The index of the phase in the schedule.
+    pub phase_index: usize,
+    /// The name of the phase.
+    pub phase_name: String,
+}
+
+/// Type for a function that is called with the [`PhaseChange`] whenever a phase starts.
+pub type PhaseListener = Box<dyn Fn(&PhaseChange) + Send + Sync>;
+
+/// [`PhasedTabuMinimizer`] searches the whole [`TabuNeighborhood`] of a solution (and a tabu
+/// list) and returns the best non-tabu neighbor with new tabus, where the neighbors are compared
+/// by the weighted sum of their levels according to the current [`ObjectivePhase`]
+/// (multi-phase tabu search, e.g., strategic oscillation between intensification and
+/// diversification).
+/// * The phases are cycled, i.e., after the last phase the first phase starts again.
+/// * Ties of the weighted sum are broken by the hierarchical [`Objective`].
+/// * The returned neighbor is evaluated by the [`Objective`] as usual, so the best solution seen
+///   by the solver is still determined by the hierarchical objective.
+/// * Whenever a phase starts, the [`PhaseListener`] (if any) is called and the phase change is
+///   reported as info.
+/// * The iterations are counted between calls, so a new instance should be used for each run.
+/// * If all neighbors are tabu, `None` is returned.
+pub struct PhasedTabuMinimizer<S, T> {
+    neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
+    objective: Arc<Objective<S>>,
+    phases: Vec<ObjectivePhase>,
+    phase_listener: Option<PhaseListener>,
+    iteration: AtomicU32,
+}
+
+impl<S, T> PhasedTabuMinimizer<S, T> {
+    /// Creates a new [`PhasedTabuMinimizer`] with the given [`TabuNeighborhood`], [`Objective`]
+    /// and schedule of [`ObjectivePhases`][ObjectivePhase].
+    /// * `phase_listener` is called whenever a phase starts. If `None`, the phase changes are
+    ///   only reported as info.
+    /// * Panics if `phases` is empty or if the number of level weights of a phase differs from
+    ///   the number of levels of the objective.
+    pub fn new(
+        neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
+        objective: Arc<Objective<S>>,
+        phases: Vec<ObjectivePhase>,
+        phase_listener: Option<PhaseListener>,
+    ) -> Self {
+        assert!(!phases.is_empty(), "At least one phase must be given.");
+        let number_of_levels = objective.zero().as_vec().len();
+        for phase in phases.iter() {
+            assert_eq!(
+                phase.level_weights.len(),
+                number_of_levels,
+                "Phase '{}' has {} level weights, but the objective has {} levels.",
+                phase.name,
+                phase.level_weights.len(),
+                number_of_levels
+            );
+        }
+        Self {
+            neighborhood,
+            objective,
+            phases,
+            phase_listener,
+            iteration: AtomicU32::new(0),
+        }
+    }
+
+    /// Returns the index of the phase of the given `iteration` (starting at 1) and whether the
+    /// phase starts in this iteration.
+    fn phase_of(&self, iteration: u32) -> (usize, bool) {
+        let cycle_length: u32 = self.phases.iter().map(|phase| phase.iterations).sum();
+        let mut offset = (iteration - 1) % cycle_length;
+        for (phase_index, phase) in self.phases.iter().enumerate() {
+            if offset < phase.iterations {
+                return (phase_index, offset == 0);
+            }
+            offset -= phase.iterations;
+        }
+        unreachable!()
+    }
+}
+
+impl<S, T> PhasedTabuMinimizer<S, T> {
+    /// Starts the next iteration (reporting a phase change) and returns the best of the non-tabu
+    /// `neighbors` with respect to the current phase and the [`NeighborCounts`].
+    #[allow(clippy::type_complexity)]
+    fn best_neighbor(
+        &self,
+        neighbors: impl Iterator<Item = (S, Vec<T>)>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let iteration = self.iteration.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        let (phase_index, phase_starts) = self.phase_of(iteration);
+        let phase = &self.phases[phase_index];
+        if phase_starts {
+            report!(
+                Info,
+                "Phase '{}' starts in iteration {}.",
+                phase.name,
+                iteration
+            );
+            if let Some(phase_listener) = &self.phase_listener {
+                phase_listener(&PhaseChange {
+                    iteration,
+                    phase_index,
+                    phase_name: phase.name.clone(),
+                });
+            }
+        }
+
+        let mut evaluated = 0;
+        let best_neighbor_with_new_tabus = neighbors
+            .map(|(neighbor, new_tabus)| {
+                evaluated += 1;
+                let neighbor = self.objective.evaluate(neighbor);
+                (
+                    phase.weighted_sum(neighbor.objective_value()),
+                    neighbor,
+                    new_tabus,
+                )
+            })
+            .min_by(|(sum1, s1, _), (sum2, s2, _)| {
+                sum1.partial_cmp(sum2)
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| self.objective.compare(s1, s2))
+            })
+            .map(|(_, neighbor, new_tabus)| (neighbor, new_tabus));
+        if best_neighbor_with_new_tabus.is_none() {
+            report!(Warning, "no swap possible.");
+        }
+
+        let neighbor_counts = NeighborCounts {
+            generated: evaluated,
+            evaluated,
+            accepted: u64::from(best_neighbor_with_new_tabus.is_some()),
+            ..NeighborCounts::default()
+        };
+        (best_neighbor_with_new_tabus, Some(neighbor_counts))
+    }
+}
+
+impl<S, T> TabuImprover<S, T> for PhasedTabuMinimizer<S, T> {
+    fn improve(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+        self.improve_counted(solution, tabu_list).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.best_neighbor(
+            self.neighborhood
+                .neighbors_of(solution.solution(), tabu_list),
+        )
+    }
+
+    /// The tabu neighbors are compared by the hierarchical [`Objective`] (not by the weighted sum
+    /// of the current phase).
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        if !aspiration.is_active() {
+            return self.improve_counted(solution, tabu_list);
+        }
+        self.best_neighbor(
+            self.neighborhood
+                .neighbors_with_tabu_status_of(solution.solution(), tabu_list)
+                .filter_map(|(neighbor, new_tabus, is_tabu)| {
+                    aspiration.filter_tabu(neighbor, new_tabus, is_tabu)
+                }),
+        )
+    }
+}
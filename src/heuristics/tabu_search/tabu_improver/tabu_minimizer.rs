@@ -3,8 +3,11 @@
 use std::{collections::VecDeque, sync::Arc};
 
 use crate::{
-    heuristics::tabu_search::TabuNeighborhood,
-    objective::{EvaluatedSolution, Objective},
+    heuristics::{
+        common::{Aspiration, NeighborCounts},
+        tabu_search::TabuNeighborhood,
+    },
+    objective::{EvaluatedSolution, Objective, SurrogateScreening},
 };
 
 use super::TabuImprover;
@@ -15,9 +18,13 @@ use super::TabuImprover;
 /// * Works for every solution type `S` and tabu type `T`.
 /// * Is fast if the computation and the evaluating of a neighbor is cheap.
 /// * If all neighbors are tabu, `None` is returned.
+/// * With a [`SurrogateScreening`] (see [`with_surrogate`][TabuMinimizer::with_surrogate]), only
+///   the best `top_k` non-tabu neighbors with respect to the cheap surrogate objective are
+///   evaluated by the exact objective.
 pub struct TabuMinimizer<S, T> {
     neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
     objective: Arc<Objective<S>>,
+    surrogate_screening: Option<SurrogateScreening<S>>,
 }
 
 impl<S, T> TabuMinimizer<S, T> {
@@ -29,29 +36,112 @@ impl<S, T> TabuMinimizer<S, T> {
         Self {
             neighborhood,
             objective,
+            surrogate_screening: None,
+        }
+    }
+
+    /// Creates a new [`TabuMinimizer`] with two-stage evaluation: All non-tabu neighbors are
+    /// evaluated by the cheap `surrogate_objective` and only the `top_k` best of them are
+    /// evaluated by the exact `objective` (see [`SurrogateScreening`]).
+    pub fn with_surrogate(
+        neighborhood: Arc<dyn TabuNeighborhood<S, T>>,
+        objective: Arc<Objective<S>>,
+        surrogate_objective: Arc<Objective<S>>,
+        top_k: usize,
+    ) -> Self {
+        Self {
+            neighborhood,
+            objective,
+            surrogate_screening: Some(SurrogateScreening::new(surrogate_objective, top_k)),
         }
     }
 }
 
-impl<S, T> TabuImprover<S, T> for TabuMinimizer<S, T> {
-    fn improve(
+impl<S, T> TabuMinimizer<S, T> {
+    /// Returns the best of the non-tabu `neighbors` (screened by the surrogate objective if
+    /// present) and the [`NeighborCounts`].
+    #[allow(clippy::type_complexity)]
+    fn best_neighbor(
         &self,
-        solution: &EvaluatedSolution<S>,
-        tabu_list: &VecDeque<T>,
-    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
-        let best_neighbor_with_new_tabus = self
-            .neighborhood
-            .neighbors_of(solution.solution(), tabu_list)
+        neighbors: impl Iterator<Item = (S, Vec<T>)>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        let mut generated = 0;
+        let mut evaluated = 0;
+        let neighbors = neighbors.inspect(|_| generated += 1);
+        let candidates: Box<dyn Iterator<Item = (S, Vec<T>)>> = match &self.surrogate_screening {
+            Some(surrogate_screening) => {
+                Box::new(surrogate_screening.screen(neighbors).into_iter())
+            }
+            None => Box::new(neighbors),
+        };
+        let best_neighbor_with_new_tabus = candidates
             .map(|(neighbor, new_tabus)| (self.objective.evaluate(neighbor), new_tabus))
+            .inspect(|_| evaluated += 1)
             .min_by(|(s1, _), (s2, _)| {
                 s1.objective_value()
                     .partial_cmp(s2.objective_value())
                     .unwrap()
             });
         if best_neighbor_with_new_tabus.is_none() {
-            println!("\x1b[31mwarning:\x1b[0m no swap possible.");
+            report!(Warning, "no swap possible.");
         }
 
-        best_neighbor_with_new_tabus
+        let neighbor_counts = NeighborCounts {
+            generated,
+            evaluated,
+            accepted: u64::from(best_neighbor_with_new_tabus.is_some()),
+            ..NeighborCounts::default()
+        };
+        (best_neighbor_with_new_tabus, Some(neighbor_counts))
+    }
+}
+
+impl<S, T> TabuImprover<S, T> for TabuMinimizer<S, T> {
+    fn improve(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> Option<(EvaluatedSolution<S>, Vec<T>)> {
+        self.improve_counted(solution, tabu_list).0
+    }
+
+    fn improve_counted(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        self.best_neighbor(
+            self.neighborhood
+                .neighbors_of(solution.solution(), tabu_list),
+        )
+    }
+
+    /// The tabu neighbors are evaluated by the exact objective (they are not screened by the
+    /// surrogate objective).
+    fn improve_with_aspiration(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        tabu_list: &VecDeque<T>,
+        aspiration: &Aspiration<S, T>,
+    ) -> (
+        Option<(EvaluatedSolution<S>, Vec<T>)>,
+        Option<NeighborCounts>,
+    ) {
+        if !aspiration.is_active() {
+            return self.improve_counted(solution, tabu_list);
+        }
+        self.best_neighbor(
+            self.neighborhood
+                .neighbors_with_tabu_status_of(solution.solution(), tabu_list)
+                .filter_map(|(neighbor, new_tabus, is_tabu)| {
+                    aspiration.filter_tabu(neighbor, new_tabus, is_tabu)
+                }),
+        )
     }
 }
@@ -2,38 +2,75 @@
 //! [threshold accpeting metaheuristic](https://doi.org/10.1016%2F0021-9991%2890%2990201-B).
 //! * Starts with an initial solution and iteratively considers neighbors.
 //! * An improvement is always accepted, but a worse neighbor is also accepted if the difference in objective value
-//! is below a given threshold.
-//! * After every step, in which a worse neighbor is accepted, the threshold is reduced by a factor.
+//!   is below a given threshold.
+//! * After every step, in which a worse neighbor is accepted, the threshold is reduced by a factor
+//!   (or according to another [`ThresholdSchedule`]).
+//! * In the [record-to-record travel][ThresholdAcceptingSolver::record_to_record_travel] variant,
+//!   a neighbor is accepted if it is at most a constant deviation worse than the best solution
+//!   seen (instead of the current solution).
 //! * The search stops after a certain number of iterations, after a certain time limit, or if the
-//! whole neighborhood is explored without any acceptance.
+//!   whole neighborhood is explored without any acceptance.
 //! * The best solution seen during this process is returned.
 //! * The threshold accepting heuristic is similar to the [simulated annealing
-//! heuristic][super::simulated_annealing], but deterministic and without
-//! computing the acceptance probability (which often contains costly computations of exponential functions).
+//!   heuristic][super::simulated_annealing], but deterministic and without
+//!   computing the acceptance probability (which often contains costly computations of exponential functions).
 //!
 //! For an example, see the [threshold accepting solver for the
 //! TSP][crate::examples::tsp::solvers::threshold_accepting].
+pub mod threshold_schedule;
 
-use super::common::{default_function_between_steps, FunctionBetweenSteps, Neighborhood};
+use super::common::SolverDescription;
+use super::common::{
+    default_function_between_steps, AcceptanceCriterion, AcceptanceDecision, AcceptanceLogger,
+    ConvergenceDetector, FunctionBetweenSteps, NeighborCounts, Neighborhood,
+};
+use super::common::{
+    report_termination, wind_down, SearchRecorder, SearchResult, TerminationCriteria,
+    TerminationReason,
+};
+use super::local_search::local_improver::{LocalImprover, TakeFirst};
 use super::Solver;
-use crate::objective::{EvaluatedSolution, Objective, ObjectiveValue};
+use crate::objective::{BaseValue, EvaluatedSolution, Objective, ObjectiveValue};
 use std::sync::Arc;
 use std::time as stdtime;
+use threshold_schedule::{ConstantThreshold, GeometricThreshold, ThresholdSchedule, ThresholdStep};
 
 /// Type for the `threshold_factor`.
 pub type ScalingFactor = f32;
 
+/// Defines to which solution the objective value of a neighbor is compared by the
+/// [`ThresholdAcceptingSolver`]: A neighbor is accepted if its objective value is smaller than
+/// the objective value of the reference solution plus the current threshold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AcceptanceReference {
+    /// The neighbor is compared to the current solution (default), i.e., classical threshold
+    /// accepting.
+    #[default]
+    CurrentSolution,
+    /// The neighbor is compared to the best solution seen so far, i.e., record-to-record travel.
+    BestSolutionSeen,
+}
+
 /// The threshold accepting solver uses a [`Neighborhood`], an [`Objective`], an
 /// `initial_threshold` ([`ObjectiveValue`]) and a `threshold_factor`
 /// (`f32` between 0 and 1, e.g., 0.9) to find a good solution,
 /// while occasionally accepting worse solutions with the hope to not get trapped within a bad local minimum.
-/// * Whenever a worse neighbor is accepted, the `current_threshold` is reduced by the `threshold_factor`.
+/// * Whenever a worse neighbor is accepted, the `current_threshold` is reduced by the
+///   `threshold_factor`, unless another [`ThresholdSchedule`] is provided.
+/// * By default, a neighbor is compared to the current solution. With
+///   [`AcceptanceReference::BestSolutionSeen`], it is compared to the best solution seen
+///   (record-to-record travel).
 /// * The `function_between_steps` is executed after each improvement step.
 /// * The default `function_between_steps` (if `None`) is printing the iteration number, the objective value
-/// (in comparison the the previous objective value) and the time elapsed since the start.
-/// * The solver stops after a certain number of iterations or after a certain time limit.
-/// * If `max_iterations` and `max_time` is `None`, the solver runs until a whole neighborhood is explored
-/// without any accpetance.
+///   (in comparison the the previous objective value) and the time elapsed since the start.
+/// * The solver stops if the [`TerminationCriteria`] (e.g., a number of iterations or a time
+///   limit) are met.
+/// * Without [`TerminationCriteria`], the solver runs until a whole neighborhood is explored
+///   without any accpetance.
+/// * Further options (e.g., a [`ThresholdSchedule`], a soft time limit) are set by the `with_*`
+///   methods.
+/// * [`solve_with_report`][Solver::solve_with_report] reports the number of iterations, the
+///   termination reason and the objective trajectory with the neighbor counts of each step.
 ///
 /// For a high-level overview, see the [module documentation][super::threshold_accepting] and for an example, see the
 /// [threshold accepting solver for the TSP][crate::examples::tsp::solvers::threshold_accepting].
@@ -41,10 +78,13 @@ pub struct ThresholdAcceptingSolver<S> {
     neighborhood: Arc<dyn Neighborhood<S>>,
     objective: Arc<Objective<S>>,
     initial_threshold: ObjectiveValue,
-    threshold_factor: ScalingFactor,
+    threshold_schedule: Box<dyn ThresholdSchedule>,
+    acceptance_reference: AcceptanceReference,
     function_between_steps: FunctionBetweenSteps<S>,
-    time_limit: Option<stdtime::Duration>,
-    iteration_limit: Option<u32>,
+    acceptance_logger: Option<Arc<AcceptanceLogger>>,
+    convergence_detector: Option<ConvergenceDetector>,
+    soft_time_limit: Option<stdtime::Duration>,
+    termination_criteria: Option<TerminationCriteria>,
 }
 
 impl<S> ThresholdAcceptingSolver<S> {
@@ -63,113 +103,313 @@ impl<S> ThresholdAcceptingSolver<S> {
             threshold_factor,
             None,
             None,
+        )
+    }
+
+    /// Creates a new [`ThresholdAcceptingSolver`] whose `initial_threshold` is the
+    /// `threshold_fraction` (e.g., 0.05) of the expected range of each hierarchy level, which is
+    /// derived from the declared [`bounds`][crate::objective::Indicator::bounds] of the indicators
+    /// (see [`Objective::level_ranges`]).
+    /// * The threshold of a level without declared bounds is zero.
+    pub fn initialize_from_bounds(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        threshold_fraction: f32,
+        threshold_factor: ScalingFactor,
+    ) -> Self {
+        let initial_threshold = ObjectiveValue::new(
+            objective
+                .level_ranges()
+                .into_iter()
+                .map(|range| range.unwrap_or(BaseValue::Zero))
+                .collect(),
+        ) * threshold_fraction;
+        Self::initialize(neighborhood, objective, initial_threshold, threshold_factor)
+    }
+
+    /// Creates a new [`ThresholdAcceptingSolver`] for the record-to-record travel variant with the
+    /// given [`Neighborhood`], [`Objective`] and `deviation`: A neighbor is accepted if its
+    /// objective value is smaller than the objective value of the best solution seen plus the
+    /// (constant) `deviation`.
+    /// * As the deviation never decreases, the search might not stop by itself. Hence, the
+    ///   `iteration_limit` is required.
+    pub fn record_to_record_travel(
+        neighborhood: Arc<dyn Neighborhood<S>>,
+        objective: Arc<Objective<S>>,
+        deviation: ObjectiveValue,
+        iteration_limit: u32,
+    ) -> Self {
+        Self::with_options(
+            neighborhood,
+            objective,
+            deviation,
+            1.0,
             None,
+            Some(TerminationCriteria::iteration_limit(iteration_limit)),
         )
+        .with_threshold_schedule(Box::new(ConstantThreshold))
+        .with_acceptance_reference(AcceptanceReference::BestSolutionSeen)
     }
 
     /// Creates a new [`ThresholdAcceptingSolver`] with the given [`Neighborhood`], [`Objective`],
     /// `initial_threshold` and `threshold_factor` (value between 0 and 1, e.g., 0.9).
     /// * `function_between_steps` is executed after each improvement step. If `None`, the default
-    /// is printing the iteration number, the objective value (in comparison the the previous
-    /// objective value) and the time elapsed since the start.
-    /// * `time_limit` is the maximum time allowed for the local search to start a new iteration.
-    /// The last iteration is allowed to finish. If `None`, there is no time limit.
-    /// * `iteration_limit` is the maximum number of iterations allowed for the local search. If
-    /// `None`, there is no iteration limit.
-    /// * If `max_iterations` and `max_time` is `None`, the solver runs until a whole neighborhood
-    /// is explored without any accpetance.
-    /// * If `max_iterations` and `max_time` are both set, the search stops when either limit is
-    /// reached first.
+    ///   is printing the iteration number, the objective value (in comparison the the previous
+    ///   objective value) and the time elapsed since the start.
+    /// * `termination_criteria` (see [`TerminationCriteria`]), e.g., a time limit, an iteration
+    ///   limit or a target objective value, are checked after each iteration with the best
+    ///   solution seen. The last iteration is allowed to finish.
+    /// * If `termination_criteria` is `None`, the solver runs until a whole neighborhood is
+    ///   explored without any accpetance.
+    /// * The threshold is multiplied by the `threshold_factor` whenever a worse neighbor is
+    ///   accepted (i.e., [`GeometricThreshold`]), unless another [`ThresholdSchedule`] is set by
+    ///   [`with_threshold_schedule`][ThresholdAcceptingSolver::with_threshold_schedule].
+    #[allow(clippy::too_many_arguments)]
     pub fn with_options(
         neighborhood: Arc<dyn Neighborhood<S>>,
         objective: Arc<Objective<S>>,
         initial_threshold: ObjectiveValue,
         threshold_factor: ScalingFactor,
         function_between_steps: Option<FunctionBetweenSteps<S>>,
-        time_limit: Option<stdtime::Duration>,
-        iteration_limit: Option<u32>,
+        termination_criteria: Option<TerminationCriteria>,
     ) -> Self {
         Self {
             neighborhood,
             objective,
             initial_threshold,
-            threshold_factor,
+            threshold_schedule: Box::new(GeometricThreshold::new(threshold_factor)),
+            acceptance_reference: AcceptanceReference::default(),
             function_between_steps: function_between_steps
                 .unwrap_or(default_function_between_steps()),
-            time_limit,
-            iteration_limit,
+            acceptance_logger: None,
+            convergence_detector: None,
+            soft_time_limit: None,
+            termination_criteria,
         }
     }
+
+    /// Replaces the [`GeometricThreshold`] with the `threshold_factor` by another
+    /// [`ThresholdSchedule`], which defines how the threshold changes after each iteration (see
+    /// [`threshold_schedule`]). The `threshold_factor` is ignored then.
+    pub fn with_threshold_schedule(
+        mut self,
+        threshold_schedule: Box<dyn ThresholdSchedule>,
+    ) -> Self {
+        self.threshold_schedule = threshold_schedule;
+        self
+    }
+
+    /// Defines whether a neighbor is compared to the current solution (default) or to the best
+    /// solution seen (see [`AcceptanceReference`]).
+    pub fn with_acceptance_reference(mut self, acceptance_reference: AcceptanceReference) -> Self {
+        self.acceptance_reference = acceptance_reference;
+        self
+    }
+
+    /// Logs every acceptance and rejection decision (rate-limited) for debugging (see
+    /// [`AcceptanceLogger`]).
+    pub fn with_acceptance_logger(mut self, acceptance_logger: Arc<AcceptanceLogger>) -> Self {
+        self.acceptance_logger = Some(acceptance_logger);
+        self
+    }
+
+    /// Stops the search as soon as the coefficient of variation of the best objective value over
+    /// the last iterations is below a threshold (see [`ConvergenceDetector`]).
+    pub fn with_convergence_detector(mut self, convergence_detector: ConvergenceDetector) -> Self {
+        self.convergence_detector = Some(convergence_detector);
+        self
+    }
+
+    /// After `soft_time_limit`, no worse neighbors are accepted anymore (i.e., threshold 0) and
+    /// the best solution seen is improved by a [`TakeFirst`] descent until a local optimum is
+    /// reached or the (hard) time limit of the [`TerminationCriteria`] is exceeded.
+    pub fn with_soft_time_limit(mut self, soft_time_limit: stdtime::Duration) -> Self {
+        self.soft_time_limit = Some(soft_time_limit);
+        self
+    }
 }
 
-impl<S: Clone> Solver<S> for ThresholdAcceptingSolver<S> {
-    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+impl<S: Clone> ThresholdAcceptingSolver<S> {
+    /// Runs the threshold accepting, where each step is passed to the `function_between_steps`
+    /// by a [`SearchRecorder`], which additionally records the trajectory if `record` is `true`.
+    fn search(&self, initial_solution: S, record: bool) -> SearchResult<S> {
         let start_time = stdtime::Instant::now();
         let mut current_solution = self.objective.evaluate(initial_solution);
         let mut best_solution_seen = current_solution.clone();
         let mut current_threshold: ObjectiveValue = self.initial_threshold.clone();
 
-        let mut iteration_counter = 1;
+        let mut convergence_detector = self.convergence_detector.clone();
+        let mut termination_criteria = self.termination_criteria.clone();
+        let time_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_time_limit);
+        let iteration_limit = termination_criteria
+            .as_ref()
+            .and_then(TerminationCriteria::effective_iteration_limit);
+        let mut recorder = SearchRecorder::new(
+            &self.function_between_steps,
+            self.objective.clone(),
+            start_time,
+            time_limit,
+            iteration_limit,
+            current_solution.objective_value(),
+            record,
+        );
+        let mut iteration_counter = 0;
+        let mut termination_reason = TerminationReason::LocalOptimum;
 
-        while let Some(new_solution) =
-            self.explore_neihborhood(&current_solution, &current_threshold)
+        while let Some((new_solution, neighbor_counts)) =
+            self.explore_neihborhood(&current_solution, &best_solution_seen, &current_threshold)
         {
-            (self.function_between_steps)(
+            iteration_counter += 1;
+            recorder.step(
                 iteration_counter,
                 &new_solution,
                 Some(&current_solution),
-                self.objective.clone(),
-                Some(start_time),
-                self.time_limit,
-                self.iteration_limit,
+                Some(neighbor_counts),
             );
 
-            if new_solution.objective_value() >= current_solution.objective_value() {
-                current_threshold = current_threshold * self.threshold_factor;
-                println!("New threshold:");
-                self.objective.print_objective_value(&current_threshold);
+            let new_threshold = self.threshold_schedule.next_threshold(
+                &current_threshold,
+                &ThresholdStep {
+                    iteration: iteration_counter,
+                    initial_threshold: &self.initial_threshold,
+                    accepted_worse: new_solution.objective_value()
+                        >= current_solution.objective_value(),
+                },
+            );
+            if new_threshold != current_threshold {
+                current_threshold = new_threshold;
+                report!(
+                    Info,
+                    "New threshold:\n{}",
+                    self.objective.format_objective_value(&current_threshold)
+                );
             }
 
             current_solution = new_solution;
-            if current_solution.objective_value() < best_solution_seen.objective_value() {
+            if self
+                .objective
+                .is_better(&current_solution, &best_solution_seen)
+            {
                 best_solution_seen = current_solution.clone();
             }
-            if let Some(time_limit) = self.time_limit {
-                if stdtime::Instant::now().duration_since(start_time) > time_limit {
-                    println!("Time limit reached.");
-                    break;
+            if let Some(reason) = termination_criteria.as_mut().and_then(|criteria| {
+                criteria.is_met(
+                    iteration_counter,
+                    start_time.elapsed(),
+                    best_solution_seen.objective_value(),
+                )
+            }) {
+                report_termination(reason);
+                termination_reason = reason;
+                break;
+            }
+            if let Some(soft_time_limit) = self.soft_time_limit {
+                if stdtime::Instant::now().duration_since(start_time) > soft_time_limit {
+                    let take_first =
+                        TakeFirst::new(self.neighborhood.clone(), self.objective.clone());
+                    let (best_solution, iterations, termination_reason) = wind_down(
+                        &mut recorder,
+                        best_solution_seen,
+                        |current_solution, deadline| {
+                            take_first.improve_counted(current_solution, deadline)
+                        },
+                        iteration_counter,
+                        start_time,
+                        time_limit,
+                    );
+                    return recorder.finish(best_solution, iterations, termination_reason);
                 }
             }
-            if let Some(iteration_limit) = self.iteration_limit {
-                if iteration_counter >= iteration_limit {
-                    println!("Iteration limit reached.");
+            if let Some(convergence_detector) = convergence_detector.as_mut() {
+                if convergence_detector.record(best_solution_seen.objective_value()) {
+                    report!(Info, "Convergence detected.");
+                    termination_reason = TerminationReason::Converged;
                     break;
                 }
             }
-            iteration_counter += 1;
         }
 
-        best_solution_seen
+        recorder.finish(best_solution_seen, iteration_counter, termination_reason)
+    }
+}
+
+impl<S: Clone> Solver<S> for ThresholdAcceptingSolver<S> {
+    fn solve(&self, initial_solution: S) -> EvaluatedSolution<S> {
+        self.search(initial_solution, false).best_solution
+    }
+
+    fn solve_with_report(&self, initial_solution: S) -> SearchResult<S> {
+        self.search(initial_solution, true)
+    }
+
+    fn describe(&self) -> SolverDescription {
+        SolverDescription::new("ThresholdAcceptingSolver")
+            .with_objective_value("initial_threshold", Some(&self.initial_threshold))
+            .with_parameter("threshold_schedule", self.threshold_schedule.name())
+            .with_parameter(
+                "acceptance_reference",
+                format!("{:?}", self.acceptance_reference),
+            )
+            .with_parameter("convergence_detector", self.convergence_detector.is_some())
+            .with_duration("soft_time_limit", self.soft_time_limit)
+            .with_optional_parameter(
+                "termination_criteria",
+                self.termination_criteria
+                    .as_ref()
+                    .map(|criteria| format!("{:?}", criteria)),
+            )
     }
 }
 
 impl<S> ThresholdAcceptingSolver<S> {
+    /// Returns the first accepted neighbor together with the [`NeighborCounts`] of the
+    /// exploration. All evaluated neighbors except the accepted one are rejected by the threshold.
     fn explore_neihborhood(
         &self,
         current_solution: &EvaluatedSolution<S>,
+        best_solution_seen: &EvaluatedSolution<S>,
         current_threshold: &ObjectiveValue,
-    ) -> Option<EvaluatedSolution<S>> {
-        self.neighborhood
+    ) -> Option<(EvaluatedSolution<S>, NeighborCounts)> {
+        let reference_solution = match self.acceptance_reference {
+            AcceptanceReference::CurrentSolution => current_solution,
+            AcceptanceReference::BestSolutionSeen => best_solution_seen,
+        };
+        let mut evaluated = 0;
+        let accepted_neighbor = self
+            .neighborhood
             .neighbors_of(current_solution.solution())
             .find_map(|neighbor| {
+                evaluated += 1;
                 let neighbor_solution = self.objective.evaluate(neighbor);
-                if neighbor_solution.objective_value().clone()
-                    < current_solution.objective_value().clone() + current_threshold.clone()
-                {
+                let accepted = neighbor_solution.objective_value().clone()
+                    < reference_solution.objective_value().clone() + current_threshold.clone();
+                if let Some(acceptance_logger) = &self.acceptance_logger {
+                    acceptance_logger.log(&AcceptanceDecision {
+                        accepted,
+                        current_objective_value: current_solution.objective_value(),
+                        neighbor_objective_value: neighbor_solution.objective_value(),
+                        criterion: AcceptanceCriterion::Threshold {
+                            threshold: current_threshold.clone(),
+                        },
+                    });
+                }
+                if accepted {
                     Some(neighbor_solution)
                 } else {
                     None
                 }
-            })
+            });
+        accepted_neighbor.map(|neighbor_solution| {
+            let neighbor_counts = NeighborCounts {
+                generated: evaluated,
+                evaluated,
+                rejected_as_tabu: 0,
+                rejected_by_threshold: evaluated - 1,
+                accepted: 1,
+            };
+            (neighbor_solution, neighbor_counts)
+        })
     }
 }
@@ -0,0 +1,146 @@
+//! This module contains the [`ThresholdSchedule`] trait, which defines how the threshold of the
+//! [`ThresholdAcceptingSolver`][super::ThresholdAcceptingSolver] changes.
+//! There are three threshold schedules to choose from:
+//! * [`GeometricThreshold`]: multiplies the threshold by a factor whenever a worse neighbor is
+//!   accepted (this is the default, using the `threshold_factor` of the solver).
+//! * [`LinearThreshold`]: subtracts a constant whenever a worse neighbor is accepted.
+//! * [`ConstantThreshold`]: never changes the threshold (e.g., for record-to-record travel).
+use crate::objective::{BaseValue, ObjectiveValue};
+
+use super::ScalingFactor;
+
+/// The information about the last iteration that is passed to a [`ThresholdSchedule`].
+#[derive(Clone, Copy, Debug)]
+pub struct ThresholdStep<'a> {
+    /// The iteration (starting with 1).
+    pub iteration: u32,
+    /// The initial threshold of the solver.
+    pub initial_threshold: &'a ObjectiveValue,
+    /// `true` if the accepted neighbor is not better than the previous solution.
+    pub accepted_worse: bool,
+}
+
+/// Defines how the threshold of the [`ThresholdAcceptingSolver`][super::ThresholdAcceptingSolver]
+/// changes after each iteration.
+pub trait ThresholdSchedule: Send + Sync {
+    /// Returns the threshold for the next iteration given the current `threshold` and the
+    /// information about the last iteration.
+    fn next_threshold(&self, threshold: &ObjectiveValue, step: &ThresholdStep) -> ObjectiveValue;
+
+    /// Returns the name of the threshold schedule, which is listed by
+    /// [`Solver::describe`][crate::heuristics::Solver::describe]. The default implementation
+    /// returns the name of the type (e.g., `"GeometricThreshold"`).
+    fn name(&self) -> String {
+        crate::heuristics::common::short_type_name::<Self>()
+    }
+}
+
+/// Multiplies the threshold by the `threshold_factor` (between 0 and 1) whenever a worse neighbor
+/// is accepted.
+pub struct GeometricThreshold {
+    threshold_factor: ScalingFactor,
+}
+
+impl GeometricThreshold {
+    /// Creates a new [`GeometricThreshold`] with the given `threshold_factor` (e.g., 0.9).
+    pub fn new(threshold_factor: ScalingFactor) -> GeometricThreshold {
+        GeometricThreshold { threshold_factor }
+    }
+}
+
+impl ThresholdSchedule for GeometricThreshold {
+    fn next_threshold(&self, threshold: &ObjectiveValue, step: &ThresholdStep) -> ObjectiveValue {
+        if step.accepted_worse {
+            threshold.clone() * self.threshold_factor
+        } else {
+            threshold.clone()
+        }
+    }
+}
+
+/// Subtracts the `decrement` from the threshold whenever a worse neighbor is accepted. Each level
+/// of the threshold never drops below zero.
+pub struct LinearThreshold {
+    decrement: ObjectiveValue,
+}
+
+impl LinearThreshold {
+    /// Creates a new [`LinearThreshold`] with the given `decrement`, e.g., the initial threshold
+    /// divided by the expected number of accepted worse neighbors.
+    pub fn new(decrement: ObjectiveValue) -> LinearThreshold {
+        LinearThreshold { decrement }
+    }
+}
+
+impl ThresholdSchedule for LinearThreshold {
+    fn next_threshold(&self, threshold: &ObjectiveValue, step: &ThresholdStep) -> ObjectiveValue {
+        if !step.accepted_worse {
+            return threshold.clone();
+        }
+        ObjectiveValue::new(
+            threshold
+                .iter()
+                .zip(self.decrement.iter())
+                .map(|(&value, &decrement)| {
+                    if value <= decrement {
+                        BaseValue::Zero
+                    } else {
+                        value - decrement
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Keeps the threshold constant, e.g., for
+/// [record-to-record travel][super::ThresholdAcceptingSolver::record_to_record_travel], where the
+/// threshold is the allowed deviation from the best solution seen.
+pub struct ConstantThreshold;
+
+impl ThresholdSchedule for ConstantThreshold {
+    fn next_threshold(&self, threshold: &ObjectiveValue, _step: &ThresholdStep) -> ObjectiveValue {
+        threshold.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_schedules() {
+        let initial_threshold = ObjectiveValue::new(vec![BaseValue::Float(100.0)]);
+        let step = |accepted_worse: bool| ThresholdStep {
+            iteration: 1,
+            initial_threshold: &initial_threshold,
+            accepted_worse,
+        };
+        let threshold = |value: f64| ObjectiveValue::new(vec![BaseValue::Float(value)]);
+
+        let geometric = GeometricThreshold::new(0.5);
+        assert_eq!(
+            geometric.next_threshold(&threshold(10.0), &step(true)),
+            threshold(5.0)
+        );
+        assert_eq!(
+            geometric.next_threshold(&threshold(10.0), &step(false)),
+            threshold(10.0)
+        );
+
+        let linear = LinearThreshold::new(threshold(4.0));
+        assert_eq!(
+            linear.next_threshold(&threshold(10.0), &step(true)),
+            threshold(6.0)
+        );
+        assert_eq!(
+            linear.next_threshold(&threshold(3.0), &step(true)),
+            ObjectiveValue::new(vec![BaseValue::Zero])
+        );
+
+        assert_eq!(
+            ConstantThreshold.next_threshold(&threshold(10.0), &step(true)),
+            threshold(10.0)
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! This module contains a loader for CSV-based instance data with typed records, in particular
+//! with [`DateTime`] and [`Duration`] columns (parsed by [`rapid_time`](https://docs.rs/rapid_time/)).
+//! * Implement [`FromCsvRecord`] for your record type and use [`load_csv`] (or [`parse_csv`] for
+//!   in-memory data) to get a vector of records.
+//! * The first non-empty line is the header. Columns are accessed by their name, so the order
+//!   of the columns does not matter.
+//! * Fields are separated by commas and surrounding whitespace is trimmed. A field can be enclosed
+//!   in double quotes, but quoted fields must not contain commas.
+//!
+//! ```rust
+//! use rapid_solve::io::{parse_csv, CsvError, CsvRecord, FromCsvRecord};
+//! use rapid_time::{DateTime, Duration};
+//!
+//! struct Shift {
+//!     start: DateTime,
+//!     length: Duration,
+//!     demand: u32,
+//! }
+//!
+//! impl FromCsvRecord for Shift {
+//!     fn from_csv_record(record: &CsvRecord) -> Result<Self, CsvError> {
+//!         Ok(Shift {
+//!             start: record.date_time("start")?,
+//!             length: record.duration("length")?,
+//!             demand: record.parse("demand")?,
+//!         })
+//!     }
+//! }
+//!
+//! let shifts: Vec<Shift> = parse_csv(
+//!     "start,length,demand\n\
+//!      2024-02-28T06:00,08:00,3\n\
+//!      2024-02-28T14:00,08:00,2",
+//! )
+//! .unwrap();
+//! assert_eq!(shifts[1].start, DateTime::new("2024-02-28T14:00:00"));
+//! assert_eq!(shifts[0].start + shifts[0].length, shifts[1].start);
+//! assert_eq!(shifts[0].demand, 3);
+//! ```
+//!
+//! Requires the `rapid_time` and the `std` feature.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use rapid_time::{DateTime, Duration};
+
+/// Error that is returned by [`load_csv`] and [`parse_csv`].
+#[derive(Debug)]
+pub enum CsvError {
+    /// The file cannot be read.
+    Io(std::io::Error),
+    /// The data does not contain a header line.
+    MissingHeader,
+    /// A line has a different number of fields than the header.
+    WrongNumberOfFields {
+        /// The line number (starting with 1).
+        line: usize,
+        /// The number of columns of the header.
+        expected: usize,
+        /// The number of fields in the line.
+        found: usize,
+    },
+    /// The requested column is not in the header.
+    MissingColumn {
+        /// The name of the column.
+        column: String,
+    },
+    /// The value cannot be parsed.
+    InvalidValue {
+        /// The line number (starting with 1).
+        line: usize,
+        /// The name of the column.
+        column: String,
+        /// The given value.
+        value: String,
+    },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Io(error) => write!(f, "Cannot read CSV file: {}", error),
+            CsvError::MissingHeader => write!(f, "The CSV data does not contain a header."),
+            CsvError::WrongNumberOfFields {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Line {} has {} fields, but the header has {} columns.",
+                line, found, expected
+            ),
+            CsvError::MissingColumn { column } => {
+                write!(f, "The CSV data does not contain the column '{}'.", column)
+            }
+            CsvError::InvalidValue {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "Invalid value '{}' in column '{}' of line {}.",
+                value, column, line
+            ),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CsvError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CsvError {
+    fn from(error: std::io::Error) -> Self {
+        CsvError::Io(error)
+    }
+}
+
+/// A line of the CSV data, whose fields are accessed by the column names of the header.
+pub struct CsvRecord<'a> {
+    header: &'a [&'a str],
+    fields: Vec<&'a str>,
+    line: usize,
+}
+
+impl CsvRecord<'_> {
+    /// Returns the line number of the record (starting with 1 for the header).
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the raw (trimmed and unquoted) value of the `column`.
+    pub fn get(&self, column: &str) -> Result<&str, CsvError> {
+        self.header
+            .iter()
+            .position(|name| *name == column)
+            .map(|index| self.fields[index])
+            .ok_or_else(|| CsvError::MissingColumn {
+                column: column.to_string(),
+            })
+    }
+
+    /// Parses the value of the `column` into `T`.
+    pub fn parse<T: FromStr>(&self, column: &str) -> Result<T, CsvError> {
+        let value = self.get(column)?;
+        value.parse().map_err(|_| self.invalid_value(column, value))
+    }
+
+    /// Parses the value of the `column` as [`DateTime`] (format `2009-06-15T13:45:13` or
+    /// `2009-06-15T13:45`; `EARLIEST` and `LATEST` are also accepted).
+    pub fn date_time(&self, column: &str) -> Result<DateTime, CsvError> {
+        let value = self.get(column)?;
+        match value {
+            "EARLIEST" => Ok(DateTime::Earliest),
+            "LATEST" => Ok(DateTime::Latest),
+            _ if is_valid_date_time(value) => Ok(DateTime::new(value)),
+            _ => Err(self.invalid_value(column, value)),
+        }
+    }
+
+    /// Parses the value of the `column` as [`Duration`] (format `hh:mm` or `hh:mm:ss`; `INF`
+    /// is also accepted).
+    pub fn duration(&self, column: &str) -> Result<Duration, CsvError> {
+        let value = self.get(column)?;
+        match value {
+            "INF" => Ok(Duration::Infinity),
+            _ if is_valid_duration(value) => Ok(Duration::new(value)),
+            _ => Err(self.invalid_value(column, value)),
+        }
+    }
+
+    fn invalid_value(&self, column: &str, value: &str) -> CsvError {
+        CsvError::InvalidValue {
+            line: self.line,
+            column: column.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// A record type that can be built from a [`CsvRecord`].
+pub trait FromCsvRecord: Sized {
+    /// Builds the record from the fields of a CSV line.
+    fn from_csv_record(record: &CsvRecord) -> Result<Self, CsvError>;
+}
+
+/// Reads the CSV file at `path` and builds a record of type `T` for each line after the header.
+pub fn load_csv<T: FromCsvRecord>(path: impl AsRef<Path>) -> Result<Vec<T>, CsvError> {
+    parse_csv(&fs::read_to_string(path)?)
+}
+
+/// Parses the CSV data and builds a record of type `T` for each line after the header. Empty
+/// lines are skipped.
+pub fn parse_csv<T: FromCsvRecord>(content: &str) -> Result<Vec<T>, CsvError> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+    let header: Vec<&str> = match lines.next() {
+        Some((_, line)) => split_fields(line),
+        None => return Err(CsvError::MissingHeader),
+    };
+    lines
+        .map(|(line, content)| {
+            let fields = split_fields(content);
+            if fields.len() != header.len() {
+                return Err(CsvError::WrongNumberOfFields {
+                    line,
+                    expected: header.len(),
+                    found: fields.len(),
+                });
+            }
+            T::from_csv_record(&CsvRecord {
+                header: &header,
+                fields,
+                line,
+            })
+        })
+        .collect()
+}
+
+fn split_fields(line: &str) -> Vec<&str> {
+    line.split(',')
+        .map(|field| {
+            let field = field.trim();
+            field
+                .strip_prefix('"')
+                .and_then(|field| field.strip_suffix('"'))
+                .unwrap_or(field)
+        })
+        .collect()
+}
+
+/// Checks the format that is expected by [`DateTime::new`] (which panics otherwise).
+fn is_valid_date_time(value: &str) -> bool {
+    let parts: Vec<&str> = value
+        .trim_end_matches('Z')
+        .split(&['T', '-', ' ', ':'][..])
+        .collect();
+    if !(5..=6).contains(&parts.len()) {
+        return false;
+    }
+    let numbers: Option<Vec<u32>> = parts.iter().map(|part| part.parse().ok()).collect();
+    let Some(numbers) = numbers else {
+        return false;
+    };
+    let (year, month, day) = (numbers[0], numbers[1], numbers[2]);
+    let days_of_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    (1..=12).contains(&month)
+        && (1..=days_of_month).contains(&day)
+        && numbers[3] <= 24
+        && numbers[4] < 60
+        && numbers.get(5).is_none_or(|second| *second < 60)
+}
+
+/// Checks the format that is expected by [`Duration::new`] (which panics otherwise).
+fn is_valid_duration(value: &str) -> bool {
+    let parts: Vec<&str> = value.split(':').collect();
+    (2..=3).contains(&parts.len())
+        && parts[0].parse::<u64>().is_ok()
+        && parts[1..]
+            .iter()
+            .all(|part| part.parse::<u8>().is_ok_and(|number| number < 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Task {
+        name: String,
+        release: DateTime,
+        processing_time: Duration,
+    }
+
+    impl FromCsvRecord for Task {
+        fn from_csv_record(record: &CsvRecord) -> Result<Self, CsvError> {
+            Ok(Task {
+                name: record.get("name")?.to_string(),
+                release: record.date_time("release")?,
+                processing_time: record.duration("processing_time")?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let tasks: Vec<Task> = parse_csv(
+            "processing_time, name, release\n\
+             01:30, \"a\", 2024-02-29T08:00\n\
+             \n\
+             INF, b, EARLIEST\n",
+        )
+        .unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].name, "a");
+        assert_eq!(tasks[0].release, DateTime::new("2024-02-29T08:00:00"));
+        assert_eq!(tasks[0].processing_time, Duration::from_seconds(5400));
+        assert_eq!(tasks[1].release, DateTime::Earliest);
+        assert_eq!(tasks[1].processing_time, Duration::Infinity);
+
+        let error = parse_csv::<Task>("name,release,processing_time\na,2023-02-29T08:00,01:00")
+            .unwrap_err();
+        assert!(matches!(error, CsvError::InvalidValue { line: 2, .. }));
+        assert!(matches!(
+            parse_csv::<Task>("name,release\na,2023-02-28T08:00"),
+            Err(CsvError::MissingColumn { .. })
+        ));
+        assert!(matches!(
+            parse_csv::<Task>("name,release,processing_time\na,b"),
+            Err(CsvError::WrongNumberOfFields { .. })
+        ));
+        assert!(matches!(
+            parse_csv::<Task>(""),
+            Err(CsvError::MissingHeader)
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 #![warn(missing_docs)]
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
 //! This library provides a metaheuristic framework for solving combinatorial optimization
 //! problems.
 //!
@@ -10,9 +11,24 @@
 //! - [parallel local search][heuristics::parallel_local_search] (the neighborhood is explored in
 //!   parallel using [`rayon`](https://docs.rs/rayon/), supports recursion)
 //! - [threshold accepting][heuristics::threshold_accepting]
-//! - [simulated annealing][heuristics::simulated_annealing]
+//! - [great deluge][heuristics::great_deluge] (with water levels per hierarchy level)
+//! - [simulated annealing][heuristics::simulated_annealing] (and a [parallel
+//!   version][heuristics::parallel_simulated_annealing] with several interacting chains)
 //! - [tabu search][heuristics::tabu_search] (and a faster [parallel
 //!   version][heuristics::parallel_tabu_search])
+//! - [iterated local search][heuristics::iterated_local_search] (with user-defined
+//!   perturbations)
+//! - [large neighborhood search][heuristics::large_neighborhood_search] (with user-defined
+//!   destroy and repair operators)
+//! - [memetic algorithm][heuristics::memetic] (a genetic algorithm with user-defined crossover
+//!   and mutation, whose offspring are improved by a local search)
+//! - [Pareto local search][heuristics::pareto_local_search] (keeps an archive of non-dominated
+//!   solutions w.r.t. selected objective levels)
+//! - [NSGA-II][heuristics::nsga2] (evolutionary multi-objective algorithm on several indicators
+//!   with non-dominated sorting and crowding distance)
+//!
+//! Initial solutions can be built with the [construction heuristics][heuristics::construction]
+//! (e.g., a greedy randomized construction with restricted candidate list).
 //!
 //! ### Hierarchical Objective
 //! The framework supports [hierarchical objective][objective], i.e., objectives
@@ -22,10 +38,13 @@
 //! measure), such that normally
 //! infeasible solutions are considered feasible. The solver than minimizes these constraints first
 //! until the violation is zero and then starts to optimize the remaining objective levels.
+//! The [`ObjectiveBuilder`][objective::ObjectiveBuilder] places such constraint levels in front of the soft objective levels
+//! and allows to check whether a solution is feasible.
 //!
 //! ### Examples
 //! As an example we provide a simple implementation of the [Traveling Salesman Problem
-//! (TSP)][examples::tsp] with the 3-opt neighborhood.
+//! (TSP)][examples::tsp] with the 3-opt neighborhood and a [shift scheduling
+//! problem][examples::shift_scheduling], whose objective consists of durations.
 //!
 //! # How to use this library (step-by-step example)
 //! Suppose you have given a combinatorial optimization problem and defined a solution type.
@@ -37,7 +56,7 @@
 //!    instead a modified clone should be returned.
 //! 3. Implement the [`Neighborhood`][heuristics::common::Neighborhood] for the local search.
 //! 4. Initialize the [`LocalSearchSolver`][heuristics::local_search::LocalSearchSolver]
-//! and run it.
+//!    and run it.
 //!
 //! We demonstrate these steps on a simple (but totally artificial) example, where the solution type
 //! consists of a fixed-size vector of integers.
@@ -231,6 +250,8 @@
 //! #         Box::new(change_entry.chain(swap))
 //! #     }
 //! # }
+//! # #[cfg(feature = "std")]
+//! # {
 //! use rapid_solve::heuristics::Solver;
 //! use rapid_solve::heuristics::local_search::LocalSearchSolver;
 //! use std::sync::Arc;
@@ -251,10 +272,96 @@
 //!     vec![1, 0, 2, 4, 5, 7, 9, 8, 6, 3]
 //! );
 //! // one global optimum is [0, 2, 4, 6, 8, 9, 7, 5, 3, 1] with a squared differences of 34.
+//! # }
 //! ```
 //!
-//! For a more less artificial demonstration, we refer to the [tsp-example][examples::tsp].
+//! This example is also implemented in the [toy example][examples::toy] (with builders for every
+//! solver of this crate). For a more less artificial demonstration, we refer to the
+//! [tsp-example][examples::tsp].
+//!
+//! # Features
+//! The default features are `std`, `rayon`, `serde_json`, `rand`, `rapid_time` and `examples`;
+//! all other features are opt-in. Users who only need the sequential solvers can disable the
+//! default features to slim down the dependency tree.
+//! - `std`: the solvers, the time measurement, the [`progress`] reporting, the [`prelude`] and
+//!   the [`io`] module. Without it, the crate is `no_std` (only `alloc` is required), e.g., for
+//!   small scheduling problems on embedded targets: only the [objective] and the sequential
+//!   [local search loop][heuristics::local_search::local_search_loop] (with the
+//!   [`LocalImprovers`][heuristics::local_search::local_improver::LocalImprover]) are available,
+//!   there is no time measurement and no printing. All other features except `rapid_time` enable
+//!   `std`.
+//! - `rayon`: the parallel execution of the parallel solvers ([parallel local
+//!   search][heuristics::parallel_local_search], [parallel tabu
+//!   search][heuristics::parallel_tabu_search]) and the parallel neighborhoods. Without it, the
+//!   parallel solvers are still available but run sequentially (see the [`parallel`] module),
+//!   such that downstream crates need only one code path.
+//! - `rand`: the [simulated annealing][heuristics::simulated_annealing], the [iterated local
+//!   search][heuristics::iterated_local_search], the [large neighborhood
+//!   search][heuristics::large_neighborhood_search], the [memetic algorithm][heuristics::memetic]
+//!   and the [construction heuristics][heuristics::construction].
+//! - `serde_json`: the JSON export of objective values.
+//! - `rapid_time`: durations as [`BaseValue::Duration`][objective::BaseValue::Duration] (using
+//!   [`rapid_time`](https://docs.rs/rapid_time/)) and the CSV loader of the [`io`] module.
+//! - `checkpoint`: periodic checkpoints of the [tabu search][heuristics::tabu_search] (using
+//!   [`serde`](https://docs.rs/serde/)), such that a crashed run can be resumed (not enabled by
+//!   default).
+//! - `external_evaluator`: the `ExternalEvaluator` and the `ExternalIndicator` of the
+//!   [objective] module, which delegate the evaluation of solutions to an external process (e.g.,
+//!   an objective written in another language) via JSON lines over stdin/stdout or a Unix socket
+//!   (not enabled by default).
+//! - `evaluation_cache`: a concurrent evaluation cache (using
+//!   [`dashmap`](https://docs.rs/dashmap/)) shared by the threads of the `TakeAnyRecursion`
+//!   improver, such that overlapping neighborhoods in the recursion tree are evaluated only once
+//!   (requires `rayon`, not enabled by default).
+//! - `log`: the `LogSink` of the [`progress`] module, which forwards the progress messages of the
+//!   solvers to the [`log`](https://docs.rs/log/) crate (not enabled by default).
+//! - `examples`: the [TSP example][examples::tsp], the [shift scheduling
+//!   example][examples::shift_scheduling], the [toy example][examples::toy] and the
+//!   `rapid_solve_tsp_example` binary (requires `rayon`, `rand` and `rapid_time`).
+//! - `tsplib_download`: downloading and caching of TSPLIB instances by name for the [TSP
+//!   example][examples::tsp] (using [`ureq`](https://docs.rs/ureq/) and
+//!   [`flate2`](https://docs.rs/flate2/), not enabled by default).
+//!
+//! The progress messages of the solvers are printed to stdout by default. They can be redirected
+//! or silenced via the [`progress`] module.
+//!
+//! Instead of importing each type separately, the [`prelude`] can be used:
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! use rapid_solve::prelude::*;
+//! ```
 //!
+extern crate alloc;
+
+/// Reports a message via the [`progress`] module (discarded without the `std` feature).
+#[cfg(feature = "std")]
+macro_rules! report {
+    ($verbosity:ident, $($arg:tt)*) => {
+        $crate::progress::report(
+            $crate::progress::Verbosity::$verbosity,
+            format_args!($($arg)*),
+        )
+    };
+}
+
+/// Without `std`, there is no printing, so all messages of the solvers are discarded.
+#[cfg(not(feature = "std"))]
+macro_rules! report {
+    ($verbosity:ident, $($arg:tt)*) => {{
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+#[cfg(feature = "examples")]
 pub mod examples;
 pub mod heuristics;
+#[cfg(all(feature = "rapid_time", feature = "std"))]
+pub mod io;
 pub mod objective;
+pub mod parallel;
+#[cfg(feature = "std")]
+pub mod prelude;
+#[cfg(feature = "std")]
+pub mod progress;
+#[cfg(test)]
+mod test_fixtures;
@@ -1,26 +1,50 @@
 //! Contains the [`BaseValue`] enum, which represents a single scalar value.
-use std::{
+use alloc::{format, string::String};
+use core::{
     cmp::Ordering,
     fmt,
     iter::Sum,
     ops::{Add, Sub},
 };
 
+#[cfg(feature = "rapid_time")]
 use rapid_time::Duration;
 
 const TOLERANCE: f64 = 0.0001;
 
+/// The number of decimal places of a [`BaseValue::Decimal`].
+pub const DECIMAL_PLACES: u32 = 4;
+
+const DECIMAL_SCALE: i128 = 10_i128.pow(DECIMAL_PLACES);
+
 /// A single value of an [`Indicator`][super::indicator::Indicator] or [`LinearCombination`][super::linear_combination::LinearCombination]. E.g., count of things, durations, costs.
-/// * Supports integers (i64), floats (f64), durations (from the RapidTime crate).
+/// * Supports integers (i64), unsigned counts (u64), fixed-point decimals (i128 scaled by
+///   10^[`DECIMAL_PLACES`]), floats (f64), durations (from the RapidTime crate, requires the
+///   `rapid_time` feature).
+/// * Decimals are added, subtracted and compared exactly (unlike floats, which are compared with
+///   a tolerance), e.g., for monetary costs.
+/// * Counts can be mixed with integers, the result is an integer. E.g., the difference of two
+///   counts is an integer if it is negative. The result is computed exactly and panics if it does
+///   not fit into the result type (in debug and release builds, see
+///   [`checked_add`][BaseValue::checked_add] for a non-panicking addition).
+/// * Decimals cannot be mixed with integers, counts or floats (adding, subtracting or comparing
+///   them panics), as the scale of the other value is unknown. Use [`BaseValue::decimal`] to
+///   convert the other value explicitly.
 /// * `Maximum` is larger (worse) than all other values.
 /// * `Zero` is the neutral element for addition.
 #[derive(Debug, Clone, Copy)]
 pub enum BaseValue {
     /// An integer value.
     Integer(i64),
+    /// An unsigned count, e.g., the number of unserved customers.
+    Count(u64),
+    /// A fixed-point decimal value with [`DECIMAL_PLACES`] decimal places stored as scaled
+    /// integer, e.g., `12.5` is stored as `125000` (see [`BaseValue::decimal`]).
+    Decimal(i128),
     /// A floating point value.
     Float(f64),
     /// A [`Duration`] value (from the RapidTime crate).
+    #[cfg(feature = "rapid_time")]
     Duration(Duration), // cannot handle negative durations
     /// Represents the maximum value.
     Maximum,
@@ -29,6 +53,29 @@ pub enum BaseValue {
 }
 
 impl BaseValue {
+    /// Creates a [`BaseValue::Decimal`] from an integer `value` with the given number of
+    /// `decimal_places`, e.g., `BaseValue::decimal(1250, 2)` is `12.50` (amount in cents).
+    /// * Panics if `decimal_places` is larger than [`DECIMAL_PLACES`] (as the value could not be
+    ///   represented exactly) or if the scaled value does not fit into an `i128`.
+    pub fn decimal(value: i128, decimal_places: u32) -> BaseValue {
+        assert!(
+            decimal_places <= DECIMAL_PLACES,
+            "A decimal can have at most {} decimal places, got {}.",
+            DECIMAL_PLACES,
+            decimal_places
+        );
+        BaseValue::Decimal(
+            value
+                .checked_mul(10_i128.pow(DECIMAL_PLACES - decimal_places))
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Decimal overflow when scaling {} with {} decimal places",
+                        value, decimal_places
+                    )
+                }),
+        )
+    }
+
     /// Unwraps [`BaseValue::Integer`].
     /// Panics if other variant.
     pub fn unwrap_integer(self) -> i64 {
@@ -38,6 +85,24 @@ impl BaseValue {
         }
     }
 
+    /// Unwraps [`BaseValue::Count`].
+    /// Panics if other variant.
+    pub fn unwrap_count(self) -> u64 {
+        match self {
+            BaseValue::Count(c) => c,
+            _ => panic!("Expected BaseValue::Count, got {:?}", self),
+        }
+    }
+
+    /// Unwraps [`BaseValue::Decimal`] (the value scaled by 10^[`DECIMAL_PLACES`]).
+    /// Panics if other variant.
+    pub fn unwrap_decimal(self) -> i128 {
+        match self {
+            BaseValue::Decimal(d) => d,
+            _ => panic!("Expected BaseValue::Decimal, got {:?}", self),
+        }
+    }
+
     /// Unwraps [`BaseValue::Float`].
     /// Panics if other variant.
     pub fn unwrap_float(self) -> f64 {
@@ -48,14 +113,79 @@ impl BaseValue {
     }
 
     /// Unwraps [`BaseValue::Duration`].
+    #[cfg(feature = "rapid_time")]
     /// Panics if other variant.
     pub fn unwrap_duration(self) -> Duration {
         match self {
+            #[cfg(feature = "rapid_time")]
             BaseValue::Duration(d) => d,
             _ => panic!("Expected BaseValue::Duration, got {:?}", self),
         }
     }
 
+    /// Returns `true` if the value is zero (floats are compared with a small tolerance).
+    pub fn is_zero(self) -> bool {
+        match self {
+            BaseValue::Float(value) if value.is_nan() => false,
+            BaseValue::Maximum => false,
+            _ => self == BaseValue::Zero,
+        }
+    }
+
+    /// Returns `true` if the value is neither [`BaseValue::Maximum`] nor an infinite or NaN float.
+    pub fn is_finite(self) -> bool {
+        match self {
+            BaseValue::Float(value) => value.is_finite(),
+            BaseValue::Maximum => false,
+            _ => true,
+        }
+    }
+
+    /// Adds `other` like `+`, but returns `None` if an addition of
+    /// [`Integer`][BaseValue::Integer], [`Count`][BaseValue::Count] or
+    /// [`Decimal`][BaseValue::Decimal] values overflows.
+    pub fn checked_add(self, other: BaseValue) -> Option<BaseValue> {
+        match (self, other) {
+            (BaseValue::Integer(a), BaseValue::Integer(b)) => {
+                a.checked_add(b).map(BaseValue::Integer)
+            }
+            (BaseValue::Count(a), BaseValue::Count(b)) => a.checked_add(b).map(BaseValue::Count),
+            (BaseValue::Count(a), BaseValue::Integer(b)) => {
+                i64::try_from(i128::from(a) + i128::from(b))
+                    .ok()
+                    .map(BaseValue::Integer)
+            }
+            (BaseValue::Integer(a), BaseValue::Count(b)) => {
+                i64::try_from(i128::from(a) + i128::from(b))
+                    .ok()
+                    .map(BaseValue::Integer)
+            }
+            (BaseValue::Decimal(a), BaseValue::Decimal(b)) => {
+                a.checked_add(b).map(BaseValue::Decimal)
+            }
+            _ => Some(self + other),
+        }
+    }
+
+    /// Converts the value into a float, e.g., for statistics or for exporting traces.
+    /// * [`BaseValue::Duration`] is converted into seconds.
+    /// * [`BaseValue::Maximum`] is converted into [`f64::INFINITY`].
+    pub fn to_f64(self) -> f64 {
+        match self {
+            BaseValue::Integer(value) => value as f64,
+            BaseValue::Count(value) => value as f64,
+            BaseValue::Decimal(value) => value as f64 / DECIMAL_SCALE as f64,
+            BaseValue::Float(value) => value,
+            #[cfg(feature = "rapid_time")]
+            BaseValue::Duration(value) => match value.in_sec() {
+                Ok(seconds) => seconds as f64,
+                Err(_) => f64::INFINITY,
+            },
+            BaseValue::Maximum => f64::INFINITY,
+            BaseValue::Zero => 0.0,
+        }
+    }
+
     /// Prints the difference between two BaseValuesin green or red depending on the sign.
     pub fn print_difference(self, other: BaseValue) -> String {
         if self == other {
@@ -65,9 +195,23 @@ impl BaseValue {
             (BaseValue::Integer(a), BaseValue::Integer(b)) => {
                 BaseValue::print_difference_in_value(a, b)
             }
+            (BaseValue::Count(a), BaseValue::Count(b)) => {
+                BaseValue::print_difference_in_value(a, b)
+            }
+            // mixed counts and integers are compared exactly (as in `integer_from`)
+            (BaseValue::Count(a), BaseValue::Integer(b)) => {
+                BaseValue::print_difference_in_value(i128::from(a), i128::from(b))
+            }
+            (BaseValue::Integer(a), BaseValue::Count(b)) => {
+                BaseValue::print_difference_in_value(i128::from(a), i128::from(b))
+            }
+            (BaseValue::Decimal(_), BaseValue::Decimal(_)) => {
+                BaseValue::print_difference_in_value(self, other)
+            }
             (BaseValue::Float(a), BaseValue::Float(b)) => {
                 BaseValue::print_difference_in_value(a, b)
             }
+            #[cfg(feature = "rapid_time")]
             (BaseValue::Duration(a), BaseValue::Duration(b)) => {
                 BaseValue::print_difference_in_value(a, b)
             }
@@ -94,13 +238,38 @@ impl BaseValue {
     }
 }
 
+/// Converts the exact result of mixing a [`BaseValue::Count`] with an integer into a
+/// [`BaseValue::Integer`] and panics if it does not fit.
+fn integer_from(value: i128, operation: &str, a: BaseValue, b: BaseValue) -> BaseValue {
+    BaseValue::Integer(
+        i64::try_from(value)
+            .unwrap_or_else(|_| panic!("Integer overflow when {} {:?} and {:?}", operation, a, b)),
+    )
+}
+
 impl Add for BaseValue {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
         match (self, other) {
             (BaseValue::Integer(a), BaseValue::Integer(b)) => BaseValue::Integer(a + b),
+            (BaseValue::Count(a), BaseValue::Count(b)) => BaseValue::Count(
+                a.checked_add(b)
+                    .unwrap_or_else(|| panic!("Count overflow when adding {} and {}", a, b)),
+            ),
+            (BaseValue::Count(a), BaseValue::Integer(b)) => {
+                integer_from(i128::from(a) + i128::from(b), "adding", self, other)
+            }
+            (BaseValue::Integer(a), BaseValue::Count(b)) => {
+                integer_from(i128::from(a) + i128::from(b), "adding", self, other)
+            }
+            (BaseValue::Decimal(a), BaseValue::Decimal(b)) => {
+                BaseValue::Decimal(a.checked_add(b).unwrap_or_else(|| {
+                    panic!("Decimal overflow when adding {} and {}", self, other)
+                }))
+            }
             (BaseValue::Float(a), BaseValue::Float(b)) => BaseValue::Float(a + b),
+            #[cfg(feature = "rapid_time")]
             (BaseValue::Duration(a), BaseValue::Duration(b)) => BaseValue::Duration(a + b),
             (BaseValue::Maximum, _) => BaseValue::Maximum,
             (_, BaseValue::Maximum) => BaseValue::Maximum,
@@ -117,11 +286,35 @@ impl Sub for BaseValue {
     fn sub(self, other: Self) -> Self {
         match (self, other) {
             (BaseValue::Integer(a), BaseValue::Integer(b)) => BaseValue::Integer(a - b),
+            (BaseValue::Count(a), BaseValue::Count(b)) if a >= b => BaseValue::Count(a - b),
+            (BaseValue::Count(a), BaseValue::Count(b)) => {
+                integer_from(i128::from(a) - i128::from(b), "subtracting", self, other)
+            }
+            (BaseValue::Count(a), BaseValue::Integer(b)) => {
+                integer_from(i128::from(a) - i128::from(b), "subtracting", self, other)
+            }
+            (BaseValue::Integer(a), BaseValue::Count(b)) => {
+                integer_from(i128::from(a) - i128::from(b), "subtracting", self, other)
+            }
+            (BaseValue::Decimal(a), BaseValue::Decimal(b)) => {
+                BaseValue::Decimal(a.checked_sub(b).unwrap_or_else(|| {
+                    panic!("Decimal overflow when subtracting {} and {}", self, other)
+                }))
+            }
             (BaseValue::Float(a), BaseValue::Float(b)) => BaseValue::Float(a - b),
+            #[cfg(feature = "rapid_time")]
             (BaseValue::Duration(a), BaseValue::Duration(b)) => BaseValue::Duration(a - b),
             (BaseValue::Maximum, _) => BaseValue::Maximum,
             (value, BaseValue::Zero) => value,
             (BaseValue::Zero, BaseValue::Integer(a)) => BaseValue::Integer(-a),
+            (BaseValue::Zero, BaseValue::Count(a)) => {
+                integer_from(-i128::from(a), "subtracting", self, other)
+            }
+            (BaseValue::Zero, BaseValue::Decimal(a)) => {
+                BaseValue::Decimal(a.checked_neg().unwrap_or_else(|| {
+                    panic!("Decimal overflow when subtracting {} and {}", self, other)
+                }))
+            }
             (BaseValue::Zero, BaseValue::Float(a)) => BaseValue::Float(-a),
             _ => panic!("Cannot sub {:?} and {:?}", self, other),
         }
@@ -132,6 +325,10 @@ impl Ord for BaseValue {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
             (BaseValue::Integer(a), BaseValue::Integer(b)) => a.cmp(b),
+            (BaseValue::Count(a), BaseValue::Count(b)) => a.cmp(b),
+            (BaseValue::Count(a), BaseValue::Integer(b)) => i128::from(*a).cmp(&i128::from(*b)),
+            (BaseValue::Integer(a), BaseValue::Count(b)) => i128::from(*a).cmp(&i128::from(*b)),
+            (BaseValue::Decimal(a), BaseValue::Decimal(b)) => a.cmp(b),
             (BaseValue::Float(a), BaseValue::Float(b)) => {
                 if a - b > TOLERANCE {
                     Ordering::Greater
@@ -141,18 +338,25 @@ impl Ord for BaseValue {
                     Ordering::Equal
                 }
             }
+            #[cfg(feature = "rapid_time")]
             (BaseValue::Duration(a), BaseValue::Duration(b)) => a.cmp(b),
             (BaseValue::Maximum, BaseValue::Maximum) => Ordering::Equal,
             (BaseValue::Zero, BaseValue::Zero) => Ordering::Equal,
             (BaseValue::Maximum, _) => Ordering::Greater,
             (_, BaseValue::Maximum) => Ordering::Less,
             (BaseValue::Zero, BaseValue::Integer(_)) => BaseValue::Integer(0).cmp(other),
+            (BaseValue::Zero, BaseValue::Count(_)) => BaseValue::Count(0).cmp(other),
+            (BaseValue::Zero, BaseValue::Decimal(_)) => BaseValue::Decimal(0).cmp(other),
             (BaseValue::Zero, BaseValue::Float(_)) => BaseValue::Float(0.0).cmp(other),
+            #[cfg(feature = "rapid_time")]
             (BaseValue::Zero, BaseValue::Duration(_)) => {
                 BaseValue::Duration(Duration::ZERO).cmp(other)
             }
             (BaseValue::Integer(_), BaseValue::Zero) => self.cmp(&BaseValue::Integer(0)),
+            (BaseValue::Count(_), BaseValue::Zero) => self.cmp(&BaseValue::Count(0)),
+            (BaseValue::Decimal(_), BaseValue::Zero) => self.cmp(&BaseValue::Decimal(0)),
             (BaseValue::Float(_), BaseValue::Zero) => self.cmp(&BaseValue::Float(0.0)),
+            #[cfg(feature = "rapid_time")]
             (BaseValue::Duration(_), BaseValue::Zero) => {
                 self.cmp(&BaseValue::Duration(Duration::ZERO))
             }
@@ -185,7 +389,17 @@ impl fmt::Display for BaseValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             BaseValue::Integer(i) => write!(f, "{}", i),
+            BaseValue::Count(c) => write!(f, "{}", c),
+            BaseValue::Decimal(d) => write!(
+                f,
+                "{}{}.{:0width$}",
+                if *d < 0 { "-" } else { "" },
+                d.unsigned_abs() / DECIMAL_SCALE as u128,
+                d.unsigned_abs() % DECIMAL_SCALE as u128,
+                width = DECIMAL_PLACES as usize
+            ),
             BaseValue::Float(c) => write!(f, "{:0.2}", c),
+            #[cfg(feature = "rapid_time")]
             BaseValue::Duration(d) => write!(f, "{}", d),
             BaseValue::Maximum => write!(f, "MAX"),
             BaseValue::Zero => write!(f, "0"),
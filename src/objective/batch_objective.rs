@@ -0,0 +1,208 @@
+//! Contains the [`BatchObjective`] trait, which evaluates many solutions at once (e.g., as one
+//! matrix computation on a GPU or with SIMD instructions).
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use super::EvaluatedSolution;
+use super::{Objective, ObjectiveValue};
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::ParallelIterator;
+
+/// Computes the [`ObjectiveValues`][ObjectiveValue] of a batch of solutions at once, which can be
+/// much faster than evaluating the solutions one at a time by [`Objective::evaluate`] if the
+/// evaluation is a vectorized computation.
+/// * Must return one [`ObjectiveValue`] per solution (in the same order) and agree with the
+///   [`Objective`] of the solver, as the best neighbor of a batch is compared with the current
+///   solution evaluated by the [`Objective`].
+/// * Can be configured on the
+///   [`ParallelMinimizer`][crate::heuristics::parallel_local_search::parallel_local_improver::ParallelMinimizer]
+///   and the
+///   [`ParallelTabuMinimizer`][crate::heuristics::parallel_tabu_search::parallel_tabu_improver::ParallelTabuMinimizer]
+///   (see `with_batch_objective`), which evaluate the neighbors in batches (in parallel).
+/// * Every [`Objective`] is a [`BatchObjective`] that evaluates the solutions one after another.
+pub trait BatchObjective<S>: Send + Sync {
+    /// Returns the [`ObjectiveValue`] of each of the `solutions` (in the same order).
+    fn evaluate_batch(&self, solutions: &[S]) -> Vec<ObjectiveValue>;
+}
+
+impl<S> BatchObjective<S> for Objective<S> {
+    fn evaluate_batch(&self, solutions: &[S]) -> Vec<ObjectiveValue> {
+        solutions
+            .iter()
+            .map(|solution| {
+                ObjectiveValue::new(
+                    self.hierarchy_levels
+                        .iter()
+                        .map(|level| level.evaluate(solution))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Evaluates the `candidates` (solutions with some additional data `X`, e.g., new tabus) by the
+/// `batch_objective` in batches of `batch_size` (at least 1) solutions, which are processed in
+/// parallel, and returns the candidate with the smallest [`ObjectiveValue`] (the first one on
+/// ties). The batches are pulled lazily from the `candidates`, such that each thread holds at
+/// most one batch at a time.
+#[cfg(feature = "std")]
+pub(crate) fn best_of_batches<S: Send + Sync, X: Send>(
+    batch_objective: &dyn BatchObjective<S>,
+    batch_size: usize,
+    candidates: impl ParallelIterator<Item = (S, X)>,
+) -> Option<(EvaluatedSolution<S>, X)> {
+    #[cfg(feature = "rayon")]
+    return candidates
+        .fold(
+            || BatchAccumulator::new(batch_objective, batch_size),
+            BatchAccumulator::push,
+        )
+        .map(BatchAccumulator::finish)
+        .reduce(|| None, smaller_candidate);
+    #[cfg(not(feature = "rayon"))]
+    return candidates
+        .fold(
+            BatchAccumulator::new(batch_objective, batch_size),
+            BatchAccumulator::push,
+        )
+        .finish();
+}
+
+/// Collects candidates until a batch is full, evaluates the batch and keeps the best candidate
+/// seen so far.
+#[cfg(feature = "std")]
+struct BatchAccumulator<'a, S, X> {
+    batch_objective: &'a dyn BatchObjective<S>,
+    batch_size: usize,
+    batch: Vec<(S, X)>,
+    best: Option<(EvaluatedSolution<S>, X)>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, S, X> BatchAccumulator<'a, S, X> {
+    fn new(batch_objective: &'a dyn BatchObjective<S>, batch_size: usize) -> Self {
+        let batch_size = batch_size.max(1);
+        BatchAccumulator {
+            batch_objective,
+            batch_size,
+            batch: Vec::with_capacity(batch_size),
+            best: None,
+        }
+    }
+
+    fn push(mut self, candidate: (S, X)) -> Self {
+        self.batch.push(candidate);
+        if self.batch.len() >= self.batch_size {
+            self.evaluate_batch();
+        }
+        self
+    }
+
+    fn finish(mut self) -> Option<(EvaluatedSolution<S>, X)> {
+        self.evaluate_batch();
+        self.best
+    }
+
+    fn evaluate_batch(&mut self) {
+        if self.batch.is_empty() {
+            return;
+        }
+        let (solutions, data): (Vec<S>, Vec<X>) = self.batch.drain(..).unzip();
+        let objective_values = self.batch_objective.evaluate_batch(&solutions);
+        assert_eq!(
+            objective_values.len(),
+            solutions.len(),
+            "The batch objective must return one objective value per solution."
+        );
+        let best_of_batch = solutions
+            .into_iter()
+            .zip(objective_values)
+            .zip(data)
+            .map(|((solution, objective_value), data)| {
+                (EvaluatedSolution::new(solution, objective_value), data)
+            })
+            .reduce(|best, candidate| smaller_candidate(Some(best), Some(candidate)).unwrap());
+        self.best = smaller_candidate(self.best.take(), best_of_batch);
+    }
+}
+
+/// Returns the candidate with the smaller [`ObjectiveValue`] (`first` on ties).
+#[cfg(feature = "std")]
+fn smaller_candidate<S, X>(
+    first: Option<(EvaluatedSolution<S>, X)>,
+    second: Option<(EvaluatedSolution<S>, X)>,
+) -> Option<(EvaluatedSolution<S>, X)> {
+    match (first, second) {
+        (Some(first), Some(second)) => {
+            if second.0.objective_value() < first.0.objective_value() {
+                Some(second)
+            } else {
+                Some(first)
+            }
+        }
+        (first, second) => first.or(second),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::parallel::IntoParallelIterator;
+    use crate::test_fixtures::Value;
+    use alloc::{boxed::Box, vec};
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_best_of_batches() {
+        let objective = Objective::new_single_indicator(Box::new(Value));
+        assert_eq!(
+            objective.evaluate_batch(&[3, -1]),
+            vec![
+                objective.evaluate(3).objective_value().clone(),
+                objective.evaluate(-1).objective_value().clone()
+            ]
+        );
+
+        for batch_size in [0, 1, 3, 10] {
+            let candidates = vec![(5, 'a'), (-2, 'b'), (7, 'c'), (-2, 'd'), (0, 'e')];
+            let (best, data) =
+                best_of_batches(&objective, batch_size, candidates.into_par_iter()).unwrap();
+            assert_eq!(*best.solution(), -2);
+            assert_eq!(data, 'b');
+        }
+        assert!(best_of_batches(&objective, 4, Vec::<(i64, ())>::new().into_par_iter()).is_none());
+    }
+
+    struct RecordingBatchObjective {
+        objective: Objective<i64>,
+        batch_lengths: Mutex<Vec<usize>>,
+    }
+
+    impl BatchObjective<i64> for RecordingBatchObjective {
+        fn evaluate_batch(&self, solutions: &[i64]) -> Vec<ObjectiveValue> {
+            self.batch_lengths.lock().unwrap().push(solutions.len());
+            self.objective.evaluate_batch(solutions)
+        }
+    }
+
+    #[test]
+    fn test_best_of_batches_evaluates_every_candidate_in_bounded_batches() {
+        let batch_objective = RecordingBatchObjective {
+            objective: Objective::new_single_indicator(Box::new(Value)),
+            batch_lengths: Mutex::new(Vec::new()),
+        };
+        let (best, _) = best_of_batches(
+            &batch_objective,
+            7,
+            (0..100i64).into_par_iter().map(|x| ((x - 42).abs(), x)),
+        )
+        .unwrap();
+        assert_eq!(*best.solution(), 0);
+
+        let batch_lengths = batch_objective.batch_lengths.into_inner().unwrap();
+        assert_eq!(batch_lengths.iter().sum::<usize>(), 100);
+        assert!(batch_lengths.iter().all(|length| (1..=7).contains(length)));
+    }
+}
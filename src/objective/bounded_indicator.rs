@@ -0,0 +1,114 @@
+//! Contains the [`BoundedIndicator`], which declares the expected domain of an [`Indicator`].
+use alloc::{boxed::Box, string::String};
+
+use super::{BaseValue, Indicator, Unit};
+
+/// Wraps an [`Indicator`] and declares its expected minimum and maximum value (see
+/// [`Indicator::bounds`]), e.g., `0` and the sum of all demands for the unserved demand.
+/// * The values of the wrapped indicator are not clamped, the bounds are only used for scaling.
+/// * Panics if `min` is larger than `max`.
+pub struct BoundedIndicator<S> {
+    indicator: Box<dyn Indicator<S>>,
+    min: BaseValue,
+    max: BaseValue,
+}
+
+impl<S> BoundedIndicator<S> {
+    /// Creates a new [`BoundedIndicator`] that declares the bounds `[min, max]` for the given
+    /// [`Indicator`].
+    pub fn new(indicator: Box<dyn Indicator<S>>, min: BaseValue, max: BaseValue) -> Self {
+        assert!(
+            min <= max,
+            "The lower bound {} of indicator '{}' is larger than the upper bound {}.",
+            min,
+            indicator.name(),
+            max
+        );
+        BoundedIndicator {
+            indicator,
+            min,
+            max,
+        }
+    }
+}
+
+impl<S> Indicator<S> for BoundedIndicator<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        self.indicator.evaluate(solution)
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.indicator.evaluate_exactly(solution)
+    }
+
+    fn name(&self) -> String {
+        self.indicator.name()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.indicator.unit()
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        Some((self.min, self.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{
+        Coefficient, LinearCombination, Objective, ObjectiveValue, UnitConversion,
+    };
+    use crate::test_fixtures::Value;
+    use alloc::vec;
+
+    fn bounded_value(min: i64, max: i64) -> Box<dyn Indicator<i64>> {
+        Box::new(BoundedIndicator::new(
+            Box::new(Value),
+            BaseValue::Integer(min),
+            BaseValue::Integer(max),
+        ))
+    }
+
+    #[test]
+    fn test_level_ranges() {
+        let objective = Objective::new(vec![
+            LinearCombination::new(vec![
+                (Coefficient::from(2), bounded_value(0, 10)),
+                (Coefficient::from(-1), bounded_value(-5, 5)),
+            ]),
+            LinearCombination::new(vec![
+                (Coefficient::from(1), bounded_value(0, 10)),
+                (Coefficient::from(1), Box::new(Value)),
+            ]),
+        ]);
+        assert_eq!(
+            objective.level_ranges(),
+            vec![Some(BaseValue::Integer(30)), None]
+        );
+        assert_eq!(
+            objective.evaluate(3).objective_value(),
+            &ObjectiveValue::new(vec![BaseValue::Integer(3), BaseValue::Integer(6)])
+        );
+    }
+
+    #[test]
+    fn test_unit_conversion_forwards_bounds() {
+        let converted = UnitConversion::new(bounded_value(0, 10), Unit::Currency);
+        assert_eq!(
+            converted.bounds(),
+            Some((BaseValue::Integer(0), BaseValue::Integer(10)))
+        );
+        assert_eq!(
+            UnitConversion::new(Box::new(Value), Unit::Currency).bounds(),
+            None
+        );
+
+        let objective = Objective::new(vec![LinearCombination::new(vec![(
+            Coefficient::from(3),
+            Box::new(converted) as Box<dyn Indicator<i64>>,
+        )])]);
+        assert_eq!(objective.level_ranges(), vec![Some(BaseValue::Integer(30))]);
+    }
+}
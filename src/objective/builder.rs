@@ -0,0 +1,308 @@
+//! Contains the [`ObjectiveBuilder`], which builds an [`Objective`] level by level with a fluent
+//! API instead of nested vectors of [`Coefficients`][Coefficient] and boxed
+//! [`Indicators`][Indicator], and places hard constraint levels in front of the soft objective
+//! levels.
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{error::Error, fmt};
+
+use super::constraint::SharedConstraint;
+use super::{
+    Coefficient, Constraint, Indicator, LinearCombination, Objective, OverflowPolicy, UnitMismatch,
+};
+
+/// Builds an [`Objective`] from hard constraint levels and soft objective levels (see
+/// [`Objective::builder`]).
+/// * Soft objective levels are either added as [`LinearCombination`] by
+///   [`add_soft_objective`][ObjectiveBuilder::add_soft_objective] or indicator by indicator by
+///   the [`LevelBuilder`] returned by [`level`][ObjectiveBuilder::level], e.g.,
+///   `Objective::builder().level().indicator(X).next_level().indicator(Y).build()`.
+/// * The hard constraint levels are the most important levels (in the order they are added),
+///   followed by the soft objective levels (in the order they are added), no matter in which
+///   order the levels are added to the builder.
+/// * The built [`Objective`] knows its constraint levels, such that
+///   [`is_feasible`][Objective::is_feasible] can check whether all violations are zero.
+/// * [`try_build`][ObjectiveBuilder::try_build] validates that there is at least one level, that
+///   no level is empty and that no level mixes incompatible [`Units`][super::Unit].
+///   [`build`][ObjectiveBuilder::build] panics instead.
+pub struct ObjectiveBuilder<S> {
+    hard_constraint_levels: Vec<Vec<Arc<Constraint<S>>>>,
+    soft_objective_levels: Vec<LinearCombination<S>>,
+}
+
+impl<S: 'static> ObjectiveBuilder<S> {
+    /// Creates a new [`ObjectiveBuilder`] without any level.
+    pub fn new() -> ObjectiveBuilder<S> {
+        ObjectiveBuilder {
+            hard_constraint_levels: Vec::new(),
+            soft_objective_levels: Vec::new(),
+        }
+    }
+
+    /// Adds a hard constraint level whose value is the sum of the violations of the given
+    /// [`Constraints`][Constraint].
+    pub fn add_hard_constraint_level(mut self, constraints: Vec<Constraint<S>>) -> Self {
+        self.hard_constraint_levels
+            .push(constraints.into_iter().map(Arc::new).collect());
+        self
+    }
+
+    /// Adds a soft objective level, which is minimized after all hard constraint levels.
+    pub fn add_soft_objective(mut self, level: LinearCombination<S>) -> Self {
+        self.soft_objective_levels.push(level);
+        self
+    }
+
+    /// Starts a new (empty) soft objective level, whose indicators are added by the returned
+    /// [`LevelBuilder`].
+    pub fn level(self) -> LevelBuilder<S> {
+        LevelBuilder {
+            objective_builder: self,
+            summands: Vec::new(),
+            name: None,
+            overflow_policy: OverflowPolicy::default(),
+        }
+    }
+
+    /// Builds the [`Objective`] with the hard constraint levels first.
+    /// * Returns an [`InvalidObjective`] error if there is no level, a level is empty, or a level
+    ///   mixes [`Indicators`][Indicator] of incompatible [`Units`][super::Unit].
+    pub fn try_build(self) -> Result<Objective<S>, InvalidObjective> {
+        let mut hierarchy_levels: Vec<LinearCombination<S>> = self
+            .hard_constraint_levels
+            .iter()
+            .map(|constraints| {
+                LinearCombination::new(
+                    constraints
+                        .iter()
+                        .map(|constraint| {
+                            (
+                                Coefficient::from(1),
+                                Box::new(SharedConstraint(constraint.clone()))
+                                    as Box<dyn Indicator<S>>,
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        hierarchy_levels.extend(self.soft_objective_levels);
+        if hierarchy_levels.is_empty() {
+            return Err(InvalidObjective::NoLevel);
+        }
+        if let Some(level) = hierarchy_levels.iter().position(|level| level.is_empty()) {
+            return Err(InvalidObjective::EmptyLevel { level });
+        }
+        Ok(Objective::try_new(hierarchy_levels)
+            .map_err(InvalidObjective::UnitMismatch)?
+            .with_constraint_levels(self.hard_constraint_levels))
+    }
+
+    /// Builds the [`Objective`] with the hard constraint levels first (see
+    /// [`try_build`][ObjectiveBuilder::try_build]).
+    /// * Panics if the objective is invalid.
+    pub fn build(self) -> Objective<S> {
+        match self.try_build() {
+            Ok(objective) => objective,
+            Err(invalid_objective) => panic!("{}", invalid_objective),
+        }
+    }
+}
+
+impl<S: 'static> Default for ObjectiveBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Adds [`Indicators`][Indicator] to the current soft objective level of an
+/// [`ObjectiveBuilder`] (see [`ObjectiveBuilder::level`]).
+pub struct LevelBuilder<S> {
+    objective_builder: ObjectiveBuilder<S>,
+    summands: Vec<(Coefficient, Box<dyn Indicator<S>>)>,
+    name: Option<String>,
+    overflow_policy: OverflowPolicy,
+}
+
+impl<S: 'static> LevelBuilder<S> {
+    /// Adds the [`Indicator`] with coefficient 1 to the current level.
+    pub fn indicator(self, indicator: impl Indicator<S> + 'static) -> Self {
+        self.weighted_indicator(1, indicator)
+    }
+
+    /// Adds the [`Indicator`] multiplied with the `coefficient` to the current level.
+    pub fn weighted_indicator(
+        mut self,
+        coefficient: impl Into<Coefficient>,
+        indicator: impl Indicator<S> + 'static,
+    ) -> Self {
+        self.summands
+            .push((coefficient.into(), Box::new(indicator)));
+        self
+    }
+
+    /// Names the current level, the name is used instead of the indicators when an
+    /// [`ObjectiveValue`][super::ObjectiveValue] is printed (see [`LinearCombination::with_name`]).
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(String::from(name));
+        self
+    }
+
+    /// Sets the [`OverflowPolicy`] of the current level (see
+    /// [`LinearCombination::with_overflow_policy`]).
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Finishes the current level and starts the next (less important) soft objective level.
+    pub fn next_level(self) -> LevelBuilder<S> {
+        self.end_level().level()
+    }
+
+    /// Finishes the current level and returns the [`ObjectiveBuilder`], e.g., to add hard
+    /// constraint levels.
+    pub fn end_level(self) -> ObjectiveBuilder<S> {
+        let linear_combination =
+            LinearCombination::new(self.summands).with_overflow_policy(self.overflow_policy);
+        let level = match self.name {
+            Some(name) => linear_combination.with_name(&name),
+            None => linear_combination,
+        };
+        self.objective_builder.add_soft_objective(level)
+    }
+
+    /// Finishes the current level and builds the [`Objective`] (see
+    /// [`ObjectiveBuilder::try_build`]).
+    pub fn try_build(self) -> Result<Objective<S>, InvalidObjective> {
+        self.end_level().try_build()
+    }
+
+    /// Finishes the current level and builds the [`Objective`] (see
+    /// [`ObjectiveBuilder::build`]).
+    /// * Panics if the objective is invalid.
+    pub fn build(self) -> Objective<S> {
+        self.end_level().build()
+    }
+}
+
+/// The error returned by [`ObjectiveBuilder::try_build`] if the [`Objective`] is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidObjective {
+    /// The objective has no level, i.e., all solutions would be equal.
+    NoLevel,
+    /// The level with the given index (counting the hard constraint levels first) has no
+    /// indicator.
+    EmptyLevel {
+        /// The index of the empty level.
+        level: usize,
+    },
+    /// A level mixes indicators of incompatible units.
+    UnitMismatch(UnitMismatch),
+}
+
+impl fmt::Display for InvalidObjective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidObjective::NoLevel => {
+                write!(f, "An objective must have at least one hierarchy level.")
+            }
+            InvalidObjective::EmptyLevel { level } => {
+                write!(f, "Level {} of the objective has no indicator.", level)
+            }
+            InvalidObjective::UnitMismatch(unit_mismatch) => write!(f, "{}", unit_mismatch),
+        }
+    }
+}
+
+impl Error for InvalidObjective {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{BaseValue, ObjectiveValue, Unit};
+    use crate::test_fixtures::Value;
+    use alloc::{string::ToString, vec};
+
+    struct Seconds;
+
+    impl Indicator<i64> for Seconds {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer(*solution)
+        }
+        fn name(&self) -> String {
+            String::from("Seconds")
+        }
+        fn unit(&self) -> Option<Unit> {
+            Some(Unit::Seconds)
+        }
+    }
+
+    struct Meters;
+
+    impl Indicator<i64> for Meters {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer(*solution)
+        }
+        fn name(&self) -> String {
+            String::from("Meters")
+        }
+        fn unit(&self) -> Option<Unit> {
+            Some(Unit::Meters)
+        }
+    }
+
+    #[test]
+    fn test_fluent_builder() {
+        let objective = Objective::builder()
+            .add_hard_constraint_level(vec![Constraint::new(Box::new(Meters))])
+            .level()
+            .indicator(Value)
+            .weighted_indicator(2.5, Value)
+            .named("Cost")
+            .next_level()
+            .weighted_indicator(-1, Value)
+            .build();
+
+        assert_eq!(objective.number_of_constraint_levels(), 1);
+        assert_eq!(
+            objective.evaluate(2).objective_value(),
+            &ObjectiveValue::new(vec![
+                BaseValue::Integer(2),
+                BaseValue::Integer(7),
+                BaseValue::Integer(-2)
+            ])
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            objective.format_objective_value(objective.evaluate(0).objective_value()),
+            " * Meters: 0\n * Cost: 0\n * -1*Value: 0"
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(
+            ObjectiveBuilder::<i64>::new().try_build().err(),
+            Some(InvalidObjective::NoLevel)
+        );
+        assert_eq!(
+            Objective::builder()
+                .level()
+                .indicator(Value)
+                .next_level()
+                .try_build()
+                .err(),
+            Some(InvalidObjective::EmptyLevel { level: 1 })
+        );
+        let unit_mismatch = Objective::builder()
+            .level()
+            .indicator(Seconds)
+            .indicator(Meters)
+            .try_build()
+            .err()
+            .unwrap();
+        assert!(unit_mismatch
+            .to_string()
+            .starts_with("incompatible units in 'Seconds + Meters'"));
+    }
+}
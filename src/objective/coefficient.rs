@@ -1,8 +1,9 @@
 //! Contains the [`Coefficient`] enum, which is used by
 //! [`LinearCombinations`][`super::LinearCombination`].
 
-use std::{fmt, ops::Mul};
+use core::{fmt, ops::Mul};
 
+#[cfg(feature = "rapid_time")]
 use rapid_time::Duration;
 
 use super::base_value::BaseValue;
@@ -25,6 +26,55 @@ impl Coefficient {
             Coefficient::Float(f) => *f == 1.0,
         }
     }
+
+    /// Returns the absolute value of the coefficient.
+    pub fn abs(&self) -> Coefficient {
+        match self {
+            Coefficient::Integer(i) => Coefficient::Integer(i.abs()),
+            Coefficient::Float(f) => Coefficient::Float(f.abs()),
+        }
+    }
+
+    /// Multiplies `value` with the coefficient like `*`, but returns `None` if the product of an
+    /// [`Integer`][BaseValue::Integer], [`Count`][BaseValue::Count] or
+    /// [`Decimal`][BaseValue::Decimal] value does not fit into its type (instead of wrapping
+    /// around or saturating silently).
+    pub fn checked_mul(self, value: BaseValue) -> Option<BaseValue> {
+        match (self, value) {
+            (Coefficient::Integer(c), BaseValue::Integer(b)) => {
+                (c as i64).checked_mul(b).map(BaseValue::Integer)
+            }
+            (Coefficient::Integer(c), BaseValue::Count(b)) if c >= 0 => {
+                (c as u64).checked_mul(b).map(BaseValue::Count)
+            }
+            (Coefficient::Integer(c), BaseValue::Count(b)) => {
+                i64::try_from(i128::from(c) * i128::from(b))
+                    .ok()
+                    .map(BaseValue::Integer)
+            }
+            (Coefficient::Integer(c), BaseValue::Decimal(b)) => {
+                (c as i128).checked_mul(b).map(BaseValue::Decimal)
+            }
+            (Coefficient::Float(c), BaseValue::Integer(b)) => {
+                let product = c * b as f32;
+                (product.is_finite() && product.abs() < i64::MAX as f32).then(|| self * value)
+            }
+            (Coefficient::Float(c), BaseValue::Count(b)) => {
+                let product = c * b as f32;
+                let in_range = if c >= 0.0 {
+                    product < u64::MAX as f32
+                } else {
+                    product.abs() < i64::MAX as f32
+                };
+                (product.is_finite() && in_range).then(|| self * value)
+            }
+            (Coefficient::Float(c), BaseValue::Decimal(b)) => {
+                let product = c as f64 * b as f64;
+                (product.is_finite() && product.abs() < i128::MAX as f64).then(|| self * value)
+            }
+            _ => Some(self * value),
+        }
+    }
 }
 
 impl From<i32> for Coefficient {
@@ -39,6 +89,12 @@ impl From<f32> for Coefficient {
     }
 }
 
+impl From<f64> for Coefficient {
+    fn from(f: f64) -> Self {
+        Coefficient::Float(f as f32)
+    }
+}
+
 impl Mul<BaseValue> for Coefficient {
     type Output = BaseValue;
 
@@ -46,7 +102,23 @@ impl Mul<BaseValue> for Coefficient {
         match self {
             Coefficient::Integer(c) => match other {
                 BaseValue::Integer(b) => BaseValue::Integer(c as i64 * b),
+                BaseValue::Count(b) if c >= 0 => {
+                    BaseValue::Count((c as u64).checked_mul(b).unwrap_or_else(|| {
+                        panic!("Count overflow when multiplying {} and {}", c, b)
+                    }))
+                }
+                BaseValue::Count(b) => {
+                    BaseValue::Integer(i64::try_from(i128::from(c) * i128::from(b)).unwrap_or_else(
+                        |_| panic!("Integer overflow when multiplying {} and {}", c, b),
+                    ))
+                }
+                BaseValue::Decimal(b) => {
+                    BaseValue::Decimal((c as i128).checked_mul(b).unwrap_or_else(|| {
+                        panic!("Decimal overflow when multiplying {} and {}", c, other)
+                    }))
+                }
                 BaseValue::Float(b) => BaseValue::Float(c as f64 * b),
+                #[cfg(feature = "rapid_time")]
                 BaseValue::Duration(b) => match b.in_sec() {
                     Ok(sec) => BaseValue::Duration(Duration::from_seconds(c as u64 * sec)),
                     Err(_) => BaseValue::Duration(Duration::Infinity),
@@ -56,7 +128,16 @@ impl Mul<BaseValue> for Coefficient {
             },
             Coefficient::Float(c) => match other {
                 BaseValue::Integer(b) => BaseValue::Integer((c * b as f32) as i64),
+                BaseValue::Count(b) if c >= 0.0 => BaseValue::Count((c * b as f32) as u64),
+                BaseValue::Count(b) => BaseValue::Integer((c * b as f32) as i64),
+                // rounded to the closest decimal, only exact for integral coefficients
+                BaseValue::Decimal(b) => {
+                    let product = c as f64 * b as f64;
+                    let rounding = if product < 0.0 { -0.5 } else { 0.5 };
+                    BaseValue::Decimal((product + rounding) as i128)
+                }
                 BaseValue::Float(b) => BaseValue::Float(c as f64 * b),
+                #[cfg(feature = "rapid_time")]
                 BaseValue::Duration(b) => match b.in_sec() {
                     Ok(sec) => BaseValue::Duration(Duration::from_seconds((c * sec as f32) as u64)),
                     Err(_) => BaseValue::Duration(Duration::Infinity),
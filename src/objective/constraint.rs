@@ -0,0 +1,226 @@
+//! Contains the [`Constraint`], which wraps an [`Indicator`] as a violation measure of a hard
+//! constraint, and the [`ViolationReport`], which explains the violation of a constraint.
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::fmt;
+
+use super::{BaseValue, Indicator, Unit};
+
+/// Type for the reporting hook of a [`Constraint`] (see [`Constraint::with_reporter`]), which
+/// returns a description of each element of the solution that violates the constraint (e.g.,
+/// `"vehicle 3 (overload 12 kg)"`).
+pub type ViolationReporter<S> = Box<dyn Fn(&S) -> Vec<String> + Send + Sync>;
+
+/// A hard constraint given by an [`Indicator`] that measures its violation, e.g., the number of
+/// overlapping shifts or the total overload of all vehicles.
+/// * The violation must be non-negative and zero if (and only if) the constraint is satisfied.
+/// * Constraints are added to an [`Objective`][super::Objective] by
+///   [`add_hard_constraint_level`][super::ObjectiveBuilder::add_hard_constraint_level], such that
+///   infeasible solutions can be evaluated and the solvers first minimize the violation.
+/// * A [`ViolationReporter`] (see [`with_reporter`][Constraint::with_reporter]) names the
+///   violating elements, such that [`Objective::explain`][super::Objective::explain] turns a
+///   violation level into actionable diagnostics.
+pub struct Constraint<S> {
+    violation: Box<dyn Indicator<S>>,
+    reporter: Option<ViolationReporter<S>>,
+}
+
+impl<S> Constraint<S> {
+    /// Creates a new [`Constraint`] whose violation is measured by the given [`Indicator`].
+    pub fn new(violation: Box<dyn Indicator<S>>) -> Constraint<S> {
+        Constraint {
+            violation,
+            reporter: None,
+        }
+    }
+
+    /// Sets the [`ViolationReporter`], which describes the elements of a solution that violate
+    /// this constraint.
+    pub fn with_reporter(self, reporter: ViolationReporter<S>) -> Constraint<S> {
+        Constraint {
+            reporter: Some(reporter),
+            ..self
+        }
+    }
+
+    /// Returns the descriptions of the elements of the `solution` that violate this constraint
+    /// (empty if no [`ViolationReporter`] is set).
+    pub fn violating_elements(&self, solution: &S) -> Vec<String> {
+        match &self.reporter {
+            Some(reporter) => reporter(solution),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl<S> Indicator<S> for Constraint<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        self.violation.evaluate(solution)
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.violation.evaluate_exactly(solution)
+    }
+
+    fn name(&self) -> String {
+        self.violation.name()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.violation.unit()
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.violation.bounds()
+    }
+}
+
+/// A [`Constraint`] shared between its hard constraint level and the
+/// [`Objective`][super::Objective], which explains the violations.
+pub(super) struct SharedConstraint<S>(pub(super) Arc<Constraint<S>>);
+
+impl<S> Indicator<S> for SharedConstraint<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        self.0.evaluate(solution)
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.0.evaluate_exactly(solution)
+    }
+
+    fn name(&self) -> String {
+        self.0.name()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.0.unit()
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.0.bounds()
+    }
+}
+
+/// Explains the violation of a [`Constraint`] by a solution (see
+/// [`Objective::explain`][super::Objective::explain]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViolationReport {
+    /// The name of the violated constraint.
+    pub constraint: String,
+    /// The index of the hard constraint level of the constraint.
+    pub level: usize,
+    /// The violation of the constraint (a summand of the level value).
+    pub violation: BaseValue,
+    /// The descriptions of the violating elements by the [`ViolationReporter`] of the constraint
+    /// (empty if the constraint has no reporter).
+    pub violating_elements: Vec<String>,
+}
+
+impl fmt::Display for ViolationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (level {}): violation {}",
+            self.constraint, self.level, self.violation
+        )?;
+        for element in self.violating_elements.iter() {
+            write!(f, "\n  - {}", element)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::{Coefficient, LinearCombination, ObjectiveBuilder};
+    use alloc::vec;
+
+    struct Overweight;
+
+    impl Indicator<(i64, i64)> for Overweight {
+        fn evaluate(&self, &(weight, _): &(i64, i64)) -> BaseValue {
+            BaseValue::Integer((weight - 10).max(0))
+        }
+        fn name(&self) -> String {
+            String::from("Overweight")
+        }
+    }
+
+    struct NegativeValue;
+
+    impl Indicator<(i64, i64)> for NegativeValue {
+        fn evaluate(&self, &(_, value): &(i64, i64)) -> BaseValue {
+            BaseValue::Integer(-value)
+        }
+        fn name(&self) -> String {
+            String::from("NegativeValue")
+        }
+    }
+
+    #[test]
+    fn test_is_feasible() {
+        let objective = ObjectiveBuilder::new()
+            .add_soft_objective(LinearCombination::new(vec![(
+                Coefficient::from(1),
+                Box::new(NegativeValue),
+            )]))
+            .add_hard_constraint_level(vec![Constraint::new(Box::new(Overweight))])
+            .build();
+
+        let feasible = objective.evaluate((10, 3));
+        let infeasible = objective.evaluate((12, 5));
+        assert_eq!(
+            feasible.objective_value().as_vec(),
+            &vec![BaseValue::Integer(0), BaseValue::Integer(-3)]
+        );
+        assert!(objective.is_feasible(&feasible));
+        assert!(!objective.is_feasible(&infeasible));
+        assert!(objective.is_better(&feasible, &infeasible));
+    }
+
+    #[test]
+    fn test_explain() {
+        let objective = ObjectiveBuilder::new()
+            .add_soft_objective(LinearCombination::new(vec![(
+                Coefficient::from(1),
+                Box::new(NegativeValue),
+            )]))
+            .add_hard_constraint_level(vec![
+                Constraint::new(Box::new(Overweight)).with_reporter(Box::new(
+                    |&(weight, _): &(i64, i64)| {
+                        if weight > 10 {
+                            vec![alloc::format!("knapsack (weight {})", weight)]
+                        } else {
+                            vec![]
+                        }
+                    },
+                )),
+                Constraint::new(Box::new(NegativeValue)),
+            ])
+            .build();
+
+        let reports = objective.explain(&objective.evaluate((12, -5)));
+        assert_eq!(
+            reports,
+            vec![
+                ViolationReport {
+                    constraint: String::from("Overweight"),
+                    level: 0,
+                    violation: BaseValue::Integer(2),
+                    violating_elements: vec![String::from("knapsack (weight 12)")],
+                },
+                ViolationReport {
+                    constraint: String::from("NegativeValue"),
+                    level: 0,
+                    violation: BaseValue::Integer(5),
+                    violating_elements: vec![],
+                }
+            ]
+        );
+        assert_eq!(
+            alloc::string::ToString::to_string(&reports[0]),
+            "Overweight (level 0): violation 2\n  - knapsack (weight 12)"
+        );
+        assert!(objective.explain(&objective.evaluate((10, 0))).is_empty());
+    }
+}
@@ -1,12 +1,20 @@
 //! Contains the [`EvaluatedSolution`] struct, which is a solution equipped with an
 //! [`ObjectiveValue`].
 
+use core::cmp::Ordering;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+
 use super::ObjectiveValue;
 
 /// A solution-wrapper that equips a solution with an
 /// [`ObjectiveValue`]. This is the result of
 /// [evaluating][super::Objective::evaluate] a solution with an [`Objective`][super::Objective].
-#[derive(Clone, Ord, PartialOrd, PartialEq, Eq)]
+/// * The ordering (and equality) is purely based on the [`ObjectiveValue`], i.e., the solutions
+///   themselves are not compared. Two different solutions with the same objective value are
+///   equal in this sense.
+#[derive(Clone)]
 pub struct EvaluatedSolution<S> {
     objective_value: ObjectiveValue,
     solution: S,
@@ -38,3 +46,50 @@ impl<S> EvaluatedSolution<S> {
         self.solution
     }
 }
+
+impl<S> Ord for EvaluatedSolution<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.objective_value.cmp(&other.objective_value)
+    }
+}
+
+impl<S> PartialOrd for EvaluatedSolution<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> PartialEq for EvaluatedSolution<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.objective_value == other.objective_value
+    }
+}
+
+impl<S> Eq for EvaluatedSolution<S> {}
+
+/// Extension trait for iterators over [`EvaluatedSolutions`][EvaluatedSolution].
+pub trait MinByObjective<S>: Iterator<Item = EvaluatedSolution<S>> + Sized {
+    /// Returns the [`EvaluatedSolution`] with the smallest [`ObjectiveValue`] (the first one if
+    /// several are equally minimal) or `None` if the iterator is empty.
+    fn min_by_objective(self) -> Option<EvaluatedSolution<S>> {
+        self.min_by(|a, b| a.objective_value().cmp(b.objective_value()))
+    }
+}
+
+impl<S, I: Iterator<Item = EvaluatedSolution<S>>> MinByObjective<S> for I {}
+
+/// Extension trait for parallel iterators over [`EvaluatedSolutions`][EvaluatedSolution].
+/// Without the `rayon` feature, the parallel iterators are sequential (see the
+/// [`parallel`][crate::parallel] module) and this is an alias of [`MinByObjective`].
+#[cfg(feature = "rayon")]
+pub trait ParallelMinByObjective<S: Send>: ParallelIterator<Item = EvaluatedSolution<S>> {
+    /// Returns the [`EvaluatedSolution`] with the smallest [`ObjectiveValue`] or `None` if the
+    /// iterator is empty. If several solutions are equally minimal, it is not specified which one
+    /// is returned.
+    fn min_by_objective(self) -> Option<EvaluatedSolution<S>> {
+        self.min_by(|a, b| a.objective_value().cmp(b.objective_value()))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<S: Send, I: ParallelIterator<Item = EvaluatedSolution<S>>> ParallelMinByObjective<S> for I {}
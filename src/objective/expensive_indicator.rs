@@ -0,0 +1,237 @@
+//! Contains the [`ExpensiveIndicator`], which wraps an [`Indicator`] backed by a simulation or
+//! an external process with a per-solution cache and a concurrent batch evaluation.
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[cfg_attr(not(feature = "rayon"), allow(unused_imports))]
+use crate::parallel::{IntoParallelRefIterator, ParallelIterator};
+
+use super::base_value::BaseValue;
+use super::indicator::Indicator;
+use super::unit::Unit;
+use crate::heuristics::common::SolutionFingerprint;
+
+/// Evaluates batches of solutions ahead of time (e.g., concurrently or in a single request to an
+/// external process) and caches the values, such that the subsequent evaluations by the
+/// [`Objective`][super::Objective] are cheap. Used by the
+/// [`PrefetchingNeighborhood`][crate::heuristics::common::PrefetchingNeighborhood].
+pub trait Prefetch<S>: Send + Sync {
+    /// Evaluates the `solutions` that are not cached yet and stores their values in the cache.
+    fn prefetch(&self, solutions: &[S]);
+}
+
+struct Cache {
+    values: HashMap<u64, BaseValue>,
+    insertion_order: VecDeque<u64>,
+}
+
+struct ExpensiveIndicatorState<S> {
+    indicator: Box<dyn Indicator<S>>,
+    fingerprint: SolutionFingerprint<S>,
+    capacity: usize,
+    cache: Mutex<Cache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Wraps an expensive [`Indicator`] (e.g., backed by a simulation or an external process) with a
+/// per-solution cache, such that each solution is evaluated only once.
+/// * The solutions are identified by a [`SolutionFingerprint`] (e.g., a hash of the solution),
+///   so solutions with the same fingerprint must have the same value.
+/// * The cache holds at most `capacity` values; if it is full, the oldest value is dropped.
+/// * [`prefetch`][Prefetch::prefetch] evaluates several solutions concurrently (on the
+///   [`rayon`](https://docs.rs/rayon/) thread pool if the `rayon` feature is enabled) and stores
+///   the values in the cache, such that the subsequent
+///   [`Objective::evaluate`][super::Objective::evaluate] calls are cache hits. The
+///   [`PrefetchingNeighborhood`][crate::heuristics::common::PrefetchingNeighborhood] does this for
+///   batches of neighbors, so every solver profits from the concurrent evaluation.
+/// * The [`ExpensiveIndicator`] is a cheap handle to a shared state: A clone can be put into the
+///   [`Objective`][super::Objective] while another one is used for prefetching and statistics.
+/// * The wrapped indicator is evaluated without holding the lock of the cache, so two threads
+///   might evaluate the same solution concurrently.
+pub struct ExpensiveIndicator<S> {
+    state: Arc<ExpensiveIndicatorState<S>>,
+}
+
+impl<S> Clone for ExpensiveIndicator<S> {
+    fn clone(&self) -> Self {
+        ExpensiveIndicator {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<S> ExpensiveIndicator<S> {
+    /// Creates a new [`ExpensiveIndicator`] that caches at most `capacity` values of the
+    /// `indicator` identified by the `fingerprint` of the solutions.
+    pub fn new(
+        indicator: Box<dyn Indicator<S>>,
+        fingerprint: SolutionFingerprint<S>,
+        capacity: usize,
+    ) -> ExpensiveIndicator<S> {
+        ExpensiveIndicator {
+            state: Arc::new(ExpensiveIndicatorState {
+                indicator,
+                fingerprint,
+                capacity,
+                cache: Mutex::new(Cache {
+                    values: HashMap::new(),
+                    insertion_order: VecDeque::new(),
+                }),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns the number of evaluations that were answered by the cache.
+    pub fn hits(&self) -> u64 {
+        self.state.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of evaluations of the wrapped indicator.
+    pub fn misses(&self) -> u64 {
+        self.state.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of cached values.
+    pub fn len(&self) -> usize {
+        self.state.cache.lock().unwrap().values.len()
+    }
+
+    /// Returns `true` if no value is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached values (e.g., if the simulation model changed).
+    pub fn clear(&self) {
+        let mut cache = self.state.cache.lock().unwrap();
+        cache.values.clear();
+        cache.insertion_order.clear();
+    }
+
+    fn cached_value(&self, fingerprint: u64) -> Option<BaseValue> {
+        self.state
+            .cache
+            .lock()
+            .unwrap()
+            .values
+            .get(&fingerprint)
+            .copied()
+    }
+
+    fn evaluate_and_store(&self, fingerprint: u64, solution: &S) -> BaseValue {
+        self.state.misses.fetch_add(1, Ordering::Relaxed);
+        let value = self.state.indicator.evaluate(solution);
+        if self.state.capacity > 0 {
+            let mut cache = self.state.cache.lock().unwrap();
+            if cache.values.insert(fingerprint, value).is_none() {
+                cache.insertion_order.push_back(fingerprint);
+                if cache.insertion_order.len() > self.state.capacity {
+                    let oldest = cache.insertion_order.pop_front().unwrap();
+                    cache.values.remove(&oldest);
+                }
+            }
+        }
+        value
+    }
+}
+
+impl<S: Sync> Prefetch<S> for ExpensiveIndicator<S> {
+    /// Evaluates all `solutions` that are not cached yet (concurrently with the `rayon` feature,
+    /// otherwise sequentially) and stores their values in the cache.
+    fn prefetch(&self, solutions: &[S]) {
+        let mut seen = HashSet::new();
+        let missing: Vec<(u64, &S)> = solutions
+            .iter()
+            .map(|solution| ((self.state.fingerprint)(solution), solution))
+            .filter(|(fingerprint, _)| {
+                seen.insert(*fingerprint) && self.cached_value(*fingerprint).is_none()
+            })
+            .collect();
+        missing.par_iter().for_each(|(fingerprint, solution)| {
+            self.evaluate_and_store(*fingerprint, solution);
+        });
+    }
+}
+
+impl<S> Indicator<S> for ExpensiveIndicator<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        let fingerprint = (self.state.fingerprint)(solution);
+        match self.cached_value(fingerprint) {
+            Some(value) => {
+                self.state.hits.fetch_add(1, Ordering::Relaxed);
+                value
+            }
+            None => self.evaluate_and_store(fingerprint, solution),
+        }
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.state.indicator.evaluate_exactly(solution)
+    }
+
+    fn name(&self) -> String {
+        self.state.indicator.name()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.state.indicator.unit()
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.state.indicator.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::Objective;
+
+    struct CountingSquare(Arc<AtomicU64>);
+
+    impl Indicator<i64> for CountingSquare {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            BaseValue::Integer(solution * solution)
+        }
+        fn name(&self) -> String {
+            String::from("CountingSquare")
+        }
+    }
+
+    #[test]
+    fn test_expensive_indicator() {
+        let simulations = Arc::new(AtomicU64::new(0));
+        let indicator = ExpensiveIndicator::new(
+            Box::new(CountingSquare(simulations.clone())),
+            Box::new(|solution: &i64| *solution as u64),
+            3,
+        );
+        let objective = Objective::new_single_indicator(Box::new(indicator.clone()));
+
+        indicator.prefetch(&[1, 2, 3, 2]);
+        assert_eq!(simulations.load(Ordering::Relaxed), 3);
+        assert_eq!(indicator.len(), 3);
+
+        assert_eq!(
+            objective.evaluate(2).objective_value().as_vec(),
+            &vec![BaseValue::Integer(4)]
+        );
+        assert_eq!(indicator.hits(), 1);
+        assert_eq!(simulations.load(Ordering::Relaxed), 3);
+
+        // the capacity is 3, so the oldest value (of 1) is dropped
+        objective.evaluate(4);
+        assert_eq!(indicator.len(), 3);
+        objective.evaluate(1);
+        assert_eq!(simulations.load(Ordering::Relaxed), 5);
+        assert_eq!(indicator.misses(), 5);
+
+        indicator.clear();
+        assert!(indicator.is_empty());
+    }
+}
@@ -0,0 +1,587 @@
+//! Contains the [`ExternalEvaluator`], which delegates the evaluation of solutions to an external
+//! process (e.g., an objective or a simulation written in another language), and the
+//! [`ExternalIndicator`], which uses it as [`Indicator`].
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+#[cfg(unix)]
+use std::net::Shutdown;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use super::base_value::BaseValue;
+use super::expensive_indicator::Prefetch;
+use super::indicator::Indicator;
+use super::json::base_value_from_json;
+
+/// Type for the function that converts a solution into the JSON value that is sent to the
+/// external process.
+pub type SolutionSerializer<S> = Box<dyn Fn(&S) -> Value + Send + Sync>;
+
+/// Error that is returned if the external process cannot evaluate a batch of solutions.
+#[derive(Debug)]
+pub enum ExternalEvaluatorError {
+    /// The process cannot be spawned or the communication with it failed.
+    Io(io::Error),
+    /// The answer of the process is not valid JSON.
+    Json(serde_json::Error),
+    /// The process did not answer within the timeout.
+    Timeout,
+    /// The process closed its output (e.g., because it crashed).
+    Disconnected,
+    /// The answer of the process does not follow the protocol.
+    InvalidResponse(String),
+    /// The process answered with an error message.
+    Remote(String),
+}
+
+impl fmt::Display for ExternalEvaluatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalEvaluatorError::Io(error) => {
+                write!(
+                    f,
+                    "Cannot communicate with the external evaluator: {}",
+                    error
+                )
+            }
+            ExternalEvaluatorError::Json(error) => {
+                write!(f, "Invalid JSON from the external evaluator: {}", error)
+            }
+            ExternalEvaluatorError::Timeout => {
+                write!(f, "The external evaluator did not answer in time")
+            }
+            ExternalEvaluatorError::Disconnected => {
+                write!(f, "The external evaluator closed the connection")
+            }
+            ExternalEvaluatorError::InvalidResponse(message) => {
+                write!(f, "Invalid response of the external evaluator: {}", message)
+            }
+            ExternalEvaluatorError::Remote(message) => {
+                write!(f, "The external evaluator reported an error: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for ExternalEvaluatorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExternalEvaluatorError::Io(error) => Some(error),
+            ExternalEvaluatorError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExternalEvaluatorError {
+    fn from(error: io::Error) -> Self {
+        ExternalEvaluatorError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ExternalEvaluatorError {
+    fn from(error: serde_json::Error) -> Self {
+        ExternalEvaluatorError::Json(error)
+    }
+}
+
+enum Transport {
+    Process(Child),
+    #[cfg(unix)]
+    UnixSocket(UnixStream),
+}
+
+struct Connection {
+    transport: Transport,
+    writer: Box<dyn Write + Send>,
+    responses: Receiver<io::Result<String>>,
+    next_id: u64,
+}
+
+struct Cache {
+    values: HashMap<String, HashMap<String, BaseValue>>,
+    insertion_order: VecDeque<String>,
+}
+
+/// Delegates the evaluation of solutions to an external process via a JSON lines protocol.
+/// * Solutions are sent in batches of at most `batch_size` solutions per request.
+/// * The values of the last `cache_capacity` solutions are cached (identified by their JSON
+///   serialization), such that several [`ExternalIndicators`][ExternalIndicator] (e.g., on
+///   different levels of the [`Objective`][super::Objective]) share a single request per solution
+///   and batches can be [prefetched][Prefetch::prefetch] (e.g., by the
+///   [`PrefetchingNeighborhood`][crate::heuristics::common::PrefetchingNeighborhood]).
+/// * If `timeout` is set, a request fails with [`ExternalEvaluatorError::Timeout`] if the process
+///   does not answer in time.
+/// * Requests of several threads are sent one after another.
+/// * A spawned process is killed when the evaluator is dropped.
+///
+/// # Protocol
+/// The evaluator communicates via JSON lines over the stdin/stdout of a child process or over a
+/// Unix socket. Each request is a single line with a unique `id` and a batch of solutions (as
+/// serialized by the [`SolutionSerializer`]):
+/// ```text
+/// {"id":1,"solutions":[[0,2,1],[1,0,2]]}
+/// ```
+/// The external process answers with a single line with the same `id` and one JSON object per
+/// solution, which maps the names of the indicators to their values (numbers, or the strings
+/// `"Zero"` and `"Maximum"`):
+/// ```text
+/// {"id":1,"values":[{"distance":12.5,"late_jobs":0},{"distance":11.0,"late_jobs":1}]}
+/// ```
+/// If the process cannot evaluate a batch, it answers with `{"id":1,"error":"<message>"}`.
+/// Late answers to requests that timed out are skipped.
+///
+/// Requires the `external_evaluator` feature.
+pub struct ExternalEvaluator {
+    connection: Mutex<Connection>,
+    batch_size: usize,
+    cache_capacity: usize,
+    timeout: Option<Duration>,
+    cache: Mutex<Cache>,
+}
+
+impl ExternalEvaluator {
+    /// Spawns the `command` and communicates via its stdin and stdout (stderr is inherited, so it
+    /// can be used for logging).
+    pub fn spawn(
+        mut command: Command,
+        batch_size: usize,
+        cache_capacity: usize,
+        timeout: Option<Duration>,
+    ) -> Result<ExternalEvaluator, ExternalEvaluatorError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        Ok(ExternalEvaluator::from_streams(
+            Transport::Process(child),
+            stdout,
+            Box::new(stdin),
+            batch_size,
+            cache_capacity,
+            timeout,
+        ))
+    }
+
+    /// Connects to an external process listening on the Unix socket at `path`.
+    #[cfg(unix)]
+    pub fn connect(
+        path: impl AsRef<Path>,
+        batch_size: usize,
+        cache_capacity: usize,
+        timeout: Option<Duration>,
+    ) -> Result<ExternalEvaluator, ExternalEvaluatorError> {
+        let stream = UnixStream::connect(path)?;
+        let reader = stream.try_clone()?;
+        let writer = stream.try_clone()?;
+        Ok(ExternalEvaluator::from_streams(
+            Transport::UnixSocket(stream),
+            reader,
+            Box::new(writer),
+            batch_size,
+            cache_capacity,
+            timeout,
+        ))
+    }
+
+    /// Returns the maximal number of solutions per request.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Evaluates the `solution` (or takes the cached values) and returns the value of the
+    /// indicator with the given `name`.
+    pub fn evaluate(
+        &self,
+        solution: &Value,
+        name: &str,
+    ) -> Result<BaseValue, ExternalEvaluatorError> {
+        let mut values = self.evaluate_batch(std::slice::from_ref(solution))?;
+        values.pop().unwrap().remove(name).ok_or_else(|| {
+            ExternalEvaluatorError::InvalidResponse(format!(
+                "missing value of indicator '{}'",
+                name
+            ))
+        })
+    }
+
+    /// Evaluates all `solutions` that are not cached yet (in batches of at most `batch_size`
+    /// solutions) and returns the values of all indicators for each solution.
+    pub fn evaluate_batch(
+        &self,
+        solutions: &[Value],
+    ) -> Result<Vec<HashMap<String, BaseValue>>, ExternalEvaluatorError> {
+        let keys: Vec<String> = solutions.iter().map(Value::to_string).collect();
+        let mut results: HashMap<&str, HashMap<String, BaseValue>> = HashMap::new();
+        let mut missing: Vec<(&str, &Value)> = Vec::new();
+        {
+            let cache = self.cache.lock().unwrap();
+            let mut requested = HashSet::new();
+            for (key, solution) in keys.iter().zip(solutions) {
+                if !requested.insert(key.as_str()) {
+                    continue;
+                }
+                match cache.values.get(key) {
+                    Some(values) => {
+                        results.insert(key, values.clone());
+                    }
+                    None => missing.push((key, solution)),
+                }
+            }
+        }
+
+        for batch in missing.chunks(self.batch_size) {
+            let batch_solutions: Vec<&Value> =
+                batch.iter().map(|(_, solution)| *solution).collect();
+            let batch_values = self.request(&batch_solutions)?;
+            let mut cache = self.cache.lock().unwrap();
+            for ((key, _), values) in batch.iter().zip(batch_values) {
+                cache.insert(key, values.clone(), self.cache_capacity);
+                results.insert(key, values);
+            }
+        }
+
+        Ok(keys
+            .iter()
+            .map(|key| results[key.as_str()].clone())
+            .collect())
+    }
+
+    fn from_streams(
+        transport: Transport,
+        reader: impl Read + Send + 'static,
+        writer: Box<dyn Write + Send>,
+        batch_size: usize,
+        cache_capacity: usize,
+        timeout: Option<Duration>,
+    ) -> ExternalEvaluator {
+        // the answers are read by a separate thread, such that waiting for them can time out
+        let (sender, responses) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let failed = line.is_err();
+                if sender.send(line).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        ExternalEvaluator {
+            connection: Mutex::new(Connection {
+                transport,
+                writer,
+                responses,
+                next_id: 0,
+            }),
+            batch_size: batch_size.max(1),
+            cache_capacity,
+            timeout,
+            cache: Mutex::new(Cache {
+                values: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn request(
+        &self,
+        solutions: &[&Value],
+    ) -> Result<Vec<HashMap<String, BaseValue>>, ExternalEvaluatorError> {
+        let mut connection = self.connection.lock().unwrap();
+        connection.next_id += 1;
+        let id = connection.next_id;
+        let mut line = json!({"id": id, "solutions": solutions}).to_string();
+        line.push('\n');
+        connection.writer.write_all(line.as_bytes())?;
+        connection.writer.flush()?;
+
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let line = match deadline {
+                Some(deadline) => connection
+                    .responses
+                    .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                    .map_err(|error| match error {
+                        RecvTimeoutError::Timeout => ExternalEvaluatorError::Timeout,
+                        RecvTimeoutError::Disconnected => ExternalEvaluatorError::Disconnected,
+                    })?,
+                None => connection
+                    .responses
+                    .recv()
+                    .map_err(|_| ExternalEvaluatorError::Disconnected)?,
+            }?;
+            let response: Value = serde_json::from_str(&line)?;
+            match response.get("id").and_then(Value::as_u64) {
+                // late answer to a request that timed out
+                Some(response_id) if response_id < id => continue,
+                Some(response_id) if response_id == id => {
+                    return parse_values(&response, solutions.len())
+                }
+                _ => {
+                    return Err(ExternalEvaluatorError::InvalidResponse(format!(
+                        "unexpected id in {}",
+                        line
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ExternalEvaluator {
+    fn drop(&mut self) {
+        if let Ok(connection) = self.connection.get_mut() {
+            match &mut connection.transport {
+                Transport::Process(child) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
+                #[cfg(unix)]
+                Transport::UnixSocket(stream) => {
+                    let _ = stream.shutdown(Shutdown::Both);
+                }
+            }
+        }
+    }
+}
+
+impl Cache {
+    fn insert(&mut self, key: &str, values: HashMap<String, BaseValue>, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        if self.values.insert(key.to_string(), values).is_none() {
+            self.insertion_order.push_back(key.to_string());
+            if self.insertion_order.len() > capacity {
+                let oldest = self.insertion_order.pop_front().unwrap();
+                self.values.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Converts the answer of the external process into the values of each solution.
+fn parse_values(
+    response: &Value,
+    number_of_solutions: usize,
+) -> Result<Vec<HashMap<String, BaseValue>>, ExternalEvaluatorError> {
+    if let Some(message) = response.get("error") {
+        return Err(ExternalEvaluatorError::Remote(
+            message
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| message.to_string()),
+        ));
+    }
+    let values = response
+        .get("values")
+        .and_then(Value::as_array)
+        .filter(|values| values.len() == number_of_solutions)
+        .ok_or_else(|| {
+            ExternalEvaluatorError::InvalidResponse(format!(
+                "expected {} values in {}",
+                number_of_solutions, response
+            ))
+        })?;
+    values
+        .iter()
+        .map(|solution_values| {
+            solution_values
+                .as_object()
+                .ok_or_else(|| {
+                    ExternalEvaluatorError::InvalidResponse(format!(
+                        "expected an object of indicator values instead of {}",
+                        solution_values
+                    ))
+                })?
+                .iter()
+                .map(|(name, value)| {
+                    base_value_from_json(value)
+                        .map(|base_value| (name.clone(), base_value))
+                        .ok_or_else(|| {
+                            ExternalEvaluatorError::InvalidResponse(format!(
+                                "invalid value {} of indicator '{}'",
+                                value, name
+                            ))
+                        })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// An [`Indicator`] whose values are computed by an [`ExternalEvaluator`].
+/// * The `name` of the indicator is the key of its value in the answers of the external process.
+/// * If the evaluation fails (e.g., due to a timeout), a warning is reported and the solution
+///   gets the value [`BaseValue::Maximum`], i.e., it is treated as the worst possible solution.
+///
+/// Requires the `external_evaluator` feature.
+pub struct ExternalIndicator<S> {
+    evaluator: Arc<ExternalEvaluator>,
+    name: String,
+    serializer: SolutionSerializer<S>,
+}
+
+impl<S> ExternalIndicator<S> {
+    /// Creates a new [`ExternalIndicator`] that sends the solutions (converted by the
+    /// `serializer`) to the `evaluator` and takes the value of the indicator `name`.
+    pub fn new(
+        evaluator: Arc<ExternalEvaluator>,
+        name: &str,
+        serializer: SolutionSerializer<S>,
+    ) -> ExternalIndicator<S> {
+        ExternalIndicator {
+            evaluator,
+            name: name.to_string(),
+            serializer,
+        }
+    }
+}
+
+impl<S> Indicator<S> for ExternalIndicator<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        match self
+            .evaluator
+            .evaluate(&(self.serializer)(solution), &self.name)
+        {
+            Ok(value) => value,
+            Err(error) => {
+                report!(Warning, "Evaluation of '{}' failed: {}", self.name, error);
+                BaseValue::Maximum
+            }
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl<S> Prefetch<S> for ExternalIndicator<S> {
+    /// Sends the `solutions` that are not cached yet in batches to the external process.
+    fn prefetch(&self, solutions: &[S]) {
+        let solutions: Vec<Value> = solutions.iter().map(&self.serializer).collect();
+        if let Err(error) = self.evaluator.evaluate_batch(&solutions) {
+            report!(Warning, "Prefetching of '{}' failed: {}", self.name, error);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::net::UnixListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::objective::Objective;
+
+    // answers each request with the square and the absolute value of the integer solutions
+    fn serve(listener: UnixListener, requests: Arc<AtomicUsize>) {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        for line in BufReader::new(stream).lines() {
+            let request: Value = serde_json::from_str(&line.unwrap()).unwrap();
+            requests.fetch_add(1, Ordering::Relaxed);
+            let values: Vec<Value> = request["solutions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|solution| {
+                    let x = solution.as_i64().unwrap();
+                    json!({"square": x * x, "abs": x.abs()})
+                })
+                .collect();
+            writeln!(writer, "{}", json!({"id": request["id"], "values": values})).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_external_evaluator_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!(
+            "rapid_solve_external_evaluator_test_{}.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let server_requests = requests.clone();
+        thread::spawn(move || serve(listener, server_requests));
+
+        let evaluator = Arc::new(
+            ExternalEvaluator::connect(&path, 2, 100, Some(Duration::from_secs(10))).unwrap(),
+        );
+        let square = ExternalIndicator::new(
+            evaluator.clone(),
+            "square",
+            Box::new(|solution: &i64| json!(solution)),
+        );
+        let objective = Objective::new_single_indicator_per_level(vec![
+            Box::new(ExternalIndicator::new(
+                evaluator.clone(),
+                "abs",
+                Box::new(|solution: &i64| json!(solution)),
+            )),
+            Box::new(ExternalIndicator::new(
+                evaluator,
+                "square",
+                Box::new(|solution: &i64| json!(solution)),
+            )),
+        ]);
+
+        // 5 distinct solutions in batches of 2
+        square.prefetch(&[1, -2, 3, 1, 4, 5]);
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+
+        // both levels are answered by the cache
+        assert_eq!(
+            objective.evaluate(-2).objective_value().as_vec(),
+            &vec![BaseValue::Integer(2), BaseValue::Integer(4)]
+        );
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+
+        assert_eq!(square.evaluate(&7), BaseValue::Integer(49));
+        assert_eq!(requests.load(Ordering::Relaxed), 4);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_external_evaluator_timeout_and_invalid_response() {
+        // `sleep` never answers
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        let evaluator =
+            ExternalEvaluator::spawn(command, 10, 10, Some(Duration::from_millis(50))).unwrap();
+        assert!(matches!(
+            evaluator.evaluate(&json!(1), "value"),
+            Err(ExternalEvaluatorError::Timeout)
+        ));
+
+        // `cat` echoes the request, which contains no values
+        let evaluator = Arc::new(
+            ExternalEvaluator::spawn(Command::new("cat"), 10, 10, Some(Duration::from_secs(10)))
+                .unwrap(),
+        );
+        assert!(matches!(
+            evaluator.evaluate(&json!(1), "value"),
+            Err(ExternalEvaluatorError::InvalidResponse(_))
+        ));
+        let indicator = ExternalIndicator::new(
+            evaluator,
+            "value",
+            Box::new(|solution: &i64| json!(solution)),
+        );
+        assert_eq!(indicator.evaluate(&1), BaseValue::Maximum);
+    }
+}
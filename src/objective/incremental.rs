@@ -0,0 +1,224 @@
+//! Contains the [`IncrementalIndicator`] trait for indicators whose value can be updated by the
+//! change caused by a modification of the solution, and the [`DeltaObjective`], which evaluates
+//! neighbors by these changes instead of evaluating them from scratch.
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+
+use super::{
+    BaseValue, Coefficient, EvaluatedSolution, Indicator, LinearCombination, Objective,
+    ObjectiveValue, Unit,
+};
+
+/// An [`Indicator`] that can compute the change of its value caused by a modification `M` of the
+/// solution (e.g., the indices of a 3-opt move of a tour) without evaluating the modified
+/// solution from scratch.
+/// * The delta must be exact, i.e., `evaluate(modified solution) = evaluate(solution) +
+///   evaluate_delta(solution, modification)`, otherwise the search works with wrong objective
+///   values.
+/// * The indicator is used by a [`DeltaObjective`].
+pub trait IncrementalIndicator<S, M>: Indicator<S> {
+    /// Returns the change of the value of this indicator if the `modification` is applied to the
+    /// `solution` (new value minus old value).
+    fn evaluate_delta(&self, solution: &S, modification: &M) -> BaseValue;
+}
+
+/// A hierarchy level of a [`DeltaObjective`], i.e., a linear combination of
+/// [`IncrementalIndicators`][IncrementalIndicator] (each multiplied with a [`Coefficient`]).
+pub type IncrementalLevel<S, M> = Vec<(Coefficient, Arc<dyn IncrementalIndicator<S, M>>)>;
+
+/// Makes an [`IncrementalIndicator`] usable as a regular [`Indicator`] of the [`Objective`].
+struct SharedIncrementalIndicator<S, M>(Arc<dyn IncrementalIndicator<S, M>>);
+
+impl<S, M> Indicator<S> for SharedIncrementalIndicator<S, M> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        self.0.evaluate(solution)
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.0.evaluate_exactly(solution)
+    }
+
+    fn name(&self) -> String {
+        self.0.name()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.0.unit()
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.0.bounds()
+    }
+}
+
+/// A hierarchical [`Objective`] whose levels are [`LinearCombinations`][LinearCombination] of
+/// [`IncrementalIndicators`][IncrementalIndicator], such that a neighbor obtained by a
+/// modification `M` can be evaluated by adding the weighted deltas to the objective value of the
+/// current solution.
+/// * Useful if evaluating a solution from scratch is expensive, while the change caused by a
+///   modification is cheap to compute (e.g., a 3-opt move only changes three arcs of a tour).
+/// * The underlying [`Objective`] (see [`objective`][DeltaObjective::objective]) evaluates
+///   solutions from scratch and is used by the solvers as usual.
+/// * Used by the [`DeltaMinimizer`][crate::heuristics::local_search::local_improver::DeltaMinimizer]
+///   for neighborhoods that yield `(neighbor, modification)` pairs and by the
+///   [`DeltaTabuMinimizer`][crate::heuristics::tabu_search::tabu_improver::DeltaTabuMinimizer]
+///   for move-based tabu neighborhoods.
+pub struct DeltaObjective<S, M> {
+    objective: Arc<Objective<S>>,
+    hierarchy_levels: Vec<IncrementalLevel<S, M>>,
+}
+
+impl<S: 'static, M: 'static> DeltaObjective<S, M> {
+    /// Creates a new [`DeltaObjective`] with the given levels of weighted
+    /// [`IncrementalIndicators`][IncrementalIndicator]. The most important level is the first
+    /// entry of the vector.
+    /// * Panics if `hierarchy_levels` is empty.
+    pub fn new(hierarchy_levels: Vec<IncrementalLevel<S, M>>) -> DeltaObjective<S, M> {
+        let objective = Objective::new(
+            hierarchy_levels
+                .iter()
+                .map(|level| {
+                    LinearCombination::new(
+                        level
+                            .iter()
+                            .map(|(coefficient, indicator)| {
+                                (
+                                    *coefficient,
+                                    Box::new(SharedIncrementalIndicator(indicator.clone()))
+                                        as Box<dyn Indicator<S>>,
+                                )
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        );
+        DeltaObjective {
+            objective: Arc::new(objective),
+            hierarchy_levels,
+        }
+    }
+
+    /// Creates a new [`DeltaObjective`] with a single [`IncrementalIndicator`] as the only
+    /// hierarchy level.
+    pub fn new_single_indicator(
+        indicator: Arc<dyn IncrementalIndicator<S, M>>,
+    ) -> DeltaObjective<S, M> {
+        DeltaObjective::new(alloc::vec![alloc::vec![(Coefficient::from(1), indicator)]])
+    }
+}
+
+impl<S, M> DeltaObjective<S, M> {
+    /// Returns the underlying [`Objective`], which evaluates solutions from scratch.
+    pub fn objective(&self) -> Arc<Objective<S>> {
+        self.objective.clone()
+    }
+
+    /// Computes the [`ObjectiveValue`] of the solution obtained by applying the `modification` to
+    /// the `solution` from the [`ObjectiveValue`] of the `solution` and the deltas of the
+    /// indicators. The modified solution itself is not needed.
+    pub fn evaluate_modification(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        modification: &M,
+    ) -> ObjectiveValue {
+        ObjectiveValue::new(
+            self.hierarchy_levels
+                .iter()
+                .zip(solution.objective_value().iter())
+                .map(|(level, old_value)| {
+                    level
+                        .iter()
+                        .fold(*old_value, |value, (coefficient, indicator)| {
+                            value
+                                + coefficient
+                                    * indicator.evaluate_delta(solution.solution(), modification)
+                        })
+                })
+                .collect(),
+        )
+    }
+
+    /// Wraps the `neighbor`, which is obtained by applying the `modification` to the `solution`,
+    /// into an [`EvaluatedSolution`] (see
+    /// [`evaluate_modification`][DeltaObjective::evaluate_modification]).
+    pub fn evaluate_neighbor(
+        &self,
+        solution: &EvaluatedSolution<S>,
+        neighbor: S,
+        modification: &M,
+    ) -> EvaluatedSolution<S> {
+        EvaluatedSolution::new(neighbor, self.evaluate_modification(solution, modification))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    // the solution is a vector of integers, a modification adds a value at an index
+    type Modification = (usize, i64);
+
+    struct SumOfSquares;
+
+    impl Indicator<Vec<i64>> for SumOfSquares {
+        fn evaluate(&self, solution: &Vec<i64>) -> BaseValue {
+            BaseValue::Integer(solution.iter().map(|x| x * x).sum())
+        }
+        fn name(&self) -> String {
+            String::from("SumOfSquares")
+        }
+    }
+
+    impl IncrementalIndicator<Vec<i64>, Modification> for SumOfSquares {
+        fn evaluate_delta(&self, solution: &Vec<i64>, &(index, value): &Modification) -> BaseValue {
+            let old = solution[index];
+            BaseValue::Integer((old + value).pow(2) - old.pow(2))
+        }
+    }
+
+    struct Sum;
+
+    impl Indicator<Vec<i64>> for Sum {
+        fn evaluate(&self, solution: &Vec<i64>) -> BaseValue {
+            BaseValue::Integer(solution.iter().sum())
+        }
+        fn name(&self) -> String {
+            String::from("Sum")
+        }
+    }
+
+    impl IncrementalIndicator<Vec<i64>, Modification> for Sum {
+        fn evaluate_delta(&self, _solution: &Vec<i64>, &(_, value): &Modification) -> BaseValue {
+            BaseValue::Integer(value)
+        }
+    }
+
+    #[test]
+    fn test_delta_objective() {
+        let delta_objective: DeltaObjective<Vec<i64>, Modification> = DeltaObjective::new(vec![
+            vec![(Coefficient::from(1), Arc::new(SumOfSquares))],
+            vec![
+                (Coefficient::from(2), Arc::new(Sum)),
+                (Coefficient::from(-1), Arc::new(SumOfSquares)),
+            ],
+        ]);
+        let objective = delta_objective.objective();
+        let solution = objective.evaluate(vec![1, -2, 3]);
+
+        for modification in [(0, 1), (1, 2), (2, -5)] {
+            let mut neighbor = solution.solution().clone();
+            neighbor[modification.0] += modification.1;
+            assert_eq!(
+                delta_objective.evaluate_modification(&solution, &modification),
+                *objective.evaluate(neighbor.clone()).objective_value()
+            );
+            assert_eq!(
+                delta_objective
+                    .evaluate_neighbor(&solution, neighbor.clone(), &modification)
+                    .solution(),
+                &neighbor
+            );
+        }
+    }
+}
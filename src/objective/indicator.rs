@@ -1,5 +1,8 @@
 //! Contains the [`Indicator`] trait, which is used to evaluate a specific quality of a solution.
+use alloc::string::String;
+
 use super::base_value::BaseValue;
+use super::unit::Unit;
 
 /// An atomic quality of the solution. E.g., `total_distance` or `number_of_tours`.
 pub trait Indicator<S>: Send + Sync {
@@ -10,4 +13,29 @@ pub trait Indicator<S>: Send + Sync {
     /// Returns the name of the indicator, which is used to display an
     /// [`ObjectiveValue`][`super::objective_value::ObjectiveValue`].
     fn name(&self) -> String;
+
+    /// Evaluates the provided solution exactly. The default is [`evaluate`][Indicator::evaluate]
+    /// and only indicators that estimate their value (e.g., a
+    /// [`SampledIndicator`][super::SampledIndicator]) compute the exact value here (see
+    /// [`Objective::evaluate_exactly`][super::Objective::evaluate_exactly]).
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.evaluate(solution)
+    }
+
+    /// Returns the [`Unit`] of the values of this indicator. The default is `None`, i.e., the
+    /// indicator has no unit and is not considered by the unit consistency checks.
+    fn unit(&self) -> Option<Unit> {
+        None
+    }
+
+    /// Returns the expected minimum and maximum value `(min, max)` of this indicator. The default
+    /// is `None`, i.e., the domain is unknown.
+    /// * The bounds do not need to be tight, but should be in the right magnitude, as they are
+    ///   used to scale the acceptance of worse solutions of some solvers (see
+    ///   [`Objective::level_ranges`][super::Objective::level_ranges]).
+    /// * To declare bounds for an existing indicator, wrap it into a
+    ///   [`BoundedIndicator`][super::BoundedIndicator].
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        None
+    }
 }
@@ -0,0 +1,160 @@
+//! Contains the import of [`ObjectiveValues`][super::ObjectiveValue] from JSON (see
+//! [`Objective::objective_value_from_json`][super::Objective::objective_value_from_json]) and the
+//! corresponding [`ObjectiveValueJsonError`].
+use alloc::{format, string::String};
+use core::{error::Error, fmt};
+
+use super::base_value::{BaseValue, DECIMAL_PLACES};
+
+/// Error that is returned if a JSON value cannot be converted to an
+/// [`ObjectiveValue`][super::ObjectiveValue] of an [`Objective`][super::Objective].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjectiveValueJsonError {
+    /// The JSON value is not an object.
+    NotAnObject,
+    /// A level of the objective is missing in the JSON object.
+    MissingLevel {
+        /// The display of the level.
+        level: String,
+    },
+    /// The JSON object contains a key that is not a level of the objective.
+    UnknownLevel {
+        /// The key in the JSON object.
+        level: String,
+    },
+    /// Two levels of the objective have the same display, so they cannot be distinguished in the
+    /// JSON object.
+    DuplicateLevel {
+        /// The display of the levels.
+        level: String,
+    },
+    /// The value of a level cannot be converted to a [`BaseValue`] (of the kind of the level).
+    InvalidValue {
+        /// The display of the level.
+        level: String,
+        /// The JSON value.
+        value: String,
+    },
+}
+
+impl fmt::Display for ObjectiveValueJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectiveValueJsonError::NotAnObject => {
+                write!(f, "the objective value must be a JSON object")
+            }
+            ObjectiveValueJsonError::MissingLevel { level } => {
+                write!(f, "missing level '{}'", level)
+            }
+            ObjectiveValueJsonError::UnknownLevel { level } => {
+                write!(f, "unknown level '{}'", level)
+            }
+            ObjectiveValueJsonError::DuplicateLevel { level } => {
+                write!(f, "several levels are displayed as '{}'", level)
+            }
+            ObjectiveValueJsonError::InvalidValue { level, value } => {
+                write!(f, "invalid value {} of level '{}'", value, level)
+            }
+        }
+    }
+}
+
+impl Error for ObjectiveValueJsonError {}
+
+/// Converts a JSON value written by
+/// [`objective_value_to_json`][super::Objective::objective_value_to_json] back to a
+/// [`BaseValue`]. Returns `None` if this is not possible.
+pub(super) fn base_value_from_json(value: &serde_json::Value) -> Option<BaseValue> {
+    match value {
+        serde_json::Value::Number(number) if number.is_f64() => {
+            number.as_f64().map(BaseValue::Float)
+        }
+        serde_json::Value::Number(number) => number.as_i64().map(BaseValue::Integer),
+        serde_json::Value::String(string) => match string.as_str() {
+            "Zero" => Some(BaseValue::Zero),
+            "Maximum" => Some(BaseValue::Maximum),
+            #[cfg(feature = "rapid_time")]
+            duration => duration_from_string(duration).map(BaseValue::Duration),
+            #[cfg(not(feature = "rapid_time"))]
+            _ => None,
+        },
+        serde_json::Value::Object(object) => match (object.get("count"), object.get("decimal")) {
+            (Some(count), None) => count.as_u64().map(BaseValue::Count),
+            (None, Some(serde_json::Value::String(decimal))) => decimal_from_string(decimal),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts the `value` read from JSON to the kind of the `kind` value (see
+/// [`LinearCombination::value_kind`][super::LinearCombination::value_kind]), e.g., `3` to `3.0`
+/// for a float level. Returns `None` if this is not possible without loss (e.g., a float for an
+/// integer level). [`BaseValue::Zero`] and [`BaseValue::Maximum`] fit every kind.
+pub(super) fn coerce_to_kind(value: BaseValue, kind: &BaseValue) -> Option<BaseValue> {
+    match (value, kind) {
+        (BaseValue::Zero | BaseValue::Maximum, _) => Some(value),
+        (BaseValue::Integer(_), BaseValue::Integer(_))
+        | (BaseValue::Count(_), BaseValue::Count(_))
+        | (BaseValue::Decimal(_), BaseValue::Decimal(_))
+        | (BaseValue::Float(_), BaseValue::Float(_)) => Some(value),
+        #[cfg(feature = "rapid_time")]
+        (BaseValue::Duration(_), BaseValue::Duration(_)) => Some(value),
+        (BaseValue::Count(count), BaseValue::Integer(_)) => {
+            i64::try_from(count).ok().map(BaseValue::Integer)
+        }
+        (BaseValue::Integer(integer), BaseValue::Count(_)) => {
+            u64::try_from(integer).ok().map(BaseValue::Count)
+        }
+        (BaseValue::Integer(integer), BaseValue::Float(_)) => {
+            Some(BaseValue::Float(integer as f64))
+        }
+        (BaseValue::Count(count), BaseValue::Float(_)) => Some(BaseValue::Float(count as f64)),
+        (BaseValue::Integer(integer), BaseValue::Decimal(_)) => {
+            Some(BaseValue::decimal(i128::from(integer), 0))
+        }
+        (BaseValue::Count(count), BaseValue::Decimal(_)) => {
+            Some(BaseValue::decimal(i128::from(count), 0))
+        }
+        _ => None,
+    }
+}
+
+/// Parses the display of a [`BaseValue::Decimal`] (e.g., `"-12.5000"`).
+fn decimal_from_string(string: &str) -> Option<BaseValue> {
+    let (negative, digits) = match string.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, string),
+    };
+    let (integer_part, fractional_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if integer_part.is_empty()
+        || fractional_part.len() > DECIMAL_PLACES as usize
+        || !integer_part
+            .chars()
+            .chain(fractional_part.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let value: i128 = format!("{}{}", integer_part, fractional_part)
+        .parse()
+        .ok()?;
+    let value = value.checked_mul(10_i128.pow(DECIMAL_PLACES - fractional_part.len() as u32))?;
+    Some(BaseValue::Decimal(if negative { -value } else { value }))
+}
+
+/// Parses the display of a [`rapid_time::Duration`] (`"Inf"`, `"hh:mmh"` or `"hh:mm:ssh"`).
+#[cfg(feature = "rapid_time")]
+fn duration_from_string(string: &str) -> Option<rapid_time::Duration> {
+    if string == "Inf" {
+        return Some(rapid_time::Duration::Infinity);
+    }
+    let string = string.strip_suffix('h')?;
+    let parts: alloc::vec::Vec<&str> = string.split(':').collect();
+    let is_valid = (2..=3).contains(&parts.len())
+        && parts[0].parse::<u64>().is_ok()
+        && parts[1..]
+            .iter()
+            .all(|part| part.parse::<u8>().is_ok_and(|number| number < 60));
+    is_valid.then(|| rapid_time::Duration::new(string))
+}
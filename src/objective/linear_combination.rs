@@ -1,32 +1,240 @@
 //! Contains the [`LinearCombination`] struct, which represents a linear combination of
 //! [`Indicators`][`Indicator`].
-use std::fmt;
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{error::Error, fmt};
 
-use super::{base_value::BaseValue, coefficient::Coefficient, indicator::Indicator};
+use super::{
+    base_value::BaseValue,
+    coefficient::Coefficient,
+    indicator::Indicator,
+    unit::{Unit, UnitMismatch},
+};
+
+/// Determines what [`LinearCombination::evaluate`] does if the products or the sum of
+/// [`Integer`][BaseValue::Integer], [`Count`][BaseValue::Count] or
+/// [`Decimal`][BaseValue::Decimal] values do not fit into their type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Uses the plain arithmetic operators (default), i.e., overflows of
+    /// [`Integer`][BaseValue::Integer] values panic in debug builds and wrap around in release
+    /// builds, while overflows of [`Count`][BaseValue::Count] and [`Decimal`][BaseValue::Decimal]
+    /// values always panic.
+    #[default]
+    Unchecked,
+    /// Panics with an [`AggregationOverflow`] message (also in release builds).
+    Panic,
+    /// Returns [`BaseValue::Maximum`], such that the solution is worse than every solution
+    /// without overflow (also if the overflow is in negative direction).
+    SaturateToMaximum,
+}
+
+/// Error that is returned by [`LinearCombination::checked_evaluate`] if the aggregation
+/// overflows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregationOverflow {
+    /// The display of the linear combination in which the overflow occurs.
+    pub linear_combination: String,
+    /// The name of the indicator whose (weighted) value caused the overflow.
+    pub indicator: String,
+}
+
+impl fmt::Display for AggregationOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "overflow in '{}' when adding the weighted value of '{}'",
+            self.linear_combination, self.indicator
+        )
+    }
+}
+
+impl Error for AggregationOverflow {}
 
 /// A linear combination of [`Indicators`][`Indicator`] (each equipped with an [`Coefficient`]). Forms a level of the [`Objective`][`super::Objective`].
 pub struct LinearCombination<S> {
     // valueType must be multiplyable with Coefficient
     summands: Vec<(Coefficient, Box<dyn Indicator<S>>)>,
+    name: Option<String>,
+    overflow_policy: OverflowPolicy,
 }
 
 impl<S> LinearCombination<S> {
     /// Evaluate the linear combination for a given solution.
+    /// * Overflows are handled according to the [`OverflowPolicy`] (see
+    ///   [`with_overflow_policy`][LinearCombination::with_overflow_policy]).
     pub fn evaluate(&self, solution: &S) -> BaseValue {
+        self.aggregate(solution, false)
+    }
+
+    /// Evaluate the linear combination for a given solution by
+    /// [`Indicator::evaluate_exactly`], i.e., estimating indicators (e.g., a
+    /// [`SampledIndicator`][super::SampledIndicator]) compute their exact value.
+    pub fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.aggregate(solution, true)
+    }
+
+    /// Evaluate the linear combination for a given solution, but returns an
+    /// [`AggregationOverflow`] error if a product or a partial sum of integral values overflows
+    /// (independent of the [`OverflowPolicy`]).
+    pub fn checked_evaluate(&self, solution: &S) -> Result<BaseValue, AggregationOverflow> {
+        self.checked_aggregate(solution, false)
+    }
+
+    fn aggregate(&self, solution: &S, exactly: bool) -> BaseValue {
+        match self.overflow_policy {
+            OverflowPolicy::Unchecked => self
+                .summands
+                .iter()
+                .map(|(coefficient, indicator)| {
+                    coefficient * indicator_value(indicator.as_ref(), solution, exactly)
+                })
+                .sum(),
+            OverflowPolicy::Panic => self
+                .checked_aggregate(solution, exactly)
+                .unwrap_or_else(|overflow| panic!("{}", overflow)),
+            OverflowPolicy::SaturateToMaximum => self
+                .checked_aggregate(solution, exactly)
+                .unwrap_or(BaseValue::Maximum),
+        }
+    }
+
+    fn checked_aggregate(
+        &self,
+        solution: &S,
+        exactly: bool,
+    ) -> Result<BaseValue, AggregationOverflow> {
         self.summands
             .iter()
-            .map(|(coefficient, indicator)| coefficient * indicator.evaluate(solution))
-            .sum()
+            .try_fold(BaseValue::Zero, |sum, (coefficient, indicator)| {
+                coefficient
+                    .checked_mul(indicator_value(indicator.as_ref(), solution, exactly))
+                    .and_then(|summand| sum.checked_add(summand))
+                    .ok_or_else(|| AggregationOverflow {
+                        linear_combination: self.to_string(),
+                        indicator: indicator.name(),
+                    })
+            })
     }
 
     /// Creates a new linear combination from a list of summands.
     pub fn new(summands: Vec<(Coefficient, Box<dyn Indicator<S>>)>) -> LinearCombination<S> {
-        LinearCombination { summands }
+        LinearCombination {
+            summands,
+            name: None,
+            overflow_policy: OverflowPolicy::default(),
+        }
+    }
+
+    /// Sets the [`OverflowPolicy`] of the linear combination, e.g., if many large
+    /// [`Integer`][BaseValue::Integer] values are summed up.
+    pub fn with_overflow_policy(self, overflow_policy: OverflowPolicy) -> LinearCombination<S> {
+        LinearCombination {
+            overflow_policy,
+            ..self
+        }
+    }
+
+    /// Returns the [`OverflowPolicy`] of the linear combination.
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    /// Names the linear combination, e.g., `"Cost"`. The name is displayed instead of the
+    /// weighted indicators (e.g., when an [`ObjectiveValue`][super::ObjectiveValue] is printed
+    /// or converted to JSON).
+    pub fn with_name(self, name: &str) -> LinearCombination<S> {
+        LinearCombination {
+            name: Some(String::from(name)),
+            ..self
+        }
+    }
+
+    /// Returns the name of the linear combination (see
+    /// [`with_name`][LinearCombination::with_name]).
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns `true` if the linear combination has no summand.
+    pub fn is_empty(&self) -> bool {
+        self.summands.is_empty()
+    }
+
+    /// Returns the expected range (maximum minus minimum) of the values of this linear
+    /// combination, i.e., the sum of the ranges of the declared
+    /// [`bounds`][Indicator::bounds] multiplied with the absolute values of the coefficients.
+    /// * Returns `None` if an indicator has no declared bounds.
+    pub fn range(&self) -> Option<BaseValue> {
+        self.summands
+            .iter()
+            .map(|(coefficient, indicator)| {
+                indicator
+                    .bounds()
+                    .map(|(min, max)| coefficient.abs() * (max - min))
+            })
+            .sum()
+    }
+
+    /// Returns a value of the kind (e.g., [`Integer`][BaseValue::Integer] or
+    /// [`Float`][BaseValue::Float]) this linear combination evaluates to, derived from the
+    /// declared [`bounds`][Indicator::bounds] of its first bounded indicator. Returns `None` if
+    /// no indicator has declared bounds.
+    #[cfg(feature = "serde_json")]
+    pub(super) fn value_kind(&self) -> Option<BaseValue> {
+        self.summands
+            .iter()
+            .filter_map(|(coefficient, indicator)| {
+                indicator
+                    .bounds()
+                    .and_then(|(min, _)| coefficient.checked_mul(min))
+            })
+            .find(|value| !matches!(value, BaseValue::Zero | BaseValue::Maximum))
+    }
+
+    /// Returns the common [`Unit`] of all indicators of this linear combination.
+    /// * Indicators without unit are ignored.
+    /// * Returns `Ok(None)` if no indicator has a unit.
+    /// * Returns an [`UnitMismatch`] error if two indicators have different units.
+    pub fn unit(&self) -> Result<Option<Unit>, UnitMismatch> {
+        let units: Vec<(String, Unit)> = self
+            .summands
+            .iter()
+            .filter_map(|(_, indicator)| indicator.unit().map(|unit| (indicator.name(), unit)))
+            .collect();
+        match units.first() {
+            None => Ok(None),
+            Some((_, first_unit)) => {
+                if units.iter().all(|(_, unit)| unit == first_unit) {
+                    Ok(Some(*first_unit))
+                } else {
+                    Err(UnitMismatch {
+                        linear_combination: self.to_string(),
+                        units,
+                    })
+                }
+            }
+        }
+    }
+}
+
+fn indicator_value<S>(indicator: &dyn Indicator<S>, solution: &S, exactly: bool) -> BaseValue {
+    if exactly {
+        indicator.evaluate_exactly(solution)
+    } else {
+        indicator.evaluate(solution)
     }
 }
 
 impl<S> fmt::Display for LinearCombination<S> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = &self.name {
+            return write!(f, "{}", name);
+        }
         write!(
             f,
             "{}",
@@ -1,26 +1,116 @@
 //! In this module, the hierarchical [`Objective`] of an optimization problem is defined.
 //! * The objective is constant throughout the optimization and consists of several levels of
-//! [`LinearCombinations`][`LinearCombination`] of [`Indicators`][`Indicator`] (each multiplied
-//! with a [`Coefficient`]).
+//!   [`LinearCombinations`][`LinearCombination`] of [`Indicators`][`Indicator`] (each multiplied
+//!   with a [`Coefficient`]).
 //! * With an [`Objective`] instance, each solution instance can be evaluated, which equips the
-//! solution with an [`ObjectiveValue`] (a vector of [`BaseValues`][`BaseValue`], one per level) by wrapping
-//! it into an [`EvaluatedSolution`].
+//!   solution with an [`ObjectiveValue`] (a vector of [`BaseValues`][`BaseValue`], one per level) by wrapping
+//!   it into an [`EvaluatedSolution`].
+//! * Hard constraints are modeled as the most important levels, whose values are the violations
+//!   of [`Constraints`][Constraint]. The [`ObjectiveBuilder`] places these levels in front of the
+//!   soft objective levels and [`Objective::is_feasible`] checks whether all violations are zero.
+//!   [`Objective::explain`] lists the violated constraints with their violating elements as
+//!   [`ViolationReports`][ViolationReport].
+//! * Instead of nesting vectors of weighted indicators, an objective can be built level by level
+//!   (with named levels for a nicer output) by the fluent [`ObjectiveBuilder`] (see
+//!   [`Objective::builder`]).
+//! * If the objective is expensive, a [`SurrogateScreening`] with a cheap surrogate objective can
+//!   pre-screen the neighbors, such that only the most promising ones are evaluated exactly.
+//! * If the objective is a vectorized computation (e.g., on a GPU), a [`BatchObjective`] lets the
+//!   parallel improvers evaluate the neighbors in batches instead of one at a time.
+//! * Indicators backed by simulations or external processes can be wrapped by an
+//!   [`ExpensiveIndicator`], which caches the values per solution and evaluates batches of
+//!   solutions concurrently.
+//! * With the `external_evaluator` feature, `ExternalIndicators` delegate the evaluation to an
+//!   external process (e.g., an objective written in another language) via an
+//!   `ExternalEvaluator`.
+//! * If the change of the objective value caused by a modification of a solution is cheap to
+//!   compute (e.g., the length change of a 3-opt move), a [`DeltaObjective`] of
+//!   [`IncrementalIndicators`][IncrementalIndicator] evaluates neighbors incrementally.
+//! * Indicators can declare their expected minimum and maximum value (directly via
+//!   [`Indicator::bounds`] or by a [`BoundedIndicator`]), such that the solvers can derive
+//!   temperatures and thresholds from the [`level_ranges`][Objective::level_ranges] instead of
+//!   hand-tuned magic numbers.
+//! * Fairness-style indicators (e.g., the maximum lateness or the standard deviation of the
+//!   workloads) can be defined by a [`StatisticalIndicator`], which applies a [`Statistic`] to
+//!   the values of the elements of a solution.
+//! * For huge solutions, a [`SampledIndicator`] estimates the sum of the element values from a
+//!   random sample of the elements, and [`Objective::evaluate_exactly`] re-evaluates the best
+//!   solution found by the approximate search exactly.
 
 mod base_value;
+mod batch_objective;
+mod bounded_indicator;
+mod builder;
 mod coefficient;
+mod constraint;
 mod evaluated_solution;
+#[cfg(feature = "std")]
+mod expensive_indicator;
+#[cfg(feature = "external_evaluator")]
+mod external_evaluator;
+mod incremental;
 mod indicator;
+#[cfg(feature = "serde_json")]
+mod json;
 mod linear_combination;
 mod objective_value;
+#[cfg(feature = "rand")]
+mod sampled_indicator;
+#[cfg(feature = "std")]
+mod statistical_indicator;
+mod surrogate;
 #[cfg(test)]
 mod tests;
+mod unit;
 
-pub use base_value::BaseValue;
+pub use base_value::{BaseValue, DECIMAL_PLACES};
+#[cfg(feature = "std")]
+pub(crate) use batch_objective::best_of_batches;
+pub use batch_objective::BatchObjective;
+pub use bounded_indicator::BoundedIndicator;
+pub use builder::{InvalidObjective, LevelBuilder, ObjectiveBuilder};
 pub use coefficient::Coefficient;
+pub use constraint::{Constraint, ViolationReport, ViolationReporter};
 pub use evaluated_solution::EvaluatedSolution;
+pub use evaluated_solution::MinByObjective;
+#[cfg(not(feature = "rayon"))]
+pub use evaluated_solution::MinByObjective as ParallelMinByObjective;
+#[cfg(feature = "rayon")]
+pub use evaluated_solution::ParallelMinByObjective;
+#[cfg(feature = "std")]
+pub use expensive_indicator::ExpensiveIndicator;
+#[cfg(feature = "std")]
+pub use expensive_indicator::Prefetch;
+#[cfg(feature = "external_evaluator")]
+pub use external_evaluator::{
+    ExternalEvaluator, ExternalEvaluatorError, ExternalIndicator, SolutionSerializer,
+};
+pub use incremental::{DeltaObjective, IncrementalIndicator, IncrementalLevel};
 pub use indicator::Indicator;
-pub use linear_combination::LinearCombination;
-pub use objective_value::ObjectiveValue;
+#[cfg(feature = "serde_json")]
+pub use json::ObjectiveValueJsonError;
+pub use linear_combination::{AggregationOverflow, LinearCombination, OverflowPolicy};
+pub use objective_value::{ObjectiveValue, ObjectiveValueDisplay};
+#[cfg(feature = "rand")]
+pub use sampled_indicator::{NumberOfElements, SampledIndicator, ValueOfElement};
+#[cfg(feature = "std")]
+pub use statistical_indicator::{ElementValues, Statistic, StatisticalIndicator};
+pub use surrogate::SurrogateScreening;
+pub use unit::{Unit, UnitConversion, UnitMismatch};
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::cmp::Ordering;
+
+/// Type for the secondary preference between two solutions with equal [`ObjectiveValues`][ObjectiveValue]
+/// (see [`Objective::with_preference`]). Returns [`Ordering::Less`] if the first solution is
+/// preferred.
+pub type PreferenceFunction<S> = Box<dyn Fn(&S, &S) -> Ordering + Send + Sync>;
 
 /// Defines the objective of an optimization problem, which is constant throughout the
 /// optimization. Afterwards an objective instance can be used to evaluate every solution object.
@@ -35,6 +125,9 @@ pub use objective_value::ObjectiveValue;
 /// `S`: the solution type for which the objective is defined.
 pub struct Objective<S> {
     hierarchy_levels: Vec<LinearCombination<S>>,
+    preference: Option<PreferenceFunction<S>>,
+    number_of_constraint_levels: usize,
+    constraints: Vec<Vec<Arc<Constraint<S>>>>,
 }
 
 // methods
@@ -50,6 +143,92 @@ impl<S> Objective<S> {
         EvaluatedSolution::new(solution, ObjectiveValue::new(objective_value_hierarchy))
     }
 
+    /// Consumes the solution and evaluates it like [`evaluate`][Objective::evaluate], but by
+    /// [`Indicator::evaluate_exactly`], such that estimating indicators (e.g., a
+    /// [`SampledIndicator`]) compute their exact value.
+    /// * Typically applied to the best solution found by a search with estimated indicators,
+    ///   e.g., `objective.evaluate_exactly(solver.solve(initial_solution).unwrap())`.
+    pub fn evaluate_exactly(&self, solution: S) -> EvaluatedSolution<S> {
+        let objective_value_hierarchy: Vec<BaseValue> = self
+            .hierarchy_levels
+            .iter()
+            .map(|level| level.evaluate_exactly(&solution))
+            .collect();
+
+        EvaluatedSolution::new(solution, ObjectiveValue::new(objective_value_hierarchy))
+    }
+
+    /// Compares two [`EvaluatedSolutions`][EvaluatedSolution] by their [`ObjectiveValues`][ObjectiveValue].
+    /// Only if the objective values are equal, the [`PreferenceFunction`] (if set via
+    /// [`with_preference`][Objective::with_preference]) decides.
+    pub fn compare(&self, a: &EvaluatedSolution<S>, b: &EvaluatedSolution<S>) -> Ordering {
+        a.objective_value()
+            .cmp(b.objective_value())
+            .then_with(|| match &self.preference {
+                Some(preference) => preference(a.solution(), b.solution()),
+                None => Ordering::Equal,
+            })
+    }
+
+    /// Returns `true` if `a` is better than `b` according to [`compare`][Objective::compare],
+    /// i.e., `a` has a smaller [`ObjectiveValue`] or the same [`ObjectiveValue`] and is
+    /// preferred.
+    pub fn is_better(&self, a: &EvaluatedSolution<S>, b: &EvaluatedSolution<S>) -> bool {
+        self.compare(a, b).is_lt()
+    }
+
+    /// Returns `true` if all hard constraint levels (see [`ObjectiveBuilder`]) of the
+    /// [`ObjectiveValue`] of the `solution` are zero, i.e., no [`Constraint`] is violated.
+    /// * Always `true` if the objective has no hard constraint levels.
+    pub fn is_feasible(&self, solution: &EvaluatedSolution<S>) -> bool {
+        (0..self.number_of_constraint_levels)
+            .all(|level| solution.objective_value().level_is_zero(level))
+    }
+
+    /// Explains the violated hard constraints of the `solution` by one [`ViolationReport`] per
+    /// violated [`Constraint`] (in the order of the levels and constraints), including the
+    /// violating elements named by the [`ViolationReporter`] of the constraint.
+    /// * Only the constraints of levels with non-zero value are evaluated.
+    /// * Empty if the solution is feasible or the objective was not built by an
+    ///   [`ObjectiveBuilder`] with hard constraint levels.
+    pub fn explain(&self, solution: &EvaluatedSolution<S>) -> Vec<ViolationReport> {
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter(|(level, _)| !solution.objective_value().level_is_zero(*level))
+            .flat_map(|(level, constraints)| {
+                constraints.iter().filter_map(move |constraint| {
+                    let violation = constraint.evaluate(solution.solution());
+                    if violation.is_zero() {
+                        return None;
+                    }
+                    Some(ViolationReport {
+                        constraint: constraint.name(),
+                        level,
+                        violation,
+                        violating_elements: constraint.violating_elements(solution.solution()),
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the number of hard constraint levels, which are the most important levels of the
+    /// objective (see [`ObjectiveBuilder`]).
+    pub fn number_of_constraint_levels(&self) -> usize {
+        self.number_of_constraint_levels
+    }
+
+    /// Returns the label of each hierarchy level, which is the name of the level (see
+    /// [`with_level_names`][Objective::with_level_names]) or otherwise the formula of its
+    /// [`LinearCombination`] (e.g., `"Distance + 2*Overtime"`).
+    pub fn level_names(&self) -> Vec<String> {
+        self.hierarchy_levels
+            .iter()
+            .map(|level| level.to_string())
+            .collect()
+    }
+
     /// Returns the zero [`ObjectiveValue`] ([`BaseValue::Zero`] on each level).
     pub fn zero(&self) -> ObjectiveValue {
         ObjectiveValue::new(vec![BaseValue::Zero; self.hierarchy_levels.len()])
@@ -60,35 +239,76 @@ impl<S> Objective<S> {
         ObjectiveValue::new(vec![BaseValue::Maximum; self.hierarchy_levels.len()])
     }
 
-    /// Prints the [`ObjectiveValue`].
+    /// Returns the expected range of each hierarchy level derived from the declared
+    /// [`bounds`][Indicator::bounds] of the indicators (see [`LinearCombination::range`]).
+    /// * A level is `None` if one of its indicators has no declared bounds.
+    /// * Used to scale the acceptance of worse solutions independently of the magnitude of the
+    ///   objective values, e.g., by
+    ///   [`initialize_from_bounds`][crate::heuristics::threshold_accepting::ThresholdAcceptingSolver::initialize_from_bounds].
+    pub fn level_ranges(&self) -> Vec<Option<BaseValue>> {
+        self.hierarchy_levels
+            .iter()
+            .map(|level| level.range())
+            .collect()
+    }
+
+    /// Prints the [`ObjectiveValue`] (see [`format_objective_value`][Objective::format_objective_value]).
+    #[cfg(feature = "std")]
     pub fn print_objective_value(&self, objective_value: &ObjectiveValue) {
-        for (level, value) in self.hierarchy_levels.iter().zip(objective_value.iter()) {
-            println!(" * {}: {}", level, value);
-        }
+        println!("{}", self.format_objective_value(objective_value));
     }
 
-    /// Prints the [`ObjectiveValue`] with a comparison to another [`ObjectiveValue`].
+    /// Prints the [`ObjectiveValue`] with a comparison to another [`ObjectiveValue`] (see
+    /// [`format_objective_value_with_comparison`][Objective::format_objective_value_with_comparison]).
+    #[cfg(feature = "std")]
     pub fn print_objective_value_with_comparison(
         &self,
         objective_value: &ObjectiveValue,
         comparison: &ObjectiveValue,
     ) {
-        for ((level, value), comparison_value) in self
-            .hierarchy_levels
+        println!(
+            "{}",
+            self.format_objective_value_with_comparison(objective_value, comparison)
+        );
+    }
+
+    /// Formats the [`ObjectiveValue`] with one line per level (see
+    /// [`ObjectiveValue::display`]).
+    #[cfg(feature = "std")]
+    pub fn format_objective_value(&self, objective_value: &ObjectiveValue) -> String {
+        objective_value.display(self).to_string()
+    }
+
+    /// Formats the [`ObjectiveValue`] with a comparison to another [`ObjectiveValue`] with one
+    /// line per level.
+    #[cfg(feature = "std")]
+    pub fn format_objective_value_with_comparison(
+        &self,
+        objective_value: &ObjectiveValue,
+        comparison: &ObjectiveValue,
+    ) -> String {
+        self.hierarchy_levels
             .iter()
             .zip(objective_value.iter())
             .zip(comparison.iter())
-        {
-            println!(
-                " * {}: {} {}",
-                level,
-                value,
-                value.print_difference(*comparison_value)
-            );
-        }
+            .map(|((level, value), comparison_value)| {
+                format!(
+                    " * {}: {} {}",
+                    level,
+                    value,
+                    value.print_difference(*comparison_value)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 
     /// Converts an [`ObjectiveValue`] to a JSON object (using [`serde_json`]).
+    /// * The keys are the displays of the levels, so levels with the same display overwrite each
+    ///   other (see [`objective_value_from_json`][Objective::objective_value_from_json]).
+    ///
+    /// Requires the `serde_json` feature.
+    #[cfg(feature = "serde_json")]
     pub fn objective_value_to_json(&self, objective_value: &ObjectiveValue) -> serde_json::Value {
         let mut json_object = serde_json::json!({});
         for (level, base_value) in self.hierarchy_levels.iter().zip(objective_value.iter()) {
@@ -96,9 +316,17 @@ impl<S> Objective<S> {
                 BaseValue::Integer(value) => {
                     json_object[level.to_string()] = serde_json::json!(value);
                 }
+                BaseValue::Count(value) => {
+                    json_object[level.to_string()] = serde_json::json!({ "count": value });
+                }
+                BaseValue::Decimal(_) => {
+                    json_object[level.to_string()] =
+                        serde_json::json!({ "decimal": base_value.to_string() });
+                }
                 BaseValue::Float(value) => {
                     json_object[level.to_string()] = serde_json::json!(value);
                 }
+                #[cfg(feature = "rapid_time")]
                 BaseValue::Duration(value) => {
                     json_object[level.to_string()] = serde_json::json!(value.to_string());
                 }
@@ -112,14 +340,169 @@ impl<S> Objective<S> {
         }
         json_object
     }
+
+    /// Converts a JSON object written by
+    /// [`objective_value_to_json`][Objective::objective_value_to_json] back to an
+    /// [`ObjectiveValue`] (e.g., to use a stored bound as target objective value).
+    /// * The keys of the JSON object must be exactly the levels of this objective (in any order).
+    /// * If the indicators of a level declare [`bounds`][Indicator::bounds], the value is
+    ///   converted to the kind of the bounds (e.g., `3` to `3.0` for a float level), and a value
+    ///   of another kind (e.g., `2.5` for an integer level) is invalid. Otherwise, the kind is
+    ///   given by the JSON value (e.g., `3` is an integer).
+    /// * Returns an [`ObjectiveValueJsonError`] if a level is missing, a key is not a level of
+    ///   this objective, two levels have the same display, or a value cannot be converted to a
+    ///   [`BaseValue`] (of the kind of the level).
+    ///
+    /// Requires the `serde_json` feature.
+    #[cfg(feature = "serde_json")]
+    pub fn objective_value_from_json(
+        &self,
+        json: &serde_json::Value,
+    ) -> Result<ObjectiveValue, ObjectiveValueJsonError> {
+        let json_object = json
+            .as_object()
+            .ok_or(ObjectiveValueJsonError::NotAnObject)?;
+        let level_names: Vec<String> = self
+            .hierarchy_levels
+            .iter()
+            .map(|level| level.to_string())
+            .collect();
+        if let Some((index, _)) = level_names
+            .iter()
+            .enumerate()
+            .find(|(index, level)| level_names[..*index].contains(level))
+        {
+            return Err(ObjectiveValueJsonError::DuplicateLevel {
+                level: level_names[index].clone(),
+            });
+        }
+        if let Some(unknown_level) = json_object.keys().find(|key| !level_names.contains(key)) {
+            return Err(ObjectiveValueJsonError::UnknownLevel {
+                level: unknown_level.clone(),
+            });
+        }
+        level_names
+            .into_iter()
+            .zip(self.hierarchy_levels.iter())
+            .map(|(level, linear_combination)| {
+                let value = json_object.get(&level).ok_or_else(|| {
+                    ObjectiveValueJsonError::MissingLevel {
+                        level: level.clone(),
+                    }
+                })?;
+                json::base_value_from_json(value)
+                    .and_then(|base_value| match linear_combination.value_kind() {
+                        Some(kind) => json::coerce_to_kind(base_value, &kind),
+                        None => Some(base_value),
+                    })
+                    .ok_or_else(|| ObjectiveValueJsonError::InvalidValue {
+                        level,
+                        value: value.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<BaseValue>, ObjectiveValueJsonError>>()
+            .map(ObjectiveValue::new)
+    }
 }
 
 // static
 impl<S> Objective<S> {
     /// Creates a new [`Objective`] with the given [`LinearCombinations`][`LinearCombination`] as hierarchy levels.
     /// The most important level is the first entry of the vector.
+    /// * Prints a warning if a level mixes [`Indicators`][`Indicator`] of incompatible
+    ///   [`Units`][`Unit`]. Use [`try_new`][`Objective::try_new`] to get an error instead.
+    /// * Panics if `hierarchy_levels` is empty.
     pub fn new(hierarchy_levels: Vec<LinearCombination<S>>) -> Objective<S> {
-        Objective { hierarchy_levels }
+        assert_non_empty(&hierarchy_levels);
+        for level in hierarchy_levels.iter() {
+            if let Err(unit_mismatch) = level.unit() {
+                report!(Warning, "{}", unit_mismatch);
+            }
+        }
+        Objective {
+            hierarchy_levels,
+            preference: None,
+            number_of_constraint_levels: 0,
+            constraints: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`Objective`] with the given [`LinearCombinations`][`LinearCombination`] as hierarchy levels.
+    /// The most important level is the first entry of the vector.
+    /// * Returns an [`UnitMismatch`] error if a level mixes [`Indicators`][`Indicator`] of
+    ///   incompatible [`Units`][`Unit`] without an explicit [`UnitConversion`].
+    /// * Panics if `hierarchy_levels` is empty.
+    pub fn try_new(
+        hierarchy_levels: Vec<LinearCombination<S>>,
+    ) -> Result<Objective<S>, UnitMismatch> {
+        assert_non_empty(&hierarchy_levels);
+        for level in hierarchy_levels.iter() {
+            level.unit()?;
+        }
+        Ok(Objective {
+            hierarchy_levels,
+            preference: None,
+            number_of_constraint_levels: 0,
+            constraints: Vec::new(),
+        })
+    }
+
+    /// Adds a secondary preference that is only applied if two solutions have equal
+    /// [`ObjectiveValues`][ObjectiveValue], e.g., to bias the search towards simpler or sparser
+    /// solutions without adding an artificial hierarchy level.
+    /// * The solvers use [`compare`][Objective::compare] to decide whether a neighbor is an
+    ///   improvement (in the [`Minimizer`][crate::heuristics::local_search::local_improver::Minimizer],
+    ///   [`TakeFirst`][crate::heuristics::local_search::local_improver::TakeFirst] and the
+    ///   parallel minimizers) and whether a solution is the best solution seen so far.
+    /// * The preference must be a total order, otherwise the local search might cycle.
+    /// * The recursion improvers only compare objective values.
+    pub fn with_preference(self, preference: PreferenceFunction<S>) -> Objective<S> {
+        Objective {
+            preference: Some(preference),
+            ..self
+        }
+    }
+
+    /// Declares the first levels as hard constraint levels of the given [`Constraints`][Constraint]
+    /// (used by the [`ObjectiveBuilder`]).
+    fn with_constraint_levels(self, constraints: Vec<Vec<Arc<Constraint<S>>>>) -> Objective<S> {
+        Objective {
+            number_of_constraint_levels: constraints.len(),
+            constraints,
+            ..self
+        }
+    }
+
+    /// Returns an [`ObjectiveBuilder`] to build an [`Objective`] level by level, e.g.,
+    /// `Objective::builder().level().indicator(X).next_level().indicator(Y).build()`.
+    pub fn builder() -> ObjectiveBuilder<S>
+    where
+        S: 'static,
+    {
+        ObjectiveBuilder::new()
+    }
+
+    /// Names the hierarchy levels (e.g., `"feasibility"` and `"cost"`), such that the names are
+    /// displayed instead of the formulas of the [`LinearCombinations`][LinearCombination] (see
+    /// [`level_names`][Objective::level_names]).
+    /// * Panics if the number of names differs from the number of levels.
+    pub fn with_level_names(self, names: &[&str]) -> Objective<S> {
+        assert_eq!(
+            names.len(),
+            self.hierarchy_levels.len(),
+            "The objective has {} levels, but {} names are given.",
+            self.hierarchy_levels.len(),
+            names.len()
+        );
+        Objective {
+            hierarchy_levels: self
+                .hierarchy_levels
+                .into_iter()
+                .zip(names)
+                .map(|(level, name)| level.with_name(name))
+                .collect(),
+            ..self
+        }
     }
 
     /// Creates a new [`Objective`] with a single [`LinearCombination`] as the only hierarchy level.
@@ -146,3 +529,11 @@ impl<S> Objective<S> {
         )
     }
 }
+
+/// Panics if the objective has no hierarchy level, as all solutions would be equal.
+fn assert_non_empty<S>(hierarchy_levels: &[LinearCombination<S>]) {
+    assert!(
+        !hierarchy_levels.is_empty(),
+        "An objective must have at least one hierarchy level."
+    );
+}
@@ -1,16 +1,20 @@
 //! Contains the [`ObjectiveValue`] struct, which represents the hierarchical objective value of a
 //! solution.
-use std::{
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{
     cmp::Ordering,
+    fmt,
     ops::{Add, Mul, Sub},
     slice::Iter,
 };
 
-use super::{base_value::BaseValue, Coefficient};
+use super::{base_value::BaseValue, Coefficient, Objective};
 
 // TODO: Implement Copy
 /// The hierarchical objective value of a solution, which is a vector of
 /// [`BaseValues`][`BaseValue`].
+/// * Comparisons and arithmetic are only defined for objective values of the same length (i.e.,
+///   from the same [`Objective`][super::Objective]) and panic otherwise.
 #[derive(Clone, Debug)]
 pub struct ObjectiveValue {
     objective_vector: Vec<BaseValue>,
@@ -23,7 +27,7 @@ impl ObjectiveValue {
     }
 
     /// Returns the entries of the objective vector.
-    pub fn iter(&self) -> Iter<BaseValue> {
+    pub fn iter(&self) -> Iter<'_, BaseValue> {
         self.objective_vector.iter()
     }
 
@@ -31,10 +35,87 @@ impl ObjectiveValue {
     pub fn as_vec(&self) -> &Vec<BaseValue> {
         &self.objective_vector
     }
+
+    /// Returns `true` if the first (most important) level is zero, e.g., if the hard constraints
+    /// modelled on the first level are satisfied.
+    pub fn first_level_is_zero(&self) -> bool {
+        self.level_is_zero(0)
+    }
+
+    /// Returns `true` if the level with index `level` is zero (see [`BaseValue::is_zero`]).
+    /// Panics if the level does not exist.
+    pub fn level_is_zero(&self, level: usize) -> bool {
+        match self.objective_vector.get(level) {
+            Some(value) => value.is_zero(),
+            None => panic!(
+                "Level {} does not exist, the objective value has {} levels.",
+                level,
+                self.objective_vector.len()
+            ),
+        }
+    }
+
+    /// Returns `true` if no level is [`BaseValue::Maximum`] or an infinite or NaN float.
+    pub fn is_finite(&self) -> bool {
+        self.objective_vector.iter().all(|value| value.is_finite())
+    }
+
+    /// Returns `true` if this objective value Pareto-dominates the `other` objective value on the
+    /// given `levels` (indices of hierarchy levels), i.e., it is at most as large on each of
+    /// these levels and strictly smaller on at least one of them. In contrast to the
+    /// lexicographic [`Ord`], the levels are equally important and the other levels are ignored.
+    /// * Panics if the objective values have different lengths or a level does not exist.
+    pub fn dominates(&self, other: &ObjectiveValue, levels: &[usize]) -> bool {
+        self.assert_same_length(other, "compare");
+        let mut strictly_smaller = false;
+        for &level in levels {
+            match self.objective_vector[level].partial_cmp(&other.objective_vector[level]) {
+                Some(Ordering::Less) => strictly_smaller = true,
+                Some(Ordering::Equal) => {}
+                _ => return false,
+            }
+        }
+        strictly_smaller
+    }
+
+    /// Returns a [`Display`][fmt::Display] of the objective value with one line per level,
+    /// labeled by the [`level_names`][Objective::level_names] of the `objective`, e.g.,
+    /// ` * feasibility: 0`.
+    pub fn display<S>(&self, objective: &Objective<S>) -> ObjectiveValueDisplay<'_> {
+        ObjectiveValueDisplay {
+            level_names: objective.level_names(),
+            objective_value: self,
+        }
+    }
+
+    /// Returns the value of each level by the [`level_names`][Objective::level_names] of the
+    /// `objective`. Levels with the same name are collapsed to the value of the last of these
+    /// levels.
+    pub fn to_named_map<S>(&self, objective: &Objective<S>) -> BTreeMap<String, BaseValue> {
+        objective
+            .level_names()
+            .into_iter()
+            .zip(self.objective_vector.iter().copied())
+            .collect()
+    }
+
+    /// Panics if the two objective values have different lengths, as zipping them would silently
+    /// ignore the surplus levels.
+    fn assert_same_length(&self, other: &ObjectiveValue, operation: &str) {
+        assert_eq!(
+            self.objective_vector.len(),
+            other.objective_vector.len(),
+            "Cannot {} objective values with different numbers of levels ({} and {}).",
+            operation,
+            self.objective_vector.len(),
+            other.objective_vector.len()
+        );
+    }
 }
 
 impl Ord for ObjectiveValue {
     fn cmp(&self, other: &Self) -> Ordering {
+        self.assert_same_length(other, "compare");
         self.objective_vector
             .iter()
             .zip(other.objective_vector.iter())
@@ -62,6 +143,7 @@ impl Add for ObjectiveValue {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
+        self.assert_same_length(&rhs, "add");
         ObjectiveValue::new(
             self.objective_vector
                 .into_iter()
@@ -76,6 +158,7 @@ impl Sub for ObjectiveValue {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
+        self.assert_same_length(&rhs, "subtract");
         ObjectiveValue::new(
             self.objective_vector
                 .into_iter()
@@ -113,3 +196,27 @@ impl Mul<i32> for ObjectiveValue {
         )
     }
 }
+
+/// Displays an [`ObjectiveValue`] with one line per level labeled by the level names of an
+/// [`Objective`] (see [`ObjectiveValue::display`]).
+pub struct ObjectiveValueDisplay<'a> {
+    level_names: Vec<String>,
+    objective_value: &'a ObjectiveValue,
+}
+
+impl fmt::Display for ObjectiveValueDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (name, value)) in self
+            .level_names
+            .iter()
+            .zip(self.objective_value.iter())
+            .enumerate()
+        {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, " * {}: {}", name, value)?;
+        }
+        Ok(())
+    }
+}
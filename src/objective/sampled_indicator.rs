@@ -0,0 +1,195 @@
+//! Contains the [`SampledIndicator`], which estimates the sum of the values of the elements of a
+//! huge solution from a random sample of the elements.
+use std::sync::Mutex;
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+use super::{BaseValue, Coefficient, Indicator, Unit};
+
+/// Type for a function that returns the number of elements of a solution (e.g., the number of
+/// jobs), see [`SampledIndicator`].
+pub type NumberOfElements<S> = Box<dyn Fn(&S) -> usize + Send + Sync>;
+
+/// Type for a function that returns the value of the element with the given index (between 0 and
+/// the [`NumberOfElements`]) of a solution (e.g., the lateness of the job), see
+/// [`SampledIndicator`].
+pub type ValueOfElement<S> = Box<dyn Fn(&S, usize) -> BaseValue + Send + Sync>;
+
+/// An [`Indicator`] that estimates the sum of the values of the elements of a solution by
+/// evaluating only a random sample of `sample_size` elements (drawn anew for each evaluation)
+/// and scaling the sum of the sample by the number of elements divided by the sample size.
+/// * Intended for huge solutions (e.g., millions of jobs), for which evaluating every neighbor
+///   exactly is too slow, while the search only needs to rank the neighbors roughly.
+/// * The estimate keeps the variant of the [`BaseValue`] (integral values are rounded, see
+///   [`Coefficient::Float`]). If the solution has at most `sample_size` elements, the value is
+///   exact.
+/// * [`evaluate_exactly`][Indicator::evaluate_exactly] sums the values of all elements. Hence,
+///   the best solution found by a search with sampled indicators can be re-evaluated exactly by
+///   [`Objective::evaluate_exactly`][super::Objective::evaluate_exactly].
+/// * Requires the `rand` feature.
+pub struct SampledIndicator<S> {
+    name: String,
+    number_of_elements: NumberOfElements<S>,
+    value_of_element: ValueOfElement<S>,
+    sample_size: usize,
+    rng: Mutex<Box<dyn RngCore + Send>>,
+    unit: Option<Unit>,
+    bounds: Option<(BaseValue, BaseValue)>,
+}
+
+impl<S> SampledIndicator<S> {
+    /// Creates a new [`SampledIndicator`] that estimates the sum of the `value_of_element` of
+    /// all elements from a sample of `sample_size` (at least 1) elements.
+    /// * The `name` describes the summed values (e.g., `"lateness"`).
+    /// * The sample is drawn by a [`StdRng`] seeded by the `random_seed` (if given), see
+    ///   [`with_rng`][SampledIndicator::with_rng] for other generators.
+    pub fn new(
+        name: &str,
+        number_of_elements: NumberOfElements<S>,
+        value_of_element: ValueOfElement<S>,
+        sample_size: usize,
+        random_seed: Option<u64>,
+    ) -> SampledIndicator<S> {
+        let rng: Box<dyn RngCore + Send> = match random_seed {
+            Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+            None => Box::new(StdRng::from_entropy()),
+        };
+        SampledIndicator {
+            name: String::from(name),
+            number_of_elements,
+            value_of_element,
+            sample_size: sample_size.max(1),
+            rng: Mutex::new(rng),
+            unit: None,
+            bounds: None,
+        }
+    }
+
+    /// Replaces the random number generator that draws the samples.
+    pub fn with_rng(self, rng: Box<dyn RngCore + Send>) -> Self {
+        SampledIndicator {
+            rng: Mutex::new(rng),
+            ..self
+        }
+    }
+
+    /// Declares the [`Unit`] of the values of the elements.
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Declares the expected minimum and maximum of the (estimated) sum (see
+    /// [`Indicator::bounds`]).
+    /// * Panics if `min` is larger than `max`.
+    pub fn with_bounds(mut self, min: BaseValue, max: BaseValue) -> Self {
+        assert!(
+            min <= max,
+            "The lower bound {} of indicator '{}' is larger than the upper bound {}.",
+            min,
+            self.name,
+            max
+        );
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Returns the number of elements that are evaluated per estimate.
+    pub fn sample_size(&self) -> usize {
+        self.sample_size
+    }
+}
+
+impl<S> Indicator<S> for SampledIndicator<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        let number_of_elements = (self.number_of_elements)(solution);
+        if number_of_elements <= self.sample_size {
+            return self.evaluate_exactly(solution);
+        }
+        let sample = {
+            let mut rng = self.rng.lock().unwrap();
+            rand::seq::index::sample(&mut **rng, number_of_elements, self.sample_size)
+        };
+        let sample_sum: BaseValue = sample
+            .into_iter()
+            .map(|index| (self.value_of_element)(solution, index))
+            .sum();
+        Coefficient::Float(number_of_elements as f32 / self.sample_size as f32) * sample_sum
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        (0..(self.number_of_elements)(solution))
+            .map(|index| (self.value_of_element)(solution, index))
+            .sum()
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.unit
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::Objective;
+
+    fn total_lateness(sample_size: usize) -> SampledIndicator<Vec<i64>> {
+        SampledIndicator::new(
+            "lateness",
+            Box::new(|jobs: &Vec<i64>| jobs.len()),
+            Box::new(|jobs: &Vec<i64>, index| BaseValue::Integer(jobs[index])),
+            sample_size,
+            Some(42),
+        )
+    }
+
+    #[test]
+    fn test_sampled_indicator() {
+        let jobs: Vec<i64> = (0..10_000).map(|job| job % 10).collect();
+        let exact: i64 = jobs.iter().sum();
+
+        let indicator = total_lateness(1000);
+        assert_eq!(indicator.evaluate_exactly(&jobs), BaseValue::Integer(exact));
+        let estimate = indicator.evaluate(&jobs).unwrap_integer();
+        assert!((estimate - exact).abs() < exact / 10);
+
+        // small solutions are evaluated exactly
+        assert_eq!(
+            total_lateness(20).evaluate(&vec![3, 4, 5]),
+            BaseValue::Integer(12)
+        );
+    }
+
+    #[test]
+    fn test_exact_re_evaluation() {
+        let objective = Objective::new_single_indicator(Box::new(total_lateness(10)));
+        let jobs: Vec<i64> = (0..1000).collect();
+
+        let estimated = objective.evaluate(jobs.clone());
+        let exact = objective.evaluate_exactly(estimated.unwrap());
+        assert_eq!(
+            exact.objective_value().iter().next(),
+            Some(&BaseValue::Integer(499_500))
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(total_lateness(10).bounds(), None);
+        assert_eq!(
+            total_lateness(10)
+                .with_bounds(BaseValue::Integer(0), BaseValue::Integer(9000))
+                .bounds(),
+            Some((BaseValue::Integer(0), BaseValue::Integer(9000)))
+        );
+    }
+}
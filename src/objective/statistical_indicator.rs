@@ -0,0 +1,229 @@
+//! Contains the [`StatisticalIndicator`], which aggregates per-element values of a solution
+//! (e.g., the lateness of each job) by a [`Statistic`].
+use std::fmt;
+
+use super::{BaseValue, Indicator, Unit};
+
+/// Type for a function that returns the values of the elements of a solution (e.g., the lateness
+/// of each job or the length of each tour), which are aggregated by a [`StatisticalIndicator`].
+pub type ElementValues<S> =
+    Box<dyn for<'a> Fn(&'a S) -> Box<dyn Iterator<Item = BaseValue> + 'a> + Send + Sync>;
+
+/// The aggregation of the element values of a [`StatisticalIndicator`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Statistic {
+    /// The largest value (e.g., for "minimize the maximum lateness").
+    Max,
+    /// The smallest value.
+    Min,
+    /// The arithmetic mean as [`BaseValue::Float`] (see [`BaseValue::to_f64`]).
+    Mean,
+    /// The population standard deviation as [`BaseValue::Float`] (see [`BaseValue::to_f64`]),
+    /// e.g., to balance the workload.
+    StdDev,
+    /// The given percentile (between 0 and 100) by the nearest-rank method, e.g., `95.0` for
+    /// the value that is not exceeded by 95% of the elements.
+    Percentile(f64),
+}
+
+impl fmt::Display for Statistic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Statistic::Max => write!(f, "max"),
+            Statistic::Min => write!(f, "min"),
+            Statistic::Mean => write!(f, "mean"),
+            Statistic::StdDev => write!(f, "stddev"),
+            Statistic::Percentile(percentile) => write!(f, "p{}", percentile),
+        }
+    }
+}
+
+/// An [`Indicator`] that applies a [`Statistic`] to the values of the elements of a solution,
+/// such that fairness-style objectives (e.g., "minimize the maximum lateness") do not need to
+/// aggregate the values manually.
+/// * [`Max`][Statistic::Max], [`Min`][Statistic::Min] and
+///   [`Percentile`][Statistic::Percentile] return one of the element values (so the variant of
+///   the [`BaseValue`] is kept), [`Mean`][Statistic::Mean] and [`StdDev`][Statistic::StdDev]
+///   return floats (durations in seconds).
+/// * If the solution has no elements, the value is [`BaseValue::Zero`].
+/// * The name of the indicator is the statistic applied to the given name, e.g.,
+///   `max(lateness)`.
+/// * Requires the `std` feature.
+pub struct StatisticalIndicator<S> {
+    name: String,
+    statistic: Statistic,
+    element_values: ElementValues<S>,
+    unit: Option<Unit>,
+    bounds: Option<(BaseValue, BaseValue)>,
+}
+
+impl<S> StatisticalIndicator<S> {
+    /// Creates a new [`StatisticalIndicator`] that applies the `statistic` to the
+    /// `element_values` of a solution. The `name` describes the elements (e.g., `"lateness"`).
+    /// * Panics if the percentile of [`Statistic::Percentile`] is not between 0 and 100.
+    pub fn new(
+        name: &str,
+        statistic: Statistic,
+        element_values: ElementValues<S>,
+    ) -> StatisticalIndicator<S> {
+        if let Statistic::Percentile(percentile) = statistic {
+            assert!(
+                (0.0..=100.0).contains(&percentile),
+                "The percentile must be between 0 and 100, got {}.",
+                percentile
+            );
+        }
+        StatisticalIndicator {
+            name: String::from(name),
+            statistic,
+            element_values,
+            unit: None,
+            bounds: None,
+        }
+    }
+
+    /// Declares the [`Unit`] of the aggregated values.
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Declares the expected minimum and maximum of the aggregated values (see
+    /// [`Indicator::bounds`]), e.g., the bounds of the element values for [`Statistic::Max`].
+    /// * Panics if `min` is larger than `max`.
+    pub fn with_bounds(mut self, min: BaseValue, max: BaseValue) -> Self {
+        assert!(
+            min <= max,
+            "The lower bound {} of indicator '{}' is larger than the upper bound {}.",
+            min,
+            self.name(),
+            max
+        );
+        self.bounds = Some((min, max));
+        self
+    }
+}
+
+impl<S> Indicator<S> for StatisticalIndicator<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        let values = (self.element_values)(solution);
+        match self.statistic {
+            Statistic::Max => values.max().unwrap_or(BaseValue::Zero),
+            Statistic::Min => values.min().unwrap_or(BaseValue::Zero),
+            Statistic::Mean => {
+                let values: Vec<f64> = values.map(BaseValue::to_f64).collect();
+                mean(&values).map_or(BaseValue::Zero, BaseValue::Float)
+            }
+            Statistic::StdDev => {
+                let values: Vec<f64> = values.map(BaseValue::to_f64).collect();
+                match mean(&values) {
+                    Some(mean) => BaseValue::Float(
+                        (values
+                            .iter()
+                            .map(|value| (value - mean) * (value - mean))
+                            .sum::<f64>()
+                            / values.len() as f64)
+                            .sqrt(),
+                    ),
+                    None => BaseValue::Zero,
+                }
+            }
+            Statistic::Percentile(percentile) => {
+                let mut values: Vec<BaseValue> = values.collect();
+                if values.is_empty() {
+                    return BaseValue::Zero;
+                }
+                values.sort_unstable();
+                let rank = (percentile / 100.0 * values.len() as f64).ceil() as usize;
+                values[rank.clamp(1, values.len()) - 1]
+            }
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("{}({})", self.statistic, self.name)
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.unit
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.bounds
+    }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lateness(statistic: Statistic) -> StatisticalIndicator<Vec<i64>> {
+        StatisticalIndicator::new(
+            "lateness",
+            statistic,
+            Box::new(|jobs: &Vec<i64>| Box::new(jobs.iter().map(|job| BaseValue::Integer(*job)))),
+        )
+    }
+
+    #[test]
+    fn test_statistics() {
+        let jobs = vec![4, 0, 2, 8, 6];
+
+        assert_eq!(
+            lateness(Statistic::Max).evaluate(&jobs),
+            BaseValue::Integer(8)
+        );
+        assert_eq!(
+            lateness(Statistic::Min).evaluate(&jobs),
+            BaseValue::Integer(0)
+        );
+        assert_eq!(
+            lateness(Statistic::Mean).evaluate(&jobs),
+            BaseValue::Float(4.0)
+        );
+        assert_eq!(
+            lateness(Statistic::StdDev).evaluate(&jobs),
+            BaseValue::Float(8.0_f64.sqrt())
+        );
+        assert_eq!(
+            lateness(Statistic::Percentile(60.0)).evaluate(&jobs),
+            BaseValue::Integer(4)
+        );
+        assert_eq!(
+            lateness(Statistic::Percentile(0.0)).evaluate(&jobs),
+            BaseValue::Integer(0)
+        );
+        assert_eq!(
+            lateness(Statistic::Max).evaluate(&Vec::new()),
+            BaseValue::Zero
+        );
+        assert_eq!(
+            lateness(Statistic::StdDev).evaluate(&Vec::new()),
+            BaseValue::Zero
+        );
+        assert_eq!(lateness(Statistic::Max).name(), "max(lateness)");
+        assert_eq!(
+            lateness(Statistic::Percentile(95.0)).name(),
+            "p95(lateness)"
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(lateness(Statistic::Max).bounds(), None);
+        assert_eq!(
+            lateness(Statistic::Max)
+                .with_bounds(BaseValue::Integer(0), BaseValue::Integer(10))
+                .bounds(),
+            Some((BaseValue::Integer(0), BaseValue::Integer(10)))
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Contains the [`SurrogateScreening`], which pre-screens neighbors by a cheap surrogate
+//! [`Objective`] such that only the most promising ones are evaluated by the exact objective.
+use alloc::{sync::Arc, vec::Vec};
+
+use super::{EvaluatedSolution, Objective};
+
+/// Two-stage evaluation: All candidates are evaluated by a cheap `surrogate_objective` and only
+/// the `top_k` best of them (with respect to the surrogate) are passed on to be evaluated by the
+/// exact [`Objective`].
+/// * Useful if the exact objective is expensive (e.g., involves a simulation) while a cheap
+///   approximation is available.
+/// * The surrogate only has to rank the candidates reasonably; its levels do not need to match
+///   the levels of the exact objective.
+/// * Can be configured on the [`Minimizer`][crate::heuristics::local_search::local_improver::Minimizer]
+///   (see `with_surrogate`) and the
+///   [`TabuMinimizer`][crate::heuristics::tabu_search::tabu_improver::TabuMinimizer].
+pub struct SurrogateScreening<S> {
+    surrogate_objective: Arc<Objective<S>>,
+    top_k: usize,
+}
+
+impl<S> SurrogateScreening<S> {
+    /// Creates a new [`SurrogateScreening`] that keeps the `top_k` (at least 1) candidates with
+    /// the best value of the `surrogate_objective`.
+    pub fn new(surrogate_objective: Arc<Objective<S>>, top_k: usize) -> SurrogateScreening<S> {
+        SurrogateScreening {
+            surrogate_objective,
+            top_k: top_k.max(1),
+        }
+    }
+
+    /// Returns the number of candidates that are kept.
+    pub fn top_k(&self) -> usize {
+        self.top_k
+    }
+
+    /// Evaluates all `candidates` (solutions with some additional data `X`, e.g., new tabus) by
+    /// the surrogate objective and returns the `top_k` best of them ordered by their surrogate
+    /// value (best first).
+    pub fn screen<X>(&self, candidates: impl Iterator<Item = (S, X)>) -> Vec<(S, X)> {
+        let mut evaluated_candidates: Vec<_> = candidates
+            .map(|(candidate, data)| (self.surrogate_objective.evaluate(candidate), data))
+            .collect();
+        let compare = |a: &(EvaluatedSolution<S>, X), b: &(EvaluatedSolution<S>, X)| {
+            self.surrogate_objective.compare(&a.0, &b.0)
+        };
+        if evaluated_candidates.len() > self.top_k {
+            evaluated_candidates.select_nth_unstable_by(self.top_k - 1, compare);
+            evaluated_candidates.truncate(self.top_k);
+        }
+        evaluated_candidates.sort_by(compare);
+        evaluated_candidates
+            .into_iter()
+            .map(|(candidate, data)| (candidate.unwrap(), data))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::heuristics::common::Neighborhood;
+    use crate::heuristics::local_search::local_improver::{LocalImprover, Minimizer};
+    use crate::objective::{BaseValue, Indicator};
+
+    struct CountingDistanceToTen(Arc<AtomicUsize>);
+
+    impl Indicator<i64> for CountingDistanceToTen {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            BaseValue::Integer((solution - 10).abs())
+        }
+        fn name(&self) -> String {
+            String::from("CountingDistanceToTen")
+        }
+    }
+
+    // a rough surrogate, which cannot distinguish neighboring solutions
+    struct HalvedDistanceToTen;
+
+    impl Indicator<i64> for HalvedDistanceToTen {
+        fn evaluate(&self, solution: &i64) -> BaseValue {
+            BaseValue::Integer((solution - 10).abs() / 2)
+        }
+        fn name(&self) -> String {
+            String::from("HalvedDistanceToTen")
+        }
+    }
+
+    struct Steps;
+
+    impl Neighborhood<i64> for Steps {
+        fn neighbors_of<'a>(
+            &'a self,
+            current_solution: &'a i64,
+        ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+            Box::new(
+                [-3, -2, -1, 1, 2, 3]
+                    .into_iter()
+                    .map(move |delta| current_solution + delta),
+            )
+        }
+    }
+
+    #[test]
+    fn test_surrogate_screening() {
+        let surrogate = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            HalvedDistanceToTen,
+        )]));
+        let screening = SurrogateScreening::new(surrogate.clone(), 2);
+        let mut screened = screening.screen([(13, 'a'), (9, 'b'), (11, 'c'), (7, 'd')].into_iter());
+        screened.sort();
+        assert_eq!(screened, vec![(9, 'b'), (11, 'c')]);
+
+        let exact_evaluations = Arc::new(AtomicUsize::new(0));
+        let objective = Arc::new(Objective::new_single_indicator_per_level(vec![Box::new(
+            CountingDistanceToTen(exact_evaluations.clone()),
+        )]));
+        let minimizer = Minimizer::with_surrogate(Arc::new(Steps), objective.clone(), surrogate, 3);
+
+        let solution = objective.evaluate(4);
+        exact_evaluations.store(0, Ordering::Relaxed);
+        let improved = minimizer.improve(&solution).unwrap();
+
+        // only the three neighbors 5, 6 and 7 with the best surrogate value are evaluated exactly
+        assert_eq!(exact_evaluations.load(Ordering::Relaxed), 3);
+        assert_eq!(*improved.solution(), 7);
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::collections::BTreeMap;
+#[cfg(feature = "rapid_time")]
 use rapid_time::Duration;
 
 use super::*;
@@ -8,7 +10,9 @@ struct TestSolution {
     field2: i32,
     field3: f32,
     field4: f32,
+    #[cfg(feature = "rapid_time")]
     field5: Duration,
+    #[cfg(feature = "rapid_time")]
     field6: Duration,
 }
 
@@ -36,8 +40,10 @@ impl Indicator<TestSolution> for SecondIndicator {
     }
 }
 
+#[cfg(feature = "rapid_time")]
 struct ThirdIndicator;
 
+#[cfg(feature = "rapid_time")]
 impl Indicator<TestSolution> for ThirdIndicator {
     fn evaluate(&self, solution: &TestSolution) -> BaseValue {
         BaseValue::Float(solution.field3 as f64)
@@ -48,8 +54,10 @@ impl Indicator<TestSolution> for ThirdIndicator {
     }
 }
 
+#[cfg(feature = "rapid_time")]
 struct FourthIndicator;
 
+#[cfg(feature = "rapid_time")]
 impl Indicator<TestSolution> for FourthIndicator {
     fn evaluate(&self, solution: &TestSolution) -> BaseValue {
         BaseValue::Float(solution.field4 as f64)
@@ -60,8 +68,10 @@ impl Indicator<TestSolution> for FourthIndicator {
     }
 }
 
+#[cfg(feature = "rapid_time")]
 struct FifthIndicator;
 
+#[cfg(feature = "rapid_time")]
 impl Indicator<TestSolution> for FifthIndicator {
     fn evaluate(&self, solution: &TestSolution) -> BaseValue {
         BaseValue::Duration(solution.field5)
@@ -72,8 +82,10 @@ impl Indicator<TestSolution> for FifthIndicator {
     }
 }
 
+#[cfg(feature = "rapid_time")]
 struct SixthIndicator;
 
+#[cfg(feature = "rapid_time")]
 impl Indicator<TestSolution> for SixthIndicator {
     fn evaluate(&self, solution: &TestSolution) -> BaseValue {
         BaseValue::Duration(solution.field6)
@@ -84,6 +96,7 @@ impl Indicator<TestSolution> for SixthIndicator {
     }
 }
 
+#[cfg(feature = "rapid_time")]
 #[test]
 fn test() {
     // ARRANGE
@@ -194,3 +207,561 @@ fn test() {
         objective_value_sum
     );
 }
+
+#[cfg(feature = "rapid_time")]
+struct DurationInSeconds;
+
+#[cfg(feature = "rapid_time")]
+impl Indicator<TestSolution> for DurationInSeconds {
+    fn evaluate(&self, solution: &TestSolution) -> BaseValue {
+        BaseValue::Integer(solution.field5.in_sec().unwrap() as i64)
+    }
+
+    fn name(&self) -> String {
+        "Duration".to_string()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        Some(Unit::Seconds)
+    }
+}
+
+#[cfg(feature = "rapid_time")]
+struct CostInCurrency;
+
+#[cfg(feature = "rapid_time")]
+impl Indicator<TestSolution> for CostInCurrency {
+    fn evaluate(&self, solution: &TestSolution) -> BaseValue {
+        BaseValue::Integer(solution.field1 as i64)
+    }
+
+    fn name(&self) -> String {
+        "Cost".to_string()
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        Some(Unit::Currency)
+    }
+}
+
+#[cfg(feature = "rapid_time")]
+#[test]
+fn test_unit_consistency() {
+    let mixed_level = LinearCombination::new(vec![
+        (Coefficient::Integer(1), Box::new(CostInCurrency)),
+        (Coefficient::Integer(2), Box::new(DurationInSeconds)),
+    ]);
+    let unit_mismatch = mixed_level.unit().unwrap_err();
+    assert_eq!(
+        unit_mismatch.units,
+        vec![
+            ("Cost".to_string(), Unit::Currency),
+            ("Duration".to_string(), Unit::Seconds)
+        ]
+    );
+    assert!(Objective::try_new(vec![mixed_level]).is_err());
+
+    let converted_level = LinearCombination::new(vec![
+        (Coefficient::Integer(1), Box::new(CostInCurrency)),
+        (
+            Coefficient::Integer(2),
+            Box::new(UnitConversion::new(
+                Box::new(DurationInSeconds),
+                Unit::Currency,
+            )),
+        ),
+        (Coefficient::Integer(1), Box::new(FirstIndicator)),
+    ]);
+    assert_eq!(converted_level.unit(), Ok(Some(Unit::Currency)));
+    let objective = Objective::try_new(vec![converted_level]).unwrap();
+
+    let solution = TestSolution {
+        field1: 3,
+        field2: 0,
+        field3: 0.0,
+        field4: 0.0,
+        field5: Duration::from_seconds(10),
+        field6: Duration::from_seconds(0),
+    };
+    assert_eq!(
+        objective.evaluate(solution).objective_value().as_vec(),
+        &vec![BaseValue::Integer(26)]
+    );
+}
+
+#[test]
+fn test_evaluated_solution_ordering() {
+    let evaluated = |name: &'static str, value: i64| {
+        EvaluatedSolution::new(name, ObjectiveValue::new(vec![BaseValue::Integer(value)]))
+    };
+
+    // the ordering only depends on the objective value, not on the solution
+    assert!(evaluated("a", 1) < evaluated("b", 2));
+    assert!(evaluated("a", 1) == evaluated("b", 1));
+
+    let best = vec![evaluated("a", 3), evaluated("b", 1), evaluated("c", 1)]
+        .into_iter()
+        .min_by_objective()
+        .unwrap();
+    assert_eq!(*best.solution(), "b");
+    assert!(Vec::<EvaluatedSolution<&str>>::new()
+        .into_iter()
+        .min_by_objective()
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "at least one hierarchy level")]
+fn test_empty_objective() {
+    let _ = Objective::<Vec<i32>>::new(vec![]);
+}
+
+#[test]
+#[should_panic(expected = "different numbers of levels (2 and 1)")]
+fn test_objective_values_of_different_lengths() {
+    let two_levels = ObjectiveValue::new(vec![BaseValue::Integer(1), BaseValue::Integer(2)]);
+    let one_level = ObjectiveValue::new(vec![BaseValue::Integer(1)]);
+    let _ = two_levels < one_level;
+}
+
+struct Constant;
+
+impl Indicator<Vec<i32>> for Constant {
+    fn evaluate(&self, _solution: &Vec<i32>) -> BaseValue {
+        BaseValue::Integer(1)
+    }
+
+    fn name(&self) -> String {
+        "Constant".to_string()
+    }
+}
+
+#[test]
+fn test_preference_on_ties() {
+    // prefer shorter solutions
+    let objective = Objective::new_single_indicator(Box::new(Constant))
+        .with_preference(Box::new(|a: &Vec<i32>, b: &Vec<i32>| a.len().cmp(&b.len())));
+    let short = objective.evaluate(vec![1]);
+    let long = objective.evaluate(vec![1, 2, 3]);
+
+    assert!(short.objective_value() == long.objective_value());
+    assert!(objective.is_better(&short, &long));
+    assert!(!objective.is_better(&long, &short));
+
+    let without_preference = Objective::new_single_indicator(Box::new(Constant));
+    assert!(!without_preference.is_better(&short, &long));
+}
+
+#[test]
+fn test_objective_value_predicates() {
+    let objective_value = ObjectiveValue::new(vec![
+        BaseValue::Zero,
+        BaseValue::Float(0.00001),
+        BaseValue::Integer(3),
+        BaseValue::Count(0),
+    ]);
+    assert!(objective_value.first_level_is_zero());
+    assert!(objective_value.level_is_zero(1));
+    assert!(!objective_value.level_is_zero(2));
+    assert!(objective_value.level_is_zero(3));
+    assert!(objective_value.is_finite());
+    #[cfg(feature = "rapid_time")]
+    assert!(BaseValue::Duration(Duration::ZERO).is_zero());
+
+    let infeasible = ObjectiveValue::new(vec![BaseValue::Maximum, BaseValue::Float(f64::NAN)]);
+    assert!(!infeasible.first_level_is_zero());
+    assert!(!infeasible.is_finite());
+    assert!(!infeasible.level_is_zero(1));
+    assert!(!ObjectiveValue::new(vec![BaseValue::Float(f64::INFINITY)]).is_finite());
+}
+
+#[test]
+fn test_dominance() {
+    let value = |levels: [i64; 3]| {
+        ObjectiveValue::new(levels.iter().map(|&v| BaseValue::Integer(v)).collect())
+    };
+    let a = value([0, 5, 9]);
+    let b = value([0, 7, 3]);
+    let c = value([1, 7, 9]);
+
+    // lexicographically a < b, but they do not dominate each other on levels 1 and 2
+    assert!(a < b);
+    assert!(!a.dominates(&b, &[1, 2]));
+    assert!(!b.dominates(&a, &[1, 2]));
+    assert!(a.dominates(&c, &[0, 1, 2]));
+    assert!(a.dominates(&c, &[1, 2]));
+    assert!(b.dominates(&c, &[0, 2]));
+    // equal values and ignored levels do not dominate
+    assert!(!a.dominates(&a, &[0, 1, 2]));
+    assert!(!a.dominates(&c, &[2]));
+}
+
+#[cfg(all(feature = "serde_json", feature = "rapid_time"))]
+#[test]
+fn test_objective_value_json_round_trip() {
+    let objective = Objective::new(vec![
+        LinearCombination::new(vec![(Coefficient::Integer(1), Box::new(FirstIndicator))]),
+        LinearCombination::new(vec![(Coefficient::Integer(1), Box::new(ThirdIndicator))]),
+        LinearCombination::new(vec![(Coefficient::Integer(1), Box::new(FifthIndicator))]),
+    ]);
+    for objective_value in [
+        ObjectiveValue::new(vec![
+            BaseValue::Integer(-3),
+            BaseValue::Float(2.0),
+            BaseValue::Duration(Duration::from_seconds(3725)),
+        ]),
+        ObjectiveValue::new(vec![
+            BaseValue::Zero,
+            BaseValue::Maximum,
+            BaseValue::Duration(Duration::Infinity),
+        ]),
+        ObjectiveValue::new(vec![
+            BaseValue::Count(7),
+            BaseValue::decimal(-1205, 2),
+            BaseValue::Decimal(3),
+        ]),
+    ] {
+        let json = objective.objective_value_to_json(&objective_value);
+        assert_eq!(
+            objective.objective_value_from_json(&json),
+            Ok(objective_value)
+        );
+    }
+
+    let mut json = objective.objective_value_to_json(&objective.zero());
+    json["Field1"] = serde_json::json!(true);
+    assert!(matches!(
+        objective.objective_value_from_json(&json),
+        Err(ObjectiveValueJsonError::InvalidValue { .. })
+    ));
+    json.as_object_mut().unwrap().remove("Field1");
+    assert!(matches!(
+        objective.objective_value_from_json(&json),
+        Err(ObjectiveValueJsonError::MissingLevel { .. })
+    ));
+    json["Field7"] = serde_json::json!(1);
+    assert!(matches!(
+        objective.objective_value_from_json(&json),
+        Err(ObjectiveValueJsonError::UnknownLevel { .. })
+    ));
+    assert_eq!(
+        objective.objective_value_from_json(&serde_json::json!([1, 2, 3])),
+        Err(ObjectiveValueJsonError::NotAnObject)
+    );
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_objective_value_from_json_coerces_to_declared_kind() {
+    let objective = Objective::new(vec![
+        LinearCombination::new(vec![(
+            Coefficient::Integer(1),
+            Box::new(BoundedIndicator::new(
+                Box::new(FirstIndicator),
+                BaseValue::Float(0.0),
+                BaseValue::Float(10.0),
+            )),
+        )]),
+        LinearCombination::new(vec![(
+            Coefficient::Integer(1),
+            Box::new(BoundedIndicator::new(
+                Box::new(SecondIndicator),
+                BaseValue::Integer(0),
+                BaseValue::Integer(10),
+            )),
+        )]),
+    ]);
+
+    let json = serde_json::json!({"Field1": 3, "Field2": 4});
+    assert_eq!(
+        objective.objective_value_from_json(&json),
+        Ok(ObjectiveValue::new(vec![
+            BaseValue::Float(3.0),
+            BaseValue::Integer(4)
+        ]))
+    );
+
+    let json = serde_json::json!({"Field1": 3, "Field2": 2.5});
+    assert!(matches!(
+        objective.objective_value_from_json(&json),
+        Err(ObjectiveValueJsonError::InvalidValue { .. })
+    ));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_objective_value_from_json_rejects_duplicate_levels() {
+    let objective = Objective::new(vec![
+        LinearCombination::new(vec![(Coefficient::Integer(1), Box::new(FirstIndicator))]),
+        LinearCombination::new(vec![(Coefficient::Integer(1), Box::new(FirstIndicator))]),
+    ]);
+    let json = serde_json::json!({"Field1": 3});
+    assert!(matches!(
+        objective.objective_value_from_json(&json),
+        Err(ObjectiveValueJsonError::DuplicateLevel { .. })
+    ));
+}
+
+#[test]
+fn test_level_names() {
+    let objective = Objective::new(vec![
+        LinearCombination::new(vec![(Coefficient::Integer(1), Box::new(FirstIndicator))]),
+        LinearCombination::new(vec![
+            (Coefficient::Integer(1), Box::new(SecondIndicator)),
+            (Coefficient::Integer(2), Box::new(FirstIndicator)),
+        ]),
+    ]);
+    let objective_value = ObjectiveValue::new(vec![BaseValue::Integer(0), BaseValue::Integer(7)]);
+    assert_eq!(objective.level_names(), vec!["Field1", "Field2 + 2*Field1"]);
+    assert_eq!(
+        objective_value.display(&objective).to_string(),
+        " * Field1: 0\n * Field2 + 2*Field1: 7"
+    );
+
+    let objective = objective.with_level_names(&["feasibility", "cost"]);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        objective.format_objective_value(&objective_value),
+        " * feasibility: 0\n * cost: 7"
+    );
+    assert_eq!(
+        objective_value.to_named_map(&objective),
+        BTreeMap::from([
+            (String::from("cost"), BaseValue::Integer(7)),
+            (String::from("feasibility"), BaseValue::Integer(0)),
+        ])
+    );
+}
+
+#[test]
+fn test_count_and_decimal_values() {
+    // 0.1 + 0.2 is exactly 0.3 for decimals
+    let sum = BaseValue::decimal(1, 1) + BaseValue::decimal(2, 1);
+    assert_eq!(sum, BaseValue::decimal(3, 1));
+    assert_eq!(sum.unwrap_decimal(), 3000);
+    assert!(BaseValue::decimal(3001, 4) > BaseValue::decimal(3, 1));
+    assert_eq!(BaseValue::decimal(-1205, 2).to_string(), "-12.0500");
+    assert_eq!(
+        Coefficient::Integer(3) * BaseValue::decimal(1, 1),
+        BaseValue::decimal(3, 1)
+    );
+    assert_eq!(
+        Coefficient::Float(0.5) * BaseValue::Decimal(3),
+        BaseValue::Decimal(2)
+    );
+    assert!((BaseValue::Zero - BaseValue::decimal(1, 0)).to_f64() == -1.0);
+
+    assert_eq!(
+        BaseValue::Count(2) + BaseValue::Count(3),
+        BaseValue::Count(5)
+    );
+    assert_eq!(
+        BaseValue::Count(2) - BaseValue::Count(3),
+        BaseValue::Integer(-1)
+    );
+    assert_eq!(
+        Coefficient::Integer(-2) * BaseValue::Count(3),
+        BaseValue::Integer(-6)
+    );
+    assert!(BaseValue::Count(3) > BaseValue::Integer(-1));
+    assert!(BaseValue::Count(0).is_zero());
+    assert_eq!(
+        [BaseValue::Count(4), BaseValue::Integer(-1)]
+            .into_iter()
+            .sum::<BaseValue>(),
+        BaseValue::Integer(3)
+    );
+    assert_eq!(
+        BaseValue::Count(u64::MAX) + BaseValue::Integer(i64::MIN),
+        BaseValue::Integer(i64::MAX)
+    );
+    assert_eq!(
+        BaseValue::Count(0) - BaseValue::Count(1 << 63),
+        BaseValue::Integer(i64::MIN)
+    );
+    assert!(BaseValue::Count(u64::MAX) > BaseValue::Integer(i64::MAX));
+}
+
+#[test]
+fn test_print_difference_of_count_and_integer() {
+    assert_eq!(
+        BaseValue::Count(5).print_difference(BaseValue::Integer(2)),
+        BaseValue::Integer(5).print_difference(BaseValue::Integer(2))
+    );
+    assert_eq!(
+        BaseValue::Integer(-2).print_difference(BaseValue::Count(5)),
+        BaseValue::Integer(-2).print_difference(BaseValue::Integer(5))
+    );
+    assert_eq!(
+        BaseValue::Count(u64::MAX).print_difference(BaseValue::Integer(i64::MIN)),
+        format!(
+            "(\x1b[0;31m+{}\x1b[0m)",
+            i128::from(u64::MAX) - i128::from(i64::MIN)
+        )
+    );
+    assert_eq!(
+        BaseValue::Count(3).print_difference(BaseValue::Integer(3)),
+        ""
+    );
+}
+
+#[test]
+#[should_panic(expected = "Count overflow when adding")]
+fn test_count_overflow_panics() {
+    let _ = BaseValue::Count(u64::MAX) + BaseValue::Count(1);
+}
+
+#[test]
+#[should_panic(expected = "Integer overflow when subtracting")]
+fn test_mixed_count_overflow_panics() {
+    let _ = BaseValue::Integer(-1) - BaseValue::Count(u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "Decimal overflow when adding")]
+fn test_decimal_overflow_panics() {
+    let _ = BaseValue::Decimal(i128::MAX) + BaseValue::decimal(1, 0);
+}
+
+#[test]
+#[should_panic(expected = "Decimal overflow when subtracting")]
+fn test_decimal_negative_overflow_panics() {
+    let _ = BaseValue::Decimal(i128::MIN) - BaseValue::decimal(1, 0);
+}
+
+#[test]
+#[should_panic(expected = "Decimal overflow when scaling")]
+fn test_decimal_scaling_overflow_panics() {
+    let _ = BaseValue::decimal(i128::MAX / 10, 0);
+}
+
+#[test]
+#[should_panic(expected = "Count overflow when multiplying")]
+fn test_coefficient_count_overflow_panics() {
+    let _ = Coefficient::Integer(2) * BaseValue::Count(u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "Integer overflow when multiplying")]
+fn test_negative_coefficient_count_overflow_panics() {
+    let _ = Coefficient::Integer(-2) * BaseValue::Count(u64::MAX / 2);
+}
+
+#[test]
+#[should_panic(expected = "Decimal overflow when multiplying")]
+fn test_coefficient_decimal_overflow_panics() {
+    let _ = Coefficient::Integer(2) * BaseValue::Decimal(i128::MAX);
+}
+
+#[test]
+fn test_negative_coefficient_count_at_integer_limit() {
+    // -1 * 2^63 is exactly i64::MIN
+    assert_eq!(
+        Coefficient::Integer(-1) * BaseValue::Count(1 << 63),
+        BaseValue::Integer(i64::MIN)
+    );
+    assert_eq!(
+        Coefficient::Integer(-1).checked_mul(BaseValue::Count(1 << 63)),
+        Some(BaseValue::Integer(i64::MIN))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot add")]
+fn test_decimal_mixed_with_integer_panics() {
+    let _ = BaseValue::decimal(1, 0) + BaseValue::Integer(1);
+}
+
+#[test]
+#[should_panic(expected = "Cannot sub")]
+fn test_decimal_mixed_with_count_panics() {
+    let _ = BaseValue::Count(1) - BaseValue::decimal(1, 0);
+}
+
+#[test]
+#[should_panic(expected = "Cannot compare")]
+fn test_decimal_mixed_with_float_panics() {
+    let _ = BaseValue::decimal(1, 0) < BaseValue::Float(1.0);
+}
+
+struct Large;
+
+impl Indicator<Vec<i32>> for Large {
+    fn evaluate(&self, _solution: &Vec<i32>) -> BaseValue {
+        BaseValue::Integer(i64::MAX / 2)
+    }
+
+    fn name(&self) -> String {
+        "Large".to_string()
+    }
+}
+
+#[test]
+fn test_overflow_policies() {
+    let large_sum = || {
+        LinearCombination::new(vec![
+            (
+                Coefficient::Integer(1),
+                Box::new(Large) as Box<dyn Indicator<Vec<i32>>>,
+            ),
+            (Coefficient::Integer(2), Box::new(Large)),
+        ])
+    };
+    let linear_combination = large_sum();
+    assert_eq!(
+        linear_combination.overflow_policy(),
+        OverflowPolicy::Unchecked
+    );
+    assert_eq!(
+        linear_combination.checked_evaluate(&vec![]),
+        Err(AggregationOverflow {
+            linear_combination: "Large + 2*Large".to_string(),
+            indicator: "Large".to_string(),
+        })
+    );
+
+    let saturating = large_sum().with_overflow_policy(OverflowPolicy::SaturateToMaximum);
+    assert_eq!(saturating.evaluate(&vec![]), BaseValue::Maximum);
+
+    // no overflow, the policy does not change the value
+    let small = LinearCombination::new(vec![(
+        Coefficient::Integer(-2),
+        Box::new(Large) as Box<dyn Indicator<Vec<i32>>>,
+    )])
+    .with_overflow_policy(OverflowPolicy::SaturateToMaximum);
+    assert_eq!(
+        small.evaluate(&vec![]),
+        BaseValue::Integer(-(i64::MAX / 2) * 2)
+    );
+
+    assert_eq!(
+        BaseValue::Count(u64::MAX).checked_add(BaseValue::Integer(-1)),
+        None
+    );
+    assert_eq!(
+        BaseValue::Count(u64::MAX).checked_add(BaseValue::Integer(i64::MIN)),
+        Some(BaseValue::Integer(i64::MAX))
+    );
+    assert_eq!(
+        BaseValue::Count(u64::MAX).checked_add(BaseValue::Count(1)),
+        None
+    );
+    assert_eq!(
+        Coefficient::Float(4.0).checked_mul(BaseValue::Integer(i64::MAX / 2)),
+        None
+    );
+}
+
+#[test]
+#[should_panic(expected = "overflow in 'Cost' when adding the weighted value of 'Large'")]
+fn test_overflow_policy_panic() {
+    let objective = Objective::builder()
+        .level()
+        .indicator(Large)
+        .indicator(Large)
+        .indicator(Large)
+        .named("Cost")
+        .with_overflow_policy(OverflowPolicy::Panic)
+        .build();
+    objective.evaluate(vec![]);
+}
@@ -0,0 +1,113 @@
+//! Contains the [`Unit`] enum, which can be attached to an [`Indicator`] to declare the physical
+//! unit of its values, and the [`UnitConversion`] wrapper to explicitly convert between units.
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::{error::Error, fmt};
+
+use super::{base_value::BaseValue, indicator::Indicator};
+
+/// The unit of the values of an [`Indicator`]. E.g., seconds for a total duration or meters for
+/// a total distance.
+/// * Indicators without a unit (the default) are not checked.
+/// * A [`LinearCombination`][super::LinearCombination] must only combine indicators of the same
+///   unit. To combine indicators of different units, wrap one of them in a [`UnitConversion`]
+///   and use the coefficient as conversion factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// A time span in seconds.
+    Seconds,
+    /// A length in meters.
+    Meters,
+    /// A count of things (e.g., number of vehicles or number of violations).
+    Count,
+    /// A monetary value.
+    Currency,
+    /// A user-defined unit identified by its name.
+    Custom(&'static str),
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unit::Seconds => write!(f, "s"),
+            Unit::Meters => write!(f, "m"),
+            Unit::Count => write!(f, "count"),
+            Unit::Currency => write!(f, "currency"),
+            Unit::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Wraps an [`Indicator`] and declares that its values (multiplied by the
+/// [`Coefficient`][super::Coefficient] of the summand) are converted to the `target_unit`.
+/// * The values (and the [`bounds`][Indicator::bounds]) of the wrapped indicator are not changed,
+///   the conversion factor is the coefficient of the summand in the
+///   [`LinearCombination`][super::LinearCombination].
+/// * E.g., driving time in seconds can be converted to currency by wrapping the driving time
+///   indicator in a [`UnitConversion`] to [`Unit::Currency`] and using the costs per second as
+///   coefficient.
+pub struct UnitConversion<S> {
+    indicator: Box<dyn Indicator<S>>,
+    target_unit: Unit,
+}
+
+impl<S> UnitConversion<S> {
+    /// Creates a new [`UnitConversion`] of the given [`Indicator`] to the `target_unit`.
+    pub fn new(indicator: Box<dyn Indicator<S>>, target_unit: Unit) -> UnitConversion<S> {
+        UnitConversion {
+            indicator,
+            target_unit,
+        }
+    }
+}
+
+impl<S> Indicator<S> for UnitConversion<S> {
+    fn evaluate(&self, solution: &S) -> BaseValue {
+        self.indicator.evaluate(solution)
+    }
+
+    fn evaluate_exactly(&self, solution: &S) -> BaseValue {
+        self.indicator.evaluate_exactly(solution)
+    }
+
+    fn name(&self) -> String {
+        match self.indicator.unit() {
+            Some(unit) => format!("{}[{}->{}]", self.indicator.name(), unit, self.target_unit),
+            None => format!("{}[->{}]", self.indicator.name(), self.target_unit),
+        }
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        Some(self.target_unit)
+    }
+
+    fn bounds(&self) -> Option<(BaseValue, BaseValue)> {
+        self.indicator.bounds()
+    }
+}
+
+/// Error that is returned if a [`LinearCombination`][super::LinearCombination] mixes indicators
+/// of incompatible [`Units`][`Unit`] without explicit [`UnitConversion`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitMismatch {
+    /// The display of the linear combination in which the mismatch occurs.
+    pub linear_combination: String,
+    /// The name and the unit of each indicator of the linear combination that has a unit.
+    pub units: Vec<(String, Unit)>,
+}
+
+impl fmt::Display for UnitMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "incompatible units in '{}': {}",
+            self.linear_combination,
+            self.units
+                .iter()
+                .map(|(name, unit)| format!("{} [{}]", name, unit))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+impl Error for UnitMismatch {}
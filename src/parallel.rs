@@ -0,0 +1,277 @@
+//! This module is the entry point for the parallel iterators used by the parallel solvers and
+//! the [`ParallelNeighborhoods`][crate::heuristics::common::ParallelNeighborhood].
+//! * With the `rayon` feature, it re-exports the parallel iterator traits and the [`scope`] of
+//!   [`rayon`](https://docs.rs/rayon/).
+//! * Without the `rayon` feature, it provides sequential fallbacks with the same names and
+//!   methods: every [`Iterator`] is a [`ParallelIterator`], [`into_par_iter`][IntoParallelIterator::into_par_iter]
+//!   is [`into_iter`][IntoIterator::into_iter], [`find_any`][ParallelIterator::find_any] is
+//!   [`find`][Iterator::find] and the tasks spawned in a [`scope`] are executed immediately.
+//!   The granularity controls of an [`IndexedParallelIterator`] (e.g.,
+//!   [`with_min_len`][IndexedParallelIterator::with_min_len]) have no effect.
+//! * The parallel solvers and improvers run in rayon's global thread pool unless a dedicated
+//!   [`ThreadPool`] is set (e.g., by
+//!   [`with_num_threads`][crate::heuristics::parallel_local_search::ParallelLocalSearchSolver::with_num_threads]),
+//!   such that the library can coexist with other users of rayon and be benchmarked at a fixed
+//!   parallelism. Without the `rayon` feature, a [`ThreadPool`] runs everything on the calling
+//!   thread.
+//!
+//! Hence, a [`ParallelNeighborhood`][crate::heuristics::common::ParallelNeighborhood] written
+//! against this module (instead of against [`rayon`](https://docs.rs/rayon/) directly) compiles
+//! regardless of the `rayon` feature, and the parallel solvers run sequentially without it:
+//! ```rust
+//! use rapid_solve::heuristics::common::ParallelNeighborhood;
+//! use rapid_solve::parallel::prelude::*;
+//!
+//! struct PlusMinusOne;
+//!
+//! impl ParallelNeighborhood<i64> for PlusMinusOne {
+//!     fn neighbors_of<'a>(&'a self, solution: &'a i64) -> impl ParallelIterator<Item = i64> + 'a {
+//!         vec![solution - 1, solution + 1].into_par_iter()
+//!     }
+//! }
+//! ```
+//! Only the methods listed above are provided in addition to those of [`Iterator`]. Methods of
+//! [`rayon`](https://docs.rs/rayon/) with a different signature than their sequential
+//! counterpart (e.g., `reduce` with an identity) are not available without the `rayon` feature.
+
+#[cfg(feature = "rayon")]
+pub use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelBridge,
+    ParallelIterator,
+};
+#[cfg(feature = "rayon")]
+pub use rayon::{scope, Scope, ThreadPool};
+
+#[cfg(not(feature = "rayon"))]
+pub use sequential::{
+    scope, IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelBridge,
+    ParallelIterator, Scope, ThreadPool,
+};
+
+/// Creates a dedicated [`ThreadPool`] with `num_threads` (at least 1) threads.
+/// * Panics if the threads cannot be spawned.
+#[cfg(feature = "rayon")]
+pub fn thread_pool(num_threads: usize) -> ThreadPool {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads.max(1))
+        .build()
+        .expect("Could not create the thread pool.")
+}
+
+/// Creates a [`ThreadPool`] stand-in, which runs everything on the calling thread (without the
+/// `rayon` feature).
+#[cfg(not(feature = "rayon"))]
+pub fn thread_pool(num_threads: usize) -> ThreadPool {
+    ThreadPool::new(num_threads)
+}
+
+/// Executes `op` in the `thread_pool` if one is given and in the current pool otherwise.
+#[cfg(feature = "std")]
+pub(crate) fn install<R: Send>(
+    thread_pool: Option<&ThreadPool>,
+    op: impl FnOnce() -> R + Send,
+) -> R {
+    match thread_pool {
+        Some(thread_pool) => thread_pool.install(op),
+        None => op(),
+    }
+}
+
+/// Re-exports the parallel iterator traits, such that a single import suffices:
+/// ```rust
+/// use rapid_solve::parallel::prelude::*;
+/// ```
+pub mod prelude {
+    pub use super::{
+        IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelBridge,
+        ParallelIterator,
+    };
+}
+
+#[cfg(not(feature = "rayon"))]
+mod sequential {
+    use core::iter::{FlatMap, Take};
+    use core::marker::PhantomData;
+
+    /// Sequential stand-in for rayon's `ParallelIterator` (without the `rayon` feature). Every
+    /// [`Iterator`] that is [`Send`] is a [`ParallelIterator`].
+    pub trait ParallelIterator: Iterator + Sized + Send {
+        /// Returns some item that satisfies the `predicate` (sequentially, this is the first one).
+        fn find_any<P: Fn(&Self::Item) -> bool>(mut self, predicate: P) -> Option<Self::Item> {
+            self.find(predicate)
+        }
+
+        /// Returns the first item that satisfies the `predicate`.
+        fn find_first<P: Fn(&Self::Item) -> bool>(mut self, predicate: P) -> Option<Self::Item> {
+            self.find(predicate)
+        }
+
+        /// Takes `n` of the items (sequentially, these are the first `n`).
+        fn take_any(self, n: usize) -> Take<Self> {
+            self.take(n)
+        }
+
+        /// Maps each item to a (sequential) iterator and flattens the result.
+        fn flat_map_iter<U: IntoIterator, F: FnMut(Self::Item) -> U>(
+            self,
+            map_op: F,
+        ) -> FlatMap<Self, U, F> {
+            self.flat_map(map_op)
+        }
+    }
+
+    impl<I: Iterator + Send> ParallelIterator for I {}
+
+    /// Sequential stand-in for rayon's `IndexedParallelIterator` (without the `rayon` feature).
+    /// Every [`ExactSizeIterator`] that is [`Send`] is an [`IndexedParallelIterator`].
+    pub trait IndexedParallelIterator: ParallelIterator + ExactSizeIterator {
+        /// Returns the iterator itself (sequentially, there is no splitting into tasks).
+        fn with_min_len(self, _min: usize) -> Self {
+            self
+        }
+
+        /// Returns the iterator itself (sequentially, there is no splitting into tasks).
+        fn with_max_len(self, _max: usize) -> Self {
+            self
+        }
+    }
+
+    impl<I: ExactSizeIterator + Send> IndexedParallelIterator for I {}
+
+    /// Sequential stand-in for rayon's `IntoParallelIterator` (without the `rayon` feature).
+    /// Every [`IntoIterator`] is an [`IntoParallelIterator`].
+    pub trait IntoParallelIterator {
+        /// The type of the items.
+        type Item;
+        /// The resulting (sequential) iterator.
+        type Iter: ParallelIterator<Item = Self::Item>;
+
+        /// Converts `self` into an iterator (same as [`into_iter`][IntoIterator::into_iter]).
+        fn into_par_iter(self) -> Self::Iter;
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I
+    where
+        I::IntoIter: Send,
+    {
+        type Item = I::Item;
+        type Iter = I::IntoIter;
+
+        fn into_par_iter(self) -> I::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    /// Sequential stand-in for rayon's `IntoParallelRefIterator` (without the `rayon` feature).
+    pub trait IntoParallelRefIterator<'data> {
+        /// The type of the items.
+        type Item: 'data;
+        /// The resulting (sequential) iterator.
+        type Iter: ParallelIterator<Item = Self::Item>;
+
+        /// Iterates over references of the items (same as `iter()`).
+        fn par_iter(&'data self) -> Self::Iter;
+    }
+
+    impl<'data, I: 'data + ?Sized> IntoParallelRefIterator<'data> for I
+    where
+        &'data I: IntoParallelIterator,
+    {
+        type Item = <&'data I as IntoParallelIterator>::Item;
+        type Iter = <&'data I as IntoParallelIterator>::Iter;
+
+        fn par_iter(&'data self) -> Self::Iter {
+            self.into_par_iter()
+        }
+    }
+
+    /// Sequential stand-in for rayon's `ParallelBridge` (without the `rayon` feature).
+    pub trait ParallelBridge: Iterator + Sized + Send {
+        /// Returns the iterator itself.
+        fn par_bridge(self) -> Self {
+            self
+        }
+    }
+
+    impl<I: Iterator + Send> ParallelBridge for I {}
+
+    /// Sequential stand-in for rayon's `Scope` (without the `rayon` feature).
+    pub struct Scope<'scope> {
+        marker: PhantomData<fn(&'scope ()) -> &'scope ()>,
+    }
+
+    impl<'scope> Scope<'scope> {
+        /// Executes the `body` immediately.
+        pub fn spawn<B: FnOnce(&Scope<'scope>) + Send + 'scope>(&self, body: B) {
+            body(self)
+        }
+    }
+
+    /// Executes `op` with a [`Scope`] whose spawned tasks are executed immediately (one after
+    /// the other).
+    pub fn scope<'scope, OP: FnOnce(&Scope<'scope>) -> R + Send, R: Send>(op: OP) -> R {
+        op(&Scope {
+            marker: PhantomData,
+        })
+    }
+
+    /// Sequential stand-in for rayon's `ThreadPool` (without the `rayon` feature), which
+    /// executes everything on the calling thread.
+    #[derive(Debug)]
+    pub struct ThreadPool {
+        num_threads: usize,
+    }
+
+    impl ThreadPool {
+        pub(crate) fn new(num_threads: usize) -> ThreadPool {
+            ThreadPool {
+                num_threads: num_threads.max(1),
+            }
+        }
+
+        /// Executes `op` (on the calling thread).
+        pub fn install<OP: FnOnce() -> R + Send, R: Send>(&self, op: OP) -> R {
+            op()
+        }
+
+        /// Returns the number of threads the pool was created with (see
+        /// [`thread_pool`][super::thread_pool]), although all tasks run on the calling thread.
+        pub fn current_num_threads(&self) -> usize {
+            self.num_threads
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::prelude::*;
+    use super::scope;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_parallel_facade() {
+        let squares: Vec<i64> = vec![1, 2, 3].into_par_iter().map(|x| x * x).collect();
+        assert_eq!(squares, vec![1, 4, 9]);
+        assert!(squares
+            .par_iter()
+            .find_any(|x| **x > 3)
+            .is_some_and(|x| *x > 3));
+
+        let spawned = Mutex::new(Vec::new());
+        scope(|s| {
+            for i in 0..3 {
+                let spawned = &spawned;
+                s.spawn(move |_| spawned.lock().unwrap().push(i));
+            }
+        });
+        let mut spawned = spawned.into_inner().unwrap();
+        spawned.sort();
+        assert_eq!(spawned, vec![0, 1, 2]);
+
+        let thread_pool = super::thread_pool(2);
+        assert_eq!(thread_pool.current_num_threads(), 2);
+        assert_eq!(super::install(Some(&thread_pool), || squares.len()), 3);
+    }
+}
@@ -0,0 +1,44 @@
+//! The prelude re-exports the most commonly used types and traits, such that a single import
+//! suffices for most use cases:
+//! ```rust
+//! use rapid_solve::prelude::*;
+//! ```
+//! * The [`Solver`] trait and all solver types.
+//! * The [`Neighborhood`] traits, the [`FunctionBetweenSteps`] type, the
+//!   [`TerminationCriteria`] and the [`SearchResult`].
+//! * The [`Objective`] with its building blocks ([`ObjectiveBuilder`], [`Indicator`],
+//!   [`LinearCombination`], [`Coefficient`], [`BaseValue`]) and the results ([`ObjectiveValue`],
+//!   [`EvaluatedSolution`]).
+
+pub use crate::heuristics::common::{
+    FunctionBetweenSteps, InitialSolutionGenerator, MultiStartSolver, Neighborhood,
+    ParallelNeighborhood, PortfolioSolver, SearchResult, TabuPolicy, TerminationCriteria,
+};
+pub use crate::heuristics::great_deluge::GreatDelugeSolver;
+#[cfg(feature = "rand")]
+pub use crate::heuristics::iterated_local_search::{IteratedLocalSearchSolver, Perturbation};
+#[cfg(feature = "rand")]
+pub use crate::heuristics::large_neighborhood_search::{
+    DestroyOperator, LnsSolver, RepairOperator,
+};
+pub use crate::heuristics::local_search::LocalSearchSolver;
+#[cfg(feature = "rand")]
+pub use crate::heuristics::memetic::MemeticSolver;
+#[cfg(feature = "rand")]
+pub use crate::heuristics::nsga2::Nsga2Solver;
+pub use crate::heuristics::parallel_local_search::ParallelLocalSearchSolver;
+#[cfg(feature = "rand")]
+pub use crate::heuristics::parallel_simulated_annealing::ParallelSimulatedAnnealingSolver;
+pub use crate::heuristics::parallel_tabu_search::{
+    ParallelTabuNeighborhood, ParallelTabuSearchSolver,
+};
+pub use crate::heuristics::pareto_local_search::ParetoLocalSearchSolver;
+#[cfg(feature = "rand")]
+pub use crate::heuristics::simulated_annealing::SimulatedAnnealingSolver;
+pub use crate::heuristics::tabu_search::{TabuNeighborhood, TabuSearchSolver};
+pub use crate::heuristics::threshold_accepting::ThresholdAcceptingSolver;
+pub use crate::heuristics::Solver;
+pub use crate::objective::{
+    BaseValue, Coefficient, EvaluatedSolution, Indicator, LinearCombination, MinByObjective,
+    Objective, ObjectiveBuilder, ObjectiveValue, ParallelMinByObjective,
+};
@@ -0,0 +1,157 @@
+//! This module contains the pluggable reporting layer for the progress messages of the solvers
+//! (iterations, temperature updates, termination reasons, warnings).
+//! * All messages are sent to the global [`ProgressSink`], which is the [`StdoutSink`] by default.
+//!   Use [`set_progress_sink`] to redirect the messages (e.g., to the
+//!   [`log`](https://docs.rs/log) crate via the `LogSink` with the `log` feature).
+//! * Messages above the global [`Verbosity`] are discarded before they reach the sink. Use
+//!   [`set_verbosity`] with [`Verbosity::Silent`] to silence the solvers completely (including the
+//!   [`default_function_between_steps`][crate::heuristics::common::default_function_between_steps]).
+//!
+//! Requires the `std` feature (without it, all messages are discarded).
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// The verbosity level of a message (and the maximal level that is reported).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Nothing is reported (only meaningful for [`set_verbosity`]).
+    Silent = 0,
+    /// Something unexpected happened (e.g., a neighborhood without neighbors).
+    Warning = 1,
+    /// The progress of the solvers (iterations, temperature updates, termination reasons).
+    /// This is the default verbosity.
+    Info = 2,
+    /// Details of the neighborhood exploration (e.g., the number of swaps of a recursion).
+    Debug = 3,
+}
+
+impl Verbosity {
+    fn from_u8(value: u8) -> Verbosity {
+        match value {
+            0 => Verbosity::Silent,
+            1 => Verbosity::Warning,
+            2 => Verbosity::Info,
+            _ => Verbosity::Debug,
+        }
+    }
+}
+
+/// Receives the progress messages of the solvers.
+pub trait ProgressSink: Send + Sync {
+    /// Reports a `message` of the given `verbosity` (never [`Verbosity::Silent`]).
+    fn report(&self, verbosity: Verbosity, message: &str);
+}
+
+/// The default [`ProgressSink`]: prints the messages to stdout (warnings are prefixed by a red
+/// `warning:`).
+pub struct StdoutSink;
+
+impl ProgressSink for StdoutSink {
+    fn report(&self, verbosity: Verbosity, message: &str) {
+        match verbosity {
+            Verbosity::Warning => println!("\x1b[31mwarning:\x1b[0m {}", message),
+            _ => println!("{}", message),
+        }
+    }
+}
+
+/// A [`ProgressSink`] that forwards the messages to the [`log`](https://docs.rs/log) crate
+/// (warnings as `warn`, progress as `info` and details as `debug`, with target `rapid_solve`).
+/// Requires the `log` feature.
+#[cfg(feature = "log")]
+pub struct LogSink;
+
+#[cfg(feature = "log")]
+impl ProgressSink for LogSink {
+    fn report(&self, verbosity: Verbosity, message: &str) {
+        let level = match verbosity {
+            Verbosity::Warning => log::Level::Warn,
+            Verbosity::Info => log::Level::Info,
+            Verbosity::Silent | Verbosity::Debug => log::Level::Debug,
+        };
+        log::log!(target: "rapid_solve", level, "{}", message);
+    }
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Info as u8);
+static SINK: RwLock<Option<Arc<dyn ProgressSink>>> = RwLock::new(None);
+
+/// Sets the global [`Verbosity`]: messages with a higher verbosity are discarded.
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Returns the global [`Verbosity`].
+pub fn verbosity() -> Verbosity {
+    Verbosity::from_u8(VERBOSITY.load(Ordering::Relaxed))
+}
+
+/// Replaces the global [`ProgressSink`] (the default is the [`StdoutSink`]).
+pub fn set_progress_sink(sink: Arc<dyn ProgressSink>) {
+    *SINK.write().unwrap() = Some(sink);
+}
+
+/// Restores the default [`ProgressSink`] ([`StdoutSink`]).
+pub fn reset_progress_sink() {
+    *SINK.write().unwrap() = None;
+}
+
+/// Returns `true` if messages of the given `verbosity` are reported. Can be used to skip the
+/// formatting of expensive messages.
+pub fn is_reported(verbosity: Verbosity) -> bool {
+    verbosity != Verbosity::Silent && verbosity <= self::verbosity()
+}
+
+/// Reports a message of the given `verbosity` to the global [`ProgressSink`] (unless the
+/// verbosity is above the global [`Verbosity`]). This can also be used in a custom
+/// [`FunctionBetweenSteps`][crate::heuristics::common::FunctionBetweenSteps].
+pub fn report(verbosity: Verbosity, message: fmt::Arguments) {
+    if !is_reported(verbosity) {
+        return;
+    }
+    let message = message.to_string();
+    match SINK.read().unwrap().as_ref() {
+        Some(sink) => sink.report(verbosity, &message),
+        None => StdoutSink.report(verbosity, &message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    struct CollectingSink(Mutex<Vec<(Verbosity, String)>>);
+
+    impl ProgressSink for CollectingSink {
+        fn report(&self, verbosity: Verbosity, message: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((verbosity, message.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_progress_sink() {
+        // the verbosity and the sink are global (and other tests run concurrently), so the
+        // defaults are restored afterwards and only the own messages are checked
+        let sink = Arc::new(CollectingSink(Mutex::new(Vec::new())));
+        set_progress_sink(sink.clone());
+        set_verbosity(Verbosity::Warning);
+        report(Verbosity::Info, format_args!("iteration {}", 1));
+        report(Verbosity::Warning, format_args!("no swap possible."));
+        set_verbosity(Verbosity::Debug);
+        report(Verbosity::Debug, format_args!("{} swaps", 3));
+        set_verbosity(Verbosity::Info);
+        reset_progress_sink();
+
+        let messages = sink.0.lock().unwrap();
+        assert!(messages.contains(&(Verbosity::Warning, "no swap possible.".to_string())));
+        assert!(messages.contains(&(Verbosity::Debug, "3 swaps".to_string())));
+        assert!(!messages.contains(&(Verbosity::Info, "iteration 1".to_string())));
+    }
+}
@@ -0,0 +1,88 @@
+//! Indicators and neighborhoods on integers that are shared by the unit tests of several modules.
+#![cfg_attr(not(feature = "std"), allow(dead_code))]
+use alloc::{boxed::Box, format, string::String};
+#[cfg(feature = "std")]
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use crate::heuristics::common::Neighborhood;
+#[cfg(feature = "std")]
+use crate::heuristics::common::ParallelNeighborhood;
+#[cfg(feature = "std")]
+use crate::heuristics::tabu_search::TabuNeighborhood;
+use crate::objective::{BaseValue, Indicator};
+#[cfg(feature = "std")]
+use crate::parallel::prelude::*;
+
+/// The value of the solution itself (minimized at the smallest integer).
+pub(crate) struct Value;
+
+impl Indicator<i64> for Value {
+    fn evaluate(&self, solution: &i64) -> BaseValue {
+        BaseValue::Integer(*solution)
+    }
+
+    fn name(&self) -> String {
+        String::from("Value")
+    }
+}
+
+/// The distance of the solution to the given target (minimized at the target).
+pub(crate) struct DistanceTo(pub(crate) i64);
+
+impl Indicator<i64> for DistanceTo {
+    fn evaluate(&self, solution: &i64) -> BaseValue {
+        BaseValue::Integer((solution - self.0).abs())
+    }
+
+    fn name(&self) -> String {
+        format!("DistanceTo{}", self.0)
+    }
+}
+
+/// The neighbors `solution - 1` and `solution + 1` (in this order). As tabu neighborhood, the
+/// previous solution is tabu after each step.
+pub(crate) struct PlusMinusOne;
+
+impl Neighborhood<i64> for PlusMinusOne {
+    fn neighbors_of<'a>(
+        &'a self,
+        solution: &'a i64,
+    ) -> Box<dyn Iterator<Item = i64> + Send + Sync + 'a> {
+        Box::new([solution - 1, solution + 1].into_iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl TabuNeighborhood<i64, i64> for PlusMinusOne {
+    fn neighbors_of<'a>(
+        &'a self,
+        solution: &'a i64,
+        tabu_list: &'a VecDeque<i64>,
+    ) -> Box<dyn Iterator<Item = (i64, Vec<i64>)> + Send + Sync + 'a> {
+        Box::new(
+            [solution - 1, solution + 1]
+                .into_iter()
+                .filter(|neighbor| !tabu_list.contains(neighbor))
+                .map(|neighbor| (neighbor, vec![*solution])),
+        )
+    }
+
+    fn neighbors_with_tabu_status_of<'a>(
+        &'a self,
+        solution: &'a i64,
+        tabu_list: &'a VecDeque<i64>,
+    ) -> Box<dyn Iterator<Item = (i64, Vec<i64>, bool)> + Send + Sync + 'a> {
+        Box::new(
+            [solution - 1, solution + 1]
+                .into_iter()
+                .map(|neighbor| (neighbor, vec![*solution], tabu_list.contains(&neighbor))),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl ParallelNeighborhood<i64> for PlusMinusOne {
+    fn neighbors_of<'a>(&'a self, solution: &'a i64) -> impl ParallelIterator<Item = i64> + 'a {
+        [solution - 1, solution + 1].into_par_iter()
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+
+/// One row of a run trace (see `RunTraceWriter` in `rapid_solve::heuristics::common`).
+struct TraceRow {
+    instance: String,
+    run: u32,
+    elapsed_seconds: f64,
+    objective: Vec<f64>,
+}
+
+/// The summary of one run: the best objective value and the time at which it was first reached.
+struct RunSummary {
+    best_objective: Vec<f64>,
+    time_to_best: f64,
+}
+
+/// The statistics of one trace on one instance.
+struct InstanceStatistics {
+    runs: usize,
+    best: f64,
+    mean: f64,
+    median: f64,
+    mean_time_to_best: f64,
+}
+
+/// With this main function, you can compare two or more run traces (e.g., of different solver
+/// configurations). The first trace is the baseline for the win/loss count.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let (level, trace_paths) = match parse_arguments(&args[1..]) {
+        Some(parsed_arguments) => parsed_arguments,
+        None => {
+            print_usage(args[0].as_str());
+            std::process::exit(1);
+        }
+    };
+
+    let traces: Vec<BTreeMap<String, InstanceStatistics>> = trace_paths
+        .iter()
+        .map(|path| {
+            let rows = read_trace(path).unwrap_or_else(|error| {
+                eprintln!("Could not read trace {}: {}", path, error);
+                std::process::exit(1);
+            });
+            instance_statistics(&summarize_runs(rows), level)
+        })
+        .collect();
+
+    let mut instances: Vec<&String> = traces.iter().flat_map(|trace| trace.keys()).collect();
+    instances.sort();
+    instances.dedup();
+
+    for instance in instances.iter() {
+        println!("\nInstance {} (objective level {}):", instance, level);
+        println!(
+            "  {:<30} {:>5} {:>14} {:>14} {:>14} {:>14}",
+            "trace", "runs", "best", "mean", "median", "time-to-best"
+        );
+        for (path, trace) in trace_paths.iter().zip(traces.iter()) {
+            if let Some(statistics) = trace.get(*instance) {
+                println!(
+                    "  {:<30} {:>5} {:>14.4} {:>14.4} {:>14.4} {:>13.2}s",
+                    path,
+                    statistics.runs,
+                    statistics.best,
+                    statistics.mean,
+                    statistics.median,
+                    statistics.mean_time_to_best
+                );
+            }
+        }
+    }
+
+    println!(
+        "\nWin/loss per instance (by mean objective) against {}:",
+        trace_paths[0]
+    );
+    for (path, trace) in trace_paths.iter().zip(traces.iter()).skip(1) {
+        let (mut wins, mut losses, mut ties) = (0, 0, 0);
+        for (instance, statistics) in trace.iter() {
+            if let Some(baseline_statistics) = traces[0].get(instance) {
+                if statistics.mean < baseline_statistics.mean {
+                    wins += 1;
+                } else if statistics.mean > baseline_statistics.mean {
+                    losses += 1;
+                } else {
+                    ties += 1;
+                }
+            }
+        }
+        println!(
+            "  {:<30} {} wins, {} losses, {} ties",
+            path, wins, losses, ties
+        );
+    }
+}
+
+fn parse_arguments(args: &[String]) -> Option<(usize, Vec<String>)> {
+    let mut level = 0;
+    let mut trace_paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--level" {
+            level = args.next()?.parse().ok()?;
+        } else {
+            trace_paths.push(arg.clone());
+        }
+    }
+    if trace_paths.len() < 2 {
+        return None;
+    }
+    Some((level, trace_paths))
+}
+
+fn read_trace(path: &str) -> Result<Vec<TraceRow>, String> {
+    let content = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    if path.ends_with(".json") {
+        return read_json_trace(&content);
+    }
+    content
+        .lines()
+        .skip(1) // header
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let columns: Vec<&str> = line.split(',').collect();
+            if columns.len() != 5 {
+                return Err(format!("Invalid row: {}", line));
+            }
+            let parse_error = |_| format!("Invalid row: {}", line);
+            Ok(TraceRow {
+                instance: columns[0].to_string(),
+                run: columns[1]
+                    .parse()
+                    .map_err(|_| format!("Invalid row: {}", line))?,
+                elapsed_seconds: columns[3].parse().map_err(parse_error)?,
+                objective: columns[4]
+                    .split(';')
+                    .map(|value| value.parse().map_err(parse_error))
+                    .collect::<Result<Vec<f64>, String>>()?,
+            })
+        })
+        .collect()
+}
+
+/// Reads a JSON trace, i.e., an array of objects with the fields `instance`, `run`,
+/// `elapsed_seconds` and `objective` (an array of numbers).
+#[cfg(feature = "serde_json")]
+fn read_json_trace(content: &str) -> Result<Vec<TraceRow>, String> {
+    let json: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    json.as_array()
+        .ok_or("A JSON trace must be an array of rows.")?
+        .iter()
+        .map(|row| {
+            let invalid_row = || format!("Invalid row: {}", row);
+            Ok(TraceRow {
+                instance: row["instance"]
+                    .as_str()
+                    .ok_or_else(invalid_row)?
+                    .to_string(),
+                run: row["run"].as_u64().ok_or_else(invalid_row)? as u32,
+                elapsed_seconds: row["elapsed_seconds"].as_f64().ok_or_else(invalid_row)?,
+                objective: row["objective"]
+                    .as_array()
+                    .ok_or_else(invalid_row)?
+                    .iter()
+                    .map(|value| value.as_f64().ok_or_else(invalid_row))
+                    .collect::<Result<Vec<f64>, String>>()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "serde_json"))]
+fn read_json_trace(_content: &str) -> Result<Vec<TraceRow>, String> {
+    Err(String::from(
+        "JSON traces require the `serde_json` feature.",
+    ))
+}
+
+/// Groups the rows by instance and run and determines the best objective value (compared
+/// lexicographically) of each run.
+fn summarize_runs(rows: Vec<TraceRow>) -> BTreeMap<String, Vec<RunSummary>> {
+    let mut runs: BTreeMap<(String, u32), RunSummary> = BTreeMap::new();
+    for row in rows {
+        match runs.get_mut(&(row.instance.clone(), row.run)) {
+            Some(summary) => {
+                if lexicographically_smaller(&row.objective, &summary.best_objective) {
+                    summary.best_objective = row.objective;
+                    summary.time_to_best = row.elapsed_seconds;
+                }
+            }
+            None => {
+                runs.insert(
+                    (row.instance, row.run),
+                    RunSummary {
+                        best_objective: row.objective,
+                        time_to_best: row.elapsed_seconds,
+                    },
+                );
+            }
+        }
+    }
+    let mut runs_per_instance: BTreeMap<String, Vec<RunSummary>> = BTreeMap::new();
+    for ((instance, _), summary) in runs {
+        runs_per_instance.entry(instance).or_default().push(summary);
+    }
+    runs_per_instance
+}
+
+fn lexicographically_smaller(a: &[f64], b: &[f64]) -> bool {
+    for (x, y) in a.iter().zip(b.iter()) {
+        if x < y {
+            return true;
+        }
+        if x > y {
+            return false;
+        }
+    }
+    false
+}
+
+/// Computes the statistics of the best objective values (at the given `level`) of the runs.
+fn instance_statistics(
+    runs_per_instance: &BTreeMap<String, Vec<RunSummary>>,
+    level: usize,
+) -> BTreeMap<String, InstanceStatistics> {
+    runs_per_instance
+        .iter()
+        .map(|(instance, runs)| {
+            let mut values: Vec<f64> = runs
+                .iter()
+                .map(|run| run.best_objective.get(level).copied().unwrap_or(f64::NAN))
+                .collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+            let count = values.len();
+            let median = if count % 2 == 1 {
+                values[count / 2]
+            } else {
+                (values[count / 2 - 1] + values[count / 2]) / 2.0
+            };
+            (
+                instance.clone(),
+                InstanceStatistics {
+                    runs: count,
+                    best: values[0],
+                    mean: values.iter().sum::<f64>() / count as f64,
+                    median,
+                    mean_time_to_best: runs.iter().map(|run| run.time_to_best).sum::<f64>()
+                        / count as f64,
+                },
+            )
+        })
+        .collect()
+}
+
+fn print_usage(program_name: &str) {
+    eprintln!(
+        "Usage: {} [--level <objective level>] <trace> <trace> [<trace> ...]",
+        program_name
+    );
+    eprintln!("The traces are CSV files written by the RunTraceWriter (or JSON files with the");
+    eprintln!("same fields). The first trace is the baseline for the win/loss count.");
+}